@@ -0,0 +1,82 @@
+use {
+  bitcoin::{opcodes, script, Witness},
+  clap::Parser,
+  criterion::{BatchSize, Criterion, Throughput},
+  ord::{api::IndexHandle, subcommand::bench::process_block, Options},
+  tempfile::TempDir,
+  test_bitcoincore_rpc::TransactionTemplate,
+};
+
+const INSCRIPTIONS: usize = 100;
+
+fn inscription_witness() -> Witness {
+  let mut witness = Witness::new();
+
+  witness.push(
+    script::Builder::new()
+      .push_opcode(opcodes::OP_FALSE)
+      .push_opcode(opcodes::all::OP_IF)
+      .push_slice(b"ord")
+      .push_slice([1])
+      .push_slice(b"text/plain;charset=utf-8")
+      .push_slice([])
+      .push_slice(b"hello")
+      .push_opcode(opcodes::all::OP_ENDIF)
+      .into_script(),
+  );
+  witness.push([]);
+
+  witness
+}
+
+fn main() {
+  let mut criterion = Criterion::default().configure_from_args();
+
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  rpc_server.mine_blocks(INSCRIPTIONS as u64);
+
+  for i in 0..INSCRIPTIONS {
+    rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(i + 1, 0, 0, inscription_witness())],
+      ..Default::default()
+    });
+  }
+
+  let block = rpc_server.mine_blocks(1).remove(0);
+
+  let mut group = criterion.benchmark_group("protocol");
+
+  group.throughput(Throughput::Elements(INSCRIPTIONS as u64));
+
+  group.bench_function("parse_inscriptions", |b| b.iter(|| process_block(&block)));
+
+  group.throughput(Throughput::Elements(rpc_server.height() + 1));
+
+  group.bench_function("index_blocks", |b| {
+    b.iter_batched(
+      || TempDir::new().unwrap(),
+      |tempdir| {
+        let options = Options::try_parse_from([
+          "ord",
+          "--rpc-url",
+          &rpc_server.url(),
+          "--cookie-file",
+          rpc_server.cookie_file().to_str().unwrap(),
+          "--data-dir",
+          tempdir.path().to_str().unwrap(),
+          "--first-inscription-height",
+          "0",
+        ])
+        .unwrap();
+
+        IndexHandle::open(&options).unwrap().update().unwrap();
+      },
+      BatchSize::PerIteration,
+    );
+  });
+
+  group.finish();
+
+  Criterion::default().configure_from_args().final_summary();
+}
@@ -0,0 +1,23 @@
+#![no_main]
+
+use {
+  bitcoin::{locktime, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, Witness},
+  libfuzzer_sys::fuzz_target,
+  ord::fuzzing,
+};
+
+fuzz_target!(|input: Vec<Vec<u8>>| {
+  let tx = Transaction {
+    input: vec![TxIn {
+      previous_output: OutPoint::null(),
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::MAX,
+      witness: Witness::from_slice(&input),
+    }],
+    lock_time: locktime::absolute::LockTime::ZERO,
+    output: Vec::new(),
+    version: 2,
+  };
+
+  fuzzing::parse_envelopes(&tx);
+});
@@ -0,0 +1,7 @@
+#![no_main]
+
+use {libfuzzer_sys::fuzz_target, ord::fuzzing};
+
+fuzz_target!(|payload: Vec<Vec<u8>>| {
+  fuzzing::parse_inscription_payload(payload);
+});
@@ -0,0 +1,154 @@
+use {super::*, crate::index::remote::RemoteIndex, std::sync::mpsc};
+
+pub use crate::{index::event::Event, signature::verify_response};
+
+/// Handle to an ord index, for Rust applications that embed ord instead of
+/// running `ord server` and querying its HTTP API.
+///
+/// Opening a handle does not update the index. Call [`IndexHandle::update`]
+/// to index blocks added to the chain since the last update. The index file is
+/// locked while the handle is open, so only one process may use it at a time.
+///
+/// Handles created with [`IndexHandle::connect`] query the JSON API of a
+/// remote ord server instead, and support a subset of queries.
+pub struct IndexHandle {
+  backend: Backend,
+}
+
+enum Backend {
+  Local(Box<Index>),
+  Remote(RemoteIndex),
+}
+
+fn unsupported<T>(query: &str) -> Result<T> {
+  bail!("`{query}` is not supported by remote indexes")
+}
+
+impl IndexHandle {
+  /// Open the index configured by `options`, which can be parsed from `ord`
+  /// command line arguments with `Options::try_parse_from`.
+  pub fn open(options: &Options) -> Result<Self> {
+    Ok(Self {
+      backend: Backend::Local(Box::new(Index::open(options)?)),
+    })
+  }
+
+  /// Query the index of the ord server at `url`, which must be run with
+  /// `--enable-json-api`.
+  pub fn connect(url: &str) -> Result<Self> {
+    Ok(Self {
+      backend: Backend::Remote(RemoteIndex::new(url)?),
+    })
+  }
+
+  /// Index blocks added to the chain since the last update, sending their
+  /// events to subscribers. Remote indexes are updated by their server, so
+  /// this does nothing for them.
+  pub fn update(&self) -> Result {
+    match &self.backend {
+      Backend::Local(index) => index.update(),
+      Backend::Remote(_) => Ok(()),
+    }
+  }
+
+  /// Receive the events of every block indexed by subsequent calls to
+  /// [`IndexHandle::update`], one `Vec` per block.
+  pub fn subscribe_events(&self) -> Result<mpsc::Receiver<Vec<Event>>> {
+    match &self.backend {
+      Backend::Local(index) => Ok(index.subscribe_events()),
+      Backend::Remote(_) => unsupported("subscribe_events"),
+    }
+  }
+
+  pub fn block_count(&self) -> Result<u32> {
+    match &self.backend {
+      Backend::Local(index) => index.block_count(),
+      Backend::Remote(index) => index.block_count(),
+    }
+  }
+
+  pub fn inscription(&self, inscription_id: InscriptionId) -> Result<Option<Inscription>> {
+    match &self.backend {
+      Backend::Local(index) => index.get_inscription_by_id(inscription_id),
+      Backend::Remote(_) => unsupported("inscription"),
+    }
+  }
+
+  pub fn inscription_id(&self, inscription_number: i32) -> Result<Option<InscriptionId>> {
+    match &self.backend {
+      Backend::Local(index) => index.get_inscription_id_by_inscription_number(inscription_number),
+      Backend::Remote(index) => Ok(
+        index
+          .inscription(inscription_number)?
+          .map(|inscription| inscription.inscription_id),
+      ),
+    }
+  }
+
+  pub fn inscription_location(&self, inscription_id: InscriptionId) -> Result<Option<SatPoint>> {
+    match &self.backend {
+      Backend::Local(index) => index.get_inscription_satpoint_by_id(inscription_id),
+      Backend::Remote(index) => Ok(
+        index
+          .inscription(inscription_id)?
+          .map(|inscription| inscription.satpoint),
+      ),
+    }
+  }
+
+  pub fn inscriptions_on_output(&self, outpoint: OutPoint) -> Result<Vec<InscriptionId>> {
+    match &self.backend {
+      Backend::Local(index) => index.get_inscriptions_on_output(outpoint),
+      Backend::Remote(index) => Ok(
+        index
+          .output(outpoint)?
+          .map(|output| output.inscriptions)
+          .unwrap_or_default(),
+      ),
+    }
+  }
+
+  /// Current location of `sat`. Requires an index built with `--index-sats`.
+  pub fn sat_location(&self, sat: Sat) -> Result<Option<SatPoint>> {
+    match &self.backend {
+      Backend::Local(index) => {
+        ensure!(
+          index.has_sat_index(),
+          "sat locations require index created with `--index-sats` flag"
+        );
+
+        index.find(sat)
+      }
+      Backend::Remote(index) => Ok(index.sat(sat)?.and_then(|sat| sat.satpoint)),
+    }
+  }
+
+  /// ID of `rune`. Requires an index built with `--index-runes`.
+  pub fn rune_id(&self, rune: Rune) -> Result<Option<RuneId>> {
+    let Backend::Local(index) = &self.backend else {
+      return unsupported("rune_id");
+    };
+
+    ensure!(
+      index.has_rune_index(),
+      "runes require index created with `--index-runes` flag"
+    );
+
+    Ok(index.rune(rune)?.map(|(id, _)| id))
+  }
+
+  /// Balance of rune `id` held by `outpoint`. Requires an index built with
+  /// `--index-runes`.
+  pub fn rune_balance(&self, outpoint: OutPoint, id: RuneId) -> Result<u128> {
+    let Backend::Local(index) = &self.backend else {
+      return unsupported("rune_balance");
+    };
+
+    ensure!(
+      index.has_rune_index(),
+      "runes require index created with `--index-runes` flag"
+    );
+
+    index.get_rune_balance(outpoint, id)
+  }
+}
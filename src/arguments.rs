@@ -1,6 +1,9 @@
 use {
   super::*,
-  clap::builder::styling::{AnsiColor, Effects, Styles},
+  clap::{
+    builder::styling::{AnsiColor, Effects, Styles},
+    CommandFactory, FromArgMatches,
+  },
 };
 
 #[derive(Debug, Parser)]
@@ -20,7 +23,76 @@ pub(crate) struct Arguments {
 }
 
 impl Arguments {
+  /// Parse the process's arguments, with settings from the config file and
+  /// `ORD_*` environment variables as option defaults.
+  pub(crate) fn load() -> Result<Self> {
+    let args = env::args_os().collect::<Vec<OsString>>();
+
+    let (config, config_dir) = Self::config_args(&args);
+
+    let config = Config::load(Config::path(config.as_deref(), config_dir.as_deref()).as_deref())?;
+
+    let command = Self::command();
+
+    let settings = config.settings(&command, &Config::env())?;
+
+    let matches = Config::apply(command, &settings).get_matches_from(args);
+
+    Ok(Self::from_arg_matches(&matches).unwrap_or_else(|err| err.exit()))
+  }
+
+  /// Values of `--config` and `--config-dir` in `args`, which are needed
+  /// before the arguments can be parsed.
+  fn config_args(args: &[OsString]) -> (Option<PathBuf>, Option<PathBuf>) {
+    let mut config = None;
+    let mut config_dir = None;
+
+    let mut args = args.iter().skip(1).filter_map(|arg| arg.to_str());
+
+    while let Some(arg) = args.next() {
+      if arg == "--" {
+        break;
+      } else if arg == "--config" {
+        config = args.next().map(PathBuf::from);
+      } else if let Some(path) = arg.strip_prefix("--config=") {
+        config = Some(path.into());
+      } else if arg == "--config-dir" {
+        config_dir = args.next().map(PathBuf::from);
+      } else if let Some(path) = arg.strip_prefix("--config-dir=") {
+        config_dir = Some(path.into());
+      }
+    }
+
+    (config, config_dir)
+  }
+
   pub(crate) fn run(self) -> SubcommandResult {
     self.subcommand.run(self.options)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn config_args() {
+    let args = |args: &[&str]| {
+      Arguments::config_args(&args.iter().map(OsString::from).collect::<Vec<OsString>>())
+    };
+
+    assert_eq!(args(&["ord", "server"]), (None, None));
+
+    assert_eq!(
+      args(&["ord", "--config", "foo.yaml", "server"]),
+      (Some("foo.yaml".into()), None)
+    );
+
+    assert_eq!(
+      args(&["ord", "--config-dir=foo", "server"]),
+      (None, Some("foo".into()))
+    );
+
+    assert_eq!(args(&["ord", "--", "--config", "foo.yaml"]), (None, None));
+  }
+}
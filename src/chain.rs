@@ -8,6 +8,7 @@ pub enum Chain {
   Mainnet,
   #[value(alias("test"))]
   Testnet,
+  Testnet4,
   Signet,
   Regtest,
 }
@@ -16,7 +17,7 @@ impl Chain {
   pub(crate) fn network(self) -> Network {
     match self {
       Self::Mainnet => Network::Bitcoin,
-      Self::Testnet => Network::Testnet,
+      Self::Testnet | Self::Testnet4 => Network::Testnet,
       Self::Signet => Network::Signet,
       Self::Regtest => Network::Regtest,
     }
@@ -28,13 +29,14 @@ impl Chain {
       Self::Regtest => 18443,
       Self::Signet => 38332,
       Self::Testnet => 18332,
+      Self::Testnet4 => 48332,
     }
   }
 
   pub(crate) fn inscription_content_size_limit(self) -> Option<usize> {
     match self {
       Self::Mainnet | Self::Regtest => None,
-      Self::Testnet | Self::Signet => Some(1024),
+      Self::Testnet | Self::Testnet4 | Self::Signet => Some(1024),
     }
   }
 
@@ -44,6 +46,7 @@ impl Chain {
       Self::Regtest => 0,
       Self::Signet => 112402,
       Self::Testnet => 2413343,
+      Self::Testnet4 => 0,
     }
   }
 
@@ -54,6 +57,7 @@ impl Chain {
         Self::Regtest => 0,
         Self::Signet => 0,
         Self::Testnet => 12,
+        Self::Testnet4 => 0,
       }
   }
 
@@ -63,11 +67,26 @@ impl Chain {
       Self::Regtest => 110,
       Self::Signet => 175392,
       Self::Testnet => 2544192,
+      Self::Testnet4 => 0,
     }
   }
 
-  pub(crate) fn genesis_block(self) -> Block {
-    bitcoin::blockdata::constants::genesis_block(self.network())
+  /// Genesis block, or `None` for testnet4, which the bitcoin library
+  /// doesn't know, and whose genesis block must be fetched from Bitcoin Core.
+  pub(crate) fn genesis_block(self) -> Option<Block> {
+    match self {
+      Self::Testnet4 => None,
+      _ => Some(bitcoin::blockdata::constants::genesis_block(self.network())),
+    }
+  }
+
+  pub(crate) fn genesis_block_hash(self) -> BlockHash {
+    match self {
+      Self::Testnet4 => "00000000da84f2bafbbc53dee25a72ae507ff4914b867c565be350b0da8bf043"
+        .parse()
+        .unwrap(),
+      _ => bitcoin::blockdata::constants::genesis_block(self.network()).block_hash(),
+    }
   }
 
   pub(crate) fn address_from_script(
@@ -81,6 +100,7 @@ impl Chain {
     match self {
       Self::Mainnet => data_dir.to_owned(),
       Self::Testnet => data_dir.join("testnet3"),
+      Self::Testnet4 => data_dir.join("testnet4"),
       Self::Signet => data_dir.join("signet"),
       Self::Regtest => data_dir.join("regtest"),
     }
@@ -97,7 +117,42 @@ impl Display for Chain {
         Self::Regtest => "regtest",
         Self::Signet => "signet",
         Self::Testnet => "testnet",
+        Self::Testnet4 => "testnet4",
       }
     )
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn genesis_block_hashes() {
+    for chain in [
+      Chain::Mainnet,
+      Chain::Testnet,
+      Chain::Signet,
+      Chain::Regtest,
+    ] {
+      assert_eq!(
+        chain.genesis_block().unwrap().block_hash(),
+        chain.genesis_block_hash()
+      );
+    }
+
+    assert_eq!(Chain::Testnet4.genesis_block(), None);
+  }
+
+  #[test]
+  fn testnet4() {
+    assert_eq!(
+      <Chain as ValueEnum>::from_str("testnet4", false).unwrap(),
+      Chain::Testnet4
+    );
+    assert_eq!(Chain::Testnet4.to_string(), "testnet4");
+    assert_eq!(Chain::Testnet4.network(), Network::Testnet);
+    assert_eq!(Chain::Testnet4.default_rpc_port(), 48332);
+    assert_eq!(Chain::Testnet4.first_inscription_height(), 0);
+  }
+}
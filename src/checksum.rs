@@ -0,0 +1,135 @@
+use {
+  super::*,
+  bech32::{ToBase32, Variant},
+};
+
+/// Human-readable part the checksum is computed with, which isn't included in
+/// checksummed strings, but keeps their checksums distinct from those of
+/// bech32m addresses with the same data.
+const HRP: &str = "ord";
+
+const LEN: usize = 6;
+
+const SEPARATOR: char = '-';
+
+/// Characters that appear in bech32m checksums.
+const CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Checksum of `s`: the last six characters of the bech32m encoding of its
+/// lowercased bytes, which catches all but a vanishingly small fraction of
+/// typos, including the transposed and mistyped characters common when
+/// copying by hand.
+fn checksum(s: &str) -> String {
+  let encoded = bech32::encode(
+    HRP,
+    s.to_ascii_lowercase().as_bytes().to_base32(),
+    Variant::Bech32m,
+  )
+  .unwrap();
+  encoded[encoded.len() - LEN..].into()
+}
+
+/// `s` followed by its checksum, as `<S>-<CHECKSUM>`.
+pub(crate) fn append(s: &str) -> String {
+  format!("{s}{SEPARATOR}{}", checksum(s))
+}
+
+/// Error returned when the checksum of a checksummed string doesn't match,
+/// almost always because of a typo, either in the string or its checksum. The
+/// correct checksum isn't reported, so that a typo in the string can't be
+/// papered over by copying it.
+#[derive(Debug, PartialEq)]
+pub struct ChecksumMismatch {
+  pub found: String,
+}
+
+impl Display for ChecksumMismatch {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "checksum `{}` does not match: check for typos",
+      self.found,
+    )
+  }
+}
+
+impl std::error::Error for ChecksumMismatch {}
+
+/// Split an optional checksum off of `s`, returning the rest of `s` if the
+/// checksum matches or `s` has none. Strings only have checksums if they end
+/// with a separator followed by six bech32 characters, all lowercase or all
+/// uppercase, so that separators and suffixes which are part of `s` itself
+/// are left alone.
+pub(crate) fn strip(s: &str) -> Result<&str, ChecksumMismatch> {
+  let Some((payload, found)) = s.rsplit_once(SEPARATOR) else {
+    return Ok(s);
+  };
+
+  if payload.is_empty() || !is_checksum(found) {
+    return Ok(s);
+  }
+
+  if found.to_ascii_lowercase() != checksum(payload) {
+    return Err(ChecksumMismatch {
+      found: found.into(),
+    });
+  }
+
+  Ok(payload)
+}
+
+fn is_checksum(s: &str) -> bool {
+  s.len() == LEN
+    && (s == s.to_ascii_lowercase() || s == s.to_ascii_uppercase())
+    && s.chars().all(|c| CHARSET.contains(c.to_ascii_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    let checksummed = append("foo");
+    assert_eq!(checksummed.len(), "foo".len() + 1 + LEN);
+    assert_eq!(strip(&checksummed).unwrap(), "foo");
+    assert_eq!(strip(&checksummed.to_uppercase()).unwrap(), "FOO");
+    assert_eq!(strip("foo").unwrap(), "foo");
+    assert_eq!(strip("-5").unwrap(), "-5");
+  }
+
+  #[test]
+  fn suffixes_which_are_not_checksums_are_left_alone() {
+    assert_eq!(strip("foo-barbaz").unwrap(), "foo-barbaz");
+    assert_eq!(strip("foo-123456").unwrap(), "foo-123456");
+    assert_eq!(strip("foo-qpzRy9").unwrap(), "foo-qpzRy9");
+    assert_eq!(strip("foo-qpzry").unwrap(), "foo-qpzry");
+    assert_eq!(strip("foo-qpzry9x").unwrap(), "foo-qpzry9x");
+    assert!(strip("foo-qpzry9").is_err());
+    assert!(strip("foo-QPZRY9").is_err());
+  }
+
+  #[test]
+  fn typos_are_caught() {
+    let checksummed = append("nvtdijuwxlp");
+
+    assert_eq!(
+      strip(&checksummed.replacen("nvt", "nvu", 1)).unwrap_err(),
+      ChecksumMismatch {
+        found: checksummed[checksummed.len() - LEN..].into(),
+      }
+    );
+
+    assert_eq!(
+      strip(&checksummed.replacen("nvt", "nvu", 1))
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "checksum `{}` does not match: check for typos",
+        &checksummed[checksummed.len() - LEN..],
+      ),
+    );
+
+    assert!(strip(&checksummed.replacen("dij", "dji", 1)).is_err());
+  }
+}
@@ -1,4 +1,7 @@
-use super::*;
+use {
+  super::*,
+  clap::{Arg, Command},
+};
 
 #[derive(Deserialize, Default, PartialEq, Debug)]
 #[serde(deny_unknown_fields)]
@@ -6,17 +9,264 @@ pub(crate) struct Config {
   pub(crate) hidden: HashSet<InscriptionId>,
   pub(crate) bitcoin_rpc_pass: Option<String>,
   pub(crate) bitcoin_rpc_user: Option<String>,
+  #[serde(default)]
+  pub(crate) options: BTreeMap<String, serde_yaml::Value>,
+  #[serde(default)]
+  pub(crate) server: BTreeMap<String, serde_yaml::Value>,
+  #[serde(default)]
+  pub(crate) wallet: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Config file sections holding option defaults, and the command whose
+/// options each section sets.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Section {
+  Options,
+  Server,
+  Wallet,
+}
+
+impl Section {
+  const ALL: [Self; 3] = [Self::Options, Self::Server, Self::Wallet];
+
+  fn subcommand(self) -> Option<&'static str> {
+    match self {
+      Self::Options => None,
+      Self::Server => Some("server"),
+      Self::Wallet => Some("wallet"),
+    }
+  }
+
+  fn env_prefix(self) -> &'static str {
+    match self {
+      Self::Options => "ORD_",
+      Self::Server => "ORD_SERVER_",
+      Self::Wallet => "ORD_WALLET_",
+    }
+  }
+
+  /// Arguments that a command-line invocation of this section's command
+  /// would need for its options to be parsed.
+  fn args(self) -> &'static [&'static str] {
+    match self {
+      Self::Options => &["ord", "epochs"],
+      Self::Server => &["ord", "server"],
+      Self::Wallet => &["ord", "wallet", "balance"],
+    }
+  }
+}
+
+impl Display for Section {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Options => "options",
+        Self::Server => "server",
+        Self::Wallet => "wallet",
+      }
+    )
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+  Config,
+  Env,
+}
+
+/// An option default taken from the config file or the environment.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Setting {
+  pub section: Section,
+  pub option: String,
+  pub source: Source,
+  pub values: Vec<String>,
 }
 
 impl Config {
+  /// Path of the config file passed with `--config`, or of `ord.yaml` in the
+  /// directory passed with `--config-dir`, if it exists.
+  pub(crate) fn path(config: Option<&Path>, config_dir: Option<&Path>) -> Option<PathBuf> {
+    match config {
+      Some(path) => Some(path.into()),
+      None => config_dir
+        .map(|dir| dir.join("ord.yaml"))
+        .filter(|path| path.exists()),
+    }
+  }
+
+  pub(crate) fn load(path: Option<&Path>) -> Result<Self> {
+    match path {
+      Some(path) => Ok(serde_yaml::from_reader(File::open(path)?)?),
+      None => Ok(Default::default()),
+    }
+  }
+
   pub(crate) fn is_hidden(&self, inscription_id: InscriptionId) -> bool {
     self.hidden.contains(&inscription_id)
   }
-}
 
+  /// Environment variables with unicode keys and values.
+  pub(crate) fn env() -> BTreeMap<String, String> {
+    env::vars_os()
+      .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+      .collect()
+  }
+
+  fn section(&self, section: Section) -> &BTreeMap<String, serde_yaml::Value> {
+    match section {
+      Section::Options => &self.options,
+      Section::Server => &self.server,
+      Section::Wallet => &self.wallet,
+    }
+  }
+
+  fn configurable(arg: &Arg) -> bool {
+    !matches!(
+      arg.get_long(),
+      None | Some("config" | "config-dir" | "help" | "version")
+    )
+  }
+
+  fn values(section: Section, option: &str, value: &serde_yaml::Value) -> Result<Vec<String>> {
+    let scalar = |value: &serde_yaml::Value| {
+      match value {
+      serde_yaml::Value::Bool(bool) => Ok(bool.to_string()),
+      serde_yaml::Value::Number(number) => Ok(number.to_string()),
+      serde_yaml::Value::String(string) => Ok(string.clone()),
+      _ => Err(anyhow!(
+        "option `{option}` in `{section}` section of config must be a string, number, boolean, or list of those"
+      )),
+    }
+    };
+
+    match value {
+      serde_yaml::Value::Sequence(sequence) => sequence.iter().map(scalar).collect(),
+      value => Ok(vec![scalar(value)?]),
+    }
+  }
+
+  /// Option defaults set by this config's `options`, `server`, and `wallet`
+  /// sections, overridden by `ORD_<OPTION>`, `ORD_SERVER_<OPTION>`, and
+  /// `ORD_WALLET_<OPTION>` environment variables in `env`. Options are named
+  /// by their long flag, without the leading `--`.
+  pub(crate) fn settings(
+    &self,
+    command: &Command,
+    env: &BTreeMap<String, String>,
+  ) -> Result<Vec<Setting>> {
+    let mut settings = Vec::new();
+
+    for section in Section::ALL {
+      let command = match section.subcommand() {
+        Some(name) => command
+          .find_subcommand(name)
+          .ok_or_else(|| anyhow!("unknown subcommand `{name}`"))?,
+        None => command,
+      };
+
+      let options = self.section(section);
+
+      for option in options.keys() {
+        ensure!(
+          command
+            .get_arguments()
+            .filter(|arg| Self::configurable(arg))
+            .any(|arg| arg.get_long() == Some(option.as_str())),
+          "unknown option `{option}` in `{section}` section of config"
+        );
+      }
+
+      for arg in command
+        .get_arguments()
+        .filter(|arg| Self::configurable(arg))
+      {
+        let Some(option) = arg.get_long() else {
+          continue;
+        };
+
+        let env_key = format!(
+          "{}{}",
+          section.env_prefix(),
+          option.to_uppercase().replace('-', "_")
+        );
+
+        let (source, values) = if let Some(value) = env.get(&env_key) {
+          (Source::Env, vec![value.clone()])
+        } else if let Some(value) = options.get(option) {
+          (Source::Config, Self::values(section, option, value)?)
+        } else {
+          continue;
+        };
+
+        settings.push(Setting {
+          section,
+          option: option.into(),
+          source,
+          values,
+        });
+      }
+    }
+
+    Ok(settings)
+  }
+
+  /// Make `settings` the defaults of their options in `command`, so that
+  /// options passed on the command line take precedence.
+  pub(crate) fn apply(command: Command, settings: &[Setting]) -> Command {
+    fn set_default(command: Command, setting: &Setting) -> Command {
+      let Some(id) = command
+        .get_arguments()
+        .find(|arg| arg.get_long() == Some(setting.option.as_str()))
+        .map(|arg| arg.get_id().as_str().to_owned())
+      else {
+        return command;
+      };
+
+      command.mut_arg(id, |arg| {
+        arg.default_values(setting.values.clone()).required(false)
+      })
+    }
+
+    settings.iter().fold(command, |command, setting| {
+      match setting.section.subcommand() {
+        Some(name) => command.mut_subcommand(name, |subcommand| set_default(subcommand, setting)),
+        None => set_default(command, setting),
+      }
+    })
+  }
+
+  /// Check that every setting parses as a value of its option.
+  pub(crate) fn validate(command: &Command, settings: &[Setting]) -> Result {
+    let command = Self::apply(command.clone(), settings);
+
+    for section in Section::ALL {
+      if let Err(err) = command.clone().try_get_matches_from(section.args()) {
+        let message = err.to_string();
+        bail!(
+          "invalid setting in `{section}` section: {}",
+          message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches("error: ")
+        );
+      }
+    }
+
+    Ok(())
+  }
+}
 #[cfg(test)]
 mod tests {
-  use super::*;
+  use {
+    super::*,
+    clap::{CommandFactory, FromArgMatches},
+  };
 
   #[test]
   fn inscriptions_can_be_hidden() {
@@ -39,6 +289,150 @@ mod tests {
 
   #[test]
   fn example_config_file_is_valid() {
-    let _: Config = serde_yaml::from_reader(File::open("ord.yaml").unwrap()).unwrap();
+    let config: Config = serde_yaml::from_reader(File::open("ord.yaml").unwrap()).unwrap();
+
+    let command = Arguments::command();
+
+    let settings = config.settings(&command, &BTreeMap::new()).unwrap();
+
+    Config::validate(&command, &settings).unwrap();
+  }
+
+  fn config(yaml: &str) -> Config {
+    serde_yaml::from_str(yaml).unwrap()
+  }
+
+  #[test]
+  fn environment_variables_override_config_settings() {
+    let config = config(
+      "
+hidden:
+options:
+  index-sats: true
+  chain: signet
+server:
+  http-port: 8080
+  acme-domain:
+  - foo.com
+  - bar.com
+",
+    );
+
+    let env = [
+      ("ORD_CHAIN".to_string(), "regtest".to_string()),
+      ("ORD_WALLET_NAME".to_string(), "foo".to_string()),
+      ("ORD_INTEGRATION_TEST".to_string(), "1".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    let settings = config.settings(&Arguments::command(), &env).unwrap();
+
+    let setting = |section, option: &str| {
+      settings
+        .iter()
+        .find(|setting| setting.section == section && setting.option == option)
+        .map(|setting| (setting.source, setting.values.clone()))
+    };
+
+    assert_eq!(
+      setting(Section::Options, "chain"),
+      Some((Source::Env, vec!["regtest".into()]))
+    );
+    assert_eq!(
+      setting(Section::Options, "index-sats"),
+      Some((Source::Config, vec!["true".into()]))
+    );
+    assert_eq!(
+      setting(Section::Server, "http-port"),
+      Some((Source::Config, vec!["8080".into()]))
+    );
+    assert_eq!(
+      setting(Section::Server, "acme-domain"),
+      Some((Source::Config, vec!["foo.com".into(), "bar.com".into()]))
+    );
+    assert_eq!(
+      setting(Section::Wallet, "name"),
+      Some((Source::Env, vec!["foo".into()]))
+    );
+    assert_eq!(settings.len(), 5);
+  }
+
+  #[test]
+  fn settings_are_option_defaults() {
+    let config = config(
+      "
+hidden:
+options:
+  index-sats: true
+  chain: signet
+wallet:
+  name: foo
+",
+    );
+
+    let command = Arguments::command();
+
+    let settings = config.settings(&command, &BTreeMap::new()).unwrap();
+
+    let arguments = Arguments::from_arg_matches(
+      &Config::apply(command.clone(), &settings)
+        .try_get_matches_from(["ord", "wallet", "balance"])
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert!(arguments.options.index_sats);
+    assert_eq!(arguments.options.chain(), Chain::Signet);
+
+    let Subcommand::Wallet(wallet) = arguments.subcommand else {
+      panic!("unexpected subcommand");
+    };
+
+    assert_eq!(wallet.name, "foo");
+
+    let arguments = Arguments::from_arg_matches(
+      &Config::apply(command, &settings)
+        .try_get_matches_from([
+          "ord", "--chain", "regtest", "wallet", "--name", "bar", "balance",
+        ])
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(arguments.options.chain(), Chain::Regtest);
+
+    let Subcommand::Wallet(wallet) = arguments.subcommand else {
+      panic!("unexpected subcommand");
+    };
+
+    assert_eq!(wallet.name, "bar");
+  }
+
+  #[test]
+  fn unknown_options_are_rejected() {
+    assert_eq!(
+      config("hidden:\nserver:\n  foo: bar")
+        .settings(&Arguments::command(), &BTreeMap::new())
+        .unwrap_err()
+        .to_string(),
+      "unknown option `foo` in `server` section of config"
+    );
+  }
+
+  #[test]
+  fn invalid_values_are_rejected() {
+    let command = Arguments::command();
+
+    let settings = config("hidden:\nserver:\n  http-port: foo")
+      .settings(&command, &BTreeMap::new())
+      .unwrap();
+
+    assert_regex_match!(
+      Config::validate(&command, &settings)
+        .unwrap_err()
+        .to_string(),
+      "invalid setting in `server` section: invalid value 'foo' for '--http-port <HTTP_PORT>'.*"
+    );
   }
 }
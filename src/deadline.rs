@@ -0,0 +1,64 @@
+use super::*;
+
+/// Time by which a query must finish, checked between the rows of long index
+/// scans, so that expensive API requests are abandoned instead of tying up
+/// server workers indefinitely.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Deadline(Option<Instant>);
+
+/// Error returned by scans which pass their deadline. `results` is how many
+/// results had been collected when the scan was abandoned.
+#[derive(Debug, PartialEq)]
+pub(crate) struct DeadlineExceeded {
+  pub(crate) results: usize,
+}
+
+impl Display for DeadlineExceeded {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "query deadline exceeded after collecting {} results",
+      self.results
+    )
+  }
+}
+
+impl std::error::Error for DeadlineExceeded {}
+
+impl Deadline {
+  /// Deadline `timeout` from now, or no deadline if `timeout` is `None`.
+  pub(crate) fn after(timeout: Option<Duration>) -> Self {
+    Self(timeout.map(|timeout| Instant::now() + timeout))
+  }
+
+  /// Fail with `DeadlineExceeded` if the deadline has passed, with `results`
+  /// collected so far.
+  pub(crate) fn check(self, results: usize) -> Result {
+    match self.0 {
+      Some(deadline) if Instant::now() >= deadline => Err(DeadlineExceeded { results }.into()),
+      _ => Ok(()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn check() {
+    assert!(Deadline::default().check(0).is_ok());
+    assert!(Deadline::after(Some(Duration::from_secs(60)))
+      .check(0)
+      .is_ok());
+
+    assert_eq!(
+      Deadline::after(Some(Duration::ZERO))
+        .check(5)
+        .unwrap_err()
+        .downcast::<DeadlineExceeded>()
+        .unwrap(),
+      DeadlineExceeded { results: 5 },
+    );
+  }
+}
@@ -39,6 +39,8 @@ impl FromStr for Decimal {
         integer.parse::<u128>()?
       };
 
+      let decimal = decimal.trim_end_matches('0');
+
       let decimal = if decimal.is_empty() {
         0
       } else {
@@ -99,6 +101,8 @@ mod tests {
     case("1.11", 111, 2);
     case("1.", 1, 0);
     case(".1", 1, 1);
+    case("1.10", 11, 1);
+    case("1.010", 101, 2);
   }
 
   #[test]
@@ -0,0 +1,120 @@
+use {
+  super::*,
+  miniscript::{descriptor::DescriptorPublicKey, Descriptor},
+};
+
+#[derive(Debug, Clone)]
+pub(crate) enum Destination {
+  Address(Address<NetworkUnchecked>),
+  Descriptor(Box<Descriptor<DescriptorPublicKey>>),
+}
+
+impl Destination {
+  /// Resolve the destination to an address on `network`. Descriptors must not
+  /// contain wildcards, and must have an address form, so bare scripts are
+  /// rejected.
+  pub(crate) fn address(&self, network: Network) -> Result<Address> {
+    match self {
+      Self::Address(address) => Ok(address.clone().require_network(network)?),
+      Self::Descriptor(descriptor) => {
+        ensure!(
+          !descriptor.has_wildcard(),
+          "descriptor `{descriptor}` may not contain wildcards"
+        );
+
+        descriptor
+          .at_derivation_index(0)?
+          .address(network)
+          .with_context(|| format!("descriptor `{descriptor}` does not have an address"))
+      }
+    }
+  }
+}
+
+impl FromStr for Destination {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Ok(if s.contains('(') {
+      Self::Descriptor(Box::new(s.parse()?))
+    } else {
+      Self::Address(s.parse()?)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, bitcoin::AddressType};
+
+  const KEY_A: &str = "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+  const KEY_B: &str = "03774ae7f858a9411e5ef4246b70c65aac5649980be5c17891bbec17895da008cb";
+
+  #[test]
+  fn address() {
+    assert_eq!(
+      "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        .parse::<Destination>()
+        .unwrap()
+        .address(Network::Bitcoin)
+        .unwrap(),
+      "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        .parse::<Address<NetworkUnchecked>>()
+        .unwrap()
+        .assume_checked(),
+    );
+  }
+
+  #[test]
+  fn address_on_wrong_network_is_an_error() {
+    assert!("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+      .parse::<Destination>()
+      .unwrap()
+      .address(Network::Regtest)
+      .is_err());
+  }
+
+  #[test]
+  fn multisig_descriptor() {
+    assert_eq!(
+      format!("wsh(multi(2,{KEY_A},{KEY_B}))")
+        .parse::<Destination>()
+        .unwrap()
+        .address(Network::Bitcoin)
+        .unwrap()
+        .address_type(),
+      Some(AddressType::P2wsh),
+    );
+  }
+
+  #[test]
+  fn timelocked_descriptor() {
+    assert_eq!(
+      format!("wsh(and_v(v:pk({KEY_A}),older(144)))")
+        .parse::<Destination>()
+        .unwrap()
+        .address(Network::Regtest)
+        .unwrap()
+        .address_type(),
+      Some(AddressType::P2wsh),
+    );
+  }
+
+  #[test]
+  fn bare_descriptor_is_an_error() {
+    assert!(format!("pk({KEY_A})")
+      .parse::<Destination>()
+      .unwrap()
+      .address(Network::Bitcoin)
+      .is_err());
+  }
+
+  #[test]
+  fn wildcard_descriptor_is_an_error() {
+    assert!("tr(xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/0/*)"
+      .parse::<Destination>()
+      .unwrap()
+      .address(Network::Bitcoin)
+      .is_err());
+  }
+}
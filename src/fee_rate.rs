@@ -22,7 +22,114 @@ impl TryFrom<f64> for FeeRate {
   }
 }
 
+/// Most virtual bytes of transactions that fit in a block.
+const BLOCK_VSIZE: u64 = 1_000_000;
+
+/// Lowest fee rate, in sats/vB, relayed by Bitcoin Core in its default
+/// configuration.
+const MIN_RELAY_FEE_RATE: f64 = 1.0;
+
+/// Fee rate passed on the command line, either a rate in sats/vB or `auto`,
+/// to estimate one with [`FeeRate::estimate`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum FeeRateArg {
+  Auto,
+  Rate(FeeRate),
+}
+
+impl FromStr for FeeRateArg {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if s == "auto" {
+      Ok(Self::Auto)
+    } else {
+      Ok(Self::Rate(s.parse()?))
+    }
+  }
+}
+
+impl FeeRateArg {
+  /// Fee rate to use, estimated for confirmation within `target` blocks if
+  /// `auto`.
+  pub(crate) fn resolve(self, client: &Client, target: u16) -> Result<FeeRate> {
+    match self {
+      Self::Auto => FeeRate::estimate(client, target),
+      Self::Rate(fee_rate) => Ok(fee_rate),
+    }
+  }
+}
+
+#[derive(Deserialize)]
+struct MempoolEntry {
+  fees: MempoolFees,
+  vsize: u64,
+}
+
+#[derive(Deserialize)]
+struct MempoolFees {
+  #[serde(with = "bitcoin::amount::serde::as_btc")]
+  modified: Amount,
+}
+
 impl FeeRate {
+  /// Estimate the fee rate needed to confirm within `target` blocks. This is
+  /// the highest of Bitcoin Core's `estimatesmartfee` estimate, the rate
+  /// needed to place among the first `target` blocks' worth of the mempool,
+  /// and the minimum relay fee rate. `estimatesmartfee` has no estimate on a
+  /// node which has only just started, or on regtest, and if the mempool
+  /// would clear within `target` blocks, it places no bound on the rate, so
+  /// either may be skipped.
+  pub(crate) fn estimate(client: &Client, target: u16) -> Result<Self> {
+    ensure!(target > 0, "confirmation target must be greater than zero");
+
+    #[allow(clippy::cast_precision_loss)]
+    let estimate = client
+      .estimate_smart_fee(target, None)?
+      .fee_rate
+      .map(|per_kvb| per_kvb.to_sat() as f64 / 1000.0);
+
+    let mempool = client
+      .call::<HashMap<Txid, MempoolEntry>>("getrawmempool", &[true.into()])?
+      .into_values()
+      .filter(|entry| entry.vsize > 0)
+      .map(|entry| {
+        #[allow(clippy::cast_precision_loss)]
+        let rate = entry.fees.modified.to_sat() as f64 / entry.vsize as f64;
+        (rate, entry.vsize)
+      })
+      .collect::<Vec<(f64, u64)>>();
+
+    Self::try_from(
+      estimate
+        .into_iter()
+        .chain(Self::mempool_rate(mempool, target))
+        .fold(MIN_RELAY_FEE_RATE, f64::max),
+    )
+  }
+
+  /// Fee rate of the last transaction in `mempool`, a list of fee rates and
+  /// virtual sizes, that would be included if the next `target` blocks were
+  /// filled in order of fee rate, or `None` if they would hold the entire
+  /// mempool.
+  fn mempool_rate(mut mempool: Vec<(f64, u64)>, target: u16) -> Option<f64> {
+    mempool.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+    let window = u64::from(target) * BLOCK_VSIZE;
+
+    let mut vsize = 0;
+
+    for (rate, size) in mempool {
+      vsize += size;
+
+      if vsize >= window {
+        return Some(rate);
+      }
+    }
+
+    None
+  }
+
   pub fn fee(&self, vsize: usize) -> Amount {
     #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::cast_sign_loss)]
@@ -48,6 +155,25 @@ mod tests {
     assert!(FeeRate::try_from(f64::NAN).is_err());
   }
 
+  #[test]
+  fn parse_arg() {
+    assert_eq!("auto".parse::<FeeRateArg>().unwrap(), FeeRateArg::Auto);
+    assert_eq!(
+      "2.5".parse::<FeeRateArg>().unwrap(),
+      FeeRateArg::Rate(FeeRate(2.5))
+    );
+    assert!("foo".parse::<FeeRateArg>().is_err());
+  }
+
+  #[test]
+  fn mempool_rate() {
+    let mempool = vec![(1.0, 600_000), (20.0, 500_000), (5.0, 700_000)];
+
+    assert_eq!(FeeRate::mempool_rate(mempool.clone(), 1), Some(5.0));
+    assert_eq!(FeeRate::mempool_rate(mempool.clone(), 2), None);
+    assert_eq!(FeeRate::mempool_rate(Vec::new(), 1), None);
+  }
+
   #[test]
   fn fee() {
     assert_eq!(
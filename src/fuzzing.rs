@@ -0,0 +1,37 @@
+//! Entry points for the targets in `fuzz/`, for code that handles
+//! attacker-controlled on-chain data but is not otherwise public.
+
+use {super::*, inscriptions::ParsedEnvelope};
+
+/// Parse the inscription envelopes in `transaction` and decode every field of
+/// each inscription, as the indexer and explorer do.
+pub fn parse_envelopes(transaction: &Transaction) {
+  for envelope in ParsedEnvelope::from_transaction(transaction) {
+    decode_fields(&envelope.payload);
+  }
+}
+
+/// Build an inscription from the pushes of an envelope's payload, which may
+/// have unknown, duplicate, or incomplete fields, and decode every field.
+pub fn parse_inscription_payload(payload: Vec<Vec<u8>>) {
+  let envelope = ParsedEnvelope::from(Envelope {
+    payload,
+    ..Default::default()
+  });
+
+  decode_fields(&envelope.payload);
+}
+
+fn decode_fields(inscription: &Inscription) {
+  inscription.content_encoding();
+  inscription.content_length();
+  inscription.content_type();
+  inscription.delegate();
+  inscription.hidden();
+  inscription.media();
+  inscription.metadata();
+  inscription.metaprotocol();
+  inscription.parent();
+  inscription.pointer();
+  inscription.append_reveal_script_to_builder(script::Builder::new());
+}
@@ -0,0 +1,191 @@
+use {super::*, std::sync::RwLock};
+
+/// Inscriptions whose content is not served or published. Inscriptions hidden
+/// in the config file can only be unhidden by editing it. Inscriptions hidden
+/// with `PUT /admin/hidden/<INSCRIPTION_ID>` are stored one per line in
+/// `<DATA_DIR>/hidden.txt`, outside of the index, so that they survive
+/// restarts and reindexes.
+pub(crate) struct Hidden {
+  config: Arc<Config>,
+  path: PathBuf,
+  stored: RwLock<HashSet<InscriptionId>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct HiddenJson {
+  pub config: Vec<InscriptionId>,
+  pub stored: Vec<InscriptionId>,
+}
+
+impl Hidden {
+  pub(crate) fn load(config: Arc<Config>, path: PathBuf) -> Result<Self> {
+    let stored = match fs::read_to_string(&path) {
+      Ok(contents) => contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+          line.parse().with_context(|| {
+            format!(
+              "invalid inscription ID `{line}` in hidden list `{}`",
+              path.display()
+            )
+          })
+        })
+        .collect::<Result<HashSet<InscriptionId>>>()?,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => HashSet::new(),
+      Err(err) => {
+        return Err(err).with_context(|| format!("failed to read hidden list `{}`", path.display()))
+      }
+    };
+
+    Ok(Self {
+      config,
+      path,
+      stored: RwLock::new(stored),
+    })
+  }
+
+  /// Whether `inscription_id` is hidden in the config file, and so can't be
+  /// unhidden with `DELETE /admin/hidden/<INSCRIPTION_ID>`.
+  pub(crate) fn in_config(&self, inscription_id: InscriptionId) -> bool {
+    self.config.is_hidden(inscription_id)
+  }
+
+  pub(crate) fn contains(&self, inscription_id: InscriptionId) -> bool {
+    self.config.is_hidden(inscription_id) || self.stored.read().unwrap().contains(&inscription_id)
+  }
+
+  /// Hide `inscription_id`, returning false if it was already stored.
+  pub(crate) fn insert(&self, inscription_id: InscriptionId) -> Result<bool> {
+    let mut stored = self.stored.write().unwrap();
+
+    if !stored.insert(inscription_id) {
+      return Ok(false);
+    }
+
+    if let Err(err) = self.store(&stored) {
+      stored.remove(&inscription_id);
+      return Err(err);
+    }
+
+    Ok(true)
+  }
+
+  /// Unhide `inscription_id`, returning false if it was not stored.
+  pub(crate) fn remove(&self, inscription_id: InscriptionId) -> Result<bool> {
+    let mut stored = self.stored.write().unwrap();
+
+    if !stored.remove(&inscription_id) {
+      return Ok(false);
+    }
+
+    if let Err(err) = self.store(&stored) {
+      stored.insert(inscription_id);
+      return Err(err);
+    }
+
+    Ok(true)
+  }
+
+  /// Write the hidden list, replacing the previous list atomically so that a
+  /// crash never leaves it partially written.
+  fn store(&self, stored: &HashSet<InscriptionId>) -> Result {
+    let mut tmp = self.path.clone().into_os_string();
+    tmp.push(".tmp");
+
+    fs::write(
+      &tmp,
+      sorted(stored)
+        .into_iter()
+        .map(|inscription_id| format!("{inscription_id}\n"))
+        .collect::<String>(),
+    )?;
+
+    fs::rename(&tmp, &self.path)?;
+
+    Ok(())
+  }
+
+  pub(crate) fn json(&self) -> HiddenJson {
+    HiddenJson {
+      config: sorted(&self.config.hidden),
+      stored: sorted(&self.stored.read().unwrap()),
+    }
+  }
+}
+
+fn sorted(inscription_ids: &HashSet<InscriptionId>) -> Vec<InscriptionId> {
+  let mut inscription_ids = inscription_ids
+    .iter()
+    .copied()
+    .collect::<Vec<InscriptionId>>();
+  inscription_ids.sort_by_key(|inscription_id| inscription_id.to_string());
+  inscription_ids
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn stored_inscriptions_persist() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("hidden.txt");
+
+    let config = Arc::new(Config {
+      hidden: HashSet::from([inscription_id(1)]),
+      ..Default::default()
+    });
+
+    let hidden = Hidden::load(config, path.clone()).unwrap();
+
+    assert!(hidden.contains(inscription_id(1)));
+    assert!(!hidden.contains(inscription_id(2)));
+
+    assert!(hidden.insert(inscription_id(3)).unwrap());
+    assert!(hidden.insert(inscription_id(2)).unwrap());
+    assert!(!hidden.insert(inscription_id(2)).unwrap());
+
+    assert_eq!(
+      fs::read_to_string(&path).unwrap(),
+      format!("{}\n{}\n", inscription_id(2), inscription_id(3)),
+    );
+
+    let hidden = Hidden::load(Arc::default(), path.clone()).unwrap();
+
+    assert!(!hidden.contains(inscription_id(1)));
+    assert!(hidden.contains(inscription_id(2)));
+    assert!(hidden.contains(inscription_id(3)));
+
+    assert!(hidden.remove(inscription_id(2)).unwrap());
+    assert!(!hidden.remove(inscription_id(2)).unwrap());
+
+    assert_eq!(
+      Hidden::load(Arc::default(), path).unwrap().json(),
+      HiddenJson {
+        config: Vec::new(),
+        stored: vec![inscription_id(3)],
+      },
+    );
+  }
+
+  #[test]
+  fn invalid_stored_inscription_id() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("hidden.txt");
+
+    fs::write(&path, "foo\n").unwrap();
+
+    assert_eq!(
+      Hidden::load(Arc::default(), path.clone())
+        .err()
+        .unwrap()
+        .to_string(),
+      format!(
+        "invalid inscription ID `foo` in hidden list `{}`",
+        path.display()
+      ),
+    );
+  }
+}
@@ -1,17 +1,26 @@
 use {
   self::{
+    collection::Collections,
+    delta::{BlockDelta, InscriptionDelta, MoveDelta},
     entry::{
-      Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
+      load_rune_balances, AddressChange, CollectionEntry, CollectionEntryValue, Entry, HeaderValue,
+      InscriptionEntry, InscriptionEntryValue, InscriptionFeesEntry, InscriptionFeesValue,
+      InscriptionIdValue, OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange,
+      TransferEntry, TransferValue, TxidValue,
     },
+    lease::Lease,
+    outbox::Outbox,
+    protocol::ProtocolTable,
     reorg::*,
     runes::{Rune, RuneId},
+    sort::{InscriptionSort, SortOrder},
     updater::Updater,
   },
   super::*,
   crate::{
     subcommand::{find::FindRangeOutput, server::InscriptionQuery},
-    templates::{RuneHtml, StatusHtml},
+    teleburn::TargetChain,
+    templates::{DashboardHtml, IndexProgress, IndexStage, RuneHtml, StatusHtml},
   },
   bitcoin::block::Header,
   bitcoincore_rpc::{json::GetBlockHeaderResult, Client},
@@ -25,23 +34,50 @@ use {
   },
   std::{
     collections::{BTreeSet, HashMap},
-    io::{BufWriter, Write},
-    sync::{Mutex, Once},
+    io::{BufWriter, Read, Write},
+    sync::{mpsc, Mutex, Once},
   },
 };
 
 pub(crate) use self::entry::RuneEntry;
 
+pub(crate) mod activity;
+mod archive;
+mod backup;
+pub(crate) mod bucket;
+mod collection;
+mod delta;
 pub(crate) mod entry;
+pub(crate) mod event;
 mod fetcher;
+pub(crate) mod filter;
+mod ipfs;
+mod lease;
+pub(crate) mod metrics;
+mod migration;
+mod mirror;
+mod nats;
+mod nostr;
+mod outbox;
+mod proto;
+pub(crate) mod protocol;
+pub(crate) mod recursion;
+pub(crate) mod remote;
 mod reorg;
+mod rest;
 mod rtx;
+pub(crate) mod rune_history;
+pub(crate) mod sort;
+pub(crate) mod subscription;
 mod updater;
+pub(crate) mod validation;
+pub(crate) mod watch;
+pub(crate) mod zmq;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 16;
+const SCHEMA_VERSION: u64 = 38;
 
 macro_rules! define_table {
   ($name:ident, $key:ty, $value:ty) => {
@@ -56,26 +92,51 @@ macro_rules! define_multimap_table {
   };
 }
 
+define_multimap_table! { HEIGHT_TO_ADDRESS_CHANGE, u32, &[u8] }
 define_multimap_table! { SATPOINT_TO_SEQUENCE_NUMBER, &SatPointValue, u32 }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
+define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], &OutPointValue }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
+define_multimap_table! { TELEBURN_CLAIM_TO_INSCRIPTION_ID, &str, InscriptionIdValue }
+define_table! { CHILD_SEQUENCE_NUMBER_TO_OWNER, u32, &[u8] }
+define_table! { COLLECTION_HOLDER_TO_CHILD_COUNT, (u32, &[u8]), u64 }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
+define_table! { HEIGHT_TO_EVENTS, u32, &[u8] }
+define_table! { HEIGHT_TO_INSCRIPTION_FEES, u32, &InscriptionFeesValue }
+define_table! { HEIGHT_TO_INSCRIPTION_MOVES, (u32, u32), &SatPointValue }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
+define_table! { INSCRIPTION_SORT_KEYS, (u8, &str, u64, u32), () }
+define_table! { OUTBOX, u64, &[u8] }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_SCRIPT_PUBKEY, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_VALUE, &OutPointValue, u64}
+define_table! { PROTOCOL_TABLE_TO_MIGRATION_CURSOR, &str, &[u8] }
+define_table! { PROTOCOL_TABLE_TO_SCHEMA_VERSION, &str, u64 }
+define_table! { QUARANTINED_ENTRY_TO_VALUE, &str, &[u8] }
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
+define_table! { SATPOINT_TO_OWNER, &SatPointValue, &[u8] }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SCRIPT_PUBKEY_TO_ACTIVITY, (&[u8], u64), &[u8] }
+define_table! { SCRIPT_PUBKEY_TO_RUNE_BALANCE_HISTORY, (&[u8], RuneIdValue, u32), u128 }
+define_table! { SEQUENCE_NUMBER_TO_CID, u32, &str }
+define_table! { SEQUENCE_NUMBER_TO_COLLECTION, u32, CollectionEntryValue }
+define_table! { SEQUENCE_NUMBER_TO_CONTENT_VERDICTS, u32, &[u8] }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
+define_table! { SEQUENCE_NUMBER_TO_RECURSIVE_ENDPOINTS, u32, u16 }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
+define_table! { SEQUENCE_NUMBER_TO_TRANSFERS, (u32, u32), &TransferValue }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
+define_table! { SUBSCRIPTION_ID_TO_SUBSCRIPTION, u64, &[u8] }
 define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
+define_table! { WATCH_ID_TO_MOVE, u64, &[u8] }
+define_table! { WATCH_ID_TO_WATCH, u64, &[u8] }
 define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
 
 #[derive(Debug, PartialEq)]
@@ -99,6 +160,12 @@ pub(crate) enum Statistic {
   SatRanges,
   UnboundInscriptions,
   IndexTransactions,
+  ShutdownCheckpoint,
+  QuarantinedEntries,
+  IndexAddresses,
+  IndexInscriptionSorts,
+  RuneReindexHeight,
+  IndexEvents,
 }
 
 impl Statistic {
@@ -115,18 +182,18 @@ impl From<Statistic> for u64 {
 
 #[derive(Serialize)]
 pub(crate) struct Info {
-  blocks_indexed: u32,
+  pub(crate) blocks_indexed: u32,
   branch_pages: u64,
   fragmented_bytes: u64,
   index_file_size: u64,
-  index_path: PathBuf,
+  pub(crate) index_path: PathBuf,
   leaf_pages: u64,
   metadata_bytes: u64,
   outputs_traversed: u64,
   page_size: usize,
   sat_ranges: u64,
   stored_bytes: u64,
-  tables: BTreeMap<String, TableInfo>,
+  pub(crate) tables: BTreeMap<String, TableInfo>,
   total_bytes: u64,
   pub(crate) transactions: Vec<TransactionInfo>,
   tree_height: u32,
@@ -136,12 +203,13 @@ pub(crate) struct Info {
 #[derive(Serialize)]
 pub(crate) struct TableInfo {
   branch_pages: u64,
-  fragmented_bytes: u64,
+  pub(crate) entries: u64,
+  pub(crate) fragmented_bytes: u64,
   leaf_pages: u64,
-  metadata_bytes: u64,
+  pub(crate) metadata_bytes: u64,
   proportion: f64,
-  stored_bytes: u64,
-  total_bytes: u64,
+  pub(crate) stored_bytes: u64,
+  pub(crate) total_bytes: u64,
   tree_height: u32,
 }
 
@@ -153,6 +221,8 @@ pub(crate) struct TransactionInfo {
 
 pub(crate) struct InscriptionInfo {
   pub(crate) children: Vec<InscriptionId>,
+  pub(crate) cid: Option<String>,
+  pub(crate) content_verdicts: Vec<ContentVerdict>,
   pub(crate) entry: InscriptionEntry,
   pub(crate) parent: Option<InscriptionId>,
   pub(crate) output: Option<TxOut>,
@@ -160,6 +230,7 @@ pub(crate) struct InscriptionInfo {
   pub(crate) inscription: Inscription,
   pub(crate) previous: Option<InscriptionId>,
   pub(crate) next: Option<InscriptionId>,
+  pub(crate) recursive_endpoints: Vec<RecursiveEndpoint>,
   pub(crate) rune: Option<SpacedRune>,
   pub(crate) charms: u16,
 }
@@ -188,18 +259,29 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 }
 
 pub struct Index {
+  cache_budget: Option<usize>,
   client: Client,
+  commit_cache_size: Option<usize>,
+  commit_interval: u32,
   database: Database,
   durability: redb::Durability,
-  first_inscription_height: u32,
+  event_subscribers: Mutex<Vec<mpsc::Sender<Vec<event::Event>>>>,
+  params: NetworkParams,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  index_addresses: bool,
+  index_events: bool,
+  index_inscription_sorts: bool,
   index_runes: bool,
   index_sats: bool,
   index_transactions: bool,
+  max_savepoints: u32,
+  metrics: metrics::Metrics,
   options: Options,
   path: PathBuf,
+  progress: Mutex<IndexProgress>,
+  savepoint_interval: u32,
   started: DateTime<Utc>,
   unrecoverably_reorged: AtomicBool,
 }
@@ -220,6 +302,29 @@ impl Index {
       );
     }
 
+    ensure!(
+      options.crash_at.is_none() || integration_test(),
+      "`--crash-at` is only available in integration tests"
+    );
+
+    let commit_interval = options.commit_interval.unwrap_or(5000);
+
+    if commit_interval == 0 {
+      bail!("commit interval must be greater than zero");
+    }
+
+    let max_savepoints = options.max_savepoints.unwrap_or(2);
+
+    if max_savepoints == 0 {
+      bail!("max savepoints must be greater than zero");
+    }
+
+    let savepoint_interval = options.savepoint_interval.unwrap_or(10);
+
+    if savepoint_interval == 0 {
+      bail!("savepoint interval must be greater than zero");
+    }
+
     let db_cache_size = match options.db_cache_size {
       Some(db_cache_size) => db_cache_size,
       None => {
@@ -237,6 +342,9 @@ impl Index {
       redb::Durability::Immediate
     };
 
+    let index_addresses;
+    let index_events;
+    let index_inscription_sorts;
     let index_runes;
     let index_sats;
     let index_transactions;
@@ -245,7 +353,9 @@ impl Index {
     let once = Once::new();
     let progress_bar = Mutex::new(None);
 
-    let database = match Database::builder()
+    let mut builder = Database::builder();
+
+    builder
       .set_cache_size(db_cache_size)
       .set_repair_callback(move |progress: &mut RepairSession| {
         once.call_once(|| println!("Index file `{}` needs recovery. This can take a long time, especially for the --index-sats index.", index_path.display()));
@@ -264,9 +374,21 @@ impl Index {
           #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
           progress_bar.set_position((progress.progress() * 100.0) as u64);
         }
-      })
-      .open(&path)
-    {
+      });
+
+    let lock_timeout = Duration::from_secs(options.index_lock_timeout.unwrap_or(0));
+    let start = Instant::now();
+
+    let result = loop {
+      match builder.open(&path) {
+        Err(DatabaseError::DatabaseAlreadyOpen) if start.elapsed() < lock_timeout => {
+          thread::sleep(Duration::from_millis(100));
+        }
+        result => break result,
+      }
+    };
+
+    let database = match result {
       Ok(database) => {
         {
           let tx = database.begin_read()?;
@@ -292,10 +414,28 @@ impl Index {
             }
           }
 
-
+          index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
+          index_events = Self::is_statistic_set(&statistics, Statistic::IndexEvents)?;
+          index_inscription_sorts =
+            Self::is_statistic_set(&statistics, Statistic::IndexInscriptionSorts)?;
           index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
           index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
           index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
+
+          if let Some(index_mode) = options.index_mode {
+            if (index_runes, index_sats, index_transactions)
+              != (
+                options.index_runes(),
+                options.index_sats(),
+                options.index_transactions(),
+              )
+            {
+              bail!(
+                "index at `{}` was not built in {index_mode} index mode, consider deleting and rebuilding the index",
+                path.display()
+              );
+            }
+          }
         }
 
         database
@@ -311,40 +451,102 @@ impl Index {
 
         tx.set_durability(durability);
 
+        tx.open_multimap_table(HEIGHT_TO_ADDRESS_CHANGE)?;
         tx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+        tx.open_multimap_table(TELEBURN_CLAIM_TO_INSCRIPTION_ID)?;
+        tx.open_table(CHILD_SEQUENCE_NUMBER_TO_OWNER)?;
+        tx.open_table(COLLECTION_HOLDER_TO_CHILD_COUNT)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+        tx.open_table(HEIGHT_TO_EVENTS)?;
+        tx.open_table(HEIGHT_TO_INSCRIPTION_FEES)?;
+        tx.open_table(HEIGHT_TO_INSCRIPTION_MOVES)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
         tx.open_table(HOME_INSCRIPTIONS)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(INSCRIPTION_SORT_KEYS)?;
+        tx.open_table(OUTBOX)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_SCRIPT_PUBKEY)?;
         tx.open_table(OUTPOINT_TO_VALUE)?;
+        tx.open_table(PROTOCOL_TABLE_TO_MIGRATION_CURSOR)?;
+        tx.open_table(QUARANTINED_ENTRY_TO_VALUE)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
+        tx.open_table(SATPOINT_TO_OWNER)?;
         tx.open_table(SAT_TO_SATPOINT)?;
+        tx.open_table(SCRIPT_PUBKEY_TO_ACTIVITY)?;
+        tx.open_table(SCRIPT_PUBKEY_TO_RUNE_BALANCE_HISTORY)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_CID)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_COLLECTION)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_CONTENT_VERDICTS)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_RECURSIVE_ENDPOINTS)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_TRANSFERS)?;
+        tx.open_table(SUBSCRIPTION_ID_TO_SUBSCRIPTION)?;
         tx.open_table(TRANSACTION_ID_TO_RUNE)?;
+        tx.open_table(WATCH_ID_TO_MOVE)?;
+        tx.open_table(WATCH_ID_TO_WATCH)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
+        {
+          let mut protocol_table_to_schema_version =
+            tx.open_table(PROTOCOL_TABLE_TO_SCHEMA_VERSION)?;
+
+          for table in ProtocolTable::ALL {
+            protocol_table_to_schema_version.insert(
+              table.name().as_str(),
+              table.schema_version(migration::MIGRATIONS),
+            )?;
+          }
+        }
+
         {
           let mut outpoint_to_sat_ranges = tx.open_table(OUTPOINT_TO_SAT_RANGES)?;
           let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
 
-          if options.index_sats {
+          if options.index_sats() {
             outpoint_to_sat_ranges.insert(&OutPoint::null().store(), [].as_slice())?;
           }
 
+          index_addresses = options.index_addresses;
+          index_events = options.index_events;
+          index_inscription_sorts = options.index_inscription_sorts;
           index_runes = options.index_runes();
-          index_sats = options.index_sats;
-          index_transactions = options.index_transactions;
-
-          Self::set_statistic(&mut statistics, Statistic::IndexRunes, u64::from(index_runes))?;
+          index_sats = options.index_sats();
+          index_transactions = options.index_transactions();
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexAddresses,
+            u64::from(index_addresses),
+          )?;
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexEvents,
+            u64::from(index_events),
+          )?;
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexInscriptionSorts,
+            u64::from(index_inscription_sorts),
+          )?;
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexRunes,
+            u64::from(index_runes),
+          )?;
           Self::set_statistic(&mut statistics, Statistic::IndexSats, u64::from(index_sats))?;
-          Self::set_statistic(&mut statistics, Statistic::IndexTransactions, u64::from(index_transactions))?;
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexTransactions,
+            u64::from(index_transactions),
+          )?;
           Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
         }
 
@@ -352,28 +554,133 @@ impl Index {
 
         database
       }
+      Err(DatabaseError::DatabaseAlreadyOpen) => bail!(
+        "index at `{}` is in use by another process{}",
+        path.display(),
+        Lease::load(&path)
+          .map(|lease| format!(" ({lease})"))
+          .unwrap_or_default(),
+      ),
       Err(error) => bail!("failed to open index: {error}"),
     };
 
-    let genesis_block_coinbase_transaction =
-      options.chain().genesis_block().coinbase().unwrap().clone();
+    let genesis_block = match options.chain().genesis_block() {
+      Some(genesis_block) => genesis_block,
+      None => {
+        let genesis_block = client.get_block(&client.get_block_hash(0)?)?;
+
+        ensure!(
+          genesis_block.block_hash() == options.chain().genesis_block_hash(),
+          "Bitcoin Core returned unexpected genesis block {}",
+          genesis_block.block_hash(),
+        );
+
+        genesis_block
+      }
+    };
+
+    let genesis_block_coinbase_transaction = genesis_block.coinbase().unwrap().clone();
 
-    Ok(Self {
+    let index = Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
+      cache_budget: options.cache_budget,
       client,
+      commit_cache_size: options.commit_cache_size,
+      commit_interval,
       database,
       durability,
-      first_inscription_height: options.first_inscription_height(),
+      event_subscribers: Mutex::new(Vec::new()),
+      params: options.network_params(),
       genesis_block_coinbase_transaction,
       height_limit: options.height_limit,
+      index_addresses,
+      index_events,
+      index_inscription_sorts,
       index_runes,
       index_sats,
       index_transactions,
+      max_savepoints,
+      metrics: metrics::Metrics::default(),
       options: options.clone(),
       path,
+      progress: Mutex::new(IndexProgress::default()),
+      savepoint_interval,
       started: Utc::now(),
       unrecoverably_reorged: AtomicBool::new(false),
-    })
+    };
+
+    index.migrate(migration::MIGRATIONS, migration::BATCH_SIZE)?;
+
+    index.store_lease()?;
+
+    if let Some(height) = index.shutdown_checkpoint()? {
+      log::info!("Resuming indexing from shutdown checkpoint at block {height}");
+    }
+
+    Ok(index)
+  }
+
+  pub(crate) fn metrics(&self) -> &metrics::Metrics {
+    &self.metrics
+  }
+
+  /// Block count at which indexing stopped, if the last commit was made while
+  /// shutting down. Commits made while running normally clear it.
+  pub(crate) fn shutdown_checkpoint(&self) -> Result<Option<u32>> {
+    match self.statistic(Statistic::ShutdownCheckpoint) {
+      0 => Ok(None),
+      height => Ok(Some(height.try_into()?)),
+    }
+  }
+
+  /// Record that this process holds the index, and the height of the last
+  /// committed block, in the index's lease file.
+  pub(crate) fn store_lease(&self) -> Result {
+    Lease {
+      height: self.block_count()?.checked_sub(1),
+      pid: process::id(),
+    }
+    .store(&self.path)
+  }
+
+  /// Receive the events of every block indexed from now on, including blocks
+  /// that are later rolled back by a reorg.
+  pub(crate) fn subscribe_events(&self) -> mpsc::Receiver<Vec<event::Event>> {
+    let (sender, receiver) = mpsc::channel();
+    self.event_subscribers.lock().unwrap().push(sender);
+    receiver
+  }
+
+  fn has_event_subscribers(&self) -> bool {
+    !self.event_subscribers.lock().unwrap().is_empty()
+  }
+
+  fn notify_event_subscribers(&self, events: &[event::Event]) {
+    self
+      .event_subscribers
+      .lock()
+      .unwrap()
+      .retain(|subscriber| subscriber.send(events.to_vec()).is_ok());
+  }
+
+  /// Height of the first block that any enabled protocol needs transactions
+  /// for. Earlier blocks are fetched as headers only, unless sats or addresses
+  /// are tracked.
+  fn first_full_block_height(&self) -> u32 {
+    if self.index_addresses {
+      return 0;
+    }
+
+    let inscriptions =
+      (!self.options.no_index_inscriptions).then_some(self.params.first_inscription_height);
+
+    let runes = self.index_runes.then_some(self.params.first_rune_height);
+
+    inscriptions
+      .into_iter()
+      .chain(runes)
+      .min()
+      .unwrap_or(u32::MAX)
   }
 
   #[cfg(test)]
@@ -395,6 +702,18 @@ impl Index {
     Ok(true)
   }
 
+  pub(crate) fn has_address_index(&self) -> bool {
+    self.index_addresses
+  }
+
+  pub(crate) fn has_event_index(&self) -> bool {
+    self.index_events
+  }
+
+  pub(crate) fn has_inscription_sort_index(&self) -> bool {
+    self.index_inscription_sorts
+  }
+
   pub(crate) fn has_rune_index(&self) -> bool {
     self.index_runes
   }
@@ -440,6 +759,8 @@ impl Index {
         self.options.chain(),
         Height(next_height),
       ),
+      progress: *self.progress.lock().unwrap(),
+      quarantined_entries: statistic(Statistic::QuarantinedEntries)?,
       rune_index: statistic(Statistic::IndexRunes)? != 0,
       runes: statistic(Statistic::Runes)?,
       sat_index: statistic(Statistic::IndexSats)? != 0,
@@ -450,6 +771,23 @@ impl Index {
     })
   }
 
+  pub(crate) fn dashboard(&self) -> Result<DashboardHtml> {
+    let chain_block_count = match self.client.get_block_count() {
+      Ok(height) => Some(u32::try_from(height)? + 1),
+      Err(err) => {
+        log::warn!("failed to get chain block count: {err}");
+        None
+      }
+    };
+
+    Ok(DashboardHtml {
+      blocks_indexed: self.block_count()?,
+      chain_block_count,
+      progress: *self.progress.lock().unwrap(),
+      samples: self.metrics.samples(),
+    })
+  }
+
   pub(crate) fn info(&self) -> Result<Info> {
     fn insert_table_info<K: RedbKey + 'static, V: RedbValue + 'static>(
       tables: &mut BTreeMap<String, TableInfo>,
@@ -457,7 +795,8 @@ impl Index {
       database_total_bytes: u64,
       definition: TableDefinition<K, V>,
     ) {
-      let stats = wtx.open_table(definition).unwrap().stats().unwrap();
+      let table = wtx.open_table(definition).unwrap();
+      let stats = table.stats().unwrap();
 
       let fragmented_bytes = stats.fragmented_bytes();
       let metadata_bytes = stats.metadata_bytes();
@@ -468,6 +807,7 @@ impl Index {
         definition.name().into(),
         TableInfo {
           branch_pages: stats.branch_pages(),
+          entries: table.len().unwrap(),
           fragmented_bytes,
           leaf_pages: stats.leaf_pages(),
           metadata_bytes,
@@ -485,11 +825,8 @@ impl Index {
       database_total_bytes: u64,
       definition: MultimapTableDefinition<K, V>,
     ) {
-      let stats = wtx
-        .open_multimap_table(definition)
-        .unwrap()
-        .stats()
-        .unwrap();
+      let table = wtx.open_multimap_table(definition).unwrap();
+      let stats = table.stats().unwrap();
 
       let fragmented_bytes = stats.fragmented_bytes();
       let metadata_bytes = stats.metadata_bytes();
@@ -500,6 +837,7 @@ impl Index {
         definition.name().into(),
         TableInfo {
           branch_pages: stats.branch_pages(),
+          entries: table.len().unwrap(),
           fragmented_bytes,
           leaf_pages: stats.leaf_pages(),
           metadata_bytes,
@@ -522,10 +860,33 @@ impl Index {
 
     let mut tables: BTreeMap<String, TableInfo> = BTreeMap::new();
 
+    insert_multimap_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_ADDRESS_CHANGE);
     insert_multimap_table_info(&mut tables, &wtx, total_bytes, SATPOINT_TO_SEQUENCE_NUMBER);
     insert_multimap_table_info(&mut tables, &wtx, total_bytes, SAT_TO_SEQUENCE_NUMBER);
+    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SCRIPT_PUBKEY_TO_OUTPOINT);
     insert_multimap_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_CHILDREN);
+    insert_multimap_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      TELEBURN_CLAIM_TO_INSCRIPTION_ID,
+    );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      CHILD_SEQUENCE_NUMBER_TO_OWNER,
+    );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      COLLECTION_HOLDER_TO_CHILD_COUNT,
+    );
     insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_BLOCK_HEADER);
+    insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_EVENTS);
+    insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_INSCRIPTION_FEES);
+    insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_INSCRIPTION_MOVES);
     insert_table_info(
       &mut tables,
       &wtx,
@@ -545,21 +906,71 @@ impl Index {
       total_bytes,
       INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
     );
+    insert_table_info(&mut tables, &wtx, total_bytes, INSCRIPTION_SORT_KEYS);
+    insert_table_info(&mut tables, &wtx, total_bytes, OUTBOX);
     insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_RUNE_BALANCES);
     insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_SAT_RANGES);
+    insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_SCRIPT_PUBKEY);
     insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_VALUE);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      PROTOCOL_TABLE_TO_MIGRATION_CURSOR,
+    );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      PROTOCOL_TABLE_TO_SCHEMA_VERSION,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, QUARANTINED_ENTRY_TO_VALUE);
     insert_table_info(&mut tables, &wtx, total_bytes, RUNE_ID_TO_RUNE_ENTRY);
     insert_table_info(&mut tables, &wtx, total_bytes, RUNE_TO_RUNE_ID);
+    insert_table_info(&mut tables, &wtx, total_bytes, SATPOINT_TO_OWNER);
     insert_table_info(&mut tables, &wtx, total_bytes, SAT_TO_SATPOINT);
+    insert_table_info(&mut tables, &wtx, total_bytes, SCRIPT_PUBKEY_TO_ACTIVITY);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      SCRIPT_PUBKEY_TO_RUNE_BALANCE_HISTORY,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_CID);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      SEQUENCE_NUMBER_TO_COLLECTION,
+    );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      SEQUENCE_NUMBER_TO_CONTENT_VERDICTS,
+    );
     insert_table_info(
       &mut tables,
       &wtx,
       total_bytes,
       SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY,
     );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      SEQUENCE_NUMBER_TO_RECURSIVE_ENDPOINTS,
+    );
     insert_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_RUNE_ID);
     insert_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_SATPOINT);
+    insert_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_TRANSFERS);
     insert_table_info(&mut tables, &wtx, total_bytes, STATISTIC_TO_COUNT);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      SUBSCRIPTION_ID_TO_SUBSCRIPTION,
+    );
     insert_table_info(&mut tables, &wtx, total_bytes, TRANSACTION_ID_TO_RUNE);
     insert_table_info(
       &mut tables,
@@ -567,6 +978,8 @@ impl Index {
       total_bytes,
       TRANSACTION_ID_TO_TRANSACTION,
     );
+    insert_table_info(&mut tables, &wtx, total_bytes, WATCH_ID_TO_MOVE);
+    insert_table_info(&mut tables, &wtx, total_bytes, WATCH_ID_TO_WATCH);
     insert_table_info(
       &mut tables,
       &wtx,
@@ -633,6 +1046,12 @@ impl Index {
   }
 
   pub(crate) fn update(&self) -> Result {
+    if let Some(height) = self.interrupted_rune_reindex()? {
+      bail!(
+        "rune reindex was interrupted at block {height}, resume with `ord protocol reindex --protocol runes --from {height}`"
+      );
+    }
+
     let mut updater = Updater::new(self)?;
 
     loop {
@@ -643,6 +1062,8 @@ impl Index {
 
           match err.downcast_ref() {
             Some(&ReorgError::Recoverable { height, depth }) => {
+              self.metrics.record_reorg(height, depth);
+
               Reorg::handle_reorg(self, height, depth)?;
 
               updater = Updater::new(self)?;
@@ -651,9 +1072,13 @@ impl Index {
               self
                 .unrecoverably_reorged
                 .store(true, atomic::Ordering::Relaxed);
+              self.metrics.record_error();
               return Err(anyhow!(ReorgError::Unrecoverable));
             }
-            _ => return Err(err),
+            _ => {
+              self.metrics.record_error();
+              return Err(err);
+            }
           };
         }
       }
@@ -710,8 +1135,7 @@ impl Index {
             .nth(satpoint.outpoint.vout.try_into().unwrap())
             .unwrap();
           self
-            .options
-            .chain()
+            .params
             .address_from_script(&output.script_pubkey)
             .map(|address| address.to_string())
             .unwrap_or_else(|e| e.to_string())
@@ -728,1183 +1152,1322 @@ impl Index {
     Ok(())
   }
 
-  fn begin_read(&self) -> Result<rtx::Rtx> {
-    Ok(rtx::Rtx(self.database.begin_read()?))
-  }
+  pub(crate) fn export_deltas(&self, from: u32, to: u32, writer: &mut impl Write) -> Result<u32> {
+    let rtx = self.database.begin_read()?;
 
-  fn begin_write(&self) -> Result<WriteTransaction> {
-    let mut tx = self.database.begin_write()?;
-    tx.set_durability(self.durability);
-    Ok(tx)
-  }
+    let height_to_block_header = rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+    let height_to_inscription_fees = rtx.open_table(HEIGHT_TO_INSCRIPTION_FEES)?;
+    let height_to_inscription_moves = rtx.open_table(HEIGHT_TO_INSCRIPTION_MOVES)?;
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let satpoint_to_owner = rtx.open_table(SATPOINT_TO_OWNER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_recursive_endpoints =
+      rtx.open_table(SEQUENCE_NUMBER_TO_RECURSIVE_ENDPOINTS)?;
+    let sequence_number_to_transfers = rtx.open_table(SEQUENCE_NUMBER_TO_TRANSFERS)?;
 
-  fn increment_statistic(wtx: &WriteTransaction, statistic: Statistic, n: u64) -> Result {
-    let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
-    let value = statistic_to_count
-      .get(&(statistic.key()))?
-      .map(|x| x.value())
-      .unwrap_or_default()
-      + n;
-    statistic_to_count.insert(&statistic.key(), &value)?;
-    Ok(())
-  }
+    let mut teleburn_claims = HashMap::<InscriptionId, Vec<String>>::new();
 
-  pub(crate) fn set_statistic(
-    statistics: &mut Table<u64, u64>,
-    statistic: Statistic,
-    value: u64,
-  ) -> Result<()> {
-    statistics.insert(&statistic.key(), &value)?;
-    Ok(())
-  }
+    for result in rtx
+      .open_multimap_table(TELEBURN_CLAIM_TO_INSCRIPTION_ID)?
+      .iter()?
+    {
+      let (claim, inscription_ids) = result?;
 
-  pub(crate) fn is_statistic_set(
-    statistics: &ReadOnlyTable<u64, u64>,
-    statistic: Statistic,
-  ) -> Result<bool> {
-    Ok(
-      statistics
-        .get(&statistic.key())?
-        .map(|guard| guard.value())
-        .unwrap_or_default()
-        != 0,
-    )
-  }
+      for inscription_id in inscription_ids {
+        teleburn_claims
+          .entry(InscriptionId::load(inscription_id?.value()))
+          .or_default()
+          .push(claim.value().into());
+      }
+    }
 
-  #[cfg(test)]
-  pub(crate) fn statistic(&self, statistic: Statistic) -> u64 {
-    self
-      .database
-      .begin_read()
-      .unwrap()
-      .open_table(STATISTIC_TO_COUNT)
-      .unwrap()
-      .get(&statistic.key())
-      .unwrap()
-      .map(|x| x.value())
-      .unwrap_or_default()
-  }
+    // satpoints that inscriptions have since moved away from no longer have
+    // owners in the index, so their owners are read from their transactions
+    let owner = |satpoint: SatPointValue| -> Result<Option<Vec<u8>>> {
+      if let Some(owner) = satpoint_to_owner.get(&satpoint)? {
+        return Ok(Some(owner.value().to_vec()));
+      }
 
-  pub(crate) fn block_count(&self) -> Result<u32> {
-    self.begin_read()?.block_count()
-  }
+      let outpoint = SatPoint::load(satpoint).outpoint;
 
-  pub(crate) fn block_height(&self) -> Result<Option<Height>> {
-    self.begin_read()?.block_height()
-  }
+      if outpoint == OutPoint::null() || outpoint == unbound_outpoint() {
+        return Ok(None);
+      }
 
-  pub(crate) fn block_hash(&self, height: Option<u32>) -> Result<Option<BlockHash>> {
-    self.begin_read()?.block_hash(height)
-  }
+      let output = self
+        .get_transaction(outpoint.txid)?
+        .and_then(|tx| tx.output.into_iter().nth(outpoint.vout.try_into().unwrap()))
+        .ok_or_else(|| anyhow!("could not find inscription output {outpoint}"))?;
 
-  pub(crate) fn blocks(&self, take: usize) -> Result<Vec<(u32, BlockHash)>> {
-    let rtx = self.begin_read()?;
+      Ok(Some(output.script_pubkey.into_bytes()))
+    };
 
-    let block_count = rtx.block_count()?;
+    log::info!("exporting index deltas for blocks {from}..{to}");
 
-    let height_to_block_header = rtx.0.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+    BlockDelta::write_preamble(writer)?;
 
-    let mut blocks = Vec::with_capacity(block_count.try_into().unwrap());
+    let mut blocks = 0;
 
-    for next in height_to_block_header
-      .range(0..block_count)?
-      .rev()
-      .take(take)
-    {
-      let next = next?;
-      blocks.push((next.0.value(), Header::load(*next.1.value()).block_hash()));
-    }
+    for height in from..to {
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        bail!("index delta export interrupted after {blocks} blocks");
+      }
 
-    Ok(blocks)
-  }
+      let Some(header) = height_to_block_header
+        .get(height)?
+        .map(|header| *header.value())
+      else {
+        break;
+      };
 
-  pub(crate) fn rare_sat_satpoints(&self) -> Result<Vec<(Sat, SatPoint)>> {
-    let rtx = self.database.begin_read()?;
+      let oldest_sequence_number = match height.checked_sub(1) {
+        Some(previous) => height_to_last_sequence_number
+          .get(previous)?
+          .map(|sequence_number| sequence_number.value())
+          .unwrap_or(0),
+        None => 0,
+      };
 
-    let sat_to_satpoint = rtx.open_table(SAT_TO_SATPOINT)?;
+      let mut inscriptions = Vec::new();
+      let mut moves = Vec::new();
+      let mut transfers = Vec::new();
 
-    let mut result = Vec::with_capacity(sat_to_satpoint.len()?.try_into().unwrap());
+      for result in height_to_inscription_moves.range((height, 0)..=(height, u32::MAX))? {
+        let (key, satpoint) = result?;
 
-    for range in sat_to_satpoint.range(0..)? {
-      let (sat, satpoint) = range?;
-      result.push((Sat(sat.value()), Entry::load(*satpoint.value())));
-    }
+        let (_height, sequence_number) = key.value();
+        let satpoint = *satpoint.value();
 
-    Ok(result)
-  }
+        if sequence_number < oldest_sequence_number {
+          moves.push(MoveDelta {
+            owner: owner(satpoint)?,
+            satpoint,
+            sequence_number,
+          });
+        } else {
+          let entry = sequence_number_to_inscription_entry
+            .get(sequence_number)?
+            .ok_or_else(|| anyhow!("could not find entry for sequence number {sequence_number}"))?
+            .value();
+
+          inscriptions.push(InscriptionDelta {
+            entry,
+            owner: owner(satpoint)?,
+            recursive_endpoints: sequence_number_to_recursive_endpoints
+              .get(sequence_number)?
+              .map(|endpoints| endpoints.value())
+              .unwrap_or_default(),
+            satpoint,
+            teleburn_claims: teleburn_claims
+              .remove(&InscriptionEntry::load(entry).id)
+              .unwrap_or_default(),
+          });
+        }
 
-  pub(crate) fn rare_sat_satpoint(&self, sat: Sat) -> Result<Option<SatPoint>> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(SAT_TO_SATPOINT)?
-        .get(&sat.n())?
-        .map(|satpoint| Entry::load(*satpoint.value())),
-    )
-  }
+        let mut inscription_transfers = Vec::new();
 
-  pub(crate) fn get_rune_by_id(&self, id: RuneId) -> Result<Option<Rune>> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
-        .get(&id.store())?
-        .map(|entry| RuneEntry::load(entry.value()).rune),
-    )
-  }
+        for result in sequence_number_to_transfers
+          .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+          .rev()
+        {
+          let (_key, transfer) = result?;
+          let transfer = *transfer.value();
 
-  pub(crate) fn rune(&self, rune: Rune) -> Result<Option<(RuneId, RuneEntry)>> {
-    let rtx = self.database.begin_read()?;
+          match TransferEntry::load(transfer).height.cmp(&height) {
+            cmp::Ordering::Less => break,
+            cmp::Ordering::Equal => inscription_transfers.push((sequence_number, transfer)),
+            cmp::Ordering::Greater => {}
+          }
+        }
 
-    let Some(id) = rtx
-      .open_table(RUNE_TO_RUNE_ID)?
-      .get(rune.0)?
-      .map(|guard| guard.value())
-    else {
-      return Ok(None);
-    };
+        transfers.extend(inscription_transfers.into_iter().rev());
+      }
 
-    let entry = RuneEntry::load(
-      rtx
-        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
-        .get(id)?
-        .unwrap()
-        .value(),
-    );
+      BlockDelta {
+        fees: height_to_inscription_fees
+          .get(height)?
+          .map(|fees| *fees.value()),
+        header,
+        height,
+        inscriptions,
+        moves,
+        transfers,
+      }
+      .encode(writer)?;
 
-    Ok(Some((RuneId::load(id), entry)))
+      blocks += 1;
+    }
+
+    BlockDelta::write_end(writer)?;
+
+    Ok(blocks)
   }
 
-  pub(crate) fn rune_html(&self, rune: Rune) -> Result<Option<RuneHtml>> {
-    let rtx = self.database.begin_read()?;
+  pub(crate) fn import_deltas(&self, reader: &mut impl Read) -> Result<u32> {
+    ensure!(
+      !self.index_sats,
+      "index deltas cannot be imported into an index built with `--index-sats`"
+    );
 
-    let Some(id) = rtx
-      .open_table(RUNE_TO_RUNE_ID)?
-      .get(rune.0)?
-      .map(|guard| guard.value())
-    else {
-      return Ok(None);
-    };
+    ensure!(
+      !self.index_inscription_sorts,
+      "index deltas cannot be imported into an index built with `--index-inscription-sorts`"
+    );
 
-    let entry = RuneEntry::load(
-      rtx
-        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
-        .get(id)?
-        .unwrap()
-        .value(),
+    ensure!(
+      !self.index_runes,
+      "index deltas cannot be imported into an index built with `--index-runes`"
     );
 
-    let parent = InscriptionId {
-      txid: entry.etching,
-      index: 0,
-    };
+    ensure!(
+      !self.index_addresses,
+      "index deltas cannot be imported into an index built with `--index-addresses`"
+    );
 
-    let parent = rtx
-      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
-      .get(&parent.store())?
-      .is_some()
-      .then_some(parent);
+    ensure!(
+      !self.index_events,
+      "index deltas cannot be imported into an index built with `--index-events`"
+    );
 
-    Ok(Some(RuneHtml {
-      entry,
-      id: RuneId::load(id),
-      parent,
-    }))
-  }
+    ensure!(
+      self.options.content_validator.is_empty(),
+      "index deltas cannot be imported with `--content-validator`"
+    );
 
-  pub(crate) fn runes(&self) -> Result<Vec<(RuneId, RuneEntry)>> {
-    let mut entries = Vec::new();
+    ensure!(
+      !Outbox::wants_events(self) && !Outbox::wants_contents(self),
+      "index deltas cannot be imported while delivering events or content to external services"
+    );
+
+    BlockDelta::read_preamble(reader)?;
+
+    let wtx = self.begin_write()?;
+
+    let mut blessed = 0;
+    let mut blocks = 0;
+    let mut cursed = 0;
+    let mut unbound = 0;
 
-    for result in self
-      .database
-      .begin_read()?
-      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
-      .iter()?
     {
-      let (id, entry) = result?;
-      entries.push((RuneId::load(id.value()), RuneEntry::load(entry.value())));
-    }
+      let mut child_sequence_number_to_owner = wtx.open_table(CHILD_SEQUENCE_NUMBER_TO_OWNER)?;
+      let mut collection_holder_to_child_count =
+        wtx.open_table(COLLECTION_HOLDER_TO_CHILD_COUNT)?;
+      let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+      let mut height_to_inscription_fees = wtx.open_table(HEIGHT_TO_INSCRIPTION_FEES)?;
+      let mut height_to_inscription_moves = wtx.open_table(HEIGHT_TO_INSCRIPTION_MOVES)?;
+      let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+      let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
+      let mut inscription_id_to_sequence_number =
+        wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+      let mut inscription_number_to_sequence_number =
+        wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+      let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+      let mut satpoint_to_owner = wtx.open_table(SATPOINT_TO_OWNER)?;
+      let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+      let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+      let mut sequence_number_to_collection = wtx.open_table(SEQUENCE_NUMBER_TO_COLLECTION)?;
+      let mut sequence_number_to_inscription_entry =
+        wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+      let mut sequence_number_to_recursive_endpoints =
+        wtx.open_table(SEQUENCE_NUMBER_TO_RECURSIVE_ENDPOINTS)?;
+      let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+      let mut sequence_number_to_transfers = wtx.open_table(SEQUENCE_NUMBER_TO_TRANSFERS)?;
+      let mut teleburn_claim_to_inscription_id =
+        wtx.open_multimap_table(TELEBURN_CLAIM_TO_INSCRIPTION_ID)?;
+
+      let mut collections = Collections {
+        child_sequence_number_to_owner: &mut child_sequence_number_to_owner,
+        collection_holder_to_child_count: &mut collection_holder_to_child_count,
+        sequence_number_to_collection: &mut sequence_number_to_collection,
+      };
 
-    Ok(entries)
-  }
+      let mut next_height = height_to_block_header
+        .range(0..)?
+        .next_back()
+        .transpose()?
+        .map(|(height, _header)| height.value() + 1)
+        .unwrap_or(0);
 
-  pub(crate) fn get_rune_balance(&self, outpoint: OutPoint, id: RuneId) -> Result<u128> {
-    let rtx = self.database.begin_read()?;
+      let mut next_sequence_number = sequence_number_to_inscription_entry
+        .iter()?
+        .next_back()
+        .transpose()?
+        .map(|(sequence_number, _entry)| sequence_number.value() + 1)
+        .unwrap_or(0);
 
-    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+      while let Some(delta) = BlockDelta::decode(reader)? {
+        ensure!(
+          delta.height == next_height,
+          "index delta for block {} does not follow index height {next_height}",
+          delta.height,
+        );
+
+        if let Some(previous) = next_height.checked_sub(1) {
+          let previous_blockhash = Header::load(
+            *height_to_block_header
+              .get(previous)?
+              .ok_or_else(|| anyhow!("missing header for block {previous}"))?
+              .value(),
+          )
+          .block_hash();
 
-    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
-      return Ok(0);
-    };
+          ensure!(
+            Header::load(delta.header).prev_blockhash == previous_blockhash,
+            "index delta for block {} does not extend indexed chain",
+            delta.height,
+          );
+        }
 
-    let balances_buffer = balances.value();
+        for inscription in delta.inscriptions {
+          let entry = InscriptionEntry::load(inscription.entry);
+          let satpoint = inscription.satpoint;
 
-    let mut i = 0;
-    while i < balances_buffer.len() {
-      let (balance_id, length) = runes::varint::decode(&balances_buffer[i..]);
-      i += length;
-      let (amount, length) = runes::varint::decode(&balances_buffer[i..]);
-      i += length;
+          ensure!(
+            entry.sequence_number == next_sequence_number,
+            "index delta inscription {} has sequence number {}, expected {next_sequence_number}",
+            entry.id,
+            entry.sequence_number,
+          );
 
-      if RuneId::try_from(balance_id).unwrap() == id {
-        return Ok(amount);
-      }
-    }
+          next_sequence_number += 1;
 
-    Ok(0)
-  }
+          if entry.inscription_number < 0 {
+            cursed += 1;
+          } else {
+            blessed += 1;
+          }
 
-  pub(crate) fn get_rune_balances_for_outpoint(
-    &self,
-    outpoint: OutPoint,
-  ) -> Result<Vec<(SpacedRune, Pile)>> {
-    let rtx = self.database.begin_read()?;
+          let location = SatPoint::load(satpoint);
 
-    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+          if location.outpoint == unbound_outpoint() {
+            unbound = unbound.max(location.offset + 1);
+          }
 
-    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+          inscription_id_to_sequence_number.insert(&entry.id.store(), entry.sequence_number)?;
+          inscription_number_to_sequence_number
+            .insert(entry.inscription_number, entry.sequence_number)?;
 
-    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
-      return Ok(Vec::new());
-    };
+          if let Some(Sat(n)) = entry.sat {
+            sat_to_sequence_number.insert(&n, &entry.sequence_number)?;
+          }
 
-    let balances_buffer = balances.value();
+          if let Some(parent) = entry.parent {
+            sequence_number_to_children.insert(parent, entry.sequence_number)?;
 
-    let mut balances = Vec::new();
-    let mut i = 0;
-    while i < balances_buffer.len() {
-      let (id, length) = runes::varint::decode(&balances_buffer[i..]);
-      i += length;
-      let (amount, length) = runes::varint::decode(&balances_buffer[i..]);
-      i += length;
+            collections.update(
+              delta.height,
+              parent,
+              entry.sequence_number,
+              inscription.owner.as_deref().map(Script::from_bytes),
+              true,
+            )?;
+          }
 
-      let id = RuneId::try_from(id).unwrap();
+          if inscription.recursive_endpoints != 0 {
+            sequence_number_to_recursive_endpoints
+              .insert(entry.sequence_number, inscription.recursive_endpoints)?;
+          }
 
-      let entry = RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value());
+          for claim in &inscription.teleburn_claims {
+            teleburn_claim_to_inscription_id.insert(claim.as_str(), &entry.id.store())?;
+          }
 
-      balances.push((
-        entry.spaced_rune(),
-        Pile {
-          amount,
-          divisibility: entry.divisibility,
-          symbol: entry.symbol,
-        },
-      ));
-    }
+          home_inscriptions.insert(&entry.sequence_number, entry.id.store())?;
 
-    Ok(balances)
-  }
+          if home_inscriptions.len()? > 100 {
+            home_inscriptions.pop_first()?;
+          }
 
-  pub(crate) fn get_runic_outputs(&self, outpoints: &[OutPoint]) -> Result<BTreeSet<OutPoint>> {
-    let rtx = self.database.begin_read()?;
+          if let Some(owner) = &inscription.owner {
+            satpoint_to_owner.insert(&satpoint, owner.as_slice())?;
+          }
 
-    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+          height_to_inscription_moves.insert((delta.height, entry.sequence_number), &satpoint)?;
+          satpoint_to_sequence_number.insert(&satpoint, entry.sequence_number)?;
+          sequence_number_to_satpoint.insert(entry.sequence_number, &satpoint)?;
 
-    let mut runic = BTreeSet::new();
+          sequence_number_to_inscription_entry.insert(entry.sequence_number, &entry.store())?;
+        }
 
-    for outpoint in outpoints {
-      if outpoint_to_balances.get(&outpoint.store())?.is_some() {
-        runic.insert(*outpoint);
-      }
-    }
+        for MoveDelta {
+          owner,
+          satpoint,
+          sequence_number,
+        } in delta.moves
+        {
+          let old_satpoint = sequence_number_to_satpoint
+            .get(sequence_number)?
+            .map(|satpoint| *satpoint.value())
+            .ok_or_else(|| {
+              anyhow!("index delta moves unknown sequence number {sequence_number}")
+            })?;
+
+          let parent = sequence_number_to_inscription_entry
+            .get(sequence_number)?
+            .map(|entry| InscriptionEntry::load(entry.value()).parent)
+            .ok_or_else(|| {
+              anyhow!("index delta moves unknown sequence number {sequence_number}")
+            })?;
+
+          if let Some(parent) = parent {
+            collections.update(
+              delta.height,
+              parent,
+              sequence_number,
+              owner.as_deref().map(Script::from_bytes),
+              false,
+            )?;
+          }
 
-    Ok(runic)
-  }
+          satpoint_to_owner.remove(&old_satpoint)?;
 
-  pub(crate) fn get_rune_balance_map(&self) -> Result<BTreeMap<Rune, BTreeMap<OutPoint, u128>>> {
-    let outpoint_balances = self.get_rune_balances()?;
+          if let Some(owner) = &owner {
+            satpoint_to_owner.insert(&satpoint, owner.as_slice())?;
+          }
 
-    let rtx = self.database.begin_read()?;
+          height_to_inscription_moves.insert((delta.height, sequence_number), &satpoint)?;
+          satpoint_to_sequence_number.remove(&old_satpoint, sequence_number)?;
+          satpoint_to_sequence_number.insert(&satpoint, sequence_number)?;
+          sequence_number_to_satpoint.insert(sequence_number, &satpoint)?;
+        }
 
-    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+        for (sequence_number, transfer) in delta.transfers {
+          let next = sequence_number_to_transfers
+            .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+            .next_back()
+            .transpose()?
+            .map(|(key, _transfer)| key.value().1 + 1)
+            .unwrap_or_default();
 
-    let mut rune_balances: BTreeMap<Rune, BTreeMap<OutPoint, u128>> = BTreeMap::new();
+          sequence_number_to_transfers.insert((sequence_number, next), &transfer)?;
+        }
 
-    for (outpoint, balances) in outpoint_balances {
-      for (rune_id, amount) in balances {
-        let rune = RuneEntry::load(
-          rune_id_to_rune_entry
-            .get(&rune_id.store())?
-            .unwrap()
-            .value(),
-        )
-        .rune;
+        if let Some(fees) = delta.fees {
+          height_to_inscription_fees.insert(&delta.height, &fees)?;
+        }
 
-        *rune_balances
-          .entry(rune)
-          .or_default()
-          .entry(outpoint)
-          .or_default() += amount;
+        height_to_last_sequence_number.insert(&delta.height, next_sequence_number)?;
+        height_to_block_header.insert(&delta.height, &delta.header)?;
+
+        next_height += 1;
+        blocks += 1;
       }
     }
 
-    Ok(rune_balances)
-  }
+    {
+      let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+
+      // unbound inscriptions are assigned offsets on the unbound outpoint in
+      // order, so later ones must not reuse the offsets of imported ones
+      let unbound_inscriptions = statistic_to_count
+        .get(&Statistic::UnboundInscriptions.key())?
+        .map(|count| count.value())
+        .unwrap_or_default();
+
+      if unbound > unbound_inscriptions {
+        Index::set_statistic(
+          &mut statistic_to_count,
+          Statistic::UnboundInscriptions,
+          unbound,
+        )?;
+      }
+    }
 
-  pub(crate) fn get_rune_balances(&self) -> Result<Vec<(OutPoint, Vec<(RuneId, u128)>)>> {
-    let mut result = Vec::new();
+    Index::increment_statistic(&wtx, Statistic::BlessedInscriptions, blessed)?;
+    Index::increment_statistic(&wtx, Statistic::CursedInscriptions, cursed)?;
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
 
-    for entry in self
-      .database
-      .begin_read()?
-      .open_table(OUTPOINT_TO_RUNE_BALANCES)?
-      .iter()?
-    {
-      let (outpoint, balances_buffer) = entry?;
-      let outpoint = OutPoint::load(*outpoint.value());
-      let balances_buffer = balances_buffer.value();
+    wtx.commit()?;
 
-      let mut balances = Vec::new();
-      let mut i = 0;
-      while i < balances_buffer.len() {
-        let (id, length) = runes::varint::decode(&balances_buffer[i..]);
-        i += length;
-        let (balance, length) = runes::varint::decode(&balances_buffer[i..]);
-        i += length;
-        balances.push((RuneId::try_from(id)?, balance));
-      }
+    Ok(blocks)
+  }
+
+  /// Rewrite the database to reclaim unused space, returning its size before
+  /// and after, and whether any space was reclaimed. Compaction is not
+  /// possible while savepoints exist, so they are deleted first and will be
+  /// recreated when the index next updates near the chain tip.
+  pub(crate) fn compact(&mut self) -> Result<(u64, u64, bool)> {
+    let before = fs::metadata(&self.path)?.len();
+
+    let wtx = self.begin_write()?;
 
-      result.push((outpoint, balances));
+    for savepoint in wtx.list_persistent_savepoints()?.collect::<Vec<u64>>() {
+      log::info!("deleting savepoint {savepoint} before compaction");
+      wtx.delete_persistent_savepoint(savepoint)?;
     }
 
-    Ok(result)
-  }
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
 
-  pub(crate) fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
-    self.client.get_block_header(&hash).into_option()
-  }
+    let compacted = self.database.compact()?;
 
-  pub(crate) fn block_header_info(&self, hash: BlockHash) -> Result<Option<GetBlockHeaderResult>> {
-    self.client.get_block_header_info(&hash).into_option()
+    Ok((before, fs::metadata(&self.path)?.len(), compacted))
   }
 
-  pub(crate) fn get_block_by_height(&self, height: u32) -> Result<Option<Block>> {
-    Ok(
-      self
-        .client
-        .get_block_hash(height.into())
-        .into_option()?
-        .map(|hash| self.client.get_block(&hash))
-        .transpose()?,
-    )
-  }
+  /// Check that every rune balance is of an etched rune, and that the
+  /// balances of each rune sum to its supply minus the amount burned.
+  pub(crate) fn check_rune_balances(
+    runes: &BTreeMap<RuneId, RuneEntry>,
+    balances: &[(OutPoint, Vec<(RuneId, u128)>)],
+  ) -> Vec<String> {
+    let mut issues = Vec::new();
 
-  pub(crate) fn get_block_by_hash(&self, hash: BlockHash) -> Result<Option<Block>> {
-    self.client.get_block(&hash).into_option()
-  }
+    let mut outstanding: BTreeMap<RuneId, u128> = BTreeMap::new();
 
-  pub(crate) fn get_collections_paginated(
-    &self,
-    page_size: usize,
-    page_index: usize,
-  ) -> Result<(Vec<InscriptionId>, bool)> {
-    let rtx = self.database.begin_read()?;
+    for (outpoint, balances) in balances {
+      for (id, balance) in balances {
+        if !runes.contains_key(id) {
+          issues.push(format!(
+            "output {outpoint} holds balance of unknown rune {id}"
+          ));
+          continue;
+        }
 
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+        let total = outstanding.entry(*id).or_default();
 
-    let mut collections = rtx
-      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
-      .iter()?
-      .skip(page_index.saturating_mul(page_size))
-      .take(page_size.saturating_add(1))
-      .map(|result| {
-        result
-          .and_then(|(parent, _children)| {
-            sequence_number_to_inscription_entry
-              .get(parent.value())
-              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          })
-          .map_err(|err| err.into())
-      })
-      .collect::<Result<Vec<InscriptionId>>>()?;
+        match total.checked_add(*balance) {
+          Some(sum) => *total = sum,
+          None => issues.push(format!("balances of rune {id} overflow")),
+        }
+      }
+    }
 
-    let more = collections.len() > page_size;
+    for (id, entry) in runes {
+      let rune = entry.spaced_rune();
+      let outstanding = outstanding.get(id).copied().unwrap_or_default();
 
-    if more {
-      collections.pop();
+      match entry.supply.checked_sub(entry.burned) {
+        Some(expected) if expected != outstanding => issues.push(format!(
+          "rune {rune} balances sum to {outstanding} but supply minus burned is {expected}"
+        )),
+        Some(_) => {}
+        None => issues.push(format!(
+          "rune {rune} burned {} exceeds supply {}",
+          entry.burned, entry.supply
+        )),
+      }
     }
 
-    Ok((collections, more))
+    issues
   }
 
-  #[cfg(test)]
-  pub(crate) fn get_children_by_inscription_id(
-    &self,
-    inscription_id: InscriptionId,
-  ) -> Result<Vec<InscriptionId>> {
-    let rtx = self.database.begin_read()?;
+  /// Check that index tables are consistent with each other, returning a
+  /// description of each inconsistency found and how many were repaired. Only
+  /// lookup tables that can be rebuilt from inscription and rune entries are
+  /// repaired, and only if `repair` is true.
+  pub(crate) fn verify(&self, repair: bool) -> Result<(Vec<String>, u64)> {
+    let wtx = self.begin_write()?;
 
-    let Some(sequence_number) = rtx
-      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
-      .get(&inscription_id.store())?
-      .map(|sequence_number| sequence_number.value())
-    else {
-      return Ok(Vec::new());
-    };
+    let mut issues = Vec::new();
+    let mut repaired = 0;
 
-    self
-      .get_children_by_sequence_number_paginated(sequence_number, usize::max_value(), 0)
-      .map(|(children, _more)| children)
-  }
+    {
+      let mut inscription_id_to_sequence_number =
+        wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+      let mut inscription_number_to_sequence_number =
+        wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+      let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+      let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+      let sequence_number_to_inscription_entry =
+        wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+      let sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+      for result in sequence_number_to_inscription_entry.iter()? {
+        let (key, entry) = result?;
+        let sequence_number = key.value();
+        let entry = InscriptionEntry::load(entry.value());
+        let id = entry.id;
+
+        if entry.sequence_number != sequence_number {
+          issues.push(format!(
+            "inscription {id} stored under sequence number {sequence_number} has sequence number {}",
+            entry.sequence_number
+          ));
+        }
 
-  #[cfg(test)]
-  pub(crate) fn get_parent_by_inscription_id(
-    &self,
-    inscription_id: InscriptionId,
-  ) -> InscriptionId {
-    let rtx = self.database.begin_read().unwrap();
+        match sequence_number_to_satpoint
+          .get(sequence_number)?
+          .map(|satpoint| *satpoint.value())
+        {
+          Some(satpoint) => {
+            let indexed = satpoint_to_sequence_number.get(&satpoint)?.any(|result| {
+              result
+                .map(|s| s.value() == sequence_number)
+                .unwrap_or_default()
+            });
+
+            if !indexed {
+              issues.push(format!(
+                "inscription {id} missing from satpoint {}",
+                SatPoint::load(satpoint)
+              ));
+              if repair {
+                satpoint_to_sequence_number.insert(&satpoint, sequence_number)?;
+                repaired += 1;
+              }
+            }
+          }
+          None => issues.push(format!("inscription {id} has no satpoint")),
+        }
 
-    let sequence_number = rtx
-      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
-      .unwrap()
-      .get(&inscription_id.store())
-      .unwrap()
-      .unwrap()
-      .value();
+        if inscription_id_to_sequence_number
+          .get(&id.store())?
+          .map(|s| s.value())
+          != Some(sequence_number)
+        {
+          issues.push(format!("inscription {id} missing from id lookup table"));
+          if repair {
+            inscription_id_to_sequence_number.insert(&id.store(), sequence_number)?;
+            repaired += 1;
+          }
+        }
 
-    let sequence_number_to_inscription_entry = rtx
-      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)
-      .unwrap();
+        if inscription_number_to_sequence_number
+          .get(entry.inscription_number)?
+          .map(|s| s.value())
+          != Some(sequence_number)
+        {
+          issues.push(format!("inscription {id} missing from number lookup table"));
+          if repair {
+            inscription_number_to_sequence_number
+              .insert(entry.inscription_number, sequence_number)?;
+            repaired += 1;
+          }
+        }
 
-    let parent_sequence_number = InscriptionEntry::load(
-      sequence_number_to_inscription_entry
-        .get(sequence_number)
-        .unwrap()
-        .unwrap()
-        .value(),
-    )
-    .parent
-    .unwrap();
+        if let Some(parent) = entry.parent {
+          if sequence_number_to_inscription_entry.get(parent)?.is_none() {
+            issues.push(format!(
+              "inscription {id} has parent with unknown sequence number {parent}"
+            ));
+          } else if !sequence_number_to_children.get(parent)?.any(|result| {
+            result
+              .map(|s| s.value() == sequence_number)
+              .unwrap_or_default()
+          }) {
+            issues.push(format!("inscription {id} missing from parent's children"));
+            if repair {
+              sequence_number_to_children.insert(parent, sequence_number)?;
+              repaired += 1;
+            }
+          }
+        }
+      }
+    }
 
-    let entry = InscriptionEntry::load(
-      sequence_number_to_inscription_entry
-        .get(parent_sequence_number)
-        .unwrap()
-        .unwrap()
-        .value(),
-    );
+    {
+      let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
+
+      for result in wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.iter()? {
+        let (id, entry) = result?;
+        let id = id.value();
+        let rune = RuneEntry::load(entry.value()).rune;
+
+        if rune_to_rune_id.get(rune.0)?.map(|id| id.value()) != Some(id) {
+          issues.push(format!("rune {rune} missing from rune lookup table"));
+          if repair {
+            rune_to_rune_id.insert(rune.0, id)?;
+            repaired += 1;
+          }
+        }
+      }
+    }
 
-    entry.id
-  }
+    {
+      let mut runes = BTreeMap::new();
 
-  pub(crate) fn get_children_by_sequence_number_paginated(
-    &self,
-    sequence_number: u32,
-    page_size: usize,
-    page_index: usize,
-  ) -> Result<(Vec<InscriptionId>, bool)> {
-    let rtx = self.database.begin_read()?;
+      for result in wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.iter()? {
+        let (id, entry) = result?;
+        runes.insert(RuneId::load(id.value()), RuneEntry::load(entry.value()));
+      }
 
-    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+      let mut balances = Vec::new();
 
-    let mut children = rtx
-      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
-      .get(sequence_number)?
-      .skip(page_index * page_size)
-      .take(page_size.saturating_add(1))
-      .map(|result| {
-        result
-          .and_then(|sequence_number| {
-            sequence_number_to_entry
-              .get(sequence_number.value())
-              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          })
-          .map_err(|err| err.into())
-      })
-      .collect::<Result<Vec<InscriptionId>>>()?;
+      for result in wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?.iter()? {
+        let (outpoint, buffer) = result?;
+        let outpoint = OutPoint::load(*outpoint.value());
 
-    let more = children.len() > page_size;
+        match load_rune_balances(buffer.value()) {
+          Ok(outpoint_balances) => balances.push((outpoint, outpoint_balances)),
+          Err(err) => issues.push(format!(
+            "output {outpoint} has malformed rune balances: {err}"
+          )),
+        }
+      }
 
-    if more {
-      children.pop();
+      issues.extend(Self::check_rune_balances(&runes, &balances));
     }
 
-    Ok((children, more))
+    for result in wtx.open_table(OUTPOINT_TO_SAT_RANGES)?.iter()? {
+      let (outpoint, sat_ranges) = result?;
+
+      if sat_ranges.value().len() % 11 != 0 {
+        issues.push(format!(
+          "output {} has malformed sat ranges",
+          OutPoint::load(*outpoint.value())
+        ));
+      }
+    }
+
+    let mut prev_blockhash = None;
+
+    for (expected, result) in (0..).zip(wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?.iter()?) {
+      let (height, header) = result?;
+      let height = height.value();
+      let header = Header::load(*header.value());
+
+      if height != expected {
+        issues.push(format!("block headers missing at height {expected}"));
+        break;
+      }
+
+      if let Some(prev_blockhash) = prev_blockhash {
+        if header.prev_blockhash != prev_blockhash {
+          issues.push(format!(
+            "block header at height {height} does not follow previous block header"
+          ));
+        }
+      }
+
+      prev_blockhash = Some(header.block_hash());
+    }
+
+    if repaired > 0 {
+      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+      wtx.commit()?;
+    }
+
+    Ok((issues, repaired))
   }
 
-  pub(crate) fn get_etching(&self, txid: Txid) -> Result<Option<SpacedRune>> {
-    let rtx = self.database.begin_read()?;
+  fn begin_read(&self) -> Result<rtx::Rtx> {
+    Ok(rtx::Rtx(self.database.begin_read()?))
+  }
 
-    let transaction_id_to_rune = rtx.open_table(TRANSACTION_ID_TO_RUNE)?;
-    let Some(rune) = transaction_id_to_rune.get(&txid.store())? else {
-      return Ok(None);
-    };
+  fn begin_write(&self) -> Result<WriteTransaction> {
+    let mut tx = self.database.begin_write()?;
+    tx.set_durability(self.durability);
+    Ok(tx)
+  }
 
-    let rune_to_rune_id = rtx.open_table(RUNE_TO_RUNE_ID)?;
-    let id = rune_to_rune_id.get(rune.value())?.unwrap();
+  fn increment_statistic(wtx: &WriteTransaction, statistic: Statistic, n: u64) -> Result {
+    let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+    let value = statistic_to_count
+      .get(&(statistic.key()))?
+      .map(|x| x.value())
+      .unwrap_or_default()
+      + n;
+    statistic_to_count.insert(&statistic.key(), &value)?;
+    Ok(())
+  }
 
-    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-    let entry = rune_id_to_rune_entry.get(&id.value())?.unwrap();
+  pub(crate) fn set_statistic(
+    statistics: &mut Table<u64, u64>,
+    statistic: Statistic,
+    value: u64,
+  ) -> Result<()> {
+    statistics.insert(&statistic.key(), &value)?;
+    Ok(())
+  }
 
-    Ok(Some(RuneEntry::load(entry.value()).spaced_rune()))
+  pub(crate) fn is_statistic_set(
+    statistics: &ReadOnlyTable<u64, u64>,
+    statistic: Statistic,
+  ) -> Result<bool> {
+    Ok(
+      statistics
+        .get(&statistic.key())?
+        .map(|guard| guard.value())
+        .unwrap_or_default()
+        != 0,
+    )
   }
 
-  pub(crate) fn get_inscription_ids_by_sat(&self, sat: Sat) -> Result<Vec<InscriptionId>> {
-    let rtx = self.database.begin_read()?;
+  pub(crate) fn statistic(&self, statistic: Statistic) -> u64 {
+    self
+      .database
+      .begin_read()
+      .unwrap()
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .get(&statistic.key())
+      .unwrap()
+      .map(|x| x.value())
+      .unwrap_or_default()
+  }
 
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+  pub(crate) fn block_count(&self) -> Result<u32> {
+    self.begin_read()?.block_count()
+  }
 
-    let ids = rtx
-      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
-      .get(&sat.n())?
-      .map(|result| {
-        result
-          .and_then(|sequence_number| {
-            let sequence_number = sequence_number.value();
-            sequence_number_to_inscription_entry
-              .get(sequence_number)
-              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          })
-          .map_err(|err| err.into())
-      })
-      .collect::<Result<Vec<InscriptionId>>>()?;
+  pub(crate) fn block_height(&self) -> Result<Option<Height>> {
+    self.begin_read()?.block_height()
+  }
 
-    Ok(ids)
+  pub(crate) fn block_hash(&self, height: Option<u32>) -> Result<Option<BlockHash>> {
+    self.begin_read()?.block_hash(height)
   }
 
-  pub(crate) fn get_inscription_ids_by_sat_paginated(
-    &self,
-    sat: Sat,
-    page_size: u64,
-    page_index: u64,
-  ) -> Result<(Vec<InscriptionId>, bool)> {
-    let rtx = self.database.begin_read()?;
+  pub(crate) fn blocks(&self, take: usize) -> Result<Vec<(u32, BlockHash)>> {
+    let rtx = self.begin_read()?;
 
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let block_count = rtx.block_count()?;
 
-    let mut ids = rtx
-      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
-      .get(&sat.n())?
-      .map(|result| {
-        result
-          .and_then(|sequence_number| {
-            let sequence_number = sequence_number.value();
-            sequence_number_to_inscription_entry
-              .get(sequence_number)
-              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          })
-          .map_err(|err| err.into())
-      })
-      .skip(page_index.saturating_mul(page_size).try_into().unwrap())
-      .take(page_size.saturating_add(1).try_into().unwrap())
-      .collect::<Result<Vec<InscriptionId>>>()?;
+    let height_to_block_header = rtx.0.open_table(HEIGHT_TO_BLOCK_HEADER)?;
 
-    let more = ids.len() > page_size.try_into().unwrap();
+    let mut blocks = Vec::with_capacity(block_count.try_into().unwrap());
 
-    if more {
-      ids.pop();
+    for next in height_to_block_header
+      .range(0..block_count)?
+      .rev()
+      .take(take)
+    {
+      let next = next?;
+      blocks.push((next.0.value(), Header::load(*next.1.value()).block_hash()));
     }
 
-    Ok((ids, more))
+    Ok(blocks)
   }
 
-  pub(crate) fn get_inscription_id_by_sat_indexed(
-    &self,
-    sat: Sat,
-    inscription_index: isize,
-  ) -> Result<Option<InscriptionId>> {
+  pub(crate) fn rare_sat_satpoints(&self) -> Result<Vec<(Sat, SatPoint)>> {
     let rtx = self.database.begin_read()?;
 
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sat_to_satpoint = rtx.open_table(SAT_TO_SATPOINT)?;
 
-    let sat_to_sequence_number = rtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+    let mut result = Vec::with_capacity(sat_to_satpoint.len()?.try_into().unwrap());
 
-    if inscription_index < 0 {
-      sat_to_sequence_number
-        .get(&sat.n())?
-        .nth_back((inscription_index + 1).abs_diff(0))
-    } else {
-      sat_to_sequence_number
-        .get(&sat.n())?
-        .nth(inscription_index.abs_diff(0))
+    for range in sat_to_satpoint.range(0..)? {
+      let (sat, satpoint) = range?;
+      result.push((Sat(sat.value()), Entry::load(*satpoint.value())));
     }
-    .map(|result| {
-      result
-        .and_then(|sequence_number| {
-          let sequence_number = sequence_number.value();
-          sequence_number_to_inscription_entry
-            .get(sequence_number)
-            .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-        })
-        .map_err(|err| anyhow!(err.to_string()))
-    })
-    .transpose()
+
+    Ok(result)
   }
 
-  #[cfg(test)]
-  pub(crate) fn get_inscription_id_by_inscription_number(
-    &self,
-    inscription_number: i32,
-  ) -> Result<Option<InscriptionId>> {
+  pub(crate) fn rare_sat_satpoint(&self, sat: Sat) -> Result<Option<SatPoint>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SAT_TO_SATPOINT)?
+        .get(&sat.n())?
+        .map(|satpoint| Entry::load(*satpoint.value())),
+    )
+  }
+
+  pub(crate) fn get_rune_by_id(&self, id: RuneId) -> Result<Option<Rune>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+        .get(&id.store())?
+        .map(|entry| RuneEntry::load(entry.value()).rune),
+    )
+  }
+
+  pub(crate) fn rune(&self, rune: Rune) -> Result<Option<(RuneId, RuneEntry)>> {
     let rtx = self.database.begin_read()?;
 
-    let Some(sequence_number) = rtx
-      .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
-      .get(inscription_number)?
+    let Some(id) = rtx
+      .open_table(RUNE_TO_RUNE_ID)?
+      .get(rune.0)?
       .map(|guard| guard.value())
     else {
       return Ok(None);
     };
 
-    let inscription_id = rtx
-      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
-      .get(&sequence_number)?
-      .map(|entry| InscriptionEntry::load(entry.value()).id);
+    let entry = RuneEntry::load(
+      rtx
+        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+        .get(id)?
+        .unwrap()
+        .value(),
+    );
 
-    Ok(inscription_id)
+    Ok(Some((RuneId::load(id), entry)))
   }
 
-  pub(crate) fn get_inscription_satpoint_by_id(
-    &self,
-    inscription_id: InscriptionId,
-  ) -> Result<Option<SatPoint>> {
+  pub(crate) fn rune_html(&self, rune: Rune) -> Result<Option<RuneHtml>> {
     let rtx = self.database.begin_read()?;
 
-    let Some(sequence_number) = rtx
-      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
-      .get(&inscription_id.store())?
+    let Some(id) = rtx
+      .open_table(RUNE_TO_RUNE_ID)?
+      .get(rune.0)?
       .map(|guard| guard.value())
     else {
       return Ok(None);
     };
 
-    let satpoint = rtx
-      .open_table(SEQUENCE_NUMBER_TO_SATPOINT)?
-      .get(sequence_number)?
-      .map(|satpoint| Entry::load(*satpoint.value()));
+    let entry = RuneEntry::load(
+      rtx
+        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+        .get(id)?
+        .unwrap()
+        .value(),
+    );
 
-    Ok(satpoint)
+    let parent = InscriptionId {
+      txid: entry.etching,
+      index: 0,
+    };
+
+    let parent = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&parent.store())?
+      .is_some()
+      .then_some(parent);
+
+    let info = match parent {
+      Some(parent) => self
+        .get_inscription_by_id(parent)?
+        .and_then(|inscription| RuneInfo::from_inscription(&inscription)),
+      None => None,
+    };
+
+    Ok(Some(RuneHtml {
+      entry,
+      id: RuneId::load(id),
+      info,
+      parent,
+    }))
   }
 
-  pub(crate) fn get_inscription_by_id(
+  /// Inscription fee statistics of the blocks in `from..=to` that created or
+  /// transferred inscriptions, in height order.
+  pub(crate) fn get_inscription_fees(
     &self,
-    inscription_id: InscriptionId,
-  ) -> Result<Option<Inscription>> {
-    if !self.inscription_exists(inscription_id)? {
+    from: u32,
+    to: u32,
+  ) -> Result<Vec<(u32, InscriptionFeesEntry)>> {
+    self
+      .database
+      .begin_read()?
+      .open_table(HEIGHT_TO_INSCRIPTION_FEES)?
+      .range(from..=to)?
+      .map(|result| {
+        let (height, fees) = result?;
+        Ok((height.value(), InscriptionFeesEntry::load(*fees.value())))
+      })
+      .collect()
+  }
+
+  /// Net changes to the address index made by the block at `height`, or
+  /// `None` if that block hasn't been indexed. Requires `--index-addresses`.
+  pub(crate) fn get_address_changes(&self, height: u32) -> Result<Option<Vec<AddressChange>>> {
+    let rtx = self.database.begin_read()?;
+
+    if rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .get(height)?
+      .is_none()
+    {
       return Ok(None);
     }
 
-    Ok(self.get_transaction(inscription_id.txid)?.and_then(|tx| {
-      ParsedEnvelope::from_transaction(&tx)
-        .into_iter()
-        .nth(inscription_id.index as usize)
-        .map(|envelope| envelope.payload)
-    }))
+    let changes = rtx
+      .open_multimap_table(HEIGHT_TO_ADDRESS_CHANGE)?
+      .get(height)?
+      .map(|result| Ok(AddressChange::load(result?.value().into())))
+      .collect::<Result<Vec<AddressChange>>>()?;
+
+    Ok(Some(changes))
   }
 
-  pub(crate) fn inscription_count(&self, txid: Txid) -> Result<u32> {
-    let start = InscriptionId { index: 0, txid };
+  /// Inscriptions created in blocks `from..to`, with their current location.
+  pub(crate) fn inscriptions_in_block_range(
+    &self,
+    from: u32,
+    to: u32,
+  ) -> Result<Vec<(InscriptionEntry, SatPoint)>> {
+    let rtx = self.database.begin_read()?;
 
-    let end = InscriptionId {
-      index: u32::MAX,
-      txid,
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+
+    let next_sequence_number = |height: u32| -> Result<u32> {
+      Ok(
+        height_to_last_sequence_number
+          .range(..height)?
+          .next_back()
+          .transpose()?
+          .map(|(_height, next)| next.value())
+          .unwrap_or(0),
+      )
     };
 
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
-        .range::<&InscriptionIdValue>(&start.store()..&end.store())?
-        .count()
-        .try_into()
-        .unwrap(),
-    )
+    let start = next_sequence_number(from)?;
+    let end = next_sequence_number(to)?;
+
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+    let mut inscriptions = Vec::new();
+
+    for result in rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .range(start..end)?
+    {
+      let (sequence_number, entry) = result?;
+
+      let satpoint = SatPoint::load(
+        *sequence_number_to_satpoint
+          .get(sequence_number.value())?
+          .unwrap()
+          .value(),
+      );
+
+      inscriptions.push((InscriptionEntry::load(entry.value()), satpoint));
+    }
+
+    Ok(inscriptions)
   }
 
-  pub(crate) fn inscription_exists(&self, inscription_id: InscriptionId) -> Result<bool> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
-        .get(&inscription_id.store())?
-        .is_some(),
-    )
+  pub(crate) fn runes(&self) -> Result<Vec<(RuneId, RuneEntry)>> {
+    let mut entries = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+      .iter()?
+    {
+      let (id, entry) = result?;
+      entries.push((RuneId::load(id.value()), RuneEntry::load(entry.value())));
+    }
+
+    Ok(entries)
   }
 
-  pub(crate) fn get_inscriptions_on_output_with_satpoints(
-    &self,
-    outpoint: OutPoint,
-  ) -> Result<Vec<(SatPoint, InscriptionId)>> {
+  pub(crate) fn get_rune_balance(&self, outpoint: OutPoint, id: RuneId) -> Result<u128> {
     let rtx = self.database.begin_read()?;
-    let satpoint_to_sequence_number = rtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    Self::inscriptions_on_output(
-      &satpoint_to_sequence_number,
-      &sequence_number_to_inscription_entry,
-      outpoint,
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+
+    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
+      return Ok(0);
+    };
+
+    Ok(
+      load_rune_balances(balances.value())?
+        .into_iter()
+        .find(|(balance_id, _)| *balance_id == id)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default(),
     )
   }
 
-  pub(crate) fn get_inscriptions_on_output(
+  pub(crate) fn get_rune_balances_for_outpoint(
     &self,
     outpoint: OutPoint,
-  ) -> Result<Vec<InscriptionId>> {
-    Ok(
-      self
-        .get_inscriptions_on_output_with_satpoints(outpoint)?
-        .iter()
-        .map(|(_satpoint, inscription_id)| *inscription_id)
-        .collect(),
-    )
-  }
+  ) -> Result<Vec<(SpacedRune, Pile)>> {
+    let rtx = self.database.begin_read()?;
 
-  pub(crate) fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
-    if txid == self.genesis_block_coinbase_txid {
-      return Ok(Some(self.genesis_block_coinbase_transaction.clone()));
-    }
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
 
-    if self.index_transactions {
-      if let Some(transaction) = self
-        .database
-        .begin_read()?
-        .open_table(TRANSACTION_ID_TO_TRANSACTION)?
-        .get(&txid.store())?
-      {
-        return Ok(Some(consensus::encode::deserialize(transaction.value())?));
-      }
-    }
+    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
 
-    self.client.get_raw_transaction(&txid, None).into_option()
-  }
+    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
+      return Ok(Vec::new());
+    };
 
-  pub(crate) fn get_transaction_blockhash(&self, txid: Txid) -> Result<Option<BlockHash>> {
-    Ok(
-      self
-        .client
-        .get_raw_transaction_info(&txid, None)
-        .into_option()?
-        .and_then(|info| {
-          if info.in_active_chain.unwrap_or_default() {
-            info.blockhash
-          } else {
-            None
-          }
-        }),
-    )
-  }
-
-  pub(crate) fn is_transaction_in_active_chain(&self, txid: Txid) -> Result<bool> {
-    Ok(
-      self
-        .client
-        .get_raw_transaction_info(&txid, None)
-        .into_option()?
-        .and_then(|info| info.in_active_chain)
-        .unwrap_or(false),
-    )
-  }
-
-  pub(crate) fn find(&self, sat: Sat) -> Result<Option<SatPoint>> {
-    let sat = sat.0;
-    let rtx = self.begin_read()?;
+    let mut piles = Vec::new();
+    for (id, amount) in load_rune_balances(balances.value())? {
+      let entry = RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value());
 
-    if rtx.block_count()? <= Sat(sat).height().n() {
-      return Ok(None);
+      piles.push((
+        entry.spaced_rune(),
+        Pile {
+          amount,
+          divisibility: entry.divisibility,
+          symbol: entry.symbol,
+        },
+      ));
     }
 
-    let outpoint_to_sat_ranges = rtx.0.open_table(OUTPOINT_TO_SAT_RANGES)?;
-
-    for range in outpoint_to_sat_ranges.range::<&[u8; 36]>(&[0; 36]..)? {
-      let (key, value) = range?;
-      let mut offset = 0;
-      for chunk in value.value().chunks_exact(11) {
-        let (start, end) = SatRange::load(chunk.try_into().unwrap());
-        if start <= sat && sat < end {
-          return Ok(Some(SatPoint {
-            outpoint: Entry::load(*key.value()),
-            offset: offset + sat - start,
-          }));
-        }
-        offset += end - start;
-      }
-    }
+    Ok(piles)
+  }
 
-    Ok(None)
+  /// Unspent outputs paying `script_pubkey`. Requires `--index-addresses`.
+  pub(crate) fn get_address_outputs(&self, script_pubkey: &Script) -> Result<Vec<OutPoint>> {
+    self
+      .database
+      .begin_read()?
+      .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?
+      .get(script_pubkey.as_bytes())?
+      .map(|result| Ok(OutPoint::load(*result?.value())))
+      .collect()
   }
 
-  pub(crate) fn find_range(
+  /// Balances of the runes held by the unspent outputs paying
+  /// `script_pubkey`, in rune ID order, along with the outpoints holding them.
+  /// Requires `--index-addresses`.
+  pub(crate) fn get_address_rune_balances(
     &self,
-    range_start: Sat,
-    range_end: Sat,
-  ) -> Result<Option<Vec<FindRangeOutput>>> {
-    let range_start = range_start.0;
-    let range_end = range_end.0;
-    let rtx = self.begin_read()?;
-
-    if rtx.block_count()? < Sat(range_end - 1).height().n() + 1 {
-      return Ok(None);
-    }
-
-    let Some(mut remaining_sats) = range_end.checked_sub(range_start) else {
-      return Err(anyhow!("range end is before range start"));
-    };
-
-    let outpoint_to_sat_ranges = rtx.0.open_table(OUTPOINT_TO_SAT_RANGES)?;
-
-    let mut result = Vec::new();
-    for range in outpoint_to_sat_ranges.range::<&[u8; 36]>(&[0; 36]..)? {
-      let (outpoint_entry, sat_ranges_entry) = range?;
+    script_pubkey: &Script,
+  ) -> Result<Vec<(RuneId, RuneEntry, Vec<(OutPoint, u128)>)>> {
+    let rtx = self.database.begin_read()?;
 
-      let mut offset = 0;
-      for sat_range in sat_ranges_entry.value().chunks_exact(11) {
-        let (start, end) = SatRange::load(sat_range.try_into().unwrap());
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
 
-        if end > range_start && start < range_end {
-          let overlap_start = start.max(range_start);
-          let overlap_end = end.min(range_end);
+    let mut balances = BTreeMap::<RuneId, Vec<(OutPoint, u128)>>::new();
 
-          result.push(FindRangeOutput {
-            start: overlap_start,
-            size: overlap_end - overlap_start,
-            satpoint: SatPoint {
-              outpoint: Entry::load(*outpoint_entry.value()),
-              offset: offset + overlap_start - start,
-            },
-          });
+    for result in rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?
+      .get(script_pubkey.as_bytes())?
+    {
+      let outpoint = OutPoint::load(*result?.value());
 
-          remaining_sats -= overlap_end - overlap_start;
+      let Some(buffer) = outpoint_to_balances.get(&outpoint.store())? else {
+        continue;
+      };
 
-          if remaining_sats == 0 {
-            break;
-          }
-        }
-        offset += end - start;
+      for (id, amount) in load_rune_balances(buffer.value())? {
+        balances.entry(id).or_default().push((outpoint, amount));
       }
     }
 
-    Ok(Some(result))
-  }
+    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
 
-  fn list_inner(&self, outpoint: OutPointValue) -> Result<Option<Vec<u8>>> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(OUTPOINT_TO_SAT_RANGES)?
-        .get(&outpoint)?
-        .map(|outpoint| outpoint.value().to_vec()),
-    )
+    balances
+      .into_iter()
+      .map(|(id, outpoints)| {
+        Ok((
+          id,
+          RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value()),
+          outpoints,
+        ))
+      })
+      .collect()
   }
 
-  pub(crate) fn list(&self, outpoint: OutPoint) -> Result<Option<List>> {
-    if !self.index_sats || outpoint == unbound_outpoint() {
-      return Ok(None);
-    }
+  pub(crate) fn get_runic_outputs(&self, outpoints: &[OutPoint]) -> Result<BTreeSet<OutPoint>> {
+    let rtx = self.database.begin_read()?;
 
-    let array = outpoint.store();
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
 
-    let sat_ranges = self.list_inner(array)?;
+    let mut runic = BTreeSet::new();
 
-    match sat_ranges {
-      Some(sat_ranges) => Ok(Some(List::Unspent(
-        sat_ranges
-          .chunks_exact(11)
-          .map(|chunk| SatRange::load(chunk.try_into().unwrap()))
-          .collect(),
-      ))),
-      None => {
-        if self.is_transaction_in_active_chain(outpoint.txid)? {
-          Ok(Some(List::Spent))
-        } else {
-          Ok(None)
-        }
+    for outpoint in outpoints {
+      if outpoint_to_balances.get(&outpoint.store())?.is_some() {
+        runic.insert(*outpoint);
       }
     }
+
+    Ok(runic)
   }
 
-  pub(crate) fn block_time(&self, height: Height) -> Result<Blocktime> {
-    let height = height.n();
+  pub(crate) fn get_rune_balance_map(&self) -> Result<BTreeMap<Rune, BTreeMap<OutPoint, u128>>> {
+    let outpoint_balances = self.get_rune_balances()?;
 
     let rtx = self.database.begin_read()?;
 
-    let height_to_block_header = rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
 
-    if let Some(guard) = height_to_block_header.get(height)? {
-      return Ok(Blocktime::confirmed(Header::load(*guard.value()).time));
-    }
+    let mut rune_balances: BTreeMap<Rune, BTreeMap<OutPoint, u128>> = BTreeMap::new();
 
-    let current = height_to_block_header
-      .range(0..)?
-      .next_back()
-      .and_then(|result| result.ok())
-      .map(|(height, _header)| height)
-      .map(|x| x.value())
-      .unwrap_or(0);
+    for (outpoint, balances) in outpoint_balances {
+      for (rune_id, amount) in balances {
+        let rune = RuneEntry::load(
+          rune_id_to_rune_entry
+            .get(&rune_id.store())?
+            .unwrap()
+            .value(),
+        )
+        .rune;
 
-    let expected_blocks = height
-      .checked_sub(current)
-      .with_context(|| format!("current {current} height is greater than sat height {height}"))?;
+        *rune_balances
+          .entry(rune)
+          .or_default()
+          .entry(outpoint)
+          .or_default() += amount;
+      }
+    }
 
-    Ok(Blocktime::Expected(
-      Utc::now()
-        .round_subsecs(0)
-        .checked_add_signed(chrono::Duration::seconds(
-          10 * 60 * i64::from(expected_blocks),
-        ))
-        .ok_or_else(|| anyhow!("block timestamp out of range"))?,
-    ))
+    Ok(rune_balances)
   }
 
-  pub(crate) fn get_inscriptions(
-    &self,
-    utxos: &BTreeMap<OutPoint, Amount>,
-  ) -> Result<BTreeMap<SatPoint, InscriptionId>> {
-    let rtx = self.database.begin_read()?;
-
-    let mut result = BTreeMap::new();
+  pub(crate) fn get_rune_balances(&self) -> Result<Vec<(OutPoint, Vec<(RuneId, u128)>)>> {
+    let mut result = Vec::new();
 
-    let satpoint_to_sequence_number = rtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-    for utxo in utxos.keys() {
-      result.extend(Self::inscriptions_on_output(
-        &satpoint_to_sequence_number,
-        &sequence_number_to_inscription_entry,
-        *utxo,
-      )?);
+    for entry in self
+      .database
+      .begin_read()?
+      .open_table(OUTPOINT_TO_RUNE_BALANCES)?
+      .iter()?
+    {
+      let (outpoint, balances_buffer) = entry?;
+      let outpoint = OutPoint::load(*outpoint.value());
+      result.push((outpoint, load_rune_balances(balances_buffer.value())?));
     }
 
     Ok(result)
   }
 
-  pub(crate) fn get_inscriptions_paginated(
+  pub(crate) fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
+    self.client.get_block_header(&hash).into_option()
+  }
+
+  pub(crate) fn block_header_info(&self, hash: BlockHash) -> Result<Option<GetBlockHeaderResult>> {
+    self.client.get_block_header_info(&hash).into_option()
+  }
+
+  pub(crate) fn get_block_by_height(&self, height: u32) -> Result<Option<Block>> {
+    Ok(
+      self
+        .client
+        .get_block_hash(height.into())
+        .into_option()?
+        .map(|hash| self.client.get_block(&hash))
+        .transpose()?,
+    )
+  }
+
+  pub(crate) fn get_block_by_hash(&self, hash: BlockHash) -> Result<Option<Block>> {
+    self.client.get_block(&hash).into_option()
+  }
+
+  pub(crate) fn get_collections_paginated(
     &self,
     page_size: usize,
     page_index: usize,
+    deadline: Deadline,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut inscriptions = sequence_number_to_inscription_entry
+    let mut collections = Vec::new();
+
+    for result in rtx
+      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
       .iter()?
-      .rev()
-      .skip(page_size.saturating_mul(page_index))
+      .skip(page_index.saturating_mul(page_size))
       .take(page_size.saturating_add(1))
-      .flat_map(|result| result.map(|(_number, entry)| InscriptionEntry::load(entry.value()).id))
-      .collect::<Vec<InscriptionId>>();
+    {
+      deadline.check(collections.len())?;
 
-    let more = inscriptions.len() > page_size;
+      let (parent, _children) = result?;
 
-    if more {
-      inscriptions.pop();
+      collections.push(
+        InscriptionEntry::load(
+          sequence_number_to_inscription_entry
+            .get(parent.value())?
+            .unwrap()
+            .value(),
+        )
+        .id,
+      );
     }
 
-    Ok((inscriptions, more))
+    let more = collections.len() > page_size;
+
+    if more {
+      collections.pop();
+    }
+
+    Ok((collections, more))
   }
 
-  pub(crate) fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
+  #[cfg(test)]
+  pub(crate) fn get_children_by_inscription_id(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Vec<InscriptionId>> {
     let rtx = self.database.begin_read()?;
 
-    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-
-    let Some(newest_sequence_number) = height_to_last_sequence_number
-      .get(&block_height)?
-      .map(|ag| ag.value())
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|sequence_number| sequence_number.value())
     else {
       return Ok(Vec::new());
     };
 
-    let oldest_sequence_number = height_to_last_sequence_number
-      .get(block_height.saturating_sub(1))?
-      .map(|ag| ag.value())
-      .unwrap_or(0);
-
-    (oldest_sequence_number..newest_sequence_number)
-      .map(|num| match sequence_number_to_inscription_entry.get(&num) {
-        Ok(Some(inscription_id)) => Ok(InscriptionEntry::load(inscription_id.value()).id),
-        Ok(None) => Err(anyhow!(
-          "could not find inscription for inscription number {num}"
-        )),
-        Err(err) => Err(anyhow!(err)),
-      })
-      .collect::<Result<Vec<InscriptionId>>>()
+    self
+      .get_children_by_sequence_number_paginated(sequence_number, usize::max_value(), 0)
+      .map(|(children, _more)| children)
   }
 
-  pub(crate) fn get_highest_paying_inscriptions_in_block(
+  #[cfg(test)]
+  pub(crate) fn get_parent_by_inscription_id(
     &self,
-    block_height: u32,
-    n: usize,
-  ) -> Result<(Vec<InscriptionId>, usize)> {
-    let inscription_ids = self.get_inscriptions_in_block(block_height)?;
-
-    let mut inscription_to_fee: Vec<(InscriptionId, u64)> = Vec::new();
-    for id in &inscription_ids {
-      inscription_to_fee.push((
-        *id,
-        self
-          .get_inscription_entry(*id)?
-          .ok_or_else(|| anyhow!("could not get entry for inscription {id}"))?
-          .fee,
-      ));
-    }
-
-    inscription_to_fee.sort_by_key(|(_, fee)| *fee);
+    inscription_id: InscriptionId,
+  ) -> InscriptionId {
+    let rtx = self.database.begin_read().unwrap();
 
-    Ok((
-      inscription_to_fee
-        .iter()
-        .map(|(id, _)| *id)
-        .rev()
-        .take(n)
-        .collect(),
-      inscription_ids.len(),
-    ))
-  }
+    let sequence_number = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .get(&inscription_id.store())
+      .unwrap()
+      .unwrap()
+      .value();
 
-  pub(crate) fn get_home_inscriptions(&self) -> Result<Vec<InscriptionId>> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(HOME_INSCRIPTIONS)?
-        .iter()?
-        .rev()
-        .flat_map(|result| result.map(|(_number, id)| InscriptionId::load(id.value())))
-        .collect(),
-    )
-  }
+    let sequence_number_to_inscription_entry = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)
+      .unwrap();
 
-  pub(crate) fn get_feed_inscriptions(&self, n: usize) -> Result<Vec<(u32, InscriptionId)>> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
-        .iter()?
-        .rev()
-        .take(n)
-        .flat_map(|result| {
-          result.map(|(number, entry)| (number.value(), InscriptionEntry::load(entry.value()).id))
-        })
-        .collect(),
+    let parent_sequence_number = InscriptionEntry::load(
+      sequence_number_to_inscription_entry
+        .get(sequence_number)
+        .unwrap()
+        .unwrap()
+        .value(),
     )
-  }
-
-  pub fn inscription_info_benchmark(index: &Index, inscription_number: i32) {
-    Self::inscription_info(index, InscriptionQuery::Number(inscription_number)).unwrap();
-  }
-
-  pub(crate) fn inscription_info(
-    index: &Index,
-    query: InscriptionQuery,
-  ) -> Result<Option<InscriptionInfo>> {
-    let rtx = index.database.begin_read()?;
-
-    let sequence_number = match query {
-      InscriptionQuery::Id(id) => {
-        let inscription_id_to_sequence_number =
-          rtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
-
-        let sequence_number = inscription_id_to_sequence_number
-          .get(&id.store())?
-          .map(|guard| guard.value());
-
-        drop(inscription_id_to_sequence_number);
-
-        sequence_number
-      }
-      InscriptionQuery::Number(inscription_number) => {
-        let inscription_number_to_sequence_number =
-          rtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
-
-        let sequence_number = inscription_number_to_sequence_number
-          .get(inscription_number)?
-          .map(|guard| guard.value());
-
-        drop(inscription_number_to_sequence_number);
-
-        sequence_number
-      }
-    };
-
-    let Some(sequence_number) = sequence_number else {
-      return Ok(None);
-    };
-
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    .parent
+    .unwrap();
 
     let entry = InscriptionEntry::load(
       sequence_number_to_inscription_entry
-        .get(&sequence_number)?
+        .get(parent_sequence_number)
         .unwrap()
-        .value(),
-    );
-
-    let Some(transaction) = index.get_transaction(entry.id.txid)? else {
-      return Ok(None);
-    };
-
-    let Some(inscription) = ParsedEnvelope::from_transaction(&transaction)
-      .into_iter()
-      .nth(entry.id.index as usize)
-      .map(|envelope| envelope.payload)
-    else {
-      return Ok(None);
-    };
-
-    let satpoint = SatPoint::load(
-      *rtx
-        .open_table(SEQUENCE_NUMBER_TO_SATPOINT)?
-        .get(sequence_number)?
         .unwrap()
         .value(),
     );
 
-    let output = if satpoint.outpoint == unbound_outpoint() || satpoint.outpoint == OutPoint::null()
-    {
-      None
-    } else {
-      let Some(transaction) = index.get_transaction(satpoint.outpoint.txid)? else {
-        return Ok(None);
-      };
+    entry.id
+  }
 
-      transaction
-        .output
-        .into_iter()
-        .nth(satpoint.outpoint.vout.try_into().unwrap())
-    };
+  /// Inscriptions that claim to be the destination of teleburning `asset` on
+  /// `chain`, in inscription ID order.
+  pub(crate) fn get_teleburn_claims(
+    &self,
+    chain: TargetChain,
+    asset: &str,
+  ) -> Result<Vec<InscriptionId>> {
+    self
+      .database
+      .begin_read()?
+      .open_multimap_table(TELEBURN_CLAIM_TO_INSCRIPTION_ID)?
+      .get(teleburn::Claim::key(chain, asset).as_str())?
+      .map(|result| {
+        result
+          .map(|inscription_id| InscriptionId::load(inscription_id.value()))
+          .map_err(|err| err.into())
+      })
+      .collect()
+  }
 
-    let previous = if let Some(n) = sequence_number.checked_sub(1) {
-      Some(
-        InscriptionEntry::load(
-          sequence_number_to_inscription_entry
-            .get(n)?
-            .unwrap()
-            .value(),
-        )
-        .id,
-      )
-    } else {
-      None
-    };
+  pub(crate) fn get_children_by_sequence_number_paginated(
+    &self,
+    sequence_number: u32,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
 
-    let next = sequence_number_to_inscription_entry
-      .get(sequence_number + 1)?
-      .map(|guard| InscriptionEntry::load(guard.value()).id);
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let children = rtx
+    let mut children = rtx
       .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
       .get(sequence_number)?
-      .take(4)
+      .skip(page_index * page_size)
+      .take(page_size.saturating_add(1))
       .map(|result| {
         result
           .and_then(|sequence_number| {
-            sequence_number_to_inscription_entry
+            sequence_number_to_entry
               .get(sequence_number.value())
               .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
           })
@@ -1912,283 +2475,1281 @@ impl Index {
       })
       .collect::<Result<Vec<InscriptionId>>>()?;
 
-    let rune = if let Some(rune_id) = rtx
-      .open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?
-      .get(sequence_number)?
-    {
-      let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-      let entry = rune_id_to_rune_entry.get(&rune_id.value())?.unwrap();
-      Some(RuneEntry::load(entry.value()).spaced_rune())
-    } else {
-      None
-    };
+    let more = children.len() > page_size;
 
-    let parent = match entry.parent {
-      Some(parent) => Some(
-        InscriptionEntry::load(
-          sequence_number_to_inscription_entry
-            .get(parent)?
-            .unwrap()
-            .value(),
-        )
-        .id,
-      ),
-      None => None,
-    };
+    if more {
+      children.pop();
+    }
 
-    let mut charms = entry.charms;
-
-    if satpoint.outpoint == OutPoint::null() {
-      Charm::Lost.set(&mut charms);
-    }
-
-    Ok(Some(InscriptionInfo {
-      children,
-      entry,
-      parent,
-      output,
-      satpoint,
-      inscription,
-      previous,
-      next,
-      rune,
-      charms,
-    }))
+    Ok((children, more))
   }
 
-  pub(crate) fn get_inscription_entry(
-    &self,
-    inscription_id: InscriptionId,
-  ) -> Result<Option<InscriptionEntry>> {
+  /// Statistics of the children of `parent`, or `None` if `parent` has no
+  /// children.
+  pub(crate) fn get_collection(&self, parent: InscriptionId) -> Result<Option<CollectionEntry>> {
     let rtx = self.database.begin_read()?;
 
     let Some(sequence_number) = rtx
       .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
-      .get(&inscription_id.store())?
-      .map(|guard| guard.value())
+      .get(&parent.store())?
+      .map(|sequence_number| sequence_number.value())
     else {
       return Ok(None);
     };
 
-    let entry = rtx
-      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+    let collection = rtx
+      .open_table(SEQUENCE_NUMBER_TO_COLLECTION)?
       .get(sequence_number)?
-      .map(|value| InscriptionEntry::load(value.value()));
+      .map(|entry| CollectionEntry::load(entry.value()));
 
-    Ok(entry)
+    Ok(collection)
   }
 
-  #[cfg(test)]
-  fn assert_inscription_location(
+  /// Script pubkeys holding children of `parent`, with the number of children
+  /// each holds, ordered by script pubkey. Fails with `DeadlineExceeded` if
+  /// the scan passes `deadline`.
+  pub(crate) fn get_collection_holders_paginated(
     &self,
-    inscription_id: InscriptionId,
-    satpoint: SatPoint,
-    sat: Option<u64>,
-  ) {
-    let rtx = self.database.begin_read().unwrap();
+    parent: InscriptionId,
+    page_size: usize,
+    page_index: usize,
+    deadline: Deadline,
+  ) -> Result<(Vec<(ScriptBuf, u64)>, bool)> {
+    let rtx = self.database.begin_read()?;
 
-    let satpoint_to_sequence_number = rtx
-      .open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)
-      .unwrap();
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&parent.store())?
+      .map(|sequence_number| sequence_number.value())
+    else {
+      return Ok((Vec::new(), false));
+    };
 
-    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT).unwrap();
+    let collection_holder_to_child_count = rtx.open_table(COLLECTION_HOLDER_TO_CHILD_COUNT)?;
 
-    let sequence_number = rtx
-      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
-      .unwrap()
-      .get(&inscription_id.store())
-      .unwrap()
-      .unwrap()
-      .value();
+    let start: (u32, &[u8]) = (sequence_number, &[]);
 
-    assert_eq!(
-      satpoint_to_sequence_number.len().unwrap(),
-      sequence_number_to_satpoint.len().unwrap(),
-    );
+    let mut holders = Vec::new();
 
-    assert_eq!(
-      SatPoint::load(
-        *sequence_number_to_satpoint
-          .get(sequence_number)
-          .unwrap()
-          .unwrap()
-          .value()
-      ),
-      satpoint,
-    );
+    let skip = page_index.saturating_mul(page_size);
 
-    assert!(satpoint_to_sequence_number
-      .get(&satpoint.store())
-      .unwrap()
-      .any(|result| result.unwrap().value() == sequence_number));
+    for (i, result) in collection_holder_to_child_count.range(start..)?.enumerate() {
+      deadline.check(holders.len())?;
 
-    match sat {
-      Some(sat) => {
-        if self.index_sats {
-          // unbound inscriptions should not be assigned to a sat
-          assert!(satpoint.outpoint != unbound_outpoint());
+      if i < skip {
+        continue;
+      }
 
-          assert!(rtx
-            .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)
-            .unwrap()
-            .get(&sat)
-            .unwrap()
-            .any(|entry| entry.unwrap().value() == sequence_number));
+      let (key, count) = result?;
 
-          // we do not track common sats (only the sat ranges)
-          if !Sat(sat).common() {
-            assert_eq!(
-              SatPoint::load(
-                *rtx
-                  .open_table(SAT_TO_SATPOINT)
-                  .unwrap()
-                  .get(&sat)
-                  .unwrap()
-                  .unwrap()
-                  .value()
-              ),
-              satpoint,
-            );
-          }
-        }
-      }
-      None => {
-        if self.index_sats {
-          assert!(satpoint.outpoint == unbound_outpoint())
-        }
-      }
-    }
-  }
+      let (holder_parent, script_pubkey) = key.value();
 
-  fn inscriptions_on_output<'a: 'tx, 'tx>(
-    satpoint_to_sequence_number: &'a impl ReadableMultimapTable<&'static SatPointValue, u32>,
-    sequence_number_to_inscription_entry: &'a impl ReadableTable<u32, InscriptionEntryValue>,
-    outpoint: OutPoint,
-  ) -> Result<Vec<(SatPoint, InscriptionId)>> {
-    let start = SatPoint {
-      outpoint,
-      offset: 0,
-    }
-    .store();
+      if holder_parent != sequence_number || holders.len() > page_size {
+        break;
+      }
 
-    let end = SatPoint {
-      outpoint,
-      offset: u64::MAX,
+      holders.push((ScriptBuf::from_bytes(script_pubkey.to_vec()), count.value()));
     }
-    .store();
 
-    let mut inscriptions = Vec::new();
+    let more = holders.len() > page_size;
 
-    for range in satpoint_to_sequence_number.range::<&[u8; 44]>(&start..=&end)? {
-      let (satpoint, sequence_numbers) = range?;
-      for sequence_number_result in sequence_numbers {
-        let sequence_number = sequence_number_result?.value();
-        let entry = sequence_number_to_inscription_entry
-          .get(sequence_number)?
-          .unwrap();
-        inscriptions.push((
-          sequence_number,
-          SatPoint::load(*satpoint.value()),
-          InscriptionEntry::load(entry.value()).id,
-        ));
-      }
+    if more {
+      holders.pop();
     }
 
-    inscriptions.sort_by_key(|(sequence_number, _, _)| *sequence_number);
-
-    Ok(
-      inscriptions
-        .into_iter()
-        .map(|(_sequence_number, satpoint, inscription_id)| (satpoint, inscription_id))
-        .collect(),
-    )
+    Ok((holders, more))
   }
-}
-
-#[cfg(test)]
-mod tests {
-  use {
-    super::*,
-    crate::index::testing::Context,
-    bitcoin::secp256k1::rand::{self, RngCore},
-  };
 
-  #[test]
-  fn height_limit() {
-    {
-      let context = Context::builder().args(["--height-limit", "0"]).build();
-      context.mine_blocks(1);
-      assert_eq!(context.index.block_height().unwrap(), None);
-      assert_eq!(context.index.block_count().unwrap(), 0);
-    }
+  pub(crate) fn get_etching(&self, txid: Txid) -> Result<Option<SpacedRune>> {
+    let rtx = self.database.begin_read()?;
 
-    {
-      let context = Context::builder().args(["--height-limit", "1"]).build();
-      context.mine_blocks(1);
-      assert_eq!(context.index.block_height().unwrap(), Some(Height(0)));
-      assert_eq!(context.index.block_count().unwrap(), 1);
-    }
+    let transaction_id_to_rune = rtx.open_table(TRANSACTION_ID_TO_RUNE)?;
+    let Some(rune) = transaction_id_to_rune.get(&txid.store())? else {
+      return Ok(None);
+    };
 
-    {
-      let context = Context::builder().args(["--height-limit", "2"]).build();
-      context.mine_blocks(2);
-      assert_eq!(context.index.block_height().unwrap(), Some(Height(1)));
-      assert_eq!(context.index.block_count().unwrap(), 2);
-    }
-  }
+    let rune_to_rune_id = rtx.open_table(RUNE_TO_RUNE_ID)?;
+    let id = rune_to_rune_id.get(rune.value())?.unwrap();
 
-  #[test]
-  fn inscriptions_below_first_inscription_height_are_skipped() {
-    let inscription = inscription("text/plain;charset=utf-8", "hello");
-    let template = TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription.to_witness())],
-      ..Default::default()
-    };
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let entry = rune_id_to_rune_entry.get(&id.value())?.unwrap();
 
-    {
-      let context = Context::builder().build();
-      context.mine_blocks(1);
-      let txid = context.rpc_server.broadcast_tx(template.clone());
-      let inscription_id = InscriptionId { txid, index: 0 };
-      context.mine_blocks(1);
+    Ok(Some(RuneEntry::load(entry.value()).spaced_rune()))
+  }
 
-      assert_eq!(
-        context.index.get_inscription_by_id(inscription_id).unwrap(),
-        Some(inscription)
-      );
+  pub(crate) fn get_inscription_ids_by_sat(&self, sat: Sat) -> Result<Vec<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
 
-      assert_eq!(
-        context
-          .index
-          .get_inscription_satpoint_by_id(inscription_id)
-          .unwrap(),
-        Some(SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0,
-        })
-      );
-    }
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    {
-      let context = Context::builder()
-        .arg("--first-inscription-height=3")
-        .build();
-      context.mine_blocks(1);
-      let txid = context.rpc_server.broadcast_tx(template);
-      let inscription_id = InscriptionId { txid, index: 0 };
-      context.mine_blocks(1);
+    let ids = rtx
+      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
+      .get(&sat.n())?
+      .map(|result| {
+        result
+          .and_then(|sequence_number| {
+            let sequence_number = sequence_number.value();
+            sequence_number_to_inscription_entry
+              .get(sequence_number)
+              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+          })
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
 
-      assert_eq!(
-        context
-          .index
-          .get_inscription_satpoint_by_id(inscription_id)
-          .unwrap(),
-        None,
-      );
-    }
+    Ok(ids)
   }
 
-  #[test]
+  pub(crate) fn get_inscription_ids_by_sat_paginated(
+    &self,
+    sat: Sat,
+    page_size: u64,
+    page_index: u64,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut ids = rtx
+      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
+      .get(&sat.n())?
+      .map(|result| {
+        result
+          .and_then(|sequence_number| {
+            let sequence_number = sequence_number.value();
+            sequence_number_to_inscription_entry
+              .get(sequence_number)
+              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+          })
+          .map_err(|err| err.into())
+      })
+      .skip(page_index.saturating_mul(page_size).try_into().unwrap())
+      .take(page_size.saturating_add(1).try_into().unwrap())
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = ids.len() > page_size.try_into().unwrap();
+
+    if more {
+      ids.pop();
+    }
+
+    Ok((ids, more))
+  }
+
+  pub(crate) fn get_inscription_id_by_sat_indexed(
+    &self,
+    sat: Sat,
+    inscription_index: isize,
+  ) -> Result<Option<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let sat_to_sequence_number = rtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+
+    if inscription_index < 0 {
+      sat_to_sequence_number
+        .get(&sat.n())?
+        .nth_back((inscription_index + 1).abs_diff(0))
+    } else {
+      sat_to_sequence_number
+        .get(&sat.n())?
+        .nth(inscription_index.abs_diff(0))
+    }
+    .map(|result| {
+      result
+        .and_then(|sequence_number| {
+          let sequence_number = sequence_number.value();
+          sequence_number_to_inscription_entry
+            .get(sequence_number)
+            .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+        })
+        .map_err(|err| anyhow!(err.to_string()))
+    })
+    .transpose()
+  }
+
+  pub(crate) fn get_inscription_id_by_inscription_number(
+    &self,
+    inscription_number: i32,
+  ) -> Result<Option<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+      .get(inscription_number)?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let inscription_id = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .get(&sequence_number)?
+      .map(|entry| InscriptionEntry::load(entry.value()).id);
+
+    Ok(inscription_id)
+  }
+
+  pub(crate) fn get_inscription_satpoint_by_id(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<SatPoint>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let satpoint = rtx
+      .open_table(SEQUENCE_NUMBER_TO_SATPOINT)?
+      .get(sequence_number)?
+      .map(|satpoint| Entry::load(*satpoint.value()));
+
+    Ok(satpoint)
+  }
+
+  pub(crate) fn get_inscription_by_id(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<Inscription>> {
+    if !self.inscription_exists(inscription_id)? {
+      return Ok(None);
+    }
+
+    Ok(self.get_transaction(inscription_id.txid)?.and_then(|tx| {
+      ParsedEnvelope::from_transaction(&tx)
+        .into_iter()
+        .nth(inscription_id.index as usize)
+        .map(|envelope| envelope.payload)
+    }))
+  }
+
+  pub(crate) fn inscription_count(&self, txid: Txid) -> Result<u32> {
+    let start = InscriptionId { index: 0, txid };
+
+    let end = InscriptionId {
+      index: u32::MAX,
+      txid,
+    };
+
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+        .range::<&InscriptionIdValue>(&start.store()..&end.store())?
+        .count()
+        .try_into()
+        .unwrap(),
+    )
+  }
+
+  pub(crate) fn inscription_exists(&self, inscription_id: InscriptionId) -> Result<bool> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+        .get(&inscription_id.store())?
+        .is_some(),
+    )
+  }
+
+  pub(crate) fn get_inscriptions_on_output_with_satpoints(
+    &self,
+    outpoint: OutPoint,
+  ) -> Result<Vec<(SatPoint, InscriptionId)>> {
+    let rtx = self.database.begin_read()?;
+    let satpoint_to_sequence_number = rtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    Self::inscriptions_on_output(
+      &satpoint_to_sequence_number,
+      &sequence_number_to_inscription_entry,
+      outpoint,
+    )
+  }
+
+  pub(crate) fn get_inscriptions_on_output(
+    &self,
+    outpoint: OutPoint,
+  ) -> Result<Vec<InscriptionId>> {
+    Ok(
+      self
+        .get_inscriptions_on_output_with_satpoints(outpoint)?
+        .iter()
+        .map(|(_satpoint, inscription_id)| *inscription_id)
+        .collect(),
+    )
+  }
+
+  pub(crate) fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    if txid == self.genesis_block_coinbase_txid {
+      return Ok(Some(self.genesis_block_coinbase_transaction.clone()));
+    }
+
+    if self.index_transactions {
+      if let Some(transaction) = self
+        .database
+        .begin_read()?
+        .open_table(TRANSACTION_ID_TO_TRANSACTION)?
+        .get(&txid.store())?
+      {
+        return Ok(Some(consensus::encode::deserialize(transaction.value())?));
+      }
+    }
+
+    self.client.get_raw_transaction(&txid, None).into_option()
+  }
+
+  pub(crate) fn get_transaction_blockhash(&self, txid: Txid) -> Result<Option<BlockHash>> {
+    Ok(
+      self
+        .client
+        .get_raw_transaction_info(&txid, None)
+        .into_option()?
+        .and_then(|info| {
+          if info.in_active_chain.unwrap_or_default() {
+            info.blockhash
+          } else {
+            None
+          }
+        }),
+    )
+  }
+
+  pub(crate) fn is_transaction_in_active_chain(&self, txid: Txid) -> Result<bool> {
+    Ok(
+      self
+        .client
+        .get_raw_transaction_info(&txid, None)
+        .into_option()?
+        .and_then(|info| info.in_active_chain)
+        .unwrap_or(false),
+    )
+  }
+
+  pub(crate) fn find(&self, sat: Sat) -> Result<Option<SatPoint>> {
+    let sat = sat.0;
+    let rtx = self.begin_read()?;
+
+    if rtx.block_count()? <= Sat(sat).height().n() {
+      return Ok(None);
+    }
+
+    let outpoint_to_sat_ranges = rtx.0.open_table(OUTPOINT_TO_SAT_RANGES)?;
+
+    for range in outpoint_to_sat_ranges.range::<&[u8; 36]>(&[0; 36]..)? {
+      let (key, value) = range?;
+      let mut offset = 0;
+      for chunk in value.value().chunks_exact(11) {
+        let (start, end) = SatRange::load(chunk.try_into().unwrap());
+        if start <= sat && sat < end {
+          return Ok(Some(SatPoint {
+            outpoint: Entry::load(*key.value()),
+            offset: offset + sat - start,
+          }));
+        }
+        offset += end - start;
+      }
+    }
+
+    Ok(None)
+  }
+
+  pub(crate) fn find_range(
+    &self,
+    range_start: Sat,
+    range_end: Sat,
+  ) -> Result<Option<Vec<FindRangeOutput>>> {
+    let range_start = range_start.0;
+    let range_end = range_end.0;
+    let rtx = self.begin_read()?;
+
+    if rtx.block_count()? < Sat(range_end - 1).height().n() + 1 {
+      return Ok(None);
+    }
+
+    let Some(mut remaining_sats) = range_end.checked_sub(range_start) else {
+      return Err(anyhow!("range end is before range start"));
+    };
+
+    let outpoint_to_sat_ranges = rtx.0.open_table(OUTPOINT_TO_SAT_RANGES)?;
+
+    let mut result = Vec::new();
+    for range in outpoint_to_sat_ranges.range::<&[u8; 36]>(&[0; 36]..)? {
+      let (outpoint_entry, sat_ranges_entry) = range?;
+
+      let mut offset = 0;
+      for sat_range in sat_ranges_entry.value().chunks_exact(11) {
+        let (start, end) = SatRange::load(sat_range.try_into().unwrap());
+
+        if end > range_start && start < range_end {
+          let overlap_start = start.max(range_start);
+          let overlap_end = end.min(range_end);
+
+          result.push(FindRangeOutput {
+            start: overlap_start,
+            size: overlap_end - overlap_start,
+            satpoint: SatPoint {
+              outpoint: Entry::load(*outpoint_entry.value()),
+              offset: offset + overlap_start - start,
+            },
+          });
+
+          remaining_sats -= overlap_end - overlap_start;
+
+          if remaining_sats == 0 {
+            break;
+          }
+        }
+        offset += end - start;
+      }
+    }
+
+    Ok(Some(result))
+  }
+
+  fn list_inner(&self, outpoint: OutPointValue) -> Result<Option<Vec<u8>>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(OUTPOINT_TO_SAT_RANGES)?
+        .get(&outpoint)?
+        .map(|outpoint| outpoint.value().to_vec()),
+    )
+  }
+
+  pub(crate) fn list(&self, outpoint: OutPoint) -> Result<Option<List>> {
+    if !self.index_sats || outpoint == unbound_outpoint() {
+      return Ok(None);
+    }
+
+    let array = outpoint.store();
+
+    let sat_ranges = self.list_inner(array)?;
+
+    match sat_ranges {
+      Some(sat_ranges) => Ok(Some(List::Unspent(
+        sat_ranges
+          .chunks_exact(11)
+          .map(|chunk| SatRange::load(chunk.try_into().unwrap()))
+          .collect(),
+      ))),
+      None => {
+        if self.is_transaction_in_active_chain(outpoint.txid)? {
+          Ok(Some(List::Spent))
+        } else {
+          Ok(None)
+        }
+      }
+    }
+  }
+
+  pub(crate) fn block_time(&self, height: Height) -> Result<Blocktime> {
+    let height = height.n();
+
+    let rtx = self.database.begin_read()?;
+
+    let height_to_block_header = rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+
+    if let Some(guard) = height_to_block_header.get(height)? {
+      return Ok(Blocktime::confirmed(Header::load(*guard.value()).time));
+    }
+
+    let current = height_to_block_header
+      .range(0..)?
+      .next_back()
+      .and_then(|result| result.ok())
+      .map(|(height, _header)| height)
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    let expected_blocks = height
+      .checked_sub(current)
+      .with_context(|| format!("current {current} height is greater than sat height {height}"))?;
+
+    Ok(Blocktime::Expected(
+      Utc::now()
+        .round_subsecs(0)
+        .checked_add_signed(chrono::Duration::seconds(
+          10 * 60 * i64::from(expected_blocks),
+        ))
+        .ok_or_else(|| anyhow!("block timestamp out of range"))?,
+    ))
+  }
+
+  pub(crate) fn get_inscriptions(
+    &self,
+    utxos: &BTreeMap<OutPoint, Amount>,
+  ) -> Result<BTreeMap<SatPoint, InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let mut result = BTreeMap::new();
+
+    let satpoint_to_sequence_number = rtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    for utxo in utxos.keys() {
+      result.extend(Self::inscriptions_on_output(
+        &satpoint_to_sequence_number,
+        &sequence_number_to_inscription_entry,
+        *utxo,
+      )?);
+    }
+
+    Ok(result)
+  }
+
+  #[cfg(test)]
+  pub(crate) fn get_inscriptions_paginated(
+    &self,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    self.get_inscriptions_sorted_paginated(
+      InscriptionSort::Height,
+      SortOrder::Desc,
+      None,
+      page_size,
+      page_index,
+    )
+  }
+
+  /// Inscriptions in `sort` and `order`, optionally only those with content
+  /// type `content_type`, which should be a content type essence.
+  pub(crate) fn get_inscriptions_sorted_paginated(
+    &self,
+    sort: InscriptionSort,
+    order: SortOrder,
+    content_type: Option<&str>,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let inscription_number_to_sequence_number =
+      rtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+
+    let inscription_sort_keys = rtx.open_table(INSCRIPTION_SORT_KEYS)?;
+
+    let sequence_numbers: Box<dyn DoubleEndedIterator<Item = Result<u32, StorageError>> + '_> =
+      match (sort, content_type) {
+        (InscriptionSort::Height, None) => Box::new(
+          sequence_number_to_inscription_entry
+            .iter()?
+            .map(|result| result.map(|(sequence_number, _entry)| sequence_number.value())),
+        ),
+        (InscriptionSort::Number, None) => Box::new(
+          inscription_number_to_sequence_number
+            .iter()?
+            .map(|result| result.map(|(_number, sequence_number)| sequence_number.value())),
+        ),
+        (sort, content_type) => Box::new(
+          inscription_sort_keys
+            .range(sort.range(content_type.unwrap_or_default()))?
+            .map(|result| result.map(|(key, _value)| key.value().3)),
+        ),
+      };
+
+    let sequence_numbers: Box<dyn Iterator<Item = Result<u32, StorageError>> + '_> = match order {
+      SortOrder::Asc => Box::new(sequence_numbers),
+      SortOrder::Desc => Box::new(sequence_numbers.rev()),
+    };
+
+    let mut inscriptions = sequence_numbers
+      .skip(page_size.saturating_mul(page_index))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(result?)?
+              .unwrap()
+              .value(),
+          )
+          .id,
+        )
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
+  pub(crate) fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let Some(newest_sequence_number) = height_to_last_sequence_number
+      .get(&block_height)?
+      .map(|ag| ag.value())
+    else {
+      return Ok(Vec::new());
+    };
+
+    let oldest_sequence_number = height_to_last_sequence_number
+      .get(block_height.saturating_sub(1))?
+      .map(|ag| ag.value())
+      .unwrap_or(0);
+
+    (oldest_sequence_number..newest_sequence_number)
+      .map(|num| match sequence_number_to_inscription_entry.get(&num) {
+        Ok(Some(inscription_id)) => Ok(InscriptionEntry::load(inscription_id.value()).id),
+        Ok(None) => Err(anyhow!(
+          "could not find inscription for inscription number {num}"
+        )),
+        Err(err) => Err(anyhow!(err)),
+      })
+      .collect::<Result<Vec<InscriptionId>>>()
+  }
+
+  pub(crate) fn get_highest_paying_inscriptions_in_block(
+    &self,
+    block_height: u32,
+    n: usize,
+  ) -> Result<(Vec<InscriptionId>, usize)> {
+    let inscription_ids = self.get_inscriptions_in_block(block_height)?;
+
+    let mut inscription_to_fee: Vec<(InscriptionId, u64)> = Vec::new();
+    for id in &inscription_ids {
+      inscription_to_fee.push((
+        *id,
+        self
+          .get_inscription_entry(*id)?
+          .ok_or_else(|| anyhow!("could not get entry for inscription {id}"))?
+          .fee,
+      ));
+    }
+
+    inscription_to_fee.sort_by_key(|(_, fee)| *fee);
+
+    Ok((
+      inscription_to_fee
+        .iter()
+        .map(|(id, _)| *id)
+        .rev()
+        .take(n)
+        .collect(),
+      inscription_ids.len(),
+    ))
+  }
+
+  pub(crate) fn get_home_inscriptions(&self) -> Result<Vec<InscriptionId>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(HOME_INSCRIPTIONS)?
+        .iter()?
+        .rev()
+        .flat_map(|result| result.map(|(_number, id)| InscriptionId::load(id.value())))
+        .collect(),
+    )
+  }
+
+  pub(crate) fn get_feed_inscriptions(&self, n: usize) -> Result<Vec<(u32, InscriptionId)>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .iter()?
+        .rev()
+        .take(n)
+        .flat_map(|result| {
+          result.map(|(number, entry)| (number.value(), InscriptionEntry::load(entry.value()).id))
+        })
+        .collect(),
+    )
+  }
+
+  pub fn inscription_info_benchmark(index: &Index, inscription_number: i32) {
+    Self::inscription_info(index, InscriptionQuery::Number(inscription_number)).unwrap();
+  }
+
+  fn query_sequence_number(
+    rtx: &redb::ReadTransaction,
+    query: InscriptionQuery,
+  ) -> Result<Option<u32>> {
+    Ok(match query {
+      InscriptionQuery::Id(id) => rtx
+        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+        .get(&id.store())?
+        .map(|guard| guard.value()),
+      InscriptionQuery::Number(inscription_number) => rtx
+        .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+        .get(inscription_number)?
+        .map(|guard| guard.value()),
+    })
+  }
+
+  /// Entry and transfers, oldest first, of the inscription matching `query`.
+  pub(crate) fn get_inscription_transfers(
+    &self,
+    query: InscriptionQuery,
+  ) -> Result<Option<(InscriptionEntry, Vec<TransferEntry>)>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = Self::query_sequence_number(&rtx, query)? else {
+      return Ok(None);
+    };
+
+    let entry = InscriptionEntry::load(
+      rtx
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .get(sequence_number)?
+        .unwrap()
+        .value(),
+    );
+
+    let transfers = rtx
+      .open_table(SEQUENCE_NUMBER_TO_TRANSFERS)?
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+      .map(|result| {
+        result
+          .map(|(_index, transfer)| TransferEntry::load(*transfer.value()))
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<TransferEntry>>>()?;
+
+    Ok(Some((entry, transfers)))
+  }
+
+  pub(crate) fn inscription_info(
+    index: &Index,
+    query: InscriptionQuery,
+  ) -> Result<Option<InscriptionInfo>> {
+    let rtx = index.database.begin_read()?;
+
+    let Some(sequence_number) = Self::query_sequence_number(&rtx, query)? else {
+      return Ok(None);
+    };
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let entry = InscriptionEntry::load(
+      sequence_number_to_inscription_entry
+        .get(&sequence_number)?
+        .unwrap()
+        .value(),
+    );
+
+    let Some(transaction) = index.get_transaction(entry.id.txid)? else {
+      return Ok(None);
+    };
+
+    let Some(inscription) = ParsedEnvelope::from_transaction(&transaction)
+      .into_iter()
+      .nth(entry.id.index as usize)
+      .map(|envelope| envelope.payload)
+    else {
+      return Ok(None);
+    };
+
+    let satpoint = SatPoint::load(
+      *rtx
+        .open_table(SEQUENCE_NUMBER_TO_SATPOINT)?
+        .get(sequence_number)?
+        .unwrap()
+        .value(),
+    );
+
+    let output = if satpoint.outpoint == unbound_outpoint() || satpoint.outpoint == OutPoint::null()
+    {
+      None
+    } else {
+      let Some(transaction) = index.get_transaction(satpoint.outpoint.txid)? else {
+        return Ok(None);
+      };
+
+      transaction
+        .output
+        .into_iter()
+        .nth(satpoint.outpoint.vout.try_into().unwrap())
+    };
+
+    let previous = if let Some(n) = sequence_number.checked_sub(1) {
+      Some(
+        InscriptionEntry::load(
+          sequence_number_to_inscription_entry
+            .get(n)?
+            .unwrap()
+            .value(),
+        )
+        .id,
+      )
+    } else {
+      None
+    };
+
+    let next = sequence_number_to_inscription_entry
+      .get(sequence_number + 1)?
+      .map(|guard| InscriptionEntry::load(guard.value()).id);
+
+    let children = rtx
+      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+      .get(sequence_number)?
+      .take(4)
+      .map(|result| {
+        result
+          .and_then(|sequence_number| {
+            sequence_number_to_inscription_entry
+              .get(sequence_number.value())
+              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+          })
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let rune = if let Some(rune_id) = rtx
+      .open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?
+      .get(sequence_number)?
+    {
+      let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+      let entry = rune_id_to_rune_entry.get(&rune_id.value())?.unwrap();
+      Some(RuneEntry::load(entry.value()).spaced_rune())
+    } else {
+      None
+    };
+
+    let parent = match entry.parent {
+      Some(parent) => Some(
+        InscriptionEntry::load(
+          sequence_number_to_inscription_entry
+            .get(parent)?
+            .unwrap()
+            .value(),
+        )
+        .id,
+      ),
+      None => None,
+    };
+
+    let cid = rtx
+      .open_table(SEQUENCE_NUMBER_TO_CID)?
+      .get(sequence_number)?
+      .map(|cid| cid.value().to_string());
+
+    let content_verdicts = rtx
+      .open_table(SEQUENCE_NUMBER_TO_CONTENT_VERDICTS)?
+      .get(sequence_number)?
+      .map(|verdicts| validation::load_content_verdicts(verdicts.value()))
+      .unwrap_or_default();
+
+    let recursive_endpoints = rtx
+      .open_table(SEQUENCE_NUMBER_TO_RECURSIVE_ENDPOINTS)?
+      .get(sequence_number)?
+      .map(|mask| recursion::load_recursive_endpoints(mask.value()))
+      .unwrap_or_default();
+
+    let mut charms = entry.charms;
+
+    if satpoint.outpoint == OutPoint::null() {
+      Charm::Lost.set(&mut charms);
+    }
+
+    Ok(Some(InscriptionInfo {
+      children,
+      cid,
+      content_verdicts,
+      entry,
+      parent,
+      output,
+      satpoint,
+      inscription,
+      previous,
+      next,
+      recursive_endpoints,
+      rune,
+      charms,
+    }))
+  }
+
+  pub(crate) fn get_inscription_entry(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<InscriptionEntry>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let entry = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .get(sequence_number)?
+      .map(|value| InscriptionEntry::load(value.value()));
+
+    Ok(entry)
+  }
+
+  #[cfg(test)]
+  fn assert_inscription_location(
+    &self,
+    inscription_id: InscriptionId,
+    satpoint: SatPoint,
+    sat: Option<u64>,
+  ) {
+    let rtx = self.database.begin_read().unwrap();
+
+    let satpoint_to_sequence_number = rtx
+      .open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)
+      .unwrap();
+
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT).unwrap();
+
+    let sequence_number = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .get(&inscription_id.store())
+      .unwrap()
+      .unwrap()
+      .value();
+
+    assert_eq!(
+      satpoint_to_sequence_number.len().unwrap(),
+      sequence_number_to_satpoint.len().unwrap(),
+    );
+
+    assert_eq!(
+      SatPoint::load(
+        *sequence_number_to_satpoint
+          .get(sequence_number)
+          .unwrap()
+          .unwrap()
+          .value()
+      ),
+      satpoint,
+    );
+
+    assert!(satpoint_to_sequence_number
+      .get(&satpoint.store())
+      .unwrap()
+      .any(|result| result.unwrap().value() == sequence_number));
+
+    match sat {
+      Some(sat) => {
+        if self.index_sats {
+          // unbound inscriptions should not be assigned to a sat
+          assert!(satpoint.outpoint != unbound_outpoint());
+
+          assert!(rtx
+            .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)
+            .unwrap()
+            .get(&sat)
+            .unwrap()
+            .any(|entry| entry.unwrap().value() == sequence_number));
+
+          // we do not track common sats (only the sat ranges)
+          if !Sat(sat).common() {
+            assert_eq!(
+              SatPoint::load(
+                *rtx
+                  .open_table(SAT_TO_SATPOINT)
+                  .unwrap()
+                  .get(&sat)
+                  .unwrap()
+                  .unwrap()
+                  .value()
+              ),
+              satpoint,
+            );
+          }
+        }
+      }
+      None => {
+        if self.index_sats {
+          assert!(satpoint.outpoint == unbound_outpoint())
+        }
+      }
+    }
+  }
+
+  fn inscriptions_on_output<'a: 'tx, 'tx>(
+    satpoint_to_sequence_number: &'a impl ReadableMultimapTable<&'static SatPointValue, u32>,
+    sequence_number_to_inscription_entry: &'a impl ReadableTable<u32, InscriptionEntryValue>,
+    outpoint: OutPoint,
+  ) -> Result<Vec<(SatPoint, InscriptionId)>> {
+    let start = SatPoint {
+      outpoint,
+      offset: 0,
+    }
+    .store();
+
+    let end = SatPoint {
+      outpoint,
+      offset: u64::MAX,
+    }
+    .store();
+
+    let mut inscriptions = Vec::new();
+
+    for range in satpoint_to_sequence_number.range::<&[u8; 44]>(&start..=&end)? {
+      let (satpoint, sequence_numbers) = range?;
+      for sequence_number_result in sequence_numbers {
+        let sequence_number = sequence_number_result?.value();
+        let entry = sequence_number_to_inscription_entry
+          .get(sequence_number)?
+          .unwrap();
+        inscriptions.push((
+          sequence_number,
+          SatPoint::load(*satpoint.value()),
+          InscriptionEntry::load(entry.value()).id,
+        ));
+      }
+    }
+
+    inscriptions.sort_by_key(|(sequence_number, _, _)| *sequence_number);
+
+    Ok(
+      inscriptions
+        .into_iter()
+        .map(|(_sequence_number, satpoint, inscription_id)| (satpoint, inscription_id))
+        .collect(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::testing::Context,
+    bitcoin::secp256k1::rand::{self, RngCore},
+  };
+
+  #[test]
+  fn height_limit() {
+    {
+      let context = Context::builder().args(["--height-limit", "0"]).build();
+      context.mine_blocks(1);
+      assert_eq!(context.index.block_height().unwrap(), None);
+      assert_eq!(context.index.block_count().unwrap(), 0);
+    }
+
+    {
+      let context = Context::builder().args(["--height-limit", "1"]).build();
+      context.mine_blocks(1);
+      assert_eq!(context.index.block_height().unwrap(), Some(Height(0)));
+      assert_eq!(context.index.block_count().unwrap(), 1);
+    }
+
+    {
+      let context = Context::builder().args(["--height-limit", "2"]).build();
+      context.mine_blocks(2);
+      assert_eq!(context.index.block_height().unwrap(), Some(Height(1)));
+      assert_eq!(context.index.block_count().unwrap(), 2);
+    }
+  }
+
+  #[test]
+  fn commit_interval() {
+    {
+      let context = Context::builder().build();
+      let commits = context.index.statistic(Statistic::Commits);
+      context.mine_blocks(3);
+      assert_eq!(context.index.statistic(Statistic::Commits), commits + 1);
+    }
+
+    {
+      let context = Context::builder().args(["--commit-interval", "1"]).build();
+      let commits = context.index.statistic(Statistic::Commits);
+      context.mine_blocks(3);
+      assert_eq!(context.index.statistic(Statistic::Commits), commits + 3);
+    }
+
+    {
+      let context = Context::builder()
+        .args(["--commit-cache-size", "0"])
+        .build();
+      let commits = context.index.statistic(Statistic::Commits);
+      context.mine_blocks(3);
+      assert_eq!(context.index.statistic(Statistic::Commits), commits + 3);
+    }
+  }
+
+  #[test]
+  fn blocks_are_fetched_over_json_rpc_if_rest_interface_is_unavailable() {
+    let context = Context::builder()
+      .args(["--index-sats", "--rpc-rest"])
+      .build();
+
+    let blocks = context.mine_blocks(2);
+
+    assert_eq!(context.index.block_count().unwrap(), 3);
+
+    assert_eq!(
+      context
+        .index
+        .list(OutPoint::new(blocks[1].txdata[0].txid(), 0))
+        .unwrap()
+        .unwrap(),
+      List::Unspent(vec![(100 * COIN_VALUE, 150 * COIN_VALUE)])
+    );
+  }
+
+  #[test]
+  fn spilling_caches_does_not_change_index() {
+    let spilled = Context::builder()
+      .args(["--index-sats", "--cache-budget", "0"])
+      .build();
+    let cached = Context::builder().arg("--index-sats").build();
+
+    for context in [&spilled, &cached] {
+      context.mine_blocks(1);
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, Default::default())],
+        outputs: 2,
+        fee: 0,
+        ..Default::default()
+      });
+      context.mine_blocks(2);
+    }
+
+    let lists = |context: &Context| {
+      [
+        OutPoint::new(context.rpc_server.tx(3, 0).txid(), 0),
+        OutPoint::new(context.rpc_server.tx(2, 1).txid(), 0),
+        OutPoint::new(context.rpc_server.tx(2, 1).txid(), 1),
+      ]
+      .map(|outpoint| context.index.list(outpoint).unwrap())
+    };
+
+    assert_eq!(lists(&spilled), lists(&cached));
+
+    assert_eq!(
+      spilled.index.statistic(Statistic::Commits),
+      cached.index.statistic(Statistic::Commits),
+    );
+  }
+
+  #[test]
+  fn first_full_block_height_is_earliest_enabled_protocol_start() {
+    let first_full_block_height = |args: &[&str]| {
+      Context::builder()
+        .chain(Chain::Signet)
+        .args(args.iter().copied())
+        .build()
+        .index
+        .first_full_block_height()
+    };
+
+    assert_eq!(
+      first_full_block_height(&[]),
+      Chain::Signet.first_inscription_height()
+    );
+
+    assert_eq!(
+      first_full_block_height(&["--index-runes"]),
+      Chain::Signet.first_rune_height()
+    );
+
+    assert_eq!(
+      first_full_block_height(&["--no-index-inscriptions"]),
+      u32::MAX
+    );
+
+    assert_eq!(
+      first_full_block_height(&["--no-index-inscriptions", "--index-runes"]),
+      Chain::Signet.first_rune_height()
+    );
+
+    assert_eq!(first_full_block_height(&["--index-addresses"]), 0);
+  }
+
+  #[test]
+  fn progress_is_reported_after_update() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(2);
+
+    let progress = context.index.status().unwrap().progress;
+
+    assert_eq!(progress.height, 3);
+    assert_eq!(progress.target, 3);
+    assert_eq!(progress.stage, IndexStage::Idle);
+    assert_eq!(progress.eta, Some(0));
+  }
+
+  #[test]
+  fn inscriptions_below_first_inscription_height_are_skipped() {
+    let inscription = inscription("text/plain;charset=utf-8", "hello");
+    let template = TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription.to_witness())],
+      ..Default::default()
+    };
+
+    {
+      let context = Context::builder().build();
+      context.mine_blocks(1);
+      let txid = context.rpc_server.broadcast_tx(template.clone());
+      let inscription_id = InscriptionId { txid, index: 0 };
+      context.mine_blocks(1);
+
+      assert_eq!(
+        context.index.get_inscription_by_id(inscription_id).unwrap(),
+        Some(inscription)
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_inscription_satpoint_by_id(inscription_id)
+          .unwrap(),
+        Some(SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        })
+      );
+    }
+
+    {
+      let context = Context::builder()
+        .arg("--first-inscription-height=3")
+        .build();
+      context.mine_blocks(1);
+      let txid = context.rpc_server.broadcast_tx(template);
+      let inscription_id = InscriptionId { txid, index: 0 };
+      context.mine_blocks(1);
+
+      assert_eq!(
+        context
+          .index
+          .get_inscription_satpoint_by_id(inscription_id)
+          .unwrap(),
+        None,
+      );
+    }
+  }
+
+  #[test]
   fn inscriptions_are_not_indexed_if_no_index_inscriptions_flag_is_set() {
     let inscription = inscription("text/plain;charset=utf-8", "hello");
     let template = TransactionTemplate {
@@ -2930,6 +4491,186 @@ mod tests {
     }
   }
 
+  #[test]
+  fn index_deltas_hold_locations_as_of_each_block() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      fee: 50 * COIN_VALUE,
+      ..Default::default()
+    });
+
+    context.mine_blocks_with_subsidy(1, 0);
+
+    let mut deltas = Vec::new();
+
+    assert_eq!(context.index.export_deltas(2, 4, &mut deltas).unwrap(), 2);
+
+    let mut reader = deltas.as_slice();
+
+    BlockDelta::read_preamble(&mut reader).unwrap();
+
+    let created = BlockDelta::decode(&mut reader).unwrap().unwrap();
+
+    assert_eq!(created.inscriptions.len(), 1);
+    assert_eq!(
+      created.inscriptions[0].satpoint,
+      SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      }
+      .store()
+    );
+    assert_eq!(created.inscriptions[0].owner, Some(Vec::new()));
+    assert!(created.moves.is_empty());
+    assert!(created.transfers.is_empty());
+
+    let lost = BlockDelta::decode(&mut reader).unwrap().unwrap();
+
+    assert!(lost.inscriptions.is_empty());
+    assert_eq!(
+      lost.moves,
+      [MoveDelta {
+        owner: None,
+        satpoint: SatPoint {
+          outpoint: OutPoint::null(),
+          offset: 0,
+        }
+        .store(),
+        sequence_number: 0,
+      }]
+    );
+    assert_eq!(lost.transfers.len(), 1);
+
+    assert_eq!(BlockDelta::decode(&mut reader).unwrap(), None);
+  }
+
+  #[test]
+  fn imported_deltas_match_indexed_tables() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/html", "<script src=/r/blockheight></script>").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let child = Inscription {
+      content_type: Some("text/plain".into()),
+      body: Some("hello".into()),
+      parent: Some(
+        InscriptionId {
+          txid: parent_txid,
+          index: 0,
+        }
+        .value(),
+      ),
+      ..Default::default()
+    };
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, child.to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Default::default())],
+      fee: 1000,
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut deltas = Vec::new();
+
+    context
+      .index
+      .export_deltas(1, context.index.block_count().unwrap(), &mut deltas)
+      .unwrap();
+
+    let replica = Context::builder().build();
+
+    assert_eq!(
+      replica.index.import_deltas(&mut deltas.as_slice()).unwrap(),
+      4
+    );
+
+    macro_rules! assert_tables_eq {
+      ($($table:ident),* $(,)?) => {
+        $(
+          let entries = |index: &Index| {
+            index
+              .database
+              .begin_read()
+              .unwrap()
+              .open_table($table)
+              .unwrap()
+              .iter()
+              .unwrap()
+              .map(|result| {
+                let (key, value) = result.unwrap();
+                format!("{:?} {:?}", key.value(), value.value())
+              })
+              .collect::<Vec<String>>()
+          };
+
+          assert!(!entries(&context.index).is_empty(), stringify!($table));
+          assert_eq!(
+            entries(&replica.index),
+            entries(&context.index),
+            stringify!($table),
+          );
+        )*
+      };
+    }
+
+    assert_tables_eq!(
+      CHILD_SEQUENCE_NUMBER_TO_OWNER,
+      COLLECTION_HOLDER_TO_CHILD_COUNT,
+      HEIGHT_TO_INSCRIPTION_FEES,
+      HEIGHT_TO_INSCRIPTION_MOVES,
+      SATPOINT_TO_OWNER,
+      SEQUENCE_NUMBER_TO_COLLECTION,
+      SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY,
+      SEQUENCE_NUMBER_TO_RECURSIVE_ENDPOINTS,
+      SEQUENCE_NUMBER_TO_TRANSFERS,
+    );
+  }
+
+  #[test]
+  fn importing_deltas_with_address_index_fails() {
+    let context = Context::builder().arg("--index-addresses").build();
+
+    assert_eq!(
+      context
+        .index
+        .import_deltas(&mut [].as_slice())
+        .unwrap_err()
+        .to_string(),
+      "index deltas cannot be imported into an index built with `--index-addresses`",
+    );
+  }
+
   #[test]
   fn multiple_inscriptions_can_be_lost() {
     for context in Context::configurations() {
@@ -3208,24 +4949,174 @@ mod tests {
 
       let wtx = context.index.database.begin_write().unwrap();
 
-      wtx
-        .open_table(STATISTIC_TO_COUNT)
-        .unwrap()
-        .insert(&Statistic::Schema.key(), &u64::MAX)
-        .unwrap();
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &u64::MAX)
+        .unwrap();
+
+      wtx.commit().unwrap();
+
+      context.tempdir
+    };
+
+    let path = tempdir.path().to_owned();
+
+    let delimiter = if cfg!(windows) { '\\' } else { '/' };
+
+    assert_eq!(
+      Context::builder().tempdir(tempdir).try_build().err().unwrap().to_string(),
+      format!("index at `{}{delimiter}regtest{delimiter}index.redb` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {}, ord schema {SCHEMA_VERSION}", path.display(), u64::MAX));
+  }
+
+  #[test]
+  fn index_modes() {
+    let context = Context::builder().args(["--index-mode", "full"]).build();
+    assert!(context.index.has_sat_index());
+    assert!(context.index.has_rune_index());
+    assert!(context.index.index_transactions);
+
+    let context = Context::builder()
+      .args(["--index-mode", "protocol-only"])
+      .build();
+    assert!(!context.index.has_sat_index());
+    assert!(context.index.has_rune_index());
+    assert!(!context.index.index_transactions);
+  }
+
+  #[test]
+  fn reopening_index_in_different_mode_gives_correct_error() {
+    let tempdir = Context::builder()
+      .args(["--index-mode", "protocol-only"])
+      .build()
+      .tempdir;
+
+    let path = tempdir.path().to_owned();
+
+    let delimiter = if cfg!(windows) { '\\' } else { '/' };
+
+    assert_eq!(
+      Context::builder()
+        .args(["--index-mode", "full"])
+        .tempdir(tempdir)
+        .try_build()
+        .err()
+        .unwrap()
+        .to_string(),
+      format!(
+        "index at `{}{delimiter}regtest{delimiter}index.redb` was not built in full index mode, consider deleting and rebuilding the index",
+        path.display()
+      ),
+    );
+  }
+
+  #[test]
+  fn verify_reports_and_repairs_missing_lookups() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.verify(false).unwrap(), (Vec::new(), 0));
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      wtx
+        .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
+        .unwrap()
+        .remove(&inscription_id.store())
+        .unwrap();
+      wtx.commit().unwrap();
+    }
+
+    let issues = vec![format!(
+      "inscription {inscription_id} missing from id lookup table"
+    )];
+
+    assert_eq!(context.index.verify(false).unwrap(), (issues.clone(), 0));
+    assert_eq!(context.index.verify(true).unwrap(), (issues, 1));
+    assert_eq!(context.index.verify(false).unwrap(), (Vec::new(), 0));
+
+    assert!(context
+      .index
+      .get_inscription_entry(inscription_id)
+      .unwrap()
+      .is_some());
+  }
+
+  #[test]
+  fn check_rune_balances_reports_invariant_violations() {
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
 
-      wtx.commit().unwrap();
+    let outpoint = OutPoint::null();
 
-      context.tempdir
+    let entry = RuneEntry {
+      burned: 10,
+      supply: 100,
+      ..Default::default()
     };
 
-    let path = tempdir.path().to_owned();
+    let runes = [(id, entry)]
+      .into_iter()
+      .collect::<BTreeMap<RuneId, RuneEntry>>();
 
-    let delimiter = if cfg!(windows) { '\\' } else { '/' };
+    assert_eq!(
+      Index::check_rune_balances(&runes, &[(outpoint, vec![(id, 90)])]),
+      Vec::<String>::new(),
+    );
 
     assert_eq!(
-      Context::builder().tempdir(tempdir).try_build().err().unwrap().to_string(),
-      format!("index at `{}{delimiter}regtest{delimiter}index.redb` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {}, ord schema {SCHEMA_VERSION}", path.display(), u64::MAX));
+      Index::check_rune_balances(&runes, &[(outpoint, vec![(id, 80)])]),
+      ["rune A balances sum to 80 but supply minus burned is 90"],
+    );
+
+    assert_eq!(
+      Index::check_rune_balances(
+        &runes,
+        &[(
+          outpoint,
+          vec![
+            (id, 90),
+            (
+              RuneId {
+                height: 3,
+                index: 0
+              },
+              1
+            )
+          ]
+        )]
+      ),
+      [format!(
+        "output {outpoint} holds balance of unknown rune 3/0"
+      )],
+    );
+
+    let runes = [(
+      id,
+      RuneEntry {
+        burned: 200,
+        ..entry
+      },
+    )]
+    .into_iter()
+    .collect::<BTreeMap<RuneId, RuneEntry>>();
+
+    assert_eq!(
+      Index::check_rune_balances(&runes, &[]),
+      ["rune A burned 200 exceeds supply 100"],
+    );
   }
 
   #[test]
@@ -4445,6 +6336,96 @@ mod tests {
     }
   }
 
+  #[test]
+  fn transfers_from_shared_output_resolve_sender() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(2);
+
+    let events = context.index.subscribe_events();
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, inscription("text/plain", "foo").to_witness()),
+        (2, 0, 0, inscription("text/plain", "bar").to_witness()),
+      ],
+      ..Default::default()
+    });
+
+    let first = InscriptionId { txid, index: 0 };
+    let second = InscriptionId { txid, index: 1 };
+
+    context.mine_blocks(1);
+
+    let owner = context.rpc_server.tx(3, 1).output[0].script_pubkey.clone();
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Default::default())],
+      outputs: 2,
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let senders = events
+      .try_iter()
+      .flatten()
+      .filter_map(|event| match event {
+        event::Event::InscriptionTransferred {
+          inscription_id,
+          sender,
+          ..
+        } => Some((inscription_id, sender)),
+        _ => None,
+      })
+      .collect::<Vec<(InscriptionId, Option<ScriptBuf>)>>();
+
+    assert_eq!(
+      senders,
+      [(first, Some(owner.clone())), (second, Some(owner))]
+    );
+  }
+
+  #[test]
+  fn inscription_created_events_carry_metaprotocol() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let events = context.index.subscribe_events();
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some(r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#.into()),
+          metaprotocol: Some("brc-20".into()),
+          ..Default::default()
+        }
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(events.try_iter().flatten().any(|event| event
+      == event::Event::InscriptionCreated {
+        block_height: 2,
+        inscription_id: InscriptionId { txid, index: 0 },
+        inscription_number: 0,
+        location: SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        metaprotocol: Some("brc-20".into()),
+        sequence_number: 0,
+      }));
+  }
+
   #[test]
   fn reinscriptions_are_ordered_correctly_for_many_outpoints() {
     for context in Context::configurations() {
@@ -4495,7 +6476,245 @@ mod tests {
   }
 
   #[test]
-  fn recover_from_reorg() {
+  fn recover_from_reorg() {
+    for mut context in Context::configurations() {
+      context.index.set_durability(redb::Durability::Immediate);
+
+      context.mine_blocks(1);
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          1,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..Default::default()
+      });
+      let first_id = InscriptionId { txid, index: 0 };
+      let first_location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      };
+
+      context.mine_blocks(6);
+
+      context
+        .index
+        .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          2,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..Default::default()
+      });
+      let second_id = InscriptionId { txid, index: 0 };
+      let second_location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      };
+
+      context.mine_blocks(1);
+
+      context
+        .index
+        .assert_inscription_location(second_id, second_location, Some(100 * COIN_VALUE));
+
+      context.rpc_server.invalidate_tip();
+      context.mine_blocks(2);
+
+      context
+        .index
+        .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
+
+      assert!(!context.index.inscription_exists(second_id).unwrap());
+    }
+  }
+
+  #[test]
+  fn rollbacks_are_published_as_events() {
+    let mut context = Context::builder().build();
+    context.index.set_durability(redb::Durability::Immediate);
+
+    for _ in 0..3 {
+      context.mine_blocks(1);
+    }
+
+    let orphaned_block_hash = context.index.block_hash(None).unwrap().unwrap();
+
+    let events = context.index.subscribe_events();
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    let events = events.try_iter().flatten().collect::<Vec<event::Event>>();
+
+    let rollback = events
+      .iter()
+      .find(|event| event.is_rollback())
+      .unwrap()
+      .clone();
+
+    let event::Event::BlocksRolledBack {
+      block_height,
+      orphaned_block_hash: orphaned,
+    } = rollback
+    else {
+      unreachable!();
+    };
+
+    assert_eq!(orphaned, orphaned_block_hash);
+    assert!(block_height <= 3);
+
+    assert!(events.contains(&event::Event::BlockIndexed {
+      block_hash: context.index.block_hash(Some(4)).unwrap().unwrap(),
+      block_height: 4,
+    }));
+
+    let rtx = context.index.database.begin_read().unwrap();
+    assert_eq!(rtx.open_table(OUTBOX).unwrap().len().unwrap(), 0);
+  }
+
+  #[test]
+  fn events_are_stored_by_height() {
+    let context = Context::builder().arg("--index-events").build();
+
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.get_events_in_block(1).unwrap(),
+      Some(Vec::new())
+    );
+
+    assert_eq!(
+      context.index.get_events_in_block(2).unwrap(),
+      Some(vec![event::Event::InscriptionCreated {
+        block_height: 2,
+        inscription_id: InscriptionId { txid, index: 0 },
+        inscription_number: 0,
+        location: SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        metaprotocol: None,
+        sequence_number: 0,
+      }])
+    );
+
+    assert_eq!(context.index.get_events_in_block(3).unwrap(), None);
+  }
+
+  #[test]
+  fn recover_from_scripted_reorg() {
+    for mut context in Context::configurations() {
+      context.index.set_durability(redb::Durability::Immediate);
+
+      context.mine_blocks(1);
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          1,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..Default::default()
+      });
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      context.mine_blocks(1);
+
+      assert!(context.index.inscription_exists(inscription_id).unwrap());
+
+      let blocks = context.rpc_server.reorg(1, 2);
+
+      context.index.update().unwrap();
+
+      assert_eq!(context.index.block_count().unwrap(), 4);
+      assert_eq!(
+        context.index.block_hash(None).unwrap(),
+        Some(blocks[1].block_hash())
+      );
+      assert!(!context.index.inscription_exists(inscription_id).unwrap());
+    }
+  }
+
+  #[test]
+  fn recover_from_3_block_deep_and_consecutive_reorg() {
+    for mut context in Context::configurations() {
+      context.index.set_durability(redb::Durability::Immediate);
+
+      context.mine_blocks(1);
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          1,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..Default::default()
+      });
+      let first_id = InscriptionId { txid, index: 0 };
+      let first_location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      };
+
+      context.mine_blocks(10);
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          2,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
+        ..Default::default()
+      });
+      let second_id = InscriptionId { txid, index: 0 };
+      let second_location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      };
+
+      context.mine_blocks(1);
+
+      context
+        .index
+        .assert_inscription_location(second_id, second_location, Some(100 * COIN_VALUE));
+
+      context.rpc_server.invalidate_tip();
+      context.rpc_server.invalidate_tip();
+      context.rpc_server.invalidate_tip();
+
+      context.mine_blocks(4);
+
+      assert!(!context.index.inscription_exists(second_id).unwrap());
+
+      context.rpc_server.invalidate_tip();
+
+      context.mine_blocks(2);
+
+      context
+        .index
+        .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
+    }
+  }
+
+  #[test]
+  fn recover_from_very_unlikely_7_block_deep_reorg() {
     for mut context in Context::configurations() {
       context.index.set_durability(redb::Durability::Immediate);
 
@@ -4510,18 +6729,15 @@ mod tests {
         )],
         ..Default::default()
       });
+
+      context.mine_blocks(11);
+
       let first_id = InscriptionId { txid, index: 0 };
       let first_location = SatPoint {
         outpoint: OutPoint { txid, vout: 0 },
         offset: 0,
       };
 
-      context.mine_blocks(6);
-
-      context
-        .index
-        .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
-
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
         inputs: &[(
           2,
@@ -4531,179 +6747,476 @@ mod tests {
         )],
         ..Default::default()
       });
+
       let second_id = InscriptionId { txid, index: 0 };
       let second_location = SatPoint {
         outpoint: OutPoint { txid, vout: 0 },
         offset: 0,
       };
 
-      context.mine_blocks(1);
+      context.mine_blocks(7);
 
       context
         .index
         .assert_inscription_location(second_id, second_location, Some(100 * COIN_VALUE));
 
-      context.rpc_server.invalidate_tip();
-      context.mine_blocks(2);
+      for _ in 0..7 {
+        context.rpc_server.invalidate_tip();
+      }
+
+      context.mine_blocks(9);
+
+      assert!(!context.index.inscription_exists(second_id).unwrap());
 
       context
         .index
         .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
+    }
+  }
 
-      assert!(!context.index.inscription_exists(second_id).unwrap());
+  #[test]
+  fn recover_from_reorg_deeper_than_default_savepoints_allow() {
+    let mut context = Context::builder().args(["--max-savepoints", "4"]).build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    let first_id = InscriptionId { txid, index: 0 };
+    let first_location = SatPoint {
+      outpoint: OutPoint { txid, vout: 0 },
+      offset: 0,
+    };
+
+    for _ in 0..35 {
+      context.mine_blocks(1);
+    }
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    let second_id = InscriptionId { txid, index: 0 };
+    let second_location = SatPoint {
+      outpoint: OutPoint { txid, vout: 0 },
+      offset: 0,
+    };
+
+    context.mine_blocks(1);
+
+    context
+      .index
+      .assert_inscription_location(second_id, second_location, Some(100 * COIN_VALUE));
+
+    for _ in 0..25 {
+      context.rpc_server.invalidate_tip();
     }
+
+    context.mine_blocks(27);
+
+    assert!(!context.index.inscription_exists(second_id).unwrap());
+
+    context
+      .index
+      .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
   }
 
   #[test]
-  fn recover_from_3_block_deep_and_consecutive_reorg() {
-    for mut context in Context::configurations() {
-      context.index.set_durability(redb::Durability::Immediate);
+  fn recover_runes_from_reorg() {
+    let mut context = Context::builder().arg("--index-runes").build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    let events = context.index.subscribe_events();
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: 1000,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(99246114928149462)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    context.assert_runes(
+      [(
+        id,
+        RuneEntry {
+          etching: txid,
+          rune: Rune(99246114928149462),
+          supply: 1000,
+          timestamp: 2,
+          ..Default::default()
+        },
+      )],
+      [(OutPoint { txid, vout: 0 }, vec![(id, 1000)])],
+    );
+
+    assert!(events.try_iter().flatten().any(|event| event
+      == event::Event::RuneTransferred {
+        amount: 1000,
+        block_height: 2,
+        outpoint: OutPoint { txid, vout: 0 },
+        rune_id: id,
+        txid,
+      }));
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    context.assert_runes([], []);
+  }
+
+  #[test]
+  fn inscription_without_parent_tag_has_no_parent_entry() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      });
 
       context.mine_blocks(1);
 
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      assert!(context
+        .index
+        .get_inscription_entry(inscription_id)
+        .unwrap()
+        .unwrap()
+        .parent
+        .is_none());
+    }
+  }
+
+  #[test]
+  fn inscription_with_parent_tag_without_parent_has_no_parent_entry() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      });
+
+      context.mine_blocks(1);
+
+      let parent_inscription_id = InscriptionId {
+        txid: parent_txid,
+        index: 0,
+      };
+
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
         inputs: &[(
-          1,
+          2,
           0,
           0,
-          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parent: Some(parent_inscription_id.value()),
+            ..Default::default()
+          }
+          .to_witness(),
         )],
         ..Default::default()
       });
-      let first_id = InscriptionId { txid, index: 0 };
-      let first_location = SatPoint {
-        outpoint: OutPoint { txid, vout: 0 },
-        offset: 0,
-      };
 
-      context.mine_blocks(10);
+      context.mine_blocks(1);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      assert!(context
+        .index
+        .get_inscription_entry(inscription_id)
+        .unwrap()
+        .unwrap()
+        .parent
+        .is_none());
+    }
+  }
+
+  #[test]
+  fn inscription_with_parent_tag_and_parent_has_parent_entry() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      });
+
+      context.mine_blocks(1);
+
+      let parent_inscription_id = InscriptionId {
+        txid: parent_txid,
+        index: 0,
+      };
 
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
         inputs: &[(
           2,
+          1,
           0,
-          0,
-          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parent: Some(parent_inscription_id.value()),
+            ..Default::default()
+          }
+          .to_witness(),
         )],
         ..Default::default()
       });
-      let second_id = InscriptionId { txid, index: 0 };
-      let second_location = SatPoint {
-        outpoint: OutPoint { txid, vout: 0 },
-        offset: 0,
-      };
 
       context.mine_blocks(1);
 
-      context
-        .index
-        .assert_inscription_location(second_id, second_location, Some(100 * COIN_VALUE));
+      let inscription_id = InscriptionId { txid, index: 0 };
 
-      context.rpc_server.invalidate_tip();
-      context.rpc_server.invalidate_tip();
-      context.rpc_server.invalidate_tip();
+      assert_eq!(
+        context.index.get_parent_by_inscription_id(inscription_id),
+        parent_inscription_id
+      );
 
-      context.mine_blocks(4);
+      assert_eq!(
+        context
+          .index
+          .get_children_by_inscription_id(parent_inscription_id)
+          .unwrap(),
+        vec![inscription_id]
+      );
+    }
+  }
 
-      assert!(!context.index.inscription_exists(second_id).unwrap());
+  #[test]
+  fn collection_statistics_are_updated_as_children_are_created() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
 
-      context.rpc_server.invalidate_tip();
+      let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      });
 
-      context.mine_blocks(2);
+      context.mine_blocks(1);
 
-      context
+      let parent_inscription_id = InscriptionId {
+        txid: parent_txid,
+        index: 0,
+      };
+
+      assert_eq!(
+        context.index.get_collection(parent_inscription_id).unwrap(),
+        None
+      );
+
+      let child = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        parent: Some(parent_inscription_id.value()),
+        ..Default::default()
+      };
+
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 1, 0, child.to_witness())],
+        ..Default::default()
+      });
+
+      context.mine_blocks(1);
+
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(3, 1, 0, child.to_witness())],
+        ..Default::default()
+      });
+
+      context.mine_blocks(1);
+
+      assert_eq!(
+        context.index.get_collection(parent_inscription_id).unwrap(),
+        Some(CollectionEntry {
+          first_child_height: 3,
+          holders: 1,
+          last_child_height: 4,
+          supply: 2,
+        })
+      );
+
+      let (holders, more) = context
         .index
-        .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
+        .get_collection_holders_paginated(parent_inscription_id, 100, 0, Deadline::default())
+        .unwrap();
+
+      assert!(!more);
+      assert_eq!(holders.len(), 1);
+      assert_eq!(holders[0].1, 2);
+
+      assert_eq!(
+        context
+          .index
+          .get_collection_holders_paginated(
+            parent_inscription_id,
+            100,
+            0,
+            Deadline::after(Some(Duration::ZERO)),
+          )
+          .unwrap_err()
+          .downcast::<DeadlineExceeded>()
+          .unwrap(),
+        DeadlineExceeded { results: 0 },
+      );
     }
   }
 
   #[test]
-  fn recover_from_very_unlikely_7_block_deep_reorg() {
-    for mut context in Context::configurations() {
-      context.index.set_durability(redb::Durability::Immediate);
-
+  fn parents_can_be_in_preceding_input() {
+    for context in Context::configurations() {
       context.mine_blocks(1);
 
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(
-          1,
-          0,
-          0,
-          inscription("text/plain;charset=utf-8", "hello").to_witness(),
-        )],
+      let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
         ..Default::default()
       });
 
-      context.mine_blocks(11);
+      context.mine_blocks(2);
 
-      let first_id = InscriptionId { txid, index: 0 };
-      let first_location = SatPoint {
-        outpoint: OutPoint { txid, vout: 0 },
-        offset: 0,
+      let parent_inscription_id = InscriptionId {
+        txid: parent_txid,
+        index: 0,
       };
 
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(
-          2,
-          0,
-          0,
-          inscription("text/plain;charset=utf-8", "hello").to_witness(),
-        )],
+        inputs: &[
+          (2, 1, 0, Default::default()),
+          (
+            3,
+            0,
+            0,
+            Inscription {
+              content_type: Some("text/plain".into()),
+              body: Some("hello".into()),
+              parent: Some(parent_inscription_id.value()),
+              ..Default::default()
+            }
+            .to_witness(),
+          ),
+        ],
         ..Default::default()
       });
 
-      let second_id = InscriptionId { txid, index: 0 };
-      let second_location = SatPoint {
-        outpoint: OutPoint { txid, vout: 0 },
-        offset: 0,
-      };
-
-      context.mine_blocks(7);
-
-      context
-        .index
-        .assert_inscription_location(second_id, second_location, Some(100 * COIN_VALUE));
-
-      for _ in 0..7 {
-        context.rpc_server.invalidate_tip();
-      }
+      context.mine_blocks(1);
 
-      context.mine_blocks(9);
+      let inscription_id = InscriptionId { txid, index: 0 };
 
-      assert!(!context.index.inscription_exists(second_id).unwrap());
+      assert_eq!(
+        context.index.get_parent_by_inscription_id(inscription_id),
+        parent_inscription_id
+      );
 
-      context
-        .index
-        .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
+      assert_eq!(
+        context
+          .index
+          .get_children_by_inscription_id(parent_inscription_id)
+          .unwrap(),
+        vec![inscription_id]
+      );
     }
   }
 
   #[test]
-  fn inscription_without_parent_tag_has_no_parent_entry() {
+  fn parents_can_be_in_following_input() {
     for context in Context::configurations() {
       context.mine_blocks(1);
 
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
         inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
         ..Default::default()
       });
 
+      context.mine_blocks(2);
+
+      let parent_inscription_id = InscriptionId {
+        txid: parent_txid,
+        index: 0,
+      };
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[
+          (
+            3,
+            0,
+            0,
+            Inscription {
+              content_type: Some("text/plain".into()),
+              body: Some("hello".into()),
+              parent: Some(parent_inscription_id.value()),
+              ..Default::default()
+            }
+            .to_witness(),
+          ),
+          (2, 1, 0, Default::default()),
+        ],
+        ..Default::default()
+      });
+
       context.mine_blocks(1);
 
       let inscription_id = InscriptionId { txid, index: 0 };
 
-      assert!(context
-        .index
-        .get_inscription_entry(inscription_id)
-        .unwrap()
-        .unwrap()
-        .parent
-        .is_none());
+      assert_eq!(
+        context.index.get_parent_by_inscription_id(inscription_id),
+        parent_inscription_id
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_children_by_inscription_id(parent_inscription_id)
+          .unwrap(),
+        vec![inscription_id]
+      );
     }
   }
 
   #[test]
-  fn inscription_with_parent_tag_without_parent_has_no_parent_entry() {
+  fn inscription_with_invalid_parent_tag_and_parent_has_no_parent_entry() {
     for context in Context::configurations() {
       context.mine_blocks(1);
 
@@ -4722,12 +7235,18 @@ mod tests {
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
         inputs: &[(
           2,
-          0,
+          1,
           0,
           Inscription {
             content_type: Some("text/plain".into()),
             body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
+            parent: Some(
+              parent_inscription_id
+                .value()
+                .into_iter()
+                .chain(iter::once(0))
+                .collect(),
+            ),
             ..Default::default()
           }
           .to_witness(),
@@ -4750,33 +7269,51 @@ mod tests {
   }
 
   #[test]
-  fn inscription_with_parent_tag_and_parent_has_parent_entry() {
+  fn inscription_with_pointer() {
     for context in Context::configurations() {
       context.mine_blocks(1);
 
-      let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      let inscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        pointer: Some(100u64.to_le_bytes().to_vec()),
+        ..Default::default()
+      };
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription.to_witness())],
         ..Default::default()
       });
 
       context.mine_blocks(1);
 
-      let parent_inscription_id = InscriptionId {
-        txid: parent_txid,
-        index: 0,
-      };
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      context.index.assert_inscription_location(
+        inscription_id,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 100,
+        },
+        Some(50 * COIN_VALUE + 100),
+      );
+    }
+  }
+
+  #[test]
+  fn teleburn_claims_are_indexed() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
 
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
         inputs: &[(
-          2,
           1,
           0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parent: Some(parent_inscription_id.value()),
-            ..Default::default()
-          }
+          0,
+          inscription(
+            "application/json",
+            r#"{"p":"teleburn","chain":"ethereum","asset":"0xabc:1"}"#,
+          )
           .to_witness(),
         )],
         ..Default::default()
@@ -4784,56 +7321,103 @@ mod tests {
 
       context.mine_blocks(1);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
-
       assert_eq!(
-        context.index.get_parent_by_inscription_id(inscription_id),
-        parent_inscription_id
+        context
+          .index
+          .get_teleburn_claims(TargetChain::Ethereum, "0xabc:1")
+          .unwrap(),
+        [InscriptionId { txid, index: 0 }],
       );
 
       assert_eq!(
         context
           .index
-          .get_children_by_inscription_id(parent_inscription_id)
+          .get_teleburn_claims(TargetChain::Solana, "0xabc:1")
           .unwrap(),
-        vec![inscription_id]
+        Vec::<InscriptionId>::new(),
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_with_pointer_greater_than_output_value_assigned_default() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let inscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        pointer: Some((50 * COIN_VALUE).to_le_bytes().to_vec()),
+        ..Default::default()
+      };
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription.to_witness())],
+        ..Default::default()
+      });
+
+      context.mine_blocks(1);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      context.index.assert_inscription_location(
+        inscription_id,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        Some(50 * COIN_VALUE),
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_with_pointer_into_fee_ignored_and_assigned_default_location() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let inscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        pointer: Some((25 * COIN_VALUE).to_le_bytes().to_vec()),
+        ..Default::default()
+      };
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription.to_witness())],
+        fee: 25 * COIN_VALUE,
+        ..Default::default()
+      });
+
+      context.mine_blocks(1);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      context.index.assert_inscription_location(
+        inscription_id,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        Some(50 * COIN_VALUE),
       );
     }
   }
 
   #[test]
-  fn parents_can_be_in_preceding_input() {
+  fn inscription_with_pointer_is_cursed() {
     for context in Context::configurations() {
       context.mine_blocks(1);
 
-      let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      let inscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("pointer-child".into()),
+        pointer: Some(0u64.to_le_bytes().to_vec()),
         ..Default::default()
-      });
-
-      context.mine_blocks(2);
-
-      let parent_inscription_id = InscriptionId {
-        txid: parent_txid,
-        index: 0,
       };
 
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[
-          (2, 1, 0, Default::default()),
-          (
-            3,
-            0,
-            0,
-            Inscription {
-              content_type: Some("text/plain".into()),
-              body: Some("hello".into()),
-              parent: Some(parent_inscription_id.value()),
-              ..Default::default()
-            }
-            .to_witness(),
-          ),
-        ],
+        inputs: &[(1, 0, 0, inscription.to_witness())],
         ..Default::default()
       });
 
@@ -4841,63 +7425,93 @@ mod tests {
 
       let inscription_id = InscriptionId { txid, index: 0 };
 
-      assert_eq!(
-        context.index.get_parent_by_inscription_id(inscription_id),
-        parent_inscription_id
+      context.index.assert_inscription_location(
+        inscription_id,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        Some(50 * COIN_VALUE),
       );
 
       assert_eq!(
         context
           .index
-          .get_children_by_inscription_id(parent_inscription_id)
-          .unwrap(),
-        vec![inscription_id]
+          .get_inscription_entry(inscription_id)
+          .unwrap()
+          .unwrap()
+          .inscription_number,
+        -1
       );
     }
   }
 
   #[test]
-  fn parents_can_be_in_following_input() {
+  fn inscription_with_pointer_to_parent_is_cursed_reinscription() {
     for context in Context::configurations() {
       context.mine_blocks(1);
 
       let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        inputs: &[(1, 0, 0, inscription("text/plain", "parent").to_witness())],
         ..Default::default()
       });
 
-      context.mine_blocks(2);
+      context.mine_blocks(1);
 
       let parent_inscription_id = InscriptionId {
         txid: parent_txid,
         index: 0,
       };
 
+      let child_inscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("pointer-child".into()),
+        parent: Some(parent_inscription_id.value()),
+        pointer: Some(0u64.to_le_bytes().to_vec()),
+        ..Default::default()
+      };
+
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[
-          (
-            3,
-            0,
-            0,
-            Inscription {
-              content_type: Some("text/plain".into()),
-              body: Some("hello".into()),
-              parent: Some(parent_inscription_id.value()),
-              ..Default::default()
-            }
-            .to_witness(),
-          ),
-          (2, 1, 0, Default::default()),
-        ],
+        inputs: &[(2, 1, 0, child_inscription.to_witness())],
         ..Default::default()
       });
 
       context.mine_blocks(1);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+      let child_inscription_id = InscriptionId { txid, index: 0 };
+
+      context.index.assert_inscription_location(
+        parent_inscription_id,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        Some(50 * COIN_VALUE),
+      );
+
+      context.index.assert_inscription_location(
+        child_inscription_id,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        Some(50 * COIN_VALUE),
+      );
 
       assert_eq!(
-        context.index.get_parent_by_inscription_id(inscription_id),
+        context
+          .index
+          .get_inscription_entry(child_inscription_id)
+          .unwrap()
+          .unwrap()
+          .inscription_number,
+        -1
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_parent_by_inscription_id(child_inscription_id),
         parent_inscription_id
       );
 
@@ -4906,119 +7520,208 @@ mod tests {
           .index
           .get_children_by_inscription_id(parent_inscription_id)
           .unwrap(),
-        vec![inscription_id]
+        vec![child_inscription_id]
       );
     }
   }
 
   #[test]
-  fn inscription_with_invalid_parent_tag_and_parent_has_no_parent_entry() {
+  fn inscriptions_in_same_input_with_pointers_to_same_output() {
     for context in Context::configurations() {
       context.mine_blocks(1);
 
-      let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      let builder = script::Builder::new();
+
+      let builder = Inscription {
+        pointer: Some(100u64.to_le_bytes().to_vec()),
         ..Default::default()
-      });
+      }
+      .append_reveal_script_to_builder(builder);
 
-      context.mine_blocks(1);
+      let builder = Inscription {
+        pointer: Some(300_000u64.to_le_bytes().to_vec()),
+        ..Default::default()
+      }
+      .append_reveal_script_to_builder(builder);
 
-      let parent_inscription_id = InscriptionId {
-        txid: parent_txid,
-        index: 0,
-      };
+      let builder = Inscription {
+        pointer: Some(1_000_000u64.to_le_bytes().to_vec()),
+        ..Default::default()
+      }
+      .append_reveal_script_to_builder(builder);
+
+      let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
 
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(
-          2,
-          1,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parent: Some(
-              parent_inscription_id
-                .value()
-                .into_iter()
-                .chain(iter::once(0))
-                .collect(),
-            ),
-            ..Default::default()
-          }
-          .to_witness(),
-        )],
+        inputs: &[(1, 0, 0, witness)],
         ..Default::default()
       });
 
       context.mine_blocks(1);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+      let first = InscriptionId { txid, index: 0 };
+      let second = InscriptionId { txid, index: 1 };
+      let third = InscriptionId { txid, index: 2 };
 
-      assert!(context
-        .index
-        .get_inscription_entry(inscription_id)
-        .unwrap()
-        .unwrap()
-        .parent
-        .is_none());
+      context.index.assert_inscription_location(
+        first,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 100,
+        },
+        Some(50 * COIN_VALUE + 100),
+      );
+
+      context.index.assert_inscription_location(
+        second,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 300_000,
+        },
+        Some(50 * COIN_VALUE + 300_000),
+      );
+
+      context.index.assert_inscription_location(
+        third,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 1_000_000,
+        },
+        Some(50 * COIN_VALUE + 1_000_000),
+      );
     }
   }
 
   #[test]
-  fn inscription_with_pointer() {
+  fn inscriptions_in_same_input_with_pointers_to_different_outputs() {
     for context in Context::configurations() {
-      context.mine_blocks(1);
+      context.mine_blocks_with_subsidy(1, 300_000);
 
-      let inscription = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello".into()),
+      let builder = script::Builder::new();
+
+      let builder = Inscription {
         pointer: Some(100u64.to_le_bytes().to_vec()),
         ..Default::default()
-      };
+      }
+      .append_reveal_script_to_builder(builder);
+
+      let builder = Inscription {
+        pointer: Some(100_111u64.to_le_bytes().to_vec()),
+        ..Default::default()
+      }
+      .append_reveal_script_to_builder(builder);
+
+      let builder = Inscription {
+        pointer: Some(299_999u64.to_le_bytes().to_vec()),
+        ..Default::default()
+      }
+      .append_reveal_script_to_builder(builder);
+
+      let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
 
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription.to_witness())],
+        inputs: &[(1, 0, 0, witness)],
+        outputs: 3,
         ..Default::default()
       });
 
       context.mine_blocks(1);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+      let first = InscriptionId { txid, index: 0 };
+      let second = InscriptionId { txid, index: 1 };
+      let third = InscriptionId { txid, index: 2 };
 
       context.index.assert_inscription_location(
-        inscription_id,
+        first,
         SatPoint {
           outpoint: OutPoint { txid, vout: 0 },
           offset: 100,
         },
         Some(50 * COIN_VALUE + 100),
       );
+
+      context.index.assert_inscription_location(
+        second,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 1 },
+          offset: 111,
+        },
+        Some(50 * COIN_VALUE + 100_111),
+      );
+
+      context.index.assert_inscription_location(
+        third,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 2 },
+          offset: 99_999,
+        },
+        Some(50 * COIN_VALUE + 299_999),
+      );
     }
   }
 
   #[test]
-  fn inscription_with_pointer_greater_than_output_value_assigned_default() {
+  fn inscriptions_in_different_inputs_with_pointers_to_different_outputs() {
     for context in Context::configurations() {
-      context.mine_blocks(1);
+      context.mine_blocks(3);
 
-      let inscription = Inscription {
+      let inscription_for_second_output = Inscription {
         content_type: Some("text/plain".into()),
-        body: Some("hello".into()),
+        body: Some("hello jupiter".into()),
         pointer: Some((50 * COIN_VALUE).to_le_bytes().to_vec()),
         ..Default::default()
       };
 
+      let inscription_for_third_output = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello mars".into()),
+        pointer: Some((100 * COIN_VALUE).to_le_bytes().to_vec()),
+        ..Default::default()
+      };
+
+      let inscription_for_first_output = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello world".into()),
+        pointer: Some(0u64.to_le_bytes().to_vec()),
+        ..Default::default()
+      };
+
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription.to_witness())],
+        inputs: &[
+          (1, 0, 0, inscription_for_second_output.to_witness()),
+          (2, 0, 0, inscription_for_third_output.to_witness()),
+          (3, 0, 0, inscription_for_first_output.to_witness()),
+        ],
+        outputs: 3,
         ..Default::default()
       });
 
       context.mine_blocks(1);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+      let inscription_for_second_output = InscriptionId { txid, index: 0 };
+      let inscription_for_third_output = InscriptionId { txid, index: 1 };
+      let inscription_for_first_output = InscriptionId { txid, index: 2 };
 
       context.index.assert_inscription_location(
-        inscription_id,
+        inscription_for_second_output,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 1 },
+          offset: 0,
+        },
+        Some(100 * COIN_VALUE),
+      );
+
+      context.index.assert_inscription_location(
+        inscription_for_third_output,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 2 },
+          offset: 0,
+        },
+        Some(150 * COIN_VALUE),
+      );
+
+      context.index.assert_inscription_location(
+        inscription_for_first_output,
         SatPoint {
           outpoint: OutPoint { txid, vout: 0 },
           offset: 0,
@@ -5029,58 +7732,106 @@ mod tests {
   }
 
   #[test]
-  fn inscription_with_pointer_into_fee_ignored_and_assigned_default_location() {
+  fn inscriptions_in_different_inputs_with_pointers_to_same_output() {
     for context in Context::configurations() {
-      context.mine_blocks(1);
+      context.mine_blocks(3);
 
-      let inscription = Inscription {
+      let first_inscription = Inscription {
         content_type: Some("text/plain".into()),
-        body: Some("hello".into()),
-        pointer: Some((25 * COIN_VALUE).to_le_bytes().to_vec()),
+        body: Some("hello jupiter".into()),
+        ..Default::default()
+      };
+
+      let second_inscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello mars".into()),
+        pointer: Some(1u64.to_le_bytes().to_vec()),
+        ..Default::default()
+      };
+
+      let third_inscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello world".into()),
+        pointer: Some(2u64.to_le_bytes().to_vec()),
         ..Default::default()
       };
 
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription.to_witness())],
-        fee: 25 * COIN_VALUE,
+        inputs: &[
+          (1, 0, 0, first_inscription.to_witness()),
+          (2, 0, 0, second_inscription.to_witness()),
+          (3, 0, 0, third_inscription.to_witness()),
+        ],
+        outputs: 1,
         ..Default::default()
       });
 
       context.mine_blocks(1);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+      let first_inscription_id = InscriptionId { txid, index: 0 };
+      let second_inscription_id = InscriptionId { txid, index: 1 };
+      let third_inscription_id = InscriptionId { txid, index: 2 };
 
       context.index.assert_inscription_location(
-        inscription_id,
+        first_inscription_id,
         SatPoint {
           outpoint: OutPoint { txid, vout: 0 },
           offset: 0,
         },
         Some(50 * COIN_VALUE),
       );
+
+      context.index.assert_inscription_location(
+        second_inscription_id,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 1,
+        },
+        Some(50 * COIN_VALUE + 1),
+      );
+
+      context.index.assert_inscription_location(
+        third_inscription_id,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 2,
+        },
+        Some(50 * COIN_VALUE + 2),
+      );
     }
   }
 
   #[test]
-  fn inscription_with_pointer_is_cursed() {
+  fn inscriptions_with_pointers_to_same_sat_one_becomes_cursed_reinscriptions() {
     for context in Context::configurations() {
-      context.mine_blocks(1);
+      context.mine_blocks(2);
 
       let inscription = Inscription {
         content_type: Some("text/plain".into()),
-        body: Some("pointer-child".into()),
+        body: Some("hello jupiter".into()),
+        ..Default::default()
+      };
+
+      let cursed_reinscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello mars".into()),
         pointer: Some(0u64.to_le_bytes().to_vec()),
         ..Default::default()
       };
 
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription.to_witness())],
+        inputs: &[
+          (1, 0, 0, inscription.to_witness()),
+          (2, 0, 0, cursed_reinscription.to_witness()),
+        ],
+        outputs: 2,
         ..Default::default()
       });
 
       context.mine_blocks(1);
 
       let inscription_id = InscriptionId { txid, index: 0 };
+      let cursed_reinscription_id = InscriptionId { txid, index: 1 };
 
       context.index.assert_inscription_location(
         inscription_id,
@@ -5091,6 +7842,15 @@ mod tests {
         Some(50 * COIN_VALUE),
       );
 
+      context.index.assert_inscription_location(
+        cursed_reinscription_id,
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        Some(50 * COIN_VALUE),
+      );
+
       assert_eq!(
         context
           .index
@@ -5098,497 +7858,670 @@ mod tests {
           .unwrap()
           .unwrap()
           .inscription_number,
+        0
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_inscription_entry(cursed_reinscription_id)
+          .unwrap()
+          .unwrap()
+          .inscription_number,
         -1
       );
     }
   }
 
   #[test]
-  fn inscription_with_pointer_to_parent_is_cursed_reinscription() {
+  fn inscribe_into_fee() {
     for context in Context::configurations() {
       context.mine_blocks(1);
 
-      let parent_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "parent").to_witness())],
-        ..Default::default()
-      });
-
-      context.mine_blocks(1);
-
-      let parent_inscription_id = InscriptionId {
-        txid: parent_txid,
-        index: 0,
-      };
-
-      let child_inscription = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("pointer-child".into()),
-        parent: Some(parent_inscription_id.value()),
-        pointer: Some(0u64.to_le_bytes().to_vec()),
-        ..Default::default()
-      };
+      let inscription = Inscription::default();
 
       let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(2, 1, 0, child_inscription.to_witness())],
+        inputs: &[(1, 0, 0, inscription.to_witness())],
+        fee: 50 * COIN_VALUE,
         ..Default::default()
       });
 
-      context.mine_blocks(1);
+      let blocks = context.mine_blocks(1);
 
-      let child_inscription_id = InscriptionId { txid, index: 0 };
+      let inscription_id = InscriptionId { txid, index: 0 };
 
       context.index.assert_inscription_location(
-        parent_inscription_id,
+        inscription_id,
         SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0,
+          outpoint: OutPoint {
+            txid: blocks[0].txdata[0].txid(),
+            vout: 0,
+          },
+          offset: 50 * COIN_VALUE,
         },
         Some(50 * COIN_VALUE),
       );
+    }
+  }
+
+  #[test]
+  fn inscribe_into_fee_with_reduced_subsidy() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let inscription = Inscription::default();
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription.to_witness())],
+        fee: 50 * COIN_VALUE,
+        ..Default::default()
+      });
+
+      let blocks = context.mine_blocks_with_subsidy(1, 25 * COIN_VALUE);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
 
       context.index.assert_inscription_location(
-        child_inscription_id,
+        inscription_id,
         SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0,
+          outpoint: OutPoint {
+            txid: blocks[0].txdata[0].txid(),
+            vout: 0,
+          },
+          offset: 50 * COIN_VALUE,
         },
         Some(50 * COIN_VALUE),
       );
+    }
+  }
 
-      assert_eq!(
-        context
-          .index
-          .get_inscription_entry(child_inscription_id)
-          .unwrap()
-          .unwrap()
-          .inscription_number,
-        -1
-      );
+  #[test]
+  fn index_in_use_by_another_process_is_reported() {
+    let context = Context::builder().build();
+
+    assert_eq!(
+      Index::open(&context.options).err().unwrap().to_string(),
+      format!(
+        "index at `{}` is in use by another process (pid {}, committed height 0)",
+        context.index.path.display(),
+        process::id(),
+      ),
+    );
+  }
+
+  #[test]
+  fn index_lock_timeout_waits_for_index_to_be_released() {
+    let Context {
+      index,
+      options,
+      rpc_server: _rpc_server,
+      tempdir: _tempdir,
+    } = Context::builder().arg("--index-lock-timeout=10").build();
+
+    let holder = thread::spawn(move || {
+      thread::sleep(Duration::from_millis(250));
+      drop(index);
+    });
+
+    let index = Index::open(&options).unwrap();
+
+    holder.join().unwrap();
+
+    assert_eq!(
+      Lease::load(&index.path),
+      Some(Lease {
+        height: Some(0),
+        pid: process::id(),
+      }),
+    );
+  }
+
+  #[test]
+  fn events_are_published_to_nats() {
+    use std::{
+      io::{BufRead, BufReader},
+      net::TcpListener,
+      sync::mpsc,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("nats://{}", listener.local_addr().unwrap());
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+      for stream in listener.incoming() {
+        let mut stream = stream.unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let sender = sender.clone();
+
+        thread::spawn(move || {
+          stream.write_all(b"INFO {}\r\n").unwrap();
+
+          let mut line = String::new();
+          while reader.read_line(&mut line).unwrap() > 0 {
+            if line == "PING\r\n" {
+              stream.write_all(b"PONG\r\n").unwrap();
+            } else if let Some(lengths) = line.strip_prefix("HPUB ") {
+              let lengths = lengths
+                .split_whitespace()
+                .skip(1)
+                .map(|len| len.parse().unwrap())
+                .collect::<Vec<usize>>();
+              let mut message = vec![0; lengths[1] + 2];
+              reader.read_exact(&mut message).unwrap();
+              sender.send(message[lengths[0]..lengths[1]].to_vec()).ok();
+            }
+            line.clear();
+          }
+        });
+      }
+    });
+
+    let context = Context::builder().args(["--nats-url", &url]).build();
+
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
 
-      assert_eq!(
-        context
-          .index
-          .get_parent_by_inscription_id(child_inscription_id),
-        parent_inscription_id
-      );
+    let events = receiver
+      .try_iter()
+      .map(|payload| serde_json::from_slice::<event::Event>(&payload).unwrap())
+      .collect::<Vec<event::Event>>();
 
-      assert_eq!(
-        context
-          .index
-          .get_children_by_inscription_id(parent_inscription_id)
-          .unwrap(),
-        vec![child_inscription_id]
-      );
-    }
+    assert_eq!(
+      events
+        .iter()
+        .filter(|event| matches!(event, event::Event::BlockIndexed { .. }))
+        .count(),
+      3,
+    );
+
+    assert!(events.contains(&event::Event::InscriptionCreated {
+      block_height: 2,
+      inscription_id: InscriptionId { txid, index: 0 },
+      inscription_number: 0,
+      location: SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      },
+      metaprotocol: None,
+      sequence_number: 0,
+    }));
   }
 
   #[test]
-  fn inscriptions_in_same_input_with_pointers_to_same_output() {
-    for context in Context::configurations() {
-      context.mine_blocks(1);
+  fn events_are_delivered_to_nats_once_it_is_reachable() {
+    use std::{
+      io::{BufRead, BufReader},
+      net::TcpListener,
+      sync::mpsc,
+    };
 
-      let builder = script::Builder::new();
+    let address = TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap();
 
-      let builder = Inscription {
-        pointer: Some(100u64.to_le_bytes().to_vec()),
-        ..Default::default()
-      }
-      .append_reveal_script_to_builder(builder);
+    let context = Context::builder()
+      .args(["--nats-url", &format!("nats://{address}")])
+      .build();
 
-      let builder = Inscription {
-        pointer: Some(300_000u64.to_le_bytes().to_vec()),
-        ..Default::default()
+    context.mine_blocks(1);
+
+    let rtx = context.index.database.begin_read().unwrap();
+    assert_eq!(rtx.open_table(OUTBOX).unwrap().len().unwrap(), 2);
+    drop(rtx);
+
+    let listener = TcpListener::bind(address).unwrap();
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+      let mut stream = listener.incoming().next().unwrap().unwrap();
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+      stream.write_all(b"INFO {}\r\n").unwrap();
+
+      let mut line = String::new();
+      while reader.read_line(&mut line).unwrap() > 0 {
+        if line == "PING\r\n" {
+          stream.write_all(b"PONG\r\n").unwrap();
+        } else if let Some(lengths) = line.strip_prefix("HPUB ") {
+          let lengths = lengths
+            .split_whitespace()
+            .skip(1)
+            .map(|len| len.parse().unwrap())
+            .collect::<Vec<usize>>();
+          let mut message = vec![0; lengths[1] + 2];
+          reader.read_exact(&mut message).unwrap();
+          sender.send(message[lengths[0]..lengths[1]].to_vec()).ok();
+        }
+        line.clear();
       }
-      .append_reveal_script_to_builder(builder);
+    });
 
-      let builder = Inscription {
-        pointer: Some(1_000_000u64.to_le_bytes().to_vec()),
-        ..Default::default()
+    context.mine_blocks(1);
+
+    assert_eq!(
+      receiver
+        .try_iter()
+        .map(|payload| serde_json::from_slice::<event::Event>(&payload).unwrap())
+        .map(|event| event.block_height())
+        .collect::<Vec<u32>>(),
+      [0, 1, 2],
+    );
+
+    let rtx = context.index.database.begin_read().unwrap();
+    assert_eq!(rtx.open_table(OUTBOX).unwrap().len().unwrap(), 0);
+  }
+
+  #[test]
+  fn events_published_to_nats_are_filtered() {
+    use std::{
+      io::{BufRead, BufReader},
+      net::TcpListener,
+      sync::mpsc,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("nats://{}", listener.local_addr().unwrap());
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+      for stream in listener.incoming() {
+        let mut stream = stream.unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let sender = sender.clone();
+
+        thread::spawn(move || {
+          stream.write_all(b"INFO {}\r\n").unwrap();
+
+          let mut line = String::new();
+          while reader.read_line(&mut line).unwrap() > 0 {
+            if line == "PING\r\n" {
+              stream.write_all(b"PONG\r\n").unwrap();
+            } else if let Some(lengths) = line.strip_prefix("HPUB ") {
+              let lengths = lengths
+                .split_whitespace()
+                .skip(1)
+                .map(|len| len.parse().unwrap())
+                .collect::<Vec<usize>>();
+              let mut message = vec![0; lengths[1] + 2];
+              reader.read_exact(&mut message).unwrap();
+              sender.send(message[lengths[0]..lengths[1]].to_vec()).ok();
+            }
+            line.clear();
+          }
+        });
       }
-      .append_reveal_script_to_builder(builder);
+    });
 
-      let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+    let context = Context::builder()
+      .args([
+        "--nats-url",
+        &url,
+        "--nats-filter",
+        r#"type == "inscription_created" || type == "block_indexed" && block_height > 1"#,
+      ])
+      .build();
 
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, witness)],
-        ..Default::default()
-      });
+    context.mine_blocks(1);
 
-      context.mine_blocks(1);
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
 
-      let first = InscriptionId { txid, index: 0 };
-      let second = InscriptionId { txid, index: 1 };
-      let third = InscriptionId { txid, index: 2 };
+    let blockhash = context.mine_blocks(1)[0].block_hash();
 
-      context.index.assert_inscription_location(
-        first,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 100,
-        },
-        Some(50 * COIN_VALUE + 100),
-      );
+    let events = receiver
+      .try_iter()
+      .map(|payload| serde_json::from_slice::<event::Event>(&payload).unwrap())
+      .collect::<Vec<event::Event>>();
 
-      context.index.assert_inscription_location(
-        second,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 300_000,
+    assert_eq!(
+      events,
+      [
+        event::Event::InscriptionCreated {
+          block_height: 2,
+          inscription_id: InscriptionId { txid, index: 0 },
+          inscription_number: 0,
+          location: SatPoint {
+            outpoint: OutPoint { txid, vout: 0 },
+            offset: 0,
+          },
+          metaprotocol: None,
+          sequence_number: 0,
         },
-        Some(50 * COIN_VALUE + 300_000),
-      );
-
-      context.index.assert_inscription_location(
-        third,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 1_000_000,
+        event::Event::BlockIndexed {
+          block_hash: blockhash,
+          block_height: 2,
         },
-        Some(50 * COIN_VALUE + 1_000_000),
-      );
-    }
+      ],
+    );
   }
 
   #[test]
-  fn inscriptions_in_same_input_with_pointers_to_different_outputs() {
-    for context in Context::configurations() {
-      context.mine_blocks_with_subsidy(1, 300_000);
-
-      let builder = script::Builder::new();
+  fn inscription_content_is_added_to_ipfs() {
+    use std::{
+      io::{BufRead, BufReader},
+      net::TcpListener,
+    };
 
-      let builder = Inscription {
-        pointer: Some(100u64.to_le_bytes().to_vec()),
-        ..Default::default()
-      }
-      .append_reveal_script_to_builder(builder);
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
 
-      let builder = Inscription {
-        pointer: Some(100_111u64.to_le_bytes().to_vec()),
-        ..Default::default()
-      }
-      .append_reveal_script_to_builder(builder);
+    thread::spawn(move || {
+      for stream in listener.incoming() {
+        let mut stream = stream.unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
 
-      let builder = Inscription {
-        pointer: Some(299_999u64.to_le_bytes().to_vec()),
-        ..Default::default()
-      }
-      .append_reveal_script_to_builder(builder);
+        let mut content_length = 0;
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+          if let Some(value) = line.to_lowercase().strip_prefix("content-length: ") {
+            content_length = value.trim().parse().unwrap();
+          }
+          line.clear();
+        }
 
-      let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
 
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, witness)],
-        outputs: 3,
-        ..Default::default()
-      });
+        let response = r#"{"Name":"content","Hash":"bafkreihello","Size":"5"}"#;
+        write!(
+          stream,
+          "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+          response.len()
+        )
+        .unwrap();
+      }
+    });
 
-      context.mine_blocks(1);
+    let context = Context::builder().args(["--ipfs-api-url", &url]).build();
 
-      let first = InscriptionId { txid, index: 0 };
-      let second = InscriptionId { txid, index: 1 };
-      let third = InscriptionId { txid, index: 2 };
+    context.mine_blocks(1);
 
-      context.index.assert_inscription_location(
-        first,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 100,
-        },
-        Some(50 * COIN_VALUE + 100),
-      );
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
 
-      context.index.assert_inscription_location(
-        second,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 1 },
-          offset: 111,
-        },
-        Some(50 * COIN_VALUE + 100_111),
-      );
+    context.mine_blocks(1);
 
-      context.index.assert_inscription_location(
-        third,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 2 },
-          offset: 99_999,
-        },
-        Some(50 * COIN_VALUE + 299_999),
-      );
-    }
+    assert_eq!(
+      Index::inscription_info(
+        &context.index,
+        InscriptionQuery::Id(InscriptionId { txid, index: 0 })
+      )
+      .unwrap()
+      .unwrap()
+      .cid
+      .as_deref(),
+      Some("bafkreihello"),
+    );
   }
 
   #[test]
-  fn inscriptions_in_different_inputs_with_pointers_to_different_outputs() {
-    for context in Context::configurations() {
-      context.mine_blocks(3);
-
-      let inscription_for_second_output = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello jupiter".into()),
-        pointer: Some((50 * COIN_VALUE).to_le_bytes().to_vec()),
-        ..Default::default()
-      };
+  fn hidden_inscription_content_is_not_added_to_ipfs() {
+    use std::net::TcpListener;
 
-      let inscription_for_third_output = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello mars".into()),
-        pointer: Some((100 * COIN_VALUE).to_le_bytes().to_vec()),
-        ..Default::default()
-      };
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
 
-      let inscription_for_first_output = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello world".into()),
-        pointer: Some(0u64.to_le_bytes().to_vec()),
-        ..Default::default()
-      };
+    let requests = Arc::new(atomic::AtomicUsize::new(0));
 
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[
-          (1, 0, 0, inscription_for_second_output.to_witness()),
-          (2, 0, 0, inscription_for_third_output.to_witness()),
-          (3, 0, 0, inscription_for_first_output.to_witness()),
-        ],
-        outputs: 3,
-        ..Default::default()
+    {
+      let requests = requests.clone();
+      thread::spawn(move || {
+        for stream in listener.incoming() {
+          drop(stream);
+          requests.fetch_add(1, atomic::Ordering::Relaxed);
+        }
       });
+    }
 
-      context.mine_blocks(1);
+    let context = Context::builder().args(["--ipfs-api-url", &url]).build();
 
-      let inscription_for_second_output = InscriptionId { txid, index: 0 };
-      let inscription_for_third_output = InscriptionId { txid, index: 1 };
-      let inscription_for_first_output = InscriptionId { txid, index: 2 };
+    context.mine_blocks(1);
 
-      context.index.assert_inscription_location(
-        inscription_for_second_output,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 1 },
-          offset: 0,
-        },
-        Some(100 * COIN_VALUE),
-      );
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
 
-      context.index.assert_inscription_location(
-        inscription_for_third_output,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 2 },
-          offset: 0,
-        },
-        Some(150 * COIN_VALUE),
-      );
+    let inscription_id = InscriptionId { txid, index: 0 };
 
-      context.index.assert_inscription_location(
-        inscription_for_first_output,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0,
-        },
-        Some(50 * COIN_VALUE),
-      );
-    }
+    fs::write(
+      context.index.options.data_dir().join("hidden.txt"),
+      format!("{inscription_id}\n"),
+    )
+    .unwrap();
+
+    context.mine_blocks(1);
+
+    assert_eq!(requests.load(atomic::Ordering::Relaxed), 0);
+
+    assert_eq!(
+      Index::inscription_info(&context.index, InscriptionQuery::Id(inscription_id))
+        .unwrap()
+        .unwrap()
+        .cid,
+      None,
+    );
+
+    assert_eq!(
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(OUTBOX)
+        .unwrap()
+        .len()
+        .unwrap(),
+      0,
+    );
   }
 
   #[test]
-  fn inscriptions_in_different_inputs_with_pointers_to_same_output() {
-    for context in Context::configurations() {
-      context.mine_blocks(3);
-
-      let first_inscription = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello jupiter".into()),
-        ..Default::default()
-      };
-
-      let second_inscription = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello mars".into()),
-        pointer: Some(1u64.to_le_bytes().to_vec()),
-        ..Default::default()
-      };
+  fn outbox_queues_inscription_references_instead_of_contents() {
+    let url = format!(
+      "http://{}",
+      std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+    );
 
-      let third_inscription = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello world".into()),
-        pointer: Some(2u64.to_le_bytes().to_vec()),
-        ..Default::default()
-      };
+    let context = Context::builder().args(["--ipfs-api-url", &url]).build();
 
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[
-          (1, 0, 0, first_inscription.to_witness()),
-          (2, 0, 0, second_inscription.to_witness()),
-          (3, 0, 0, third_inscription.to_witness()),
-        ],
-        outputs: 1,
-        ..Default::default()
-      });
+    context.mine_blocks(1);
 
-      context.mine_blocks(1);
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain", [b'a'; 1000]).to_witness(),
+      )],
+      ..Default::default()
+    });
 
-      let first_inscription_id = InscriptionId { txid, index: 0 };
-      let second_inscription_id = InscriptionId { txid, index: 1 };
-      let third_inscription_id = InscriptionId { txid, index: 2 };
+    context.mine_blocks(1);
 
-      context.index.assert_inscription_location(
-        first_inscription_id,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0,
-        },
-        Some(50 * COIN_VALUE),
-      );
+    let rtx = context.index.database.begin_read().unwrap();
 
-      context.index.assert_inscription_location(
-        second_inscription_id,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 1,
-        },
-        Some(50 * COIN_VALUE + 1),
-      );
+    let entries = rtx
+      .open_table(OUTBOX)
+      .unwrap()
+      .iter()
+      .unwrap()
+      .map(|result| result.unwrap().1.value().len())
+      .collect::<Vec<usize>>();
 
-      context.index.assert_inscription_location(
-        third_inscription_id,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 2,
-        },
-        Some(50 * COIN_VALUE + 2),
-      );
-    }
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0] < 1000);
   }
 
   #[test]
-  fn inscriptions_with_pointers_to_same_sat_one_becomes_cursed_reinscriptions() {
-    for context in Context::configurations() {
-      context.mine_blocks(2);
+  fn shutdown_checkpoint_is_cleared_by_commits() {
+    let context = Context::builder().build();
 
-      let inscription = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello jupiter".into()),
-        ..Default::default()
-      };
+    assert_eq!(context.index.shutdown_checkpoint().unwrap(), None);
 
-      let cursed_reinscription = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello mars".into()),
-        pointer: Some(0u64.to_le_bytes().to_vec()),
-        ..Default::default()
-      };
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      Index::set_statistic(
+        &mut wtx.open_table(STATISTIC_TO_COUNT).unwrap(),
+        Statistic::ShutdownCheckpoint,
+        1,
+      )
+      .unwrap();
+      wtx.commit().unwrap();
+    }
 
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[
-          (1, 0, 0, inscription.to_witness()),
-          (2, 0, 0, cursed_reinscription.to_witness()),
-        ],
-        outputs: 2,
-        ..Default::default()
-      });
+    assert_eq!(context.index.shutdown_checkpoint().unwrap(), Some(1));
 
-      context.mine_blocks(1);
+    context.mine_blocks(1);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
-      let cursed_reinscription_id = InscriptionId { txid, index: 1 };
+    assert_eq!(context.index.shutdown_checkpoint().unwrap(), None);
+  }
 
-      context.index.assert_inscription_location(
-        inscription_id,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0,
-        },
-        Some(50 * COIN_VALUE),
-      );
+  #[test]
+  fn corrupt_rune_balances_are_quarantined() {
+    let context = Context::builder().arg("--index-runes").build();
 
-      context.index.assert_inscription_location(
-        cursed_reinscription_id,
-        SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0,
-        },
-        Some(50 * COIN_VALUE),
-      );
+    context.mine_blocks(1);
 
-      assert_eq!(
-        context
-          .index
-          .get_inscription_entry(inscription_id)
-          .unwrap()
-          .unwrap()
-          .inscription_number,
-        0
-      );
+    let outpoint = OutPoint {
+      txid: context.rpc_server.tx(1, 0).txid(),
+      vout: 0,
+    };
 
-      assert_eq!(
-        context
-          .index
-          .get_inscription_entry(cursed_reinscription_id)
-          .unwrap()
-          .unwrap()
-          .inscription_number,
-        -1
-      );
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      wtx
+        .open_table(OUTPOINT_TO_RUNE_BALANCES)
+        .unwrap()
+        .insert(&outpoint.store(), [0xff, 0xff].as_slice())
+        .unwrap();
+      wtx.commit().unwrap();
     }
-  }
 
-  #[test]
-  fn inscribe_into_fee() {
-    for context in Context::configurations() {
-      context.mine_blocks(1);
+    assert_eq!(
+      context
+        .index
+        .get_rune_balance(
+          outpoint,
+          RuneId {
+            height: 1,
+            index: 0
+          }
+        )
+        .unwrap_err()
+        .to_string(),
+      "truncated varint at offset 0 of rune balances",
+    );
 
-      let inscription = Inscription::default();
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      ..Default::default()
+    });
 
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription.to_witness())],
-        fee: 50 * COIN_VALUE,
-        ..Default::default()
-      });
+    context.mine_blocks(1);
 
-      let blocks = context.mine_blocks(1);
+    assert_eq!(context.index.status().unwrap().quarantined_entries, 1);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+    let rtx = context.index.database.begin_read().unwrap();
+    let quarantined_entry_to_value = rtx.open_table(QUARANTINED_ENTRY_TO_VALUE).unwrap();
 
-      context.index.assert_inscription_location(
-        inscription_id,
-        SatPoint {
-          outpoint: OutPoint {
-            txid: blocks[0].txdata[0].txid(),
-            vout: 0,
-          },
-          offset: 50 * COIN_VALUE,
-        },
-        Some(50 * COIN_VALUE),
-      );
-    }
+    assert_eq!(
+      quarantined_entry_to_value
+        .get(format!("OUTPOINT_TO_RUNE_BALANCES:{outpoint}").as_str())
+        .unwrap()
+        .unwrap()
+        .value(),
+      [0xff, 0xff],
+    );
+
+    drop(quarantined_entry_to_value);
+    drop(rtx);
+
+    context
+      .index
+      .reindex_protocol(protocol::Protocol::Runes, None)
+      .unwrap();
+
+    assert_eq!(context.index.status().unwrap().quarantined_entries, 0);
+
+    assert!(context
+      .index
+      .database
+      .begin_read()
+      .unwrap()
+      .open_table(QUARANTINED_ENTRY_TO_VALUE)
+      .unwrap()
+      .is_empty()
+      .unwrap());
   }
 
   #[test]
-  fn inscribe_into_fee_with_reduced_subsidy() {
-    for context in Context::configurations() {
-      context.mine_blocks(1);
+  fn interrupted_rune_reindex_must_be_resumed() {
+    let context = Context::builder().arg("--index-runes").build();
 
-      let inscription = Inscription::default();
+    context.mine_blocks(3);
 
-      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription.to_witness())],
-        fee: 50 * COIN_VALUE,
-        ..Default::default()
-      });
+    assert_eq!(
+      context
+        .index
+        .reindex_protocol(protocol::Protocol::Runes, Some(1))
+        .unwrap_err()
+        .to_string(),
+      "no interrupted rune reindex to resume",
+    );
 
-      let blocks = context.mine_blocks_with_subsidy(1, 25 * COIN_VALUE);
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::RuneReindexHeight.key(), 2)
+        .unwrap();
+      wtx.commit().unwrap();
+    }
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+    assert_eq!(
+      context.index.update().unwrap_err().to_string(),
+      "rune reindex was interrupted at block 2, resume with `ord protocol reindex --protocol runes --from 2`",
+    );
 
-      context.index.assert_inscription_location(
-        inscription_id,
-        SatPoint {
-          outpoint: OutPoint {
-            txid: blocks[0].txdata[0].txid(),
-            vout: 0,
-          },
-          offset: 50 * COIN_VALUE,
-        },
-        Some(50 * COIN_VALUE),
-      );
-    }
+    assert_eq!(
+      context
+        .index
+        .reindex_protocol(protocol::Protocol::Runes, Some(1))
+        .unwrap_err()
+        .to_string(),
+      "interrupted rune reindex must be resumed from block 2",
+    );
+
+    assert_eq!(
+      context
+        .index
+        .reindex_protocol(protocol::Protocol::Runes, Some(2))
+        .unwrap(),
+      2,
+    );
+
+    assert_eq!(context.index.interrupted_rune_reindex().unwrap(), None);
+
+    context.mine_blocks(1);
   }
 }
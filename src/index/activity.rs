@@ -0,0 +1,218 @@
+use super::{event::Event, updater::BlockData, *};
+
+/// Most entries `/activity/<ADDRESS>` returns per page.
+pub(crate) const MAX_ACTIVITY_PAGE_SIZE: usize = 100;
+
+/// Inscription or rune movement into or out of an address, recorded while
+/// indexing with `--index-addresses` and served, newest first, by
+/// `/activity/<ADDRESS>`. Entries of every protocol share this shape, with
+/// the fields which don't apply to an entry's `type` left `null`, so that new
+/// protocols can be added without breaking clients.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Activity {
+  pub amount: Option<u128>,
+  pub block_height: u32,
+  pub direction: Direction,
+  pub inscription_id: Option<InscriptionId>,
+  #[serde(rename = "type")]
+  pub kind: ActivityKind,
+  pub metaprotocol: Option<String>,
+  pub outpoint: OutPoint,
+  pub rune_id: Option<RuneId>,
+  pub txid: Txid,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+  InscriptionCreated,
+  InscriptionTransferred,
+  RuneTransferred,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+  Received,
+  Sent,
+}
+
+impl Direction {
+  fn bit(self) -> u64 {
+    match self {
+      Self::Received => 0,
+      Self::Sent => 1,
+    }
+  }
+}
+
+/// Position of an entry in an address's feed, ordered by block height, then
+/// by the position of its event among the block's events, then by direction,
+/// so that a transfer from an address back to itself produces two entries.
+/// Cursors returned by `/activity/<ADDRESS>` are these positions.
+fn cursor(height: u32, event: usize, direction: Direction) -> u64 {
+  (u64::from(height) << 32) | (u64::try_from(event).unwrap() << 1) | direction.bit()
+}
+
+/// Record the activity produced by `events`, all of which belong to `block`
+/// at `height`, against the addresses involved. Receiving addresses are those
+/// of the block's outputs, and sending addresses are taken from `spent`, the
+/// script pubkeys of the outputs the block spent.
+pub(crate) fn record(
+  wtx: &WriteTransaction,
+  height: u32,
+  block: &BlockData,
+  events: &[Event],
+  spent: &HashMap<OutPoint, ScriptBuf>,
+) -> Result {
+  let mut script_pubkey_to_activity = wtx.open_table(SCRIPT_PUBKEY_TO_ACTIVITY)?;
+
+  let transactions = block
+    .txdata
+    .iter()
+    .map(|(tx, txid)| (*txid, tx))
+    .collect::<HashMap<Txid, &Transaction>>();
+
+  let receiver = |outpoint: OutPoint| {
+    transactions
+      .get(&outpoint.txid)
+      .and_then(|tx| tx.output.get(usize::try_from(outpoint.vout).ok()?))
+      .map(|output| output.script_pubkey.clone())
+  };
+
+  for (i, event) in events.iter().enumerate() {
+    let (activity, sender) = match event {
+      Event::InscriptionCreated {
+        inscription_id,
+        location,
+        metaprotocol,
+        ..
+      } => (
+        Activity {
+          amount: None,
+          block_height: height,
+          direction: Direction::Received,
+          inscription_id: Some(*inscription_id),
+          kind: ActivityKind::InscriptionCreated,
+          metaprotocol: metaprotocol.clone(),
+          outpoint: location.outpoint,
+          rune_id: None,
+          txid: inscription_id.txid,
+        },
+        None,
+      ),
+      Event::InscriptionTransferred {
+        inscription_id,
+        new_location,
+        old_location,
+        sender,
+        ..
+      } => (
+        Activity {
+          amount: None,
+          block_height: height,
+          direction: Direction::Received,
+          inscription_id: Some(*inscription_id),
+          kind: ActivityKind::InscriptionTransferred,
+          metaprotocol: None,
+          outpoint: new_location.outpoint,
+          rune_id: None,
+          txid: new_location.outpoint.txid,
+        },
+        sender
+          .clone()
+          .or_else(|| spent.get(&old_location.outpoint).cloned()),
+      ),
+      Event::RuneTransferred {
+        amount,
+        outpoint,
+        rune_id,
+        txid,
+        ..
+      } => (
+        Activity {
+          amount: Some(*amount),
+          block_height: height,
+          direction: Direction::Received,
+          inscription_id: None,
+          kind: ActivityKind::RuneTransferred,
+          metaprotocol: None,
+          outpoint: *outpoint,
+          rune_id: Some(*rune_id),
+          txid: *txid,
+        },
+        None,
+      ),
+      _ => continue,
+    };
+
+    if let Some(script_pubkey) = receiver(activity.outpoint) {
+      script_pubkey_to_activity.insert(
+        (
+          script_pubkey.as_bytes(),
+          cursor(height, i, Direction::Received),
+        ),
+        serde_json::to_vec(&activity)?.as_slice(),
+      )?;
+    }
+
+    if let Some(script_pubkey) = sender {
+      script_pubkey_to_activity.insert(
+        (script_pubkey.as_bytes(), cursor(height, i, Direction::Sent)),
+        serde_json::to_vec(&Activity {
+          direction: Direction::Sent,
+          ..activity
+        })?
+        .as_slice(),
+      )?;
+    }
+  }
+
+  Ok(())
+}
+
+impl Index {
+  /// Up to `limit` entries of the activity feed of `script_pubkey`, newest
+  /// first, starting before `cursor`, or with the newest entry if `cursor`
+  /// is `None`, along with the cursor of the next page, if there is one.
+  /// Requires `--index-addresses`.
+  pub(crate) fn get_address_activity(
+    &self,
+    script_pubkey: &Script,
+    cursor: Option<u64>,
+    limit: usize,
+  ) -> Result<(Vec<Activity>, Option<u64>)> {
+    let rtx = self.database.begin_read()?;
+
+    let script_pubkey_to_activity = rtx.open_table(SCRIPT_PUBKEY_TO_ACTIVITY)?;
+
+    let start: (&[u8], u64) = (script_pubkey.as_bytes(), 0);
+    let end: (&[u8], u64) = (script_pubkey.as_bytes(), cursor.unwrap_or(u64::MAX));
+
+    let mut activity = Vec::new();
+
+    for result in script_pubkey_to_activity.range(start..end)?.rev() {
+      let (key, entry) = result?;
+
+      if activity.len() == limit {
+        return Ok((activity, Some(key.value().1 + 1)));
+      }
+
+      activity.push(serde_json::from_slice(entry.value())?);
+    }
+
+    Ok((activity, None))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cursors_order_entries() {
+    assert!(cursor(1, 0, Direction::Sent) < cursor(2, 0, Direction::Received));
+    assert!(cursor(2, 0, Direction::Sent) < cursor(2, 1, Direction::Received));
+    assert!(cursor(2, 1, Direction::Received) < cursor(2, 1, Direction::Sent));
+  }
+}
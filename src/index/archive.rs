@@ -0,0 +1,111 @@
+use {super::*, reqwest::Url};
+
+/// Client for an Esplora-compatible HTTP API, used to fetch blocks and
+/// transactions that a pruned Bitcoin Core node no longer has. Everything
+/// fetched is checked against the hash that Bitcoin Core reports, so the
+/// archive does not need to be trusted.
+pub(crate) struct Archive {
+  client: reqwest::Client,
+  url: Url,
+}
+
+impl Archive {
+  pub(crate) fn new(url: &str) -> Result<Self> {
+    let mut url = Url::parse(url).with_context(|| format!("invalid block archive url `{url}`"))?;
+
+    if !url.path().ends_with('/') {
+      url.set_path(&format!("{}/", url.path()));
+    }
+
+    Ok(Self {
+      client: reqwest::Client::new(),
+      url,
+    })
+  }
+
+  pub(crate) async fn get_block(&self, hash: BlockHash) -> Result<Block> {
+    let block: Block =
+      consensus::encode::deserialize(&self.get(&format!("block/{hash}/raw")).await?)
+        .with_context(|| format!("block archive returned invalid block {hash}"))?;
+
+    ensure!(
+      block.block_hash() == hash,
+      "block archive returned block {} instead of {hash}",
+      block.block_hash(),
+    );
+
+    ensure!(
+      block.check_merkle_root(),
+      "block archive returned block {hash} with invalid merkle root"
+    );
+
+    Ok(block)
+  }
+
+  pub(crate) async fn get_transaction(&self, txid: Txid) -> Result<Transaction> {
+    let transaction: Transaction =
+      consensus::encode::deserialize(&self.get(&format!("tx/{txid}/raw")).await?)
+        .with_context(|| format!("block archive returned invalid transaction {txid}"))?;
+
+    ensure!(
+      transaction.txid() == txid,
+      "block archive returned transaction {} instead of {txid}",
+      transaction.txid(),
+    );
+
+    Ok(transaction)
+  }
+
+  async fn get(&self, path: &str) -> Result<Vec<u8>> {
+    let url = self.url.join(path)?;
+
+    Ok(
+      self
+        .client
+        .get(url.clone())
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("failed to fetch {url} from block archive"))?
+        .bytes()
+        .await?
+        .to_vec(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn urls_are_treated_as_directories() {
+    assert_eq!(
+      Archive::new("https://example.com/api")
+        .unwrap()
+        .url
+        .join("block/0/raw")
+        .unwrap()
+        .as_str(),
+      "https://example.com/api/block/0/raw",
+    );
+
+    assert_eq!(
+      Archive::new("https://example.com/api/")
+        .unwrap()
+        .url
+        .join("tx/0/raw")
+        .unwrap()
+        .as_str(),
+      "https://example.com/api/tx/0/raw",
+    );
+  }
+
+  #[test]
+  fn invalid_urls_are_rejected() {
+    assert_eq!(
+      Archive::new("example.com").err().unwrap().to_string(),
+      "invalid block archive url `example.com`",
+    );
+  }
+}
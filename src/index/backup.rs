@@ -0,0 +1,178 @@
+use {
+  super::*,
+  redb::{ReadTransaction, TableError},
+};
+
+/// Contents of a backup made by [`Index::backup`].
+pub(crate) struct Backup {
+  pub(crate) blocks_indexed: u32,
+  pub(crate) schema_version: u64,
+  pub(crate) tables: BTreeMap<String, u64>,
+}
+
+fn copy_table<K: RedbKey + 'static, V: RedbValue + 'static>(
+  rtx: &ReadTransaction,
+  wtx: &WriteTransaction,
+  tables: &mut BTreeMap<String, u64>,
+  definition: TableDefinition<K, V>,
+) -> Result {
+  // tables which are only created once they're first written to, like
+  // `TRANSACTION_ID_TO_TRANSACTION`, may not exist yet
+  let source = match rtx.open_table(definition) {
+    Ok(source) => source,
+    Err(TableError::TableDoesNotExist(_)) => return Ok(()),
+    Err(err) => return Err(err.into()),
+  };
+  let mut destination = wtx.open_table(definition)?;
+
+  let mut entries = 0;
+
+  for result in source.iter()? {
+    let (key, value) = result?;
+    destination.insert(key.value(), value.value())?;
+    entries += 1;
+  }
+
+  tables.insert(definition.name().into(), entries);
+
+  Ok(())
+}
+
+fn copy_multimap_table<K: RedbKey + 'static, V: RedbKey + 'static>(
+  rtx: &ReadTransaction,
+  wtx: &WriteTransaction,
+  tables: &mut BTreeMap<String, u64>,
+  definition: MultimapTableDefinition<K, V>,
+) -> Result {
+  let source = rtx.open_multimap_table(definition)?;
+  let mut destination = wtx.open_multimap_table(definition)?;
+
+  let mut entries = 0;
+
+  for result in source.iter()? {
+    let (key, values) = result?;
+    for value in values {
+      destination.insert(key.value(), value?.value())?;
+      entries += 1;
+    }
+  }
+
+  tables.insert(definition.name().into(), entries);
+
+  Ok(())
+}
+
+impl Index {
+  /// Copy every table into a new index at `path`. Tables are copied from a
+  /// single read transaction, which sees the index as of the last commit, so
+  /// the backup is consistent even if blocks are committed while it is made.
+  pub(crate) fn backup(&self, path: &Path) -> Result<Backup> {
+    ensure!(
+      !path.exists(),
+      "backup destination `{}` already exists",
+      path.display()
+    );
+
+    let rtx = self.database.begin_read()?;
+
+    let backup = Database::builder().create(path)?;
+
+    let wtx = backup.begin_write()?;
+
+    let mut tables = BTreeMap::new();
+
+    copy_multimap_table(&rtx, &wtx, &mut tables, HEIGHT_TO_ADDRESS_CHANGE)?;
+    copy_multimap_table(&rtx, &wtx, &mut tables, SATPOINT_TO_SEQUENCE_NUMBER)?;
+    copy_multimap_table(&rtx, &wtx, &mut tables, SAT_TO_SEQUENCE_NUMBER)?;
+    copy_multimap_table(&rtx, &wtx, &mut tables, SCRIPT_PUBKEY_TO_OUTPOINT)?;
+    copy_multimap_table(&rtx, &wtx, &mut tables, SEQUENCE_NUMBER_TO_CHILDREN)?;
+    copy_multimap_table(&rtx, &wtx, &mut tables, TELEBURN_CLAIM_TO_INSCRIPTION_ID)?;
+    copy_table(&rtx, &wtx, &mut tables, CHILD_SEQUENCE_NUMBER_TO_OWNER)?;
+    copy_table(&rtx, &wtx, &mut tables, COLLECTION_HOLDER_TO_CHILD_COUNT)?;
+    copy_table(&rtx, &wtx, &mut tables, HEIGHT_TO_BLOCK_HEADER)?;
+    copy_table(&rtx, &wtx, &mut tables, HEIGHT_TO_EVENTS)?;
+    copy_table(&rtx, &wtx, &mut tables, HEIGHT_TO_INSCRIPTION_FEES)?;
+    copy_table(&rtx, &wtx, &mut tables, HEIGHT_TO_INSCRIPTION_MOVES)?;
+    copy_table(&rtx, &wtx, &mut tables, HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    copy_table(&rtx, &wtx, &mut tables, HOME_INSCRIPTIONS)?;
+    copy_table(&rtx, &wtx, &mut tables, INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+    copy_table(
+      &rtx,
+      &wtx,
+      &mut tables,
+      INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
+    )?;
+    copy_table(&rtx, &wtx, &mut tables, INSCRIPTION_SORT_KEYS)?;
+    copy_table(&rtx, &wtx, &mut tables, OUTBOX)?;
+    copy_table(&rtx, &wtx, &mut tables, OUTPOINT_TO_RUNE_BALANCES)?;
+    copy_table(&rtx, &wtx, &mut tables, OUTPOINT_TO_SAT_RANGES)?;
+    copy_table(&rtx, &wtx, &mut tables, OUTPOINT_TO_SCRIPT_PUBKEY)?;
+    copy_table(&rtx, &wtx, &mut tables, OUTPOINT_TO_VALUE)?;
+    copy_table(&rtx, &wtx, &mut tables, PROTOCOL_TABLE_TO_MIGRATION_CURSOR)?;
+    copy_table(&rtx, &wtx, &mut tables, PROTOCOL_TABLE_TO_SCHEMA_VERSION)?;
+    copy_table(&rtx, &wtx, &mut tables, QUARANTINED_ENTRY_TO_VALUE)?;
+    copy_table(&rtx, &wtx, &mut tables, RUNE_ID_TO_RUNE_ENTRY)?;
+    copy_table(&rtx, &wtx, &mut tables, RUNE_TO_RUNE_ID)?;
+    copy_table(&rtx, &wtx, &mut tables, SATPOINT_TO_OWNER)?;
+    copy_table(&rtx, &wtx, &mut tables, SAT_TO_SATPOINT)?;
+    copy_table(&rtx, &wtx, &mut tables, SCRIPT_PUBKEY_TO_ACTIVITY)?;
+    copy_table(
+      &rtx,
+      &wtx,
+      &mut tables,
+      SCRIPT_PUBKEY_TO_RUNE_BALANCE_HISTORY,
+    )?;
+    copy_table(&rtx, &wtx, &mut tables, SEQUENCE_NUMBER_TO_CID)?;
+    copy_table(&rtx, &wtx, &mut tables, SEQUENCE_NUMBER_TO_COLLECTION)?;
+    copy_table(&rtx, &wtx, &mut tables, SEQUENCE_NUMBER_TO_CONTENT_VERDICTS)?;
+    copy_table(
+      &rtx,
+      &wtx,
+      &mut tables,
+      SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY,
+    )?;
+    copy_table(
+      &rtx,
+      &wtx,
+      &mut tables,
+      SEQUENCE_NUMBER_TO_RECURSIVE_ENDPOINTS,
+    )?;
+    copy_table(&rtx, &wtx, &mut tables, SEQUENCE_NUMBER_TO_RUNE_ID)?;
+    copy_table(&rtx, &wtx, &mut tables, SEQUENCE_NUMBER_TO_SATPOINT)?;
+    copy_table(&rtx, &wtx, &mut tables, SEQUENCE_NUMBER_TO_TRANSFERS)?;
+    copy_table(&rtx, &wtx, &mut tables, STATISTIC_TO_COUNT)?;
+    copy_table(&rtx, &wtx, &mut tables, SUBSCRIPTION_ID_TO_SUBSCRIPTION)?;
+    copy_table(&rtx, &wtx, &mut tables, TRANSACTION_ID_TO_RUNE)?;
+    copy_table(&rtx, &wtx, &mut tables, TRANSACTION_ID_TO_TRANSACTION)?;
+    copy_table(&rtx, &wtx, &mut tables, WATCH_ID_TO_MOVE)?;
+    copy_table(&rtx, &wtx, &mut tables, WATCH_ID_TO_WATCH)?;
+    copy_table(
+      &rtx,
+      &wtx,
+      &mut tables,
+      WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP,
+    )?;
+
+    wtx.commit()?;
+
+    let blocks_indexed = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .and_then(|result| result.ok())
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
+
+    let schema_version = rtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Schema.key())?
+      .map(|schema_version| schema_version.value())
+      .unwrap_or_default();
+
+    Ok(Backup {
+      blocks_indexed,
+      schema_version,
+      tables,
+    })
+  }
+}
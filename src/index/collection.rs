@@ -0,0 +1,91 @@
+use super::*;
+
+/// The tables holding collection statistics, which are updated when children
+/// are created or move, both by the updater and by delta imports.
+pub(super) struct Collections<'a, 'db, 'tx> {
+  pub(super) child_sequence_number_to_owner: &'a mut Table<'db, 'tx, u32, &'static [u8]>,
+  pub(super) collection_holder_to_child_count: &'a mut Table<'db, 'tx, (u32, &'static [u8]), u64>,
+  pub(super) sequence_number_to_collection: &'a mut Table<'db, 'tx, u32, CollectionEntryValue>,
+}
+
+impl<'a, 'db, 'tx> Collections<'a, 'db, 'tx> {
+  /// Update the statistics of the collection of `parent` after `child` was
+  /// created or moved to an output paying `owner` in the block at `height`,
+  /// or lost if `owner` is `None`.
+  pub(super) fn update(
+    &mut self,
+    height: u32,
+    parent: u32,
+    child: u32,
+    owner: Option<&Script>,
+    created: bool,
+  ) -> Result {
+    let mut collection = self
+      .sequence_number_to_collection
+      .get(parent)?
+      .map(|entry| CollectionEntry::load(entry.value()))
+      .unwrap_or(CollectionEntry {
+        first_child_height: height,
+        holders: 0,
+        last_child_height: height,
+        supply: 0,
+      });
+
+    if created {
+      collection.last_child_height = height;
+      collection.supply += 1;
+    }
+
+    let old_owner = self
+      .child_sequence_number_to_owner
+      .remove(child)?
+      .map(|owner| owner.value().to_vec());
+
+    if let Some(old_owner) = old_owner {
+      let key = (parent, old_owner.as_slice());
+
+      let count = self
+        .collection_holder_to_child_count
+        .get(key)?
+        .unwrap()
+        .value();
+
+      if count == 1 {
+        self.collection_holder_to_child_count.remove(key)?;
+        collection.holders -= 1;
+      } else {
+        self
+          .collection_holder_to_child_count
+          .insert(key, count - 1)?;
+      }
+    }
+
+    if let Some(owner) = owner {
+      let key = (parent, owner.as_bytes());
+
+      let count = self
+        .collection_holder_to_child_count
+        .get(key)?
+        .map(|count| count.value())
+        .unwrap_or_default();
+
+      if count == 0 {
+        collection.holders += 1;
+      }
+
+      self
+        .collection_holder_to_child_count
+        .insert(key, count + 1)?;
+
+      self
+        .child_sequence_number_to_owner
+        .insert(child, owner.as_bytes())?;
+    }
+
+    self
+      .sequence_number_to_collection
+      .insert(parent, collection.store())?;
+
+    Ok(())
+  }
+}
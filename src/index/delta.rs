@@ -0,0 +1,479 @@
+//! Binary format used by `ord index export --output` and `ord index import`.
+//!
+//! A delta stream begins with the eight byte magic `ORDDELTA` followed by a
+//! single format version byte, and is followed by zero or more block records
+//! and an end marker, which is a height of `u32::MAX`. A stream without an end
+//! marker is truncated, and is rejected. All integers are little endian.
+//!
+//! | field        | encoding                                          |
+//! |--------------|---------------------------------------------------|
+//! | height       | `u32`                                             |
+//! | header       | 80 byte consensus encoded block header            |
+//! | fees         | `option<48 bytes>`, the block's inscription fees  |
+//! | inscriptions | `u32` count followed by `count` inscriptions      |
+//! | moves        | `u32` count followed by `count` satpoint moves    |
+//! | transfers    | `u32` count followed by `count` transfers         |
+//!
+//! An inscription is its index entry followed by its satpoint at the end of
+//! the block and what was derived from its content:
+//! charms `u16`, fee `u64`, height `u32`, txid 32 bytes, index `u32`,
+//! inscription number `i32`, parent `option<u32>`, sat `option<u64>`,
+//! sequence number `u32`, timestamp `u32`, a 44 byte satpoint, owner
+//! `option<bytes>`, recursive endpoints `u16`, and teleburn claims, a `u32`
+//! count followed by `count` `bytes`. Options are a `u8` tag, `0` for none and
+//! `1` for some, followed by the value if present, and `bytes` are a `u32`
+//! length followed by `length` bytes.
+//!
+//! A satpoint move is a sequence number `u32` followed by the 44 byte satpoint
+//! that the inscription with that sequence number was last moved to in the
+//! block, which has the null outpoint if the inscription was lost to fees, and
+//! the owner of that satpoint, `option<bytes>`.
+//!
+//! A transfer is a sequence number `u32` followed by a 108 byte transfer
+//! entry, for every transfer of an inscription in the block, in order.
+
+use {
+  super::*,
+  std::io::{Read, Write},
+};
+
+const END: u32 = u32::MAX;
+const MAGIC: &[u8; 8] = b"ORDDELTA";
+const VERSION: u8 = 2;
+
+#[derive(Debug, PartialEq)]
+pub(super) struct BlockDelta {
+  pub(super) fees: Option<InscriptionFeesValue>,
+  pub(super) header: HeaderValue,
+  pub(super) height: u32,
+  pub(super) inscriptions: Vec<InscriptionDelta>,
+  pub(super) moves: Vec<MoveDelta>,
+  pub(super) transfers: Vec<(u32, TransferValue)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct InscriptionDelta {
+  pub(super) entry: InscriptionEntryValue,
+  pub(super) owner: Option<Vec<u8>>,
+  pub(super) recursive_endpoints: u16,
+  pub(super) satpoint: SatPointValue,
+  pub(super) teleburn_claims: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct MoveDelta {
+  pub(super) owner: Option<Vec<u8>>,
+  pub(super) satpoint: SatPointValue,
+  pub(super) sequence_number: u32,
+}
+
+impl BlockDelta {
+  pub(super) fn write_preamble(writer: &mut impl Write) -> Result {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION])?;
+    Ok(())
+  }
+
+  pub(super) fn write_end(writer: &mut impl Write) -> Result {
+    writer.write_all(&END.to_le_bytes())?;
+    Ok(())
+  }
+
+  pub(super) fn read_preamble(reader: &mut impl Read) -> Result {
+    let magic: [u8; 8] = read_array(reader)?;
+
+    ensure!(&magic == MAGIC, "input is not an ord index delta stream");
+
+    let [version] = read_array::<1>(reader)?;
+
+    ensure!(
+      version == VERSION,
+      "unsupported index delta version {version}, expected {VERSION}"
+    );
+
+    Ok(())
+  }
+
+  pub(super) fn encode(&self, writer: &mut impl Write) -> Result {
+    writer.write_all(&self.height.to_le_bytes())?;
+    writer.write_all(&self.header)?;
+
+    match &self.fees {
+      Some(fees) => {
+        writer.write_all(&[1])?;
+        writer.write_all(fees)?;
+      }
+      None => writer.write_all(&[0])?,
+    }
+
+    writer.write_all(&u32::try_from(self.inscriptions.len())?.to_le_bytes())?;
+
+    for inscription in &self.inscriptions {
+      let (charms, fee, height, id, inscription_number, parent, sat, sequence_number, timestamp) =
+        inscription.entry;
+
+      writer.write_all(&charms.to_le_bytes())?;
+      writer.write_all(&fee.to_le_bytes())?;
+      writer.write_all(&height.to_le_bytes())?;
+      writer.write_all(&InscriptionId::load(id).txid.store())?;
+      writer.write_all(&id.2.to_le_bytes())?;
+      writer.write_all(&inscription_number.to_le_bytes())?;
+
+      match parent {
+        Some(parent) => {
+          writer.write_all(&[1])?;
+          writer.write_all(&parent.to_le_bytes())?;
+        }
+        None => writer.write_all(&[0])?,
+      }
+
+      match sat {
+        Some(sat) => {
+          writer.write_all(&[1])?;
+          writer.write_all(&sat.to_le_bytes())?;
+        }
+        None => writer.write_all(&[0])?,
+      }
+
+      writer.write_all(&sequence_number.to_le_bytes())?;
+      writer.write_all(&timestamp.to_le_bytes())?;
+      writer.write_all(&inscription.satpoint)?;
+      write_owner(writer, inscription.owner.as_deref())?;
+      writer.write_all(&inscription.recursive_endpoints.to_le_bytes())?;
+
+      writer.write_all(&u32::try_from(inscription.teleburn_claims.len())?.to_le_bytes())?;
+
+      for claim in &inscription.teleburn_claims {
+        write_bytes(writer, claim.as_bytes())?;
+      }
+    }
+
+    writer.write_all(&u32::try_from(self.moves.len())?.to_le_bytes())?;
+
+    for delta in &self.moves {
+      writer.write_all(&delta.sequence_number.to_le_bytes())?;
+      writer.write_all(&delta.satpoint)?;
+      write_owner(writer, delta.owner.as_deref())?;
+    }
+
+    writer.write_all(&u32::try_from(self.transfers.len())?.to_le_bytes())?;
+
+    for (sequence_number, transfer) in &self.transfers {
+      writer.write_all(&sequence_number.to_le_bytes())?;
+      writer.write_all(transfer)?;
+    }
+
+    Ok(())
+  }
+
+  /// Decode the next block record, returning `None` at the end marker.
+  pub(super) fn decode(reader: &mut impl Read) -> Result<Option<Self>> {
+    let height = u32::from_le_bytes(read_array(reader)?);
+
+    if height == END {
+      return Ok(None);
+    }
+
+    let header: HeaderValue = read_array(reader)?;
+
+    let fees = match read_array::<1>(reader)? {
+      [0] => None,
+      [1] => Some(read_array(reader)?),
+      [tag] => bail!("invalid fees tag {tag} in index delta for block {height}"),
+    };
+
+    let count = u32::from_le_bytes(read_array(reader)?);
+
+    let mut inscriptions = Vec::new();
+
+    for _ in 0..count {
+      let charms = u16::from_le_bytes(read_array(reader)?);
+      let fee = u64::from_le_bytes(read_array(reader)?);
+      let inscription_height = u32::from_le_bytes(read_array(reader)?);
+      let txid = Txid::load(read_array(reader)?);
+      let index = u32::from_le_bytes(read_array(reader)?);
+      let inscription_number = i32::from_le_bytes(read_array(reader)?);
+
+      let parent = match read_array::<1>(reader)? {
+        [0] => None,
+        [1] => Some(u32::from_le_bytes(read_array(reader)?)),
+        [tag] => bail!("invalid parent tag {tag} in index delta for block {height}"),
+      };
+
+      let sat = match read_array::<1>(reader)? {
+        [0] => None,
+        [1] => Some(u64::from_le_bytes(read_array(reader)?)),
+        [tag] => bail!("invalid sat tag {tag} in index delta for block {height}"),
+      };
+
+      let sequence_number = u32::from_le_bytes(read_array(reader)?);
+      let timestamp = u32::from_le_bytes(read_array(reader)?);
+      let satpoint: SatPointValue = read_array(reader)?;
+      let owner = read_owner(reader, height)?;
+      let recursive_endpoints = u16::from_le_bytes(read_array(reader)?);
+
+      let mut teleburn_claims = Vec::new();
+
+      for _ in 0..u32::from_le_bytes(read_array(reader)?) {
+        teleburn_claims
+          .push(String::from_utf8(read_bytes(reader)?).with_context(|| {
+            format!("invalid teleburn claim in index delta for block {height}")
+          })?);
+      }
+
+      inscriptions.push(InscriptionDelta {
+        entry: (
+          charms,
+          fee,
+          inscription_height,
+          InscriptionId { txid, index }.store(),
+          inscription_number,
+          parent,
+          sat,
+          sequence_number,
+          timestamp,
+        ),
+        owner,
+        recursive_endpoints,
+        satpoint,
+        teleburn_claims,
+      });
+    }
+
+    let count = u32::from_le_bytes(read_array(reader)?);
+
+    let mut moves = Vec::new();
+
+    for _ in 0..count {
+      let sequence_number = u32::from_le_bytes(read_array(reader)?);
+      let satpoint = read_array::<44>(reader)?;
+
+      moves.push(MoveDelta {
+        owner: read_owner(reader, height)?,
+        satpoint,
+        sequence_number,
+      });
+    }
+
+    let count = u32::from_le_bytes(read_array(reader)?);
+
+    let mut transfers = Vec::new();
+
+    for _ in 0..count {
+      let sequence_number = u32::from_le_bytes(read_array(reader)?);
+      transfers.push((sequence_number, read_array(reader)?));
+    }
+
+    Ok(Some(Self {
+      fees,
+      header,
+      height,
+      inscriptions,
+      moves,
+      transfers,
+    }))
+  }
+}
+
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> Result {
+  writer.write_all(&u32::try_from(bytes.len())?.to_le_bytes())?;
+  writer.write_all(bytes)?;
+  Ok(())
+}
+
+fn write_owner(writer: &mut impl Write, owner: Option<&[u8]>) -> Result {
+  match owner {
+    Some(owner) => {
+      writer.write_all(&[1])?;
+      write_bytes(writer, owner)
+    }
+    None => {
+      writer.write_all(&[0])?;
+      Ok(())
+    }
+  }
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> Result<[u8; N]> {
+  let mut array = [0; N];
+  reader
+    .read_exact(&mut array)
+    .context("index delta stream is truncated")?;
+  Ok(array)
+}
+
+fn read_bytes(reader: &mut impl Read) -> Result<Vec<u8>> {
+  let len = u32::from_le_bytes(read_array(reader)?);
+  let mut bytes = Vec::new();
+  reader.take(len.into()).read_to_end(&mut bytes)?;
+  ensure!(
+    bytes.len() == usize::try_from(len).unwrap(),
+    "index delta stream is truncated"
+  );
+  Ok(bytes)
+}
+
+fn read_owner(reader: &mut impl Read, height: u32) -> Result<Option<Vec<u8>>> {
+  match read_array::<1>(reader)? {
+    [0] => Ok(None),
+    [1] => Ok(Some(read_bytes(reader)?)),
+    [tag] => bail!("invalid owner tag {tag} in index delta for block {height}"),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn block_deltas_round_trip() {
+    let deltas = [
+      BlockDelta {
+        fees: None,
+        header: Chain::Mainnet.genesis_block().unwrap().header.store(),
+        height: 0,
+        inscriptions: Vec::new(),
+        moves: Vec::new(),
+        transfers: Vec::new(),
+      },
+      BlockDelta {
+        fees: Some(
+          InscriptionFeesEntry {
+            creation_fees: 7,
+            creations: 2,
+            ..Default::default()
+          }
+          .store(),
+        ),
+        header: Chain::Regtest.genesis_block().unwrap().header.store(),
+        height: 1,
+        inscriptions: vec![
+          InscriptionDelta {
+            entry: (1, 2, 1, inscription_id(1).store(), 0, None, Some(50), 0, 3),
+            owner: Some(vec![0x51]),
+            recursive_endpoints: 0b101,
+            satpoint: satpoint(1, 0).store(),
+            teleburn_claims: vec!["ethereum:0x0".into()],
+          },
+          InscriptionDelta {
+            entry: (0, 5, 1, inscription_id(2).store(), -1, Some(0), None, 1, 3),
+            owner: None,
+            recursive_endpoints: 0,
+            satpoint: unbound_outpoint_satpoint().store(),
+            teleburn_claims: Vec::new(),
+          },
+        ],
+        moves: vec![MoveDelta {
+          owner: Some(Vec::new()),
+          satpoint: satpoint(2, 100).store(),
+          sequence_number: 7,
+        }],
+        transfers: vec![(
+          7,
+          TransferEntry {
+            fee: 1,
+            height: 1,
+            new_satpoint: satpoint(2, 100),
+            old_satpoint: satpoint(1, 0),
+            value: 10,
+          }
+          .store(),
+        )],
+      },
+    ];
+
+    let mut buffer = Vec::new();
+
+    BlockDelta::write_preamble(&mut buffer).unwrap();
+
+    for delta in &deltas {
+      delta.encode(&mut buffer).unwrap();
+    }
+
+    BlockDelta::write_end(&mut buffer).unwrap();
+
+    let mut reader = buffer.as_slice();
+
+    BlockDelta::read_preamble(&mut reader).unwrap();
+
+    for delta in deltas {
+      assert_eq!(BlockDelta::decode(&mut reader).unwrap().unwrap(), delta);
+    }
+
+    assert_eq!(BlockDelta::decode(&mut reader).unwrap(), None);
+  }
+
+  #[test]
+  fn invalid_preamble_is_rejected() {
+    assert_eq!(
+      BlockDelta::read_preamble(&mut b"ORDDELTX\x02".as_slice())
+        .unwrap_err()
+        .to_string(),
+      "input is not an ord index delta stream",
+    );
+
+    assert_eq!(
+      BlockDelta::read_preamble(&mut b"ORDDELTA\x01".as_slice())
+        .unwrap_err()
+        .to_string(),
+      "unsupported index delta version 1, expected 2",
+    );
+  }
+
+  #[test]
+  fn truncated_record_is_an_error() {
+    let mut buffer = Vec::new();
+
+    BlockDelta {
+      fees: None,
+      header: Chain::Mainnet.genesis_block().unwrap().header.store(),
+      height: 0,
+      inscriptions: Vec::new(),
+      moves: Vec::new(),
+      transfers: Vec::new(),
+    }
+    .encode(&mut buffer)
+    .unwrap();
+
+    buffer.pop();
+
+    assert_eq!(
+      BlockDelta::decode(&mut buffer.as_slice())
+        .unwrap_err()
+        .to_string(),
+      "index delta stream is truncated",
+    );
+  }
+
+  #[test]
+  fn missing_end_marker_is_an_error() {
+    let mut buffer = Vec::new();
+
+    BlockDelta {
+      fees: None,
+      header: Chain::Mainnet.genesis_block().unwrap().header.store(),
+      height: 0,
+      inscriptions: Vec::new(),
+      moves: Vec::new(),
+      transfers: Vec::new(),
+    }
+    .encode(&mut buffer)
+    .unwrap();
+
+    let mut reader = buffer.as_slice();
+
+    BlockDelta::decode(&mut reader).unwrap().unwrap();
+
+    assert_eq!(
+      BlockDelta::decode(&mut reader).unwrap_err().to_string(),
+      "index delta stream is truncated",
+    );
+  }
+
+  fn unbound_outpoint_satpoint() -> SatPoint {
+    SatPoint {
+      outpoint: unbound_outpoint(),
+      offset: 0,
+    }
+  }
+}
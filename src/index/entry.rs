@@ -382,6 +382,222 @@ impl Entry for Txid {
   }
 }
 
+/// Statistics of the children of a parent inscription, updated as children
+/// are created and transferred.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CollectionEntry {
+  pub(crate) first_child_height: u32,
+  pub(crate) holders: u64,
+  pub(crate) last_child_height: u32,
+  pub(crate) supply: u64,
+}
+
+pub(super) type CollectionEntryValue = (
+  u32, // first child height
+  u64, // holders
+  u32, // last child height
+  u64, // supply
+);
+
+impl Entry for CollectionEntry {
+  type Value = CollectionEntryValue;
+
+  fn load((first_child_height, holders, last_child_height, supply): CollectionEntryValue) -> Self {
+    Self {
+      first_child_height,
+      holders,
+      last_child_height,
+      supply,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.first_child_height,
+      self.holders,
+      self.last_child_height,
+      self.supply,
+    )
+  }
+}
+
+pub(super) type TransferValue = [u8; 108];
+
+/// An inscription moving from `old_satpoint` to `new_satpoint` in a block at
+/// `height`. Transfers are stored in `SEQUENCE_NUMBER_TO_TRANSFERS` under the
+/// inscription's sequence number and the transfer's position among the
+/// inscription's transfers, in the order in which they occur.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct TransferEntry {
+  pub(crate) fee: u64,
+  pub(crate) height: u32,
+  pub(crate) new_satpoint: SatPoint,
+  pub(crate) old_satpoint: SatPoint,
+  pub(crate) value: u64,
+}
+
+impl Entry for TransferEntry {
+  type Value = TransferValue;
+
+  fn load(value: Self::Value) -> Self {
+    Self {
+      height: u32::from_le_bytes(value[..4].try_into().unwrap()),
+      new_satpoint: SatPoint::load(value[4..48].try_into().unwrap()),
+      old_satpoint: SatPoint::load(value[48..92].try_into().unwrap()),
+      fee: u64::from_le_bytes(value[92..100].try_into().unwrap()),
+      value: u64::from_le_bytes(value[100..].try_into().unwrap()),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let mut value = [0; 108];
+    value[..4].copy_from_slice(&self.height.to_le_bytes());
+    value[4..48].copy_from_slice(&self.new_satpoint.store());
+    value[48..92].copy_from_slice(&self.old_satpoint.store());
+    value[92..100].copy_from_slice(&self.fee.to_le_bytes());
+    value[100..].copy_from_slice(&self.value.to_le_bytes());
+    value
+  }
+}
+
+pub(super) type InscriptionFeesValue = [u8; 48];
+
+/// Inscription creations and transfers in one block, with the fees paid by
+/// their transactions and the value of the outputs they were sent to. A
+/// transaction's fee is split evenly between the inscriptions it creates, and
+/// separately between the inscriptions it transfers.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct InscriptionFeesEntry {
+  pub(crate) creation_fees: u64,
+  pub(crate) creation_value: u64,
+  pub(crate) creations: u64,
+  pub(crate) transfer_fees: u64,
+  pub(crate) transfer_value: u64,
+  pub(crate) transfers: u64,
+}
+
+impl Entry for InscriptionFeesEntry {
+  type Value = InscriptionFeesValue;
+
+  fn load(value: Self::Value) -> Self {
+    let field = |i: usize| u64::from_le_bytes(value[i * 8..i * 8 + 8].try_into().unwrap());
+
+    Self {
+      creation_fees: field(0),
+      creation_value: field(1),
+      creations: field(2),
+      transfer_fees: field(3),
+      transfer_value: field(4),
+      transfers: field(5),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let mut value = [0; 48];
+
+    for (i, field) in [
+      self.creation_fees,
+      self.creation_value,
+      self.creations,
+      self.transfer_fees,
+      self.transfer_value,
+      self.transfers,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      value[i * 8..i * 8 + 8].copy_from_slice(&field.to_le_bytes());
+    }
+
+    value
+  }
+}
+
+/// Net change to the address index in one block, either an outpoint that was
+/// added to `script_pubkey`, or one that was removed from it. Stored in
+/// `HEIGHT_TO_ADDRESS_CHANGE` as a kind byte, the outpoint, and the script
+/// pubkey.
+#[derive(Debug, PartialEq)]
+pub(crate) struct AddressChange {
+  pub(crate) added: bool,
+  pub(crate) outpoint: OutPoint,
+  pub(crate) script_pubkey: ScriptBuf,
+}
+
+impl Entry for AddressChange {
+  type Value = Vec<u8>;
+
+  fn load(value: Self::Value) -> Self {
+    Self {
+      added: value[0] != 0,
+      outpoint: OutPoint::load(value[1..37].try_into().unwrap()),
+      script_pubkey: ScriptBuf::from_bytes(value[37..].to_vec()),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let mut value = vec![u8::from(self.added)];
+    value.extend_from_slice(&self.outpoint.store());
+    value.extend_from_slice(self.script_pubkey.as_bytes());
+    value
+  }
+}
+
+/// Error decoding a rune balance buffer from `OUTPOINT_TO_RUNE_BALANCES`.
+#[derive(Debug, PartialEq)]
+pub(crate) enum BalanceError {
+  InvalidRuneId { offset: usize, id: u128 },
+  Truncated { offset: usize },
+}
+
+impl fmt::Display for BalanceError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidRuneId { offset, id } => {
+        write!(
+          f,
+          "invalid rune ID {id} at offset {offset} of rune balances"
+        )
+      }
+      Self::Truncated { offset } => {
+        write!(f, "truncated varint at offset {offset} of rune balances")
+      }
+    }
+  }
+}
+
+impl std::error::Error for BalanceError {}
+
+/// Decode a buffer of varint encoded `(rune ID, amount)` pairs, returning an
+/// error instead of panicking if the buffer is corrupt.
+pub(crate) fn load_rune_balances(buffer: &[u8]) -> Result<Vec<(RuneId, u128)>, BalanceError> {
+  fn decode(buffer: &[u8], offset: usize) -> Result<(u128, usize), BalanceError> {
+    let (n, length) = runes::varint::decode(&buffer[offset..]);
+
+    if length == 0 || buffer[offset + length - 1] & 0b1000_0000 != 0 {
+      return Err(BalanceError::Truncated { offset });
+    }
+
+    Ok((n, length))
+  }
+
+  let mut balances = Vec::new();
+  let mut i = 0;
+  while i < buffer.len() {
+    let offset = i;
+    let (id, length) = decode(buffer, i)?;
+    i += length;
+    let (amount, length) = decode(buffer, i)?;
+    i += length;
+
+    let id = RuneId::try_from(id).map_err(|_| BalanceError::InvalidRuneId { offset, id })?;
+
+    balances.push((id, amount));
+  }
+
+  Ok(balances)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -474,6 +690,81 @@ mod tests {
     assert_eq!(RuneEntry::load(value), entry);
   }
 
+  #[test]
+  fn transfer_entry() {
+    let entry = TransferEntry {
+      fee: 8,
+      height: 1,
+      new_satpoint: SatPoint {
+        outpoint: OutPoint {
+          txid: Txid::from_byte_array([2; 32]),
+          vout: 3,
+        },
+        offset: 4,
+      },
+      old_satpoint: SatPoint {
+        outpoint: OutPoint {
+          txid: Txid::from_byte_array([5; 32]),
+          vout: 6,
+        },
+        offset: 7,
+      },
+      value: 9,
+    };
+
+    let value = entry.store();
+
+    assert_eq!(value[..4], [1, 0, 0, 0]);
+    assert_eq!(value[92..100], [8, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(TransferEntry::load(value), entry);
+  }
+
+  #[test]
+  fn inscription_fees_entry() {
+    let entry = InscriptionFeesEntry {
+      creation_fees: 1,
+      creation_value: 2,
+      creations: 3,
+      transfer_fees: 4,
+      transfer_value: 5,
+      transfers: 6,
+    };
+
+    let value = entry.store();
+
+    assert_eq!(value[40..], [6, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(InscriptionFeesEntry::load(value), entry);
+  }
+
+  #[test]
+  fn address_change() {
+    let change = AddressChange {
+      added: true,
+      outpoint: OutPoint {
+        txid: Txid::from_byte_array([2; 32]),
+        vout: 3,
+      },
+      script_pubkey: ScriptBuf::from_bytes(vec![4, 5, 6]),
+    };
+
+    let value = change.store();
+
+    assert_eq!(value[0], 1);
+    assert_eq!(value[37..], [4, 5, 6]);
+
+    assert_eq!(
+      AddressChange::load(value),
+      AddressChange {
+        added: true,
+        outpoint: OutPoint {
+          txid: Txid::from_byte_array([2; 32]),
+          vout: 3,
+        },
+        script_pubkey: ScriptBuf::from_bytes(vec![4, 5, 6]),
+      }
+    );
+  }
+
   #[test]
   fn rune_id_entry() {
     assert_eq!(
@@ -494,6 +785,54 @@ mod tests {
     );
   }
 
+  #[test]
+  fn rune_balances() {
+    let mut buffer = Vec::new();
+    runes::varint::encode_to_vec(
+      u128::from(RuneId {
+        height: 1,
+        index: 2,
+      }),
+      &mut buffer,
+    );
+    runes::varint::encode_to_vec(300, &mut buffer);
+
+    assert_eq!(
+      load_rune_balances(&buffer),
+      Ok(vec![(
+        RuneId {
+          height: 1,
+          index: 2
+        },
+        300
+      )]),
+    );
+
+    assert_eq!(load_rune_balances(&[]), Ok(Vec::new()));
+
+    assert_eq!(
+      load_rune_balances(&buffer[..buffer.len() - 1]),
+      Err(BalanceError::Truncated { offset: 3 }),
+    );
+
+    assert_eq!(
+      load_rune_balances(&[0]),
+      Err(BalanceError::Truncated { offset: 1 }),
+    );
+
+    let mut buffer = Vec::new();
+    runes::varint::encode_to_vec(u128::MAX, &mut buffer);
+    runes::varint::encode_to_vec(1, &mut buffer);
+
+    assert_eq!(
+      load_rune_balances(&buffer),
+      Err(BalanceError::InvalidRuneId {
+        offset: 0,
+        id: u128::MAX,
+      }),
+    );
+  }
+
   #[test]
   fn header() {
     let expected = [
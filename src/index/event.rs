@@ -0,0 +1,216 @@
+use {
+  super::{proto, *},
+  prost::Message,
+};
+
+/// Inscription and protocol events produced while indexing a block, in the
+/// order in which they occurred.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+  BlockIndexed {
+    block_hash: BlockHash,
+    block_height: u32,
+  },
+  /// The index was rolled back after a reorg, removing every block at and
+  /// above `block_height`, so consumers must undo the events of those blocks.
+  /// `orphaned_block_hash` is the hash of the abandoned chain tip.
+  BlocksRolledBack {
+    block_height: u32,
+    orphaned_block_hash: BlockHash,
+  },
+  InscriptionCreated {
+    block_height: u32,
+    inscription_id: InscriptionId,
+    inscription_number: i32,
+    location: SatPoint,
+    metaprotocol: Option<String>,
+    sequence_number: u32,
+  },
+  InscriptionTransferred {
+    block_height: u32,
+    inscription_id: InscriptionId,
+    new_location: SatPoint,
+    old_location: SatPoint,
+    sender: Option<ScriptBuf>,
+    sequence_number: u32,
+  },
+  RuneBurned {
+    amount: u128,
+    block_height: u32,
+    rune_id: RuneId,
+    txid: Txid,
+  },
+  RuneEtched {
+    block_height: u32,
+    rune: Rune,
+    rune_id: RuneId,
+    txid: Txid,
+  },
+  RuneMinted {
+    amount: u128,
+    block_height: u32,
+    rune_id: RuneId,
+    txid: Txid,
+  },
+  RuneTransferred {
+    amount: u128,
+    block_height: u32,
+    outpoint: OutPoint,
+    rune_id: RuneId,
+    txid: Txid,
+  },
+}
+
+impl Event {
+  /// Subject suffix under which the event is published, identical to the
+  /// event's serialized `type`.
+  pub(crate) fn kind(&self) -> &'static str {
+    match self {
+      Self::BlockIndexed { .. } => "block_indexed",
+      Self::BlocksRolledBack { .. } => "blocks_rolled_back",
+      Self::InscriptionCreated { .. } => "inscription_created",
+      Self::InscriptionTransferred { .. } => "inscription_transferred",
+      Self::RuneBurned { .. } => "rune_burned",
+      Self::RuneEtched { .. } => "rune_etched",
+      Self::RuneMinted { .. } => "rune_minted",
+      Self::RuneTransferred { .. } => "rune_transferred",
+    }
+  }
+
+  pub(crate) fn is_rollback(&self) -> bool {
+    matches!(self, Self::BlocksRolledBack { .. })
+  }
+
+  pub(crate) fn block_height(&self) -> u32 {
+    match self {
+      Self::BlockIndexed { block_height, .. }
+      | Self::BlocksRolledBack { block_height, .. }
+      | Self::InscriptionCreated { block_height, .. }
+      | Self::InscriptionTransferred { block_height, .. }
+      | Self::RuneBurned { block_height, .. }
+      | Self::RuneEtched { block_height, .. }
+      | Self::RuneMinted { block_height, .. }
+      | Self::RuneTransferred { block_height, .. } => *block_height,
+    }
+  }
+
+  /// Key identifying the event as `<HEIGHT>:<TXID>:<INDEX>`, where `index` is
+  /// the event's position among the events of its block, `BlockIndexed`
+  /// events use the block hash in place of a txid, and `BlocksRolledBack`
+  /// events use the hash of the orphaned tip. Reprocessing a block after
+  /// a restart produces the same keys, so consumers can use them to discard
+  /// events they have already applied.
+  pub(crate) fn idempotency_key(&self, index: usize) -> String {
+    let txid = match self {
+      Self::BlockIndexed { block_hash, .. } => block_hash.to_string(),
+      Self::BlocksRolledBack {
+        orphaned_block_hash,
+        ..
+      } => orphaned_block_hash.to_string(),
+      Self::InscriptionCreated { inscription_id, .. } => inscription_id.txid.to_string(),
+      Self::InscriptionTransferred { new_location, .. } => new_location.outpoint.txid.to_string(),
+      Self::RuneBurned { txid, .. }
+      | Self::RuneEtched { txid, .. }
+      | Self::RuneMinted { txid, .. }
+      | Self::RuneTransferred { txid, .. } => txid.to_string(),
+    };
+
+    format!("{}:{txid}:{index}", self.block_height())
+  }
+}
+
+/// Events of a block, stored under its height in `HEIGHT_TO_EVENTS` when
+/// indexing with `--index-events`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct BlockEvents {
+  #[prost(message, repeated, tag = "1")]
+  events: Vec<proto::Event>,
+}
+
+pub(crate) fn store(events: &[Event]) -> Vec<u8> {
+  BlockEvents {
+    events: events.iter().map(proto::Event::from).collect(),
+  }
+  .encode_to_vec()
+}
+
+impl Index {
+  /// Events of the block at `height`, in the order in which they occurred,
+  /// or `None` if the block hasn't been indexed. Requires `--index-events`.
+  pub(crate) fn get_events_in_block(&self, height: u32) -> Result<Option<Vec<Event>>> {
+    let Some(events) = self
+      .database
+      .begin_read()?
+      .open_table(HEIGHT_TO_EVENTS)?
+      .get(height)?
+      .map(|events| BlockEvents::decode(events.value()))
+      .transpose()?
+    else {
+      return Ok(None);
+    };
+
+    events
+      .events
+      .into_iter()
+      .map(Event::try_from)
+      .collect::<Result<Vec<Event>>>()
+      .map(Some)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn kind_matches_serialized_type() {
+    let event = Event::RuneMinted {
+      amount: 1000,
+      block_height: 8,
+      rune_id: RuneId {
+        height: 2,
+        index: 1,
+      },
+      txid: Txid::all_zeros(),
+    };
+
+    assert_eq!(serde_json::to_value(&event).unwrap()["type"], event.kind());
+
+    assert_eq!(
+      serde_json::to_string(&event).unwrap(),
+      format!(
+        r#"{{"type":"rune_minted","amount":1000,"block_height":8,"rune_id":"2/1","txid":"{}"}}"#,
+        Txid::all_zeros()
+      ),
+    );
+  }
+
+  #[test]
+  fn idempotency_key() {
+    assert_eq!(
+      Event::InscriptionCreated {
+        block_height: 5,
+        inscription_id: inscription_id(1),
+        inscription_number: 0,
+        location: SatPoint::from_str(
+          "1111111111111111111111111111111111111111111111111111111111111111:0:0"
+        )
+        .unwrap(),
+        metaprotocol: None,
+        sequence_number: 0,
+      }
+      .idempotency_key(2),
+      format!("5:{}:2", inscription_id(1).txid),
+    );
+
+    assert_eq!(
+      Event::BlockIndexed {
+        block_hash: BlockHash::all_zeros(),
+        block_height: 5,
+      }
+      .idempotency_key(3),
+      format!("5:{}:3", BlockHash::all_zeros()),
+    );
+  }
+}
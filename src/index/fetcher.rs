@@ -1,11 +1,12 @@
 use {
-  super::*,
+  super::{archive::Archive, *},
   base64::Engine,
   hyper::{client::HttpConnector, Body, Client, Method, Request, Uri},
   serde_json::{json, Value},
 };
 
 pub(crate) struct Fetcher {
+  archive: Option<Archive>,
   auth: String,
   client: Client<HttpConnector>,
   url: Uri,
@@ -42,7 +43,18 @@ impl Fetcher {
       "Basic {}",
       &base64::engine::general_purpose::STANDARD.encode(auth)
     );
-    Ok(Fetcher { client, url, auth })
+    let archive = options
+      .block_archive_url
+      .as_deref()
+      .map(Archive::new)
+      .transpose()?;
+
+    Ok(Fetcher {
+      archive,
+      client,
+      url,
+      auth,
+    })
   }
 
   pub(crate) async fn get_transactions(&self, txids: Vec<Txid>) -> Result<Vec<Transaction>> {
@@ -91,17 +103,34 @@ impl Fetcher {
     }
 
     // Return early on any error, because we need all results to proceed
-    if let Some(err) = results.iter().find_map(|res| res.error.as_ref()) {
-      return Err(anyhow!(
-        "failed to fetch raw transaction: code {} message {}",
-        err.code,
-        err.message
-      ));
+    if self.archive.is_none() {
+      if let Some(err) = results.iter().find_map(|res| res.error.as_ref()) {
+        return Err(anyhow!(
+          "failed to fetch raw transaction: code {} message {}",
+          err.code,
+          err.message
+        ));
+      }
     }
 
     // Results from batched JSON-RPC requests can come back in any order, so we must sort them by id
     results.sort_by(|a, b| a.id.cmp(&b.id));
 
+    // Transactions that Bitcoin Core could not return, likely because their
+    // blocks have been pruned, are fetched from the archive instead
+    if let Some(archive) = &self.archive {
+      for res in &mut results {
+        if res.error.is_some() {
+          let txid = txids
+            .get(res.id)
+            .ok_or_else(|| anyhow!("invalid id {} in batched JSON-RPC response", res.id))?;
+          let tx = archive.get_transaction(*txid).await?;
+          res.result = Some(hex::encode(consensus::encode::serialize(&tx)));
+          res.error = None;
+        }
+      }
+    }
+
     let txs = results
       .into_iter()
       .map(|res| {
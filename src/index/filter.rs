@@ -0,0 +1,388 @@
+use {super::*, serde_json::Value};
+
+/// Boolean expression over the fields of an event's JSON payload, e.g.
+/// `type == "rune_minted" && amount > 1000`. Fields are referred to by name,
+/// with nested fields separated by dots, and evaluate to `null` if missing.
+/// Numbers are compared numerically, strings lexicographically, and ordering
+/// comparisons between values of different types are false.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter(Expression);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expression {
+  And(Box<Expression>, Box<Expression>),
+  Comparison(Operand, Operator, Operand),
+  Not(Box<Expression>),
+  Operand(Operand),
+  Or(Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+  Field(Vec<String>),
+  Literal(Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+  Eq,
+  Ge,
+  Gt,
+  Le,
+  Lt,
+  Ne,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  And,
+  Close,
+  Identifier(String),
+  Literal(Value),
+  Not,
+  Open,
+  Operator(Operator),
+  Or,
+}
+
+impl Filter {
+  pub(crate) fn matches(&self, payload: &Value) -> bool {
+    self.0.evaluate(payload)
+  }
+}
+
+impl FromStr for Filter {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let mut parser = Parser {
+      tokens: tokenize(s)?,
+      position: 0,
+    };
+
+    let expression = parser.or()?;
+
+    if let Some(token) = parser.tokens.get(parser.position) {
+      bail!("unexpected token {token:?} in filter `{s}`");
+    }
+
+    Ok(Self(expression))
+  }
+}
+
+impl Expression {
+  fn evaluate(&self, payload: &Value) -> bool {
+    match self {
+      Self::And(a, b) => a.evaluate(payload) && b.evaluate(payload),
+      Self::Comparison(a, operator, b) => operator.apply(&a.value(payload), &b.value(payload)),
+      Self::Not(expression) => !expression.evaluate(payload),
+      Self::Operand(operand) => !matches!(operand.value(payload), Value::Null | Value::Bool(false)),
+      Self::Or(a, b) => a.evaluate(payload) || b.evaluate(payload),
+    }
+  }
+}
+
+impl Operand {
+  fn value(&self, payload: &Value) -> Value {
+    match self {
+      Self::Field(path) => path
+        .iter()
+        .try_fold(payload, |value, key| value.get(key))
+        .cloned()
+        .unwrap_or(Value::Null),
+      Self::Literal(value) => value.clone(),
+    }
+  }
+}
+
+impl Operator {
+  fn apply(self, a: &Value, b: &Value) -> bool {
+    let ordering = match (a, b) {
+      (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+      (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+      _ => {
+        return match self {
+          Self::Eq => a == b,
+          Self::Ne => a != b,
+          _ => false,
+        }
+      }
+    };
+
+    let Some(ordering) = ordering else {
+      return false;
+    };
+
+    match self {
+      Self::Eq => ordering.is_eq(),
+      Self::Ge => ordering.is_ge(),
+      Self::Gt => ordering.is_gt(),
+      Self::Le => ordering.is_le(),
+      Self::Lt => ordering.is_lt(),
+      Self::Ne => ordering.is_ne(),
+    }
+  }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>> {
+  let mut tokens = Vec::new();
+  let mut chars = s.char_indices().peekable();
+
+  while let Some((i, c)) = chars.next() {
+    let next = chars.peek().map(|(_, next)| *next);
+
+    let token = match (c, next) {
+      (c, _) if c.is_whitespace() => continue,
+      ('(', _) => Token::Open,
+      (')', _) => Token::Close,
+      ('&', Some('&')) | ('|', Some('|')) | ('=' | '!' | '<' | '>', Some('=')) => {
+        chars.next();
+        match c {
+          '&' => Token::And,
+          '|' => Token::Or,
+          '=' => Token::Operator(Operator::Eq),
+          '!' => Token::Operator(Operator::Ne),
+          '<' => Token::Operator(Operator::Le),
+          _ => Token::Operator(Operator::Ge),
+        }
+      }
+      ('!', _) => Token::Not,
+      ('<', _) => Token::Operator(Operator::Lt),
+      ('>', _) => Token::Operator(Operator::Gt),
+      ('"', _) => {
+        let mut string = String::new();
+        loop {
+          match chars.next() {
+            Some((_, '"')) => break,
+            Some((_, '\\')) => match chars.next() {
+              Some((_, c)) => string.push(c),
+              None => bail!("unterminated string in filter `{s}`"),
+            },
+            Some((_, c)) => string.push(c),
+            None => bail!("unterminated string in filter `{s}`"),
+          }
+        }
+        Token::Literal(Value::String(string))
+      }
+      (c, _) if c.is_ascii_digit() || c == '-' => {
+        let mut end = i + c.len_utf8();
+        while let Some((j, c)) = chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+          end = j + c.len_utf8();
+        }
+        let number = &s[i..end];
+        Token::Literal(
+          serde_json::from_str::<Value>(number)
+            .ok()
+            .filter(Value::is_number)
+            .ok_or_else(|| anyhow!("invalid number `{number}` in filter `{s}`"))?,
+        )
+      }
+      (c, _) if c.is_ascii_alphabetic() || c == '_' => {
+        let mut end = i + c.len_utf8();
+        while let Some((j, c)) =
+          chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_' || *c == '.')
+        {
+          end = j + c.len_utf8();
+        }
+        match &s[i..end] {
+          "true" => Token::Literal(Value::Bool(true)),
+          "false" => Token::Literal(Value::Bool(false)),
+          "null" => Token::Literal(Value::Null),
+          identifier => Token::Identifier(identifier.into()),
+        }
+      }
+      (c, _) => bail!("unexpected character `{c}` in filter `{s}`"),
+    };
+
+    tokens.push(token);
+  }
+
+  Ok(tokens)
+}
+
+struct Parser {
+  position: usize,
+  tokens: Vec<Token>,
+}
+
+impl Parser {
+  fn next(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.position).cloned();
+    self.position += 1;
+    token
+  }
+
+  fn accept(&mut self, token: &Token) -> bool {
+    if self.tokens.get(self.position) == Some(token) {
+      self.position += 1;
+      true
+    } else {
+      false
+    }
+  }
+
+  fn or(&mut self) -> Result<Expression> {
+    let mut expression = self.and()?;
+
+    while self.accept(&Token::Or) {
+      expression = Expression::Or(Box::new(expression), Box::new(self.and()?));
+    }
+
+    Ok(expression)
+  }
+
+  fn and(&mut self) -> Result<Expression> {
+    let mut expression = self.not()?;
+
+    while self.accept(&Token::And) {
+      expression = Expression::And(Box::new(expression), Box::new(self.not()?));
+    }
+
+    Ok(expression)
+  }
+
+  fn not(&mut self) -> Result<Expression> {
+    if self.accept(&Token::Not) {
+      return Ok(Expression::Not(Box::new(self.not()?)));
+    }
+
+    if self.accept(&Token::Open) {
+      let expression = self.or()?;
+      ensure!(
+        self.accept(&Token::Close),
+        "unbalanced parentheses in filter"
+      );
+      return Ok(expression);
+    }
+
+    let a = self.operand()?;
+
+    if let Some(Token::Operator(operator)) = self.tokens.get(self.position).cloned() {
+      self.position += 1;
+      return Ok(Expression::Comparison(a, operator, self.operand()?));
+    }
+
+    Ok(Expression::Operand(a))
+  }
+
+  fn operand(&mut self) -> Result<Operand> {
+    match self.next() {
+      Some(Token::Identifier(identifier)) => Ok(Operand::Field(
+        identifier.split('.').map(str::to_string).collect(),
+      )),
+      Some(Token::Literal(value)) => Ok(Operand::Literal(value)),
+      Some(token) => bail!("expected field or value in filter, found {token:?}"),
+      None => bail!("unexpected end of filter"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[track_caller]
+  fn case(filter: &str, payload: Value, expected: bool) {
+    assert_eq!(
+      filter.parse::<Filter>().unwrap().matches(&payload),
+      expected,
+      "{filter}"
+    );
+  }
+
+  #[test]
+  fn comparisons() {
+    let payload = serde_json::json!({
+      "type": "rune_minted",
+      "amount": 1500,
+      "rune_id": "2:1",
+      "nested": { "flag": true },
+    });
+
+    case(r#"type == "rune_minted""#, payload.clone(), true);
+    case(r#"type != "rune_minted""#, payload.clone(), false);
+    case("amount > 1000", payload.clone(), true);
+    case("amount >= 1500", payload.clone(), true);
+    case("amount < 1500", payload.clone(), false);
+    case("amount <= 1500.0", payload.clone(), true);
+    case("amount == 1500.0", payload.clone(), true);
+    case("amount > -1", payload.clone(), true);
+    case(r#"amount > "1000""#, payload.clone(), false);
+    case(r#"rune_id < "3""#, payload.clone(), true);
+    case("nested.flag", payload.clone(), true);
+    case("nested.flag == true", payload.clone(), true);
+    case("missing", payload.clone(), false);
+    case("missing == null", payload.clone(), true);
+    case("missing.field != null", payload, false);
+  }
+
+  #[test]
+  fn boolean_operators() {
+    let payload = serde_json::json!({"type": "rune_minted", "amount": 1500});
+
+    case(
+      r#"type == "rune_minted" && amount > 1000"#,
+      payload.clone(),
+      true,
+    );
+    case(
+      r#"type == "rune_burned" && amount > 1000"#,
+      payload.clone(),
+      false,
+    );
+    case(
+      r#"type == "rune_burned" || amount > 1000"#,
+      payload.clone(),
+      true,
+    );
+    case(r#"!(type == "rune_minted")"#, payload.clone(), false);
+    case(
+      r#"type == "rune_burned" || type == "rune_minted" && amount < 1000"#,
+      payload.clone(),
+      false,
+    );
+    case(
+      r#"(type == "rune_burned" || type == "rune_minted") && amount > 1000"#,
+      payload,
+      true,
+    );
+  }
+
+  #[test]
+  fn strings_may_contain_escaped_quotes() {
+    case(
+      r#"name == "a \"b\"""#,
+      serde_json::json!({"name": "a \"b\""}),
+      true,
+    );
+  }
+
+  #[test]
+  fn invalid_filters_are_rejected() {
+    #[track_caller]
+    fn case(filter: &str, error: &str) {
+      assert_eq!(filter.parse::<Filter>().unwrap_err().to_string(), error);
+    }
+
+    case("", "unexpected end of filter");
+    case("amount >", "unexpected end of filter");
+    case("(amount > 1", "unbalanced parentheses in filter");
+    case(
+      "amount > 1)",
+      "unexpected token Close in filter `amount > 1)`",
+    );
+    case(
+      r#"type == "foo"#,
+      r#"unterminated string in filter `type == "foo`"#,
+    );
+    case(
+      "amount = 1",
+      "unexpected character `=` in filter `amount = 1`",
+    );
+    case(
+      "amount > 1.2.3",
+      "invalid number `1.2.3` in filter `amount > 1.2.3`",
+    );
+    case("&& amount", "expected field or value in filter, found And");
+  }
+}
@@ -0,0 +1,139 @@
+use {super::*, reqwest::Url};
+
+const BOUNDARY: &str = "ord-inscription-content";
+
+/// Client for the HTTP RPC API of an IPFS node, e.g. Kubo, used to add and
+/// pin inscription content. Content is added with CIDv1, so that identical
+/// content inscribed twice shares a CID.
+pub(crate) struct Ipfs {
+  client: reqwest::blocking::Client,
+  url: Url,
+}
+
+#[derive(Deserialize)]
+struct AddResponse {
+  #[serde(rename = "Hash")]
+  hash: String,
+}
+
+impl Ipfs {
+  pub(crate) fn new(url: &str) -> Result<Self> {
+    let mut url = Url::parse(url).with_context(|| format!("invalid IPFS API url `{url}`"))?;
+
+    url.set_path("/api/v0/add");
+    url.set_query(Some("pin=true&cid-version=1"));
+
+    Ok(Self {
+      client: reqwest::blocking::Client::new(),
+      url,
+    })
+  }
+
+  /// Add and pin the body of `inscription`, returning its CID, or `None` if
+  /// the inscription has no body.
+  pub(crate) fn add(&self, inscription: &Inscription) -> Result<Option<String>> {
+    let Some(body) = inscription.body() else {
+      return Ok(None);
+    };
+
+    let mut form = format!(
+      "--{BOUNDARY}\r\n\
+       Content-Disposition: form-data; name=\"file\"; filename=\"content\"\r\n\
+       Content-Type: application/octet-stream\r\n\
+       \r\n"
+    )
+    .into_bytes();
+    form.extend_from_slice(body);
+    form.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+    let response = self
+      .client
+      .post(self.url.clone())
+      .header(
+        reqwest::header::CONTENT_TYPE,
+        format!("multipart/form-data; boundary={BOUNDARY}"),
+      )
+      .body(form)
+      .send()?;
+
+    ensure!(
+      response.status().is_success(),
+      "IPFS node returned {}: {}",
+      response.status(),
+      response.text().unwrap_or_default(),
+    );
+
+    Ok(Some(response.json::<AddResponse>()?.hash))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    std::{
+      io::{BufRead, BufReader, Read, Write},
+      net::TcpListener,
+    },
+  };
+
+  #[test]
+  fn content_is_added_and_pinned() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let server = thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+      let mut request_line = String::new();
+      reader.read_line(&mut request_line).unwrap();
+
+      let mut content_length = 0;
+      loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line == "\r\n" {
+          break;
+        }
+        if let Some(value) = line.to_lowercase().strip_prefix("content-length: ") {
+          content_length = value.trim().parse().unwrap();
+        }
+      }
+
+      let mut body = vec![0; content_length];
+      reader.read_exact(&mut body).unwrap();
+
+      let response = r#"{"Name":"content","Hash":"bafkreifoo","Size":"3"}"#;
+      write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+      )
+      .unwrap();
+
+      (request_line, String::from_utf8(body).unwrap())
+    });
+
+    let ipfs = Ipfs::new(&url).unwrap();
+
+    assert_eq!(ipfs.add(&Inscription::default()).unwrap(), None);
+
+    assert_eq!(
+      ipfs
+        .add(&inscription("text/plain", "FOO"))
+        .unwrap()
+        .as_deref(),
+      Some("bafkreifoo"),
+    );
+
+    let (request_line, body) = server.join().unwrap();
+
+    assert_eq!(
+      request_line,
+      "POST /api/v0/add?pin=true&cid-version=1 HTTP/1.1\r\n"
+    );
+
+    assert!(body.contains("\r\n\r\nFOO\r\n--ord-inscription-content--\r\n"));
+  }
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+/// Record of the process that has the index open, written to
+/// `<INDEX>.lease` and updated after every commit. redb's file lock is what
+/// keeps other processes out of the index. The lease tells them which
+/// process holds the index and the height of the last committed block, so
+/// they can report it, watch indexing progress, or wait to take over.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Lease {
+  pub(crate) height: Option<u32>,
+  pub(crate) pid: u32,
+}
+
+impl Lease {
+  fn path(index: &Path) -> PathBuf {
+    let mut path = index.as_os_str().to_owned();
+    path.push(".lease");
+    path.into()
+  }
+
+  pub(crate) fn load(index: &Path) -> Option<Self> {
+    serde_json::from_slice(&fs::read(Self::path(index)).ok()?).ok()
+  }
+
+  /// Write the lease, replacing the previous lease atomically so readers
+  /// never see a partial write.
+  pub(crate) fn store(&self, index: &Path) -> Result {
+    let path = Self::path(index);
+
+    let mut tmp = path.clone().into_os_string();
+    tmp.push(".tmp");
+
+    fs::write(&tmp, serde_json::to_vec(self)?)?;
+    fs::rename(&tmp, &path)?;
+
+    Ok(())
+  }
+}
+
+impl Display for Lease {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "pid {}", self.pid)?;
+
+    if let Some(height) = self.height {
+      write!(f, ", committed height {height}")?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn store_and_load() {
+    let tempdir = TempDir::new().unwrap();
+    let index = tempdir.path().join("index.redb");
+
+    assert_eq!(Lease::load(&index), None);
+
+    let lease = Lease {
+      height: Some(5),
+      pid: 1,
+    };
+
+    lease.store(&index).unwrap();
+
+    assert_eq!(Lease::load(&index), Some(lease));
+
+    assert_eq!(
+      fs::read_to_string(tempdir.path().join("index.redb.lease")).unwrap(),
+      r#"{"height":5,"pid":1}"#
+    );
+  }
+}
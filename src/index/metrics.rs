@@ -0,0 +1,118 @@
+use {super::*, std::collections::VecDeque};
+
+/// Number of samples of each kind kept for `/dashboard`.
+const HISTORY: usize = 100;
+
+/// Indexer activity since the index was opened, kept in memory and rendered
+/// by `/dashboard`.
+#[derive(Default)]
+pub(crate) struct Metrics {
+  enabled: AtomicBool,
+  samples: Mutex<Samples>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Samples {
+  pub(crate) commits: VecDeque<CommitSample>,
+  pub(crate) database_sizes: VecDeque<u64>,
+  pub(crate) errors: u64,
+  pub(crate) events: VecDeque<EventSample>,
+  pub(crate) reorgs: VecDeque<ReorgSample>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CommitSample {
+  pub(crate) duration: Duration,
+  pub(crate) height: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct EventSample {
+  pub(crate) events: usize,
+  pub(crate) height: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ReorgSample {
+  pub(crate) depth: u32,
+  pub(crate) height: u32,
+  pub(crate) time: DateTime<Utc>,
+}
+
+fn push<T>(samples: &mut VecDeque<T>, sample: T) {
+  if samples.len() == HISTORY {
+    samples.pop_front();
+  }
+
+  samples.push_back(sample);
+}
+
+impl Metrics {
+  /// Collect events from indexed blocks, even if nothing else consumes them,
+  /// so that event throughput can be shown.
+  pub(crate) fn enable(&self) {
+    self.enabled.store(true, atomic::Ordering::Relaxed);
+  }
+
+  pub(crate) fn enabled(&self) -> bool {
+    self.enabled.load(atomic::Ordering::Relaxed)
+  }
+
+  pub(crate) fn samples(&self) -> Samples {
+    self.samples.lock().unwrap().clone()
+  }
+
+  pub(crate) fn record_commit(&self, height: u32, duration: Duration, database_size: u64) {
+    let mut samples = self.samples.lock().unwrap();
+    push(&mut samples.commits, CommitSample { duration, height });
+    push(&mut samples.database_sizes, database_size);
+  }
+
+  pub(crate) fn record_error(&self) {
+    self.samples.lock().unwrap().errors += 1;
+  }
+
+  pub(crate) fn record_events(&self, height: u32, events: usize) {
+    push(
+      &mut self.samples.lock().unwrap().events,
+      EventSample { events, height },
+    );
+  }
+
+  pub(crate) fn record_reorg(&self, height: u32, depth: u32) {
+    push(
+      &mut self.samples.lock().unwrap().reorgs,
+      ReorgSample {
+        depth,
+        height,
+        time: Utc::now(),
+      },
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn samples_are_limited_to_history() {
+    let metrics = Metrics::default();
+
+    for height in 0..u32::try_from(HISTORY).unwrap() + 10 {
+      metrics.record_commit(height, Duration::from_millis(height.into()), height.into());
+      metrics.record_events(height, 1);
+    }
+
+    metrics.record_error();
+
+    let samples = metrics.samples();
+
+    assert_eq!(samples.commits.len(), HISTORY);
+    assert_eq!(samples.commits.front().unwrap().height, 10);
+    assert_eq!(samples.database_sizes.back(), Some(&(HISTORY as u64 + 9)));
+    assert_eq!(samples.events.len(), HISTORY);
+    assert_eq!(samples.errors, 1);
+    assert!(samples.reorgs.is_empty());
+  }
+}
@@ -0,0 +1,284 @@
+use {super::*, std::ops::Bound};
+
+/// Entries migrated per write transaction. Each batch is committed along with
+/// a cursor, so an interrupted migration resumes after the last batch.
+pub(crate) const BATCH_SIZE: usize = 10_000;
+
+/// Registered migrations, in the order they are applied.
+pub(crate) const MIGRATIONS: &[Migration] = &[];
+
+/// Migrate up to `limit` entries after the key with bytes `after`, or from
+/// the first key if `after` is `None`. Returns the number of entries migrated,
+/// and the bytes of the last migrated key if there may be more to migrate.
+pub(crate) type Migrate =
+  fn(&WriteTransaction, Option<&[u8]>, usize) -> Result<(usize, Option<Vec<u8>>)>;
+
+/// A change to the encoding of a protocol table, which upgrades the table to
+/// schema `version`. Indexes with tables at earlier versions are migrated
+/// when opened, instead of having to be rebuilt.
+pub(crate) struct Migration {
+  pub(crate) table: ProtocolTable,
+  pub(crate) version: u64,
+  pub(crate) description: &'static str,
+  pub(crate) migrate: Migrate,
+}
+
+impl ProtocolTable {
+  /// Schema version of this table's encoding after `migrations` are applied.
+  pub(crate) fn schema_version(self, migrations: &[Migration]) -> u64 {
+    migrations
+      .iter()
+      .filter(|migration| migration.table == self)
+      .map(|migration| migration.version)
+      .max()
+      .unwrap_or_default()
+  }
+}
+
+/// Re-encode the values of a table with byte values using `transform`, for
+/// use by [`Migrate`] functions.
+#[allow(unused)]
+pub(crate) fn migrate_values<K: RedbKey + 'static>(
+  wtx: &WriteTransaction,
+  definition: TableDefinition<K, &'static [u8]>,
+  after: Option<&[u8]>,
+  limit: usize,
+  transform: fn(&[u8]) -> Result<Vec<u8>>,
+) -> Result<(usize, Option<Vec<u8>>)> {
+  let mut table = wtx.open_table(definition)?;
+
+  let mut batch = Vec::new();
+
+  {
+    let range = match after {
+      Some(after) => table.range((Bound::Excluded(K::from_bytes(after)), Bound::Unbounded))?,
+      None => table.iter()?,
+    };
+
+    for result in range.take(limit) {
+      let (key, value) = result?;
+      batch.push((
+        K::as_bytes(&key.value()).as_ref().to_vec(),
+        transform(value.value())?,
+      ));
+    }
+  }
+
+  for (key, value) in &batch {
+    table.insert(K::from_bytes(key), value.as_slice())?;
+  }
+
+  let count = batch.len();
+
+  let cursor = if count == limit {
+    batch.pop().map(|(key, _value)| key)
+  } else {
+    None
+  };
+
+  Ok((count, cursor))
+}
+
+impl Index {
+  /// Apply `migrations` to tables whose recorded schema version is older,
+  /// committing every `batch_size` entries.
+  pub(crate) fn migrate(&self, migrations: &[Migration], batch_size: usize) -> Result {
+    for migration in migrations {
+      let name = migration.table.name();
+
+      let (version, mut cursor) = {
+        let rtx = self.database.begin_read()?;
+
+        let version = rtx
+          .open_table(PROTOCOL_TABLE_TO_SCHEMA_VERSION)?
+          .get(name.as_str())?
+          .map(|version| version.value())
+          .unwrap_or_default();
+
+        let cursor = rtx
+          .open_table(PROTOCOL_TABLE_TO_MIGRATION_CURSOR)?
+          .get(name.as_str())?
+          .map(|cursor| cursor.value().to_vec());
+
+        (version, cursor)
+      };
+
+      if version >= migration.version {
+        continue;
+      }
+
+      ensure!(
+        migration.version == version + 1,
+        "no migration for table `{name}` from schema {version} to {}",
+        version + 1,
+      );
+
+      match &cursor {
+        Some(_) => log::info!(
+          "Resuming migration of table `{name}` to schema {}: {}",
+          migration.version,
+          migration.description
+        ),
+        None => log::info!(
+          "Migrating table `{name}` to schema {}: {}",
+          migration.version,
+          migration.description
+        ),
+      }
+
+      let mut migrated = 0;
+
+      loop {
+        let wtx = self.begin_write()?;
+
+        let (count, next) = (migration.migrate)(&wtx, cursor.as_deref(), batch_size)?;
+
+        migrated += count;
+
+        {
+          let mut cursors = wtx.open_table(PROTOCOL_TABLE_TO_MIGRATION_CURSOR)?;
+
+          match &next {
+            Some(next) => {
+              cursors.insert(name.as_str(), next.as_slice())?;
+            }
+            None => {
+              cursors.remove(name.as_str())?;
+              wtx
+                .open_table(PROTOCOL_TABLE_TO_SCHEMA_VERSION)?
+                .insert(name.as_str(), migration.version)?;
+            }
+          }
+        }
+
+        wtx.commit()?;
+
+        log::info!("Migrated {migrated} entries of table `{name}`");
+
+        if next.is_none() {
+          break;
+        }
+
+        cursor = next;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  fn append_one(value: &[u8]) -> Result<Vec<u8>> {
+    let mut value = value.to_vec();
+    value.push(1);
+    Ok(value)
+  }
+
+  fn migrate_append_one(
+    wtx: &WriteTransaction,
+    after: Option<&[u8]>,
+    limit: usize,
+  ) -> Result<(usize, Option<Vec<u8>>)> {
+    migrate_values(wtx, OUTPOINT_TO_RUNE_BALANCES, after, limit, append_one)
+  }
+
+  const MIGRATIONS: &[Migration] = &[Migration {
+    table: ProtocolTable::OutpointToRuneBalances,
+    version: 1,
+    description: "append one",
+    migrate: migrate_append_one,
+  }];
+
+  fn insert_balances(context: &Context, outpoints: &[u8]) {
+    let wtx = context.index.database.begin_write().unwrap();
+
+    {
+      let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES).unwrap();
+
+      for outpoint in outpoints {
+        outpoint_to_rune_balances
+          .insert(&[*outpoint; 36], [0u8].as_slice())
+          .unwrap();
+      }
+    }
+
+    wtx.commit().unwrap();
+  }
+
+  fn balances(context: &Context) -> Vec<Vec<u8>> {
+    let rtx = context.index.database.begin_read().unwrap();
+
+    let outpoint_to_rune_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES).unwrap();
+
+    outpoint_to_rune_balances
+      .iter()
+      .unwrap()
+      .map(|result| result.unwrap().1.value().to_vec())
+      .collect()
+  }
+
+  fn schema_version(context: &Context) -> Option<u64> {
+    let rtx = context.index.database.begin_read().unwrap();
+
+    let protocol_table_to_schema_version =
+      rtx.open_table(PROTOCOL_TABLE_TO_SCHEMA_VERSION).unwrap();
+
+    let version = protocol_table_to_schema_version
+      .get(ProtocolTable::OutpointToRuneBalances.name().as_str())
+      .unwrap()
+      .map(|version| version.value());
+
+    version
+  }
+
+  #[test]
+  fn new_indexes_record_current_schema_versions() {
+    let context = Context::builder().build();
+
+    assert_eq!(schema_version(&context), Some(0));
+  }
+
+  #[test]
+  fn migrations_are_applied_in_batches() {
+    let context = Context::builder().build();
+
+    insert_balances(&context, &[1, 2, 3, 4, 5]);
+
+    context.index.migrate(MIGRATIONS, 2).unwrap();
+
+    assert_eq!(balances(&context), vec![vec![0, 1]; 5]);
+    assert_eq!(schema_version(&context), Some(1));
+
+    context.index.migrate(MIGRATIONS, 2).unwrap();
+
+    assert_eq!(balances(&context), vec![vec![0, 1]; 5]);
+  }
+
+  #[test]
+  fn migrations_resume_after_cursor() {
+    let context = Context::builder().build();
+
+    insert_balances(&context, &[1, 2, 3]);
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      wtx
+        .open_table(PROTOCOL_TABLE_TO_MIGRATION_CURSOR)
+        .unwrap()
+        .insert(
+          ProtocolTable::OutpointToRuneBalances.name().as_str(),
+          [1; 36].as_slice(),
+        )
+        .unwrap();
+      wtx.commit().unwrap();
+    }
+
+    context.index.migrate(MIGRATIONS, 2).unwrap();
+
+    assert_eq!(balances(&context), [vec![0], vec![0, 1], vec![0, 1]]);
+    assert_eq!(schema_version(&context), Some(1));
+  }
+}
@@ -0,0 +1,293 @@
+use {
+  super::{event::Event, *},
+  postgres::{NoTls, Transaction},
+};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS ord_blocks (
+  height BIGINT PRIMARY KEY,
+  hash TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS ord_inscriptions (
+  sequence_number BIGINT PRIMARY KEY,
+  inscription_id TEXT NOT NULL UNIQUE,
+  height BIGINT NOT NULL,
+  genesis_location TEXT NOT NULL,
+  location TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS ord_inscription_transfers (
+  id BIGSERIAL PRIMARY KEY,
+  sequence_number BIGINT NOT NULL,
+  height BIGINT NOT NULL,
+  old_location TEXT NOT NULL,
+  new_location TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS ord_rune_events (
+  id BIGSERIAL PRIMARY KEY,
+  height BIGINT NOT NULL,
+  type TEXT NOT NULL,
+  rune_id TEXT NOT NULL,
+  rune TEXT,
+  txid TEXT NOT NULL,
+  amount NUMERIC(39, 0)
+);
+
+ALTER TABLE ord_rune_events ADD COLUMN IF NOT EXISTS outpoint TEXT;
+
+ALTER TABLE ord_inscription_transfers ADD COLUMN IF NOT EXISTS sender TEXT;
+
+ALTER TABLE ord_inscriptions ADD COLUMN IF NOT EXISTS metaprotocol TEXT;
+
+ALTER TABLE ord_inscriptions ADD COLUMN IF NOT EXISTS inscription_number BIGINT;
+
+CREATE TABLE IF NOT EXISTS ord_event_keys (
+  key TEXT PRIMARY KEY,
+  height BIGINT NOT NULL
+);
+";
+
+/// Mirror of inscription and rune state in PostgreSQL. Each block's events
+/// are applied in a single Postgres transaction, after the block has been
+/// committed to the index. Blocks removed from the index after a reorg are
+/// removed from the mirror when the `BlocksRolledBack` event is applied, and
+/// any blocks the index does not have are removed when the mirror is opened.
+///
+/// The idempotency key of every applied event is recorded in
+/// `ord_event_keys`, and events whose key is already present are skipped, so
+/// a block reprocessed after a crash is never applied twice. Keys are pruned
+/// once their block is deeper than `finality_depth`, beyond which the index
+/// can no longer roll back.
+pub(crate) struct Mirror {
+  client: postgres::Client,
+  finality_depth: u32,
+}
+
+impl Mirror {
+  pub(crate) fn open(url: &str, height: u32, finality_depth: u32) -> Result<Self> {
+    let mut client = postgres::Client::connect(url, NoTls)
+      .with_context(|| "failed to connect to Postgres at `--postgres-url`")?;
+
+    client.batch_execute(SCHEMA)?;
+
+    let mut mirror = Self {
+      client,
+      finality_depth,
+    };
+
+    let mut tx = mirror.client.transaction()?;
+    Self::rollback(&mut tx, height)?;
+    tx.commit()?;
+
+    Ok(mirror)
+  }
+
+  /// Remove all blocks at or above `height`, restoring inscription locations
+  /// to where they were before.
+  fn rollback(tx: &mut Transaction, height: u32) -> Result {
+    let height = i64::from(height);
+
+    let removed = tx.execute("DELETE FROM ord_blocks WHERE height >= $1", &[&height])?;
+
+    if removed > 0 {
+      log::info!("Rolling back {removed} blocks from Postgres mirror");
+
+      tx.execute(
+        "DELETE FROM ord_inscriptions WHERE height >= $1",
+        &[&height],
+      )?;
+
+      tx.execute(
+        "DELETE FROM ord_inscription_transfers WHERE height >= $1",
+        &[&height],
+      )?;
+
+      tx.execute(
+        "UPDATE ord_inscriptions i SET location = COALESCE(
+          (
+            SELECT t.new_location FROM ord_inscription_transfers t
+            WHERE t.sequence_number = i.sequence_number
+            ORDER BY t.id DESC
+            LIMIT 1
+          ),
+          i.genesis_location
+        )",
+        &[],
+      )?;
+
+      tx.execute("DELETE FROM ord_rune_events WHERE height >= $1", &[&height])?;
+    }
+
+    tx.execute("DELETE FROM ord_event_keys WHERE height >= $1", &[&height])?;
+
+    Ok(())
+  }
+
+  pub(crate) fn apply(&mut self, events: &[Event]) -> Result {
+    let mut tx = self.client.transaction()?;
+
+    for (i, event) in events.iter().enumerate() {
+      // rolling back is idempotent, and removes the keys of the rolled back
+      // blocks, so rollbacks are applied without recording a key
+      if let Event::BlocksRolledBack { block_height, .. } = event {
+        Self::rollback(&mut tx, *block_height)?;
+        continue;
+      }
+
+      let inserted = tx.execute(
+        "INSERT INTO ord_event_keys (key, height) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        &[&event.idempotency_key(i), &i64::from(event.block_height())],
+      )?;
+
+      if inserted == 0 {
+        log::warn!(
+          "Skipping already applied event {}",
+          event.idempotency_key(i)
+        );
+        continue;
+      }
+
+      Self::apply_event(&mut tx, event)?;
+
+      if let Event::BlockIndexed { block_height, .. } = event {
+        tx.execute(
+          "DELETE FROM ord_event_keys WHERE height < $1",
+          &[&(i64::from(*block_height) - i64::from(self.finality_depth))],
+        )?;
+      }
+    }
+
+    tx.commit()?;
+
+    Ok(())
+  }
+
+  fn apply_event(tx: &mut Transaction, event: &Event) -> Result {
+    match event {
+      Event::BlockIndexed {
+        block_hash,
+        block_height,
+      } => {
+        tx.execute(
+          "INSERT INTO ord_blocks (height, hash) VALUES ($1, $2)",
+          &[&i64::from(*block_height), &block_hash.to_string()],
+        )?;
+      }
+      Event::BlocksRolledBack { block_height, .. } => Self::rollback(tx, *block_height)?,
+      Event::InscriptionCreated {
+        block_height,
+        inscription_id,
+        inscription_number,
+        location,
+        metaprotocol,
+        sequence_number,
+      } => {
+        tx.execute(
+          "INSERT INTO ord_inscriptions
+            (sequence_number, inscription_id, height, genesis_location, location, metaprotocol, inscription_number)
+            VALUES ($1, $2, $3, $4, $4, $5, $6)",
+          &[
+            &i64::from(*sequence_number),
+            &inscription_id.to_string(),
+            &i64::from(*block_height),
+            &location.to_string(),
+            metaprotocol,
+            &i64::from(*inscription_number),
+          ],
+        )?;
+      }
+      Event::InscriptionTransferred {
+        block_height,
+        inscription_id: _,
+        new_location,
+        old_location,
+        sender,
+        sequence_number,
+      } => {
+        tx.execute(
+          "INSERT INTO ord_inscription_transfers
+            (sequence_number, height, old_location, new_location, sender)
+            VALUES ($1, $2, $3, $4, $5)",
+          &[
+            &i64::from(*sequence_number),
+            &i64::from(*block_height),
+            &old_location.to_string(),
+            &new_location.to_string(),
+            &sender.as_ref().map(|sender| hex::encode(sender.as_bytes())),
+          ],
+        )?;
+
+        tx.execute(
+          "UPDATE ord_inscriptions SET location = $2 WHERE sequence_number = $1",
+          &[&i64::from(*sequence_number), &new_location.to_string()],
+        )?;
+      }
+      Event::RuneBurned {
+        amount,
+        block_height,
+        rune_id,
+        txid,
+      }
+      | Event::RuneMinted {
+        amount,
+        block_height,
+        rune_id,
+        txid,
+      } => {
+        tx.execute(
+          "INSERT INTO ord_rune_events (height, type, rune_id, txid, amount)
+            VALUES ($1, $2, $3, $4, $5::TEXT::NUMERIC)",
+          &[
+            &i64::from(*block_height),
+            &event.kind(),
+            &rune_id.to_string(),
+            &txid.to_string(),
+            &amount.to_string(),
+          ],
+        )?;
+      }
+      Event::RuneEtched {
+        block_height,
+        rune,
+        rune_id,
+        txid,
+      } => {
+        tx.execute(
+          "INSERT INTO ord_rune_events (height, type, rune_id, rune, txid)
+            VALUES ($1, $2, $3, $4, $5)",
+          &[
+            &i64::from(*block_height),
+            &event.kind(),
+            &rune_id.to_string(),
+            &rune.to_string(),
+            &txid.to_string(),
+          ],
+        )?;
+      }
+      Event::RuneTransferred {
+        amount,
+        block_height,
+        outpoint,
+        rune_id,
+        txid,
+      } => {
+        tx.execute(
+          "INSERT INTO ord_rune_events (height, type, rune_id, txid, amount, outpoint)
+            VALUES ($1, $2, $3, $4, $5::TEXT::NUMERIC, $6)",
+          &[
+            &i64::from(*block_height),
+            &event.kind(),
+            &rune_id.to_string(),
+            &txid.to_string(),
+            &amount.to_string(),
+            &outpoint.to_string(),
+          ],
+        )?;
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,269 @@
+use {
+  super::{event::Event, filter::Filter, proto, *},
+  prost::Message,
+  std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpStream,
+  },
+};
+
+/// Minimal NATS client speaking the plain text protocol, which is all that is
+/// needed to publish indexer events. Every batch is followed by a `PING`, and
+/// the batch only counts as delivered once the server answers with `PONG`,
+/// at which point the server has processed every preceding `HPUB`. Each
+/// event carries its idempotency key in a `Nats-Msg-Id` header, which JetStream
+/// uses to drop events republished after a restart.
+pub(crate) struct Publisher {
+  encoding: EventEncoding,
+  filter: Option<Filter>,
+  reader: BufReader<TcpStream>,
+  stream: TcpStream,
+  subject_prefix: String,
+}
+
+impl Publisher {
+  pub(crate) fn connect(
+    url: &str,
+    subject_prefix: &str,
+    filter: Option<Filter>,
+    encoding: EventEncoding,
+  ) -> Result<Self> {
+    let address = url
+      .strip_prefix("nats://")
+      .ok_or_else(|| anyhow!("unsupported NATS url `{url}`, only nats:// is supported"))?;
+
+    let stream =
+      TcpStream::connect(address).with_context(|| format!("failed to connect to NATS at {url}"))?;
+
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+
+    let mut publisher = Self {
+      encoding,
+      filter,
+      reader: BufReader::new(stream.try_clone()?),
+      stream,
+      subject_prefix: subject_prefix.into(),
+    };
+
+    let info = publisher.read_line()?;
+
+    ensure!(
+      info.starts_with("INFO "),
+      "NATS server at {url} sent unexpected greeting: {info}"
+    );
+
+    publisher.stream.write_all(
+      format!(
+        "CONNECT {}\r\n",
+        serde_json::json!({
+          "headers": true,
+          "lang": "rust",
+          "name": "ord",
+          "pedantic": false,
+          "verbose": false,
+          "version": env!("CARGO_PKG_VERSION"),
+        })
+      )
+      .as_bytes(),
+    )?;
+
+    publisher.flush()?;
+
+    Ok(publisher)
+  }
+
+  /// Publish `events` matching the publisher's filter on
+  /// `<SUBJECT_PREFIX>.<TYPE>` and wait until the server has acknowledged them.
+  /// `BlocksRolledBack` events are published regardless of the filter, since
+  /// every consumer needs to undo the events of rolled back blocks.
+  pub(crate) fn publish(&mut self, events: &[Event]) -> Result {
+    let mut buffer = Vec::new();
+
+    for (i, event) in events.iter().enumerate() {
+      if let (Some(filter), false) = (&self.filter, event.is_rollback()) {
+        // `serde_json::to_value` rejects amounts above `u64::MAX`, whereas
+        // parsing serialized JSON converts them to floats
+        if !filter.matches(&serde_json::from_slice(&serde_json::to_vec(event)?)?) {
+          continue;
+        }
+      }
+
+      let payload = match self.encoding {
+        EventEncoding::Json => serde_json::to_vec(event)?,
+        EventEncoding::Protobuf => proto::Event::from(event).encode_to_vec(),
+      };
+
+      let headers = format!(
+        "NATS/1.0\r\nNats-Msg-Id: {}\r\n\r\n",
+        event.idempotency_key(i)
+      );
+
+      write!(
+        buffer,
+        "HPUB {}.{} {} {}\r\n{headers}",
+        self.subject_prefix,
+        event.kind(),
+        headers.len(),
+        headers.len() + payload.len(),
+      )?;
+      buffer.extend_from_slice(&payload);
+      buffer.extend_from_slice(b"\r\n");
+    }
+
+    self.stream.write_all(&buffer)?;
+
+    self.flush()
+  }
+
+  fn flush(&mut self) -> Result {
+    self.stream.write_all(b"PING\r\n")?;
+
+    loop {
+      let line = self.read_line()?;
+
+      match line.as_str() {
+        "PONG" => return Ok(()),
+        "PING" => self.stream.write_all(b"PONG\r\n")?,
+        "+OK" => {}
+        _ if line.starts_with("INFO ") => {}
+        _ if line.starts_with("-ERR") => bail!("NATS server error: {line}"),
+        _ => bail!("unexpected message from NATS server: {line}"),
+      }
+    }
+  }
+
+  fn read_line(&mut self) -> Result<String> {
+    let mut line = String::new();
+
+    ensure!(
+      self.reader.read_line(&mut line)? > 0,
+      "NATS server closed connection"
+    );
+
+    Ok(line.trim_end().into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    std::{io::Read, net::TcpListener},
+  };
+
+  #[test]
+  fn publish_waits_for_pong() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("nats://{}", listener.local_addr().unwrap());
+
+    let server = thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+      stream
+        .write_all(b"INFO {\"max_payload\":1048576}\r\n")
+        .unwrap();
+
+      let mut line = String::new();
+      reader.read_line(&mut line).unwrap();
+      assert!(line.starts_with("CONNECT {"));
+
+      line.clear();
+      reader.read_line(&mut line).unwrap();
+      assert_eq!(line, "PING\r\n");
+      stream.write_all(b"PONG\r\n").unwrap();
+
+      line.clear();
+      reader.read_line(&mut line).unwrap();
+      let (headers_len, total_len) = line
+        .strip_prefix("HPUB ord.block_indexed ")
+        .unwrap()
+        .trim_end()
+        .split_once(' ')
+        .map(|(headers, total)| {
+          (
+            headers.parse::<usize>().unwrap(),
+            total.parse::<usize>().unwrap(),
+          )
+        })
+        .unwrap();
+
+      let mut message = vec![0; total_len + 2];
+      reader.read_exact(&mut message).unwrap();
+      assert!(message.ends_with(b"\r\n"));
+
+      assert_eq!(
+        String::from_utf8(message[..headers_len].to_vec()).unwrap(),
+        format!(
+          "NATS/1.0\r\nNats-Msg-Id: 3:{}:0\r\n\r\n",
+          BlockHash::all_zeros()
+        ),
+      );
+
+      line.clear();
+      reader.read_line(&mut line).unwrap();
+      assert_eq!(line, "PING\r\n");
+      stream.write_all(b"PING\r\n+OK\r\n").unwrap();
+
+      line.clear();
+      reader.read_line(&mut line).unwrap();
+      assert_eq!(line, "PONG\r\n");
+      stream.write_all(b"PONG\r\n").unwrap();
+
+      serde_json::from_slice::<Event>(&message[headers_len..total_len]).unwrap()
+    });
+
+    let event = Event::BlockIndexed {
+      block_hash: BlockHash::all_zeros(),
+      block_height: 3,
+    };
+
+    let mut publisher = Publisher::connect(&url, "ord", None, EventEncoding::Json).unwrap();
+
+    publisher.publish(std::slice::from_ref(&event)).unwrap();
+
+    assert_eq!(server.join().unwrap(), event);
+  }
+
+  #[test]
+  fn server_errors_are_reported() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("nats://{}", listener.local_addr().unwrap());
+
+    let server = thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+      stream.write_all(b"INFO {}\r\n").unwrap();
+
+      let mut line = String::new();
+      reader.read_line(&mut line).unwrap();
+      reader.read_line(&mut line).unwrap();
+      assert!(line.ends_with("PING\r\n"));
+
+      stream
+        .write_all(b"-ERR 'Authorization Violation'\r\n")
+        .unwrap();
+    });
+
+    assert_eq!(
+      Publisher::connect(&url, "ord", None, EventEncoding::Json)
+        .err()
+        .unwrap()
+        .to_string(),
+      "NATS server error: -ERR 'Authorization Violation'",
+    );
+
+    server.join().unwrap();
+  }
+
+  #[test]
+  fn only_nats_urls_are_supported() {
+    assert_eq!(
+      Publisher::connect("tls://127.0.0.1:4222", "ord", None, EventEncoding::Json)
+        .err()
+        .unwrap()
+        .to_string(),
+      "unsupported NATS url `tls://127.0.0.1:4222`, only nats:// is supported",
+    );
+  }
+}
@@ -0,0 +1,504 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{
+    hashes::sha256,
+    secp256k1::{
+      rand::{self, RngCore},
+      KeyPair, Message, Secp256k1,
+    },
+  },
+  reqwest::Url,
+  std::{
+    io::{Read, Write},
+    net::TcpStream,
+  },
+};
+
+trait Stream: Read + Write {}
+
+impl<T: Read + Write> Stream for T {}
+
+/// Publishes a Nostr event for every newly indexed inscription that matches
+/// the configured parents, or for every inscription if none are configured.
+/// Relays are written to over a minimal WebSocket client. A relay that cannot
+/// be reached or rejects an event is logged and skipped, so that a flaky relay
+/// never stalls indexing.
+pub(crate) struct Nostr {
+  key_pair: KeyPair,
+  kind: u16,
+  parents: Vec<InscriptionId>,
+  relays: Vec<Url>,
+}
+
+impl Nostr {
+  pub(crate) fn new(relays: &[String], kind: u16, parents: &[InscriptionId]) -> Result<Self> {
+    let secret_key = env::var("NOSTR_SECRET_KEY")
+      .map_err(|_| anyhow!("publishing to Nostr requires `NOSTR_SECRET_KEY` to be set"))?;
+
+    Ok(Self {
+      key_pair: KeyPair::from_seckey_str(&Secp256k1::new(), &secret_key)
+        .context("`NOSTR_SECRET_KEY` must be a hex-encoded secp256k1 secret key")?,
+      kind,
+      parents: parents.to_vec(),
+      relays: relays
+        .iter()
+        .map(|relay| {
+          Url::parse(relay).with_context(|| format!("invalid Nostr relay url `{relay}`"))
+        })
+        .collect::<Result<Vec<Url>>>()?,
+    })
+  }
+
+  pub(crate) fn matches(&self, inscription: &Inscription) -> bool {
+    self.parents.is_empty()
+      || inscription
+        .parent()
+        .map(|parent| self.parents.contains(&parent))
+        .unwrap_or_default()
+  }
+
+  /// Publish an event for every inscription in `inscriptions` matching the
+  /// configured parents, and one for every `BlocksRolledBack` event in
+  /// `indexer_events`, tagged with `rollback` and the height rolled back to,
+  /// so that clients can discard events of inscriptions in rolled back blocks.
+  pub(crate) fn publish(
+    &self,
+    indexer_events: &[event::Event],
+    inscriptions: &[(InscriptionId, Inscription)],
+    created_at: u32,
+  ) -> Result {
+    let events = indexer_events
+      .iter()
+      .filter_map(|event| match event {
+        event::Event::BlocksRolledBack {
+          block_height,
+          orphaned_block_hash,
+        } => Some(self.rollback(*block_height, *orphaned_block_hash, created_at)),
+        _ => None,
+      })
+      .chain(
+        inscriptions
+          .iter()
+          .filter(|(_, inscription)| self.matches(inscription))
+          .map(|(inscription_id, inscription)| {
+            self.event(*inscription_id, inscription.parent(), created_at)
+          }),
+      )
+      .collect::<Result<Vec<serde_json::Value>>>()?;
+
+    if events.is_empty() {
+      return Ok(());
+    }
+
+    for relay in &self.relays {
+      if let Err(err) = Self::send(relay, &events) {
+        log::warn!("failed to publish inscriptions to Nostr relay {relay}: {err}");
+      }
+    }
+
+    Ok(())
+  }
+
+  fn event(
+    &self,
+    inscription_id: InscriptionId,
+    parent: Option<InscriptionId>,
+    created_at: u32,
+  ) -> Result<serde_json::Value> {
+    let mut tags = vec![vec!["inscription".to_string(), inscription_id.to_string()]];
+
+    if let Some(parent) = parent {
+      tags.push(vec!["parent".into(), parent.to_string()]);
+    }
+
+    self.sign(tags, inscription_id.to_string(), created_at)
+  }
+
+  fn rollback(
+    &self,
+    block_height: u32,
+    orphaned_block_hash: BlockHash,
+    created_at: u32,
+  ) -> Result<serde_json::Value> {
+    self.sign(
+      vec![
+        vec!["rollback".into(), block_height.to_string()],
+        vec!["orphaned".into(), orphaned_block_hash.to_string()],
+      ],
+      format!("blocks at and above height {block_height} were rolled back"),
+      created_at,
+    )
+  }
+
+  fn sign(
+    &self,
+    tags: Vec<Vec<String>>,
+    content: String,
+    created_at: u32,
+  ) -> Result<serde_json::Value> {
+    let pubkey = self.key_pair.x_only_public_key().0.to_string();
+
+    let id = sha256::Hash::hash(
+      serde_json::to_string(&serde_json::json!([
+        0, pubkey, created_at, self.kind, tags, content
+      ]))?
+      .as_bytes(),
+    );
+
+    let sig = Secp256k1::new()
+      .sign_schnorr_no_aux_rand(&Message::from_slice(id.as_byte_array())?, &self.key_pair);
+
+    Ok(serde_json::json!({
+      "id": id.to_string(),
+      "pubkey": pubkey,
+      "created_at": created_at,
+      "kind": self.kind,
+      "tags": tags,
+      "content": content,
+      "sig": sig.to_string(),
+    }))
+  }
+
+  fn send(relay: &Url, events: &[serde_json::Value]) -> Result {
+    let mut stream = Self::connect(relay)?;
+
+    for event in events {
+      write_frame(
+        &mut stream,
+        0x1,
+        serde_json::to_string(&serde_json::json!(["EVENT", event]))?.as_bytes(),
+      )?;
+    }
+
+    let mut pending = events
+      .iter()
+      .map(|event| event["id"].as_str().unwrap_or_default().to_string())
+      .collect::<BTreeSet<String>>();
+
+    while !pending.is_empty() {
+      let (opcode, payload) = read_frame(&mut stream)?;
+
+      match opcode {
+        0x1 => {
+          let message = serde_json::from_slice::<serde_json::Value>(&payload)?;
+
+          if message[0] != "OK" {
+            continue;
+          }
+
+          let id = message[1].as_str().unwrap_or_default();
+
+          if pending.remove(id) && message[2] != true {
+            log::warn!("Nostr relay {relay} rejected event {id}: {}", message[3]);
+          }
+        }
+        0x8 => bail!("relay closed connection"),
+        0x9 => write_frame(&mut stream, 0xA, &payload)?,
+        _ => {}
+      }
+    }
+
+    write_frame(&mut stream, 0x8, &[]).ok();
+
+    Ok(())
+  }
+
+  fn connect(relay: &Url) -> Result<Box<dyn Stream>> {
+    let host = relay
+      .host_str()
+      .ok_or_else(|| anyhow!("Nostr relay url has no host"))?;
+
+    let port = relay
+      .port_or_known_default()
+      .ok_or_else(|| anyhow!("Nostr relay url has no port"))?;
+
+    let tcp = TcpStream::connect((host, port))?;
+    tcp.set_read_timeout(Some(Duration::from_secs(10)))?;
+    tcp.set_write_timeout(Some(Duration::from_secs(10)))?;
+
+    let mut stream: Box<dyn Stream> = match relay.scheme() {
+      "ws" => Box::new(tcp),
+      "wss" => Box::new(native_tls::TlsConnector::new()?.connect(host, tcp)?),
+      scheme => {
+        bail!("unsupported Nostr relay scheme `{scheme}`, only ws:// and wss:// are supported")
+      }
+    };
+
+    let mut key = [0; 16];
+    rand::thread_rng().fill_bytes(&mut key);
+
+    write!(
+      stream,
+      "GET {}{} HTTP/1.1\r\n\
+       Host: {host}\r\n\
+       Upgrade: websocket\r\n\
+       Connection: Upgrade\r\n\
+       Sec-WebSocket-Key: {}\r\n\
+       Sec-WebSocket-Version: 13\r\n\
+       \r\n",
+      relay.path(),
+      relay
+        .query()
+        .map(|query| format!("?{query}"))
+        .unwrap_or_default(),
+      base64::engine::general_purpose::STANDARD.encode(key),
+    )?;
+
+    // read the response one byte at a time, since frames may follow the
+    // headers immediately
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+      let mut byte = [0];
+      stream.read_exact(&mut byte)?;
+      response.push(byte[0]);
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status = response.lines().next().unwrap_or_default();
+
+    ensure!(
+      status.split_whitespace().nth(1) == Some("101"),
+      "relay refused WebSocket upgrade: {status}"
+    );
+
+    Ok(stream)
+  }
+}
+
+/// Write a single masked WebSocket frame, as required of clients.
+fn write_frame(stream: &mut impl Write, opcode: u8, payload: &[u8]) -> Result {
+  let mut frame = vec![0x80 | opcode];
+
+  match payload.len() {
+    len @ 0..=125 => frame.push(0x80 | u8::try_from(len).unwrap()),
+    len @ 126..=0xFFFF => {
+      frame.push(0x80 | 126);
+      frame.extend_from_slice(&u16::try_from(len).unwrap().to_be_bytes());
+    }
+    len => {
+      frame.push(0x80 | 127);
+      frame.extend_from_slice(&u64::try_from(len)?.to_be_bytes());
+    }
+  }
+
+  let mut mask = [0; 4];
+  rand::thread_rng().fill_bytes(&mut mask);
+  frame.extend_from_slice(&mask);
+
+  frame.extend(
+    payload
+      .iter()
+      .enumerate()
+      .map(|(i, byte)| byte ^ mask[i % 4]),
+  );
+
+  stream.write_all(&frame)?;
+
+  Ok(())
+}
+
+/// Read a single WebSocket frame, returning its opcode and unmasked payload.
+/// Fragmented messages are not supported, since relays send small messages.
+fn read_frame(stream: &mut impl Read) -> Result<(u8, Vec<u8>)> {
+  let mut header = [0; 2];
+  stream.read_exact(&mut header)?;
+
+  let len = match header[1] & 0x7F {
+    126 => {
+      let mut len = [0; 2];
+      stream.read_exact(&mut len)?;
+      u64::from(u16::from_be_bytes(len))
+    }
+    127 => {
+      let mut len = [0; 8];
+      stream.read_exact(&mut len)?;
+      u64::from_be_bytes(len)
+    }
+    len => u64::from(len),
+  };
+
+  ensure!(len <= 1 << 20, "WebSocket frame of {len} bytes too large");
+
+  let mask = if header[1] & 0x80 != 0 {
+    let mut mask = [0; 4];
+    stream.read_exact(&mut mask)?;
+    Some(mask)
+  } else {
+    None
+  };
+
+  let mut payload = vec![0; usize::try_from(len)?];
+  stream.read_exact(&mut payload)?;
+
+  if let Some(mask) = mask {
+    for (i, byte) in payload.iter_mut().enumerate() {
+      *byte ^= mask[i % 4];
+    }
+  }
+
+  Ok((header[0] & 0x0F, payload))
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::secp256k1::{schnorr::Signature, XOnlyPublicKey},
+    std::{
+      io::{BufRead, BufReader},
+      net::TcpListener,
+    },
+  };
+
+  fn nostr(relays: Vec<Url>, parents: Vec<InscriptionId>) -> Nostr {
+    Nostr {
+      key_pair: KeyPair::from_seckey_str(
+        &Secp256k1::new(),
+        "0000000000000000000000000000000000000000000000000000000000000003",
+      )
+      .unwrap(),
+      kind: 1,
+      parents,
+      relays,
+    }
+  }
+
+  #[test]
+  fn frames_round_trip() {
+    for len in [0, 125, 126, 0xFFFF, 0x10000] {
+      let payload = vec![7; len];
+      let mut frame = Vec::new();
+      write_frame(&mut frame, 0x1, &payload).unwrap();
+      assert_eq!(read_frame(&mut frame.as_slice()).unwrap(), (0x1, payload));
+    }
+  }
+
+  #[test]
+  fn events_are_signed() {
+    let event = nostr(Vec::new(), Vec::new())
+      .event(inscription_id(1), Some(inscription_id(2)), 1000)
+      .unwrap();
+
+    assert_eq!(
+      event["pubkey"],
+      "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9"
+    );
+
+    assert_eq!(
+      event["tags"],
+      serde_json::json!([
+        ["inscription", inscription_id(1)],
+        ["parent", inscription_id(2)],
+      ])
+    );
+
+    let id = sha256::Hash::hash(
+      serde_json::to_string(&serde_json::json!([
+        0,
+        event["pubkey"],
+        event["created_at"],
+        event["kind"],
+        event["tags"],
+        event["content"],
+      ]))
+      .unwrap()
+      .as_bytes(),
+    );
+
+    assert_eq!(event["id"], id.to_string());
+
+    Secp256k1::new()
+      .verify_schnorr(
+        &event["sig"].as_str().unwrap().parse::<Signature>().unwrap(),
+        &Message::from_slice(id.as_byte_array()).unwrap(),
+        &event["pubkey"]
+          .as_str()
+          .unwrap()
+          .parse::<XOnlyPublicKey>()
+          .unwrap(),
+      )
+      .unwrap();
+  }
+
+  #[test]
+  fn rollbacks_are_tagged_with_height() {
+    let event = nostr(Vec::new(), Vec::new())
+      .rollback(5, BlockHash::all_zeros(), 1000)
+      .unwrap();
+
+    assert_eq!(
+      event["tags"],
+      serde_json::json!([["rollback", "5"], ["orphaned", BlockHash::all_zeros()],])
+    );
+
+    assert_eq!(
+      event["content"],
+      "blocks at and above height 5 were rolled back"
+    );
+  }
+
+  #[test]
+  fn only_children_of_parents_match() {
+    let child = Inscription {
+      parent: Some(inscription_id(1).value()),
+      ..Default::default()
+    };
+
+    assert!(nostr(Vec::new(), Vec::new()).matches(&Inscription::default()));
+    assert!(nostr(Vec::new(), vec![inscription_id(1)]).matches(&child));
+    assert!(!nostr(Vec::new(), vec![inscription_id(2)]).matches(&child));
+    assert!(!nostr(Vec::new(), vec![inscription_id(1)]).matches(&Inscription::default()));
+  }
+
+  #[test]
+  fn events_are_sent_to_relays() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let relay = format!("ws://{}/", listener.local_addr().unwrap())
+      .parse()
+      .unwrap();
+
+    let server = thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+      let mut line = String::new();
+      reader.read_line(&mut line).unwrap();
+      assert_eq!(line, "GET / HTTP/1.1\r\n");
+
+      while line != "\r\n" {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+      }
+
+      stream
+        .write_all(
+          b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\r\n",
+        )
+        .unwrap();
+
+      let (opcode, payload) = read_frame(&mut reader).unwrap();
+      assert_eq!(opcode, 0x1);
+
+      let message = serde_json::from_slice::<serde_json::Value>(&payload).unwrap();
+      assert_eq!(message[0], "EVENT");
+
+      let ok = serde_json::json!(["OK", message[1]["id"], true, ""]).to_string();
+      stream
+        .write_all(&[0x81, u8::try_from(ok.len()).unwrap()])
+        .unwrap();
+      stream.write_all(ok.as_bytes()).unwrap();
+
+      message[1].clone()
+    });
+
+    nostr(vec![relay], Vec::new())
+      .publish(&[], &[(inscription_id(1), Inscription::default())], 1000)
+      .unwrap();
+
+    let event = server.join().unwrap();
+
+    assert_eq!(event["content"], inscription_id(1).to_string());
+    assert_eq!(event["created_at"], 1000);
+  }
+}
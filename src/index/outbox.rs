@@ -0,0 +1,473 @@
+use {
+  super::{
+    bucket::Bucket, entry::Entry as _, event::Event, ipfs::Ipfs, mirror::Mirror, nats::Publisher,
+    nostr::Nostr, proto, *,
+  },
+  prost::Message,
+};
+
+/// External services that events and inscription content are delivered to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Sink {
+  Bucket,
+  Ipfs,
+  Mirror,
+  Nats,
+  Nostr,
+}
+
+impl Sink {
+  const ALL: [Self; 5] = [
+    Self::Bucket,
+    Self::Ipfs,
+    Self::Mirror,
+    Self::Nats,
+    Self::Nostr,
+  ];
+
+  fn bit(self) -> u32 {
+    1 << self as u32
+  }
+
+  /// Whether the sink is delivered the contents of new inscriptions, rather
+  /// than events.
+  fn wants_contents(self) -> bool {
+    matches!(self, Self::Bucket | Self::Ipfs | Self::Nostr)
+  }
+
+  /// Whether the sink has anything to do with a block's `events` and
+  /// `contents`. Nostr is also told about rollbacks.
+  fn wants(self, events: &[Event], contents: &[(InscriptionId, u32)]) -> bool {
+    if self.wants_contents() && !contents.is_empty() {
+      return true;
+    }
+
+    match self {
+      Self::Mirror | Self::Nats => !events.is_empty(),
+      Self::Nostr => events.iter().any(Event::is_rollback),
+      Self::Bucket | Self::Ipfs => false,
+    }
+  }
+
+  fn configured(self, index: &Index) -> bool {
+    match self {
+      Self::Bucket => index.options.content_bucket.is_some(),
+      Self::Ipfs => index.options.ipfs_api_url.is_some(),
+      Self::Mirror => index.options.postgres_url.is_some(),
+      Self::Nats => index.options.nats_url.is_some(),
+      Self::Nostr => !index.options.nostr_relay.is_empty(),
+    }
+  }
+}
+
+impl Display for Sink {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Bucket => "content bucket",
+        Self::Ipfs => "IPFS",
+        Self::Mirror => "Postgres mirror",
+        Self::Nats => "NATS",
+        Self::Nostr => "Nostr",
+      }
+    )
+  }
+}
+
+/// Events and inscription content of a block, waiting to be delivered to the
+/// sinks in `pending`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct Entry {
+  #[prost(uint32, tag = "1")]
+  height: u32,
+  #[prost(uint32, tag = "2")]
+  pending: u32,
+  #[prost(uint32, tag = "3")]
+  timestamp: u32,
+  #[prost(message, repeated, tag = "4")]
+  events: Vec<proto::Event>,
+  #[prost(message, repeated, tag = "5")]
+  contents: Vec<Content>,
+}
+
+/// Reference to an inscription whose content is to be delivered. Bodies can
+/// be hundreds of kilobytes, so they aren't queued, and are instead read from
+/// the inscription's transaction when delivered.
+#[derive(Clone, PartialEq, prost::Message)]
+struct Content {
+  #[prost(string, tag = "1")]
+  inscription_id: String,
+  #[prost(uint32, tag = "2")]
+  sequence_number: u32,
+}
+
+impl Content {
+  /// Read the referenced inscription from the index, returning `None` if it
+  /// is hidden, or if it no longer exists, which happens when its block has
+  /// been rolled back since it was queued.
+  fn load(&self, index: &Index, hidden: &Hidden) -> Result<Option<(InscriptionId, Inscription)>> {
+    let inscription_id = self.inscription_id.parse::<InscriptionId>()?;
+
+    if hidden.contains(inscription_id) {
+      return Ok(None);
+    }
+
+    if index
+      .get_inscription_entry(inscription_id)?
+      .map(|entry| entry.sequence_number)
+      != Some(self.sequence_number)
+    {
+      return Ok(None);
+    }
+
+    let transaction = index
+      .get_transaction(inscription_id.txid)?
+      .ok_or_else(|| anyhow!("transaction of inscription {inscription_id} not found"))?;
+
+    let inscription = ParsedEnvelope::from_transaction(&transaction)
+      .into_iter()
+      .nth(inscription_id.index.try_into().unwrap())
+      .map(|envelope| envelope.payload)
+      .ok_or_else(|| anyhow!("inscription {inscription_id} not found in its transaction"))?;
+
+    Ok(Some((inscription_id, inscription)))
+  }
+}
+
+/// Delivers events and inscription content to external sinks after the
+/// blocks they belong to have been committed, so that a sink which is down
+/// never stalls indexing, and a sink is never told about a block that the
+/// index then fails to commit.
+///
+/// Each block's deliveries are queued in the `OUTBOX` table in the same write
+/// transaction as the block itself, and removed once every sink has received
+/// them. Failed deliveries are logged and retried on the next update, and a
+/// sink which fails receives no later blocks until the failed block has been
+/// delivered, so every sink receives blocks in order. Delivery is
+/// at-least-once: a block may be redelivered after a crash or after the index
+/// is restored to a savepoint, which sinks handle with idempotency keys or
+/// idempotent writes.
+pub(crate) struct Outbox {
+  bucket: Option<(Bucket, reqwest::blocking::Client)>,
+  ipfs: Option<Ipfs>,
+  mirror: Option<Mirror>,
+  nats: Option<Publisher>,
+  nostr: Option<Nostr>,
+}
+
+impl Outbox {
+  pub(crate) fn new(index: &Index) -> Result<Self> {
+    Ok(Self {
+      bucket: index
+        .options
+        .content_bucket()?
+        .map(|bucket| (bucket, reqwest::blocking::Client::new())),
+      ipfs: index
+        .options
+        .ipfs_api_url
+        .as_deref()
+        .map(Ipfs::new)
+        .transpose()?,
+      mirror: None,
+      nats: None,
+      nostr: (!index.options.nostr_relay.is_empty())
+        .then(|| {
+          Nostr::new(
+            &index.options.nostr_relay,
+            index.options.nostr_kind.unwrap_or(1),
+            &index.options.nostr_parent,
+          )
+        })
+        .transpose()?,
+    })
+  }
+
+  /// Whether any configured sink is delivered events.
+  pub(crate) fn wants_events(index: &Index) -> bool {
+    Sink::ALL
+      .into_iter()
+      .any(|sink| !sink.wants_contents() && sink.configured(index))
+  }
+
+  /// Whether any configured sink is delivered inscription content.
+  pub(crate) fn wants_contents(index: &Index) -> bool {
+    Sink::ALL
+      .into_iter()
+      .any(|sink| sink.wants_contents() && sink.configured(index))
+  }
+
+  /// Queue the `events` of the block at `height`, and the contents of the
+  /// inscriptions in `contents`, given by ID and sequence number, for
+  /// delivery once `wtx` has been committed.
+  pub(crate) fn push(
+    index: &Index,
+    wtx: &WriteTransaction,
+    height: u32,
+    timestamp: u32,
+    events: &[Event],
+    contents: &[(InscriptionId, u32)],
+  ) -> Result {
+    let pending = Sink::ALL
+      .into_iter()
+      .filter(|sink| sink.configured(index) && sink.wants(events, contents))
+      .fold(0, |pending, sink| pending | sink.bit());
+
+    if pending == 0 {
+      return Ok(());
+    }
+
+    let mut outbox = wtx.open_table(OUTBOX)?;
+
+    let next = outbox
+      .last()?
+      .map(|(key, _)| key.value() + 1)
+      .unwrap_or_default();
+
+    outbox.insert(
+      next,
+      Entry {
+        height,
+        pending,
+        timestamp,
+        events: events.iter().map(proto::Event::from).collect(),
+        contents: contents
+          .iter()
+          .map(|(inscription_id, sequence_number)| Content {
+            inscription_id: inscription_id.to_string(),
+            sequence_number: *sequence_number,
+          })
+          .collect(),
+      }
+      .encode_to_vec()
+      .as_slice(),
+    )?;
+
+    Ok(())
+  }
+
+  /// Deliver queued blocks to every sink, in the order they were queued.
+  pub(crate) fn deliver(&mut self, index: &Index) -> Result {
+    let entries = index
+      .database
+      .begin_read()?
+      .open_table(OUTBOX)?
+      .iter()?
+      .map(|result| {
+        let (key, entry) = result?;
+        Ok((key.value(), Entry::decode(entry.value())?))
+      })
+      .collect::<Result<Vec<(u64, Entry)>>>()?;
+
+    if entries.is_empty() {
+      return Ok(());
+    }
+
+    let mut cids = Vec::new();
+    let mut failed = 0;
+    let mut updates = Vec::new();
+
+    for (key, mut entry) in entries {
+      let pending = entry.pending;
+
+      let events = entry
+        .events
+        .iter()
+        .cloned()
+        .map(Event::try_from)
+        .collect::<Result<Vec<Event>>>()?;
+
+      let content_sinks = Sink::ALL
+        .into_iter()
+        .filter(|sink| sink.wants_contents())
+        .fold(0, |bits, sink| bits | sink.bit());
+
+      // contents are only read if a sink which will be delivered to wants
+      // them, and if they can't be read, those sinks are retried later.
+      // The hidden list is reread for each block, since it can be changed
+      // while the server is running.
+      let contents = if entry.pending & content_sinks & !failed != 0 {
+        match index.options.hidden().and_then(|hidden| {
+          entry
+            .contents
+            .iter()
+            .filter_map(|content| content.load(index, &hidden).transpose())
+            .collect::<Result<Vec<(InscriptionId, Inscription)>>>()
+        }) {
+          Ok(contents) => contents,
+          Err(err) => {
+            log::error!(
+              "Failed to read contents of block {}, retrying on next update: {err:?}",
+              entry.height
+            );
+            index.metrics.record_error();
+            failed |= content_sinks;
+            Vec::new()
+          }
+        }
+      } else {
+        Vec::new()
+      };
+
+      for sink in Sink::ALL {
+        if entry.pending & sink.bit() == 0 || failed & sink.bit() != 0 {
+          continue;
+        }
+
+        if !sink.configured(index) {
+          log::warn!(
+            "Dropping deliveries of block {} to {sink}, which is no longer configured",
+            entry.height
+          );
+          entry.pending &= !sink.bit();
+          continue;
+        }
+
+        match self.deliver_to(index, sink, &entry, &events, &contents, &mut cids) {
+          Ok(()) => entry.pending &= !sink.bit(),
+          Err(err) => {
+            log::error!(
+              "Failed to deliver block {} to {sink}, retrying on next update: {err:?}",
+              entry.height
+            );
+            index.metrics.record_error();
+            failed |= sink.bit();
+          }
+        }
+      }
+
+      if entry.pending != pending {
+        updates.push((key, entry));
+      }
+    }
+
+    if updates.is_empty() {
+      return Ok(());
+    }
+
+    let wtx = index.begin_write()?;
+
+    {
+      let mut outbox = wtx.open_table(OUTBOX)?;
+
+      for (key, entry) in updates {
+        if entry.pending == 0 {
+          outbox.remove(key)?;
+        } else {
+          outbox.insert(key, entry.encode_to_vec().as_slice())?;
+        }
+      }
+
+      let inscription_id_to_sequence_number = wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+      let mut sequence_number_to_cid = wtx.open_table(SEQUENCE_NUMBER_TO_CID)?;
+
+      for (inscription_id, cid) in cids {
+        let Some(sequence_number) =
+          inscription_id_to_sequence_number.get(&inscription_id.store())?
+        else {
+          continue;
+        };
+
+        sequence_number_to_cid.insert(sequence_number.value(), cid.as_str())?;
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  fn deliver_to(
+    &mut self,
+    index: &Index,
+    sink: Sink,
+    entry: &Entry,
+    events: &[Event],
+    contents: &[(InscriptionId, Inscription)],
+    cids: &mut Vec<(InscriptionId, String)>,
+  ) -> Result {
+    match sink {
+      Sink::Bucket => {
+        let (bucket, client) = self.bucket.as_ref().unwrap();
+
+        for (inscription_id, inscription) in contents {
+          bucket
+            .put(client, *inscription_id, inscription)
+            .with_context(|| format!("failed to upload content of inscription {inscription_id}"))?;
+        }
+      }
+      Sink::Ipfs => {
+        let ipfs = self.ipfs.as_ref().unwrap();
+
+        for (inscription_id, inscription) in contents {
+          if let Some(cid) = ipfs.add(inscription).with_context(|| {
+            format!("failed to add content of inscription {inscription_id} to IPFS")
+          })? {
+            cids.push((*inscription_id, cid));
+          }
+        }
+      }
+      Sink::Mirror => {
+        if self.mirror.is_none() {
+          self.mirror = Some(Mirror::open(
+            index.options.postgres_url.as_deref().unwrap(),
+            index.block_count()?,
+            index.max_savepoints * index.savepoint_interval,
+          )?);
+        }
+
+        // reconnect on the next delivery after a failure, since the failure
+        // may have left the connection unusable
+        if let Err(err) = self.mirror.as_mut().unwrap().apply(events) {
+          self.mirror = None;
+          return Err(err);
+        }
+      }
+      Sink::Nats => {
+        if self.nats.is_none() {
+          self.nats = Some(Publisher::connect(
+            index.options.nats_url.as_deref().unwrap(),
+            index
+              .options
+              .nats_subject_prefix
+              .as_deref()
+              .unwrap_or("ord"),
+            index.options.nats_filter.clone(),
+            index.options.nats_encoding.unwrap_or(EventEncoding::Json),
+          )?);
+        }
+
+        if let Err(err) = self.nats.as_mut().unwrap().publish(events) {
+          self.nats = None;
+          return Err(err);
+        }
+      }
+      Sink::Nostr => {
+        self
+          .nostr
+          .as_ref()
+          .unwrap()
+          .publish(events, contents, entry.timestamp)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sinks_have_distinct_bits() {
+    assert_eq!(
+      Sink::ALL
+        .into_iter()
+        .fold(0, |bits, sink| bits | sink.bit())
+        .count_ones(),
+      5,
+    );
+  }
+}
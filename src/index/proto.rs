@@ -0,0 +1,428 @@
+use {super::event, anyhow::Context};
+
+/// Protobuf encoding of an indexer event, matching `proto/ord.proto`. Used as
+/// a compact alternative to JSON for published events, and to store events in
+/// the outbox until they have been delivered.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct Event {
+  #[prost(oneof = "Kind", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+  pub(crate) kind: Option<Kind>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub(crate) enum Kind {
+  #[prost(message, tag = "1")]
+  BlockIndexed(BlockIndexed),
+  #[prost(message, tag = "2")]
+  InscriptionCreated(InscriptionCreated),
+  #[prost(message, tag = "3")]
+  InscriptionTransferred(InscriptionTransferred),
+  #[prost(message, tag = "4")]
+  RuneBurned(RuneBurned),
+  #[prost(message, tag = "5")]
+  RuneEtched(RuneEtched),
+  #[prost(message, tag = "6")]
+  RuneMinted(RuneMinted),
+  #[prost(message, tag = "7")]
+  RuneTransferred(RuneTransferred),
+  #[prost(message, tag = "8")]
+  BlocksRolledBack(BlocksRolledBack),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct BlockIndexed {
+  #[prost(string, tag = "1")]
+  pub(crate) block_hash: String,
+  #[prost(uint32, tag = "2")]
+  pub(crate) block_height: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct BlocksRolledBack {
+  #[prost(uint32, tag = "1")]
+  pub(crate) block_height: u32,
+  #[prost(string, tag = "2")]
+  pub(crate) orphaned_block_hash: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct InscriptionCreated {
+  #[prost(uint32, tag = "1")]
+  pub(crate) block_height: u32,
+  #[prost(string, tag = "2")]
+  pub(crate) inscription_id: String,
+  #[prost(string, tag = "3")]
+  pub(crate) location: String,
+  #[prost(uint32, tag = "4")]
+  pub(crate) sequence_number: u32,
+  #[prost(string, optional, tag = "5")]
+  pub(crate) metaprotocol: Option<String>,
+  #[prost(sint32, tag = "6")]
+  pub(crate) inscription_number: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct InscriptionTransferred {
+  #[prost(uint32, tag = "1")]
+  pub(crate) block_height: u32,
+  #[prost(string, tag = "2")]
+  pub(crate) inscription_id: String,
+  #[prost(string, tag = "3")]
+  pub(crate) new_location: String,
+  #[prost(string, tag = "4")]
+  pub(crate) old_location: String,
+  #[prost(uint32, tag = "5")]
+  pub(crate) sequence_number: u32,
+  #[prost(string, optional, tag = "6")]
+  pub(crate) sender: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct RuneBurned {
+  #[prost(string, tag = "1")]
+  pub(crate) amount: String,
+  #[prost(uint32, tag = "2")]
+  pub(crate) block_height: u32,
+  #[prost(message, optional, tag = "3")]
+  pub(crate) rune_id: Option<RuneId>,
+  #[prost(string, tag = "4")]
+  pub(crate) txid: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct RuneEtched {
+  #[prost(uint32, tag = "1")]
+  pub(crate) block_height: u32,
+  #[prost(string, tag = "2")]
+  pub(crate) rune: String,
+  #[prost(message, optional, tag = "3")]
+  pub(crate) rune_id: Option<RuneId>,
+  #[prost(string, tag = "4")]
+  pub(crate) txid: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct RuneMinted {
+  #[prost(string, tag = "1")]
+  pub(crate) amount: String,
+  #[prost(uint32, tag = "2")]
+  pub(crate) block_height: u32,
+  #[prost(message, optional, tag = "3")]
+  pub(crate) rune_id: Option<RuneId>,
+  #[prost(string, tag = "4")]
+  pub(crate) txid: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct RuneTransferred {
+  #[prost(string, tag = "1")]
+  pub(crate) amount: String,
+  #[prost(uint32, tag = "2")]
+  pub(crate) block_height: u32,
+  #[prost(string, tag = "3")]
+  pub(crate) outpoint: String,
+  #[prost(message, optional, tag = "4")]
+  pub(crate) rune_id: Option<RuneId>,
+  #[prost(string, tag = "5")]
+  pub(crate) txid: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct RuneId {
+  #[prost(uint32, tag = "1")]
+  pub(crate) height: u32,
+  #[prost(uint32, tag = "2")]
+  pub(crate) index: u32,
+}
+
+impl From<crate::RuneId> for RuneId {
+  fn from(id: crate::RuneId) -> Self {
+    Self {
+      height: id.height,
+      index: id.index.into(),
+    }
+  }
+}
+
+impl From<&event::Event> for Event {
+  fn from(event: &event::Event) -> Self {
+    let kind = match event.clone() {
+      event::Event::BlockIndexed {
+        block_hash,
+        block_height,
+      } => Kind::BlockIndexed(BlockIndexed {
+        block_hash: block_hash.to_string(),
+        block_height,
+      }),
+      event::Event::BlocksRolledBack {
+        block_height,
+        orphaned_block_hash,
+      } => Kind::BlocksRolledBack(BlocksRolledBack {
+        block_height,
+        orphaned_block_hash: orphaned_block_hash.to_string(),
+      }),
+      event::Event::InscriptionCreated {
+        block_height,
+        inscription_id,
+        inscription_number,
+        location,
+        metaprotocol,
+        sequence_number,
+      } => Kind::InscriptionCreated(InscriptionCreated {
+        block_height,
+        inscription_id: inscription_id.to_string(),
+        location: location.to_string(),
+        sequence_number,
+        metaprotocol,
+        inscription_number,
+      }),
+      event::Event::InscriptionTransferred {
+        block_height,
+        inscription_id,
+        new_location,
+        old_location,
+        sender,
+        sequence_number,
+      } => Kind::InscriptionTransferred(InscriptionTransferred {
+        block_height,
+        inscription_id: inscription_id.to_string(),
+        new_location: new_location.to_string(),
+        old_location: old_location.to_string(),
+        sequence_number,
+        sender: sender.map(|sender| hex::encode(sender.as_bytes())),
+      }),
+      event::Event::RuneBurned {
+        amount,
+        block_height,
+        rune_id,
+        txid,
+      } => Kind::RuneBurned(RuneBurned {
+        amount: amount.to_string(),
+        block_height,
+        rune_id: Some(rune_id.into()),
+        txid: txid.to_string(),
+      }),
+      event::Event::RuneEtched {
+        block_height,
+        rune,
+        rune_id,
+        txid,
+      } => Kind::RuneEtched(RuneEtched {
+        block_height,
+        rune: rune.to_string(),
+        rune_id: Some(rune_id.into()),
+        txid: txid.to_string(),
+      }),
+      event::Event::RuneMinted {
+        amount,
+        block_height,
+        rune_id,
+        txid,
+      } => Kind::RuneMinted(RuneMinted {
+        amount: amount.to_string(),
+        block_height,
+        rune_id: Some(rune_id.into()),
+        txid: txid.to_string(),
+      }),
+      event::Event::RuneTransferred {
+        amount,
+        block_height,
+        outpoint,
+        rune_id,
+        txid,
+      } => Kind::RuneTransferred(RuneTransferred {
+        amount: amount.to_string(),
+        block_height,
+        outpoint: outpoint.to_string(),
+        rune_id: Some(rune_id.into()),
+        txid: txid.to_string(),
+      }),
+    };
+
+    Self { kind: Some(kind) }
+  }
+}
+
+impl TryFrom<RuneId> for crate::RuneId {
+  type Error = anyhow::Error;
+
+  fn try_from(id: RuneId) -> anyhow::Result<Self> {
+    Ok(Self {
+      height: id.height,
+      index: id.index.try_into()?,
+    })
+  }
+}
+
+impl TryFrom<Event> for event::Event {
+  type Error = anyhow::Error;
+
+  fn try_from(event: Event) -> anyhow::Result<Self> {
+    let rune_id = |rune_id: Option<RuneId>| -> anyhow::Result<crate::RuneId> {
+      rune_id.context("missing rune ID")?.try_into()
+    };
+
+    Ok(match event.kind.context("missing event kind")? {
+      Kind::BlockIndexed(BlockIndexed {
+        block_hash,
+        block_height,
+      }) => Self::BlockIndexed {
+        block_hash: block_hash.parse()?,
+        block_height,
+      },
+      Kind::BlocksRolledBack(BlocksRolledBack {
+        block_height,
+        orphaned_block_hash,
+      }) => Self::BlocksRolledBack {
+        block_height,
+        orphaned_block_hash: orphaned_block_hash.parse()?,
+      },
+      Kind::InscriptionCreated(InscriptionCreated {
+        block_height,
+        inscription_id,
+        location,
+        sequence_number,
+        metaprotocol,
+        inscription_number,
+      }) => Self::InscriptionCreated {
+        block_height,
+        inscription_id: inscription_id.parse()?,
+        inscription_number,
+        location: location.parse()?,
+        metaprotocol,
+        sequence_number,
+      },
+      Kind::InscriptionTransferred(InscriptionTransferred {
+        block_height,
+        inscription_id,
+        new_location,
+        old_location,
+        sequence_number,
+        sender,
+      }) => Self::InscriptionTransferred {
+        block_height,
+        inscription_id: inscription_id.parse()?,
+        new_location: new_location.parse()?,
+        old_location: old_location.parse()?,
+        sender: sender
+          .map(|sender| hex::decode(sender).map(crate::ScriptBuf::from_bytes))
+          .transpose()?,
+        sequence_number,
+      },
+      Kind::RuneBurned(RuneBurned {
+        amount,
+        block_height,
+        rune_id: id,
+        txid,
+      }) => Self::RuneBurned {
+        amount: amount.parse()?,
+        block_height,
+        rune_id: rune_id(id)?,
+        txid: txid.parse()?,
+      },
+      Kind::RuneEtched(RuneEtched {
+        block_height,
+        rune,
+        rune_id: id,
+        txid,
+      }) => Self::RuneEtched {
+        block_height,
+        rune: rune.parse()?,
+        rune_id: rune_id(id)?,
+        txid: txid.parse()?,
+      },
+      Kind::RuneMinted(RuneMinted {
+        amount,
+        block_height,
+        rune_id: id,
+        txid,
+      }) => Self::RuneMinted {
+        amount: amount.parse()?,
+        block_height,
+        rune_id: rune_id(id)?,
+        txid: txid.parse()?,
+      },
+      Kind::RuneTransferred(RuneTransferred {
+        amount,
+        block_height,
+        outpoint,
+        rune_id: id,
+        txid,
+      }) => Self::RuneTransferred {
+        amount: amount.parse()?,
+        block_height,
+        outpoint: outpoint.parse()?,
+        rune_id: rune_id(id)?,
+        txid: txid.parse()?,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::{BlockHash, Hash, Txid},
+    prost::Message,
+  };
+
+  #[test]
+  fn block_indexed_encoding() {
+    let encoded = Event::from(&event::Event::BlockIndexed {
+      block_hash: BlockHash::all_zeros(),
+      block_height: 3,
+    })
+    .encode_to_vec();
+
+    let mut expected = vec![0x0a, 0x44, 0x0a, 0x40];
+    expected.extend_from_slice(&[b'0'; 64]);
+    expected.extend_from_slice(&[0x10, 0x03]);
+
+    assert_eq!(encoded, expected);
+  }
+
+  #[test]
+  fn events_round_trip() {
+    let event = Event::from(&event::Event::RuneMinted {
+      amount: u128::MAX,
+      block_height: 8,
+      rune_id: crate::RuneId {
+        height: 2,
+        index: 1,
+      },
+      txid: Txid::all_zeros(),
+    });
+
+    assert_eq!(
+      Event::decode(event.encode_to_vec().as_slice()).unwrap(),
+      event
+    );
+
+    assert_eq!(
+      event::Event::try_from(event.clone()).unwrap(),
+      event::Event::RuneMinted {
+        amount: u128::MAX,
+        block_height: 8,
+        rune_id: crate::RuneId {
+          height: 2,
+          index: 1,
+        },
+        txid: Txid::all_zeros(),
+      },
+    );
+
+    assert_eq!(
+      event.kind,
+      Some(Kind::RuneMinted(RuneMinted {
+        amount: u128::MAX.to_string(),
+        block_height: 8,
+        rune_id: Some(RuneId {
+          height: 2,
+          index: 1,
+        }),
+        txid: Txid::all_zeros().to_string(),
+      })),
+    );
+  }
+}
@@ -0,0 +1,325 @@
+use {super::*, clap::ValueEnum};
+
+/// Protocols whose state `ord protocol reindex` can rebuild on its own.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum Protocol {
+  Runes,
+}
+
+/// Index tables holding protocol state, which `ord protocol` exposes for
+/// debugging without ad-hoc redb scripts.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum ProtocolTable {
+  OutpointToRuneBalances,
+  RuneIdToRuneEntry,
+  RuneToRuneId,
+  SequenceNumberToRuneId,
+  TeleburnClaimToInscriptionId,
+  TransactionIdToRune,
+}
+
+pub(crate) struct ProtocolTableStats {
+  pub(crate) entries: u64,
+  pub(crate) stored_bytes: u64,
+  pub(crate) total_bytes: u64,
+}
+
+impl ProtocolTable {
+  pub(crate) const ALL: [Self; 6] = [
+    Self::OutpointToRuneBalances,
+    Self::RuneIdToRuneEntry,
+    Self::RuneToRuneId,
+    Self::SequenceNumberToRuneId,
+    Self::TeleburnClaimToInscriptionId,
+    Self::TransactionIdToRune,
+  ];
+
+  pub(crate) fn protocol(self) -> &'static str {
+    match self {
+      Self::TeleburnClaimToInscriptionId => "teleburn",
+      _ => "runes",
+    }
+  }
+
+  pub(crate) fn name(self) -> String {
+    match self {
+      Self::OutpointToRuneBalances => OUTPOINT_TO_RUNE_BALANCES.name().into(),
+      Self::RuneIdToRuneEntry => RUNE_ID_TO_RUNE_ENTRY.name().into(),
+      Self::RuneToRuneId => RUNE_TO_RUNE_ID.name().into(),
+      Self::SequenceNumberToRuneId => SEQUENCE_NUMBER_TO_RUNE_ID.name().into(),
+      Self::TeleburnClaimToInscriptionId => TELEBURN_CLAIM_TO_INSCRIPTION_ID.name().into(),
+      Self::TransactionIdToRune => TRANSACTION_ID_TO_RUNE.name().into(),
+    }
+  }
+}
+
+fn table_stats<K: RedbKey + 'static, V: RedbValue + 'static>(
+  table: impl ReadableTable<K, V>,
+) -> Result<ProtocolTableStats> {
+  let stats = table.stats()?;
+
+  Ok(ProtocolTableStats {
+    entries: table.len()?,
+    stored_bytes: stats.stored_bytes(),
+    total_bytes: stats.stored_bytes() + stats.metadata_bytes() + stats.fragmented_bytes(),
+  })
+}
+
+fn multimap_table_stats<K: RedbKey + 'static, V: RedbKey + 'static>(
+  table: impl ReadableMultimapTable<K, V>,
+) -> Result<ProtocolTableStats> {
+  let stats = table.stats()?;
+
+  Ok(ProtocolTableStats {
+    entries: table.len()?,
+    stored_bytes: stats.stored_bytes(),
+    total_bytes: stats.stored_bytes() + stats.metadata_bytes() + stats.fragmented_bytes(),
+  })
+}
+
+impl Index {
+  pub(crate) fn protocol_table_stats(&self, table: ProtocolTable) -> Result<ProtocolTableStats> {
+    let rtx = self.database.begin_read()?;
+
+    match table {
+      ProtocolTable::OutpointToRuneBalances => {
+        table_stats(rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?)
+      }
+      ProtocolTable::RuneIdToRuneEntry => table_stats(rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?),
+      ProtocolTable::RuneToRuneId => table_stats(rtx.open_table(RUNE_TO_RUNE_ID)?),
+      ProtocolTable::SequenceNumberToRuneId => {
+        table_stats(rtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?)
+      }
+      ProtocolTable::TeleburnClaimToInscriptionId => {
+        multimap_table_stats(rtx.open_multimap_table(TELEBURN_CLAIM_TO_INSCRIPTION_ID)?)
+      }
+      ProtocolTable::TransactionIdToRune => table_stats(rtx.open_table(TRANSACTION_ID_TO_RUNE)?),
+    }
+  }
+
+  /// Values stored under `key` in `table`, decoded for display. `key` is
+  /// parsed according to the table's key type, e.g. an outpoint for
+  /// `outpoint-to-rune-balances` or `<CHAIN>:<ASSET>` for
+  /// `teleburn-claim-to-inscription-id`.
+  pub(crate) fn protocol_table_get(&self, table: ProtocolTable, key: &str) -> Result<Vec<String>> {
+    let rtx = self.database.begin_read()?;
+
+    let parse_error = || format!("invalid key `{key}` for table `{}`", table.name());
+
+    let values = match table {
+      ProtocolTable::OutpointToRuneBalances => {
+        let outpoint = key.parse::<OutPoint>().with_context(parse_error)?;
+
+        let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+
+        let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
+          return Ok(Vec::new());
+        };
+
+        load_rune_balances(balances.value())?
+          .into_iter()
+          .map(|(id, amount)| format!("{id}: {amount}"))
+          .collect()
+      }
+      ProtocolTable::RuneIdToRuneEntry => {
+        let id = key.parse::<RuneId>().with_context(parse_error)?;
+
+        rtx
+          .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+          .get(&id.store())?
+          .map(|entry| format!("{:?}", RuneEntry::load(entry.value())))
+          .into_iter()
+          .collect()
+      }
+      ProtocolTable::RuneToRuneId => {
+        let rune = key.parse::<Rune>().with_context(parse_error)?;
+
+        rtx
+          .open_table(RUNE_TO_RUNE_ID)?
+          .get(rune.0)?
+          .map(|id| RuneId::load(id.value()).to_string())
+          .into_iter()
+          .collect()
+      }
+      ProtocolTable::SequenceNumberToRuneId => {
+        let sequence_number = key.parse::<u32>().with_context(parse_error)?;
+
+        rtx
+          .open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?
+          .get(sequence_number)?
+          .map(|id| RuneId::load(id.value()).to_string())
+          .into_iter()
+          .collect()
+      }
+      ProtocolTable::TeleburnClaimToInscriptionId => rtx
+        .open_multimap_table(TELEBURN_CLAIM_TO_INSCRIPTION_ID)?
+        .get(key)?
+        .map(|result| {
+          result
+            .map(|inscription_id| InscriptionId::load(inscription_id.value()).to_string())
+            .map_err(|err| err.into())
+        })
+        .collect::<Result<Vec<String>>>()?,
+      ProtocolTable::TransactionIdToRune => {
+        let txid = key.parse::<Txid>().with_context(parse_error)?;
+
+        rtx
+          .open_table(TRANSACTION_ID_TO_RUNE)?
+          .get(&txid.store())?
+          .map(|rune| Rune(rune.value()).to_string())
+          .into_iter()
+          .collect()
+      }
+    };
+
+    Ok(values)
+  }
+
+  /// Height of the next block to be replayed by a rune reindex which was
+  /// interrupted, if any. The index can't be updated until the reindex is
+  /// resumed and finished, since rune state is incomplete until then.
+  pub(crate) fn interrupted_rune_reindex(&self) -> Result<Option<u32>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(STATISTIC_TO_COUNT)?
+        .get(&Statistic::RuneReindexHeight.key())?
+        .map(|height| height.value().try_into().unwrap()),
+    )
+  }
+
+  /// Rebuild the state of `protocol` by clearing its tables and replaying
+  /// every block from the protocol's activation height, without touching the
+  /// inscription index. Progress is committed every `--commit-interval`
+  /// blocks, so that an interrupted reindex can be resumed by passing the
+  /// height it stopped at as `from`. Returns the number of blocks replayed.
+  pub(crate) fn reindex_protocol(&self, protocol: Protocol, from: Option<u32>) -> Result<u32> {
+    match protocol {
+      Protocol::Runes => ensure!(self.index_runes, "index was not built with `--index-runes`"),
+    }
+
+    let block_count = self.block_count()?;
+    let interrupted = self.interrupted_rune_reindex()?;
+
+    let start = match from {
+      Some(from) => {
+        ensure!(
+          interrupted == Some(from),
+          "{}",
+          match interrupted {
+            Some(height) => format!("interrupted rune reindex must be resumed from block {height}"),
+            None => "no interrupted rune reindex to resume".into(),
+          }
+        );
+        from
+      }
+      None => {
+        let wtx = self.begin_write()?;
+
+        wtx.delete_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        wtx.delete_table(RUNE_ID_TO_RUNE_ENTRY)?;
+        wtx.delete_table(RUNE_TO_RUNE_ID)?;
+        wtx.delete_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+        wtx.delete_table(TRANSACTION_ID_TO_RUNE)?;
+
+        wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+        wtx.open_table(RUNE_TO_RUNE_ID)?;
+        wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+        wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
+
+        {
+          let mut quarantined_entry_to_value = wtx.open_table(QUARANTINED_ENTRY_TO_VALUE)?;
+
+          let prefix = format!("{}:", OUTPOINT_TO_RUNE_BALANCES.name());
+
+          let mut quarantined = Vec::new();
+
+          for result in quarantined_entry_to_value.range(prefix.as_str()..)? {
+            let (key, _value) = result?;
+
+            if !key.value().starts_with(&prefix) {
+              break;
+            }
+
+            quarantined.push(key.value().to_string());
+          }
+
+          for key in &quarantined {
+            quarantined_entry_to_value.remove(key.as_str())?;
+          }
+
+          let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+
+          let count = statistic_to_count
+            .get(&Statistic::QuarantinedEntries.key())?
+            .map(|count| count.value())
+            .unwrap_or_default();
+
+          statistic_to_count.insert(
+            &Statistic::QuarantinedEntries.key(),
+            count.saturating_sub(quarantined.len().try_into().unwrap()),
+          )?;
+          statistic_to_count.remove(&Statistic::Runes.key())?;
+          statistic_to_count.remove(&Statistic::ReservedRunes.key())?;
+
+          if self.params.first_rune_height < block_count {
+            statistic_to_count.insert(
+              &Statistic::RuneReindexHeight.key(),
+              u64::from(self.params.first_rune_height),
+            )?;
+          }
+        }
+
+        wtx.commit()?;
+
+        self.params.first_rune_height
+      }
+    };
+
+    let mut height = start;
+
+    while height < block_count {
+      let end = block_count.min(height.saturating_add(self.commit_interval));
+
+      let wtx = self.begin_write()?;
+
+      {
+        let inscription_id_to_sequence_number =
+          wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+        let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+
+        for height in height..end {
+          let block = self
+            .get_block_by_height(height)?
+            .ok_or_else(|| anyhow!("block {height} not found"))?;
+
+          updater::index_runes(
+            self,
+            &wtx,
+            height,
+            &block.into(),
+            None,
+            &inscription_id_to_sequence_number,
+            &mut statistic_to_count,
+          )?;
+        }
+
+        if end == block_count {
+          statistic_to_count.remove(&Statistic::RuneReindexHeight.key())?;
+        } else {
+          statistic_to_count.insert(&Statistic::RuneReindexHeight.key(), u64::from(end))?;
+        }
+      }
+
+      wtx.commit()?;
+
+      log::info!("Replayed runes up to block {}", end - 1);
+
+      height = end;
+    }
+
+    Ok(block_count.saturating_sub(start))
+  }
+}
@@ -0,0 +1,284 @@
+use {super::*, brotli::Decompressor, memchr::memmem, std::borrow::Cow};
+
+/// Upper bound on the decompressed size of brotli-encoded content scanned for
+/// recursive endpoints, so that decompression bombs can't stall the updater.
+const MAX_DECOMPRESSED_SIZE: u64 = 4_000_000;
+
+/// Content types which can request recursive endpoints. Content of any other
+/// type isn't scanned.
+const SCANNED_CONTENT_TYPES: [&str; 5] = [
+  "application/javascript",
+  "image/svg+xml",
+  "text/css",
+  "text/html",
+  "text/javascript",
+];
+
+/// Recursive endpoints that inscription content can request, either
+/// `/content/<INSCRIPTION_ID>` or one of the endpoints under `/r/`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecursiveEndpoint {
+  BlockHash,
+  BlockHeight,
+  BlockTime,
+  Checkpoint,
+  Children,
+  Content,
+  Metadata,
+  Sat,
+  Teleburn,
+}
+
+impl RecursiveEndpoint {
+  const ALL: [Self; 9] = [
+    Self::BlockHash,
+    Self::BlockHeight,
+    Self::BlockTime,
+    Self::Checkpoint,
+    Self::Children,
+    Self::Content,
+    Self::Metadata,
+    Self::Sat,
+    Self::Teleburn,
+  ];
+
+  fn path(self) -> &'static str {
+    match self {
+      Self::BlockHash => "/r/blockhash",
+      Self::BlockHeight => "/r/blockheight",
+      Self::BlockTime => "/r/blocktime",
+      Self::Checkpoint => "/r/checkpoint/",
+      Self::Children => "/r/children/",
+      Self::Content => "/content/",
+      Self::Metadata => "/r/metadata/",
+      Self::Sat => "/r/sat/",
+      Self::Teleburn => "/r/teleburn/",
+    }
+  }
+
+  /// Whether `rest`, which follows `/r/`, requests this endpoint. Names
+  /// followed by further alphanumeric characters don't count, so that
+  /// `/r/blockhashes` doesn't request `/r/blockhash`.
+  fn requested_by(self, rest: &[u8]) -> bool {
+    let Some(name) = self.path().strip_prefix("/r/") else {
+      return false;
+    };
+
+    rest.starts_with(name.as_bytes())
+      && (name.ends_with('/')
+        || !rest
+          .get(name.len())
+          .map(u8::is_ascii_alphanumeric)
+          .unwrap_or_default())
+  }
+
+  fn mask(self) -> u16 {
+    1 << Self::ALL
+      .iter()
+      .position(|endpoint| *endpoint == self)
+      .unwrap()
+  }
+}
+
+impl Display for RecursiveEndpoint {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.path())
+  }
+}
+
+/// Find the recursive endpoints requested by `inscription`'s content,
+/// returned as a bitmask with one bit per endpoint in
+/// [`RecursiveEndpoint::ALL`].
+pub(crate) fn recursive_endpoints(inscription: &Inscription) -> u16 {
+  let scanned = inscription
+    .content_type()
+    .and_then(|content_type| content_type.split(';').next())
+    .map(|essence| {
+      SCANNED_CONTENT_TYPES
+        .iter()
+        .any(|scanned| essence.trim().eq_ignore_ascii_case(scanned))
+    })
+    .unwrap_or_default();
+
+  if !scanned {
+    return 0;
+  }
+
+  let Some(body) = decoded_body(inscription) else {
+    return 0;
+  };
+
+  body_endpoints(&body)
+}
+
+/// Find the recursive endpoints requested by `body`, which must already be
+/// decoded. Endpoints under `/r/` are found with a single scan for `/r/`,
+/// followed by a check of what comes after each match.
+pub(crate) fn body_endpoints(body: &[u8]) -> u16 {
+  let mut mask = 0;
+
+  for i in memmem::find_iter(body, b"/r/") {
+    let rest = &body[i + 3..];
+
+    if let Some(endpoint) = RecursiveEndpoint::ALL
+      .into_iter()
+      .find(|endpoint| endpoint.requested_by(rest))
+    {
+      mask |= endpoint.mask();
+    }
+  }
+
+  if memmem::find(body, b"/content/").is_some() {
+    mask |= RecursiveEndpoint::Content.mask();
+  }
+
+  mask
+}
+
+/// `inscription`'s body, ready to be scanned. Brotli-encoded content is
+/// decompressed, and content with any other encoding, or which fails to
+/// decompress, is skipped.
+pub(crate) fn decoded_body(inscription: &Inscription) -> Option<Cow<'_, [u8]>> {
+  let body = inscription.body()?;
+
+  match inscription.content_encoding() {
+    None => Some(Cow::Borrowed(body)),
+    Some(content_encoding) if content_encoding == "br" => {
+      let mut decompressed = Vec::new();
+
+      Decompressor::new(body, 4096)
+        .take(MAX_DECOMPRESSED_SIZE)
+        .read_to_end(&mut decompressed)
+        .ok()?;
+
+      Some(Cow::Owned(decompressed))
+    }
+    Some(_) => None,
+  }
+}
+
+pub(crate) fn load_recursive_endpoints(mask: u16) -> Vec<RecursiveEndpoint> {
+  RecursiveEndpoint::ALL
+    .into_iter()
+    .filter(|endpoint| mask & endpoint.mask() != 0)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn endpoints(body: &str) -> Vec<RecursiveEndpoint> {
+    load_recursive_endpoints(recursive_endpoints(&inscription("text/html", body)))
+  }
+
+  #[test]
+  fn no_endpoints() {
+    assert_eq!(endpoints("<p>hello</p>"), Vec::new());
+    assert_eq!(endpoints("/r/"), Vec::new());
+    assert_eq!(endpoints("/r/blockhashes"), Vec::new());
+    assert_eq!(
+      recursive_endpoints(&Inscription::new(Some("text/html".into()), None)),
+      0
+    );
+  }
+
+  #[test]
+  fn only_scriptable_content_types_are_scanned() {
+    for content_type in [
+      "application/javascript",
+      "image/svg+xml",
+      "text/css",
+      "text/html;charset=utf-8",
+      "text/javascript",
+      "Text/HTML; charset=UTF-8",
+    ] {
+      assert_eq!(
+        load_recursive_endpoints(recursive_endpoints(&inscription(
+          content_type,
+          "/r/blockheight"
+        ))),
+        [RecursiveEndpoint::BlockHeight],
+        "{content_type}",
+      );
+    }
+
+    for content_type in ["text/plain", "application/json", "image/png"] {
+      assert_eq!(
+        recursive_endpoints(&inscription(content_type, "/r/blockheight")),
+        0,
+        "{content_type}",
+      );
+    }
+
+    assert_eq!(
+      recursive_endpoints(&Inscription::new(None, Some("/r/blockheight".into()))),
+      0
+    );
+  }
+
+  #[test]
+  fn endpoints_are_detected() {
+    assert_eq!(
+      endpoints(
+        "<script>fetch('/r/sat/1/at/-1');fetch('/r/blockheight').then(() => fetch('/r/blockhash'))</script><img src=/content/foo>"
+      ),
+      [
+        RecursiveEndpoint::BlockHash,
+        RecursiveEndpoint::BlockHeight,
+        RecursiveEndpoint::Content,
+        RecursiveEndpoint::Sat,
+      ]
+    );
+
+    assert_eq!(
+      endpoints("fetch(`/r/blockhash/${height}`)"),
+      [RecursiveEndpoint::BlockHash]
+    );
+
+    assert_eq!(
+      endpoints("/r/blockhashes /r/blocktime /r/children/"),
+      [RecursiveEndpoint::BlockTime, RecursiveEndpoint::Children]
+    );
+  }
+
+  #[test]
+  fn compressed_content_is_scanned() {
+    let mut compressed = Vec::new();
+
+    brotli::CompressorWriter::new(&mut compressed, 4096, 9, 22)
+      .write_all(b"fetch('/r/metadata/foo')")
+      .unwrap();
+
+    let inscription = Inscription {
+      body: Some(compressed),
+      content_encoding: Some("br".into()),
+      content_type: Some("text/html".into()),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      load_recursive_endpoints(recursive_endpoints(&inscription)),
+      [RecursiveEndpoint::Metadata]
+    );
+
+    assert_eq!(
+      recursive_endpoints(&Inscription {
+        content_encoding: Some("gzip".into()),
+        ..inscription
+      }),
+      0
+    );
+  }
+
+  #[test]
+  fn masks_are_distinct() {
+    assert_eq!(
+      RecursiveEndpoint::ALL
+        .into_iter()
+        .fold(0, |mask, endpoint| mask | endpoint.mask()),
+      0b1_1111_1111
+    );
+  }
+}
@@ -0,0 +1,228 @@
+use {
+  super::*,
+  crate::templates::{InscriptionJson, OutputJson, SatJson},
+  reqwest::{blocking::Client, header, StatusCode, Url},
+  serde::de::DeserializeOwned,
+};
+
+/// Index backed by the JSON API of a remote ord server, for hosts that can't
+/// keep a local index. The server must be run with `--enable-json-api`, and
+/// is trusted to return correct results.
+pub(crate) struct RemoteIndex {
+  client: Client,
+  url: Url,
+}
+
+impl RemoteIndex {
+  pub(crate) fn new(url: &str) -> Result<Self> {
+    let mut url = Url::parse(url).with_context(|| format!("invalid index url `{url}`"))?;
+
+    if !url.path().ends_with('/') {
+      url.set_path(&format!("{}/", url.path()));
+    }
+
+    Ok(Self {
+      client: Client::new(),
+      url,
+    })
+  }
+
+  fn get(&self, path: &str) -> Result<Option<reqwest::blocking::Response>> {
+    let url = self.url.join(path)?;
+
+    let response = self
+      .client
+      .get(url.clone())
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .with_context(|| format!("failed to query remote index at {url}"))?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    ensure!(
+      response.status().is_success(),
+      "remote index returned {} for {url}: {}",
+      response.status(),
+      response.text().unwrap_or_default(),
+    );
+
+    Ok(Some(response))
+  }
+
+  fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+    self
+      .get(path)?
+      .map(|response| {
+        response
+          .json()
+          .with_context(|| format!("remote index returned invalid JSON for `{path}`"))
+      })
+      .transpose()
+  }
+
+  pub(crate) fn block_count(&self) -> Result<u32> {
+    self
+      .get("blockcount")?
+      .ok_or_else(|| anyhow!("remote index returned not found for `blockcount`"))?
+      .text()?
+      .parse()
+      .context("remote index returned invalid block count")
+  }
+
+  /// Ensure the remote index has caught up with Bitcoin Core, which the
+  /// wallet's outputs come from.
+  pub(crate) fn check_sync(&self, client: &bitcoincore_rpc::Client) -> Result {
+    let remote = self.block_count()?;
+    let local = u32::try_from(client.get_block_count()? + 1)?;
+
+    ensure!(
+      remote >= local,
+      "remote index at {} has {remote} blocks but Bitcoin Core has {local}",
+      self.url,
+    );
+
+    Ok(())
+  }
+
+  pub(crate) fn output(&self, outpoint: OutPoint) -> Result<Option<OutputJson>> {
+    self.get_json(&format!("output/{outpoint}"))
+  }
+
+  pub(crate) fn inscription(&self, query: impl Display) -> Result<Option<InscriptionJson>> {
+    self.get_json(&format!("inscription/{query}"))
+  }
+
+  pub(crate) fn sat(&self, sat: Sat) -> Result<Option<SatJson>> {
+    self.get_json(&format!("sat/{}", sat.n()))
+  }
+
+  pub(crate) fn get_inscriptions(
+    &self,
+    utxos: &BTreeMap<OutPoint, Amount>,
+  ) -> Result<BTreeMap<SatPoint, InscriptionId>> {
+    let mut inscriptions = BTreeMap::new();
+
+    for outpoint in utxos.keys() {
+      let output = self.output(*outpoint)?.ok_or_else(|| {
+        anyhow!("output in Bitcoin Core wallet but not in remote index: {outpoint}")
+      })?;
+
+      for inscription_id in output.inscriptions {
+        let inscription = self
+          .inscription(inscription_id)?
+          .ok_or_else(|| anyhow!("inscription {inscription_id} not found in remote index"))?;
+
+        inscriptions.insert(inscription.satpoint, inscription_id);
+      }
+    }
+
+    Ok(inscriptions)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    std::io::{BufRead, BufReader, Write},
+  };
+
+  #[test]
+  fn inscriptions_are_queried_from_output_and_inscription_endpoints() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/ord", listener.local_addr().unwrap());
+
+    let outpoint = OutPoint {
+      txid: txid(1),
+      vout: 0,
+    };
+
+    let satpoint = SatPoint {
+      outpoint,
+      offset: 7,
+    };
+
+    let output = serde_json::to_string(&OutputJson {
+      value: 10_000,
+      script_pubkey: String::new(),
+      address: None,
+      transaction: txid(1).to_string(),
+      sat_ranges: None,
+      inscriptions: vec![inscription_id(1)],
+      runes: BTreeMap::new(),
+    })
+    .unwrap();
+
+    let inscription = serde_json::to_string(&InscriptionJson {
+      address: None,
+      children: Vec::new(),
+      cid: None,
+      content_length: None,
+      content_type: None,
+      content_verdicts: Vec::new(),
+      genesis_fee: 0,
+      genesis_height: 0,
+      inscription_id: inscription_id(1),
+      inscription_number: 0,
+      next: None,
+      output_value: None,
+      parent: None,
+      previous: None,
+      recursive_endpoints: Vec::new(),
+      rune: None,
+      sat: None,
+      satpoint,
+      timestamp: 0,
+    })
+    .unwrap();
+
+    let server = thread::spawn(move || {
+      let mut paths = Vec::new();
+
+      for (response, stream) in [output, inscription].into_iter().zip(listener.incoming()) {
+        let mut stream = stream.unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        paths.push(request_line.split_whitespace().nth(1).unwrap().to_string());
+
+        loop {
+          let mut line = String::new();
+          reader.read_line(&mut line).unwrap();
+          if line == "\r\n" {
+            break;
+          }
+        }
+
+        write!(
+          stream,
+          "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+          response.len()
+        )
+        .unwrap();
+      }
+
+      paths
+    });
+
+    let index = RemoteIndex::new(&url).unwrap();
+
+    assert_eq!(
+      index
+        .get_inscriptions(&[(outpoint, Amount::from_sat(10_000))].into())
+        .unwrap(),
+      [(satpoint, inscription_id(1))].into(),
+    );
+
+    assert_eq!(
+      server.join().unwrap(),
+      [
+        format!("/ord/output/{outpoint}"),
+        format!("/ord/inscription/{}", inscription_id(1)),
+      ],
+    );
+  }
+}
@@ -1,4 +1,4 @@
-use {super::*, updater::BlockData};
+use {super::*, event::Event, outbox::Outbox, subscription::Notifier, updater::BlockData};
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum ReorgError {
@@ -19,10 +19,6 @@ impl fmt::Display for ReorgError {
 
 impl std::error::Error for ReorgError {}
 
-const MAX_SAVEPOINTS: u32 = 2;
-const SAVEPOINT_INTERVAL: u32 = 10;
-const CHAIN_TIP_DISTANCE: u32 = 21;
-
 pub(crate) struct Reorg {}
 
 impl Reorg {
@@ -33,7 +29,7 @@ impl Reorg {
       Some(index_prev_blockhash) if index_prev_blockhash == bitcoind_prev_blockhash => Ok(()),
       Some(index_prev_blockhash) if index_prev_blockhash != bitcoind_prev_blockhash => {
         let max_recoverable_reorg_depth =
-          (MAX_SAVEPOINTS - 1) * SAVEPOINT_INTERVAL + height % SAVEPOINT_INTERVAL;
+          (index.max_savepoints - 1) * index.savepoint_interval + height % index.savepoint_interval;
 
         for depth in 1..max_recoverable_reorg_depth {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
@@ -53,6 +49,9 @@ impl Reorg {
     }
   }
 
+  /// Restore the oldest savepoint, and tell event consumers that the blocks
+  /// above it were rolled back with a `BlocksRolledBack` event, which is
+  /// queued in the outbox in the same write transaction as the restore.
   pub(crate) fn handle_reorg(index: &Index, height: u32, depth: u32) -> Result {
     log::info!("rolling back database after reorg of depth {depth} at height {height}");
 
@@ -60,6 +59,10 @@ impl Reorg {
       panic!("set index durability to `Durability::Immediate` to test reorg handling");
     }
 
+    let orphaned_block_hash = index
+      .block_hash(None)?
+      .ok_or_else(|| anyhow!("cannot roll back empty index"))?;
+
     let mut wtx = index.begin_write()?;
 
     let oldest_savepoint =
@@ -67,13 +70,46 @@ impl Reorg {
 
     wtx.restore_savepoint(&oldest_savepoint)?;
 
+    let block_height = wtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .last()?
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
+
+    let events = [Event::BlocksRolledBack {
+      block_height,
+      orphaned_block_hash,
+    }];
+
+    Outbox::push(
+      index,
+      &wtx,
+      block_height,
+      u32::try_from(
+        SystemTime::now()
+          .duration_since(SystemTime::UNIX_EPOCH)
+          .map(|duration| duration.as_secs())
+          .unwrap_or(0),
+      )
+      .unwrap_or(u32::MAX),
+      &events,
+      &[],
+    )?;
+
+    let subscriptions = subscription::registered(&wtx)?;
+
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
-    log::info!(
-      "successfully rolled back database to height {}",
-      index.block_count()?
-    );
+    if !subscriptions.is_empty() {
+      if let Err(err) = Notifier::new().notify(&subscriptions, block_height, &events) {
+        log::error!("Failed to notify subscriptions of rollback to block {block_height}: {err}");
+      }
+    }
+
+    index.notify_event_subscribers(&events);
+
+    log::info!("successfully rolled back database to height {block_height}");
 
     Ok(())
   }
@@ -83,7 +119,11 @@ impl Reorg {
       return Ok(());
     }
 
-    if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
+    // savepoints are only useful if they are close enough to the chain tip to
+    // still be among the most recent when a reorg happens
+    let chain_tip_distance = index.max_savepoints * index.savepoint_interval + 1;
+
+    if (height < index.savepoint_interval || height % index.savepoint_interval == 0)
       && u32::try_from(
         index
           .options
@@ -93,13 +133,13 @@ impl Reorg {
       )
       .unwrap()
       .saturating_sub(height)
-        <= CHAIN_TIP_DISTANCE
+        <= chain_tip_distance
     {
       let wtx = index.begin_write()?;
 
       let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
 
-      if savepoints.len() >= usize::try_from(MAX_SAVEPOINTS).unwrap() {
+      if savepoints.len() >= usize::try_from(index.max_savepoints).unwrap() {
         wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
       }
 
@@ -0,0 +1,244 @@
+use super::{updater::BlockData, *};
+
+/// Rune balances of the outputs spent by `block` which pay addresses, read
+/// before the block's runes are indexed. Outputs created earlier in the same
+/// block are left out, as are their balances in `record`, since an output
+/// created and spent in one block doesn't change any address's balance.
+pub(crate) fn spent(
+  wtx: &WriteTransaction,
+  block: &BlockData,
+) -> Result<Vec<(ScriptBuf, RuneId, u128)>> {
+  let outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+  let outpoint_to_script_pubkey = wtx.open_table(OUTPOINT_TO_SCRIPT_PUBKEY)?;
+
+  let mut spent = Vec::new();
+
+  for (tx, _txid) in &block.txdata {
+    for input in &tx.input {
+      let outpoint = input.previous_output.store();
+
+      let Some(balances) = outpoint_to_rune_balances.get(&outpoint)? else {
+        continue;
+      };
+
+      let Some(script_pubkey) = outpoint_to_script_pubkey.get(&outpoint)? else {
+        continue;
+      };
+
+      let script_pubkey = ScriptBuf::from_bytes(script_pubkey.value().to_vec());
+
+      for (id, amount) in load_rune_balances(balances.value())? {
+        spent.push((script_pubkey.clone(), id, amount));
+      }
+    }
+  }
+
+  Ok(spent)
+}
+
+/// Append the balance of every address and rune whose balance changed in
+/// `block` at `height` to the address's rune balance history, given `spent`,
+/// the balances returned by `spent` before the block was indexed. History
+/// entries are never modified, so the balance as of any block is the latest
+/// entry at or below its height.
+pub(crate) fn record(
+  wtx: &WriteTransaction,
+  height: u32,
+  block: &BlockData,
+  spent: Vec<(ScriptBuf, RuneId, u128)>,
+) -> Result {
+  let outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+  let mut script_pubkey_to_rune_balance_history =
+    wtx.open_table(SCRIPT_PUBKEY_TO_RUNE_BALANCE_HISTORY)?;
+
+  let mut changes = BTreeMap::<(ScriptBuf, RuneId), (u128, u128)>::new();
+
+  for (script_pubkey, id, amount) in spent {
+    changes.entry((script_pubkey, id)).or_default().1 += amount;
+  }
+
+  for (tx, txid) in &block.txdata {
+    for (vout, output) in tx.output.iter().enumerate() {
+      let outpoint = OutPoint {
+        txid: *txid,
+        vout: vout.try_into().unwrap(),
+      };
+
+      let Some(balances) = outpoint_to_rune_balances.get(&outpoint.store())? else {
+        continue;
+      };
+
+      for (id, amount) in load_rune_balances(balances.value())? {
+        changes
+          .entry((output.script_pubkey.clone(), id))
+          .or_default()
+          .0 += amount;
+      }
+    }
+  }
+
+  for ((script_pubkey, id), (received, sent)) in changes {
+    let previous = script_pubkey_to_rune_balance_history
+      .range(
+        (script_pubkey.as_bytes(), id.store(), 0)..(script_pubkey.as_bytes(), id.store(), height),
+      )?
+      .next_back()
+      .transpose()?
+      .map(|(_key, balance)| balance.value())
+      .unwrap_or_default();
+
+    let balance = if received >= sent {
+      previous + (received - sent)
+    } else {
+      previous - (sent - received)
+    };
+
+    if balance != previous {
+      script_pubkey_to_rune_balance_history
+        .insert((script_pubkey.as_bytes(), id.store(), height), balance)?;
+    }
+  }
+
+  Ok(())
+}
+
+impl Index {
+  /// Balance of rune `id` held by `script_pubkey` at the end of the block at
+  /// `height`. Requires `--index-addresses` and `--index-runes`.
+  pub(crate) fn get_address_rune_balance_at(
+    &self,
+    script_pubkey: &Script,
+    id: RuneId,
+    height: u32,
+  ) -> Result<u128> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SCRIPT_PUBKEY_TO_RUNE_BALANCE_HISTORY)?
+        .range(
+          (script_pubkey.as_bytes(), id.store(), 0)
+            ..=(script_pubkey.as_bytes(), id.store(), height),
+        )?
+        .next_back()
+        .transpose()?
+        .map(|(_key, balance)| balance.value())
+        .unwrap_or_default(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn balances_are_recorded_when_they_change() {
+    let context = Context::builder()
+      .arg("--index-addresses")
+      .arg("--index-runes")
+      .build();
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(99246114928149462)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![
+            Edict {
+              id: id.into(),
+              amount: 100,
+              output: 0,
+            },
+            Edict {
+              id: id.into(),
+              amount: 0,
+              output: 1,
+            },
+          ],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: id.into(),
+            amount: 100,
+            output: 1,
+          }],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let script_pubkey = ScriptBuf::new();
+
+    let balance = |height| {
+      context
+        .index
+        .get_address_rune_balance_at(&script_pubkey, id, height)
+        .unwrap()
+    };
+
+    assert_eq!(balance(1), 0);
+    assert_eq!(balance(2), u128::MAX);
+    assert_eq!(balance(3), u128::MAX);
+    assert_eq!(balance(4), u128::MAX - 100);
+
+    assert_eq!(
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(SCRIPT_PUBKEY_TO_RUNE_BALANCE_HISTORY)
+        .unwrap()
+        .iter()
+        .unwrap()
+        .map(|result| result.unwrap().0.value().2)
+        .collect::<Vec<u32>>(),
+      [2, 4],
+    );
+  }
+}
@@ -0,0 +1,145 @@
+use {super::*, std::ops::RangeInclusive};
+
+/// Orders in which `/inscriptions` can be listed. Listing all inscriptions by
+/// height or number uses the primary tables, while listing by fee or size, or
+/// filtering by content type, uses `INSCRIPTION_SORT_KEYS`, which is only
+/// populated with `--index-inscription-sorts`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum InscriptionSort {
+  Fee,
+  #[default]
+  Height,
+  Number,
+  Size,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SortOrder {
+  Asc,
+  #[default]
+  Desc,
+}
+
+impl InscriptionSort {
+  pub(crate) fn needs_sort_index(self, content_type: Option<&str>) -> bool {
+    content_type.is_some() || matches!(self, Self::Fee | Self::Size)
+  }
+
+  /// Range of `INSCRIPTION_SORT_KEYS` holding inscriptions with
+  /// `content_type`, or all inscriptions if `content_type` is empty.
+  pub(super) fn range(self, content_type: &str) -> RangeInclusive<(u8, &str, u64, u32)> {
+    (self as u8, content_type, 0, 0)..=(self as u8, content_type, u64::MAX, u32::MAX)
+  }
+}
+
+impl Display for InscriptionSort {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Fee => "fee",
+        Self::Height => "height",
+        Self::Number => "number",
+        Self::Size => "size",
+      }
+    )
+  }
+}
+
+impl Display for SortOrder {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Asc => "asc",
+        Self::Desc => "desc",
+      }
+    )
+  }
+}
+
+/// Content type without parameters, in lowercase, so that
+/// `text/plain;charset=utf-8` and `text/plain` are listed together.
+pub(crate) fn content_type_essence(content_type: &str) -> String {
+  content_type
+    .split(';')
+    .next()
+    .unwrap_or_default()
+    .trim()
+    .to_ascii_lowercase()
+}
+
+/// Keys under which a new inscription is stored in `INSCRIPTION_SORT_KEYS`,
+/// as `(sort, content type, key)` triples. Sorting all inscriptions by height
+/// or number doesn't need keys, so only fee and size are stored with an empty
+/// content type.
+pub(super) fn sort_keys(
+  content_type: Option<&str>,
+  fee: u64,
+  height: u32,
+  inscription_number: i32,
+  size: u64,
+) -> Vec<(InscriptionSort, String, u64)> {
+  let mut keys = vec![
+    (InscriptionSort::Fee, String::new(), fee),
+    (InscriptionSort::Size, String::new(), size),
+  ];
+
+  if let Some(content_type) = content_type.map(content_type_essence) {
+    if !content_type.is_empty() {
+      keys.extend([
+        (InscriptionSort::Fee, content_type.clone(), fee),
+        (InscriptionSort::Height, content_type.clone(), height.into()),
+        (
+          InscriptionSort::Number,
+          content_type.clone(),
+          (i64::from(inscription_number) - i64::from(i32::MIN))
+            .try_into()
+            .unwrap(),
+        ),
+        (InscriptionSort::Size, content_type, size),
+      ]);
+    }
+  }
+
+  keys
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn essence() {
+    assert_eq!(content_type_essence("text/plain"), "text/plain");
+    assert_eq!(
+      content_type_essence("Text/HTML; charset=utf-8"),
+      "text/html"
+    );
+  }
+
+  #[test]
+  fn number_keys_are_ordered() {
+    let key = |number| sort_keys(Some("text/plain"), 0, 0, number, 0)[4].2;
+
+    assert_eq!(key(i32::MIN), 0);
+    assert!(key(-1) < key(0));
+    assert!(key(0) < key(1));
+    assert_eq!(key(i32::MAX), u64::from(u32::MAX));
+  }
+
+  #[test]
+  fn inscriptions_without_content_type_are_only_sorted_by_fee_and_size() {
+    assert_eq!(
+      sort_keys(None, 1, 2, 3, 4),
+      [
+        (InscriptionSort::Fee, String::new(), 1),
+        (InscriptionSort::Size, String::new(), 4),
+      ]
+    );
+  }
+}
@@ -0,0 +1,248 @@
+use {
+  super::{event::Event, filter::Filter, *},
+  reqwest::Url,
+  std::sync::mpsc,
+};
+
+/// Persistent request to be notified of indexed events matching `filter`,
+/// e.g. `type == "rune_minted" && rune_id == "840000/1"`, registered with
+/// `POST /admin/subscriptions` and stored in the index. While a block is
+/// indexed, each subscription's matching events are queued for delivery to
+/// `url` as a single JSON `POST`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Subscription {
+  pub filter: String,
+  pub url: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SubscriptionJson {
+  pub id: u64,
+  #[serde(flatten)]
+  pub subscription: Subscription,
+}
+
+/// Subscription with its filter and URL parsed, as loaded by the updater.
+pub(crate) struct Registered {
+  filter: Filter,
+  id: u64,
+  url: Url,
+}
+
+impl Subscription {
+  pub(crate) fn parse(&self) -> Result<(Filter, Url)> {
+    Ok((
+      self
+        .filter
+        .parse()
+        .with_context(|| format!("invalid subscription filter `{}`", self.filter))?,
+      self
+        .url
+        .parse()
+        .with_context(|| format!("invalid subscription URL `{}`", self.url))?,
+    ))
+  }
+}
+
+/// Load the subscriptions that events are checked against while indexing.
+pub(crate) fn registered(wtx: &WriteTransaction) -> Result<Vec<Registered>> {
+  wtx
+    .open_table(SUBSCRIPTION_ID_TO_SUBSCRIPTION)?
+    .iter()?
+    .map(|result| {
+      let (id, subscription) = result?;
+      let (filter, url) = serde_json::from_slice::<Subscription>(subscription.value())?.parse()?;
+
+      Ok(Registered {
+        filter,
+        id: id.value(),
+        url,
+      })
+    })
+    .collect()
+}
+
+struct Notification {
+  body: serde_json::Value,
+  source: String,
+  url: Url,
+}
+
+/// Delivers notifications on a background thread, so that slow or
+/// unreachable subscribers don't stall indexing. Delivery is best-effort:
+/// notifications still queued when ord exits are lost, and failed deliveries
+/// are logged and not retried.
+pub(crate) struct Notifier {
+  sender: mpsc::Sender<Notification>,
+}
+
+impl Notifier {
+  pub(crate) fn new() -> Self {
+    let (sender, receiver) = mpsc::channel::<Notification>();
+
+    thread::spawn(move || {
+      let client = reqwest::blocking::Client::new();
+
+      for notification in receiver {
+        if let Err(err) = client
+          .post(notification.url.clone())
+          .json(&notification.body)
+          .send()
+          .and_then(|response| response.error_for_status())
+        {
+          log::warn!(
+            "failed to notify {} at {}: {err}",
+            notification.source,
+            notification.url,
+          );
+        }
+      }
+    });
+
+    Self { sender }
+  }
+
+  /// Queue a notification for every subscription in `registered` that
+  /// matches at least one of `events`, all of which belong to the block at
+  /// `height`. `BlocksRolledBack` events match every subscription.
+  pub(crate) fn notify(&self, registered: &[Registered], height: u32, events: &[Event]) -> Result {
+    let events = events
+      .iter()
+      .map(|event| {
+        Ok((
+          event.is_rollback(),
+          serde_json::from_slice(&serde_json::to_vec(event)?)?,
+        ))
+      })
+      .collect::<Result<Vec<(bool, serde_json::Value)>>>()?;
+
+    for subscription in registered {
+      let matching = events
+        .iter()
+        .filter(|(rollback, event)| *rollback || subscription.filter.matches(event))
+        .map(|(_, event)| event)
+        .collect::<Vec<&serde_json::Value>>();
+
+      if matching.is_empty() {
+        continue;
+      }
+
+      self.send(
+        format!("subscription {}", subscription.id),
+        subscription.url.clone(),
+        serde_json::json!({
+          "block_height": height,
+          "events": matching,
+          "subscription": subscription.id,
+        }),
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Queue `body` for delivery to `url`, naming the notification's `source`
+  /// if delivery fails.
+  pub(crate) fn send(&self, source: String, url: Url, body: serde_json::Value) -> Result {
+    self
+      .sender
+      .send(Notification { body, source, url })
+      .map_err(|_| anyhow!("notifier stopped"))
+  }
+}
+
+impl Index {
+  /// Register `subscription`, returning its ID.
+  pub(crate) fn add_subscription(&self, subscription: &Subscription) -> Result<u64> {
+    subscription.parse()?;
+
+    let wtx = self.begin_write()?;
+
+    let id = {
+      let mut subscriptions = wtx.open_table(SUBSCRIPTION_ID_TO_SUBSCRIPTION)?;
+
+      let id = subscriptions
+        .iter()?
+        .next_back()
+        .transpose()?
+        .map(|(id, _)| id.value() + 1)
+        .unwrap_or_default();
+
+      subscriptions.insert(id, serde_json::to_vec(subscription)?.as_slice())?;
+
+      id
+    };
+
+    wtx.commit()?;
+
+    Ok(id)
+  }
+
+  /// Remove subscription `id`, returning false if it doesn't exist.
+  pub(crate) fn remove_subscription(&self, id: u64) -> Result<bool> {
+    let wtx = self.begin_write()?;
+
+    let removed = wtx
+      .open_table(SUBSCRIPTION_ID_TO_SUBSCRIPTION)?
+      .remove(id)?
+      .is_some();
+
+    wtx.commit()?;
+
+    Ok(removed)
+  }
+
+  pub(crate) fn subscriptions(&self) -> Result<Vec<SubscriptionJson>> {
+    self
+      .database
+      .begin_read()?
+      .open_table(SUBSCRIPTION_ID_TO_SUBSCRIPTION)?
+      .iter()?
+      .map(|result| {
+        let (id, subscription) = result?;
+
+        Ok(SubscriptionJson {
+          id: id.value(),
+          subscription: serde_json::from_slice(subscription.value())?,
+        })
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn invalid_subscriptions_are_rejected() {
+    assert!(Subscription {
+      filter: "type == \"rune_minted\"".into(),
+      url: "http://localhost:8080/hook".into(),
+    }
+    .parse()
+    .is_ok());
+
+    assert_eq!(
+      Subscription {
+        filter: "type ==".into(),
+        url: "http://localhost:8080/hook".into(),
+      }
+      .parse()
+      .unwrap_err()
+      .to_string(),
+      "invalid subscription filter `type ==`",
+    );
+
+    assert_eq!(
+      Subscription {
+        filter: "type == \"rune_minted\"".into(),
+        url: "localhost".into(),
+      }
+      .parse()
+      .unwrap_err()
+      .to_string(),
+      "invalid subscription URL `localhost`",
+    );
+  }
+}
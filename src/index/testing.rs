@@ -120,19 +120,9 @@ impl Context {
 
     assert_eq!(balances, self.index.get_rune_balances().unwrap());
 
-    let mut outstanding: HashMap<RuneId, u128> = HashMap::new();
-
-    for (_, balances) in balances {
-      for (id, balance) in balances {
-        *outstanding.entry(*id).or_default() += *balance;
-      }
-    }
-
-    for (id, entry) in runes {
-      assert_eq!(
-        outstanding.get(id).copied().unwrap_or_default(),
-        entry.supply - entry.burned
-      );
-    }
+    assert_eq!(
+      Index::check_rune_balances(&runes.iter().copied().collect(), balances),
+      Vec::<String>::new(),
+    );
   }
 }
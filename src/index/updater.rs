@@ -1,6 +1,16 @@
 use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
-  super::{fetcher::Fetcher, *},
+  super::{
+    activity,
+    archive::Archive,
+    event::Event,
+    fetcher::Fetcher,
+    outbox::Outbox,
+    rest::Rest,
+    rune_history,
+    subscription::{Notifier, Registered},
+    watch, *,
+  },
   futures::future::try_join_all,
   std::sync::mpsc,
   tokio::sync::mpsc::{error::TryRecvError, Receiver, Sender},
@@ -31,9 +41,15 @@ impl From<Block> for BlockData {
 }
 
 pub(crate) struct Updater<'index> {
-  range_cache: HashMap<OutPointValue, Vec<u8>>,
+  range_cache: HashMap<OutPointValue, (u64, Vec<u8>)>,
+  range_cache_clock: u64,
+  range_cache_order: BTreeMap<u64, OutPointValue>,
+  range_cache_size: usize,
   height: u32,
   index: &'index Index,
+  notifications: Vec<(u32, Vec<Registered>, Vec<Event>)>,
+  notifier: Option<Notifier>,
+  outbox: Outbox,
   sat_ranges_since_flush: u64,
   outputs_cached: u64,
   outputs_inserted_since_flush: u64,
@@ -42,10 +58,18 @@ pub(crate) struct Updater<'index> {
 
 impl<'index> Updater<'_> {
   pub(crate) fn new(index: &'index Index) -> Result<Updater<'index>> {
+    let height = index.block_count()?;
+
     Ok(Updater {
       range_cache: HashMap::new(),
-      height: index.block_count()?,
+      range_cache_clock: 0,
+      range_cache_order: BTreeMap::new(),
+      range_cache_size: 0,
+      height,
       index,
+      notifications: Vec::new(),
+      notifier: None,
+      outbox: Outbox::new(index)?,
       sat_ranges_since_flush: 0,
       outputs_cached: 0,
       outputs_inserted_since_flush: 0,
@@ -54,6 +78,9 @@ impl<'index> Updater<'_> {
   }
 
   pub(crate) fn update_index(&mut self) -> Result {
+    // retry deliveries which failed during earlier updates
+    self.outbox.deliver(self.index)?;
+
     let mut wtx = self.index.begin_write()?;
     let starting_height = u32::try_from(self.index.client.get_block_count()?).unwrap() + 1;
 
@@ -67,7 +94,13 @@ impl<'index> Updater<'_> {
           .unwrap_or(0),
       )?;
 
+    let started = Instant::now();
+    let first_height = self.height;
+
+    self.report_progress(IndexStage::Indexing, starting_height, started, first_height)?;
+
     let mut progress_bar = if cfg!(test)
+      || self.index.options.progress == Some(ProgressFormat::Json)
       || log_enabled!(log::Level::Info)
       || starting_height <= self.height
       || integration_test()
@@ -112,7 +145,29 @@ impl<'index> Updater<'_> {
 
       uncommitted += 1;
 
-      if uncommitted == 5000 {
+      self.report_progress(IndexStage::Indexing, starting_height, started, first_height)?;
+
+      let cache_size = self.cache_size(&value_cache);
+
+      if let Some(cache_budget) = self.index.cache_budget {
+        if cache_size > cache_budget {
+          self.spill(&mut wtx, &mut value_cache, cache_budget)?;
+        }
+      }
+
+      if uncommitted >= self.index.commit_interval
+        || self
+          .index
+          .commit_cache_size
+          .map(|commit_cache_size| cache_size >= commit_cache_size)
+          .unwrap_or_default()
+      {
+        self.report_progress(
+          IndexStage::Committing,
+          starting_height,
+          started,
+          first_height,
+        )?;
         self.commit(wtx, value_cache)?;
         value_cache = HashMap::new();
         uncommitted = 0;
@@ -145,10 +200,20 @@ impl<'index> Updater<'_> {
       }
     }
 
-    if uncommitted > 0 {
+    // commit even if no blocks are uncommitted when shutting down, to record
+    // the shutdown checkpoint
+    if uncommitted > 0 || SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      self.report_progress(
+        IndexStage::Committing,
+        starting_height,
+        started,
+        first_height,
+      )?;
       self.commit(wtx, value_cache)?;
     }
 
+    self.report_progress(IndexStage::Idle, starting_height, started, first_height)?;
+
     if let Some(progress_bar) = &mut progress_bar {
       progress_bar.finish_and_clear();
     }
@@ -167,7 +232,27 @@ impl<'index> Updater<'_> {
 
     let client = index.options.bitcoin_rpc_client(None)?;
 
-    let first_inscription_height = index.first_inscription_height;
+    let first_full_block_height = index.first_full_block_height();
+
+    let archive = index
+      .options
+      .block_archive_url
+      .as_deref()
+      .map(|url| {
+        Ok::<_, Error>((
+          Archive::new(url)?,
+          tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?,
+        ))
+      })
+      .transpose()?;
+
+    let rest = index
+      .options
+      .rpc_rest
+      .then(|| Rest::new(&index.options.rpc_url(None)))
+      .transpose()?;
 
     thread::spawn(move || loop {
       if let Some(height_limit) = height_limit {
@@ -176,7 +261,14 @@ impl<'index> Updater<'_> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
+      match Self::get_block_with_retries(
+        &client,
+        rest.as_ref(),
+        archive.as_ref(),
+        height,
+        index_sats,
+        first_full_block_height,
+      ) {
         Ok(Some(block)) => {
           if let Err(err) = tx.send(block.into()) {
             log::info!("Block receiver disconnected: {err}");
@@ -197,9 +289,11 @@ impl<'index> Updater<'_> {
 
   fn get_block_with_retries(
     client: &Client,
+    rest: Option<&Rest>,
+    archive: Option<&(Archive, Runtime)>,
     height: u32,
     index_sats: bool,
-    first_inscription_height: u32,
+    first_full_block_height: u32,
   ) -> Result<Option<Block>> {
     let mut errors = 0;
     loop {
@@ -209,8 +303,22 @@ impl<'index> Updater<'_> {
         .and_then(|option| {
           option
             .map(|hash| {
-              if index_sats || height >= first_inscription_height {
-                Ok(client.get_block(&hash)?)
+              if index_sats || height >= first_full_block_height {
+                if let Some(rest) = rest.filter(|rest| rest.available()) {
+                  match rest.get_block(hash) {
+                    Ok(block) => return Ok(block),
+                    Err(err) => log::debug!("fetching block {hash} over JSON-RPC: {err}"),
+                  }
+                }
+
+                match (client.get_block(&hash), archive) {
+                  (Ok(block), _) => Ok(block),
+                  (Err(err), Some((archive, runtime))) => {
+                    log::debug!("fetching block {hash} from archive: {err}");
+                    runtime.block_on(archive.get_block(hash))
+                  }
+                  (Err(err), None) => Err(err.into()),
+                }
               } else {
                 Ok(Block {
                   header: client.get_block_header(&hash)?,
@@ -340,8 +448,33 @@ impl<'index> Updater<'_> {
 
     let mut outpoint_to_value = wtx.open_table(OUTPOINT_TO_VALUE)?;
 
+    let subscriptions = subscription::registered(wtx)?;
+
+    let watches = watch::registered(wtx)?;
+
+    if !watches.is_empty() {
+      watch::check(
+        self.index,
+        wtx,
+        self.notifier.get_or_insert_with(Notifier::new),
+        &watches,
+        self.height,
+        &block,
+      )?;
+    }
+
+    let mut events = Vec::new();
+    let collect_events = Outbox::wants_events(self.index)
+      || !subscriptions.is_empty()
+      || self.index.has_event_subscribers()
+      || self.index.metrics.enabled()
+      || index.index_addresses
+      || index.index_events;
+
+    let mut contents = Vec::new();
+
     let index_inscriptions =
-      self.height >= index.first_inscription_height && !index.options.no_index_inscriptions;
+      self.height >= index.params.first_inscription_height && !index.options.no_index_inscriptions;
 
     if index_inscriptions {
       // Send all missing input outpoints to be fetched right away
@@ -377,20 +510,31 @@ impl<'index> Updater<'_> {
       }
     }
 
+    let mut child_sequence_number_to_owner = wtx.open_table(CHILD_SEQUENCE_NUMBER_TO_OWNER)?;
+    let mut collection_holder_to_child_count = wtx.open_table(COLLECTION_HOLDER_TO_CHILD_COUNT)?;
     let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+    let mut height_to_inscription_moves = wtx.open_table(HEIGHT_TO_INSCRIPTION_MOVES)?;
     let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
     let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
     let mut inscription_id_to_sequence_number =
       wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
     let mut inscription_number_to_sequence_number =
       wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let mut inscription_sort_keys = wtx.open_table(INSCRIPTION_SORT_KEYS)?;
     let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+    let mut satpoint_to_owner = wtx.open_table(SATPOINT_TO_OWNER)?;
     let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+    let mut sequence_number_to_collection = wtx.open_table(SEQUENCE_NUMBER_TO_COLLECTION)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let mut sequence_number_to_recursive_endpoints =
+      wtx.open_table(SEQUENCE_NUMBER_TO_RECURSIVE_ENDPOINTS)?;
     let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+    let mut sequence_number_to_transfers = wtx.open_table(SEQUENCE_NUMBER_TO_TRANSFERS)?;
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+    let mut teleburn_claim_to_inscription_id =
+      wtx.open_multimap_table(TELEBURN_CLAIM_TO_INSCRIPTION_ID)?;
     let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
 
     let mut lost_sats = statistic_to_count
@@ -424,24 +568,39 @@ impl<'index> Updater<'_> {
 
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
-      chain: self.index.options.chain(),
+      child_sequence_number_to_owner: &mut child_sequence_number_to_owner,
+      collection_holder_to_child_count: &mut collection_holder_to_child_count,
+      contents: (Outbox::wants_contents(self.index)
+        || !self.index.options.content_validator.is_empty())
+      .then_some(&mut contents),
       cursed_inscription_count,
+      events: collect_events.then_some(&mut events),
+      fees: InscriptionFeesEntry::default(),
       flotsam: Vec::new(),
       height: self.height,
+      height_to_inscription_moves: &mut height_to_inscription_moves,
       home_inscription_count,
       home_inscriptions: &mut home_inscriptions,
       id_to_sequence_number: &mut inscription_id_to_sequence_number,
+      index_inscription_sorts: self.index.index_inscription_sorts,
       index_transactions: self.index.index_transactions,
       inscription_number_to_sequence_number: &mut inscription_number_to_sequence_number,
+      inscription_sort_keys: &mut inscription_sort_keys,
       lost_sats,
       next_sequence_number,
       outpoint_to_value: &mut outpoint_to_value,
+      params: self.index.params,
       reward: Height(self.height).subsidy(),
       sat_to_sequence_number: &mut sat_to_sequence_number,
+      satpoint_to_owner: &mut satpoint_to_owner,
       satpoint_to_sequence_number: &mut satpoint_to_sequence_number,
       sequence_number_to_children: &mut sequence_number_to_children,
+      sequence_number_to_collection: &mut sequence_number_to_collection,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
+      sequence_number_to_recursive_endpoints: &mut sequence_number_to_recursive_endpoints,
       sequence_number_to_satpoint: &mut sequence_number_to_satpoint,
+      sequence_number_to_transfers: &mut sequence_number_to_transfers,
+      teleburn_claim_to_inscription_id: &mut teleburn_claim_to_inscription_id,
       timestamp: block.header.time,
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
@@ -471,7 +630,7 @@ impl<'index> Updater<'_> {
         for input in &tx.input {
           let key = input.previous_output.store();
 
-          let sat_ranges = match self.range_cache.remove(&key) {
+          let sat_ranges = match self.take_cached_sat_ranges(&key) {
             Some(sat_ranges) => {
               self.outputs_cached += 1;
               sat_ranges
@@ -549,6 +708,12 @@ impl<'index> Updater<'_> {
     if index_inscriptions {
       height_to_last_sequence_number
         .insert(&self.height, inscription_updater.next_sequence_number)?;
+
+      if inscription_updater.fees != InscriptionFeesEntry::default() {
+        wtx
+          .open_table(HEIGHT_TO_INSCRIPTION_FEES)?
+          .insert(&self.height, &inscription_updater.fees.store())?;
+      }
     }
 
     statistic_to_count.insert(
@@ -575,54 +740,164 @@ impl<'index> Updater<'_> {
       &inscription_updater.unbound_inscriptions,
     )?;
 
-    if index.index_runes && self.height >= self.index.options.first_rune_height() {
-      let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-      let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-      let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
-      let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
-      let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
-
-      let runes = statistic_to_count
-        .get(&Statistic::Runes.into())?
-        .map(|x| x.value())
-        .unwrap_or(0);
-
-      let mut rune_updater = RuneUpdater {
-        height: self.height,
-        id_to_entry: &mut rune_id_to_rune_entry,
-        inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
-        minimum: Rune::minimum_at_height(self.index.options.chain(), Height(self.height)),
-        outpoint_to_balances: &mut outpoint_to_rune_balances,
-        rune_to_id: &mut rune_to_rune_id,
-        runes,
-        sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
-        statistic_to_count: &mut statistic_to_count,
-        timestamp: block.header.time,
-        transaction_id_to_rune: &mut transaction_id_to_rune,
-        updates: HashMap::new(),
-      };
+    let index_rune_history = index.index_addresses
+      && index.index_runes
+      && self.height >= self.index.params.first_rune_height;
+
+    let rune_balances_spent = if index_rune_history {
+      rune_history::spent(wtx, &block)?
+    } else {
+      Vec::new()
+    };
+
+    if index.index_runes && self.height >= self.index.params.first_rune_height {
+      index_runes(
+        self.index,
+        wtx,
+        self.height,
+        &block,
+        collect_events.then_some(&mut events),
+        &inscription_id_to_sequence_number,
+        &mut statistic_to_count,
+      )?;
+    }
+
+    if index.index_addresses {
+      let mut height_to_address_change = wtx.open_multimap_table(HEIGHT_TO_ADDRESS_CHANGE)?;
+      let mut outpoint_to_script_pubkey = wtx.open_table(OUTPOINT_TO_SCRIPT_PUBKEY)?;
+      let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+
+      // outputs created earlier in this block, which are left out of the
+      // block's address changes if they are also spent in it
+      let mut created = HashMap::new();
+      let mut changes = Vec::new();
+      let mut spent = HashMap::new();
+
+      for (tx, txid) in &block.txdata {
+        for input in &tx.input {
+          let outpoint = input.previous_output.store();
+
+          if let Some(script_pubkey) = outpoint_to_script_pubkey.remove(&outpoint)? {
+            script_pubkey_to_outpoint.remove(script_pubkey.value(), &outpoint)?;
+
+            let script_pubkey = ScriptBuf::from_bytes(script_pubkey.value().to_vec());
+
+            if created.remove(&outpoint).is_none() {
+              changes.push(AddressChange {
+                added: false,
+                outpoint: input.previous_output,
+                script_pubkey: script_pubkey.clone(),
+              });
+            }
 
-      for (i, (tx, txid)) in block.txdata.iter().enumerate() {
-        rune_updater.index_runes(i, tx, *txid)?;
+            spent.insert(input.previous_output, script_pubkey);
+          }
+        }
+
+        for (vout, output) in tx.output.iter().enumerate() {
+          if output.script_pubkey.is_op_return() {
+            continue;
+          }
+
+          let outpoint = OutPoint {
+            txid: *txid,
+            vout: vout.try_into().unwrap(),
+          }
+          .store();
+
+          outpoint_to_script_pubkey.insert(&outpoint, output.script_pubkey.as_bytes())?;
+          script_pubkey_to_outpoint.insert(output.script_pubkey.as_bytes(), &outpoint)?;
+
+          created.insert(outpoint, &output.script_pubkey);
+        }
       }
 
-      for (rune_id, update) in rune_updater.updates {
-        let mut entry = RuneEntry::load(
-          rune_id_to_rune_entry
-            .get(&rune_id.store())?
+      for (outpoint, script_pubkey) in created {
+        changes.push(AddressChange {
+          added: true,
+          outpoint: OutPoint::load(outpoint),
+          script_pubkey: script_pubkey.clone(),
+        });
+      }
+
+      for change in changes {
+        height_to_address_change.insert(&self.height, change.store().as_slice())?;
+      }
+
+      activity::record(wtx, self.height, &block, &events, &spent)?;
+    }
+
+    if index_rune_history {
+      rune_history::record(wtx, self.height, &block, rune_balances_spent)?;
+    }
+
+    self.crash_at(CrashPoint::MidBlock);
+
+    height_to_block_header.insert(&self.height, &block.header.store())?;
+
+    let sequence_numbers = contents
+      .iter()
+      .map(|(inscription_id, _)| {
+        Ok(
+          inscription_id_to_sequence_number
+            .get(&inscription_id.store())?
             .unwrap()
             .value(),
-        );
+        )
+      })
+      .collect::<Result<Vec<u32>>>()?;
 
-        entry.burned += update.burned;
-        entry.mints += update.mints;
-        entry.supply += update.supply;
+    if !self.index.options.content_validator.is_empty() {
+      let mut sequence_number_to_content_verdicts =
+        wtx.open_table(SEQUENCE_NUMBER_TO_CONTENT_VERDICTS)?;
 
-        rune_id_to_rune_entry.insert(&rune_id.store(), entry.store())?;
+      let verdicts = validation::validate_contents(
+        &self.index.options.content_validator,
+        self.index.options.max_content_size.unwrap_or(400_000),
+        &contents,
+      );
+
+      for (sequence_number, verdicts) in sequence_numbers.iter().zip(verdicts) {
+        sequence_number_to_content_verdicts.insert(
+          sequence_number,
+          validation::store_content_verdicts(&verdicts).as_slice(),
+        )?;
       }
     }
 
-    height_to_block_header.insert(&self.height, &block.header.store())?;
+    if index.index_events {
+      wtx
+        .open_table(HEIGHT_TO_EVENTS)?
+        .insert(&self.height, event::store(&events).as_slice())?;
+    }
+
+    if collect_events {
+      self.index.metrics.record_events(self.height, events.len());
+
+      events.push(Event::BlockIndexed {
+        block_hash: block.header.block_hash(),
+        block_height: self.height,
+      });
+    }
+
+    Outbox::push(
+      self.index,
+      wtx,
+      self.height,
+      block.header.time,
+      &events,
+      &contents
+        .iter()
+        .map(|(inscription_id, _)| *inscription_id)
+        .zip(sequence_numbers)
+        .collect::<Vec<(InscriptionId, u32)>>(),
+    )?;
+
+    if collect_events {
+      self
+        .notifications
+        .push((self.height, subscriptions, events));
+    }
 
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
@@ -694,13 +969,133 @@ impl<'index> Updater<'_> {
 
       *outputs_traversed += 1;
 
-      self.range_cache.insert(outpoint.store(), sats);
+      self.cache_sat_ranges(outpoint.store(), sats);
       self.outputs_inserted_since_flush += 1;
     }
 
     Ok(())
   }
 
+  #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  fn report_progress(
+    &self,
+    stage: IndexStage,
+    target: u32,
+    started: Instant,
+    first_height: u32,
+  ) -> Result {
+    let elapsed = started.elapsed().as_secs_f64();
+
+    let blocks_per_second = if elapsed > 0.0 {
+      f64::from(self.height - first_height) / elapsed
+    } else {
+      0.0
+    };
+
+    let target = target.max(self.height);
+
+    let progress = IndexProgress {
+      blocks_per_second,
+      eta: (blocks_per_second > 0.0)
+        .then(|| (f64::from(target - self.height) / blocks_per_second).round() as u64),
+      height: self.height,
+      stage,
+      target,
+    };
+
+    *self.index.progress.lock().unwrap() = progress;
+
+    if self.index.options.progress == Some(ProgressFormat::Json) {
+      eprintln!("{}", serde_json::to_string(&progress)?);
+    }
+
+    Ok(())
+  }
+
+  fn cache_size(&self, value_cache: &HashMap<OutPoint, u64>) -> usize {
+    self.range_cache_size
+      + self.range_cache_order.len() * (mem::size_of::<u64>() + mem::size_of::<OutPointValue>())
+      + value_cache.len() * (mem::size_of::<OutPoint>() + mem::size_of::<u64>())
+  }
+
+  /// Cache the sat ranges of a newly created output, marking it as the most
+  /// recently used entry.
+  fn cache_sat_ranges(&mut self, outpoint: OutPointValue, sat_ranges: Vec<u8>) {
+    self.range_cache_clock += 1;
+    self.range_cache_size += mem::size_of::<OutPointValue>() + sat_ranges.len();
+    self
+      .range_cache_order
+      .insert(self.range_cache_clock, outpoint);
+
+    if let Some((used, replaced)) = self
+      .range_cache
+      .insert(outpoint, (self.range_cache_clock, sat_ranges))
+    {
+      self.range_cache_order.remove(&used);
+      self.range_cache_size -= mem::size_of::<OutPointValue>() + replaced.len();
+    }
+  }
+
+  /// Remove and return the cached sat ranges of a spent output, if any.
+  fn take_cached_sat_ranges(&mut self, outpoint: &OutPointValue) -> Option<Vec<u8>> {
+    let (used, sat_ranges) = self.range_cache.remove(outpoint)?;
+    self.range_cache_order.remove(&used);
+    self.range_cache_size -= mem::size_of::<OutPointValue>() + sat_ranges.len();
+    Some(sat_ranges)
+  }
+
+  /// Move cached output values and sat ranges into the index tables of the
+  /// current write transaction, without committing, until the caches are
+  /// within half of `cache_budget`. Sat ranges are spilled least recently used
+  /// first, since recently created outputs are the most likely to be spent
+  /// soon. Spilled entries are read back from the tables when needed.
+  fn spill(
+    &mut self,
+    wtx: &mut WriteTransaction,
+    value_cache: &mut HashMap<OutPoint, u64>,
+    cache_budget: usize,
+  ) -> Result {
+    let before = self.cache_size(value_cache);
+
+    {
+      let mut outpoint_to_value = wtx.open_table(OUTPOINT_TO_VALUE)?;
+
+      for (outpoint, value) in value_cache.drain() {
+        outpoint_to_value.insert(&outpoint.store(), &value)?;
+      }
+    }
+
+    if self.index.index_sats {
+      let mut outpoint_to_sat_ranges = wtx.open_table(OUTPOINT_TO_SAT_RANGES)?;
+
+      while self.cache_size(value_cache) > cache_budget / 2 {
+        let Some((_, outpoint)) = self.range_cache_order.pop_first() else {
+          break;
+        };
+
+        let (_, sat_ranges) = self.range_cache.remove(&outpoint).unwrap();
+        self.range_cache_size -= mem::size_of::<OutPointValue>() + sat_ranges.len();
+        outpoint_to_sat_ranges.insert(&outpoint, sat_ranges.as_slice())?;
+      }
+    }
+
+    log::info!(
+      "Spilled {} bytes of cached outputs to index at block height {}",
+      before - self.cache_size(value_cache),
+      self.height,
+    );
+
+    Ok(())
+  }
+
+  /// Abort the process if `--crash-at` is `point`, simulating a crash.
+  fn crash_at(&self, point: CrashPoint) {
+    if self.index.options.crash_at == Some(point) {
+      eprintln!("aborting indexer at height {} at {point:?}", self.height);
+      process::abort();
+    }
+  }
+
   fn commit(&mut self, wtx: WriteTransaction, value_cache: HashMap<OutPoint, u64>) -> Result {
     log::info!(
       "Committing at block height {}, {} outputs traversed, {} in map, {} cached",
@@ -720,10 +1115,13 @@ impl<'index> Updater<'_> {
 
       let mut outpoint_to_sat_ranges = wtx.open_table(OUTPOINT_TO_SAT_RANGES)?;
 
-      for (outpoint, sat_range) in self.range_cache.drain() {
+      for (outpoint, (_, sat_range)) in self.range_cache.drain() {
         outpoint_to_sat_ranges.insert(&outpoint, sat_range.as_slice())?;
       }
 
+      self.range_cache_order.clear();
+      self.range_cache_size = 0;
+
       self.outputs_inserted_since_flush = 0;
     }
 
@@ -739,11 +1137,121 @@ impl<'index> Updater<'_> {
     self.outputs_traversed = 0;
     Index::increment_statistic(&wtx, Statistic::SatRanges, self.sat_ranges_since_flush)?;
     self.sat_ranges_since_flush = 0;
+    Index::set_statistic(
+      &mut wtx.open_table(STATISTIC_TO_COUNT)?,
+      Statistic::ShutdownCheckpoint,
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        self.height.into()
+      } else {
+        0
+      },
+    )?;
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+
+    self.crash_at(CrashPoint::PreCommit);
+
+    let started = Instant::now();
     wtx.commit()?;
 
+    self.crash_at(CrashPoint::PostCommit);
+
+    self.index.metrics.record_commit(
+      self.height,
+      started.elapsed(),
+      fs::metadata(&self.index.path)?.len(),
+    );
+
+    self.index.store_lease()?;
+
     Reorg::update_savepoints(self.index, self.height)?;
 
+    self.notify();
+
+    self.outbox.deliver(self.index)?;
+
     Ok(())
   }
+
+  /// Notify subscribers of the events of blocks which have been committed.
+  /// Notifications are queued and delivered in the background, so failures
+  /// never stall indexing.
+  fn notify(&mut self) {
+    for (height, subscriptions, events) in mem::take(&mut self.notifications) {
+      if !subscriptions.is_empty() {
+        if let Err(err) =
+          self
+            .notifier
+            .get_or_insert_with(Notifier::new)
+            .notify(&subscriptions, height, &events)
+        {
+          log::error!("Failed to notify subscriptions of block {height}: {err}");
+        }
+      }
+
+      self.index.notify_event_subscribers(&events);
+    }
+  }
+}
+
+/// Apply the runestones in `block` at `height` to the rune tables. Only reads
+/// inscription tables, so that `ord protocol reindex` can use it to rebuild
+/// rune state without touching the inscription index.
+pub(super) fn index_runes<'db, 'tx>(
+  index: &Index,
+  wtx: &'tx WriteTransaction<'db>,
+  height: u32,
+  block: &BlockData,
+  events: Option<&mut Vec<Event>>,
+  inscription_id_to_sequence_number: &Table<'db, 'tx, InscriptionIdValue, u32>,
+  statistic_to_count: &mut Table<'db, 'tx, u64, u64>,
+) -> Result {
+  let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+  let mut quarantined_entry_to_value = wtx.open_table(QUARANTINED_ENTRY_TO_VALUE)?;
+  let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+  let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
+  let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+  let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
+
+  let runes = statistic_to_count
+    .get(&Statistic::Runes.into())?
+    .map(|x| x.value())
+    .unwrap_or(0);
+
+  let mut rune_updater = RuneUpdater {
+    events,
+    height,
+    id_to_entry: &mut rune_id_to_rune_entry,
+    inscription_id_to_sequence_number,
+    minimum: Rune::minimum_at_height(index.options.chain(), Height(height)),
+    outpoint_to_balances: &mut outpoint_to_rune_balances,
+    quarantined_entry_to_value: &mut quarantined_entry_to_value,
+    rune_to_id: &mut rune_to_rune_id,
+    runes,
+    sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
+    statistic_to_count,
+    timestamp: block.header.time,
+    transaction_id_to_rune: &mut transaction_id_to_rune,
+    updates: HashMap::new(),
+  };
+
+  for (i, (tx, txid)) in block.txdata.iter().enumerate() {
+    rune_updater.index_runes(i, tx, *txid)?;
+  }
+
+  for (rune_id, update) in rune_updater.updates {
+    let mut entry = RuneEntry::load(
+      rune_id_to_rune_entry
+        .get(&rune_id.store())?
+        .unwrap()
+        .value(),
+    );
+
+    entry.burned += update.burned;
+    entry.mints += update.mints;
+    entry.supply += update.supply;
+
+    rune_id_to_rune_entry.insert(&rune_id.store(), entry.store())?;
+  }
+
+  Ok(())
 }
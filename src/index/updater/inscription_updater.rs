@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, crate::index::collection::Collections};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 enum Curse {
@@ -23,43 +23,65 @@ pub(super) struct Flotsam {
 #[derive(Debug, Clone)]
 enum Origin {
   New {
+    content_type: Option<String>,
     cursed: bool,
     fee: u64,
     hidden: bool,
+    metaprotocol: Option<String>,
     parent: Option<InscriptionId>,
     pointer: Option<u64>,
+    recursive_endpoints: u16,
     reinscription: bool,
+    size: u64,
     unbound: bool,
   },
   Old {
+    fee: u64,
     old_satpoint: SatPoint,
+    sender: Option<ScriptBuf>,
   },
 }
 
 pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) blessed_inscription_count: u64,
-  pub(super) chain: Chain,
+  pub(super) child_sequence_number_to_owner: &'a mut Table<'db, 'tx, u32, &'static [u8]>,
+  pub(super) collection_holder_to_child_count: &'a mut Table<'db, 'tx, (u32, &'static [u8]), u64>,
+  pub(super) contents: Option<&'a mut Vec<(InscriptionId, Inscription)>>,
   pub(super) cursed_inscription_count: u64,
+  pub(super) events: Option<&'a mut Vec<Event>>,
+  pub(super) fees: InscriptionFeesEntry,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) height: u32,
+  pub(super) height_to_inscription_moves:
+    &'a mut Table<'db, 'tx, (u32, u32), &'static SatPointValue>,
   pub(super) home_inscription_count: u64,
   pub(super) home_inscriptions: &'a mut Table<'db, 'tx, u32, InscriptionIdValue>,
   pub(super) id_to_sequence_number: &'a mut Table<'db, 'tx, InscriptionIdValue, u32>,
+  pub(super) index_inscription_sorts: bool,
   pub(super) index_transactions: bool,
   pub(super) inscription_number_to_sequence_number: &'a mut Table<'db, 'tx, i32, u32>,
+  pub(super) inscription_sort_keys: &'a mut Table<'db, 'tx, (u8, &'static str, u64, u32), ()>,
   pub(super) lost_sats: u64,
   pub(super) next_sequence_number: u32,
   pub(super) outpoint_to_value: &'a mut Table<'db, 'tx, &'static OutPointValue, u64>,
+  pub(super) params: NetworkParams,
   pub(super) reward: u64,
   pub(super) transaction_buffer: Vec<u8>,
   pub(super) transaction_id_to_transaction:
     &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'db, 'tx, u64, u32>,
+  pub(super) satpoint_to_owner: &'a mut Table<'db, 'tx, &'static SatPointValue, &'static [u8]>,
   pub(super) satpoint_to_sequence_number:
     &'a mut MultimapTable<'db, 'tx, &'static SatPointValue, u32>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'db, 'tx, u32, u32>,
+  pub(super) sequence_number_to_collection: &'a mut Table<'db, 'tx, u32, CollectionEntryValue>,
   pub(super) sequence_number_to_entry: &'a mut Table<'db, 'tx, u32, InscriptionEntryValue>,
+  pub(super) sequence_number_to_recursive_endpoints: &'a mut Table<'db, 'tx, u32, u16>,
   pub(super) sequence_number_to_satpoint: &'a mut Table<'db, 'tx, u32, &'static SatPointValue>,
+  pub(super) sequence_number_to_transfers:
+    &'a mut Table<'db, 'tx, (u32, u32), &'static TransferValue>,
+  pub(super) teleburn_claim_to_inscription_id:
+    &'a mut MultimapTable<'db, 'tx, &'static str, InscriptionIdValue>,
   pub(super) timestamp: u32,
   pub(super) unbound_inscriptions: u64,
   pub(super) value_cache: &'a mut HashMap<OutPoint, u64>,
@@ -97,10 +119,22 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         tx_in.previous_output,
       )? {
         let offset = total_input_value + old_satpoint.offset;
+
+        // owners are keyed by satpoint rather than outpoint, so that each of
+        // several inscriptions sharing an output resolves its own sender
+        let sender = self
+          .satpoint_to_owner
+          .get(&old_satpoint.store())?
+          .map(|owner| ScriptBuf::from_bytes(owner.value().to_vec()));
+
         floating_inscriptions.push(Flotsam {
           offset,
           inscription_id,
-          origin: Origin::Old { old_satpoint },
+          origin: Origin::Old {
+            fee: 0,
+            old_satpoint,
+            sender,
+          },
         });
 
         inscribed_offsets
@@ -142,7 +176,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           index: id_counter,
         };
 
-        let curse = if self.height >= self.chain.jubilee_height() {
+        let curse = if self.height >= self.params.jubilee_height {
           None
         } else if inscription.payload.unrecognized_even_field {
           Some(Curse::UnrecognizedEvenField)
@@ -201,12 +235,21 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           inscription_id,
           offset,
           origin: Origin::New {
+            content_type: inscription.payload.content_type().map(str::to_string),
             reinscription: inscribed_offsets.get(&offset).is_some(),
             cursed: curse.is_some(),
             fee: 0,
             hidden: inscription.payload.hidden(),
+            metaprotocol: inscription.payload.metaprotocol().map(str::to_string),
             parent: inscription.payload.parent(),
             pointer: inscription.payload.pointer(),
+            recursive_endpoints: recursion::recursive_endpoints(&inscription.payload),
+            size: inscription
+              .payload
+              .content_length()
+              .unwrap_or_default()
+              .try_into()
+              .unwrap(),
             unbound,
           },
         });
@@ -216,7 +259,21 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           .or_insert((inscription_id, 0))
           .1 += 1;
 
-        envelopes.next();
+        if let Some(claim) = teleburn::Claim::from_inscription(&inscription.payload) {
+          self.teleburn_claim_to_inscription_id.insert(
+            teleburn::Claim::key(claim.chain, &claim.asset).as_str(),
+            &inscription_id.store(),
+          )?;
+        }
+
+        let inscription = envelopes.next().unwrap();
+
+        // content consumers share the payload by reference, so move it rather
+        // than cloning bodies that can be hundreds of kilobytes
+        if let Some(contents) = self.contents.as_mut() {
+          contents.push((inscription_id, inscription.payload));
+        }
+
         id_counter += 1;
       }
     }
@@ -251,14 +308,20 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       }
     }
 
+    let transfer_count = floating_inscriptions
+      .iter()
+      .filter(|flotsam| matches!(flotsam.origin, Origin::Old { .. }))
+      .count();
+
     // still have to normalize over inscription size
     for flotsam in &mut floating_inscriptions {
-      if let Flotsam {
-        origin: Origin::New { ref mut fee, .. },
-        ..
-      } = flotsam
-      {
-        *fee = (total_input_value - total_output_value) / u64::from(id_counter);
+      match flotsam.origin {
+        Origin::New { ref mut fee, .. } => {
+          *fee = (total_input_value - total_output_value) / u64::from(id_counter);
+        }
+        Origin::Old { ref mut fee, .. } => {
+          *fee = (total_input_value - total_output_value) / u64::try_from(transfer_count).unwrap();
+        }
       }
     }
 
@@ -332,7 +395,9 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         _ => new_satpoint,
       };
 
-      self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+      let output = &tx.output[usize::try_from(new_satpoint.outpoint.vout).unwrap()];
+
+      self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint, Some(output))?;
     }
 
     if is_coinbase {
@@ -341,7 +406,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           outpoint: OutPoint::null(),
           offset: self.lost_sats + flotsam.offset - output_value,
         };
-        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint, None)?;
       }
       self.lost_sats += self.reward - output_value;
       Ok(())
@@ -379,167 +444,310 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
+    output: Option<&TxOut>,
   ) -> Result {
     let inscription_id = flotsam.inscription_id;
-    let (unbound, sequence_number) = match flotsam.origin {
-      Origin::Old { old_satpoint } => {
-        self
-          .satpoint_to_sequence_number
-          .remove_all(&old_satpoint.store())?;
-
-        (
-          false,
+    let owner = output.map(|output| output.script_pubkey.as_script());
+
+    let fee = match flotsam.origin {
+      Origin::New { fee, .. } | Origin::Old { fee, .. } => fee,
+    };
+    let (unbound, sequence_number, inscription_number, old_satpoint, sender, metaprotocol, parent) =
+      match flotsam.origin {
+        Origin::Old {
+          old_satpoint,
+          sender,
+          ..
+        } => {
           self
+            .satpoint_to_sequence_number
+            .remove_all(&old_satpoint.store())?;
+
+          self.satpoint_to_owner.remove(&old_satpoint.store())?;
+
+          let sequence_number = self
             .id_to_sequence_number
             .get(&inscription_id.store())?
             .unwrap()
-            .value(),
-        )
-      }
-      Origin::New {
-        cursed,
-        fee,
-        hidden,
-        parent,
-        pointer: _,
-        reinscription,
-        unbound,
-      } => {
-        let inscription_number = if cursed {
-          let number: i32 = self.cursed_inscription_count.try_into().unwrap();
-          self.cursed_inscription_count += 1;
-
-          // because cursed numbers start at -1
-          -(number + 1)
-        } else {
-          let number: i32 = self.blessed_inscription_count.try_into().unwrap();
-          self.blessed_inscription_count += 1;
+            .value();
 
-          number
-        };
+          let entry = InscriptionEntry::load(
+            self
+              .sequence_number_to_entry
+              .get(sequence_number)?
+              .unwrap()
+              .value(),
+          );
 
-        let sequence_number = self.next_sequence_number;
-        self.next_sequence_number += 1;
+          (
+            false,
+            sequence_number,
+            entry.inscription_number,
+            Some(old_satpoint),
+            sender,
+            None,
+            entry.parent,
+          )
+        }
+        Origin::New {
+          content_type,
+          cursed,
+          fee,
+          hidden,
+          metaprotocol,
+          parent,
+          pointer: _,
+          recursive_endpoints,
+          reinscription,
+          size,
+          unbound,
+        } => {
+          let inscription_number = if cursed {
+            let number: i32 = self.cursed_inscription_count.try_into().unwrap();
+            self.cursed_inscription_count += 1;
+
+            // because cursed numbers start at -1
+            -(number + 1)
+          } else {
+            let number: i32 = self.blessed_inscription_count.try_into().unwrap();
+            self.blessed_inscription_count += 1;
 
-        self
-          .inscription_number_to_sequence_number
-          .insert(inscription_number, sequence_number)?;
+            number
+          };
 
-        let sat = if unbound {
-          None
-        } else {
-          Self::calculate_sat(input_sat_ranges, flotsam.offset)
-        };
+          let sequence_number = self.next_sequence_number;
+          self.next_sequence_number += 1;
 
-        let mut charms = 0;
+          self
+            .inscription_number_to_sequence_number
+            .insert(inscription_number, sequence_number)?;
 
-        if cursed {
-          Charm::Cursed.set(&mut charms);
-        }
+          let sat = if unbound {
+            None
+          } else {
+            Self::calculate_sat(input_sat_ranges, flotsam.offset)
+          };
 
-        if reinscription {
-          Charm::Reinscription.set(&mut charms);
-        }
+          let mut charms = 0;
 
-        if let Some(sat) = sat {
-          if sat.nineball() {
-            Charm::Nineball.set(&mut charms);
+          if cursed {
+            Charm::Cursed.set(&mut charms);
           }
 
-          if sat.coin() {
-            Charm::Coin.set(&mut charms);
+          if reinscription {
+            Charm::Reinscription.set(&mut charms);
           }
 
-          match sat.rarity() {
-            Rarity::Common | Rarity::Mythic => {}
-            Rarity::Uncommon => Charm::Uncommon.set(&mut charms),
-            Rarity::Rare => Charm::Rare.set(&mut charms),
-            Rarity::Epic => Charm::Epic.set(&mut charms),
-            Rarity::Legendary => Charm::Legendary.set(&mut charms),
-          }
-        }
+          if let Some(sat) = sat {
+            if sat.nineball() {
+              Charm::Nineball.set(&mut charms);
+            }
 
-        if new_satpoint.outpoint == OutPoint::null() {
-          Charm::Lost.set(&mut charms);
-        }
+            if sat.coin() {
+              Charm::Coin.set(&mut charms);
+            }
 
-        if unbound {
-          Charm::Unbound.set(&mut charms);
-        }
+            match sat.rarity() {
+              Rarity::Common | Rarity::Mythic => {}
+              Rarity::Uncommon => Charm::Uncommon.set(&mut charms),
+              Rarity::Rare => Charm::Rare.set(&mut charms),
+              Rarity::Epic => Charm::Epic.set(&mut charms),
+              Rarity::Legendary => Charm::Legendary.set(&mut charms),
+            }
+          }
 
-        if let Some(Sat(n)) = sat {
-          self.sat_to_sequence_number.insert(&n, &sequence_number)?;
-        }
+          if new_satpoint.outpoint == OutPoint::null() {
+            Charm::Lost.set(&mut charms);
+          }
 
-        let parent = match parent {
-          Some(parent_id) => {
-            let parent_sequence_number = self
-              .id_to_sequence_number
-              .get(&parent_id.store())?
-              .unwrap()
-              .value();
-            self
-              .sequence_number_to_children
-              .insert(parent_sequence_number, sequence_number)?;
+          if unbound {
+            Charm::Unbound.set(&mut charms);
+          }
 
-            Some(parent_sequence_number)
+          if let Some(Sat(n)) = sat {
+            self.sat_to_sequence_number.insert(&n, &sequence_number)?;
           }
-          None => None,
-        };
 
-        self.sequence_number_to_entry.insert(
-          sequence_number,
-          &InscriptionEntry {
-            charms,
-            fee,
-            height: self.height,
-            id: inscription_id,
-            inscription_number,
-            parent,
-            sat,
+          let parent = match parent {
+            Some(parent_id) => {
+              let parent_sequence_number = self
+                .id_to_sequence_number
+                .get(&parent_id.store())?
+                .unwrap()
+                .value();
+              self
+                .sequence_number_to_children
+                .insert(parent_sequence_number, sequence_number)?;
+
+              Some(parent_sequence_number)
+            }
+            None => None,
+          };
+
+          self.sequence_number_to_entry.insert(
             sequence_number,
-            timestamp: self.timestamp,
+            &InscriptionEntry {
+              charms,
+              fee,
+              height: self.height,
+              id: inscription_id,
+              inscription_number,
+              parent,
+              sat,
+              sequence_number,
+              timestamp: self.timestamp,
+            }
+            .store(),
+          )?;
+
+          self
+            .id_to_sequence_number
+            .insert(&inscription_id.store(), sequence_number)?;
+
+          if recursive_endpoints != 0 {
+            self
+              .sequence_number_to_recursive_endpoints
+              .insert(sequence_number, recursive_endpoints)?;
           }
-          .store(),
-        )?;
 
-        self
-          .id_to_sequence_number
-          .insert(&inscription_id.store(), sequence_number)?;
+          if self.index_inscription_sorts {
+            for (sort, content_type, key) in sort::sort_keys(
+              content_type.as_deref(),
+              fee,
+              self.height,
+              inscription_number,
+              size,
+            ) {
+              self.inscription_sort_keys.insert(
+                (sort as u8, content_type.as_str(), key, sequence_number),
+                (),
+              )?;
+            }
+          }
 
-        if !hidden {
-          self
-            .home_inscriptions
-            .insert(&sequence_number, inscription_id.store())?;
+          if !hidden {
+            self
+              .home_inscriptions
+              .insert(&sequence_number, inscription_id.store())?;
 
-          if self.home_inscription_count == 100 {
-            self.home_inscriptions.pop_first()?;
-          } else {
-            self.home_inscription_count += 1;
+            if self.home_inscription_count == 100 {
+              self.home_inscriptions.pop_first()?;
+            } else {
+              self.home_inscription_count += 1;
+            }
           }
-        }
 
-        (unbound, sequence_number)
-      }
-    };
+          (
+            unbound,
+            sequence_number,
+            inscription_number,
+            None,
+            None,
+            metaprotocol,
+            parent,
+          )
+        }
+      };
 
-    let satpoint = if unbound {
+    let new_satpoint = if unbound {
       let new_unbound_satpoint = SatPoint {
         outpoint: unbound_outpoint(),
         offset: self.unbound_inscriptions,
       };
       self.unbound_inscriptions += 1;
-      new_unbound_satpoint.store()
+      new_unbound_satpoint
     } else {
-      new_satpoint.store()
+      new_satpoint
     };
 
+    let value = output
+      .filter(|_| !unbound)
+      .map(|output| output.value)
+      .unwrap_or_default();
+
+    if old_satpoint.is_some() {
+      self.fees.transfers += 1;
+      self.fees.transfer_fees += fee;
+      self.fees.transfer_value += value;
+    } else {
+      self.fees.creations += 1;
+      self.fees.creation_fees += fee;
+      self.fees.creation_value += value;
+    }
+
+    if let Some(events) = self.events.as_mut() {
+      events.push(match old_satpoint {
+        Some(old_location) => Event::InscriptionTransferred {
+          block_height: self.height,
+          inscription_id,
+          new_location: new_satpoint,
+          old_location,
+          sender,
+          sequence_number,
+        },
+        None => Event::InscriptionCreated {
+          block_height: self.height,
+          inscription_id,
+          inscription_number,
+          location: new_satpoint,
+          metaprotocol,
+          sequence_number,
+        },
+      });
+    }
+
+    if let Some(parent) = parent {
+      Collections {
+        child_sequence_number_to_owner: self.child_sequence_number_to_owner,
+        collection_holder_to_child_count: self.collection_holder_to_child_count,
+        sequence_number_to_collection: self.sequence_number_to_collection,
+      }
+      .update(
+        self.height,
+        parent,
+        sequence_number,
+        owner.filter(|_| !unbound),
+        old_satpoint.is_none(),
+      )?;
+    }
+
+    if let Some(old_satpoint) = old_satpoint {
+      let transfer = self
+        .sequence_number_to_transfers
+        .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+        .next_back()
+        .transpose()?
+        .map(|(key, _transfer)| key.value().1 + 1)
+        .unwrap_or_default();
+
+      self.sequence_number_to_transfers.insert(
+        (sequence_number, transfer),
+        &TransferEntry {
+          fee,
+          height: self.height,
+          new_satpoint,
+          old_satpoint,
+          value,
+        }
+        .store(),
+      )?;
+    }
+
+    let satpoint = new_satpoint.store();
+
+    if let Some(owner) = owner.filter(|_| !unbound) {
+      self.satpoint_to_owner.insert(&satpoint, owner.as_bytes())?;
+    }
+
     self
       .satpoint_to_sequence_number
       .insert(&satpoint, sequence_number)?;
     self
       .sequence_number_to_satpoint
       .insert(sequence_number, &satpoint)?;
+    self
+      .height_to_inscription_moves
+      .insert((self.height, sequence_number), &satpoint)?;
 
     Ok(())
   }
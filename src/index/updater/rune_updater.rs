@@ -27,11 +27,13 @@ pub(crate) struct RuneUpdate {
 }
 
 pub(super) struct RuneUpdater<'a, 'db, 'tx> {
+  pub(super) events: Option<&'a mut Vec<Event>>,
   pub(super) height: u32,
   pub(super) id_to_entry: &'a mut Table<'db, 'tx, RuneIdValue, RuneEntryValue>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'db, 'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
   pub(super) outpoint_to_balances: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) quarantined_entry_to_value: &'a mut Table<'db, 'tx, &'static str, &'static [u8]>,
   pub(super) rune_to_id: &'a mut Table<'db, 'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'db, 'tx, u32, RuneIdValue>,
@@ -55,13 +57,36 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
         .remove(&input.previous_output.store())?
       {
         let buffer = guard.value();
-        let mut i = 0;
-        while i < buffer.len() {
-          let (id, len) = varint::decode(&buffer[i..]);
-          i += len;
-          let (balance, len) = varint::decode(&buffer[i..]);
-          i += len;
-          *unallocated.entry(id).or_default() += balance;
+
+        match load_rune_balances(buffer) {
+          Ok(balances) => {
+            for (id, balance) in balances {
+              *unallocated.entry(id.into()).or_default() += balance;
+            }
+          }
+          Err(err) => {
+            let key = format!(
+              "{}:{}",
+              OUTPOINT_TO_RUNE_BALANCES.name(),
+              input.previous_output
+            );
+
+            log::warn!("quarantining corrupt entry `{key}`: {err}");
+
+            self
+              .quarantined_entry_to_value
+              .insert(key.as_str(), buffer)?;
+
+            let quarantined = self
+              .statistic_to_count
+              .get(&Statistic::QuarantinedEntries.into())?
+              .map(|entry| entry.value())
+              .unwrap_or_default();
+
+            self
+              .statistic_to_count
+              .insert(&Statistic::QuarantinedEntries.into(), quarantined + 1)?;
+          }
         }
       }
     }
@@ -272,6 +297,15 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
               .or_default();
             update.mints += 1;
             update.supply += minted;
+
+            if let Some(events) = self.events.as_mut() {
+              events.push(Event::RuneMinted {
+                amount: minted,
+                block_height: self.height,
+                rune_id: RuneId::try_from(id).unwrap(),
+                txid,
+              });
+            }
           }
         }
       }
@@ -324,6 +358,15 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
           .store(),
         )?;
 
+        if let Some(events) = self.events.as_mut() {
+          events.push(Event::RuneEtched {
+            block_height: self.height,
+            rune,
+            rune_id: id,
+            txid,
+          });
+        }
+
         let inscription_id = InscriptionId { txid, index: 0 };
 
         if let Some(sequence_number) = self
@@ -393,28 +436,45 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
       // Sort balances by id so tests can assert balances in a fixed order
       balances.sort();
 
+      let outpoint = OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      };
+
       for (id, balance) in balances {
         varint::encode_to_vec(id, &mut buffer);
         varint::encode_to_vec(balance, &mut buffer);
-      }
 
-      self.outpoint_to_balances.insert(
-        &OutPoint {
-          txid,
-          vout: vout.try_into().unwrap(),
+        if let Some(events) = self.events.as_mut() {
+          events.push(Event::RuneTransferred {
+            amount: balance,
+            block_height: self.height,
+            outpoint,
+            rune_id: RuneId::try_from(id).unwrap(),
+            txid,
+          });
         }
-        .store(),
-        buffer.as_slice(),
-      )?;
+      }
+
+      self
+        .outpoint_to_balances
+        .insert(&outpoint.store(), buffer.as_slice())?;
     }
 
     // increment entries with burned runes
     for (id, amount) in burned {
-      self
-        .updates
-        .entry(RuneId::try_from(id).unwrap())
-        .or_default()
-        .burned += amount;
+      let rune_id = RuneId::try_from(id).unwrap();
+
+      self.updates.entry(rune_id).or_default().burned += amount;
+
+      if let Some(events) = self.events.as_mut() {
+        events.push(Event::RuneBurned {
+          amount,
+          block_height: self.height,
+          rune_id,
+          txid,
+        });
+      }
     }
 
     Ok(())
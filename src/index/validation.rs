@@ -0,0 +1,311 @@
+use {
+  super::*,
+  clap::ValueEnum,
+  image::{io::Reader, ImageFormat},
+};
+
+/// Magic bytes of content types whose content can be reliably identified,
+/// as `(offset, bytes)` pairs that must all match.
+const SIGNATURES: &[(&str, &[(usize, &[u8])])] = &[
+  ("application/pdf", &[(0, b"%PDF-")]),
+  ("audio/flac", &[(0, b"fLaC")]),
+  ("audio/wav", &[(0, b"RIFF"), (8, b"WAVE")]),
+  ("font/woff", &[(0, b"wOFF")]),
+  ("font/woff2", &[(0, b"wOF2")]),
+  ("image/apng", &[(0, b"\x89PNG\r\n\x1a\n")]),
+  ("image/avif", &[(4, b"ftypavif")]),
+  ("image/gif", &[(0, b"GIF8")]),
+  ("image/jpeg", &[(0, b"\xff\xd8\xff")]),
+  ("image/png", &[(0, b"\x89PNG\r\n\x1a\n")]),
+  ("image/webp", &[(0, b"RIFF"), (8, b"WEBP")]),
+  ("model/gltf-binary", &[(0, b"glTF")]),
+  ("video/mp4", &[(4, b"ftyp")]),
+  ("video/webm", &[(0, b"\x1a\x45\xdf\xa3")]),
+];
+
+/// Checks that can be run on the content of newly indexed inscriptions, so
+/// that front-ends can avoid rendering oversized or mislabeled content.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContentValidator {
+  /// Content must decode as the raster image format of its content type.
+  ImageDecode,
+  /// Content must be no larger than `--max-content-size` bytes.
+  MaxSize,
+  /// Content's magic bytes must agree with its content type.
+  MimeSniff,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContentVerdict {
+  pub validator: ContentValidator,
+  pub valid: bool,
+}
+
+impl ContentValidator {
+  const ALL: [Self; 3] = [Self::ImageDecode, Self::MaxSize, Self::MimeSniff];
+
+  fn validate(self, inscription: &Inscription, max_content_size: usize) -> bool {
+    let body = inscription.body().unwrap_or_default();
+
+    let content_type = inscription.content_type().map(|content_type| {
+      content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+    });
+
+    match self {
+      Self::ImageDecode => {
+        let format = match content_type.as_deref() {
+          Some("image/apng" | "image/png") => ImageFormat::Png,
+          Some("image/gif") => ImageFormat::Gif,
+          Some("image/jpeg") => ImageFormat::Jpeg,
+          Some("image/webp") => ImageFormat::WebP,
+          _ => return true,
+        };
+
+        Reader::with_format(Cursor::new(body), format)
+          .decode()
+          .is_ok()
+      }
+      Self::MaxSize => body.len() <= max_content_size,
+      Self::MimeSniff => {
+        let matches = |signature: &[(usize, &[u8])]| {
+          signature
+            .iter()
+            .all(|(offset, magic)| body.get(*offset..).unwrap_or_default().starts_with(magic))
+        };
+
+        match SIGNATURES.iter().find(|(signature_content_type, _)| {
+          Some(*signature_content_type) == content_type.as_deref()
+        }) {
+          Some((_, signature)) => matches(signature),
+          None => !SIGNATURES.iter().any(|(_, signature)| matches(signature)),
+        }
+      }
+    }
+  }
+}
+
+/// Run `validators` on `inscription`'s content, returning one verdict per
+/// validator, in a stable order.
+pub(crate) fn validate_content(
+  validators: &[ContentValidator],
+  max_content_size: usize,
+  inscription: &Inscription,
+) -> Vec<ContentVerdict> {
+  ContentValidator::ALL
+    .into_iter()
+    .filter(|validator| validators.contains(validator))
+    .map(|validator| ContentVerdict {
+      validator,
+      valid: validator.validate(inscription, max_content_size),
+    })
+    .collect()
+}
+
+/// Run `validators` on the content of every inscription in `contents`,
+/// returning verdicts in the same order. Validating one inscription doesn't
+/// depend on any other, so blocks with many new inscriptions, such as mint
+/// storms, are split into chunks validated on a pool of scoped threads, one
+/// per available core. Writing the verdicts is left to the caller.
+pub(crate) fn validate_contents(
+  validators: &[ContentValidator],
+  max_content_size: usize,
+  contents: &[(InscriptionId, Inscription)],
+) -> Vec<Vec<ContentVerdict>> {
+  let threads = thread::available_parallelism()
+    .map(usize::from)
+    .unwrap_or(1);
+
+  if threads == 1 || contents.len() < threads * 2 {
+    return contents
+      .iter()
+      .map(|(_, inscription)| validate_content(validators, max_content_size, inscription))
+      .collect();
+  }
+
+  thread::scope(|scope| {
+    contents
+      .chunks((contents.len() + threads - 1) / threads)
+      .map(|chunk| {
+        scope.spawn(move || {
+          chunk
+            .iter()
+            .map(|(_, inscription)| validate_content(validators, max_content_size, inscription))
+            .collect::<Vec<Vec<ContentVerdict>>>()
+        })
+      })
+      .collect::<Vec<thread::ScopedJoinHandle<Vec<Vec<ContentVerdict>>>>>()
+      .into_iter()
+      .flat_map(|handle| handle.join().unwrap())
+      .collect()
+  })
+}
+
+/// Encode `verdicts` as one byte per verdict, holding the validator's index
+/// in [`ContentValidator::ALL`] shifted left by one, and the verdict in the
+/// low bit.
+pub(crate) fn store_content_verdicts(verdicts: &[ContentVerdict]) -> Vec<u8> {
+  verdicts
+    .iter()
+    .map(|verdict| {
+      let index = ContentValidator::ALL
+        .iter()
+        .position(|validator| *validator == verdict.validator)
+        .unwrap();
+
+      (u8::try_from(index).unwrap() << 1) | u8::from(verdict.valid)
+    })
+    .collect()
+}
+
+pub(crate) fn load_content_verdicts(buffer: &[u8]) -> Vec<ContentVerdict> {
+  buffer
+    .iter()
+    .filter_map(|byte| {
+      Some(ContentVerdict {
+        validator: *ContentValidator::ALL.get(usize::from(byte >> 1))?,
+        valid: byte & 1 == 1,
+      })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+    0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8, 0xcf, 0xc0, 0xf0,
+    0x1f, 0x00, 0x05, 0x00, 0x01, 0xff, 0x89, 0x99, 0x3d, 0x1d, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45,
+    0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+  ];
+
+  fn verdict(validator: ContentValidator, content_type: &str, body: &[u8]) -> bool {
+    validate_content(&[validator], 4, &inscription(content_type, body))[0].valid
+  }
+
+  #[test]
+  fn max_size() {
+    assert!(verdict(ContentValidator::MaxSize, "text/plain", b"1234"));
+    assert!(!verdict(ContentValidator::MaxSize, "text/plain", b"12345"));
+  }
+
+  #[test]
+  fn mime_sniff() {
+    assert!(verdict(ContentValidator::MimeSniff, "image/png", PNG));
+    assert!(verdict(ContentValidator::MimeSniff, "image/apng", PNG));
+    assert!(verdict(
+      ContentValidator::MimeSniff,
+      "IMAGE/PNG; foo=bar",
+      PNG
+    ));
+    assert!(verdict(
+      ContentValidator::MimeSniff,
+      "text/plain;charset=utf-8",
+      b"hello"
+    ));
+    assert!(!verdict(ContentValidator::MimeSniff, "image/png", b"hello"));
+    assert!(!verdict(ContentValidator::MimeSniff, "image/jpeg", PNG));
+    assert!(!verdict(ContentValidator::MimeSniff, "text/html", PNG));
+    assert!(!verdict(
+      ContentValidator::MimeSniff,
+      "text/plain",
+      b"%PDF-1.7"
+    ));
+  }
+
+  #[test]
+  fn image_decode() {
+    assert!(verdict(ContentValidator::ImageDecode, "image/png", PNG));
+    assert!(verdict(
+      ContentValidator::ImageDecode,
+      "text/plain",
+      b"hello"
+    ));
+    assert!(verdict(
+      ContentValidator::ImageDecode,
+      "image/svg+xml",
+      b"<svg/>"
+    ));
+    assert!(!verdict(
+      ContentValidator::ImageDecode,
+      "image/png",
+      &PNG[..40]
+    ));
+    assert!(!verdict(ContentValidator::ImageDecode, "image/gif", PNG));
+  }
+
+  #[test]
+  fn contents_are_validated_in_order() {
+    let contents = (0..100)
+      .map(|i| {
+        (
+          InscriptionId {
+            txid: Txid::all_zeros(),
+            index: i,
+          },
+          inscription("text/plain", "x".repeat(usize::try_from(i).unwrap())),
+        )
+      })
+      .collect::<Vec<(InscriptionId, Inscription)>>();
+
+    assert_eq!(
+      validate_contents(&[ContentValidator::MaxSize], 50, &contents)
+        .into_iter()
+        .map(|verdicts| verdicts[0].valid)
+        .collect::<Vec<bool>>(),
+      (0..100).map(|i| i <= 50).collect::<Vec<bool>>(),
+    );
+  }
+
+  #[test]
+  fn verdicts_are_ordered_and_deduplicated() {
+    assert_eq!(
+      validate_content(
+        &[
+          ContentValidator::MimeSniff,
+          ContentValidator::MaxSize,
+          ContentValidator::MimeSniff,
+        ],
+        4,
+        &inscription("image/png", "hello"),
+      ),
+      [
+        ContentVerdict {
+          validator: ContentValidator::MaxSize,
+          valid: false,
+        },
+        ContentVerdict {
+          validator: ContentValidator::MimeSniff,
+          valid: false,
+        },
+      ],
+    );
+  }
+
+  #[test]
+  fn store_and_load() {
+    let verdicts = ContentValidator::ALL
+      .into_iter()
+      .flat_map(|validator| {
+        [true, false]
+          .into_iter()
+          .map(move |valid| ContentVerdict { validator, valid })
+      })
+      .collect::<Vec<ContentVerdict>>();
+
+    assert_eq!(
+      load_content_verdicts(&store_content_verdicts(&verdicts)),
+      verdicts
+    );
+
+    assert_eq!(load_content_verdicts(&[0xff]), Vec::new());
+  }
+}
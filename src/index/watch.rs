@@ -0,0 +1,317 @@
+use {
+  super::{subscription::Notifier, updater::BlockData, *},
+  reqwest::Url,
+};
+
+/// Location registered with `POST /admin/watches`, either an outpoint, e.g.
+/// `<TXID>:<VOUT>`, or a satpoint, e.g. `<TXID>:<VOUT>:<OFFSET>`. When the
+/// outpoint is spent, the move is recorded in the index and, if `url` is set,
+/// delivered to it as a JSON `POST`. A watch moves at most once, since a spent
+/// outpoint can't be spent again.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Watch {
+  pub location: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub url: Option<String>,
+}
+
+/// Transaction that spent a watched location. For satpoint watches,
+/// `satpoint` is the new location of the watched sat, or `None` if it was
+/// spent as fee.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WatchMove {
+  pub height: u32,
+  pub input: u32,
+  pub satpoint: Option<SatPoint>,
+  pub txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct WatchJson {
+  pub id: u64,
+  pub moved: Option<WatchMove>,
+  #[serde(flatten)]
+  pub watch: Watch,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Location {
+  OutPoint(OutPoint),
+  SatPoint(SatPoint),
+}
+
+impl Location {
+  fn outpoint(self) -> OutPoint {
+    match self {
+      Self::OutPoint(outpoint) => outpoint,
+      Self::SatPoint(satpoint) => satpoint.outpoint,
+    }
+  }
+}
+
+/// Watch with its location and URL parsed, as loaded by the updater.
+pub(crate) struct Registered {
+  id: u64,
+  location: Location,
+  url: Option<Url>,
+}
+
+impl Watch {
+  pub(crate) fn parse(&self) -> Result<(Location, Option<Url>)> {
+    let location = if let Ok(satpoint) = self.location.parse::<SatPoint>() {
+      Location::SatPoint(satpoint)
+    } else if let Ok(outpoint) = self.location.parse::<OutPoint>() {
+      Location::OutPoint(outpoint)
+    } else {
+      bail!("invalid watch location `{}`", self.location);
+    };
+
+    let url = self
+      .url
+      .as_ref()
+      .map(|url| {
+        url
+          .parse()
+          .with_context(|| format!("invalid watch URL `{url}`"))
+      })
+      .transpose()?;
+
+    Ok((location, url))
+  }
+}
+
+/// Load the watches which haven't moved yet, keyed by watched outpoint.
+pub(crate) fn registered(wtx: &WriteTransaction) -> Result<HashMap<OutPoint, Vec<Registered>>> {
+  let moves = wtx.open_table(WATCH_ID_TO_MOVE)?;
+
+  let mut registered = HashMap::<OutPoint, Vec<Registered>>::new();
+
+  for result in wtx.open_table(WATCH_ID_TO_WATCH)?.iter()? {
+    let (id, watch) = result?;
+
+    let id = id.value();
+
+    if moves.get(id)?.is_some() {
+      continue;
+    }
+
+    let (location, url) = serde_json::from_slice::<Watch>(watch.value())?.parse()?;
+
+    registered
+      .entry(location.outpoint())
+      .or_default()
+      .push(Registered { id, location, url });
+  }
+
+  Ok(registered)
+}
+
+/// Record the moves of watches in `registered` spent by `block`, at
+/// `height`, and queue notifications for those with URLs.
+pub(crate) fn check(
+  index: &Index,
+  wtx: &WriteTransaction,
+  notifier: &Notifier,
+  registered: &HashMap<OutPoint, Vec<Registered>>,
+  height: u32,
+  block: &BlockData,
+) -> Result {
+  let mut moves = wtx.open_table(WATCH_ID_TO_MOVE)?;
+
+  for (tx, txid) in &block.txdata {
+    for (input, txin) in tx.input.iter().enumerate() {
+      let Some(watches) = registered.get(&txin.previous_output) else {
+        continue;
+      };
+
+      for watch in watches {
+        let satpoint = match watch.location {
+          Location::OutPoint(_) => None,
+          Location::SatPoint(satpoint) => {
+            new_satpoint(index, block, tx, *txid, input, satpoint.offset)?
+          }
+        };
+
+        let moved = WatchMove {
+          height,
+          input: input.try_into().unwrap(),
+          satpoint,
+          txid: *txid,
+        };
+
+        moves.insert(watch.id, serde_json::to_vec(&moved)?.as_slice())?;
+
+        log::info!("Watch {} moved in transaction {txid}", watch.id);
+
+        if let Some(url) = &watch.url {
+          notifier.send(
+            format!("watch {}", watch.id),
+            url.clone(),
+            serde_json::json!({
+              "moved": moved,
+              "watch": watch.id,
+            }),
+          )?;
+        }
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Where the sat at `offset` in input `input` of `tx` ends up, using the
+/// values of the preceding inputs, which are looked up in `block` or fetched
+/// from Bitcoin Core.
+fn new_satpoint(
+  index: &Index,
+  block: &BlockData,
+  tx: &Transaction,
+  txid: Txid,
+  input: usize,
+  offset: u64,
+) -> Result<Option<SatPoint>> {
+  let mut offset = offset;
+
+  for txin in &tx.input[..input] {
+    let previous_output = txin.previous_output;
+
+    let transaction = match block
+      .txdata
+      .iter()
+      .find(|(_, txid)| *txid == previous_output.txid)
+    {
+      Some((transaction, _)) => transaction.clone(),
+      None => index
+        .get_transaction(previous_output.txid)?
+        .with_context(|| format!("failed to fetch transaction {}", previous_output.txid))?,
+    };
+
+    offset += transaction
+      .output
+      .get(usize::try_from(previous_output.vout).unwrap())
+      .with_context(|| format!("output {previous_output} not found"))?
+      .value;
+  }
+
+  for (vout, output) in tx.output.iter().enumerate() {
+    if offset < output.value {
+      return Ok(Some(SatPoint {
+        outpoint: OutPoint {
+          txid,
+          vout: vout.try_into().unwrap(),
+        },
+        offset,
+      }));
+    }
+
+    offset -= output.value;
+  }
+
+  Ok(None)
+}
+
+impl Index {
+  /// Register `watch`, returning its ID.
+  pub(crate) fn add_watch(&self, watch: &Watch) -> Result<u64> {
+    watch.parse()?;
+
+    let wtx = self.begin_write()?;
+
+    let id = {
+      let mut watches = wtx.open_table(WATCH_ID_TO_WATCH)?;
+
+      let id = watches
+        .iter()?
+        .next_back()
+        .transpose()?
+        .map(|(id, _)| id.value() + 1)
+        .unwrap_or_default();
+
+      watches.insert(id, serde_json::to_vec(watch)?.as_slice())?;
+
+      id
+    };
+
+    wtx.commit()?;
+
+    Ok(id)
+  }
+
+  /// Remove watch `id` and its recorded move, returning false if it doesn't
+  /// exist.
+  pub(crate) fn remove_watch(&self, id: u64) -> Result<bool> {
+    let wtx = self.begin_write()?;
+
+    let removed = wtx.open_table(WATCH_ID_TO_WATCH)?.remove(id)?.is_some();
+
+    wtx.open_table(WATCH_ID_TO_MOVE)?.remove(id)?;
+
+    wtx.commit()?;
+
+    Ok(removed)
+  }
+
+  pub(crate) fn watches(&self) -> Result<Vec<WatchJson>> {
+    let rtx = self.database.begin_read()?;
+
+    let moves = rtx.open_table(WATCH_ID_TO_MOVE)?;
+
+    let watches = rtx
+      .open_table(WATCH_ID_TO_WATCH)?
+      .iter()?
+      .map(|result| {
+        let (id, watch) = result?;
+
+        Ok(WatchJson {
+          id: id.value(),
+          moved: moves
+            .get(id.value())?
+            .map(|moved| serde_json::from_slice(moved.value()))
+            .transpose()?,
+          watch: serde_json::from_slice(watch.value())?,
+        })
+      })
+      .collect::<Result<Vec<WatchJson>>>()?;
+
+    Ok(watches)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn watch(location: &str, url: Option<&str>) -> Watch {
+    Watch {
+      location: location.into(),
+      url: url.map(str::to_string),
+    }
+  }
+
+  #[test]
+  fn locations_are_parsed() {
+    assert_eq!(
+      watch(&outpoint(1).to_string(), None).parse().unwrap().0,
+      Location::OutPoint(outpoint(1)),
+    );
+
+    assert_eq!(
+      watch(&satpoint(1, 5).to_string(), None).parse().unwrap().0,
+      Location::SatPoint(satpoint(1, 5)),
+    );
+
+    assert_eq!(
+      watch("foo", None).parse().unwrap_err().to_string(),
+      "invalid watch location `foo`",
+    );
+
+    assert_eq!(
+      watch(&outpoint(1).to_string(), Some("localhost"))
+        .parse()
+        .unwrap_err()
+        .to_string(),
+      "invalid watch URL `localhost`",
+    );
+  }
+}
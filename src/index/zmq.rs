@@ -0,0 +1,256 @@
+use {
+  super::*,
+  std::{
+    io::{Read, Write},
+    net::TcpStream,
+    sync::mpsc,
+  },
+};
+
+const COMMAND: u8 = 0x04;
+const LONG: u8 = 0x02;
+const MORE: u8 = 0x01;
+
+// Bitcoin Core never publishes anything larger than a block
+const MAX_FRAME_SIZE: u64 = 32 * 1024 * 1024;
+
+/// Minimal ZMTP 3.0 `SUB` socket using the `NULL` security mechanism, which is
+/// all that is needed to consume Bitcoin Core's `-zmqpub*` notifications.
+pub(crate) struct Subscriber {
+  stream: TcpStream,
+}
+
+impl Subscriber {
+  pub(crate) fn connect(endpoint: &str, topic: &str) -> Result<Self> {
+    let address = endpoint
+      .strip_prefix("tcp://")
+      .ok_or_else(|| anyhow!("unsupported ZMQ endpoint `{endpoint}`, only tcp:// is supported"))?;
+
+    let mut stream = TcpStream::connect(address)
+      .with_context(|| format!("failed to connect to ZMQ endpoint {endpoint}"))?;
+
+    let mut greeting = [0; 64];
+    greeting[0] = 0xFF;
+    greeting[9] = 0x7F;
+    greeting[10] = 3;
+    greeting[12..16].copy_from_slice(b"NULL");
+
+    stream.write_all(&greeting)?;
+
+    stream.read_exact(&mut greeting)?;
+
+    ensure!(
+      greeting[0] == 0xFF && greeting[9] == 0x7F,
+      "ZMQ endpoint {endpoint} sent invalid greeting"
+    );
+
+    ensure!(
+      greeting[10] >= 3,
+      "ZMQ endpoint {endpoint} uses unsupported ZMTP version {}.{}",
+      greeting[10],
+      greeting[11],
+    );
+
+    ensure!(
+      &greeting[12..16] == b"NULL",
+      "ZMQ endpoint {endpoint} requires unsupported security mechanism"
+    );
+
+    let mut ready = vec![5];
+    ready.extend_from_slice(b"READY");
+    ready.push(11);
+    ready.extend_from_slice(b"Socket-Type");
+    ready.extend_from_slice(&3u32.to_be_bytes());
+    ready.extend_from_slice(b"SUB");
+
+    Self::write_frame(&mut stream, COMMAND, &ready)?;
+
+    let (flags, body) = Self::read_frame(&mut stream)?;
+
+    ensure!(
+      flags & COMMAND != 0 && body.get(1..6) == Some(b"READY".as_slice()),
+      "ZMQ endpoint {endpoint} did not send READY command"
+    );
+
+    let mut subscription = vec![1];
+    subscription.extend_from_slice(topic.as_bytes());
+
+    Self::write_frame(&mut stream, 0, &subscription)?;
+
+    Ok(Self { stream })
+  }
+
+  /// Block until the next message arrives and return its frames.
+  pub(crate) fn receive(&mut self) -> Result<Vec<Vec<u8>>> {
+    let mut frames = Vec::new();
+
+    loop {
+      let (flags, body) = Self::read_frame(&mut self.stream)?;
+
+      if flags & COMMAND != 0 {
+        continue;
+      }
+
+      frames.push(body);
+
+      if flags & MORE == 0 {
+        return Ok(frames);
+      }
+    }
+  }
+
+  fn write_frame(stream: &mut TcpStream, flags: u8, body: &[u8]) -> Result {
+    match u8::try_from(body.len()) {
+      Ok(len) => stream.write_all(&[flags, len])?,
+      Err(_) => {
+        stream.write_all(&[flags | LONG])?;
+        stream.write_all(&u64::try_from(body.len())?.to_be_bytes())?;
+      }
+    }
+
+    stream.write_all(body)?;
+
+    Ok(())
+  }
+
+  fn read_frame(stream: &mut TcpStream) -> Result<(u8, Vec<u8>)> {
+    let mut flags = [0];
+    stream.read_exact(&mut flags)?;
+    let flags = flags[0];
+
+    let len = if flags & LONG != 0 {
+      let mut len = [0; 8];
+      stream.read_exact(&mut len)?;
+      u64::from_be_bytes(len)
+    } else {
+      let mut len = [0];
+      stream.read_exact(&mut len)?;
+      len[0].into()
+    };
+
+    ensure!(
+      len <= MAX_FRAME_SIZE,
+      "ZMQ frame of {len} bytes exceeds maximum of {MAX_FRAME_SIZE} bytes"
+    );
+
+    let mut body = vec![0; usize::try_from(len)?];
+    stream.read_exact(&mut body)?;
+
+    Ok((flags, body))
+  }
+}
+
+/// Subscribe to `rawblock` notifications at `endpoint` in a background
+/// thread, reconnecting on error. The returned receiver yields a value
+/// whenever one or more new blocks have been announced.
+pub(crate) fn block_notifications(endpoint: String) -> mpsc::Receiver<()> {
+  let (sender, receiver) = mpsc::sync_channel(1);
+
+  thread::spawn(move || loop {
+    if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      break;
+    }
+
+    match Subscriber::connect(&endpoint, "rawblock") {
+      Ok(mut subscriber) => {
+        log::info!("Subscribed to ZMQ block notifications at {endpoint}");
+
+        loop {
+          let frames = match subscriber.receive() {
+            Ok(frames) => frames,
+            Err(err) => {
+              log::warn!("ZMQ subscription to {endpoint} failed: {err}");
+              break;
+            }
+          };
+
+          if frames.first().map(Vec::as_slice) != Some(b"rawblock".as_slice()) {
+            continue;
+          }
+
+          if let Some(header) = frames
+            .get(1)
+            .and_then(|block| block.get(..80))
+            .and_then(|header| consensus::encode::deserialize::<Header>(header).ok())
+          {
+            log::info!(
+              "Received ZMQ notification for block {}",
+              header.block_hash()
+            );
+          }
+
+          match sender.try_send(()) {
+            Ok(()) | Err(mpsc::TrySendError::Full(())) => {}
+            Err(mpsc::TrySendError::Disconnected(())) => return,
+          }
+        }
+      }
+      Err(err) => log::warn!("{err}"),
+    }
+
+    thread::sleep(Duration::from_secs(5));
+  });
+
+  receiver
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::net::TcpListener};
+
+  #[test]
+  fn subscriber_receives_multipart_messages() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("tcp://{}", listener.local_addr().unwrap());
+
+    let publisher = thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+
+      let mut greeting = [0; 64];
+      stream.read_exact(&mut greeting).unwrap();
+      assert_eq!(&greeting[12..16], b"NULL");
+      stream.write_all(&greeting).unwrap();
+
+      let (flags, ready) = Subscriber::read_frame(&mut stream).unwrap();
+      assert_eq!(flags, COMMAND);
+      assert_eq!(&ready[1..6], b"READY");
+      assert!(ready.ends_with(b"SUB"));
+
+      let mut ready = vec![5];
+      ready.extend_from_slice(b"READY");
+      Subscriber::write_frame(&mut stream, COMMAND, &ready).unwrap();
+
+      let (flags, subscription) = Subscriber::read_frame(&mut stream).unwrap();
+      assert_eq!(flags, 0);
+      assert_eq!(subscription, b"\x01rawblock");
+
+      Subscriber::write_frame(&mut stream, MORE, b"rawblock").unwrap();
+      Subscriber::write_frame(&mut stream, MORE, &[0; 300]).unwrap();
+      Subscriber::write_frame(&mut stream, 0, &7u32.to_le_bytes()).unwrap();
+    });
+
+    let mut subscriber = Subscriber::connect(&endpoint, "rawblock").unwrap();
+
+    assert_eq!(
+      subscriber.receive().unwrap(),
+      vec![
+        b"rawblock".to_vec(),
+        vec![0; 300],
+        7u32.to_le_bytes().to_vec()
+      ],
+    );
+
+    publisher.join().unwrap();
+  }
+
+  #[test]
+  fn only_tcp_endpoints_are_supported() {
+    assert_eq!(
+      Subscriber::connect("ipc:///tmp/bitcoind", "rawblock")
+        .err()
+        .unwrap()
+        .to_string(),
+      "unsupported ZMQ endpoint `ipc:///tmp/bitcoind`, only tcp:// is supported",
+    );
+  }
+}
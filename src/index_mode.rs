@@ -0,0 +1,37 @@
+use {super::*, clap::ValueEnum};
+
+#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum IndexMode {
+  #[default]
+  Standard,
+  Full,
+  ProtocolOnly,
+}
+
+impl IndexMode {
+  pub(crate) fn index_sats(self) -> bool {
+    self == Self::Full
+  }
+
+  pub(crate) fn index_transactions(self) -> bool {
+    self == Self::Full
+  }
+
+  pub(crate) fn index_runes(self) -> bool {
+    matches!(self, Self::Full | Self::ProtocolOnly)
+  }
+}
+
+impl Display for IndexMode {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Standard => "standard",
+        Self::Full => "full",
+        Self::ProtocolOnly => "protocol-only",
+      }
+    )
+  }
+}
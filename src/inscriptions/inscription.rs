@@ -30,7 +30,6 @@ pub struct Inscription {
 }
 
 impl Inscription {
-  #[cfg(test)]
   pub(crate) fn new(content_type: Option<Vec<u8>>, body: Option<Vec<u8>>) -> Self {
     Self {
       content_type,
@@ -52,7 +51,33 @@ impl Inscription {
 
     let body = fs::read(path).with_context(|| format!("io error reading {}", path.display()))?;
 
-    let (content_type, compression_mode) = Media::content_type_for_path(path)?;
+    let (content_type, _) = Media::content_type_for_path(path)?;
+
+    Self::from_content(
+      chain,
+      body,
+      content_type,
+      parent,
+      pointer,
+      metaprotocol,
+      metadata,
+      compress,
+    )
+  }
+
+  /// Create an inscription with `body` and `content_type`, for content that
+  /// does not come from a file with a recognized extension.
+  pub(crate) fn from_content(
+    chain: Chain,
+    body: Vec<u8>,
+    content_type: &str,
+    parent: Option<InscriptionId>,
+    pointer: Option<u64>,
+    metaprotocol: Option<String>,
+    metadata: Option<Vec<u8>>,
+    compress: bool,
+  ) -> Result<Self, Error> {
+    let compression_mode = Media::compression_mode_for_content_type(content_type);
 
     let (body, content_encoding) = if compress {
       let mut compressed = Vec::new();
@@ -52,15 +52,25 @@ impl Serialize for InscriptionId {
   }
 }
 
+/// Inscription IDs are displayed as `<TXID>i<INDEX>`, or with the alternate
+/// flag, `{:#}`, followed by a checksum, as `<TXID>i<INDEX>-<CHECKSUM>`. Both
+/// forms are accepted when parsing.
 impl Display for InscriptionId {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-    write!(f, "{}i{}", self.txid, self.index)
+    let id = format!("{}i{}", self.txid, self.index);
+
+    if f.alternate() {
+      write!(f, "{}", crate::checksum::append(&id))
+    } else {
+      write!(f, "{id}")
+    }
   }
 }
 
 #[derive(Debug)]
 pub enum ParseError {
   Character(char),
+  Checksum(crate::checksum::ChecksumMismatch),
   Length(usize),
   Separator(char),
   Txid(bitcoin::hashes::hex::Error),
@@ -71,6 +81,7 @@ impl Display for ParseError {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     match self {
       Self::Character(c) => write!(f, "invalid character: '{c}'"),
+      Self::Checksum(err) => write!(f, "invalid inscription ID: {err}"),
       Self::Length(len) => write!(f, "invalid length: {len}"),
       Self::Separator(c) => write!(f, "invalid separator: `{c}`"),
       Self::Txid(err) => write!(f, "invalid txid: {err}"),
@@ -89,6 +100,8 @@ impl FromStr for InscriptionId {
       return Err(ParseError::Character(char));
     }
 
+    let s = crate::checksum::strip(s).map_err(ParseError::Checksum)?;
+
     const TXID_LEN: usize = 64;
     const MIN_LEN: usize = TXID_LEN + 2;
 
@@ -169,6 +182,23 @@ mod tests {
     );
   }
 
+  #[test]
+  fn checksummed() {
+    let checksummed = format!("{:#}", inscription_id(1));
+
+    assert!(checksummed.starts_with(&format!("{}-", inscription_id(1))));
+    assert_eq!(
+      checksummed.parse::<InscriptionId>().unwrap(),
+      inscription_id(1)
+    );
+    assert_matches!(
+      checksummed
+        .replacen("i1-", "i2-", 1)
+        .parse::<InscriptionId>(),
+      Err(ParseError::Checksum(_)),
+    );
+  }
+
   #[test]
   fn from_str_bad_character() {
     assert_matches!(
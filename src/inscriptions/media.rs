@@ -122,6 +122,18 @@ impl Media {
     ))
   }
 
+  pub(crate) fn compression_mode_for_content_type(content_type: &str) -> BrotliEncoderMode {
+    let content_type = content_type.split(';').next().unwrap_or_default().trim();
+
+    Self::TABLE
+      .iter()
+      .find(|(table_content_type, _, _, _)| {
+        table_content_type.split(';').next() == Some(content_type)
+      })
+      .map(|(_, mode, _, _)| *mode)
+      .unwrap_or(BROTLI_MODE_GENERIC)
+  }
+
   pub(crate) fn check_mp4_codec(path: &Path) -> Result<(), Error> {
     let f = File::open(path)?;
     let size = f.metadata()?.len();
@@ -189,6 +201,26 @@ mod tests {
     );
   }
 
+  #[test]
+  fn compression_mode_for_content_type() {
+    assert_eq!(
+      Media::compression_mode_for_content_type("text/plain"),
+      BrotliEncoderMode::BROTLI_MODE_TEXT
+    );
+    assert_eq!(
+      Media::compression_mode_for_content_type("text/html; charset=utf-8"),
+      BrotliEncoderMode::BROTLI_MODE_TEXT
+    );
+    assert_eq!(
+      Media::compression_mode_for_content_type("image/png"),
+      BrotliEncoderMode::BROTLI_MODE_GENERIC
+    );
+    assert_eq!(
+      Media::compression_mode_for_content_type("application/x-unknown"),
+      BrotliEncoderMode::BROTLI_MODE_GENERIC
+    );
+  }
+
   #[test]
   fn h264_in_mp4_is_allowed() {
     assert!(Media::check_mp4_codec(Path::new("examples/h264.mp4")).is_ok(),);
@@ -1,15 +1,38 @@
-use {super::*, sha3::Digest, sha3::Keccak256};
+use {super::*, clap::ValueEnum, sha3::Digest, sha3::Keccak256};
+
+/// Chain that assets can be teleburned from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TargetChain {
+  Ethereum,
+  Solana,
+}
+
+impl Display for TargetChain {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Ethereum => write!(f, "ethereum"),
+      Self::Solana => write!(f, "solana"),
+    }
+  }
+}
+
+/// SHA-256 of the inscription ID's txid followed by its big-endian index,
+/// from which teleburn addresses are derived.
+fn digest(inscription_id: InscriptionId) -> bitcoin::hashes::sha256::Hash {
+  let mut array = [0; 36];
+  let (txid, index) = array.split_at_mut(32);
+  txid.copy_from_slice(inscription_id.txid.as_ref());
+  index.copy_from_slice(&inscription_id.index.to_be_bytes());
+  bitcoin::hashes::sha256::Hash::hash(&array)
+}
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Ethereum(String);
 
 impl From<InscriptionId> for Ethereum {
   fn from(inscription_id: InscriptionId) -> Self {
-    let mut array = [0; 36];
-    let (txid, index) = array.split_at_mut(32);
-    txid.copy_from_slice(inscription_id.txid.as_ref());
-    index.copy_from_slice(&inscription_id.index.to_be_bytes());
-    let digest = bitcoin::hashes::sha256::Hash::hash(&array);
+    let digest = digest(inscription_id);
     Self(create_address_with_checksum(&hex::encode(&digest[0..20])))
   }
 }
@@ -20,6 +43,57 @@ impl Display for Ethereum {
   }
 }
 
+/// Teleburn claim declared by an inscription with a JSON body of the form
+/// `{"p":"teleburn","chain":"<CHAIN>","asset":"<ASSET>"}`, where `<ASSET>`
+/// identifies the asset on `<CHAIN>` that was burned to the inscription's
+/// teleburn address. The index only records claims, it does not check that
+/// the asset was actually burned.
+#[derive(Debug, PartialEq, Deserialize)]
+pub(crate) struct Claim {
+  p: String,
+  pub(crate) chain: TargetChain,
+  pub(crate) asset: String,
+}
+
+impl Claim {
+  const MAX_ASSET_LEN: usize = 256;
+
+  /// Longest body that can hold a claim, well above the length of a claim
+  /// with the longest allowed asset.
+  const MAX_BODY_LEN: usize = Self::MAX_ASSET_LEN + 128;
+
+  /// Parse the claim declared by `inscription`, if any. Called on every
+  /// inscription while indexing, so bodies are only parsed as JSON if they
+  /// are short enough to be claims and contain `"teleburn"`.
+  pub(crate) fn from_inscription(inscription: &Inscription) -> Option<Self> {
+    let content_type = inscription.content_type()?;
+
+    if !content_type.starts_with("application/json") && !content_type.starts_with("text/plain") {
+      return None;
+    }
+
+    let body = inscription.body()?;
+
+    if body.len() > Self::MAX_BODY_LEN
+      || !body
+        .windows(br#""teleburn""#.len())
+        .any(|window| window == br#""teleburn""#)
+    {
+      return None;
+    }
+
+    let claim = serde_json::from_slice::<Self>(body).ok()?;
+
+    (claim.p == "teleburn" && !claim.asset.is_empty() && claim.asset.len() <= Self::MAX_ASSET_LEN)
+      .then_some(claim)
+  }
+
+  /// Key of claims of `asset` on `chain` in the index.
+  pub(crate) fn key(chain: TargetChain, asset: &str) -> String {
+    format!("{chain}:{asset}")
+  }
+}
+
 /// Given the hex digits of an Ethereum address, return that address with a
 /// checksum as per https://eips.ethereum.org/EIPS/eip-55
 fn create_address_with_checksum(address: &str) -> String {
@@ -91,4 +165,71 @@ mod tests {
       assert_eq!(*addr, Ethereum::from(*inscription_id).0);
     }
   }
+
+  #[test]
+  fn claims_are_parsed_from_json_bodies() {
+    let claim =
+      |content_type: &str, body: &str| Claim::from_inscription(&inscription(content_type, body));
+
+    assert_eq!(
+      claim(
+        "application/json",
+        r#"{"p":"teleburn","chain":"ethereum","asset":"0xabc:1"}"#
+      ),
+      Some(Claim {
+        p: "teleburn".into(),
+        chain: TargetChain::Ethereum,
+        asset: "0xabc:1".into(),
+      }),
+    );
+
+    assert_eq!(
+      claim(
+        "text/plain;charset=utf-8",
+        r#"{"p":"teleburn","chain":"solana","asset":"foo"}"#
+      )
+      .unwrap()
+      .chain,
+      TargetChain::Solana,
+    );
+
+    assert_eq!(
+      claim(
+        "image/png",
+        r#"{"p":"teleburn","chain":"ethereum","asset":"foo"}"#
+      ),
+      None
+    );
+    assert_eq!(
+      claim(
+        "application/json",
+        r#"{"p":"brc-20","chain":"ethereum","asset":"foo"}"#
+      ),
+      None
+    );
+    assert_eq!(
+      claim(
+        "application/json",
+        r#"{"p":"teleburn","chain":"dogecoin","asset":"foo"}"#
+      ),
+      None
+    );
+    assert_eq!(
+      claim(
+        "application/json",
+        r#"{"p":"teleburn","chain":"ethereum","asset":""}"#
+      ),
+      None
+    );
+    assert_eq!(
+      claim(
+        "application/json",
+        &format!(
+          r#"{{"p":"teleburn","chain":"ethereum","asset":"foo","padding":"{}"}}"#,
+          " ".repeat(Claim::MAX_BODY_LEN)
+        )
+      ),
+      None
+    );
+  }
 }
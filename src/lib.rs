@@ -15,17 +15,24 @@ use {
     arguments::Arguments,
     blocktime::Blocktime,
     config::Config,
+    deadline::{Deadline, DeadlineExceeded},
     decimal::Decimal,
     decimal_sat::DecimalSat,
     degree::Degree,
     deserialize_from_str::DeserializeFromStr,
+    destination::Destination,
     epoch::Epoch,
+    fee_rate::FeeRateArg,
     height::Height,
+    hidden::Hidden,
     index::{List, RuneEntry},
+    index_mode::IndexMode,
     inscriptions::{media, teleburn, Charm, Media, ParsedEnvelope},
+    network_params::NetworkParams,
+    options::{CrashPoint, EventEncoding, ProgressFormat},
     outgoing::Outgoing,
     representation::Representation,
-    runes::{Etching, Pile, SpacedRune},
+    runes::{Etching, Pile, RuneInfo, SpacedRune},
     subcommand::{Subcommand, SubcommandResult},
     tally::Tally,
   },
@@ -85,7 +92,14 @@ use {
 pub use self::{
   chain::Chain,
   fee_rate::FeeRate,
-  index::Index,
+  index::{
+    activity::{Activity, ActivityKind, Direction},
+    recursion::RecursiveEndpoint,
+    subscription::{Subscription, SubscriptionJson},
+    validation::{ContentValidator, ContentVerdict},
+    watch::{Watch, WatchJson, WatchMove},
+    Index,
+  },
   inscriptions::{Envelope, Inscription, InscriptionId},
   object::Object,
   options::Options,
@@ -113,28 +127,39 @@ macro_rules! tprintln {
     };
 }
 
+pub mod api;
 mod arguments;
 mod blocktime;
 pub mod chain;
+mod checksum;
 mod config;
+mod deadline;
 mod decimal;
 mod decimal_sat;
 mod degree;
 mod deserialize_from_str;
+mod destination;
 mod epoch;
 mod fee_rate;
+#[cfg(fuzzing)]
+pub mod fuzzing;
 mod height;
+mod hidden;
 mod index;
+mod index_mode;
 mod inscriptions;
+mod network_params;
 mod object;
 mod options;
 mod outgoing;
 pub mod rarity;
 mod representation;
+mod rpc_transport;
 pub mod runes;
 pub mod sat;
 mod sat_point;
 mod server_config;
+mod signature;
 pub mod subcommand;
 mod tally;
 pub mod templates;
@@ -144,8 +169,8 @@ type Result<T = (), E = Error> = std::result::Result<T, E>;
 const CYCLE_EPOCHS: u32 = 6;
 
 static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
-static LISTENERS: Mutex<Vec<axum_server::Handle>> = Mutex::new(Vec::new());
-static INDEXER: Mutex<Option<thread::JoinHandle<()>>> = Mutex::new(Option::None);
+static LISTENERS: Mutex<Vec<(axum_server::Handle, Duration)>> = Mutex::new(Vec::new());
+static INDEXERS: Mutex<Vec<thread::JoinHandle<()>>> = Mutex::new(Vec::new());
 
 const TARGET_POSTAGE: Amount = Amount::from_sat(10_000);
 
@@ -202,12 +227,16 @@ fn unbound_outpoint() -> OutPoint {
 }
 
 fn gracefully_shutdown_indexer() {
-  if let Some(indexer) = INDEXER.lock().unwrap().take() {
-    // We explicitly set this to true to notify the thread to not take on new work
+  let indexers = mem::take(&mut *INDEXERS.lock().unwrap());
+
+  if !indexers.is_empty() {
+    // We explicitly set this to true to notify the threads to not take on new work
     SHUTTING_DOWN.store(true, atomic::Ordering::Relaxed);
-    log::info!("Waiting for index thread to finish...");
-    if indexer.join().is_err() {
-      log::warn!("Index thread panicked; join failed");
+    log::info!("Waiting for index threads to finish...");
+    for indexer in indexers {
+      if indexer.join().is_err() {
+        log::warn!("Index thread panicked; join failed");
+      }
     }
   }
 }
@@ -226,11 +255,11 @@ pub fn main() {
       .lock()
       .unwrap()
       .iter()
-      .for_each(|handle| handle.graceful_shutdown(Some(Duration::from_millis(100))));
+      .for_each(|(handle, drain_timeout)| handle.graceful_shutdown(Some(*drain_timeout)));
   })
   .expect("Error setting <CTRL-C> handler");
 
-  match Arguments::parse().run() {
+  match Arguments::load().and_then(Arguments::run) {
     Err(err) => {
       eprintln!("error: {err}");
       err
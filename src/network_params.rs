@@ -0,0 +1,100 @@
+use super::*;
+
+/// Chain-dependent parameters used by the inscription and rune handlers:
+/// the network addresses are derived for and checked against, and the heights
+/// at which each protocol activates. They are resolved once, from the chain
+/// and any overriding options, and passed to every handler, so that handlers
+/// agree on them on every chain, including signet and regtest deployments
+/// with overridden heights.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct NetworkParams {
+  pub(crate) chain: Chain,
+  pub(crate) first_inscription_height: u32,
+  pub(crate) first_rune_height: u32,
+  pub(crate) jubilee_height: u32,
+}
+
+impl NetworkParams {
+  pub(crate) fn new(chain: Chain) -> Self {
+    Self {
+      chain,
+      first_inscription_height: chain.first_inscription_height(),
+      first_rune_height: chain.first_rune_height(),
+      jubilee_height: chain.jubilee_height(),
+    }
+  }
+
+  pub(crate) fn network(self) -> Network {
+    self.chain.network()
+  }
+
+  pub(crate) fn address_from_script(
+    self,
+    script: &Script,
+  ) -> Result<Address, bitcoin::address::Error> {
+    self.chain.address_from_script(script)
+  }
+
+  /// Check that `address` is valid on this network.
+  pub(crate) fn require_network(self, address: Address<NetworkUnchecked>) -> Result<Address> {
+    Ok(address.require_network(self.network())?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn defaults_come_from_chain() {
+    assert_eq!(
+      NetworkParams::new(Chain::Signet),
+      NetworkParams {
+        chain: Chain::Signet,
+        first_inscription_height: 112402,
+        first_rune_height: 0,
+        jubilee_height: 175392,
+      }
+    );
+  }
+
+  #[test]
+  fn options_override_defaults() {
+    let params = Arguments::try_parse_from([
+      "ord",
+      "--chain",
+      "signet",
+      "--first-inscription-height",
+      "100",
+      "index",
+      "update",
+    ])
+    .unwrap()
+    .options
+    .network_params();
+
+    assert_eq!(params.first_inscription_height, 100);
+    assert_eq!(params.jubilee_height, Chain::Signet.jubilee_height());
+  }
+
+  #[test]
+  fn addresses_must_match_network() {
+    let params = NetworkParams::new(Chain::Regtest);
+
+    assert!(params
+      .require_network(
+        "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+          .parse()
+          .unwrap()
+      )
+      .is_ok());
+
+    assert!(params
+      .require_network(
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+          .parse()
+          .unwrap()
+      )
+      .is_err());
+  }
+}
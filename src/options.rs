@@ -1,4 +1,11 @@
-use {super::*, bitcoincore_rpc::Auth};
+use {
+  super::*,
+  crate::index::{bucket::Bucket, filter::Filter},
+  crate::rpc_transport::{MethodLimit, RpcTransport},
+  bitcoin::hashes::sha256d,
+  bitcoincore_rpc::{jsonrpc, Auth},
+  clap::ValueEnum,
+};
 
 #[derive(Clone, Default, Debug, Parser)]
 #[command(group(
@@ -13,6 +20,11 @@ pub struct Options {
   pub(crate) bitcoin_rpc_pass: Option<String>,
   #[arg(long, help = "Authenticate to Bitcoin Core RPC as <RPC_USER>.")]
   pub(crate) bitcoin_rpc_user: Option<String>,
+  #[arg(
+    long,
+    help = "Fetch blocks and transactions pruned by Bitcoin Core from Esplora-compatible API at <BLOCK_ARCHIVE_URL>, e.g. `https://blockstream.info/api`. Fetched data is validated against block hashes from Bitcoin Core."
+  )]
+  pub(crate) block_archive_url: Option<String>,
   #[arg(
     long = "chain",
     value_enum,
@@ -20,12 +32,51 @@ pub struct Options {
     help = "Use <CHAIN>."
   )]
   pub(crate) chain_argument: Chain,
+  #[arg(
+    long,
+    help = "Limit output caches used while indexing to approximately <CACHE_BUDGET> bytes, moving the oldest cached outputs to the index when exceeded. Lower values reduce memory use at the cost of sync speed."
+  )]
+  pub(crate) cache_budget: Option<usize>,
+  #[arg(
+    long,
+    help = "Commit index early if output caches grow beyond approximately <COMMIT_CACHE_SIZE> bytes."
+  )]
+  pub(crate) commit_cache_size: Option<usize>,
+  #[arg(
+    long,
+    help = "Commit index every <COMMIT_INTERVAL> blocks. Lower values lose less progress on crash, higher values sync faster. [default: 5000]"
+  )]
+  pub(crate) commit_interval: Option<u32>,
   #[arg(long, help = "Load configuration from <CONFIG>.")]
   pub(crate) config: Option<PathBuf>,
   #[arg(long, help = "Load configuration from <CONFIG_DIR>.")]
   pub(crate) config_dir: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Upload the content of every inscription indexed to S3-compatible bucket at <CONTENT_BUCKET>, e.g. `https://s3.us-east-1.amazonaws.com/my-bucket`, as `<CONTENT_BUCKET>/<INSCRIPTION_ID>`. Requests are signed with `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` if set."
+  )]
+  pub(crate) content_bucket: Option<String>,
+  #[arg(
+    long,
+    requires = "content_bucket",
+    help = "Sign content bucket requests for <CONTENT_BUCKET_REGION>. [default: us-east-1]"
+  )]
+  pub(crate) content_bucket_region: Option<String>,
+  #[arg(
+    long,
+    value_enum,
+    help = "Check the content of every inscription indexed with <CONTENT_VALIDATOR> and store the verdict in the index. May be passed more than once. `max-size` rejects content larger than `--max-content-size`, `mime-sniff` rejects content whose magic bytes contradict its content type, and `image-decode` rejects PNG, APNG, GIF, JPEG, and WebP images that fail to decode."
+  )]
+  pub(crate) content_validator: Vec<ContentValidator>,
   #[arg(long, help = "Load Bitcoin Core RPC cookie file from <COOKIE_FILE>.")]
   pub(crate) cookie_file: Option<PathBuf>,
+  #[arg(
+    long,
+    hide = true,
+    value_enum,
+    help = "Abort indexer at <CRASH_AT>, to test crash recovery. Only available in integration tests."
+  )]
+  pub(crate) crash_at: Option<CrashPoint>,
   #[arg(long, help = "Store index in <DATA_DIR>.", default_value_os_t = Options::default_data_dir())]
   pub(crate) data_dir: PathBuf,
   #[arg(
@@ -42,6 +93,33 @@ pub struct Options {
   pub(crate) height_limit: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Track the outputs paying each address, so that balances can be looked up by address."
+  )]
+  pub(crate) index_addresses: bool,
+  #[arg(
+    long,
+    help = "Store the events of each block, so that they can be served by `/export/blocks`, `/r/checkpoint/<HEIGHT>`, and the `getblockevents` JSON-RPC method."
+  )]
+  pub(crate) index_events: bool,
+  #[arg(
+    long,
+    help = "Index inscriptions by fee, size, and content type, so that they can be listed in those orders."
+  )]
+  pub(crate) index_inscription_sorts: bool,
+  #[arg(
+    long,
+    help = "Wait up to <INDEX_LOCK_TIMEOUT> seconds for another process to release the index before giving up, so that a replacement process can be started before the previous one has exited. [default: 0]"
+  )]
+  pub(crate) index_lock_timeout: Option<u64>,
+  #[arg(
+    long,
+    value_enum,
+    conflicts_with_all = &["index_sats", "index_transactions"],
+    help = "Build index in <INDEX_MODE>. `full` tracks all satoshis and stores transactions, `protocol-only` tracks inscriptions and runes but not satoshis or transactions, for a much smaller index. [default: standard]"
+  )]
+  pub(crate) index_mode: Option<IndexMode>,
   #[arg(
     long,
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
@@ -51,6 +129,45 @@ pub struct Options {
   pub(crate) index_sats: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
+  #[arg(
+    long,
+    help = "Add and pin the content of every inscription indexed to IPFS node with HTTP RPC API at <IPFS_API_URL>, e.g. `http://127.0.0.1:5001`, and store its CID in the index."
+  )]
+  pub(crate) ipfs_api_url: Option<String>,
+  #[arg(
+    long,
+    help = "Reject inscription content larger than <MAX_CONTENT_SIZE> bytes with the `max-size` content validator. [default: 400000]"
+  )]
+  pub(crate) max_content_size: Option<usize>,
+  #[arg(
+    long,
+    help = "Keep <MAX_SAVEPOINTS> savepoints to roll back to on reorg. Reorgs up to `(<MAX_SAVEPOINTS> - 1) * <SAVEPOINT_INTERVAL>` blocks deep can always be recovered from. [default: 2]"
+  )]
+  pub(crate) max_savepoints: Option<u32>,
+  #[arg(
+    long,
+    help = "Publish inscription and rune events for every indexed block as JSON to NATS server at <NATS_URL>, e.g. `nats://127.0.0.1:4222`. Events are published before the block is committed to the index, so delivery is at-least-once and resumes from the last committed height after a restart. Each event carries a `Nats-Msg-Id` header of the form `<HEIGHT>:<TXID>:<INDEX>`, which JetStream uses to drop duplicates."
+  )]
+  pub(crate) nats_url: Option<String>,
+  #[arg(
+    long,
+    requires = "nats_url",
+    help = "Publish NATS events on subjects `<NATS_SUBJECT_PREFIX>.<EVENT_TYPE>`. [default: ord]"
+  )]
+  pub(crate) nats_subject_prefix: Option<String>,
+  #[arg(
+    long,
+    requires = "nats_url",
+    help = "Only publish NATS events whose JSON payload matches <NATS_FILTER>, e.g. `type == \"rune_minted\" && amount > 1000` or `metaprotocol == \"brc-20\"`. Payload fields may be compared to string, number, boolean and null literals with `==`, `!=`, `<`, `<=`, `>` and `>=`, and combined with `&&`, `||`, `!` and parentheses. Missing fields are null."
+  )]
+  pub(crate) nats_filter: Option<Filter>,
+  #[arg(
+    long,
+    value_enum,
+    requires = "nats_url",
+    help = "Publish NATS events encoded as <NATS_ENCODING>. `protobuf` messages are defined in `proto/ord.proto`. [default: json]"
+  )]
+  pub(crate) nats_encoding: Option<EventEncoding>,
   #[arg(
     long,
     short,
@@ -58,17 +175,119 @@ pub struct Options {
     help = "Do not index inscriptions."
   )]
   pub(crate) no_index_inscriptions: bool,
+  #[arg(
+    long,
+    requires = "nostr_relay",
+    help = "Publish Nostr events of kind <NOSTR_KIND>. [default: 1]"
+  )]
+  pub(crate) nostr_kind: Option<u16>,
+  #[arg(
+    long,
+    requires = "nostr_relay",
+    help = "Only publish Nostr events for children of <NOSTR_PARENT>. May be given multiple times."
+  )]
+  pub(crate) nostr_parent: Vec<InscriptionId>,
+  #[arg(
+    long,
+    help = "Publish a Nostr event for every inscription indexed to relay <NOSTR_RELAY>, e.g. `wss://relay.example.com`. Events are signed with the hex-encoded secret key in `NOSTR_SECRET_KEY` and their content is the inscription ID. May be given multiple times."
+  )]
+  pub(crate) nostr_relay: Vec<String>,
+  #[arg(
+    long,
+    value_enum,
+    help = "Report indexing progress as <PROGRESS>. `json` writes one JSON object per indexed block to stderr. [default: bar]"
+  )]
+  pub(crate) progress: Option<ProgressFormat>,
+  #[arg(
+    long,
+    help = "Mirror inscriptions and rune events into PostgreSQL database at <POSTGRES_URL>, e.g. `postgres://ord@localhost/ord`. Each block is written in its own Postgres transaction, events already applied before a restart are skipped, and blocks rolled back by a reorg are removed."
+  )]
+  pub(crate) postgres_url: Option<String>,
   #[arg(long, short, help = "Use regtest. Equivalent to `--chain regtest`.")]
   pub(crate) regtest: bool,
+  #[arg(
+    long,
+    help = "Fail over to Bitcoin Core RPC at <RPC_FAILOVER_URL> when requests to the current endpoint fail and are retried. Uses the same credentials as `--rpc-url`. May be given multiple times."
+  )]
+  pub(crate) rpc_failover_url: Vec<String>,
+  #[arg(
+    long,
+    help = "Limit concurrent Bitcoin Core RPC requests for a method with <RPC_METHOD_LIMIT>, of the form `<METHOD>=<LIMIT>`, e.g. `getblock=4`. May be given multiple times."
+  )]
+  pub(crate) rpc_method_limit: Vec<MethodLimit>,
+  #[arg(
+    long,
+    help = "Fetch blocks from the Bitcoin Core REST interface, which is faster than JSON-RPC. Bitcoin Core must be run with `-rest`, otherwise blocks are fetched over JSON-RPC."
+  )]
+  pub(crate) rpc_rest: bool,
+  #[arg(
+    long,
+    help = "Retry Bitcoin Core RPC requests that fail because the node is unreachable or starting up <RPC_RETRIES> times. [default: 0]"
+  )]
+  pub(crate) rpc_retries: Option<u32>,
+  #[arg(
+    long,
+    help = "Wait <RPC_RETRY_DELAY> milliseconds before retrying a failed Bitcoin Core RPC request, doubling after each retry up to one minute. [default: 500]"
+  )]
+  pub(crate) rpc_retry_delay: Option<u64>,
   #[arg(long, help = "Connect to Bitcoin Core RPC at <RPC_URL>.")]
   pub(crate) rpc_url: Option<String>,
+  #[arg(
+    long,
+    help = "Create a savepoint every <SAVEPOINT_INTERVAL> blocks when near the chain tip. [default: 10]"
+  )]
+  pub(crate) savepoint_interval: Option<u32>,
   #[arg(long, short, help = "Use signet. Equivalent to `--chain signet`.")]
   pub(crate) signet: bool,
+  #[arg(
+    long,
+    value_parser = ScriptBuf::from_hex,
+    help = "Use custom signet with hex-encoded block signing challenge <SIGNET_CHALLENGE>. Requires `--chain signet`."
+  )]
+  pub(crate) signet_challenge: Option<ScriptBuf>,
   #[arg(long, short, help = "Use testnet. Equivalent to `--chain testnet`.")]
   pub(crate) testnet: bool,
 }
 
+/// Points in the indexer at which `--crash-at` aborts the process.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum CrashPoint {
+  /// After a block's inscriptions and runes are indexed, but before its
+  /// header is stored.
+  MidBlock,
+  /// Before the write transaction is committed.
+  PreCommit,
+  /// After the write transaction is committed, but before the lease and
+  /// savepoints are updated.
+  PostCommit,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum EventEncoding {
+  Json,
+  Protobuf,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum ProgressFormat {
+  Bar,
+  Json,
+}
+
 impl Options {
+  pub(crate) fn content_bucket(&self) -> Result<Option<Bucket>> {
+    self
+      .content_bucket
+      .as_deref()
+      .map(|url| {
+        Bucket::new(
+          url,
+          self.content_bucket_region.as_deref().unwrap_or("us-east-1"),
+        )
+      })
+      .transpose()
+  }
+
   pub(crate) fn chain(&self) -> Chain {
     if self.signet {
       Chain::Signet
@@ -81,26 +300,48 @@ impl Options {
     }
   }
 
-  pub(crate) fn first_inscription_height(&self) -> u32 {
+  pub(crate) fn network_params(&self) -> NetworkParams {
+    let params = NetworkParams::new(self.chain());
+
     if integration_test() {
-      0
-    } else {
-      self
-        .first_inscription_height
-        .unwrap_or_else(|| self.chain().first_inscription_height())
+      return NetworkParams {
+        first_inscription_height: 0,
+        first_rune_height: 0,
+        ..params
+      };
     }
-  }
 
-  pub(crate) fn first_rune_height(&self) -> u32 {
-    if integration_test() {
-      0
+    // Protocols are active from genesis on custom signets.
+    let params = if self.custom_signet_magic().is_some() {
+      NetworkParams {
+        first_inscription_height: 0,
+        first_rune_height: 0,
+        jubilee_height: 0,
+        ..params
+      }
     } else {
-      self.chain().first_rune_height()
+      params
+    };
+
+    NetworkParams {
+      first_inscription_height: self
+        .first_inscription_height
+        .unwrap_or(params.first_inscription_height),
+      ..params
     }
   }
 
   pub(crate) fn index_runes(&self) -> bool {
-    self.index_runes && self.chain() != Chain::Mainnet
+    (self.index_runes || self.index_mode.unwrap_or_default().index_runes())
+      && self.chain() != Chain::Mainnet
+  }
+
+  pub(crate) fn index_sats(&self) -> bool {
+    self.index_sats || self.index_mode.unwrap_or_default().index_sats()
+  }
+
+  pub(crate) fn index_transactions(&self) -> bool {
+    self.index_transactions || self.index_mode.unwrap_or_default().index_transactions()
   }
 
   pub(crate) fn rpc_url(&self, wallet_name: Option<String>) -> String {
@@ -109,6 +350,10 @@ impl Options {
       .clone()
       .unwrap_or(format!("127.0.0.1:{}", self.chain().default_rpc_port()));
 
+    Self::wallet_url(&base_url, wallet_name.as_deref())
+  }
+
+  fn wallet_url(base_url: &str, wallet_name: Option<&str>) -> String {
     match wallet_name {
       Some(wallet_name) => format!("{base_url}/wallet/{wallet_name}"),
       None => format!("{base_url}/"),
@@ -144,19 +389,45 @@ impl Options {
   }
 
   pub(crate) fn data_dir(&self) -> PathBuf {
-    self.chain().join_with_data_dir(&self.data_dir)
+    match self.custom_signet_magic() {
+      Some(magic) => self.data_dir.join(format!("signet-{}", hex::encode(magic))),
+      None => self.chain().join_with_data_dir(&self.data_dir),
+    }
   }
 
-  pub(crate) fn load_config(&self) -> Result<Config> {
-    match &self.config {
-      Some(path) => Ok(serde_yaml::from_reader(File::open(path)?)?),
-      None => match &self.config_dir {
-        Some(dir) if dir.join("ord.yaml").exists() => {
-          Ok(serde_yaml::from_reader(File::open(dir.join("ord.yaml"))?)?)
-        }
-        Some(_) | None => Ok(Default::default()),
-      },
+  /// Network magic of the custom signet selected with `--signet-challenge`,
+  /// which is the first four bytes of the double SHA-256 hash of the
+  /// serialized challenge. Custom signets share Bitcoin Core's `signet` data
+  /// directory, but ord keeps a separate index for each of them, named after
+  /// its magic.
+  pub(crate) fn custom_signet_magic(&self) -> Option<[u8; 4]> {
+    if self.chain() != Chain::Signet {
+      return None;
     }
+
+    let challenge = self.signet_challenge.as_ref()?;
+
+    let hash = sha256d::Hash::hash(&consensus::serialize(challenge)).to_byte_array();
+
+    Some([hash[0], hash[1], hash[2], hash[3]])
+  }
+
+  pub(crate) fn config_path(&self) -> Option<PathBuf> {
+    Config::path(self.config.as_deref(), self.config_dir.as_deref())
+  }
+
+  pub(crate) fn load_config(&self) -> Result<Config> {
+    Config::load(self.config_path().as_deref())
+  }
+
+  /// Inscriptions hidden in the config file or with `PUT
+  /// /admin/hidden/<INSCRIPTION_ID>`, which are stored in
+  /// `<DATA_DIR>/hidden.txt`.
+  pub(crate) fn hidden(&self) -> Result<Hidden> {
+    Hidden::load(
+      Arc::new(self.load_config()?),
+      self.data_dir().join("hidden.txt"),
+    )
   }
 
   fn derive_var(
@@ -209,7 +480,12 @@ impl Options {
   }
 
   pub(crate) fn bitcoin_rpc_client(&self, wallet: Option<String>) -> Result<Client> {
-    let rpc_url = self.rpc_url(wallet);
+    ensure!(
+      self.signet_challenge.is_none() || self.chain() == Chain::Signet,
+      "`--signet-challenge` requires `--chain signet`",
+    );
+
+    let rpc_url = self.rpc_url(wallet.clone());
 
     let auth = self.auth()?;
 
@@ -228,12 +504,35 @@ impl Options {
       );
     }
 
-    let client = Client::new(&rpc_url, auth)
+    let mut urls = vec![rpc_url.clone()];
+
+    urls.extend(
+      self
+        .rpc_failover_url
+        .iter()
+        .map(|url| Self::wallet_url(url, wallet.as_deref())),
+    );
+
+    let client = auth
+      .get_user_pass()
+      .map_err(Error::from)
+      .and_then(|(user, pass)| {
+        RpcTransport::new(
+          &urls,
+          user,
+          pass,
+          &self.rpc_method_limit,
+          self.rpc_retries.unwrap_or_default(),
+          Duration::from_millis(self.rpc_retry_delay.unwrap_or(500)),
+        )
+      })
+      .map(|transport| Client::from_jsonrpc(jsonrpc::Client::with_transport(transport)))
       .with_context(|| format!("failed to connect to Bitcoin Core RPC at {rpc_url}"))?;
 
     let rpc_chain = match client.get_blockchain_info()?.chain.as_str() {
       "main" => Chain::Mainnet,
       "test" => Chain::Testnet,
+      "testnet4" => Chain::Testnet4,
       "regtest" => Chain::Regtest,
       "signet" => Chain::Signet,
       other => bail!("Bitcoin RPC server on unknown chain: {other}"),
@@ -490,6 +789,14 @@ mod tests {
         "ord/testnet3"
       },
     );
+    check_network_alias(
+      "testnet4",
+      if cfg!(windows) {
+        r"ord\testnet4"
+      } else {
+        "ord/testnet4"
+      },
+    );
   }
 
   #[test]
@@ -513,6 +820,56 @@ mod tests {
     );
   }
 
+  #[test]
+  fn custom_signet() {
+    const DEFAULT_SIGNET_CHALLENGE: &str = "512103ad5e0edad18cb1f0fc0d28a3d4f1f3e445640337489abb10404f2d1e086be430210359ef5021964fe22d6f8e05b2463c9540ce96883fe3b278760f048f5189f2e6c452ae";
+
+    let options = Arguments::try_parse_from([
+      "ord",
+      "--chain",
+      "signet",
+      "--signet-challenge",
+      DEFAULT_SIGNET_CHALLENGE,
+      "--data-dir",
+      "foo",
+      "index",
+      "update",
+    ])
+    .unwrap()
+    .options;
+
+    assert_eq!(
+      options.custom_signet_magic(),
+      Some([0x0a, 0x03, 0xcf, 0x40])
+    );
+    assert_eq!(options.data_dir(), Path::new("foo").join("signet-0a03cf40"));
+
+    let params = options.network_params();
+    assert_eq!(params.first_inscription_height, 0);
+    assert_eq!(params.first_rune_height, 0);
+    assert_eq!(params.jubilee_height, 0);
+
+    let options = Arguments::try_parse_from([
+      "ord",
+      "--signet-challenge",
+      DEFAULT_SIGNET_CHALLENGE,
+      "index",
+      "update",
+    ])
+    .unwrap()
+    .options;
+
+    assert_eq!(options.custom_signet_magic(), None);
+    assert_eq!(
+      options.bitcoin_rpc_client(None).unwrap_err().to_string(),
+      "`--signet-challenge` requires `--chain signet`"
+    );
+
+    assert!(
+      Arguments::try_parse_from(["ord", "--signet-challenge", "foo", "index", "update"]).is_err()
+    );
+  }
+
   #[test]
   fn chain_flags() {
     Arguments::try_parse_from(["ord", "--signet", "--chain", "signet", "index", "update"])
@@ -14,7 +14,8 @@ impl FromStr for Outgoing {
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     lazy_static! {
       static ref SATPOINT: Regex = Regex::new(r"^[[:xdigit:]]{64}:\d+:\d+$").unwrap();
-      static ref INSCRIPTION_ID: Regex = Regex::new(r"^[[:xdigit:]]{64}i\d+$").unwrap();
+      static ref INSCRIPTION_ID: Regex =
+        Regex::new(r"^[[:xdigit:]]{64}i\d+(-[[:alnum:]]{6})?$").unwrap();
       static ref AMOUNT: Regex = Regex::new(
         r"(?x)
         ^
@@ -89,6 +90,11 @@ mod tests {
       ),
     );
 
+    case(
+      &format!("{:#}", inscription_id(1)),
+      Outgoing::InscriptionId(inscription_id(1)),
+    );
+
     case(
       "0000000000000000000000000000000000000000000000000000000000000000:0:0",
       Outgoing::SatPoint(
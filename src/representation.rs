@@ -24,9 +24,9 @@ impl Representation {
         Self::Decimal => r"^.*\..*$",
         Self::Degree => r"^.*°.*′.*″(.*‴)?$",
         Self::Hash => r"^[[:xdigit:]]{64}$",
-        Self::InscriptionId => r"^[[:xdigit:]]{64}i\d+$",
+        Self::InscriptionId => r"^[[:xdigit:]]{64}i\d+(-[[:alnum:]]{6})?$",
         Self::Integer => r"^[0-9]*$",
-        Self::Name => r"^[a-z]{1,11}$",
+        Self::Name => r"^[a-z]{1,11}(-[[:alnum:]]{6})?$",
         Self::OutPoint => r"^[[:xdigit:]]{64}:\d+$",
         Self::Percentile => r"^.*%$",
         Self::Rune => r"^[A-Z•.]+$",
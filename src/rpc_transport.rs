@@ -0,0 +1,261 @@
+use {
+  super::*,
+  bitcoincore_rpc::jsonrpc::{
+    self, simple_http::SimpleHttpTransport, Request, Response, Transport,
+  },
+  std::sync::{atomic::AtomicUsize, Condvar},
+};
+
+/// Bitcoin Core returns this error code while it is starting up, e.g. while
+/// loading the block index.
+const RPC_IN_WARMUP: i32 = -28;
+
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Maximum number of concurrent requests for an RPC method.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MethodLimit {
+  pub(crate) method: String,
+  pub(crate) limit: usize,
+}
+
+impl FromStr for MethodLimit {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let (method, limit) = s
+      .split_once('=')
+      .ok_or_else(|| anyhow!("method limit `{s}` must be of the form `<METHOD>=<LIMIT>`"))?;
+
+    let limit = limit.parse::<usize>()?;
+
+    ensure!(limit > 0, "method limit for `{method}` must be at least 1");
+
+    Ok(Self {
+      method: method.into(),
+      limit,
+    })
+  }
+}
+
+/// JSON-RPC transport for Bitcoin Core that retries requests that fail
+/// because the node is unreachable or starting up, fails over to other
+/// endpoints, and limits the number of concurrent requests per method.
+pub(crate) struct RpcTransport {
+  current: AtomicUsize,
+  endpoints: Vec<SimpleHttpTransport>,
+  in_flight: Mutex<HashMap<String, usize>>,
+  limits: HashMap<String, usize>,
+  released: Condvar,
+  retries: u32,
+  retry_delay: Duration,
+}
+
+/// Slot for a request to a rate-limited method, released on drop.
+struct Permit<'a> {
+  method: &'a str,
+  transport: &'a RpcTransport,
+}
+
+impl Drop for Permit<'_> {
+  fn drop(&mut self) {
+    let mut in_flight = self.transport.in_flight.lock().unwrap();
+    if let Some(count) = in_flight.get_mut(self.method) {
+      *count -= 1;
+    }
+    self.transport.released.notify_all();
+  }
+}
+
+impl RpcTransport {
+  pub(crate) fn new(
+    urls: &[String],
+    user: Option<String>,
+    pass: Option<String>,
+    limits: &[MethodLimit],
+    retries: u32,
+    retry_delay: Duration,
+  ) -> Result<Self> {
+    let endpoints = urls
+      .iter()
+      .map(|url| {
+        let mut builder = SimpleHttpTransport::builder().url(url)?;
+        if let Some(user) = &user {
+          builder = builder.auth(user.as_str(), pass.as_deref());
+        }
+        Ok(builder.build())
+      })
+      .collect::<Result<Vec<SimpleHttpTransport>>>()?;
+
+    ensure!(!endpoints.is_empty(), "no Bitcoin Core RPC endpoints");
+
+    Ok(Self {
+      current: AtomicUsize::new(0),
+      endpoints,
+      in_flight: Mutex::new(HashMap::new()),
+      limits: limits
+        .iter()
+        .map(|limit| (limit.method.clone(), limit.limit))
+        .collect(),
+      released: Condvar::new(),
+      retries,
+      retry_delay,
+    })
+  }
+
+  fn acquire<'a>(&'a self, method: &'a str) -> Option<Permit<'a>> {
+    let limit = *self.limits.get(method)?;
+
+    let mut in_flight = self.in_flight.lock().unwrap();
+
+    while in_flight.get(method).copied().unwrap_or_default() >= limit {
+      in_flight = self.released.wait(in_flight).unwrap();
+    }
+
+    *in_flight.entry(method.into()).or_default() += 1;
+
+    Some(Permit {
+      method,
+      transport: self,
+    })
+  }
+
+  fn is_transient<T>(result: &Result<T, jsonrpc::Error>, response_error: Option<i32>) -> bool {
+    matches!(result, Err(jsonrpc::Error::Transport(_))) || response_error == Some(RPC_IN_WARMUP)
+  }
+
+  fn retry_delay(&self, attempt: u32) -> Duration {
+    self
+      .retry_delay
+      .saturating_mul(2u32.saturating_pow(attempt))
+      .min(MAX_RETRY_DELAY)
+  }
+
+  fn send<T>(
+    &self,
+    send: impl Fn(&SimpleHttpTransport) -> Result<T, jsonrpc::Error>,
+    response_error: impl Fn(&T) -> Option<i32>,
+  ) -> Result<T, jsonrpc::Error> {
+    let mut attempt = 0;
+
+    loop {
+      let current = self.current.load(atomic::Ordering::Relaxed) % self.endpoints.len();
+
+      let result = send(&self.endpoints[current]);
+
+      let error = result.as_ref().ok().and_then(&response_error);
+
+      if attempt >= self.retries || !Self::is_transient(&result, error) {
+        return result;
+      }
+
+      let next = (current + 1) % self.endpoints.len();
+
+      if next != current {
+        self
+          .current
+          .compare_exchange(
+            current,
+            next,
+            atomic::Ordering::Relaxed,
+            atomic::Ordering::Relaxed,
+          )
+          .ok();
+      }
+
+      let delay = self.retry_delay(attempt);
+
+      log::warn!(
+        "Bitcoin Core RPC request failed, retrying with endpoint {next} in {}ms",
+        delay.as_millis(),
+      );
+
+      thread::sleep(delay);
+
+      attempt += 1;
+    }
+  }
+}
+
+impl Transport for RpcTransport {
+  fn send_request(&self, request: Request) -> Result<Response, jsonrpc::Error> {
+    let _permit = self.acquire(request.method);
+
+    self.send(
+      |endpoint| endpoint.send_request(request.clone()),
+      |response| response.error.as_ref().map(|error| error.code),
+    )
+  }
+
+  fn send_batch(&self, requests: &[Request]) -> Result<Vec<Response>, jsonrpc::Error> {
+    self.send(|endpoint| endpoint.send_batch(requests), |_| None)
+  }
+
+  fn fmt_target(&self, f: &mut Formatter) -> fmt::Result {
+    self.endpoints[self.current.load(atomic::Ordering::Relaxed) % self.endpoints.len()]
+      .fmt_target(f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn method_limit_from_str() {
+    assert_eq!(
+      "getblock=4".parse::<MethodLimit>().unwrap(),
+      MethodLimit {
+        method: "getblock".into(),
+        limit: 4,
+      }
+    );
+
+    assert_eq!(
+      "getblock".parse::<MethodLimit>().unwrap_err().to_string(),
+      "method limit `getblock` must be of the form `<METHOD>=<LIMIT>`"
+    );
+
+    assert_eq!(
+      "getblock=0".parse::<MethodLimit>().unwrap_err().to_string(),
+      "method limit for `getblock` must be at least 1"
+    );
+  }
+
+  #[test]
+  fn retry_delay_doubles_up_to_maximum() {
+    let transport = RpcTransport::new(
+      &["127.0.0.1:1".into()],
+      None,
+      None,
+      &[],
+      10,
+      Duration::from_millis(500),
+    )
+    .unwrap();
+
+    assert_eq!(transport.retry_delay(0), Duration::from_millis(500));
+    assert_eq!(transport.retry_delay(1), Duration::from_secs(1));
+    assert_eq!(transport.retry_delay(3), Duration::from_secs(4));
+    assert_eq!(transport.retry_delay(20), MAX_RETRY_DELAY);
+  }
+
+  #[test]
+  fn requests_fail_over_to_next_endpoint() {
+    let rpc_server = test_bitcoincore_rpc::spawn();
+
+    let transport = RpcTransport::new(
+      &["127.0.0.1:1".into(), rpc_server.url()],
+      None,
+      None,
+      &[],
+      1,
+      Duration::from_millis(1),
+    )
+    .unwrap();
+
+    let client = Client::from_jsonrpc(jsonrpc::Client::with_transport(transport));
+
+    assert_eq!(client.get_block_count().unwrap(), 0);
+  }
+}
@@ -2,7 +2,7 @@ use super::*;
 
 pub use {edict::Edict, rune::Rune, rune_id::RuneId, runestone::Runestone};
 
-pub(crate) use {etching::Etching, pile::Pile, spaced_rune::SpacedRune};
+pub(crate) use {etching::Etching, info::RuneInfo, pile::Pile, spaced_rune::SpacedRune};
 
 pub const MAX_DIVISIBILITY: u8 = 38;
 pub(crate) const CLAIM_BIT: u128 = 1 << 48;
@@ -11,6 +11,7 @@ const RESERVED: u128 = 6402364363415443603228541259936211926;
 
 mod edict;
 mod etching;
+mod info;
 mod pile;
 mod rune;
 mod rune_id;
@@ -22,7 +23,7 @@ type Result<T, E = Error> = std::result::Result<T, E>;
 
 #[cfg(test)]
 mod tests {
-  use {super::*, crate::index::testing::Context};
+  use {super::*, crate::index::testing::Context, proptest::prelude::*};
 
   const RUNE: u128 = 99246114928149462;
 
@@ -1271,6 +1272,76 @@ mod tests {
     );
   }
 
+  #[test]
+  fn verify_finds_no_rune_balance_issues_after_transfers_and_burns() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::max_value(),
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.verify(false).unwrap(), (Vec::new(), 0));
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: id.into(),
+            amount: 1000,
+            output: 1,
+          }],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.verify(false).unwrap(), (Vec::new(), 0));
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 1, Witness::new())],
+      op_return: Some(Runestone::default().encipher()),
+      outputs: 0,
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.runes().unwrap()[0].1.burned, 1000);
+
+    assert_eq!(context.index.verify(false).unwrap(), (Vec::new(), 0));
+  }
+
   #[test]
   fn unallocated_runes_are_assigned_to_default_output() {
     let context = Context::builder().arg("--index-runes").build();
@@ -5189,4 +5260,181 @@ mod tests {
       )],
     );
   }
+
+  /// Operations applied to the rune index by `rune_balances_are_conserved`.
+  /// Outputs and runes are chosen by index into the outputs holding runes and
+  /// the runes etched so far, wrapping around, and operations which refer to
+  /// outputs or runes when there are none are skipped.
+  #[derive(Clone, Debug)]
+  enum Operation {
+    Burn {
+      output: usize,
+    },
+    Etch {
+      limit: Option<u128>,
+      premine: u128,
+    },
+    Mint {
+      amount: u128,
+      rune: usize,
+    },
+    Transfer {
+      amount: u128,
+      output: usize,
+      split: bool,
+    },
+  }
+
+  fn amount() -> impl Strategy<Value = u128> {
+    prop_oneof![0..2000u128, Just(u128::MAX)]
+  }
+
+  fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+      any::<usize>().prop_map(|output| Operation::Burn { output }),
+      (proptest::option::of(1..2000u128), amount())
+        .prop_map(|(limit, premine)| Operation::Etch { limit, premine }),
+      (amount(), any::<usize>()).prop_map(|(amount, rune)| Operation::Mint { amount, rune }),
+      (amount(), any::<usize>(), any::<bool>()).prop_map(|(amount, output, split)| {
+        Operation::Transfer {
+          amount,
+          output,
+          split,
+        }
+      }),
+    ]
+  }
+
+  proptest! {
+    #![proptest_config(ProptestConfig {
+      cases: 16,
+      failure_persistence: None,
+      ..Default::default()
+    })]
+
+    #[test]
+    fn rune_balances_are_conserved(operations in proptest::collection::vec(operation(), 1..8)) {
+      let context = Context::builder().arg("--index-runes").build();
+
+      context.mine_blocks(1);
+
+      let mut burned = HashMap::<RuneId, u128>::new();
+      let mut etchings = 0;
+      let mut heights = HashMap::<Txid, usize>::new();
+      let mut tip = 1;
+
+      for operation in operations {
+        let balances = context.index.get_rune_balances().unwrap();
+        let runes = context.index.runes().unwrap();
+
+        let holder = |output: usize| {
+          let (outpoint, balances) = balances.get(output % balances.len().max(1))?;
+          Some((heights[&outpoint.txid], usize::try_from(outpoint.vout).unwrap(), balances))
+        };
+
+        let (inputs, runestone, outputs) = match operation {
+          Operation::Burn { output } => {
+            let Some((height, vout, balances)) = holder(output) else {
+              continue;
+            };
+
+            for (id, balance) in balances {
+              *burned.entry(*id).or_default() += balance;
+            }
+
+            (
+              (height, 1, vout),
+              Runestone {
+                burn: true,
+                ..Default::default()
+              },
+              1,
+            )
+          }
+          Operation::Etch { limit, premine } => {
+            etchings += 1;
+
+            (
+              (tip, 0, 0),
+              Runestone {
+                edicts: vec![Edict {
+                  id: 0,
+                  amount: premine,
+                  output: 0,
+                }],
+                etching: Some(Etching {
+                  limit,
+                  rune: Some(Rune(RUNE + etchings)),
+                  ..Default::default()
+                }),
+                ..Default::default()
+              },
+              1,
+            )
+          }
+          Operation::Mint { amount, rune } => {
+            let Some((id, _entry)) = runes.get(rune % runes.len().max(1)) else {
+              continue;
+            };
+
+            (
+              (tip, 0, 0),
+              Runestone {
+                edicts: vec![Edict {
+                  id: u128::from(*id) | CLAIM_BIT,
+                  amount,
+                  output: 0,
+                }],
+                ..Default::default()
+              },
+              1,
+            )
+          }
+          Operation::Transfer {
+            amount,
+            output,
+            split,
+          } => {
+            let Some((height, vout, balances)) = holder(output) else {
+              continue;
+            };
+
+            (
+              (height, 1, vout),
+              Runestone {
+                edicts: vec![Edict {
+                  id: balances[0].0.into(),
+                  amount,
+                  output: u128::from(split),
+                }],
+                ..Default::default()
+              },
+              2,
+            )
+          }
+        };
+
+        let (height, index, vout) = inputs;
+
+        let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+          inputs: &[(height, index, vout, Witness::new())],
+          op_return: Some(runestone.encipher()),
+          outputs,
+          ..Default::default()
+        });
+
+        context.mine_blocks(1);
+
+        tip += 1;
+
+        heights.insert(txid, tip);
+
+        prop_assert_eq!(context.index.verify(false).unwrap(), (Vec::new(), 0));
+
+        for (id, entry) in context.index.runes().unwrap() {
+          prop_assert_eq!(entry.burned, burned.get(&id).copied().unwrap_or_default());
+        }
+      }
+    }
+  }
 }
@@ -0,0 +1,110 @@
+use {super::*, reqwest::Url};
+
+/// Descriptive fields read from the CBOR metadata of a rune's etching
+/// inscription. Metadata must be a map with text keys. `name` and `website`
+/// must be text, `icon` must be the text form of an inscription ID, and
+/// `website` must be an `http` or `https` URL, which is stored normalized so
+/// that it can be used as a link. Unrecognized keys and invalid values are
+/// ignored.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct RuneInfo {
+  pub(crate) icon: Option<InscriptionId>,
+  pub(crate) name: Option<String>,
+  pub(crate) website: Option<String>,
+}
+
+impl RuneInfo {
+  pub(crate) fn from_inscription(inscription: &Inscription) -> Option<Self> {
+    let Value::Map(entries) = inscription.metadata()? else {
+      return None;
+    };
+
+    let mut info = Self::default();
+
+    for (key, value) in entries {
+      let (Value::Text(key), Value::Text(value)) = (key, value) else {
+        continue;
+      };
+
+      match key.as_str() {
+        "icon" => info.icon = value.parse().ok(),
+        "name" => info.name = Some(value),
+        "website" => {
+          info.website = value
+            .parse::<Url>()
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .map(|url| url.to_string())
+        }
+        _ => {}
+      }
+    }
+
+    (info != Self::default()).then_some(info)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn info(metadata: Value) -> Option<RuneInfo> {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&metadata, &mut cbor).unwrap();
+
+    RuneInfo::from_inscription(&Inscription {
+      metadata: Some(cbor),
+      ..Default::default()
+    })
+  }
+
+  fn text(s: &str) -> Value {
+    Value::Text(s.into())
+  }
+
+  #[test]
+  fn recognized_fields_are_parsed() {
+    assert_eq!(
+      info(Value::Map(vec![
+        (text("name"), text("Foo Coin")),
+        (text("icon"), text(&inscription_id(1).to_string())),
+        (text("website"), text("https://foo.com")),
+        (text("foo"), text("bar")),
+      ])),
+      Some(RuneInfo {
+        icon: Some(inscription_id(1)),
+        name: Some("Foo Coin".into()),
+        website: Some("https://foo.com/".into()),
+      })
+    );
+  }
+
+  #[test]
+  fn invalid_fields_are_ignored() {
+    assert_eq!(
+      info(Value::Map(vec![
+        (text("name"), Value::Integer(1.into())),
+        (text("icon"), text("foo")),
+        (text("website"), text("javascript:alert(1)")),
+      ])),
+      None,
+    );
+
+    assert_eq!(
+      info(Value::Map(vec![
+        (text("name"), text("Foo Coin")),
+        (text("website"), text("ftp://foo.com")),
+      ])),
+      Some(RuneInfo {
+        name: Some("Foo Coin".into()),
+        ..Default::default()
+      })
+    );
+  }
+
+  #[test]
+  fn metadata_must_be_a_map() {
+    assert_eq!(info(text("foo")), None);
+    assert_eq!(RuneInfo::from_inscription(&Inscription::default()), None);
+  }
+}
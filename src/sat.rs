@@ -84,7 +84,17 @@ impl Sat {
     name.chars().rev().collect()
   }
 
-  fn from_name(s: &str) -> Result<Self> {
+  /// Name followed by a checksum, as `<NAME>-<CHECKSUM>`, which is accepted
+  /// wherever sat names are.
+  pub(crate) fn checksummed_name(self) -> String {
+    checksum::append(&self.name())
+  }
+
+  pub(crate) fn from_name(s: &str) -> Result<Self> {
+    let s = checksum::strip(s).context("invalid sat name")?;
+
+    ensure!(!s.is_empty(), "sat name is empty");
+
     let mut x = 0;
     for c in s.chars() {
       match c {
@@ -558,6 +568,30 @@ mod tests {
     assert!(parse("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa").is_err());
   }
 
+  #[test]
+  fn from_name() {
+    assert_eq!(Sat::from_name("nvtdijuwxlp").unwrap(), 0);
+    assert_eq!(
+      Sat::from_name("").unwrap_err().to_string(),
+      "sat name is empty"
+    );
+    assert!(Sat::from_name("1").is_err());
+  }
+
+  #[test]
+  fn checksummed_name() {
+    let name = Sat(0).checksummed_name();
+
+    assert!(name.starts_with("nvtdijuwxlp-"));
+    assert_eq!(name.parse::<Sat>().unwrap(), 0);
+    assert_eq!(Sat::from_name(&name).unwrap(), 0);
+
+    let err = Sat::from_name(&name.replacen("nvt", "nvu", 1)).unwrap_err();
+
+    assert_eq!(err.to_string(), "invalid sat name");
+    assert!(err.downcast_ref::<checksum::ChecksumMismatch>().is_some());
+  }
+
   #[test]
   fn cycle() {
     assert_eq!(
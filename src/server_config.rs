@@ -1,11 +1,31 @@
-use super::*;
+use {super::*, crate::index::bucket::Bucket, bitcoin::secp256k1::KeyPair, clap::ValueEnum};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum ContentBucketMode {
+  Proxy,
+  Redirect,
+}
 
 #[derive(Default)]
 pub(crate) struct ServerConfig {
+  pub(crate) admin_token: Option<String>,
   pub(crate) chain: Chain,
+  pub(crate) content_bucket: Option<(Bucket, ContentBucketMode)>,
   pub(crate) csp_origin: Option<String>,
+  pub(crate) dashboard_token: Option<String>,
   pub(crate) decompress: bool,
+  pub(crate) decompress_limit: u64,
   pub(crate) domain: Option<String>,
+  pub(crate) export_token: Option<String>,
   pub(crate) index_sats: bool,
   pub(crate) is_json_api_enabled: bool,
+  pub(crate) query_timeout: Option<Duration>,
+  pub(crate) signing_key: Option<KeyPair>,
+}
+
+impl ServerConfig {
+  /// Deadline for the index scans of a request received now.
+  pub(crate) fn deadline(&self) -> Deadline {
+    Deadline::after(self.query_timeout)
+  }
 }
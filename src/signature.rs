@@ -0,0 +1,178 @@
+use {
+  super::*,
+  bitcoin::{
+    hashes::{sha256, Hash, HashEngine},
+    secp256k1::{schnorr, KeyPair, Message, Secp256k1, XOnlyPublicKey},
+  },
+};
+
+/// Response header holding the hex-encoded BIP-340 signature of the response.
+pub(crate) const SIGNATURE_HEADER: &str = "ord-signature";
+
+/// Response header holding the hex-encoded x-only public key of the operator
+/// key that signed the response.
+pub(crate) const PUBLIC_KEY_HEADER: &str = "ord-public-key";
+
+/// Tag of the digests of checkpoint responses, kept distinct from that of
+/// other responses so that a signed checkpoint can't be passed off as any
+/// other response, or vice versa.
+const CHECKPOINT_TAG: &str = "ord/checkpoint";
+
+const RESPONSE_TAG: &str = "ord/response";
+
+/// BIP-340 tagged hash of the request method, the path and query of the
+/// request, and the response body, each prefixed with its length as a
+/// big-endian u64, so that a signature can't be replayed as the response to
+/// a different request.
+fn message(method: &str, path_and_query: &str, body: &[u8]) -> Message {
+  let tag = sha256::Hash::hash(
+    if path_and_query.starts_with("/r/checkpoint/") {
+      CHECKPOINT_TAG
+    } else {
+      RESPONSE_TAG
+    }
+    .as_bytes(),
+  );
+
+  let mut engine = sha256::Hash::engine();
+  engine.input(tag.as_ref());
+  engine.input(tag.as_ref());
+
+  for field in [method.as_bytes(), path_and_query.as_bytes(), body] {
+    engine.input(&u64::try_from(field.len()).unwrap().to_be_bytes());
+    engine.input(field);
+  }
+
+  Message::from_slice(sha256::Hash::from_engine(engine).as_ref()).unwrap()
+}
+
+/// Load the operator key, a hex-encoded secp256k1 secret key, from `path`.
+pub(crate) fn load_key(path: &Path) -> Result<KeyPair> {
+  let key = fs::read_to_string(path)
+    .with_context(|| format!("failed to read signing key from `{}`", path.display()))?;
+
+  KeyPair::from_seckey_str(&Secp256k1::signing_only(), key.trim())
+    .with_context(|| format!("invalid signing key in `{}`", path.display()))
+}
+
+pub(crate) fn sign(
+  key: &KeyPair,
+  method: &str,
+  path_and_query: &str,
+  body: &[u8],
+) -> schnorr::Signature {
+  Secp256k1::signing_only().sign_schnorr(&message(method, path_and_query, body), key)
+}
+
+/// Verify that `signature`, taken from the `Ord-Signature` header of an ord
+/// server response, is a signature by `public_key` of `body` in response to a
+/// `method` request for `path_and_query`, e.g. `GET` and
+/// `/r/checkpoint/100`. `public_key` should be the operator key the client
+/// expects rather than the key in the `Ord-Public-Key` header.
+pub fn verify_response(
+  public_key: &str,
+  method: &str,
+  path_and_query: &str,
+  body: &[u8],
+  signature: &str,
+) -> Result {
+  let public_key = public_key
+    .parse::<XOnlyPublicKey>()
+    .with_context(|| format!("invalid public key `{public_key}`"))?;
+
+  let signature = signature
+    .parse::<schnorr::Signature>()
+    .with_context(|| format!("invalid signature `{signature}`"))?;
+
+  Secp256k1::verification_only()
+    .verify_schnorr(
+      &signature,
+      &message(method, path_and_query, body),
+      &public_key,
+    )
+    .context("response signature does not match request and body")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn signed_responses_verify() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("key");
+
+    fs::write(&path, format!("{}\n", "01".repeat(32))).unwrap();
+
+    let key = load_key(&path).unwrap();
+    let public_key = key.x_only_public_key().0.to_string();
+    let signature = sign(&key, "GET", "/r/blockhash/0", b"{}").to_string();
+
+    verify_response(&public_key, "GET", "/r/blockhash/0", b"{}", &signature).unwrap();
+
+    for (method, path_and_query, body) in [
+      ("GET", "/r/blockhash/0", b"[]".as_slice()),
+      ("GET", "/r/blockhash/1", b"{}"),
+      ("POST", "/r/blockhash/0", b"{}"),
+    ] {
+      assert_eq!(
+        verify_response(&public_key, method, path_and_query, body, &signature)
+          .unwrap_err()
+          .to_string(),
+        "response signature does not match request and body",
+      );
+    }
+  }
+
+  #[test]
+  fn checkpoints_are_signed_with_a_distinct_tag() {
+    let tagged = |tag: &str, path_and_query: &str| {
+      let tag = sha256::Hash::hash(tag.as_bytes());
+      Message::from_slice(
+        sha256::Hash::hash(
+          &[
+            tag.as_byte_array().as_slice(),
+            tag.as_byte_array(),
+            &3u64.to_be_bytes(),
+            b"GET".as_slice(),
+            &u64::try_from(path_and_query.len()).unwrap().to_be_bytes(),
+            path_and_query.as_bytes(),
+            &2u64.to_be_bytes(),
+            b"{}",
+          ]
+          .concat(),
+        )
+        .as_ref(),
+      )
+      .unwrap()
+    };
+
+    assert_eq!(
+      message("GET", "/r/blockhash/0", b"{}"),
+      tagged(RESPONSE_TAG, "/r/blockhash/0"),
+    );
+
+    assert_eq!(
+      message("GET", "/r/checkpoint/0", b"{}"),
+      tagged(CHECKPOINT_TAG, "/r/checkpoint/0"),
+    );
+
+    assert_ne!(
+      tagged(CHECKPOINT_TAG, "/r/checkpoint/0"),
+      tagged(RESPONSE_TAG, "/r/checkpoint/0"),
+    );
+  }
+
+  #[test]
+  fn invalid_keys_are_rejected() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("key");
+
+    fs::write(&path, "foo").unwrap();
+
+    assert_eq!(
+      load_key(&path).unwrap_err().to_string(),
+      format!("invalid signing key in `{}`", path.display()),
+    );
+  }
+}
@@ -1,18 +1,25 @@
 use super::*;
 
 pub mod balances;
+pub mod bench;
+pub mod brc20;
+pub mod config;
 pub mod decode;
 pub mod epochs;
+pub mod export;
 pub mod find;
 pub mod index;
 pub mod list;
 pub mod parse;
 mod preview;
+pub mod protocol;
+pub mod reconcile;
 pub mod runes;
 pub(crate) mod server;
 pub mod subsidy;
 pub mod supply;
 pub mod teleburn;
+pub mod test_fixture;
 pub mod traits;
 pub mod wallet;
 
@@ -20,10 +27,18 @@ pub mod wallet;
 pub(crate) enum Subcommand {
   #[command(about = "List all rune balances")]
   Balances,
+  #[command(about = "Measure protocol processing throughput on a block corpus")]
+  Bench(bench::Bench),
+  #[command(subcommand, about = "BRC-20 commands")]
+  Brc20(brc20::Brc20Subcommand),
+  #[command(subcommand, about = "Config commands")]
+  Config(config::ConfigSubcommand),
   #[command(about = "Decode a transaction")]
   Decode(decode::Decode),
   #[command(about = "List the first satoshis of each reward epoch")]
   Epochs,
+  #[command(about = "Export inscriptions or runes as CSV files for analysis")]
+  Export(export::Export),
   #[command(about = "Find a satoshi's current location")]
   Find(find::Find),
   #[command(subcommand, about = "Index commands")]
@@ -34,6 +49,10 @@ pub(crate) enum Subcommand {
   Parse(parse::Parse),
   #[command(about = "Run an explorer server populated with inscriptions")]
   Preview(preview::Preview),
+  #[command(subcommand, about = "Inspect protocol state tables")]
+  Protocol(protocol::ProtocolSubcommand),
+  #[command(about = "Compare inscriptions against another ord server")]
+  Reconcile(reconcile::Reconcile),
   #[command(about = "List all runes")]
   Runes,
   #[command(about = "Run the explorer server")]
@@ -44,6 +63,8 @@ pub(crate) enum Subcommand {
   Supply,
   #[command(about = "Generate teleburn addresses")]
   Teleburn(teleburn::Teleburn),
+  #[command(subcommand, about = "Generate test fixtures")]
+  TestFixture(test_fixture::TestFixtureSubcommand),
   #[command(about = "Display satoshi traits")]
   Traits(traits::Traits),
   #[command(about = "Wallet commands")]
@@ -54,23 +75,33 @@ impl Subcommand {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
     match self {
       Self::Balances => balances::run(options),
+      Self::Bench(bench) => bench.run(),
+      Self::Brc20(brc20) => brc20.run(options),
+      Self::Config(config) => config.run(options),
       Self::Decode(decode) => decode.run(options),
       Self::Epochs => epochs::run(),
+      Self::Export(export) => export.run(options),
       Self::Find(find) => find.run(options),
       Self::Index(index) => index.run(options),
       Self::List(list) => list.run(options),
       Self::Parse(parse) => parse.run(),
       Self::Preview(preview) => preview.run(),
+      Self::Protocol(protocol) => protocol.run(options),
+      Self::Reconcile(reconcile) => reconcile.run(options),
       Self::Runes => runes::run(options),
       Self::Server(server) => {
         let index = Arc::new(Index::open(&options)?);
         let handle = axum_server::Handle::new();
-        LISTENERS.lock().unwrap().push(handle.clone());
+        LISTENERS
+          .lock()
+          .unwrap()
+          .push((handle.clone(), server.drain_timeout()));
         server.run(options, index, handle)
       }
       Self::Subsidy(subsidy) => subsidy.run(),
       Self::Supply => supply::run(),
       Self::Teleburn(teleburn) => teleburn.run(),
+      Self::TestFixture(test_fixture) => test_fixture.run(options),
       Self::Traits(traits) => traits.run(),
       Self::Wallet(wallet) => wallet.run(options),
     }
@@ -0,0 +1,142 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Bench {
+  #[arg(help = "Process blocks in <CORPUS>, a directory of `<HEIGHT>.blk` files.")]
+  corpus: PathBuf,
+  #[arg(long, default_value = "1", help = "Process corpus <ITERATIONS> times.")]
+  iterations: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub blocks: u64,
+  pub blocks_per_second: f64,
+  pub elapsed: f64,
+  pub inscriptions: u64,
+  pub inscriptions_per_second: f64,
+  pub runestones: u64,
+  pub transactions: u64,
+  pub transactions_per_second: f64,
+}
+
+/// Protocol messages found in a block by [`process_block`].
+#[derive(Debug, Default, PartialEq)]
+pub struct BlockStats {
+  pub inscriptions: u64,
+  pub runestones: u64,
+  pub transactions: u64,
+}
+
+/// Parse the inscription envelopes and runestones in `block` and decode the
+/// inscription fields the indexer reads, without touching the database. Public
+/// so that the criterion benchmarks in `benches/` measure the same work as
+/// `ord bench`.
+pub fn process_block(block: &Block) -> BlockStats {
+  let mut stats = BlockStats::default();
+
+  for tx in &block.txdata {
+    stats.transactions += 1;
+
+    for envelope in ParsedEnvelope::from_transaction(tx) {
+      let inscription = &envelope.payload;
+      inscription.content_type();
+      inscription.delegate();
+      inscription.metadata();
+      inscription.metaprotocol();
+      inscription.parent();
+      inscription.pointer();
+      stats.inscriptions += 1;
+    }
+
+    if Runestone::from_transaction(tx).is_some() {
+      stats.runestones += 1;
+    }
+  }
+
+  stats
+}
+
+/// Load the blocks in `corpus`, which are consensus encoded and named by
+/// height, as written by `ord test-fixture`, in order of height.
+pub fn load_corpus(corpus: &Path) -> Result<Vec<Block>> {
+  let mut paths = Vec::new();
+
+  for entry in
+    fs::read_dir(corpus).with_context(|| format!("failed to read corpus `{}`", corpus.display()))?
+  {
+    let path = entry?.path();
+
+    if path.extension().and_then(|extension| extension.to_str()) != Some("blk") {
+      continue;
+    }
+
+    let height = path
+      .file_stem()
+      .and_then(|stem| stem.to_str())
+      .and_then(|stem| stem.parse::<u32>().ok())
+      .with_context(|| format!("corpus file `{}` is not named by height", path.display()))?;
+
+    paths.push((height, path));
+  }
+
+  ensure!(
+    !paths.is_empty(),
+    "no `.blk` files found in corpus `{}`",
+    corpus.display()
+  );
+
+  paths.sort();
+
+  paths
+    .into_iter()
+    .map(|(_, path)| {
+      consensus::encode::deserialize(&fs::read(&path)?)
+        .with_context(|| format!("failed to decode block `{}`", path.display()))
+    })
+    .collect()
+}
+
+impl Bench {
+  pub(crate) fn run(self) -> SubcommandResult {
+    ensure!(self.iterations > 0, "iterations must be greater than zero");
+
+    let blocks = load_corpus(&self.corpus)?;
+
+    let mut total = BlockStats::default();
+
+    let start = Instant::now();
+
+    for _ in 0..self.iterations {
+      for block in &blocks {
+        let stats = process_block(block);
+        total.inscriptions += stats.inscriptions;
+        total.runestones += stats.runestones;
+        total.transactions += stats.transactions;
+      }
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+
+    let blocks = u64::try_from(blocks.len())? * u64::from(self.iterations);
+
+    let per_second = |count: u64| {
+      if elapsed > 0.0 {
+        count as f64 / elapsed
+      } else {
+        0.0
+      }
+    };
+
+    Ok(Box::new(Output {
+      blocks,
+      blocks_per_second: per_second(blocks),
+      elapsed,
+      inscriptions: total.inscriptions,
+      inscriptions_per_second: per_second(total.inscriptions),
+      runestones: total.runestones,
+      transactions: total.transactions,
+      transactions_per_second: per_second(total.transactions),
+    }))
+  }
+}
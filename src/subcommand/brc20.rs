@@ -0,0 +1,63 @@
+use {super::*, crate::subcommand::wallet::brc20::Operation};
+
+#[derive(Debug, Parser)]
+pub(crate) enum Brc20Subcommand {
+  #[command(about = "Check a BRC-20 payload without inscribing it")]
+  Simulate(Simulate),
+}
+
+impl Brc20Subcommand {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self {
+      Self::Simulate(simulate) => simulate.run(options),
+    }
+  }
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Simulate {
+  #[arg(long, help = "Check the BRC-20 JSON payload in <PAYLOAD>.")]
+  payload: PathBuf,
+  #[arg(long, help = "Simulate inscribing the payload to <OWNER>.")]
+  owner: Address<NetworkUnchecked>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub accepted: bool,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub operation: Option<Operation>,
+  pub owner: String,
+}
+
+impl Simulate {
+  /// The index does not track BRC-20 token state, so payloads are only
+  /// checked against the rules that don't depend on it: that the payload is
+  /// a well-formed operation with a valid tick and amounts. Nothing is read
+  /// from or written to the index.
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let owner = options.network_params().require_network(self.owner)?;
+
+    let body = fs::read(&self.payload)
+      .with_context(|| format!("failed to read payload from `{}`", self.payload.display()))?;
+
+    let operation = Operation::from_inscription(&Inscription::new(
+      Some("text/plain;charset=utf-8".into()),
+      Some(body),
+    ));
+
+    let error = match &operation {
+      Some(operation) => operation.validate().err().map(|err| err.to_string()),
+      None => Some("payload is not a BRC-20 operation".into()),
+    };
+
+    Ok(Box::new(Output {
+      accepted: error.is_none(),
+      error,
+      operation,
+      owner: owner.to_string(),
+    }))
+  }
+}
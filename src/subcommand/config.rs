@@ -0,0 +1,38 @@
+use {super::*, clap::CommandFactory};
+
+pub use crate::config::{Section, Setting, Source};
+
+#[derive(Debug, Parser)]
+pub(crate) enum ConfigSubcommand {
+  #[command(about = "Validate the config file and environment variable settings")]
+  Check,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub config: Option<PathBuf>,
+  pub settings: Vec<Setting>,
+}
+
+impl ConfigSubcommand {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self {
+      Self::Check => {
+        let path = options.config_path();
+
+        let config = Config::load(path.as_deref())?;
+
+        let command = Arguments::command();
+
+        let settings = config.settings(&command, &Config::env())?;
+
+        Config::validate(&command, &settings)?;
+
+        Ok(Box::new(Output {
+          config: path,
+          settings,
+        }))
+      }
+    }
+  }
+}
@@ -1,5 +1,7 @@
 use super::*;
 
+pub mod tx;
+
 #[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
 pub struct CompactOutput {
   pub inscriptions: Vec<CompactInscription>,
@@ -57,7 +59,18 @@ impl TryFrom<Inscription> for CompactInscription {
 }
 
 #[derive(Debug, Parser)]
+pub(crate) enum DecodeSubcommand {
+  #[command(
+    about = "Decode a transaction's inscriptions and the protocol operations they would perform"
+  )]
+  Tx(tx::Tx),
+}
+
+#[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 pub(crate) struct Decode {
+  #[command(subcommand)]
+  subcommand: Option<DecodeSubcommand>,
   #[arg(
     long,
     conflicts_with = "file",
@@ -75,6 +88,10 @@ pub(crate) struct Decode {
 
 impl Decode {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    if let Some(DecodeSubcommand::Tx(tx)) = self.subcommand {
+      return tx.run(options);
+    }
+
     let transaction = if let Some(txid) = self.txid {
       options
         .bitcoin_rpc_client(None)?
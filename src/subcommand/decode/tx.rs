@@ -0,0 +1,147 @@
+use {
+  super::*, crate::subcommand::wallet::brc20::Operation, bitcoin::consensus::encode::deserialize,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Tx {
+  #[arg(
+    help = "Decode <TRANSACTION>, a hex-encoded raw transaction, or the txid of a transaction to fetch from Bitcoin Core."
+  )]
+  transaction: String,
+}
+
+#[derive(Serialize, PartialEq, Deserialize, Debug)]
+pub struct InscriptionOutput {
+  pub id: InscriptionId,
+  pub input: u32,
+  pub offset: u32,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub content_type: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub body: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub text: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub brc20: Option<Operation>,
+}
+
+#[derive(Serialize, PartialEq, Deserialize, Debug)]
+pub struct Output {
+  pub txid: Txid,
+  pub inscriptions: Vec<InscriptionOutput>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub runestone: Option<Runestone>,
+}
+
+impl Tx {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let transaction = if let Ok(txid) = self.transaction.parse::<Txid>() {
+      options
+        .bitcoin_rpc_client(None)?
+        .get_raw_transaction(&txid, None)?
+    } else {
+      deserialize::<Transaction>(
+        &hex::decode(self.transaction.trim()).context("transaction is not a txid or valid hex")?,
+      )
+      .context("failed to decode transaction")?
+    };
+
+    Ok(Box::new(Self::decode(&transaction)))
+  }
+
+  /// Decode the inscriptions and protocol operations in `transaction`, as
+  /// they would be interpreted if it were confirmed. BRC-20 operations are
+  /// only parsed, not checked against token state, so a confirmed operation
+  /// may still be rejected by BRC-20 indexers.
+  fn decode(transaction: &Transaction) -> Output {
+    let txid = transaction.txid();
+
+    let inscriptions = ParsedEnvelope::from_transaction(transaction)
+      .into_iter()
+      .enumerate()
+      .map(|(index, envelope)| {
+        let inscription = envelope.payload;
+
+        let text = inscription
+          .content_type()
+          .filter(|content_type| {
+            content_type.starts_with("text/") || content_type.starts_with("application/json")
+          })
+          .and(inscription.body())
+          .and_then(|body| std::str::from_utf8(body).ok())
+          .map(str::to_string);
+
+        InscriptionOutput {
+          id: InscriptionId {
+            txid,
+            index: index.try_into().unwrap(),
+          },
+          input: envelope.input,
+          offset: envelope.offset,
+          content_type: inscription.content_type().map(str::to_string),
+          body: inscription.body().map(hex::encode),
+          text,
+          brc20: Operation::from_inscription(&inscription),
+        }
+      })
+      .collect();
+
+    Output {
+      txid,
+      inscriptions,
+      runestone: Runestone::from_transaction(transaction),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn brc20_operations_are_decoded() {
+    let witness = inscription(
+      "text/plain;charset=utf-8",
+      r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#,
+    )
+    .to_witness();
+
+    let transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness,
+      }],
+      output: Vec::new(),
+    };
+
+    let output = Tx::decode(&transaction);
+
+    assert_eq!(output.runestone, None);
+    assert_eq!(output.inscriptions.len(), 1);
+
+    let inscription = &output.inscriptions[0];
+
+    assert_eq!(
+      inscription.id,
+      InscriptionId {
+        txid: transaction.txid(),
+        index: 0,
+      }
+    );
+    assert_eq!(
+      inscription.text.as_deref(),
+      Some(r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#),
+    );
+    assert_eq!(
+      inscription.brc20,
+      Some(Operation::Mint {
+        tick: "ordi".into(),
+        amt: "1000".into(),
+      }),
+    );
+  }
+}
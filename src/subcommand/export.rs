@@ -0,0 +1,245 @@
+use {super::*, clap::ValueEnum};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Dataset {
+  Inscriptions,
+  Runes,
+}
+
+impl Display for Dataset {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Inscriptions => write!(f, "inscriptions"),
+      Self::Runes => write!(f, "runes"),
+    }
+  }
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Export {
+  #[arg(value_enum, help = "Export <DATASET>.")]
+  dataset: Dataset,
+  #[arg(
+    long,
+    default_value = "10000",
+    value_parser = clap::value_parser!(u32).range(1..),
+    help = "Write one file for every <BLOCKS_PER_FILE> blocks."
+  )]
+  blocks_per_file: u32,
+  #[arg(
+    long,
+    help = "Write CSV files to <OUTPUT> directory, continuing from the last block exported to it."
+  )]
+  output: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub files: Vec<PathBuf>,
+  pub from: u32,
+  pub rows: u64,
+  pub to: u32,
+}
+
+const INSCRIPTION_COLUMNS: &[&str] = &[
+  "sequence_number",
+  "number",
+  "id",
+  "height",
+  "timestamp",
+  "fee",
+  "sat",
+  "charms",
+  "parent_sequence_number",
+  "location",
+];
+
+#[derive(Serialize)]
+struct InscriptionRow {
+  sequence_number: u32,
+  number: i32,
+  id: InscriptionId,
+  height: u32,
+  timestamp: u32,
+  fee: u64,
+  sat: Option<u64>,
+  charms: u16,
+  parent_sequence_number: Option<u32>,
+  location: SatPoint,
+}
+
+const RUNE_COLUMNS: &[&str] = &[
+  "id",
+  "number",
+  "rune",
+  "spacers",
+  "divisibility",
+  "symbol",
+  "etching",
+  "height",
+  "timestamp",
+  "supply",
+  "burned",
+  "mints",
+  "limit",
+  "end",
+  "deadline",
+];
+
+#[derive(Serialize)]
+struct RuneRow {
+  id: RuneId,
+  number: u64,
+  rune: Rune,
+  spacers: u32,
+  divisibility: u8,
+  symbol: Option<char>,
+  etching: Txid,
+  height: u32,
+  timestamp: u32,
+  supply: u128,
+  burned: u128,
+  mints: u64,
+  limit: Option<u128>,
+  end: Option<u32>,
+  deadline: Option<u32>,
+}
+
+impl Export {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    if let Dataset::Runes = self.dataset {
+      ensure!(
+        index.has_rune_index(),
+        "`ord export runes` requires index created with `--index-runes` flag",
+      );
+    }
+
+    index.update()?;
+
+    fs::create_dir_all(&self.output)
+      .with_context(|| format!("failed to create directory `{}`", self.output.display()))?;
+
+    let from = self.last_exported_height()?;
+    let to = index.block_count()?;
+
+    let mut files = Vec::new();
+    let mut rows = 0;
+    let mut start = from;
+
+    while start < to {
+      let end = ((start / self.blocks_per_file + 1) * self.blocks_per_file).min(to);
+
+      let path = self
+        .output
+        .join(format!("{}-{start:010}-{end:010}.csv", self.dataset));
+
+      // write to a temporary file first, so that an interrupted export does
+      // not leave a partial file behind that would be skipped on resume
+      let partial = path.with_extension("csv.partial");
+
+      let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_path(&partial)?;
+
+      match self.dataset {
+        Dataset::Inscriptions => {
+          writer.write_record(INSCRIPTION_COLUMNS)?;
+
+          for (entry, location) in index.inscriptions_in_block_range(start, end)? {
+            writer.serialize(InscriptionRow {
+              sequence_number: entry.sequence_number,
+              number: entry.inscription_number,
+              id: entry.id,
+              height: entry.height,
+              timestamp: entry.timestamp,
+              fee: entry.fee,
+              sat: entry.sat.map(|sat| sat.0),
+              charms: entry.charms,
+              parent_sequence_number: entry.parent,
+              location,
+            })?;
+            rows += 1;
+          }
+        }
+        Dataset::Runes => {
+          writer.write_record(RUNE_COLUMNS)?;
+
+          for (id, entry) in index.runes()? {
+            if !(start..end).contains(&id.height) {
+              continue;
+            }
+
+            writer.serialize(RuneRow {
+              id,
+              number: entry.number,
+              rune: entry.rune,
+              spacers: entry.spacers,
+              divisibility: entry.divisibility,
+              symbol: entry.symbol,
+              etching: entry.etching,
+              height: id.height,
+              timestamp: entry.timestamp,
+              supply: entry.supply,
+              burned: entry.burned,
+              mints: entry.mints,
+              limit: entry.limit,
+              end: entry.end,
+              deadline: entry.deadline,
+            })?;
+            rows += 1;
+          }
+        }
+      }
+
+      writer.flush()?;
+      drop(writer);
+
+      fs::rename(&partial, &path)?;
+
+      files.push(path);
+
+      start = end;
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+    }
+
+    Ok(Box::new(Output {
+      files,
+      from,
+      rows,
+      to: start,
+    }))
+  }
+
+  /// Height up to which blocks have already been exported, determined from the
+  /// names of files previously written to the output directory.
+  fn last_exported_height(&self) -> Result<u32> {
+    let prefix = format!("{}-", self.dataset);
+
+    let mut height = 0;
+
+    for entry in fs::read_dir(&self.output)? {
+      let name = entry?.file_name();
+
+      let Some(range) = name
+        .to_str()
+        .and_then(|name| name.strip_prefix(&prefix))
+        .and_then(|name| name.strip_suffix(".csv"))
+      else {
+        continue;
+      };
+
+      if let Some((_start, end)) = range.split_once('-') {
+        if let Ok(end) = end.parse::<u32>() {
+          height = height.max(end);
+        }
+      }
+    }
+
+    Ok(height)
+  }
+}
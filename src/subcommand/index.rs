@@ -1,25 +1,47 @@
 use super::*;
 
-mod export;
+pub mod backup;
+pub mod compact;
+pub mod export;
+pub mod import;
 pub mod info;
+pub mod stats;
 mod update;
+pub mod verify;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
-  #[command(about = "Write inscription numbers and ids to a tab-separated file")]
+  #[command(about = "Write a consistent copy of the index to a file")]
+  Backup(backup::Backup),
+  #[command(about = "Compact the index database, reclaiming unused space")]
+  Compact(compact::Compact),
+  #[command(
+    about = "Write inscription numbers and ids to a tab-separated file, or index deltas to a binary file"
+  )]
   Export(export::Export),
+  #[command(about = "Apply index deltas written by `ord index export --output`")]
+  Import(import::Import),
   #[command(about = "Print index statistics")]
   Info(info::Info),
+  #[command(about = "Print table entry counts and sizes, and their growth since the last run")]
+  Stats(stats::Stats),
   #[command(about = "Update the index", alias = "run")]
   Update,
+  #[command(about = "Check index tables for inconsistencies")]
+  Verify(verify::Verify),
 }
 
 impl IndexSubcommand {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
     match self {
+      Self::Backup(backup) => backup.run(options),
+      Self::Compact(compact) => compact.run(options),
       Self::Export(export) => export.run(options),
+      Self::Import(import) => import.run(options),
       Self::Info(info) => info.run(options),
+      Self::Stats(stats) => stats.run(options),
       Self::Update => update::run(options),
+      Self::Verify(verify) => verify.run(options),
     }
   }
 }
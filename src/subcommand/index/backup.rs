@@ -0,0 +1,75 @@
+use {
+  super::*,
+  bitcoin::hashes::{sha256, Hash},
+  std::io::Write,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Backup {
+  #[arg(long, help = "Write backup to <OUTPUT>.")]
+  output: PathBuf,
+  #[arg(long, help = "Compress backup with brotli.")]
+  compress: bool,
+}
+
+/// Written to `<OUTPUT>.manifest.json` next to the backup, to check its
+/// integrity before restoring it.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+  pub blocks_indexed: u32,
+  pub compressed: bool,
+  pub schema_version: u64,
+  pub sha256: String,
+  pub size: u64,
+  pub tables: BTreeMap<String, u64>,
+}
+
+impl Backup {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    ensure!(
+      !self.output.exists(),
+      "backup destination `{}` already exists",
+      self.output.display()
+    );
+
+    let backup = if self.compress {
+      let mut uncompressed = self.output.clone().into_os_string();
+      uncompressed.push(".uncompressed");
+      let uncompressed = PathBuf::from(uncompressed);
+
+      let backup = index.backup(&uncompressed)?;
+
+      let mut writer = brotli::CompressorWriter::new(File::create(&self.output)?, 4096, 9, 22);
+      io::copy(&mut File::open(&uncompressed)?, &mut writer)?;
+      writer.flush()?;
+      writer.into_inner().sync_all()?;
+
+      fs::remove_file(&uncompressed)?;
+
+      backup
+    } else {
+      index.backup(&self.output)?
+    };
+
+    let mut engine = sha256::Hash::engine();
+    io::copy(&mut File::open(&self.output)?, &mut engine)?;
+
+    let manifest = Manifest {
+      blocks_indexed: backup.blocks_indexed,
+      compressed: self.compress,
+      schema_version: backup.schema_version,
+      sha256: sha256::Hash::from_engine(engine).to_string(),
+      size: self.output.metadata()?.len(),
+      tables: backup.tables,
+    };
+
+    let mut path = self.output.into_os_string();
+    path.push(".manifest.json");
+
+    fs::write(path, serde_json::to_vec_pretty(&manifest)?)?;
+
+    Ok(Box::new(manifest))
+  }
+}
@@ -0,0 +1,25 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Compact {}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub after: u64,
+  pub before: u64,
+  pub compacted: bool,
+}
+
+impl Compact {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let mut index = Index::open(&options)?;
+
+    let (before, after, compacted) = index.compact()?;
+
+    Ok(Box::new(Output {
+      after,
+      before,
+      compacted,
+    }))
+  }
+}
@@ -1,11 +1,37 @@
-use super::*;
+use {super::*, std::io::Write};
 
 #[derive(Debug, Parser)]
+#[clap(
+  group = ArgGroup::new("destination")
+      .required(true)
+      .args(&["tsv", "output"]),
+)]
 pub(crate) struct Export {
-  #[arg(long, help = "Include addresses in export")]
+  #[arg(
+    long,
+    requires = "output",
+    help = "Export index deltas starting at block <FROM>."
+  )]
+  from: Option<u32>,
+  #[arg(long, conflicts_with = "output", help = "Include addresses in export")]
   include_addresses: bool,
+  #[arg(long, help = "Write binary index deltas to <OUTPUT>.")]
+  output: Option<PathBuf>,
+  #[arg(
+    long,
+    requires = "output",
+    help = "Export index deltas for blocks below <TO>. Defaults to the current block count."
+  )]
+  to: Option<u32>,
   #[arg(long, help = "Write export to <TSV>")]
-  tsv: String,
+  tsv: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub blocks: u32,
+  pub from: u32,
+  pub to: u32,
 }
 
 impl Export {
@@ -13,8 +39,34 @@ impl Export {
     let index = Index::open(&options)?;
 
     index.update()?;
-    index.export(&self.tsv, self.include_addresses)?;
 
-    Ok(Box::new(Empty {}))
+    match (self.tsv, self.output) {
+      (Some(tsv), None) => {
+        index.export(&tsv, self.include_addresses)?;
+        Ok(Box::new(Empty {}))
+      }
+      (None, Some(output)) => {
+        let from = self.from.unwrap_or(0);
+        let to = match self.to {
+          Some(to) => to,
+          None => index.block_count()?,
+        };
+
+        ensure!(from <= to, "--from {from} is greater than --to {to}");
+
+        let mut writer = io::BufWriter::new(File::create(&output)?);
+
+        let blocks = index.export_deltas(from, to, &mut writer)?;
+
+        writer.flush()?;
+
+        Ok(Box::new(Output {
+          blocks,
+          from,
+          to: from + blocks,
+        }))
+      }
+      _ => unreachable!(),
+    }
   }
 }
@@ -0,0 +1,26 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Import {
+  #[arg(long, help = "Read binary index deltas from <INPUT>.")]
+  input: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub blocks: u32,
+  pub height: Option<u32>,
+}
+
+impl Import {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    let blocks = index.import_deltas(&mut io::BufReader::new(File::open(&self.input)?))?;
+
+    Ok(Box::new(Output {
+      blocks,
+      height: index.block_height()?.map(|height| height.n()),
+    }))
+  }
+}
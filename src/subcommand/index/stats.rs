@@ -0,0 +1,126 @@
+use {super::*, crate::index::protocol::ProtocolTable};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Stats {
+  #[arg(
+    long,
+    help = "Report growth since the baseline in <BASELINE>, and replace it with the current table sizes. [default: <INDEX>.stats]"
+  )]
+  baseline: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableStats {
+  pub protocol: Option<String>,
+  pub entries: u64,
+  pub stored_bytes: u64,
+  pub overhead_bytes: u64,
+  pub total_bytes: u64,
+  pub entries_growth: Option<i64>,
+  pub total_bytes_growth: Option<i64>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub baseline_blocks_indexed: Option<u32>,
+  pub blocks_indexed: u32,
+  pub tables: BTreeMap<String, TableStats>,
+}
+
+/// Table sizes recorded by the previous run, which growth is measured
+/// against.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Baseline {
+  blocks_indexed: u32,
+  tables: BTreeMap<String, BaselineTable>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct BaselineTable {
+  entries: u64,
+  total_bytes: u64,
+}
+
+impl Stats {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let info = index.info()?;
+
+    let path = self.baseline.unwrap_or_else(|| {
+      let mut path = info.index_path.clone().into_os_string();
+      path.push(".stats");
+      path.into()
+    });
+
+    let baseline = if path.exists() {
+      Some(
+        serde_json::from_slice::<Baseline>(&fs::read(&path)?)
+          .with_context(|| format!("invalid baseline `{}`", path.display()))?,
+      )
+    } else {
+      None
+    };
+
+    let growth = |current: u64, previous: u64| -> Result<i64> {
+      Ok(i64::try_from(current)? - i64::try_from(previous)?)
+    };
+
+    let mut tables = BTreeMap::new();
+
+    for (name, table) in &info.tables {
+      let previous = baseline
+        .as_ref()
+        .and_then(|baseline| baseline.tables.get(name));
+
+      tables.insert(
+        name.clone(),
+        TableStats {
+          protocol: ProtocolTable::ALL
+            .into_iter()
+            .find(|protocol_table| protocol_table.name() == *name)
+            .map(|protocol_table| protocol_table.protocol().into()),
+          entries: table.entries,
+          stored_bytes: table.stored_bytes,
+          overhead_bytes: table.metadata_bytes + table.fragmented_bytes,
+          total_bytes: table.total_bytes,
+          entries_growth: previous
+            .map(|previous| growth(table.entries, previous.entries))
+            .transpose()?,
+          total_bytes_growth: previous
+            .map(|previous| growth(table.total_bytes, previous.total_bytes))
+            .transpose()?,
+        },
+      );
+    }
+
+    fs::write(
+      &path,
+      serde_json::to_vec(&Baseline {
+        blocks_indexed: info.blocks_indexed,
+        tables: info
+          .tables
+          .iter()
+          .map(|(name, table)| {
+            (
+              name.clone(),
+              BaselineTable {
+                entries: table.entries,
+                total_bytes: table.total_bytes,
+              },
+            )
+          })
+          .collect(),
+      })?,
+    )
+    .with_context(|| format!("failed to write baseline `{}`", path.display()))?;
+
+    Ok(Box::new(Output {
+      baseline_blocks_indexed: baseline.map(|baseline| baseline.blocks_indexed),
+      blocks_indexed: info.blocks_indexed,
+      tables,
+    }))
+  }
+}
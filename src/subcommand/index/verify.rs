@@ -0,0 +1,26 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Verify {
+  #[arg(
+    long,
+    help = "Repair inconsistencies in lookup tables that can be rebuilt from other tables."
+  )]
+  repair: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub issues: Vec<String>,
+  pub repaired: u64,
+}
+
+impl Verify {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    let (issues, repaired) = index.verify(self.repair)?;
+
+    Ok(Box::new(Output { issues, repaired }))
+  }
+}
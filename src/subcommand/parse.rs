@@ -2,18 +2,36 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Parse {
+  #[arg(
+    long,
+    help = "Print <OBJECT> followed by a checksum, which is checked when it is parsed. Only inscription IDs and sat names can be checksummed."
+  )]
+  checksum: bool,
   #[arg(help = "Parse <OBJECT>.")]
   object: Object,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub checksummed: Option<String>,
   pub object: Object,
 }
 
 impl Parse {
   pub(crate) fn run(self) -> SubcommandResult {
+    let checksummed = if self.checksum {
+      Some(match &self.object {
+        Object::InscriptionId(inscription_id) => format!("{inscription_id:#}"),
+        Object::Sat(sat) => sat.checksummed_name(),
+        _ => bail!("only inscription IDs and sat names can be checksummed"),
+      })
+    } else {
+      None
+    };
+
     Ok(Box::new(Output {
+      checksummed,
       object: self.object,
     }))
   }
@@ -86,7 +86,7 @@ impl Preview {
     super::wallet::create::Create {
       passphrase: "".into(),
     }
-    .run("ord".into(), options.clone())?;
+    .run("ord".into(), options.clone(), Default::default())?;
 
     let rpc_client = options.bitcoin_rpc_client(None)?;
 
@@ -103,26 +103,37 @@ impl Preview {
         Arguments {
           options: options.clone(),
           subcommand: Subcommand::Wallet(super::wallet::Wallet {
+            backend: Default::default(),
             name: "ord".into(),
-            subcommand: super::wallet::Subcommand::Inscribe(super::wallet::inscribe::Inscribe {
-              batch: None,
-              cbor_metadata: None,
-              commit_fee_rate: None,
-              compress: false,
-              destination: None,
-              dry_run: false,
-              fee_rate: FeeRate::try_from(1.0).unwrap(),
-              file: Some(file),
-              json_metadata: None,
-              metaprotocol: None,
-              no_backup: true,
-              no_limit: false,
-              parent: None,
-              postage: Some(TARGET_POSTAGE),
-              reinscribe: false,
-              satpoint: None,
-              sat: None,
-            }),
+            index_url: None,
+            postage: Default::default(),
+            subcommand: super::wallet::Subcommand::Inscribe(Box::new(
+              super::wallet::inscribe::Inscribe {
+                batch: None,
+                cbor_metadata: None,
+                commit_fee_rate: None,
+                compress: false,
+                confirmation_target: 6,
+                content_type: None,
+                destination: None,
+                dry_run: false,
+                fee_rate: FeeRateArg::Rate(FeeRate::try_from(1.0).unwrap()),
+                file: Some(file),
+                json_metadata: None,
+                metaprotocol: None,
+                no_backup: true,
+                no_limit: false,
+                parent: None,
+                postage: Some(TARGET_POSTAGE),
+                reinscribe: false,
+                satpoint: None,
+                sat: None,
+                signer: super::wallet::signer::Signer::Wallet,
+                stdin: false,
+                unsigned: false,
+                url: None,
+              },
+            )),
           }),
         }
         .run()?;
@@ -136,26 +147,37 @@ impl Preview {
         Arguments {
           options: options.clone(),
           subcommand: Subcommand::Wallet(super::wallet::Wallet {
+            backend: Default::default(),
             name: "ord".into(),
-            subcommand: super::wallet::Subcommand::Inscribe(super::wallet::inscribe::Inscribe {
-              batch: Some(batch),
-              cbor_metadata: None,
-              commit_fee_rate: None,
-              compress: false,
-              destination: None,
-              dry_run: false,
-              fee_rate: FeeRate::try_from(1.0).unwrap(),
-              file: None,
-              json_metadata: None,
-              metaprotocol: None,
-              no_backup: true,
-              no_limit: false,
-              parent: None,
-              postage: Some(TARGET_POSTAGE),
-              reinscribe: false,
-              satpoint: None,
-              sat: None,
-            }),
+            index_url: None,
+            postage: Default::default(),
+            subcommand: super::wallet::Subcommand::Inscribe(Box::new(
+              super::wallet::inscribe::Inscribe {
+                batch: Some(batch),
+                cbor_metadata: None,
+                commit_fee_rate: None,
+                compress: false,
+                confirmation_target: 6,
+                content_type: None,
+                destination: None,
+                dry_run: false,
+                fee_rate: FeeRateArg::Rate(FeeRate::try_from(1.0).unwrap()),
+                file: None,
+                json_metadata: None,
+                metaprotocol: None,
+                no_backup: true,
+                no_limit: false,
+                parent: None,
+                postage: Some(TARGET_POSTAGE),
+                reinscribe: false,
+                satpoint: None,
+                sat: None,
+                signer: super::wallet::signer::Signer::Wallet,
+                stdin: false,
+                unsigned: false,
+                url: None,
+              },
+            )),
           }),
         }
         .run()?;
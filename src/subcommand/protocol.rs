@@ -0,0 +1,107 @@
+use {
+  super::*,
+  crate::index::protocol::{Protocol, ProtocolTable},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) enum ProtocolSubcommand {
+  #[command(about = "List protocol state tables with entry counts and sizes")]
+  Tables,
+  #[command(about = "Print the values stored under a key in a protocol state table")]
+  Get(Get),
+  #[command(about = "Rebuild protocol state without reindexing inscriptions")]
+  Reindex(Reindex),
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Reindex {
+  #[arg(long, value_enum, help = "Rebuild state of <PROTOCOL>.")]
+  protocol: Protocol,
+  #[arg(
+    long,
+    help = "Resume an interrupted reindex at block <FROM>, the height it stopped at."
+  )]
+  from: Option<u32>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Get {
+  #[arg(value_enum, help = "Read from protocol state table <TABLE>.")]
+  table: ProtocolTable,
+  #[arg(help = "Print values stored under <KEY>.")]
+  key: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableOutput {
+  pub protocol: String,
+  pub table: String,
+  pub entries: u64,
+  pub stored_bytes: u64,
+  pub total_bytes: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReindexOutput {
+  pub protocol: String,
+  pub blocks: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetOutput {
+  pub table: String,
+  pub key: String,
+  pub values: Vec<String>,
+}
+
+impl ProtocolSubcommand {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    // reindexing replays every block up to the current block count, and
+    // updating isn't possible while a reindex is interrupted
+    if !matches!(self, Self::Reindex(_)) {
+      index.update()?;
+    }
+
+    match self {
+      Self::Tables => {
+        let mut tables = Vec::new();
+
+        for table in ProtocolTable::ALL {
+          let stats = index.protocol_table_stats(table)?;
+
+          tables.push(TableOutput {
+            protocol: table.protocol().into(),
+            table: table.name(),
+            entries: stats.entries,
+            stored_bytes: stats.stored_bytes,
+            total_bytes: stats.total_bytes,
+          });
+        }
+
+        Ok(Box::new(tables))
+      }
+      Self::Get(get) => {
+        let values = index.protocol_table_get(get.table, &get.key)?;
+
+        ensure!(
+          !values.is_empty(),
+          "key `{}` not found in table `{}`",
+          get.key,
+          get.table.name(),
+        );
+
+        Ok(Box::new(GetOutput {
+          table: get.table.name(),
+          key: get.key,
+          values,
+        }))
+      }
+      Self::Reindex(reindex) => Ok(Box::new(ReindexOutput {
+        protocol: format!("{:?}", reindex.protocol).to_lowercase(),
+        blocks: index.reindex_protocol(reindex.protocol, reindex.from)?,
+      })),
+    }
+  }
+}
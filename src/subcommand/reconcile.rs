@@ -0,0 +1,153 @@
+use {super::*, crate::templates::InscriptionsJson, reqwest::Url, serde::de::DeserializeOwned};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Reconcile {
+  #[arg(
+    long,
+    help = "Compare against the JSON API of the ord server at <AGAINST>, which must be run with `--enable-json-api`."
+  )]
+  against: Url,
+  #[arg(
+    long,
+    help = "Start at block <FROM>. [default: first inscription height]"
+  )]
+  from: Option<u32>,
+  #[arg(
+    long,
+    help = "Stop before block <TO>. [default: lower of local and remote block count]"
+  )]
+  to: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub discrepancies: Vec<Discrepancy>,
+  pub first_divergent_inscription: Option<InscriptionId>,
+  pub from: u32,
+  pub to: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Discrepancy {
+  pub first_divergent_inscription: InscriptionId,
+  pub height: u32,
+  pub missing_locally: Vec<InscriptionId>,
+  pub missing_remotely: Vec<InscriptionId>,
+}
+
+struct Remote {
+  client: reqwest::blocking::Client,
+  url: Url,
+}
+
+impl Remote {
+  fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+    let url = self.url.join(path)?;
+
+    let response = self
+      .client
+      .get(url.clone())
+      .header(reqwest::header::ACCEPT, "application/json")
+      .send()
+      .with_context(|| format!("failed to fetch {url}"))?;
+
+    ensure!(
+      response.status().is_success(),
+      "{url} returned {}",
+      response.status()
+    );
+
+    response
+      .json()
+      .with_context(|| format!("failed to deserialize response from {url}"))
+  }
+
+  fn inscriptions_in_block(&self, height: u32) -> Result<Vec<InscriptionId>> {
+    let mut inscriptions = Vec::new();
+
+    for page_index in 0.. {
+      let page =
+        self.get::<InscriptionsJson>(&format!("inscriptions/block/{height}/{page_index}"))?;
+
+      inscriptions.extend(page.inscriptions);
+
+      if !page.more {
+        break;
+      }
+    }
+
+    Ok(inscriptions)
+  }
+}
+
+impl Reconcile {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let mut url = self.against;
+
+    if !url.path().ends_with('/') {
+      url.set_path(&format!("{}/", url.path()));
+    }
+
+    let remote = Remote {
+      client: reqwest::blocking::Client::new(),
+      url,
+    };
+
+    let from = self
+      .from
+      .unwrap_or_else(|| options.network_params().first_inscription_height);
+
+    let to = match self.to {
+      Some(to) => to,
+      None => index.block_count()?.min(remote.get("blockcount")?),
+    };
+
+    let mut discrepancies = Vec::new();
+
+    for height in from..to {
+      let local = index.get_inscriptions_in_block(height)?;
+      let remote = remote.inscriptions_in_block(height)?;
+
+      if local == remote {
+        continue;
+      }
+
+      let first_divergent_inscription = local
+        .iter()
+        .zip(&remote)
+        .find(|(local, remote)| local != remote)
+        .map(|(local, _)| *local)
+        .or_else(|| local.get(remote.len()).copied())
+        .or_else(|| remote.get(local.len()).copied())
+        .unwrap();
+
+      discrepancies.push(Discrepancy {
+        first_divergent_inscription,
+        height,
+        missing_locally: remote
+          .iter()
+          .filter(|id| !local.contains(id))
+          .copied()
+          .collect(),
+        missing_remotely: local
+          .iter()
+          .filter(|id| !remote.contains(id))
+          .copied()
+          .collect(),
+      });
+    }
+
+    Ok(Box::new(Output {
+      first_divergent_inscription: discrepancies
+        .first()
+        .map(|discrepancy| discrepancy.first_divergent_inscription),
+      discrepancies,
+      from,
+      to,
+    }))
+  }
+}
@@ -7,24 +7,37 @@ use {
   },
   super::*,
   crate::{
-    server_config::ServerConfig,
+    index::{
+      activity::MAX_ACTIVITY_PAGE_SIZE,
+      bucket::Bucket,
+      recursion,
+      sort::{self, InscriptionSort, SortOrder},
+      InscriptionInfo,
+    },
+    server_config::{ContentBucketMode, ServerConfig},
+    signature,
+    teleburn::TargetChain,
     templates::{
-      BlockHtml, BlockJson, BlocksHtml, ChildrenHtml, ChildrenJson, ClockSvg, CollectionsHtml,
-      HomeHtml, InputHtml, InscriptionHtml, InscriptionJson, InscriptionsBlockHtml,
-      InscriptionsHtml, InscriptionsJson, OutputHtml, OutputJson, PageContent, PageHtml,
-      PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
+      ActivityJson, AddressChangeJson, AddressChangesJson, AddressRuneBalanceJson, AddressRuneJson,
+      AddressRunesJson, BlockFeesJson, BlockHtml, BlockJson, BlocksHtml, ChildrenHtml,
+      ChildrenJson, ClockSvg, CollectionHolderJson, CollectionHoldersJson, CollectionStatsJson,
+      CollectionsHtml, FeesJson, HomeHtml, InputHtml, InscriptionHtml, InscriptionJson,
+      InscriptionTransferJson, InscriptionTransfersJson, InscriptionsBlockHtml, InscriptionsHtml,
+      InscriptionsJson, OutputHtml, OutputJson, PageContent, PageHtml, PreviewAudioHtml,
+      PreviewCheckJson, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
       PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
-      RangeHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, SatInscriptionJson, SatInscriptionsJson,
-      SatJson, TransactionHtml,
+      RangeHtml, RareTxt, RuneHtml, RuneOutpointJson, RunesHtml, SatHtml, SatInscriptionJson,
+      SatInscriptionsJson, SatJson, SatSummaryJson, TransactionHtml,
     },
   },
   axum::{
     body,
     extract::{Extension, Json, Path, Query},
     headers::UserAgent,
-    http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode, Uri},
+    middleware::{self, Next},
     response::{IntoResponse, Redirect, Response},
-    routing::get,
+    routing::{delete, get, post, put},
     Router, TypedHeader,
   },
   axum_server::Handle,
@@ -36,7 +49,7 @@ use {
     caches::DirCache,
     AcmeConfig,
   },
-  std::{cmp::Ordering, io::Read, str, sync::Arc},
+  std::{cmp::Ordering, collections::hash_map, io::Read, net::SocketAddr, str, sync::Arc},
   tokio_stream::StreamExt,
   tower_http::{
     compression::CompressionLayer,
@@ -47,7 +60,17 @@ use {
 
 mod accept_encoding;
 mod accept_json;
+mod admin;
+mod dashboard;
+mod electrum;
 mod error;
+mod export;
+mod json_rpc;
+mod preflight;
+
+const DEFAULT_DECOMPRESS_LIMIT: u64 = 16_000_000;
+const MAX_FEES_BLOCKS: u32 = 10_000;
+const MAX_SAT_SUMMARIES: usize = 1000;
 
 #[derive(Copy, Clone)]
 pub(crate) enum InscriptionQuery {
@@ -104,6 +127,66 @@ struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct ActivityQuery {
+  cursor: Option<u64>,
+  limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct BalanceQuery {
+  height: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct FeesQuery {
+  from: Option<u32>,
+  to: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct InscriptionsQuery {
+  content_type: Option<String>,
+  order: Option<SortOrder>,
+  sort: Option<InscriptionSort>,
+}
+
+impl InscriptionsQuery {
+  /// Query string selecting the same listing, for use in pagination links.
+  fn to_query_string(&self) -> String {
+    let mut pairs = Vec::new();
+
+    if let Some(content_type) = &self.content_type {
+      let encoded = content_type
+        .bytes()
+        .map(|byte| {
+          if byte.is_ascii_alphanumeric() || b"-./_~".contains(&byte) {
+            char::from(byte).to_string()
+          } else {
+            format!("%{byte:02X}")
+          }
+        })
+        .collect::<String>();
+
+      pairs.push(format!("content_type={encoded}"));
+    }
+
+    if let Some(order) = self.order {
+      pairs.push(format!("order={order}"));
+    }
+
+    if let Some(sort) = self.sort {
+      pairs.push(format!("sort={sort}"));
+    }
+
+    if pairs.is_empty() {
+      String::new()
+    } else {
+      format!("?{}", pairs.join("&"))
+    }
+  }
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -125,23 +208,39 @@ impl Display for StaticHtml {
   }
 }
 
-#[derive(Debug, Parser)]
+#[derive(Clone, Debug, Parser)]
 pub(crate) struct Server {
   #[arg(
     long,
     help = "Listen on <ADDRESS> for incoming requests. [default: 0.0.0.0]"
   )]
   address: Option<String>,
+  #[arg(
+    long,
+    value_enum,
+    help = "Also index and serve <ALSO_CHAIN> in the same process, using its own data directory and Bitcoin Core RPC port. The nth additional chain is served over HTTP on <HTTP_PORT> + n. May be given multiple times."
+  )]
+  also_chain: Vec<Chain>,
   #[arg(
     long,
     help = "Request ACME TLS certificate for <ACME_DOMAIN>. This ord instance must be reachable at <ACME_DOMAIN>:443 to respond to Let's Encrypt ACME challenges."
   )]
   acme_domain: Vec<String>,
+  #[arg(
+    long,
+    help = "Serve `/admin` endpoints to clients that send header `Authorization: Bearer <ADMIN_TOKEN>`. `/admin` endpoints are disabled if not set."
+  )]
+  pub(crate) admin_token: Option<String>,
   #[arg(
     long,
     help = "Use <CSP_ORIGIN> in Content-Security-Policy header. Set this to the public-facing URL of your ord instance."
   )]
   csp_origin: Option<String>,
+  #[arg(
+    long,
+    help = "Serve `/dashboard` to clients that authenticate with HTTP basic auth using password <DASHBOARD_TOKEN>. `/dashboard` is disabled if not set."
+  )]
+  pub(crate) dashboard_token: Option<String>,
   #[arg(
     long,
     help = "Listen on <HTTP_PORT> for incoming HTTP requests. [default: 80]"
@@ -167,16 +266,99 @@ pub(crate) struct Server {
   pub(crate) enable_json_api: bool,
   #[arg(
     long,
-    help = "Decompress encoded content. Currently only supports brotli. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector."
+    help = "Also serve requests on unix socket <JSON_RPC_SOCKET>, e.g. for JSON-RPC clients on the same host. Requires `--enable-json-api` for `/rpc`."
+  )]
+  pub(crate) json_rpc_socket: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Decompress encoded content for clients that don't accept its encoding, like browsers loading recursive content over plain HTTP. Currently only supports brotli. Decompressed content is limited by `--decompress-limit`."
   )]
   pub(crate) decompress: bool,
+  #[arg(
+    long,
+    requires = "decompress",
+    help = "Decompress at most <DECOMPRESS_LIMIT> bytes of encoded content. Content which decompresses to more is only served to clients that accept its encoding. [default: 16000000]"
+  )]
+  pub(crate) decompress_limit: Option<u64>,
+  #[arg(
+    long,
+    help = "Listen on <ELECTRUM_PORT> for Electrum-style subscriptions to inscriptions sent and received by addresses."
+  )]
+  pub(crate) electrum_port: Option<u16>,
+  #[arg(
+    long,
+    help = "Serve `/export/blocks` to clients that send header `Authorization: Bearer <EXPORT_TOKEN>`. `/export/blocks` is disabled if not set."
+  )]
+  pub(crate) export_token: Option<String>,
+  #[arg(
+    long,
+    help = "Sign JSON responses with the hex-encoded secp256k1 secret key in <SIGNING_KEY_FILE>. BIP-340 signatures of response bodies are sent in the `Ord-Signature` header, and the public key in the `Ord-Public-Key` header."
+  )]
+  pub(crate) signing_key_file: Option<PathBuf>,
+  #[arg(
+    long,
+    value_enum,
+    help = "Serve `/content` from `--content-bucket` using <CONTENT_BUCKET_MODE>. `proxy` fetches content from the bucket, `redirect` redirects clients to it and requires a publicly readable bucket. Content that has not been uploaded yet is served from the index."
+  )]
+  pub(crate) content_bucket_mode: Option<ContentBucketMode>,
+  #[arg(
+    long,
+    help = "Update the index as soon as Bitcoin Core publishes a new block on ZMQ <ZMQ_RAWBLOCK> endpoint, e.g. `tcp://127.0.0.1:28332`. Requires Bitcoin Core to be started with `-zmqpubrawblock`. Polling every 5 seconds continues as a fallback."
+  )]
+  pub(crate) zmq_rawblock: Option<String>,
+  #[arg(
+    long,
+    default_value = "5",
+    help = "Wait up to <DRAIN_TIMEOUT> seconds for open connections to finish when shutting down."
+  )]
+  pub(crate) drain_timeout: u64,
+  #[arg(
+    long,
+    help = "Abandon index scans of API requests after <QUERY_TIMEOUT> milliseconds, responding with 503 Service Unavailable. Applies to paginated collection and holder listings."
+  )]
+  pub(crate) query_timeout: Option<u64>,
 }
 
 impl Server {
+  pub(crate) fn drain_timeout(&self) -> Duration {
+    Duration::from_secs(self.drain_timeout)
+  }
+
   pub(crate) fn run(self, options: Options, index: Arc<Index>, handle: Handle) -> SubcommandResult {
+    let content_bucket = match self.content_bucket_mode {
+      Some(mode) => Some((
+        options
+          .content_bucket()?
+          .ok_or_else(|| anyhow!("`--content-bucket-mode` requires `--content-bucket`"))?,
+        mode,
+      )),
+      None => None,
+    };
+
+    for (n, chain) in self.also_chain.iter().enumerate() {
+      self.spawn_chain(&options, *chain, u16::try_from(n)? + 1)?;
+    }
+
     Runtime::new()?.block_on(async {
+      if let Some(electrum_port) = self.electrum_port {
+        electrum::Electrum::spawn(
+          index.clone(),
+          options.chain(),
+          self.socket_addr(electrum_port)?,
+        )?;
+      }
+
+      if self.dashboard_token.is_some() {
+        index.metrics().enable();
+      }
+
       let index_clone = index.clone();
 
+      let block_notifications = self
+        .zmq_rawblock
+        .clone()
+        .map(crate::index::zmq::block_notifications);
+
       let index_thread = thread::spawn(move || loop {
         if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
           break;
@@ -184,24 +366,62 @@ impl Server {
         if let Err(error) = index_clone.update() {
           log::warn!("Updating index: {error}");
         }
-        thread::sleep(Duration::from_millis(5000));
+        match &block_notifications {
+          Some(block_notifications) => {
+            block_notifications
+              .recv_timeout(Duration::from_millis(5000))
+              .ok();
+          }
+          None => thread::sleep(Duration::from_millis(5000)),
+        }
       });
-      INDEXER.lock().unwrap().replace(index_thread);
+      INDEXERS.lock().unwrap().push(index_thread);
 
-      let config = Arc::new(options.load_config()?);
+      let hidden = Arc::new(options.hidden()?);
       let acme_domains = self.acme_domains()?;
 
       let server_config = Arc::new(ServerConfig {
+        admin_token: self.admin_token.clone(),
         chain: options.chain(),
+        content_bucket,
         csp_origin: self.csp_origin.clone(),
+        dashboard_token: self.dashboard_token.clone(),
         domain: acme_domains.first().cloned(),
+        export_token: self.export_token.clone(),
         index_sats: index.has_sat_index(),
         is_json_api_enabled: self.enable_json_api,
+        query_timeout: self.query_timeout.map(Duration::from_millis),
         decompress: self.decompress,
+        decompress_limit: self.decompress_limit.unwrap_or(DEFAULT_DECOMPRESS_LIMIT),
+        signing_key: self
+          .signing_key_file
+          .as_deref()
+          .map(signature::load_key)
+          .transpose()?,
       });
 
       let router = Router::new()
         .route("/", get(Self::home))
+        .route("/activity/:address", get(Self::activity))
+        .route("/address-changes/:height", get(Self::address_changes))
+        .route("/address/:address/runes", get(Self::address_runes))
+        .route(
+          "/address/:address/runes/:rune",
+          get(Self::address_rune_balance),
+        )
+        .route("/admin/hidden", get(admin::hidden))
+        .route(
+          "/admin/hidden/:inscription_id",
+          put(admin::hide).delete(admin::unhide),
+        )
+        .route(
+          "/admin/subscriptions",
+          get(admin::subscriptions).post(admin::subscribe),
+        )
+        .route("/admin/subscriptions/:id", delete(admin::unsubscribe))
+        .route("/admin/watches", get(admin::watches).post(admin::watch))
+        .route("/admin/watches/:id", delete(admin::unwatch))
+        .route("/analytics/fees", get(Self::fees))
         .route("/block/:query", get(Self::block))
         .route("/blockcount", get(Self::block_count))
         .route("/blockhash", get(Self::block_hash))
@@ -216,14 +436,32 @@ impl Server {
           get(Self::children_paginated),
         )
         .route("/clock", get(Self::clock))
+        .route(
+          "/collection/:inscription_id/holders",
+          get(Self::collection_holders),
+        )
+        .route(
+          "/collection/:inscription_id/holders/:page",
+          get(Self::collection_holders_paginated),
+        )
+        .route(
+          "/collection/:inscription_id/stats",
+          get(Self::collection_stats),
+        )
         .route("/collections", get(Self::collections))
         .route("/collections/:page", get(Self::collections_paginated))
         .route("/content/:inscription_id", get(Self::content))
+        .route("/dashboard", get(dashboard::dashboard))
+        .route("/export/blocks", get(export::blocks))
         .route("/faq", get(Self::faq))
         .route("/favicon.ico", get(Self::favicon))
         .route("/feed.xml", get(Self::feed))
         .route("/input/:block/:transaction/:input", get(Self::input))
         .route("/inscription/:inscription_query", get(Self::inscription))
+        .route(
+          "/inscription/:inscription_query/transfers",
+          get(Self::inscription_transfers),
+        )
         .route("/inscriptions", get(Self::inscriptions))
         .route("/inscriptions/:page", get(Self::inscriptions_paginated))
         .route(
@@ -238,6 +476,7 @@ impl Server {
         .route("/ordinal/:sat", get(Self::ordinal))
         .route("/output/:output", get(Self::output))
         .route("/preview/:inscription_id", get(Self::preview))
+        .route("/preview/:inscription_id/check", get(preflight::check))
         .route("/r/blockhash", get(Self::block_hash_json))
         .route(
           "/r/blockhash/:height",
@@ -245,6 +484,7 @@ impl Server {
         )
         .route("/r/blockheight", get(Self::block_height))
         .route("/r/blocktime", get(Self::block_time))
+        .route("/r/checkpoint/:height", get(export::checkpoint))
         .route("/r/children/:inscription_id", get(Self::children_recursive))
         .route(
           "/r/children/:inscription_id/:page",
@@ -260,19 +500,24 @@ impl Server {
           "/r/sat/:sat_number/at/:index",
           get(Self::sat_inscription_at_index),
         )
+        .route("/r/teleburn/:chain/:asset", get(Self::teleburn_claims))
         .route("/range/:start/:end", get(Self::range))
         .route("/rare.txt", get(Self::rare_txt))
+        .route("/rpc", post(json_rpc::handler))
         .route("/rune/:rune", get(Self::rune))
         .route("/runes", get(Self::runes))
         .route("/sat/:sat", get(Self::sat))
+        .route("/sats", post(Self::sats))
+        .route("/sats/by-name", post(Self::sats_by_name))
         .route("/search", get(Self::search_by_query))
         .route("/search/*query", get(Self::search_by_path))
         .route("/static/*path", get(Self::static_asset))
         .route("/status", get(Self::status))
         .route("/tx/:txid", get(Self::transaction))
+        .layer(middleware::from_fn(Self::sign_response))
         .layer(Extension(index))
         .layer(Extension(server_config.clone()))
-        .layer(Extension(config))
+        .layer(Extension(hidden))
         .layer(SetResponseHeaderLayer::if_not_present(
           header::CONTENT_SECURITY_POLICY,
           HeaderValue::from_static("default-src 'self'"),
@@ -283,12 +528,16 @@ impl Server {
         ))
         .layer(
           CorsLayer::new()
-            .allow_methods([http::Method::GET])
+            .allow_methods([http::Method::GET, http::Method::POST])
             .allow_origin(Any),
         )
         .layer(CompressionLayer::new())
         .with_state(server_config);
 
+      if let Some(path) = &self.json_rpc_socket {
+        Self::spawn_unix(router.clone(), path)?;
+      }
+
       match (self.http_port(), self.https_port()) {
         (Some(http_port), None) => {
           self
@@ -334,6 +583,170 @@ impl Server {
     })
   }
 
+  /// Sign JSON responses with `--signing-key-file`, together with the method,
+  /// path, and query of the request they answer. Responses are signed before
+  /// compression, so signatures cover the bytes clients see after decoding
+  /// the response.
+  async fn sign_response<B>(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    request: Request<B>,
+    next: Next<B>,
+  ) -> Response {
+    let method = request.method().clone();
+
+    let path_and_query = request
+      .uri()
+      .path_and_query()
+      .map(|path_and_query| path_and_query.as_str().to_owned())
+      .unwrap_or_else(|| request.uri().path().into());
+
+    let response = next.run(request).await;
+
+    let Some(key) = server_config.signing_key else {
+      return response;
+    };
+
+    let is_json = response
+      .headers()
+      .get(header::CONTENT_TYPE)
+      .map(|content_type| content_type.as_bytes().starts_with(b"application/json"))
+      .unwrap_or_default();
+
+    if !is_json {
+      return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+
+    let bytes = match hyper::body::to_bytes(body).await {
+      Ok(bytes) => bytes,
+      Err(err) => return ServerError::Internal(anyhow!(err)).into_response(),
+    };
+
+    parts.headers.insert(
+      signature::SIGNATURE_HEADER,
+      HeaderValue::from_str(
+        &signature::sign(&key, method.as_str(), &path_and_query, &bytes).to_string(),
+      )
+      .unwrap(),
+    );
+
+    parts.headers.insert(
+      signature::PUBLIC_KEY_HEADER,
+      HeaderValue::from_str(&key.x_only_public_key().0.to_string()).unwrap(),
+    );
+
+    Response::from_parts(parts, body::boxed(body::Full::from(bytes)))
+  }
+
+  #[cfg(unix)]
+  fn spawn_unix(router: Router, path: &std::path::Path) -> Result {
+    use std::os::unix::fs::FileTypeExt;
+
+    if let Ok(metadata) = fs::symlink_metadata(path) {
+      ensure!(
+        metadata.file_type().is_socket(),
+        "refusing to replace `{}`, which is not a socket",
+        path.display()
+      );
+      fs::remove_file(path)?;
+    }
+
+    let listener = tokio::net::UnixListener::bind(path)
+      .with_context(|| format!("failed to bind unix socket `{}`", path.display()))?;
+
+    let incoming = hyper::server::accept::poll_fn(move |cx| {
+      listener
+        .poll_accept(cx)
+        .map(|result| Some(result.map(|(stream, _)| stream)))
+    });
+
+    tokio::spawn(async move {
+      if let Err(err) = axum::Server::builder(incoming)
+        .serve(router.into_make_service())
+        .await
+      {
+        log::error!("unix socket server failed: {err}");
+      }
+    });
+
+    Ok(())
+  }
+
+  #[cfg(not(unix))]
+  fn spawn_unix(_router: Router, _path: &std::path::Path) -> Result {
+    bail!("`--json-rpc-socket` is only supported on unix");
+  }
+
+  /// Options for serving `chain` alongside the primary chain. Settings which
+  /// point at resources of the primary chain, such as its node, data files,
+  /// and event sinks, are cleared, so that the additional chain doesn't
+  /// publish its events to the primary chain's subscribers.
+  fn chain_options(options: &Options, chain: Chain) -> Options {
+    Options {
+      block_archive_url: None,
+      chain_argument: chain,
+      content_bucket: None,
+      cookie_file: None,
+      index: None,
+      ipfs_api_url: None,
+      nats_url: None,
+      nostr_relay: Vec::new(),
+      postgres_url: None,
+      regtest: false,
+      rpc_failover_url: Vec::new(),
+      rpc_url: None,
+      signet: false,
+      testnet: false,
+      ..options.clone()
+    }
+  }
+
+  fn spawn_chain(&self, options: &Options, chain: Chain, port_offset: u16) -> Result {
+    ensure!(
+      chain != options.chain() && self.also_chain.iter().filter(|c| **c == chain).count() == 1,
+      "chain {chain} may only be served once"
+    );
+
+    ensure!(
+      self.https_port().is_none(),
+      "additional chains can only be served over HTTP"
+    );
+
+    let http_port = self
+      .http_port()
+      .unwrap()
+      .checked_add(port_offset)
+      .ok_or_else(|| anyhow!("HTTP port for chain {chain} out of range"))?;
+
+    let options = Self::chain_options(options, chain);
+
+    let server = Server {
+      also_chain: Vec::new(),
+      content_bucket_mode: None,
+      electrum_port: None,
+      http_port: Some(http_port),
+      json_rpc_socket: None,
+      zmq_rawblock: None,
+      ..self.clone()
+    };
+
+    let index = Arc::new(Index::open(&options)?);
+    let handle = Handle::new();
+    LISTENERS
+      .lock()
+      .unwrap()
+      .push((handle.clone(), server.drain_timeout()));
+
+    thread::spawn(move || {
+      if let Err(err) = server.run(options, index, handle) {
+        log::error!("{chain} server failed: {err}");
+      }
+    });
+
+    Ok(())
+  }
+
   fn spawn(
     &self,
     router: Router,
@@ -341,21 +754,7 @@ impl Server {
     port: u16,
     config: SpawnConfig,
   ) -> Result<task::JoinHandle<io::Result<()>>> {
-    let address = match &self.address {
-      Some(address) => address.as_str(),
-      None => {
-        if cfg!(test) || integration_test() {
-          "127.0.0.1"
-        } else {
-          "0.0.0.0"
-        }
-      }
-    };
-
-    let addr = (address, port)
-      .to_socket_addrs()?
-      .next()
-      .ok_or_else(|| anyhow!("failed to get socket addrs"))?;
+    let addr = self.socket_addr(port)?;
 
     if !integration_test() {
       eprintln!(
@@ -397,6 +796,24 @@ impl Server {
     }))
   }
 
+  fn socket_addr(&self, port: u16) -> Result<SocketAddr> {
+    let address = match &self.address {
+      Some(address) => address.as_str(),
+      None => {
+        if cfg!(test) || integration_test() {
+          "127.0.0.1"
+        } else {
+          "0.0.0.0"
+        }
+      }
+    };
+
+    (address, port)
+      .to_socket_addrs()?
+      .next()
+      .ok_or_else(|| anyhow!("failed to get socket addrs"))
+  }
+
   fn acme_cache(acme_cache: Option<&PathBuf>, options: &Options) -> PathBuf {
     acme_cache
       .unwrap_or(&options.data_dir().join("acme-cache"))
@@ -488,14 +905,7 @@ impl Server {
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
     let inscriptions = index.get_inscription_ids_by_sat(sat)?;
-    let satpoint = index.rare_sat_satpoint(sat)?.or_else(|| {
-      inscriptions.first().and_then(|&first_inscription_id| {
-        index
-          .get_inscription_satpoint_by_id(first_inscription_id)
-          .ok()
-          .flatten()
-      })
-    });
+    let satpoint = Self::sat_satpoint(&index, sat, &inscriptions)?;
     let blocktime = index.block_time(sat.height())?;
     Ok(if accept_json {
       Json(SatJson {
@@ -527,6 +937,78 @@ impl Server {
     })
   }
 
+  /// Location of `sat`, if it is rare and sats are indexed, or if it is
+  /// inscribed.
+  fn sat_satpoint(
+    index: &Index,
+    sat: Sat,
+    inscriptions: &[InscriptionId],
+  ) -> Result<Option<SatPoint>> {
+    Ok(index.rare_sat_satpoint(sat)?.or_else(|| {
+      inscriptions.first().and_then(|&first_inscription_id| {
+        index
+          .get_inscription_satpoint_by_id(first_inscription_id)
+          .ok()
+          .flatten()
+      })
+    }))
+  }
+
+  async fn sats(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Json(queries): Json<Vec<String>>,
+  ) -> ServerResult<Response> {
+    Self::sat_summaries(&server_config, &index, queries, |query| query.parse())
+  }
+
+  async fn sats_by_name(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Json(names): Json<Vec<String>>,
+  ) -> ServerResult<Response> {
+    Self::sat_summaries(&server_config, &index, names, Sat::from_name)
+  }
+
+  /// Summarize the sats in `queries`, parsed with `parse`, in order, so that
+  /// sat hunting tools can classify many sats in one request.
+  fn sat_summaries(
+    server_config: &ServerConfig,
+    index: &Index,
+    queries: Vec<String>,
+    parse: impl Fn(&str) -> Result<Sat>,
+  ) -> ServerResult<Response> {
+    if !server_config.is_json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+    }
+
+    if queries.len() > MAX_SAT_SUMMARIES {
+      return Err(ServerError::BadRequest(format!(
+        "at most {MAX_SAT_SUMMARIES} sats may be requested at once"
+      )));
+    }
+
+    let mut summaries = Vec::new();
+
+    for query in queries {
+      let sat = parse(&query)
+        .map_err(|err| ServerError::BadRequest(format!("invalid sat `{query}`: {err}")))?;
+
+      let inscriptions = index.get_inscription_ids_by_sat(sat)?;
+
+      summaries.push(SatSummaryJson {
+        number: sat.n(),
+        name: sat.name(),
+        decimal: sat.decimal().to_string(),
+        degree: sat.degree().to_string(),
+        rarity: sat.rarity(),
+        satpoint: Self::sat_satpoint(index, sat, &inscriptions)?,
+      });
+    }
+
+    Ok(Json(summaries).into_response())
+  }
+
   async fn ordinal(Path(sat): Path<String>) -> Redirect {
     Redirect::to(&format!("/sat/{sat}"))
   }
@@ -613,6 +1095,268 @@ impl Server {
     Ok(RareTxt(index.rare_sat_satpoints()?))
   }
 
+  async fn address_changes(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(height): Path<u32>,
+  ) -> ServerResult<Response> {
+    if !server_config.is_json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+    }
+
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    let changes = index
+      .get_address_changes(height)?
+      .ok_or_not_found(|| format!("block at height {height}"))?;
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+
+    for change in changes {
+      let json = AddressChangeJson {
+        address: server_config
+          .chain
+          .address_from_script(&change.script_pubkey)
+          .ok()
+          .map(|address| address.to_string()),
+        outpoint: change.outpoint,
+        script_pubkey: hex::encode(change.script_pubkey.as_bytes()),
+      };
+
+      if change.added {
+        added.push(json);
+      } else {
+        removed.push(json);
+      }
+    }
+
+    Ok(
+      Json(AddressChangesJson {
+        added,
+        height,
+        removed,
+      })
+      .into_response(),
+    )
+  }
+
+  async fn fees(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<FeesQuery>,
+  ) -> ServerResult<Response> {
+    if !server_config.is_json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+    }
+
+    let tip = index
+      .block_count()?
+      .checked_sub(1)
+      .ok_or_not_found(|| "blocks")?;
+
+    let to = query.to.unwrap_or(tip);
+    let from = query.from.unwrap_or(to.saturating_sub(MAX_FEES_BLOCKS - 1));
+
+    if from > to {
+      return Err(ServerError::BadRequest(format!(
+        "`from` {from} is greater than `to` {to}"
+      )));
+    }
+
+    if to - from >= MAX_FEES_BLOCKS {
+      return Err(ServerError::BadRequest(format!(
+        "cannot request more than {MAX_FEES_BLOCKS} blocks"
+      )));
+    }
+
+    Ok(
+      Json(FeesJson {
+        blocks: index
+          .get_inscription_fees(from, to)?
+          .into_iter()
+          .map(|(height, fees)| BlockFeesJson {
+            creation_fees: fees.creation_fees,
+            creation_value: fees.creation_value,
+            creations: fees.creations,
+            height,
+            transfer_fees: fees.transfer_fees,
+            transfer_value: fees.transfer_value,
+            transfers: fees.transfers,
+          })
+          .collect(),
+      })
+      .into_response(),
+    )
+  }
+
+  async fn activity(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(address)): Path<DeserializeFromStr<Address<NetworkUnchecked>>>,
+    Query(query): Query<ActivityQuery>,
+  ) -> ServerResult<Response> {
+    if !server_config.is_json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+    }
+
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    let address = address
+      .require_network(server_config.chain.network())
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let (activity, next) = index.get_address_activity(
+      &address.script_pubkey(),
+      query.cursor,
+      query
+        .limit
+        .unwrap_or(MAX_ACTIVITY_PAGE_SIZE)
+        .clamp(1, MAX_ACTIVITY_PAGE_SIZE),
+    )?;
+
+    Ok(
+      Json(ActivityJson {
+        activity,
+        address: address.to_string(),
+        next,
+      })
+      .into_response(),
+    )
+  }
+
+  async fn address_runes(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(address)): Path<DeserializeFromStr<Address<NetworkUnchecked>>>,
+  ) -> ServerResult<Response> {
+    if !server_config.is_json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+    }
+
+    if !index.has_rune_index() {
+      return Err(ServerError::NotFound(
+        "this server has no rune index".to_string(),
+      ));
+    }
+
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    let address = address
+      .require_network(server_config.chain.network())
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let runes = index
+      .get_address_rune_balances(&address.script_pubkey())?
+      .into_iter()
+      .map(|(id, entry, outpoints)| {
+        let amount = outpoints.iter().map(|(_, amount)| amount).sum();
+
+        AddressRuneJson {
+          amount,
+          display: Pile {
+            amount,
+            divisibility: entry.divisibility,
+            symbol: entry.symbol,
+          }
+          .to_string(),
+          divisibility: entry.divisibility,
+          id,
+          outpoints: outpoints
+            .into_iter()
+            .map(|(outpoint, amount)| RuneOutpointJson { amount, outpoint })
+            .collect(),
+          rune: entry.spaced_rune(),
+          symbol: entry.symbol,
+        }
+      })
+      .collect();
+
+    Ok(
+      Json(AddressRunesJson {
+        address: address.to_string(),
+        runes,
+      })
+      .into_response(),
+    )
+  }
+
+  async fn address_rune_balance(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(address), DeserializeFromStr(spaced_rune))): Path<(
+      DeserializeFromStr<Address<NetworkUnchecked>>,
+      DeserializeFromStr<SpacedRune>,
+    )>,
+    Query(query): Query<BalanceQuery>,
+  ) -> ServerResult<Response> {
+    if !server_config.is_json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+    }
+
+    if !index.has_rune_index() {
+      return Err(ServerError::NotFound(
+        "this server has no rune index".to_string(),
+      ));
+    }
+
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    let address = address
+      .require_network(server_config.chain.network())
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let tip = index
+      .block_height()?
+      .ok_or_not_found(|| "genesis block")?
+      .n();
+
+    let height = query.height.unwrap_or(tip);
+
+    if height > tip {
+      return Err(ServerError::NotFound(format!("block {height}")));
+    }
+
+    let (id, entry) = index
+      .rune(spaced_rune.rune)?
+      .ok_or_not_found(|| format!("rune {spaced_rune}"))?;
+
+    let amount = index.get_address_rune_balance_at(&address.script_pubkey(), id, height)?;
+
+    Ok(
+      Json(AddressRuneBalanceJson {
+        address: address.to_string(),
+        amount,
+        display: Pile {
+          amount,
+          divisibility: entry.divisibility,
+          symbol: entry.symbol,
+        }
+        .to_string(),
+        height,
+        id,
+        rune: entry.spaced_rune(),
+      })
+      .into_response(),
+    )
+  }
+
   async fn rune(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1019,20 +1763,39 @@ impl Server {
 
   async fn content(
     Extension(index): Extension<Arc<Index>>,
-    Extension(config): Extension<Arc<Config>>,
+    Extension(hidden): Extension<Arc<Hidden>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path(inscription_id): Path<InscriptionId>,
     accept_encoding: AcceptEncoding,
   ) -> ServerResult<Response> {
-    if config.is_hidden(inscription_id) {
+    if hidden.contains(inscription_id) {
       return Ok(PreviewUnknownHtml.into_response());
     }
 
+    if let Some((bucket, mode)) = &server_config.content_bucket {
+      if let Some(response) = Self::content_from_bucket(
+        bucket,
+        *mode,
+        inscription_id,
+        &accept_encoding,
+        &hidden,
+        &server_config,
+      )
+      .await?
+      {
+        return Ok(response);
+      }
+    }
+
     let mut inscription = index
       .get_inscription_by_id(inscription_id)?
       .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
     if let Some(delegate) = inscription.delegate() {
+      if hidden.contains(delegate) {
+        return Ok(PreviewUnknownHtml.into_response());
+      }
+
       inscription = index
         .get_inscription_by_id(delegate)?
         .ok_or_not_found(|| format!("delegate {inscription_id}"))?
@@ -1045,11 +1808,83 @@ impl Server {
     )
   }
 
-  fn content_response(
-    inscription: Inscription,
-    accept_encoding: AcceptEncoding,
+  /// Serve content uploaded to the content bucket, following a delegate
+  /// recorded in the object's metadata. Returns `None` if the content should
+  /// be served from the index instead, because it has not been uploaded yet,
+  /// its encoding is not acceptable to the client, or the bucket could not be
+  /// reached, or because it is delegated to a hidden inscription.
+  async fn content_from_bucket(
+    bucket: &Bucket,
+    mode: ContentBucketMode,
+    inscription_id: InscriptionId,
+    accept_encoding: &AcceptEncoding,
+    hidden: &Hidden,
     server_config: &ServerConfig,
-  ) -> ServerResult<Option<(HeaderMap, Vec<u8>)>> {
+  ) -> ServerResult<Option<Response>> {
+    let client = reqwest::Client::new();
+
+    let method = match mode {
+      ContentBucketMode::Proxy => reqwest::Method::GET,
+      ContentBucketMode::Redirect => reqwest::Method::HEAD,
+    };
+
+    let mut id = inscription_id;
+
+    let object = loop {
+      let object = match bucket.get(&client, method.clone(), id).await {
+        Ok(Some(object)) => object,
+        Ok(None) => return Ok(None),
+        Err(err) => {
+          log::warn!("failed to get inscription {id} from content bucket: {err}");
+          return Ok(None);
+        }
+      };
+
+      match object.delegate {
+        Some(delegate) if hidden.contains(delegate) => return Ok(None),
+        Some(delegate) if id == inscription_id => id = delegate,
+        _ => break object,
+      }
+    };
+
+    if let Some(content_encoding) = &object.content_encoding {
+      let acceptable = HeaderValue::from_str(content_encoding)
+        .map(|content_encoding| accept_encoding.is_acceptable(&content_encoding))
+        .unwrap_or_default();
+
+      if !acceptable {
+        return Ok(None);
+      }
+    }
+
+    match mode {
+      ContentBucketMode::Redirect => Ok(Some(
+        Redirect::temporary(bucket.object_url(id).as_str()).into_response(),
+      )),
+      ContentBucketMode::Proxy => {
+        let mut headers = Self::content_headers(server_config)?;
+
+        headers.insert(
+          header::CONTENT_TYPE,
+          object
+            .content_type
+            .and_then(|content_type| content_type.parse().ok())
+            .unwrap_or(HeaderValue::from_static("application/octet-stream")),
+        );
+
+        if let Some(content_encoding) = object
+          .content_encoding
+          .and_then(|content_encoding| content_encoding.parse().ok())
+        {
+          headers.insert(header::CONTENT_ENCODING, content_encoding);
+        }
+
+        Ok(Some((headers, object.body).into_response()))
+      }
+    }
+  }
+
+  fn content_headers(server_config: &ServerConfig) -> ServerResult<HeaderMap> {
     let mut headers = HeaderMap::new();
 
     match &server_config.csp_origin {
@@ -1077,6 +1912,16 @@ impl Server {
       HeaderValue::from_static("public, max-age=31536000, immutable"),
     );
 
+    Ok(headers)
+  }
+
+  fn content_response(
+    inscription: Inscription,
+    accept_encoding: AcceptEncoding,
+    server_config: &ServerConfig,
+  ) -> ServerResult<Option<(HeaderMap, Vec<u8>)>> {
+    let mut headers = Self::content_headers(server_config)?;
+
     headers.insert(
       header::CONTENT_TYPE,
       inscription
@@ -1096,9 +1941,17 @@ impl Server {
         let mut decompressed = Vec::new();
 
         Decompressor::new(body.as_slice(), 4096)
+          .take(server_config.decompress_limit.saturating_add(1))
           .read_to_end(&mut decompressed)
           .map_err(|err| ServerError::Internal(err.into()))?;
 
+        if u64::try_from(decompressed.len()).unwrap() > server_config.decompress_limit {
+          return Err(ServerError::NotAcceptable {
+            accept_encoding,
+            content_encoding,
+          });
+        }
+
         return Ok(Some((headers, decompressed)));
       } else {
         return Err(ServerError::NotAcceptable {
@@ -1117,12 +1970,12 @@ impl Server {
 
   async fn preview(
     Extension(index): Extension<Arc<Index>>,
-    Extension(config): Extension<Arc<Config>>,
+    Extension(hidden): Extension<Arc<Hidden>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path(inscription_id): Path<InscriptionId>,
     accept_encoding: AcceptEncoding,
   ) -> ServerResult<Response> {
-    if config.is_hidden(inscription_id) {
+    if hidden.contains(inscription_id) {
       return Ok(PreviewUnknownHtml.into_response());
     }
 
@@ -1131,6 +1984,10 @@ impl Server {
       .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
     if let Some(delegate) = inscription.delegate() {
+      if hidden.contains(delegate) {
+        return Ok(PreviewUnknownHtml.into_response());
+      }
+
       inscription = index
         .get_inscription_by_id(delegate)?
         .ok_or_not_found(|| format!("delegate {inscription_id}"))?
@@ -1215,41 +2072,24 @@ impl Server {
   async fn inscription(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(hidden): Extension<Arc<Hidden>>,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<InscriptionQuery>>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
-    let info =
+    let mut info =
       Index::inscription_info(&index, query)?.ok_or_not_found(|| format!("inscription {query}"))?;
 
+    // the CID and recursive endpoints are derived from the content, so they
+    // are withheld along with it
+    let hidden = hidden.contains(info.entry.id);
+
+    if hidden {
+      info.cid = None;
+      info.recursive_endpoints.clear();
+    }
+
     Ok(if accept_json {
-      Json(InscriptionJson {
-        inscription_id: info.entry.id,
-        children: info.children,
-        inscription_number: info.entry.inscription_number,
-        genesis_height: info.entry.height,
-        parent: info.parent,
-        genesis_fee: info.entry.fee,
-        output_value: info.output.as_ref().map(|o| o.value),
-        address: info
-          .output
-          .as_ref()
-          .and_then(|o| {
-            server_config
-              .chain
-              .address_from_script(&o.script_pubkey)
-              .ok()
-          })
-          .map(|address| address.to_string()),
-        sat: info.entry.sat,
-        satpoint: info.satpoint,
-        content_type: info.inscription.content_type().map(|s| s.to_string()),
-        content_length: info.inscription.content_length(),
-        timestamp: timestamp(info.entry.timestamp).timestamp(),
-        previous: info.previous,
-        next: info.next,
-        rune: info.rune,
-      })
-      .into_response()
+      Json(Self::inscription_json(info, server_config.chain)).into_response()
     } else {
       InscriptionHtml {
         chain: server_config.chain,
@@ -1257,6 +2097,7 @@ impl Server {
         children: info.children,
         genesis_fee: info.entry.fee,
         genesis_height: info.entry.height,
+        hidden,
         inscription: info.inscription,
         inscription_id: info.entry.id,
         inscription_number: info.entry.inscription_number,
@@ -1264,6 +2105,7 @@ impl Server {
         output: info.output,
         parent: info.parent,
         previous: info.previous,
+        recursive_endpoints: info.recursive_endpoints,
         rune: info.rune,
         sat: info.entry.sat,
         satpoint: info.satpoint,
@@ -1274,6 +2116,100 @@ impl Server {
     })
   }
 
+  async fn inscription_transfers(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(query)): Path<DeserializeFromStr<InscriptionQuery>>,
+  ) -> ServerResult<Response> {
+    if !server_config.is_json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+    }
+
+    let (entry, transfers) = index
+      .get_inscription_transfers(query)?
+      .ok_or_not_found(|| format!("inscription {query}"))?;
+
+    let mut transactions = HashMap::new();
+
+    let mut address = |satpoint: SatPoint| -> ServerResult<Option<String>> {
+      let outpoint = satpoint.outpoint;
+
+      if outpoint == OutPoint::null() || outpoint == unbound_outpoint() {
+        return Ok(None);
+      }
+
+      if let hash_map::Entry::Vacant(entry) = transactions.entry(outpoint.txid) {
+        entry.insert(index.get_transaction(outpoint.txid)?);
+      }
+
+      Ok(
+        transactions[&outpoint.txid]
+          .as_ref()
+          .and_then(|transaction| transaction.output.get(usize::try_from(outpoint.vout).ok()?))
+          .and_then(|output| {
+            server_config
+              .chain
+              .address_from_script(&output.script_pubkey)
+              .ok()
+          })
+          .map(|address| address.to_string()),
+      )
+    };
+
+    let mut transfers_json = Vec::new();
+
+    for transfer in transfers {
+      transfers_json.push(InscriptionTransferJson {
+        fee: transfer.fee,
+        from_address: address(transfer.old_satpoint)?,
+        from_satpoint: transfer.old_satpoint,
+        height: transfer.height,
+        to_address: address(transfer.new_satpoint)?,
+        to_satpoint: transfer.new_satpoint,
+        txid: transfer.new_satpoint.outpoint.txid,
+        value: transfer.value,
+      });
+    }
+
+    Ok(
+      Json(InscriptionTransfersJson {
+        genesis_fee: entry.fee,
+        genesis_height: entry.height,
+        inscription_id: entry.id,
+        transfers: transfers_json,
+      })
+      .into_response(),
+    )
+  }
+
+  fn inscription_json(info: InscriptionInfo, chain: Chain) -> InscriptionJson {
+    InscriptionJson {
+      inscription_id: info.entry.id,
+      children: info.children,
+      cid: info.cid,
+      content_verdicts: info.content_verdicts,
+      inscription_number: info.entry.inscription_number,
+      genesis_height: info.entry.height,
+      parent: info.parent,
+      genesis_fee: info.entry.fee,
+      output_value: info.output.as_ref().map(|o| o.value),
+      address: info
+        .output
+        .as_ref()
+        .and_then(|o| chain.address_from_script(&o.script_pubkey).ok())
+        .map(|address| address.to_string()),
+      sat: info.entry.sat,
+      satpoint: info.satpoint,
+      content_type: info.inscription.content_type().map(|s| s.to_string()),
+      content_length: info.inscription.content_length(),
+      timestamp: timestamp(info.entry.timestamp).timestamp(),
+      previous: info.previous,
+      next: info.next,
+      recursive_endpoints: info.recursive_endpoints,
+      rune: info.rune,
+    }
+  }
+
   async fn collections(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1281,24 +2217,114 @@ impl Server {
     Self::collections_paginated(Extension(server_config), Extension(index), Path(0)).await
   }
 
-  async fn collections_paginated(
+  async fn collections_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page_index): Path<usize>,
+  ) -> ServerResult<Response> {
+    let (collections, more_collections) =
+      index.get_collections_paginated(100, page_index, server_config.deadline())?;
+
+    let prev = page_index.checked_sub(1);
+
+    let next = more_collections.then_some(page_index + 1);
+
+    Ok(
+      CollectionsHtml {
+        inscriptions: collections,
+        prev,
+        next,
+      }
+      .page(server_config)
+      .into_response(),
+    )
+  }
+
+  async fn collection_stats(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Response> {
+    if !server_config.is_json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+    }
+
+    if !index.inscription_exists(inscription_id)? {
+      return Err(ServerError::NotFound(format!(
+        "inscription {inscription_id} not found"
+      )));
+    }
+
+    let collection = index.get_collection(inscription_id)?;
+
+    Ok(
+      Json(CollectionStatsJson {
+        first_child_height: collection.map(|collection| collection.first_child_height),
+        holders: collection
+          .map(|collection| collection.holders)
+          .unwrap_or_default(),
+        inscription_id,
+        last_child_height: collection.map(|collection| collection.last_child_height),
+        supply: collection
+          .map(|collection| collection.supply)
+          .unwrap_or_default(),
+      })
+      .into_response(),
+    )
+  }
+
+  async fn collection_holders(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Response> {
+    Self::collection_holders_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((inscription_id, 0)),
+    )
+    .await
+  }
+
+  async fn collection_holders_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path(page_index): Path<usize>,
+    Path((inscription_id, page_index)): Path<(InscriptionId, usize)>,
   ) -> ServerResult<Response> {
-    let (collections, more_collections) = index.get_collections_paginated(100, page_index)?;
+    if !server_config.is_json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+    }
 
-    let prev = page_index.checked_sub(1);
+    if !index.inscription_exists(inscription_id)? {
+      return Err(ServerError::NotFound(format!(
+        "inscription {inscription_id} not found"
+      )));
+    }
 
-    let next = more_collections.then_some(page_index + 1);
+    let (holders, more) = index.get_collection_holders_paginated(
+      inscription_id,
+      100,
+      page_index,
+      server_config.deadline(),
+    )?;
 
     Ok(
-      CollectionsHtml {
-        inscriptions: collections,
-        prev,
-        next,
-      }
-      .page(server_config)
+      Json(CollectionHoldersJson {
+        holders: holders
+          .into_iter()
+          .map(|(script_pubkey, children)| CollectionHolderJson {
+            address: server_config
+              .chain
+              .address_from_script(&script_pubkey)
+              .ok()
+              .map(|address| address.to_string()),
+            children,
+            script_pubkey: script_pubkey.to_asm_string(),
+          })
+          .collect(),
+        more,
+        page_index,
+      })
       .into_response(),
     )
   }
@@ -1347,6 +2373,13 @@ impl Server {
     )
   }
 
+  async fn teleburn_claims(
+    Extension(index): Extension<Arc<Index>>,
+    Path((chain, asset)): Path<(TargetChain, String)>,
+  ) -> ServerResult<Json<Vec<InscriptionId>>> {
+    Ok(Json(index.get_teleburn_claims(chain, &asset)?))
+  }
+
   async fn children_recursive(
     Extension(index): Extension<Arc<Index>>,
     Path(inscription_id): Path<InscriptionId>,
@@ -1372,12 +2405,14 @@ impl Server {
   async fn inscriptions(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    query: Query<InscriptionsQuery>,
     accept_json: AcceptJson,
   ) -> ServerResult<Response> {
     Self::inscriptions_paginated(
       Extension(server_config),
       Extension(index),
       Path(0),
+      query,
       accept_json,
     )
     .await
@@ -1387,9 +2422,30 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<usize>,
+    Query(query): Query<InscriptionsQuery>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
-    let (inscriptions, more_inscriptions) = index.get_inscriptions_paginated(100, page_index)?;
+    let sort = query.sort.unwrap_or_default();
+
+    let content_type = query
+      .content_type
+      .as_deref()
+      .map(sort::content_type_essence)
+      .filter(|content_type| !content_type.is_empty());
+
+    if sort.needs_sort_index(content_type.as_deref()) && !index.has_inscription_sort_index() {
+      return Err(ServerError::NotFound(
+        "this server has no inscription sort index".to_string(),
+      ));
+    }
+
+    let (inscriptions, more_inscriptions) = index.get_inscriptions_sorted_paginated(
+      sort,
+      query.order.unwrap_or_default(),
+      content_type.as_deref(),
+      100,
+      page_index,
+    )?;
 
     let prev = page_index.checked_sub(1);
 
@@ -1407,6 +2463,7 @@ impl Server {
         inscriptions,
         next,
         prev,
+        query: query.to_query_string(),
       }
       .page(server_config)
       .into_response()
@@ -1525,7 +2582,7 @@ mod tests {
     crate::runes::{Edict, Etching, Rune, Runestone},
     reqwest::Url,
     serde::de::DeserializeOwned,
-    std::net::TcpListener,
+    std::{io::Write, net::TcpListener},
   };
 
   const RUNE: u128 = 99246114928149462;
@@ -1596,13 +2653,6 @@ mod tests {
       )
     }
 
-    fn new_with_bitcoin_rpc_server_and_config(
-      bitcoin_rpc_server: test_bitcoincore_rpc::Handle,
-      config: String,
-    ) -> Self {
-      Self::new_server(bitcoin_rpc_server, Some(config), &[], &[])
-    }
-
     fn new_server(
       bitcoin_rpc_server: test_bitcoincore_rpc::Handle,
       config: Option<String>,
@@ -1791,6 +2841,60 @@ mod tests {
     }
   }
 
+  #[test]
+  fn additional_chains_may_only_be_served_once() {
+    let (options, server) = parse_server_args("ord server --also-chain signet --also-chain signet");
+
+    assert_eq!(
+      server
+        .spawn_chain(&options, Chain::Signet, 1)
+        .unwrap_err()
+        .to_string(),
+      "chain signet may only be served once",
+    );
+
+    let (options, server) = parse_server_args("ord --chain signet server --also-chain signet");
+
+    assert_eq!(
+      server
+        .spawn_chain(&options, Chain::Signet, 1)
+        .unwrap_err()
+        .to_string(),
+      "chain signet may only be served once",
+    );
+  }
+
+  #[test]
+  fn additional_chains_do_not_share_event_sinks() {
+    let (options, _) = parse_server_args(
+      "ord --nats-url nats://localhost --postgres-url postgres://localhost \
+        --ipfs-api-url http://localhost:5001 --nostr-relay wss://relay.example \
+        --rpc-failover-url http://localhost:18332 server --also-chain signet",
+    );
+
+    let options = Server::chain_options(&options, Chain::Signet);
+
+    assert_eq!(options.chain(), Chain::Signet);
+    assert_eq!(options.ipfs_api_url, None);
+    assert_eq!(options.nats_url, None);
+    assert_eq!(options.postgres_url, None);
+    assert!(options.nostr_relay.is_empty());
+    assert!(options.rpc_failover_url.is_empty());
+  }
+
+  #[test]
+  fn additional_chains_cannot_be_served_over_https() {
+    let (options, server) = parse_server_args("ord server --https --also-chain signet");
+
+    assert_eq!(
+      server
+        .spawn_chain(&options, Chain::Signet, 1)
+        .unwrap_err()
+        .to_string(),
+      "additional chains can only be served over HTTP",
+    );
+  }
+
   #[test]
   fn http_and_https_port_dont_conflict() {
     parse_server_args(
@@ -2271,6 +3375,71 @@ mod tests {
     );
   }
 
+  #[test]
+  fn rune_page_shows_info_from_etching_inscription_metadata() {
+    let server = TestServer::new_with_regtest_with_index_runes();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &Value::Map(vec![
+        (Value::Text("name".into()), Value::Text("Foo Coin".into())),
+        (
+          Value::Text("website".into()),
+          Value::Text("https://foo.com".into()),
+        ),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          metadata: Some(metadata),
+          ..inscription("text/plain", "hello")
+        }
+        .to_witness(),
+      )],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::max_value(),
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(rune),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/rune/{rune}"),
+      StatusCode::OK,
+      ".*
+  <dt>name</dt>
+  <dd>Foo Coin</dd>
+  <dt>website</dt>
+  <dd><a href=https://foo.com/>https://foo.com/</a></dd>
+</dl>
+.*",
+    );
+  }
+
   #[test]
   fn runes_are_spaced() {
     let server = TestServer::new_with_regtest_with_index_runes();
@@ -2561,6 +3730,8 @@ mod tests {
   <dd>mainnet</dd>
   <dt>height</dt>
   <dd>0</dd>
+  <dt>indexing</dt>
+  <dd>idle</dd>
   <dt>inscriptions</dt>
   <dd>0</dd>
   <dt>blessed inscriptions</dt>
@@ -2571,6 +3742,8 @@ mod tests {
   <dd>0</dd>
   <dt>lost sats</dt>
   <dd>.*</dd>
+  <dt>quarantined entries</dt>
+  <dd>0</dd>
   <dt>started</dt>
   <dd>.*</dd>
   <dt>uptime</dt>
@@ -2738,6 +3911,15 @@ mod tests {
     );
   }
 
+  #[test]
+  fn sat_name_with_checksum() {
+    let server = TestServer::new();
+
+    for name in ["nvtdijuwxlp".into(), Sat(0).checksummed_name()] {
+      server.assert_response_regex(format!("/sat/{name}"), StatusCode::OK, ".*<h1>Sat 0</h1>.*");
+    }
+  }
+
   #[test]
   fn sat() {
     TestServer::new().assert_response_regex(
@@ -3388,6 +4570,70 @@ mod tests {
     assert_eq!(body, vec![1, 2, 3]);
   }
 
+  #[test]
+  fn content_response_decompresses_brotli() {
+    let mut compressed = Vec::new();
+
+    brotli::CompressorWriter::new(&mut compressed, 4096, 9, 22)
+      .write_all(b"hello, world")
+      .unwrap();
+
+    let inscription = Inscription {
+      body: Some(compressed.clone()),
+      content_encoding: Some("br".into()),
+      content_type: Some("text/plain".into()),
+      ..Default::default()
+    };
+
+    assert!(matches!(
+      Server::content_response(
+        inscription.clone(),
+        AcceptEncoding::default(),
+        &ServerConfig::default(),
+      ),
+      Err(ServerError::NotAcceptable { .. })
+    ));
+
+    let (headers, body) = Server::content_response(
+      inscription.clone(),
+      AcceptEncoding(Some("br".into())),
+      &ServerConfig::default(),
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-encoding"], "br");
+    assert_eq!(body, compressed);
+
+    let (headers, body) = Server::content_response(
+      inscription.clone(),
+      AcceptEncoding::default(),
+      &ServerConfig {
+        decompress: true,
+        decompress_limit: 12,
+        ..Default::default()
+      },
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers.get("content-encoding"), None);
+    assert_eq!(body, b"hello, world");
+
+    assert!(matches!(
+      Server::content_response(
+        inscription,
+        AcceptEncoding::default(),
+        &ServerConfig {
+          decompress: true,
+          decompress_limit: 11,
+          ..Default::default()
+        },
+      ),
+      Err(ServerError::NotAcceptable { .. })
+    ));
+  }
+
   #[test]
   fn content_security_policy_no_origin() {
     let (headers, _) = Server::content_response(
@@ -3690,6 +4936,29 @@ mod tests {
     );
   }
 
+  #[test]
+  fn inscription_page_accepts_ids_with_and_without_checksums() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    for id in [id.to_string(), format!("{id:#}")] {
+      server.assert_response_regex(
+        format!("/inscription/{id}"),
+        StatusCode::OK,
+        ".*<title>Inscription 0</title>.*",
+      );
+    }
+  }
+
   #[test]
   fn inscription_page_has_sat_when_sats_are_tracked() {
     let server = TestServer::new_with_regtest_with_index_sats();
@@ -4021,36 +5290,75 @@ next
 
   #[test]
   fn inscriptions_can_be_hidden_with_config() {
-    let bitcoin_rpc_server = test_bitcoincore_rpc::spawn();
+    let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+      .network(bitcoin::network::constants::Network::Regtest)
+      .build();
     bitcoin_rpc_server.mine_blocks(1);
     let txid = bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[(
         1,
         0,
         0,
-        inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        inscription(
+          "text/html;charset=utf-8",
+          "<script src=/r/blockheight></script>",
+        )
+        .to_witness(),
       )],
       ..Default::default()
     });
     let inscription = InscriptionId { txid, index: 0 };
     bitcoin_rpc_server.mine_blocks(1);
 
-    let server = TestServer::new_with_bitcoin_rpc_server_and_config(
+    let txid = bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        Inscription {
+          delegate: Some(inscription.value()),
+          ..Default::default()
+        }
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    let delegating = InscriptionId { txid, index: 0 };
+    bitcoin_rpc_server.mine_blocks(1);
+
+    let server = TestServer::new_server(
       bitcoin_rpc_server,
-      format!("\"hidden\":\n - {inscription}"),
+      Some(format!("\"hidden\":\n - {inscription}")),
+      &["--chain", "regtest"],
+      &["--enable-json-api"],
     );
 
-    server.assert_response(
-      format!("/preview/{inscription}"),
-      StatusCode::OK,
-      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
-    );
+    for id in [inscription, delegating] {
+      server.assert_response(
+        format!("/preview/{id}"),
+        StatusCode::OK,
+        &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+      );
 
-    server.assert_response(
-      format!("/content/{inscription}"),
-      StatusCode::OK,
-      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
-    );
+      server.assert_response(
+        format!("/content/{id}"),
+        StatusCode::OK,
+        &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+      );
+    }
+
+    let page = server
+      .get(format!("/inscription/{inscription}"))
+      .text()
+      .unwrap();
+
+    assert!(page.contains(&format!("<dd class=monospace>{inscription}</dd>")));
+    assert!(!page.contains(&format!("href=/content/{inscription}")));
+
+    let json = server.get_json::<InscriptionJson>(format!("/inscription/{inscription}"));
+
+    assert_eq!(json.cid, None);
+    assert_eq!(json.recursive_endpoints, Vec::new());
   }
 
   #[test]
@@ -5014,4 +6322,100 @@ next
 
     server.assert_response(format!("/preview/{id}"), StatusCode::OK, "foo");
   }
+
+  #[test]
+  fn json_responses_are_signed_with_signing_key() {
+    let tempdir = TempDir::new().unwrap();
+    let key = tempdir.path().join("key");
+    fs::write(&key, "01".repeat(32)).unwrap();
+
+    let server = TestServer::new_with_args(&[], &["--signing-key-file", key.to_str().unwrap()]);
+
+    let response = server.get("/r/blockhash/0");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let headers = response.headers().clone();
+
+    let public_key = headers[signature::PUBLIC_KEY_HEADER].to_str().unwrap();
+
+    assert_eq!(
+      public_key,
+      signature::load_key(&key)
+        .unwrap()
+        .x_only_public_key()
+        .0
+        .to_string(),
+    );
+
+    let body = response.bytes().unwrap();
+    let signature = headers[signature::SIGNATURE_HEADER].to_str().unwrap();
+
+    crate::api::verify_response(public_key, "GET", "/r/blockhash/0", &body, signature).unwrap();
+
+    assert!(
+      crate::api::verify_response(public_key, "GET", "/r/blockhash/1", &body, signature).is_err()
+    );
+
+    let response = server.get("/");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key(signature::SIGNATURE_HEADER));
+  }
+
+  #[test]
+  fn checkpoint() {
+    TestServer::new().assert_response(
+      "/r/checkpoint/0",
+      StatusCode::NOT_FOUND,
+      "block events require an index built with `--index-events`",
+    );
+
+    let server = TestServer::new_with_args(&["--index-events"], &[]);
+
+    let checkpoint = server.get_json::<export::CheckpointJson>("/r/checkpoint/0");
+
+    assert_eq!(checkpoint.height, 0);
+    assert_eq!(
+      checkpoint.hash,
+      server.index.block_hash(Some(0)).unwrap().unwrap()
+    );
+    assert_eq!(checkpoint.records, 1);
+
+    server.assert_response(
+      "/r/checkpoint/1",
+      StatusCode::NOT_FOUND,
+      "block 1 not found",
+    );
+  }
+
+  #[test]
+  fn teleburn_claims_endpoint() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription(
+          "application/json",
+          r#"{"p":"teleburn","chain":"solana","asset":"foo"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<Vec<InscriptionId>>("/r/teleburn/solana/foo"),
+      [InscriptionId { txid, index: 0 }],
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<Vec<InscriptionId>>("/r/teleburn/ethereum/foo"),
+      Vec::<InscriptionId>::new(),
+    );
+  }
 }
@@ -0,0 +1,222 @@
+use {
+  super::*,
+  crate::index::{
+    subscription::{Subscription, SubscriptionJson},
+    watch::{Watch, WatchJson},
+  },
+  bitcoin::hashes::sha256,
+};
+
+/// Check that the request was sent with header `Authorization: Bearer
+/// <ADMIN_TOKEN>`, returning the response to send if not.
+fn authorize(server_config: &ServerConfig, headers: &HeaderMap) -> ServerResult<Option<Response>> {
+  let Some(token) = &server_config.admin_token else {
+    return Err(ServerError::NotFound("admin API not enabled".into()));
+  };
+
+  Ok(check_bearer(token, headers))
+}
+
+/// Check that the request was sent with header `Authorization: Bearer
+/// <TOKEN>`, returning a `401 Unauthorized` response to send if not. Tokens
+/// are compared by hash, so that comparisons don't leak how long a prefix of
+/// `token` was guessed.
+pub(super) fn check_bearer(token: &str, headers: &HeaderMap) -> Option<Response> {
+  let authorized = headers
+    .get(header::AUTHORIZATION)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.strip_prefix("Bearer "))
+    .map(|provided| sha256::Hash::hash(provided.as_bytes()) == sha256::Hash::hash(token.as_bytes()))
+    .unwrap_or_default();
+
+  (!authorized).then(|| {
+    (
+      StatusCode::UNAUTHORIZED,
+      [(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"))],
+      "unauthorized",
+    )
+      .into_response()
+  })
+}
+
+pub(super) async fn hidden(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(hidden): Extension<Arc<Hidden>>,
+  headers: HeaderMap,
+) -> ServerResult<Response> {
+  if let Some(response) = authorize(&server_config, &headers)? {
+    return Ok(response);
+  }
+
+  Ok(Json(hidden.json()).into_response())
+}
+
+pub(super) async fn hide(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(hidden): Extension<Arc<Hidden>>,
+  Path(inscription_id): Path<InscriptionId>,
+  headers: HeaderMap,
+) -> ServerResult<Response> {
+  if let Some(response) = authorize(&server_config, &headers)? {
+    return Ok(response);
+  }
+
+  Ok(
+    if hidden.insert(inscription_id)? {
+      log::info!("Hid inscription {inscription_id}");
+      StatusCode::CREATED
+    } else {
+      StatusCode::OK
+    }
+    .into_response(),
+  )
+}
+
+pub(super) async fn unhide(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(hidden): Extension<Arc<Hidden>>,
+  Path(inscription_id): Path<InscriptionId>,
+  headers: HeaderMap,
+) -> ServerResult<Response> {
+  if let Some(response) = authorize(&server_config, &headers)? {
+    return Ok(response);
+  }
+
+  if hidden.in_config(inscription_id) {
+    return Err(ServerError::BadRequest(format!(
+      "inscription {inscription_id} is hidden by config file"
+    )));
+  }
+
+  if !hidden.remove(inscription_id)? {
+    return Err(ServerError::NotFound(format!(
+      "inscription {inscription_id} is not hidden"
+    )));
+  }
+
+  log::info!("Unhid inscription {inscription_id}");
+
+  Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+pub(super) async fn subscriptions(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  headers: HeaderMap,
+) -> ServerResult<Response> {
+  if let Some(response) = authorize(&server_config, &headers)? {
+    return Ok(response);
+  }
+
+  Ok(Json(index.subscriptions()?).into_response())
+}
+
+pub(super) async fn subscribe(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  headers: HeaderMap,
+  Json(subscription): Json<Subscription>,
+) -> ServerResult<Response> {
+  if let Some(response) = authorize(&server_config, &headers)? {
+    return Ok(response);
+  }
+
+  subscription
+    .parse()
+    .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+  let id = index.add_subscription(&subscription)?;
+
+  log::info!("Added subscription {id} for `{}`", subscription.filter);
+
+  Ok(
+    (
+      StatusCode::CREATED,
+      Json(SubscriptionJson { id, subscription }),
+    )
+      .into_response(),
+  )
+}
+
+pub(super) async fn unsubscribe(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Path(id): Path<u64>,
+  headers: HeaderMap,
+) -> ServerResult<Response> {
+  if let Some(response) = authorize(&server_config, &headers)? {
+    return Ok(response);
+  }
+
+  if !index.remove_subscription(id)? {
+    return Err(ServerError::NotFound(format!(
+      "subscription {id} not found"
+    )));
+  }
+
+  log::info!("Removed subscription {id}");
+
+  Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+pub(super) async fn watches(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  headers: HeaderMap,
+) -> ServerResult<Response> {
+  if let Some(response) = authorize(&server_config, &headers)? {
+    return Ok(response);
+  }
+
+  Ok(Json(index.watches()?).into_response())
+}
+
+pub(super) async fn watch(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  headers: HeaderMap,
+  Json(watch): Json<Watch>,
+) -> ServerResult<Response> {
+  if let Some(response) = authorize(&server_config, &headers)? {
+    return Ok(response);
+  }
+
+  watch
+    .parse()
+    .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+  let id = index.add_watch(&watch)?;
+
+  log::info!("Added watch {id} for {}", watch.location);
+
+  Ok(
+    (
+      StatusCode::CREATED,
+      Json(WatchJson {
+        id,
+        moved: None,
+        watch,
+      }),
+    )
+      .into_response(),
+  )
+}
+
+pub(super) async fn unwatch(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Path(id): Path<u64>,
+  headers: HeaderMap,
+) -> ServerResult<Response> {
+  if let Some(response) = authorize(&server_config, &headers)? {
+    return Ok(response);
+  }
+
+  if !index.remove_watch(id)? {
+    return Err(ServerError::NotFound(format!("watch {id} not found")));
+  }
+
+  log::info!("Removed watch {id}");
+
+  Ok(StatusCode::NO_CONTENT.into_response())
+}
@@ -0,0 +1,48 @@
+use {super::*, base64::Engine, bitcoin::hashes::sha256};
+
+/// Render the operator dashboard for clients that authenticate with HTTP basic
+/// auth, using any username and the dashboard token as the password, so that
+/// browsers prompt for it.
+pub(super) async fn dashboard(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  headers: HeaderMap,
+) -> ServerResult<Response> {
+  let Some(token) = &server_config.dashboard_token else {
+    return Err(ServerError::NotFound("dashboard not enabled".into()));
+  };
+
+  let authorized = headers
+    .get(header::AUTHORIZATION)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.strip_prefix("Basic "))
+    .and_then(|credentials| {
+      base64::engine::general_purpose::STANDARD
+        .decode(credentials)
+        .ok()
+    })
+    .and_then(|credentials| String::from_utf8(credentials).ok())
+    .and_then(|credentials| {
+      credentials
+        .split_once(':')
+        .map(|(_username, password)| password.to_string())
+    })
+    .map(|provided| sha256::Hash::hash(provided.as_bytes()) == sha256::Hash::hash(token.as_bytes()))
+    .unwrap_or_default();
+
+  if !authorized {
+    return Ok(
+      (
+        StatusCode::UNAUTHORIZED,
+        [(
+          header::WWW_AUTHENTICATE,
+          HeaderValue::from_static("Basic realm=\"ord\""),
+        )],
+        "unauthorized",
+      )
+        .into_response(),
+    );
+  }
+
+  Ok(index.dashboard()?.page(server_config).into_response())
+}
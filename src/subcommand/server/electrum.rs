@@ -0,0 +1,366 @@
+use {
+  super::{
+    json_rpc::{Response, INTERNAL_ERROR, INVALID_PARAMS, METHOD_NOT_FOUND, PARSE_ERROR},
+    *,
+  },
+  crate::index::event::Event,
+  serde_json::Value,
+  std::{
+    collections::hash_map,
+    io::{BufRead, BufReader, Write},
+    net::{Shutdown, TcpStream},
+  },
+};
+
+const SUBSCRIBE: &str = "inscriptions.address.subscribe";
+
+/// Subscription protocol modeled on Electrum's. Clients send newline-delimited
+/// JSON-RPC requests over TCP and subscribe to addresses with
+/// `inscriptions.address.subscribe`, which returns the current block count.
+/// For every subsequently indexed block that creates or moves inscriptions to
+/// or from a subscribed address, or sends runes to it, the server sends a notification with the
+/// same method and params `[<ADDRESS>, <HEIGHT>, <CHANGES>]`.
+pub(super) struct Electrum {
+  chain: Chain,
+  connections: Mutex<BTreeMap<u64, Connection>>,
+  index: Arc<Index>,
+}
+
+struct Connection {
+  stream: TcpStream,
+  subscriptions: BTreeMap<ScriptBuf, String>,
+}
+
+#[derive(Deserialize)]
+struct Request {
+  id: Option<Value>,
+  method: String,
+  #[serde(default)]
+  params: Vec<Value>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Change {
+  InscriptionReceived {
+    inscription_id: InscriptionId,
+    location: SatPoint,
+  },
+  InscriptionSent {
+    inscription_id: InscriptionId,
+    location: SatPoint,
+  },
+  RuneReceived {
+    amount: u128,
+    outpoint: OutPoint,
+    rune_id: RuneId,
+  },
+}
+
+#[derive(Serialize)]
+struct Notification<'a> {
+  jsonrpc: &'static str,
+  method: &'static str,
+  params: (&'a str, u32, &'a [Change]),
+}
+
+impl Electrum {
+  pub(super) fn spawn(index: Arc<Index>, chain: Chain, addr: SocketAddr) -> Result {
+    let listener = TcpListener::bind(addr)
+      .with_context(|| format!("failed to bind Electrum listener to {addr}"))?;
+
+    let events = index.subscribe_events();
+
+    let electrum = Arc::new(Self {
+      chain,
+      connections: Mutex::new(BTreeMap::new()),
+      index,
+    });
+
+    let clone = electrum.clone();
+    thread::spawn(move || {
+      for events in events {
+        if let Err(err) = clone.notify(&events) {
+          log::warn!("failed to send Electrum notifications: {err}");
+        }
+      }
+    });
+
+    thread::spawn(move || {
+      for (id, stream) in (0..).zip(listener.incoming()) {
+        match stream {
+          Ok(stream) => {
+            let electrum = electrum.clone();
+            thread::spawn(move || electrum.serve(id, stream));
+          }
+          Err(err) => log::warn!("failed to accept Electrum connection: {err}"),
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  fn serve(&self, id: u64, stream: TcpStream) {
+    if let Err(err) = self.serve_connection(id, stream) {
+      log::debug!("Electrum connection {id} closed: {err}");
+    }
+
+    self.connections.lock().unwrap().remove(&id);
+  }
+
+  fn serve_connection(&self, id: u64, stream: TcpStream) -> Result {
+    stream.set_write_timeout(Some(Duration::from_secs(10)))?;
+
+    self.connections.lock().unwrap().insert(
+      id,
+      Connection {
+        stream: stream.try_clone()?,
+        subscriptions: BTreeMap::new(),
+      },
+    );
+
+    for line in BufReader::new(stream).lines() {
+      let line = line?;
+
+      if line.trim().is_empty() {
+        continue;
+      }
+
+      let Some(response) = self.handle(id, &line) else {
+        continue;
+      };
+
+      let mut connections = self.connections.lock().unwrap();
+
+      let connection = connections
+        .get_mut(&id)
+        .ok_or_else(|| anyhow!("connection dropped"))?;
+
+      writeln!(connection.stream, "{}", serde_json::to_string(&response)?)?;
+    }
+
+    Ok(())
+  }
+
+  fn handle(&self, id: u64, line: &str) -> Option<Response> {
+    let request = match serde_json::from_str::<Request>(line) {
+      Ok(request) => request,
+      Err(err) => {
+        return Some(Response::error(
+          Value::Null,
+          PARSE_ERROR,
+          format!("parse error: {err}"),
+        ))
+      }
+    };
+
+    let result = match request.method.as_str() {
+      "server.ping" => Ok(Value::Null),
+      "server.version" => Ok(serde_json::json!([
+        format!("ord {}", env!("CARGO_PKG_VERSION")),
+        "1.4"
+      ])),
+      SUBSCRIBE => self.subscribe(id, &request.params),
+      "inscriptions.address.unsubscribe" => self.unsubscribe(id, &request.params),
+      method => Err((METHOD_NOT_FOUND, format!("method `{method}` not found"))),
+    };
+
+    let request_id = request.id?;
+
+    Some(match result {
+      Ok(result) => Response::result(request_id, result),
+      Err((code, message)) => Response::error(request_id, code, message),
+    })
+  }
+
+  fn address(&self, params: &[Value]) -> Result<(String, ScriptBuf), (i32, String)> {
+    let [Value::String(address)] = params else {
+      return Err((INVALID_PARAMS, "expected one address parameter".into()));
+    };
+
+    let script_pubkey = address
+      .parse::<Address<NetworkUnchecked>>()
+      .map_err(|err| err.to_string())
+      .and_then(|address| {
+        address
+          .require_network(self.chain.network())
+          .map_err(|err| err.to_string())
+      })
+      .map_err(|err| {
+        (
+          INVALID_PARAMS,
+          format!("invalid address `{address}`: {err}"),
+        )
+      })?
+      .script_pubkey();
+
+    Ok((address.clone(), script_pubkey))
+  }
+
+  fn subscribe(&self, id: u64, params: &[Value]) -> Result<Value, (i32, String)> {
+    let (address, script_pubkey) = self.address(params)?;
+
+    if let Some(connection) = self.connections.lock().unwrap().get_mut(&id) {
+      connection.subscriptions.insert(script_pubkey, address);
+    }
+
+    self
+      .index
+      .block_count()
+      .map(Value::from)
+      .map_err(|err| (INTERNAL_ERROR, err.to_string()))
+  }
+
+  fn unsubscribe(&self, id: u64, params: &[Value]) -> Result<Value, (i32, String)> {
+    let (_, script_pubkey) = self.address(params)?;
+
+    Ok(Value::Bool(
+      self
+        .connections
+        .lock()
+        .unwrap()
+        .get_mut(&id)
+        .map(|connection| connection.subscriptions.remove(&script_pubkey).is_some())
+        .unwrap_or_default(),
+    ))
+  }
+
+  fn notify(&self, events: &[Event]) -> Result {
+    let subscribed = self
+      .connections
+      .lock()
+      .unwrap()
+      .values()
+      .any(|connection| !connection.subscriptions.is_empty());
+
+    if !subscribed {
+      return Ok(());
+    }
+
+    let mut height = None;
+    let mut changes = BTreeMap::<ScriptBuf, Vec<Change>>::new();
+    let mut transactions = HashMap::new();
+
+    for event in events {
+      match event {
+        Event::BlockIndexed { block_height, .. } => height = Some(*block_height),
+        Event::InscriptionCreated {
+          inscription_id,
+          location,
+          ..
+        } => {
+          if let Some(script_pubkey) = self.script_pubkey(&mut transactions, location.outpoint)? {
+            changes
+              .entry(script_pubkey)
+              .or_default()
+              .push(Change::InscriptionReceived {
+                inscription_id: *inscription_id,
+                location: *location,
+              });
+          }
+        }
+        Event::InscriptionTransferred {
+          inscription_id,
+          new_location,
+          old_location,
+          sender,
+          ..
+        } => {
+          let sender = match sender {
+            Some(sender) => Some(sender.clone()),
+            None => self.script_pubkey(&mut transactions, old_location.outpoint)?,
+          };
+
+          if let Some(script_pubkey) = sender {
+            changes
+              .entry(script_pubkey)
+              .or_default()
+              .push(Change::InscriptionSent {
+                inscription_id: *inscription_id,
+                location: *new_location,
+              });
+          }
+
+          if let Some(script_pubkey) =
+            self.script_pubkey(&mut transactions, new_location.outpoint)?
+          {
+            changes
+              .entry(script_pubkey)
+              .or_default()
+              .push(Change::InscriptionReceived {
+                inscription_id: *inscription_id,
+                location: *new_location,
+              });
+          }
+        }
+        Event::RuneTransferred {
+          amount,
+          outpoint,
+          rune_id,
+          ..
+        } => {
+          if let Some(script_pubkey) = self.script_pubkey(&mut transactions, *outpoint)? {
+            changes
+              .entry(script_pubkey)
+              .or_default()
+              .push(Change::RuneReceived {
+                amount: *amount,
+                outpoint: *outpoint,
+                rune_id: *rune_id,
+              });
+          }
+        }
+        _ => {}
+      }
+    }
+
+    let Some(height) = height else {
+      return Ok(());
+    };
+
+    let mut connections = self.connections.lock().unwrap();
+
+    for connection in connections.values_mut() {
+      for (script_pubkey, address) in &connection.subscriptions {
+        let Some(changes) = changes.get(script_pubkey) else {
+          continue;
+        };
+
+        let notification = serde_json::to_string(&Notification {
+          jsonrpc: "2.0",
+          method: SUBSCRIBE,
+          params: (address, height, changes),
+        })?;
+
+        if writeln!(connection.stream, "{notification}").is_err() {
+          connection.stream.shutdown(Shutdown::Both).ok();
+          break;
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn script_pubkey(
+    &self,
+    transactions: &mut HashMap<Txid, Option<Transaction>>,
+    outpoint: OutPoint,
+  ) -> Result<Option<ScriptBuf>> {
+    if outpoint == OutPoint::null() || outpoint == unbound_outpoint() {
+      return Ok(None);
+    }
+
+    if let hash_map::Entry::Vacant(entry) = transactions.entry(outpoint.txid) {
+      entry.insert(self.index.get_transaction(outpoint.txid)?);
+    }
+
+    Ok(
+      transactions[&outpoint.txid]
+        .as_ref()
+        .and_then(|transaction| transaction.output.get(usize::try_from(outpoint.vout).ok()?))
+        .map(|output| output.script_pubkey.clone()),
+    )
+  }
+}
@@ -9,6 +9,7 @@ pub(super) enum ServerError {
     content_encoding: HeaderValue,
   },
   NotFound(String),
+  ServiceUnavailable(String),
 }
 
 pub(super) type ServerResult<T> = Result<T, ServerError>;
@@ -50,6 +51,12 @@ impl IntoResponse for ServerError {
         message,
       )
         .into_response(),
+      Self::ServiceUnavailable(message) => (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(header::RETRY_AFTER, HeaderValue::from_static("1"))],
+        message,
+      )
+        .into_response(),
     }
   }
 }
@@ -69,6 +76,9 @@ impl<T> OptionExt<T> for Option<T> {
 
 impl From<Error> for ServerError {
   fn from(error: Error) -> Self {
-    Self::Internal(error)
+    match error.downcast_ref::<DeadlineExceeded>() {
+      Some(exceeded) => Self::ServiceUnavailable(format!("{exceeded}, try again later")),
+      None => Self::Internal(error),
+    }
   }
 }
@@ -0,0 +1,194 @@
+use {
+  super::{admin::check_bearer, *},
+  crate::index::event::Event,
+  bitcoin::hashes::{sha256, HashEngine},
+  serde_json::Value,
+  tokio::sync::mpsc,
+  tokio_stream::wrappers::ReceiverStream,
+};
+
+#[derive(Deserialize)]
+pub(super) struct ExportQuery {
+  from: Option<u32>,
+  to: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct CheckpointJson {
+  pub(super) height: u32,
+  pub(super) hash: BlockHash,
+  pub(super) records: u64,
+  pub(super) sha256: String,
+}
+
+/// Stream the blocks in `from..=to` and the events they contain as NDJSON, in
+/// height order. Each block is a `block` record followed by a record for
+/// every event in the block. The stream ends with a `checksum` record holding
+/// the number of preceding records and the SHA-256 of their bytes, so clients
+/// can detect truncated exports.
+pub(super) async fn blocks(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  headers: HeaderMap,
+  Query(query): Query<ExportQuery>,
+) -> ServerResult<Response> {
+  let Some(token) = &server_config.export_token else {
+    return Err(ServerError::NotFound("export not enabled".into()));
+  };
+
+  if let Some(response) = check_bearer(token, &headers) {
+    return Ok(response);
+  }
+
+  if !index.has_event_index() {
+    return Err(events_not_indexed());
+  }
+
+  let tip = index
+    .block_count()?
+    .checked_sub(1)
+    .ok_or_not_found(|| "blocks")?;
+
+  let from = query.from.unwrap_or(0);
+  let to = query.to.unwrap_or(tip);
+
+  if from > to {
+    return Err(ServerError::BadRequest(format!(
+      "`from` {from} is greater than `to` {to}"
+    )));
+  }
+
+  if to > tip {
+    return Err(ServerError::NotFound(format!("block {to} not found")));
+  }
+
+  let (sender, receiver) = mpsc::channel(64);
+
+  thread::spawn(move || {
+    if let Err(err) = export(&index, from, to, &sender) {
+      sender
+        .blocking_send(Err(io::Error::new(io::ErrorKind::Other, err.to_string())))
+        .ok();
+    }
+  });
+
+  Ok(
+    (
+      [(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson"),
+      )],
+      body::StreamBody::new(ReceiverStream::new(receiver)),
+    )
+      .into_response(),
+  )
+}
+
+/// Error returned when block events are requested from an index built
+/// without `--index-events`.
+pub(super) fn events_not_indexed() -> ServerError {
+  ServerError::NotFound("block events require an index built with `--index-events`".into())
+}
+
+/// Events of block `height`, in the order in which they occurred.
+pub(super) fn block_events(index: &Index, height: u32) -> ServerResult<Vec<Event>> {
+  if !index.has_event_index() {
+    return Err(events_not_indexed());
+  }
+
+  index
+    .get_events_in_block(height)?
+    .ok_or_not_found(|| format!("block {height}"))
+}
+
+/// The `block` record of block `height`, followed by a record for every event
+/// in the block.
+fn block_records(index: &Index, height: u32, events: &[Event]) -> Result<Vec<Value>> {
+  let hash = index
+    .block_hash(Some(height))?
+    .ok_or_else(|| anyhow!("block {height} not found"))?;
+
+  let mut records = vec![serde_json::json!({
+    "type": "block",
+    "height": height,
+    "hash": hash,
+  })];
+
+  for event in events {
+    records.push(serde_json::to_value(event)?);
+  }
+
+  Ok(records)
+}
+
+fn export(index: &Index, from: u32, to: u32, sender: &mpsc::Sender<io::Result<Vec<u8>>>) -> Result {
+  let mut engine = sha256::Hash::engine();
+  let mut records = 0u64;
+
+  let mut send = |record: Value| {
+    let mut line = serde_json::to_vec(&record)?;
+    line.push(b'\n');
+    engine.input(&line);
+    records += 1;
+    sender
+      .blocking_send(Ok(line))
+      .map_err(|_| anyhow!("export client disconnected"))
+  };
+
+  for height in from..=to {
+    let events = index
+      .get_events_in_block(height)?
+      .ok_or_else(|| anyhow!("block {height} not found"))?;
+
+    for record in block_records(index, height, &events)? {
+      send(record)?;
+    }
+  }
+
+  let line = format!(
+    "{}\n",
+    serde_json::json!({
+      "type": "checksum",
+      "from": from,
+      "to": to,
+      "records": records,
+      "sha256": sha256::Hash::from_engine(engine).to_string(),
+    })
+  );
+
+  sender
+    .blocking_send(Ok(line.into_bytes()))
+    .map_err(|_| anyhow!("export client disconnected"))
+}
+
+/// Checkpoint of block `height`, holding the SHA-256 of the records that
+/// `/export/blocks` returns for the block. When the server signs responses,
+/// checkpoints let clients prove which indexer reported which events for a
+/// block, without signing whole exports.
+pub(super) async fn checkpoint(
+  Extension(index): Extension<Arc<Index>>,
+  Path(height): Path<u32>,
+) -> ServerResult<Json<CheckpointJson>> {
+  let events = block_events(&index, height)?;
+
+  let hash = index
+    .block_hash(Some(height))?
+    .ok_or_not_found(|| format!("block {height}"))?;
+
+  let mut engine = sha256::Hash::engine();
+  let mut records = 0;
+
+  for record in block_records(&index, height, &events)? {
+    let mut line = serde_json::to_vec(&record).map_err(|err| anyhow!(err))?;
+    line.push(b'\n');
+    engine.input(&line);
+    records += 1;
+  }
+
+  Ok(Json(CheckpointJson {
+    height,
+    hash,
+    records,
+    sha256: sha256::Hash::from_engine(engine).to_string(),
+  }))
+}
@@ -0,0 +1,227 @@
+use {super::*, crate::index::event::Event, serde_json::Value};
+
+pub(super) const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+pub(super) const METHOD_NOT_FOUND: i32 = -32601;
+pub(super) const INVALID_PARAMS: i32 = -32602;
+pub(super) const INTERNAL_ERROR: i32 = -32603;
+const NOT_FOUND: i32 = -32001;
+const SERVICE_UNAVAILABLE: i32 = -32002;
+
+#[derive(Deserialize)]
+struct Request {
+  jsonrpc: String,
+  id: Option<Value>,
+  method: String,
+  #[serde(default)]
+  params: Value,
+}
+
+#[derive(Serialize)]
+pub(super) struct Response {
+  jsonrpc: String,
+  id: Value,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  result: Option<Value>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  error: Option<ErrorObject>,
+}
+
+#[derive(Serialize)]
+struct ErrorObject {
+  code: i32,
+  message: String,
+}
+
+#[derive(Serialize)]
+struct BlockEvents {
+  height: u32,
+  events: Vec<Event>,
+}
+
+impl Response {
+  pub(super) fn result(id: Value, result: Value) -> Self {
+    Self {
+      jsonrpc: "2.0".into(),
+      id,
+      result: Some(result),
+      error: None,
+    }
+  }
+
+  pub(super) fn error(id: Value, code: i32, message: impl Into<String>) -> Self {
+    Self {
+      jsonrpc: "2.0".into(),
+      id,
+      result: None,
+      error: Some(ErrorObject {
+        code,
+        message: message.into(),
+      }),
+    }
+  }
+}
+
+/// JSON-RPC 2.0 endpoint exposing a subset of the JSON API. Accepts single
+/// requests and batches. Notifications, i.e. requests without an `id`, are
+/// executed but not answered.
+pub(super) async fn handler(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  body: String,
+) -> axum::response::Response {
+  if !server_config.is_json_api_enabled {
+    return (StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response();
+  }
+
+  let request = match serde_json::from_str::<Value>(&body) {
+    Ok(request) => request,
+    Err(err) => {
+      return Json(Response::error(
+        Value::Null,
+        PARSE_ERROR,
+        format!("parse error: {err}"),
+      ))
+      .into_response()
+    }
+  };
+
+  match request {
+    Value::Array(requests) if requests.is_empty() => {
+      Json(Response::error(Value::Null, INVALID_REQUEST, "empty batch")).into_response()
+    }
+    Value::Array(requests) => {
+      let responses = requests
+        .into_iter()
+        .filter_map(|request| call(&index, &server_config, request))
+        .collect::<Vec<Response>>();
+
+      if responses.is_empty() {
+        StatusCode::NO_CONTENT.into_response()
+      } else {
+        Json(responses).into_response()
+      }
+    }
+    request => match call(&index, &server_config, request) {
+      Some(response) => Json(response).into_response(),
+      None => StatusCode::NO_CONTENT.into_response(),
+    },
+  }
+}
+
+fn call(index: &Index, server_config: &ServerConfig, request: Value) -> Option<Response> {
+  let request = match serde_json::from_value::<Request>(request) {
+    Ok(request) if request.jsonrpc == "2.0" => request,
+    Ok(request) => {
+      return Some(Response::error(
+        request.id.unwrap_or_default(),
+        INVALID_REQUEST,
+        "jsonrpc must be \"2.0\"",
+      ))
+    }
+    Err(err) => {
+      return Some(Response::error(
+        Value::Null,
+        INVALID_REQUEST,
+        format!("invalid request: {err}"),
+      ))
+    }
+  };
+
+  let result = match request.method.as_str() {
+    "getblockcount" => index
+      .block_count()
+      .map(|count| count.into())
+      .map_err(ServerError::Internal),
+    "getblockevents" => get_block_events(index, server_config, &request.params),
+    "getinscription" => get_inscription(index, server_config, &request.params),
+    method => {
+      return request
+        .id
+        .map(|id| Response::error(id, METHOD_NOT_FOUND, format!("method `{method}` not found")))
+    }
+  };
+
+  let id = request.id?;
+
+  Some(match result {
+    Ok(result) => Response::result(id, result),
+    Err(ServerError::BadRequest(message)) => Response::error(id, INVALID_PARAMS, message),
+    Err(ServerError::NotFound(message)) => Response::error(id, NOT_FOUND, message),
+    Err(ServerError::ServiceUnavailable(message)) => {
+      Response::error(id, SERVICE_UNAVAILABLE, message)
+    }
+    Err(ServerError::Internal(err)) => {
+      eprintln!("error serving JSON-RPC request: {err}");
+      Response::error(id, INTERNAL_ERROR, "internal error")
+    }
+    Err(err @ ServerError::NotAcceptable { .. }) => {
+      Response::error(id, INTERNAL_ERROR, format!("{err:?}"))
+    }
+  })
+}
+
+/// Get the single positional parameter of a request, which may also be
+/// passed by name.
+fn param<'a>(params: &'a Value, name: &str) -> ServerResult<&'a Value> {
+  match params {
+    Value::Array(params) if params.len() == 1 => Ok(&params[0]),
+    Value::Object(params) => params
+      .get(name)
+      .ok_or_else(|| ServerError::BadRequest(format!("missing parameter `{name}`"))),
+    _ => Err(ServerError::BadRequest(format!(
+      "expected one parameter `{name}`"
+    ))),
+  }
+}
+
+fn get_block_events(
+  index: &Index,
+  server_config: &ServerConfig,
+  params: &Value,
+) -> ServerResult<Value> {
+  let height = param(params, "height")?
+    .as_u64()
+    .and_then(|height| u32::try_from(height).ok())
+    .ok_or_else(|| ServerError::BadRequest("`height` must be a block height".into()))?;
+
+  if height >= index.block_count()? {
+    return Err(ServerError::NotFound(format!("block {height} not found")));
+  }
+
+  server_config.deadline().check(0)?;
+
+  let events = export::block_events(index, height)?;
+
+  serde_json::to_value(BlockEvents { height, events })
+    .map_err(|err| ServerError::Internal(err.into()))
+}
+
+fn get_inscription(
+  index: &Index,
+  server_config: &ServerConfig,
+  params: &Value,
+) -> ServerResult<Value> {
+  let query = match param(params, "inscription")? {
+    Value::String(query) => query
+      .parse::<InscriptionQuery>()
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?,
+    Value::Number(number) => InscriptionQuery::Number(
+      number
+        .as_i64()
+        .and_then(|number| i32::try_from(number).ok())
+        .ok_or_else(|| ServerError::BadRequest(format!("invalid inscription number {number}")))?,
+    ),
+    _ => {
+      return Err(ServerError::BadRequest(
+        "`inscription` must be an inscription ID or number".into(),
+      ))
+    }
+  };
+
+  let info =
+    Index::inscription_info(index, query)?.ok_or_not_found(|| format!("inscription {query}"))?;
+
+  serde_json::to_value(Server::inscription_json(info, server_config.chain))
+    .map_err(|err| ServerError::Internal(err.into()))
+}
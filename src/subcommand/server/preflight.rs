@@ -0,0 +1,225 @@
+use super::*;
+
+/// Most distinct inscriptions a preview may pull in through `/content/`
+/// before its dependency tree is considered oversized.
+const MAX_DEPENDENCIES: usize = 100;
+
+/// Most levels of `/content/` references followed before a preview's
+/// dependency tree is considered oversized.
+const MAX_DEPTH: u32 = 10;
+
+/// Schemes of URLs which would be fetched from outside of ord, and which the
+/// content security policy of `/content` and `/preview` blocks.
+const EXTERNAL_SCHEMES: [&str; 4] = ["http://", "https://", "ws://", "wss://"];
+
+/// XML namespaces, which appear in almost every SVG, are identifiers and are
+/// never fetched.
+const NAMESPACE_PREFIX: &str = "http://www.w3.org/";
+
+/// Statically analyze the content `/preview/<INSCRIPTION_ID>` would render,
+/// following `/content/<INSCRIPTION_ID>` references breadth first, and report
+/// whether it attempts to reach outside of ord or pulls in an oversized tree
+/// of recursive dependencies. Only HTML, SVG, JavaScript, and CSS are scanned,
+/// since other media can't make requests. The analysis is a best-effort
+/// search for literal URLs and paths, so content assembling them at runtime
+/// isn't caught, and `safe` means only that nothing disallowed was found.
+pub(super) async fn check(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Extension(hidden): Extension<Arc<Hidden>>,
+  Path(inscription_id): Path<InscriptionId>,
+) -> ServerResult<Response> {
+  if !server_config.is_json_api_enabled {
+    return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API not enabled").into_response());
+  }
+
+  if hidden.contains(inscription_id) {
+    return Err(ServerError::NotFound(format!(
+      "inscription {inscription_id}"
+    )));
+  }
+
+  let inscription =
+    content(&index, inscription_id)?.ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+  let mut check = PreviewCheckJson {
+    content_type: inscription.content_type().map(|s| s.to_string()),
+    dependencies: Vec::new(),
+    depth: 0,
+    external_urls: Vec::new(),
+    id: inscription_id,
+    missing_dependencies: Vec::new(),
+    recursive_endpoints: Vec::new(),
+    safe: false,
+    scripted: matches!(inscription.media(), Media::Iframe),
+    truncated: false,
+  };
+
+  let mut endpoints = 0;
+  let mut external_urls = BTreeSet::new();
+  let mut seen = HashSet::from([inscription_id]);
+  let mut queue = VecDeque::from([(inscription, 0)]);
+
+  while let Some((inscription, depth)) = queue.pop_front() {
+    if !scanned(inscription.media()) {
+      continue;
+    }
+
+    let Some(body) = recursion::decoded_body(&inscription) else {
+      continue;
+    };
+
+    endpoints |= recursion::body_endpoints(&body);
+    external_urls.extend(external(&body));
+
+    for dependency in dependencies(&body) {
+      if !seen.insert(dependency) {
+        continue;
+      }
+
+      if depth + 1 > MAX_DEPTH || check.dependencies.len() == MAX_DEPENDENCIES {
+        check.truncated = true;
+        continue;
+      }
+
+      check.depth = check.depth.max(depth + 1);
+
+      match content(&index, dependency)? {
+        Some(inscription) => {
+          check.dependencies.push(dependency);
+          queue.push_back((inscription, depth + 1));
+        }
+        None => check.missing_dependencies.push(dependency),
+      }
+    }
+  }
+
+  check.external_urls = external_urls.into_iter().collect();
+  check.recursive_endpoints = recursion::load_recursive_endpoints(endpoints);
+  check.safe = check.external_urls.is_empty() && !check.truncated;
+
+  Ok(Json(check).into_response())
+}
+
+/// The inscription whose content is served for `inscription_id`, which is
+/// its delegate, if it has one.
+fn content(index: &Index, inscription_id: InscriptionId) -> Result<Option<Inscription>> {
+  let Some(inscription) = index.get_inscription_by_id(inscription_id)? else {
+    return Ok(None);
+  };
+
+  match inscription.delegate() {
+    Some(delegate) => index.get_inscription_by_id(delegate),
+    None => Ok(Some(inscription)),
+  }
+}
+
+fn scanned(media: Media) -> bool {
+  matches!(
+    media,
+    Media::Iframe | Media::Code(media::Language::Css | media::Language::JavaScript)
+  )
+}
+
+/// Inscriptions referenced by `/content/<INSCRIPTION_ID>` paths in `body`.
+fn dependencies(body: &[u8]) -> Vec<InscriptionId> {
+  const PATH: &[u8] = b"/content/";
+
+  body
+    .windows(PATH.len())
+    .enumerate()
+    .filter(|(_, window)| *window == PATH)
+    .filter_map(|(i, _)| {
+      let id = token(&body[i + PATH.len()..], |c| c.is_ascii_alphanumeric());
+      std::str::from_utf8(id).ok()?.parse().ok()
+    })
+    .collect()
+}
+
+/// Absolute URLs in `body` with a scheme in [`EXTERNAL_SCHEMES`], other than
+/// XML namespaces.
+fn external(body: &[u8]) -> Vec<String> {
+  let lowercase = body.to_ascii_lowercase();
+
+  let mut urls = Vec::new();
+
+  for scheme in EXTERNAL_SCHEMES {
+    let scheme = scheme.as_bytes();
+
+    for (i, _) in lowercase
+      .windows(scheme.len())
+      .enumerate()
+      .filter(|(_, window)| *window == scheme)
+    {
+      if i > 0 && lowercase[i - 1].is_ascii_alphanumeric() {
+        continue;
+      }
+
+      let url = token(&body[i..], |c| {
+        !c.is_ascii_whitespace() && !b"\"'`<>()\\".contains(&c)
+      });
+
+      if url.len() == scheme.len() || lowercase[i..].starts_with(NAMESPACE_PREFIX.as_bytes()) {
+        continue;
+      }
+
+      urls.push(String::from_utf8_lossy(url).into());
+    }
+  }
+
+  urls
+}
+
+fn token(bytes: &[u8], predicate: impl Fn(u8) -> bool) -> &[u8] {
+  let end = bytes
+    .iter()
+    .position(|c| !predicate(*c))
+    .unwrap_or(bytes.len());
+
+  &bytes[..end]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn dependencies_are_found() {
+    assert_eq!(
+      dependencies(
+        format!(
+          "<script src=/content/{}></script><img src='/content/{}'><a href=/content/foo>",
+          inscription_id(1),
+          inscription_id(2)
+        )
+        .as_bytes()
+      ),
+      [inscription_id(1), inscription_id(2)]
+    );
+
+    assert_eq!(dependencies(b"/content/"), Vec::new());
+  }
+
+  #[test]
+  fn external_urls_are_found() {
+    assert_eq!(
+      external(
+        br#"<svg xmlns="http://www.w3.org/2000/svg"><image href="HTTPS://foo.com/a.png"/></svg>
+        <script>new WebSocket(`wss://bar.com`);fetch('http://baz.com/?q=1')</script>"#
+      ),
+      [
+        "http://baz.com/?q=1",
+        "HTTPS://foo.com/a.png",
+        "wss://bar.com",
+      ]
+    );
+  }
+
+  #[test]
+  fn non_urls_are_ignored() {
+    assert_eq!(
+      external(b"news://foo http:// /r/blockheight"),
+      Vec::<String>::new()
+    );
+  }
+}
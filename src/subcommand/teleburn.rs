@@ -1,20 +1,38 @@
-use super::*;
+use {
+  super::*,
+  crate::teleburn::{Ethereum, TargetChain},
+};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Teleburn {
   #[arg(help = "Generate teleburn addresses for inscription <DESTINATION>.")]
   destination: InscriptionId,
+  #[arg(
+    long,
+    value_enum,
+    help = "Generate teleburn address for <CHAIN>. May be given multiple times. [default: all chains]"
+  )]
+  chain: Vec<TargetChain>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
-  pub ethereum: crate::teleburn::Ethereum,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub ethereum: Option<Ethereum>,
 }
 
 impl Teleburn {
   pub(crate) fn run(self) -> SubcommandResult {
+    if self.chain.contains(&TargetChain::Solana) {
+      bail!("teleburn addresses are not supported for solana");
+    }
+
+    let all = self.chain.is_empty();
+
+    let target = |chain| all || self.chain.contains(&chain);
+
     Ok(Box::new(Output {
-      ethereum: self.destination.into(),
+      ethereum: target(TargetChain::Ethereum).then(|| self.destination.into()),
     }))
   }
 }
@@ -0,0 +1,17 @@
+use super::*;
+
+pub mod brc20;
+
+#[derive(Debug, Parser)]
+pub(crate) enum TestFixtureSubcommand {
+  #[command(about = "Generate a BRC-20 fixture by running a scenario on regtest")]
+  Brc20(brc20::Brc20),
+}
+
+impl TestFixtureSubcommand {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self {
+      Self::Brc20(brc20) => brc20.run(options),
+    }
+  }
+}
@@ -0,0 +1,323 @@
+use {
+  super::*,
+  crate::subcommand::wallet::{
+    bitcoin_rpc_client_for_wallet_command,
+    brc20::{inscribe, Brc20 as WalletBrc20, Operation},
+    get_change_address,
+    inscribe::inscriber::Inscriber,
+  },
+  clap::ValueEnum,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scenario {
+  DeployMintRace,
+  TransferAsFee,
+  ReorgAcrossSettle,
+}
+
+enum Step {
+  Wallet(&'static str),
+  Transfer(&'static str),
+  SpendTransferAsFee,
+  Send(&'static str),
+  Mine(u64),
+  Reorg { depth: u32, blocks: u64 },
+}
+
+const DEPLOY: Step = Step::Wallet("deploy --fee-rate 1 --tick ordi --max 21000 --limit 1000");
+const MINT: Step = Step::Wallet("mint --fee-rate 1 --tick ordi --amount 1000");
+
+impl Scenario {
+  fn steps(self) -> &'static [Step] {
+    match self {
+      // The early mint pays a higher fee rate, so Bitcoin Core includes it in
+      // the block before the deploy it races.
+      Self::DeployMintRace => &[
+        Step::Wallet("mint --fee-rate 10 --tick ordi --amount 1000"),
+        DEPLOY,
+        Step::Mine(1),
+        MINT,
+        Step::Mine(1),
+      ],
+      Self::TransferAsFee => &[
+        DEPLOY,
+        Step::Mine(1),
+        MINT,
+        Step::Mine(1),
+        Step::Transfer("500"),
+        Step::Mine(1),
+        Step::SpendTransferAsFee,
+        Step::Mine(1),
+      ],
+      Self::ReorgAcrossSettle => &[
+        DEPLOY,
+        Step::Mine(1),
+        MINT,
+        Step::Mine(1),
+        Step::Send("500"),
+        Step::Mine(1),
+        Step::Reorg {
+          depth: 1,
+          blocks: 2,
+        },
+      ],
+    }
+  }
+}
+
+impl Display for Step {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Wallet(args) => write!(f, "wallet brc20 {args}"),
+      Self::Transfer(amount) => write!(f, "inscribe transfer of {amount} ordi"),
+      Self::SpendTransferAsFee => write!(f, "spend transfer inscription as fee"),
+      Self::Send(amount) => write!(f, "send {amount} ordi"),
+      Self::Mine(blocks) => write!(f, "mine {blocks} blocks"),
+      Self::Reorg { depth, blocks } => {
+        write!(f, "replace last {depth} blocks with {blocks} blocks")
+      }
+    }
+  }
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Brc20 {
+  #[arg(long, value_enum, help = "Run <SCENARIO>.")]
+  scenario: Scenario,
+  #[arg(
+    long,
+    help = "Write blocks and expected operations to directory <OUTPUT>."
+  )]
+  output: PathBuf,
+  #[arg(long, default_value = "ord", help = "Inscribe with wallet <WALLET>.")]
+  wallet: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct StepRecord {
+  pub block_count: u32,
+  pub description: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct OperationRecord {
+  pub height: u32,
+  pub inscription: InscriptionId,
+  pub operation: Operation,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub blocks: u32,
+  pub operations: Vec<OperationRecord>,
+  pub scenario: Scenario,
+  pub steps: Vec<StepRecord>,
+}
+
+impl Brc20 {
+  /// Run the scenario against the regtest node, then write every block of the
+  /// resulting chain to `<OUTPUT>/blocks/<HEIGHT>.blk` and every block
+  /// replaced by a reorg to `<OUTPUT>/stale/<HEIGHT>.blk`, consensus-encoded.
+  /// The index does not track BRC-20 token state, so `<OUTPUT>/fixture.json`
+  /// records the BRC-20 operations that were inscribed in each block, which
+  /// protocol handlers can be checked against.
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    ensure!(
+      options.chain() == Chain::Regtest,
+      "test fixtures can only be generated on regtest"
+    );
+
+    ensure!(
+      !self.output.exists(),
+      "fixture destination `{}` already exists",
+      self.output.display()
+    );
+
+    let client = bitcoin_rpc_client_for_wallet_command(self.wallet.clone(), &options)?;
+
+    let address = get_change_address(&client, options.chain())?;
+
+    // coinbase outputs must mature before they can be spent
+    client.generate_to_address(101, &address)?;
+
+    fs::create_dir_all(self.output.join("blocks"))?;
+    fs::create_dir_all(self.output.join("stale"))?;
+
+    let mut steps = Vec::new();
+    let mut transfer = None;
+
+    for step in self.scenario.steps() {
+      let block_count = u32::try_from(client.get_block_count()?)? + 1;
+
+      match step {
+        Step::Wallet(args) => {
+          WalletBrc20::try_parse_from(std::iter::once("brc20").chain(args.split_whitespace()))?
+            .run(self.wallet.clone(), options.clone())?;
+        }
+        Step::Transfer(amount) => {
+          let mut inscriber = Inscriber::load(self.wallet.clone(), &options)?;
+
+          let owner = get_change_address(&inscriber.client, inscriber.chain)?;
+
+          let output = inscribe(
+            &mut inscriber,
+            &[Operation::Transfer {
+              tick: "ordi".into(),
+              amt: amount.to_string(),
+            }],
+            vec![owner],
+            FeeRate::try_from(1.0)?,
+            TARGET_POSTAGE,
+          )?;
+
+          transfer = Some(output.inscriptions[0].location.outpoint);
+        }
+        Step::SpendTransferAsFee => {
+          let transfer = transfer.ok_or_else(|| anyhow!("no transfer inscription to spend"))?;
+          self.spend_as_fee(&options, transfer)?;
+        }
+        Step::Send(amount) => {
+          let to = get_change_address(&client, options.chain())?;
+
+          WalletBrc20::try_parse_from([
+            "brc20",
+            "send",
+            "--fee-rate",
+            "1",
+            "--tick",
+            "ordi",
+            "--amount",
+            amount,
+            "--to",
+            &to.to_string(),
+          ])?
+          .run(self.wallet.clone(), options.clone())?;
+        }
+        Step::Mine(blocks) => {
+          client.generate_to_address(*blocks, &address)?;
+        }
+        Step::Reorg { depth, blocks } => {
+          let first = block_count - depth;
+
+          for height in first..block_count {
+            let block = client.get_block(&client.get_block_hash(height.into())?)?;
+            fs::write(
+              self.output.join("stale").join(format!("{height}.blk")),
+              consensus::encode::serialize(&block),
+            )?;
+          }
+
+          client.invalidate_block(&client.get_block_hash(first.into())?)?;
+          client.generate_to_address(*blocks, &address)?;
+        }
+      }
+
+      steps.push(StepRecord {
+        block_count,
+        description: step.to_string(),
+      });
+    }
+
+    let blocks = u32::try_from(client.get_block_count()?)? + 1;
+
+    for height in 0..blocks {
+      let block = client.get_block(&client.get_block_hash(height.into())?)?;
+      fs::write(
+        self.output.join("blocks").join(format!("{height}.blk")),
+        consensus::encode::serialize(&block),
+      )?;
+    }
+
+    let index = Index::open(&options)?;
+    index.update()?;
+
+    let mut operations = Vec::new();
+
+    for height in 0..index.block_count()? {
+      for inscription_id in index.get_inscriptions_in_block(height)? {
+        let Some(inscription) = index.get_inscription_by_id(inscription_id)? else {
+          continue;
+        };
+
+        if let Some(operation) = Operation::from_inscription(&inscription) {
+          operations.push(OperationRecord {
+            height,
+            inscription: inscription_id,
+            operation,
+          });
+        }
+      }
+    }
+
+    let output = Output {
+      blocks,
+      operations,
+      scenario: self.scenario,
+      steps,
+    };
+
+    fs::write(
+      self.output.join("fixture.json"),
+      serde_json::to_string_pretty(&output)?,
+    )?;
+
+    Ok(Box::new(output))
+  }
+
+  /// Spend the transfer inscription at `transfer` after a cardinal output,
+  /// with a single output smaller than the cardinal, so that the inscription
+  /// is paid to the miner as fee.
+  fn spend_as_fee(&self, options: &Options, transfer: OutPoint) -> Result {
+    let inscriber = Inscriber::load(self.wallet.clone(), options)?;
+
+    let mut cardinal = None;
+
+    for (outpoint, amount) in inscriber.utxos() {
+      if *outpoint == transfer
+        || !inscriber
+          .index
+          .get_inscriptions_on_output(*outpoint)?
+          .is_empty()
+      {
+        continue;
+      }
+
+      if cardinal.map_or(true, |(_, max)| *amount > max) {
+        cardinal = Some((*outpoint, *amount));
+      }
+    }
+
+    let (cardinal, amount) =
+      cardinal.ok_or_else(|| anyhow!("wallet contains no cardinal outputs"))?;
+
+    let transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: [cardinal, transfer]
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: vec![TxOut {
+        script_pubkey: get_change_address(&inscriber.client, inscriber.chain)?.script_pubkey(),
+        value: amount.to_sat() - 1000,
+      }],
+    };
+
+    let signed = inscriber
+      .client
+      .sign_raw_transaction_with_wallet(&transaction, None, None)?;
+
+    ensure!(signed.complete, "failed to sign transaction");
+
+    inscriber.client.send_raw_transaction(&signed.hex)?;
+
+    Ok(())
+  }
+}
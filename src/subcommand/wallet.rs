@@ -1,5 +1,8 @@
 use {
   super::*,
+  crate::index::remote::RemoteIndex,
+  base64::Engine,
+  bitcoin::psbt::Psbt,
   bitcoin::secp256k1::{
     rand::{self, RngCore},
     All, Secp256k1,
@@ -9,29 +12,59 @@ use {
     Network,
   },
   bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, Timestamp},
+  clap::ValueEnum,
   fee_rate::FeeRate,
+  internal::InternalWallet,
   miniscript::descriptor::{Descriptor, DescriptorSecretKey, DescriptorXKey, Wildcard},
+  postage::{PostageOptions, PostagePolicy},
+  signer::Signer,
   transaction_builder::TransactionBuilder,
 };
 
 pub mod balance;
+pub mod brc20;
+pub mod broadcast_psbt;
+pub mod bump_fee;
 pub mod cardinals;
 pub mod create;
 pub mod etch;
+pub mod export_descriptors;
+pub mod import_descriptors;
 pub mod inscribe;
 pub mod inscriptions;
+mod internal;
+pub mod mint_collection;
+pub mod offer;
 pub mod outputs;
+pub mod postage;
 pub mod receive;
 mod restore;
+pub mod rune;
 pub mod sats;
 pub mod send;
+pub mod signer;
 pub mod transaction_builder;
 pub mod transactions;
+pub mod utxos;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Wallet {
+  #[arg(
+    long,
+    value_enum,
+    default_value_t,
+    help = "Manage wallet with <BACKEND>. `core` uses a Bitcoin Core wallet. `internal` keeps keys and outputs in the ord data directory, for nodes without wallet support, and requires `--index-addresses`."
+  )]
+  pub(crate) backend: Backend,
   #[arg(long, default_value = "ord", help = "Use wallet named <WALLET>.")]
   pub(crate) name: String,
+  #[arg(
+    long,
+    help = "Query the JSON API of the ord server at <INDEX_URL> instead of the local index. Supported by `cardinals`, `inscriptions`, and `outputs`."
+  )]
+  pub(crate) index_url: Option<String>,
+  #[command(flatten)]
+  pub(crate) postage: PostageOptions,
   #[command(subcommand)]
   pub(crate) subcommand: Subcommand,
 }
@@ -40,45 +73,143 @@ pub(crate) struct Wallet {
 pub(crate) enum Subcommand {
   #[command(about = "Get wallet balance")]
   Balance,
+  #[command(subcommand, about = "BRC-20 commands")]
+  Brc20(brc20::Brc20),
+  #[command(about = "Finalize and broadcast signed PSBTs")]
+  BroadcastPsbt(broadcast_psbt::BroadcastPsbt),
+  #[command(about = "Bump fee of unconfirmed transaction")]
+  BumpFee(bump_fee::BumpFee),
   #[command(about = "Create new wallet")]
   Create(create::Create),
   #[command(about = "Create rune")]
   Etch(etch::Etch),
+  #[command(about = "Export wallet output descriptors without private keys")]
+  ExportDescriptors,
+  #[command(about = "Create watch-only wallet from exported output descriptors")]
+  ImportDescriptors(import_descriptors::ImportDescriptors),
   #[command(about = "Create inscription")]
-  Inscribe(inscribe::Inscribe),
+  Inscribe(Box<inscribe::Inscribe>),
   #[command(about = "List wallet inscriptions")]
   Inscriptions,
+  #[command(about = "Inscribe directory of files as children of a parent")]
+  MintCollection(mint_collection::MintCollection),
+  #[command(subcommand, about = "Offer commands")]
+  Offer(offer::Offer),
   #[command(about = "Generate receive address")]
   Receive,
   #[command(about = "Restore wallet")]
   Restore(restore::Restore),
+  #[command(subcommand, about = "Rune commands")]
+  Rune(rune::Runes),
   #[command(about = "List wallet satoshis")]
   Sats(sats::Sats),
   #[command(about = "Send sat or inscription")]
   Send(send::Send),
   #[command(about = "See wallet transactions")]
   Transactions(transactions::Transactions),
+  #[command(subcommand, about = "Manage unspent outputs")]
+  Utxos(utxos::Utxos),
   #[command(about = "List all unspent outputs in wallet")]
   Outputs,
   #[command(about = "List unspent cardinal outputs in wallet")]
   Cardinals,
 }
 
+#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Backend {
+  #[default]
+  Core,
+  Internal,
+}
+
 impl Wallet {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    if self.backend == Backend::Internal {
+      ensure!(
+        self.index_url.is_none(),
+        "`--index-url` is not supported by the internal wallet backend"
+      );
+
+      return internal::run(self.name, options, self.subcommand);
+    }
+
+    if self.index_url.is_some() {
+      ensure!(
+        matches!(
+          self.subcommand,
+          Subcommand::Cardinals | Subcommand::Inscriptions | Subcommand::Outputs
+        ),
+        "`--index-url` is only supported by `ord wallet cardinals`, `ord wallet inscriptions`, and `ord wallet outputs`"
+      );
+    }
+
+    let policy = self.postage.policy()?;
+
     match self.subcommand {
       Subcommand::Balance => balance::run(self.name, options),
-      Subcommand::Create(create) => create.run(self.name, options),
-      Subcommand::Etch(etch) => etch.run(self.name, options),
-      Subcommand::Inscribe(inscribe) => inscribe.run(self.name, options),
-      Subcommand::Inscriptions => inscriptions::run(self.name, options),
+      Subcommand::Brc20(brc20) => brc20.run(self.name, options),
+      Subcommand::BroadcastPsbt(broadcast_psbt) => broadcast_psbt.run(self.name, options),
+      Subcommand::BumpFee(bump_fee) => bump_fee.run(self.name, options),
+      Subcommand::Create(create) => create.run(self.name, options, self.backend),
+      Subcommand::Etch(etch) => etch.run(self.name, options, policy),
+      Subcommand::ExportDescriptors => export_descriptors::run(self.name, options),
+      Subcommand::ImportDescriptors(import_descriptors) => {
+        import_descriptors.run(self.name, options)
+      }
+      Subcommand::Inscribe(inscribe) => inscribe.run(self.name, options, policy),
+      Subcommand::Inscriptions => inscriptions::run(self.name, options, self.index_url),
+      Subcommand::MintCollection(mint_collection) => {
+        mint_collection.run(self.name, options, policy)
+      }
+      Subcommand::Offer(offer) => offer.run(self.name, options),
       Subcommand::Receive => receive::run(self.name, options),
-      Subcommand::Restore(restore) => restore.run(self.name, options),
+      Subcommand::Restore(restore) => restore.run(self.name, options, self.backend),
+      Subcommand::Rune(rune) => rune.run(self.name, options, policy),
       Subcommand::Sats(sats) => sats.run(self.name, options),
-      Subcommand::Send(send) => send.run(self.name, options),
+      Subcommand::Send(send) => send.run(self.name, options, policy),
       Subcommand::Transactions(transactions) => transactions.run(self.name, options),
-      Subcommand::Outputs => outputs::run(self.name, options),
-      Subcommand::Cardinals => cardinals::run(self.name, options),
+      Subcommand::Utxos(utxos) => utxos.run(self.name, options),
+      Subcommand::Outputs => outputs::run(self.name, options, self.index_url),
+      Subcommand::Cardinals => cardinals::run(self.name, options, self.index_url),
+    }
+  }
+}
+
+/// Index queried by wallet commands that support `--index-url`.
+pub(crate) enum WalletIndex {
+  Local(Box<Index>),
+  Remote(RemoteIndex),
+}
+
+impl WalletIndex {
+  pub(crate) fn open(options: &Options, index_url: Option<String>) -> Result<Self> {
+    match index_url {
+      Some(url) => Ok(Self::Remote(RemoteIndex::new(&url)?)),
+      None => {
+        let index = Index::open(options)?;
+        index.update()?;
+        Ok(Self::Local(Box::new(index)))
+      }
+    }
+  }
+
+  pub(crate) fn get_unspent_outputs(&self, client: &Client) -> Result<BTreeMap<OutPoint, Amount>> {
+    match self {
+      Self::Local(index) => get_unspent_outputs(client, index),
+      Self::Remote(index) => {
+        index.check_sync(client)?;
+        list_unspent_outputs(client)
+      }
+    }
+  }
+
+  pub(crate) fn get_inscriptions(
+    &self,
+    utxos: &BTreeMap<OutPoint, Amount>,
+  ) -> Result<BTreeMap<SatPoint, InscriptionId>> {
+    match self {
+      Self::Local(index) => index.get_inscriptions(utxos),
+      Self::Remote(index) => index.get_inscriptions(utxos),
     }
   }
 }
@@ -87,6 +218,14 @@ pub(crate) fn get_unspent_outputs(
   client: &Client,
   index: &Index,
 ) -> Result<BTreeMap<OutPoint, Amount>> {
+  let utxos = list_unspent_outputs(client)?;
+
+  index.check_sync(&utxos)?;
+
+  Ok(utxos)
+}
+
+fn list_unspent_outputs(client: &Client) -> Result<BTreeMap<OutPoint, Amount>> {
   let mut utxos = BTreeMap::new();
   utxos.extend(
     client
@@ -113,8 +252,6 @@ pub(crate) fn get_unspent_outputs(
     );
   }
 
-  index.check_sync(&utxos)?;
-
   Ok(utxos)
 }
 
@@ -157,6 +294,54 @@ pub(crate) fn get_change_address(client: &Client, chain: Chain) -> Result<Addres
   )
 }
 
+/// Create a base64-encoded PSBT for `transaction`, so that it can be signed
+/// offline. Inputs that already have a witness are finalized with it. Spent
+/// outputs not in `prevouts` are looked up with Bitcoin Core, which also adds
+/// key derivations for the wallet's inputs.
+pub(crate) fn create_psbt(
+  client: &Client,
+  transaction: &Transaction,
+  prevouts: &BTreeMap<OutPoint, TxOut>,
+) -> Result<String> {
+  let mut unsigned_transaction = transaction.clone();
+
+  for input in &mut unsigned_transaction.input {
+    input.script_sig = ScriptBuf::new();
+    input.witness = Witness::new();
+  }
+
+  let mut psbt = Psbt::from_unsigned_tx(unsigned_transaction)?;
+
+  for (psbt_input, input) in psbt.inputs.iter_mut().zip(&transaction.input) {
+    let outpoint = input.previous_output;
+
+    psbt_input.witness_utxo = Some(match prevouts.get(&outpoint) {
+      Some(tx_out) => tx_out.clone(),
+      None => client
+        .get_raw_transaction(&outpoint.txid, None)?
+        .output
+        .into_iter()
+        .nth(outpoint.vout.try_into().unwrap())
+        .ok_or_else(|| anyhow!("output {outpoint} not found"))?,
+    });
+
+    if !input.witness.is_empty() {
+      psbt_input.final_script_witness = Some(input.witness.clone());
+    }
+  }
+
+  Ok(
+    client
+      .wallet_process_psbt(
+        &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+        Some(false),
+        None,
+        Some(true),
+      )?
+      .psbt,
+  )
+}
+
 pub(crate) fn initialize(wallet: String, options: &Options, seed: [u8; 64]) -> Result {
   check_version(options.bitcoin_rpc_client(None)?)?.create_wallet(
     &wallet,
@@ -168,8 +353,27 @@ pub(crate) fn initialize(wallet: String, options: &Options, seed: [u8; 64]) -> R
 
   let client = options.bitcoin_rpc_client(Some(wallet))?;
 
-  let network = options.chain().network();
+  for (change, descriptor) in [false, true]
+    .into_iter()
+    .zip(derive_descriptors(options.chain().network(), seed)?)
+  {
+    client.import_descriptors(ImportDescriptors {
+      descriptor,
+      timestamp: Timestamp::Now,
+      active: Some(true),
+      range: None,
+      next_index: None,
+      internal: Some(change),
+      label: None,
+    })?;
+  }
 
+  Ok(())
+}
+
+/// Taproot output descriptors, including private keys, for the receive and
+/// change addresses of the wallet with `seed`, in that order.
+pub(crate) fn derive_descriptors(network: Network, seed: [u8; 64]) -> Result<[String; 2]> {
   let secp = Secp256k1::new();
 
   let master_private_key = ExtendedPrivKey::new_master(network, &seed)?;
@@ -185,26 +389,28 @@ pub(crate) fn initialize(wallet: String, options: &Options, seed: [u8; 64]) -> R
 
   let derived_private_key = master_private_key.derive_priv(&secp, &derivation_path)?;
 
-  for change in [false, true] {
-    derive_and_import_descriptor(
-      &client,
+  Ok([
+    derive_descriptor(
       &secp,
       (fingerprint, derivation_path.clone()),
       derived_private_key,
-      change,
-    )?;
-  }
-
-  Ok(())
+      false,
+    )?,
+    derive_descriptor(
+      &secp,
+      (fingerprint, derivation_path),
+      derived_private_key,
+      true,
+    )?,
+  ])
 }
 
-fn derive_and_import_descriptor(
-  client: &Client,
+fn derive_descriptor(
   secp: &Secp256k1<All>,
   origin: (Fingerprint, DerivationPath),
   derived_private_key: ExtendedPrivKey,
   change: bool,
-) -> Result {
+) -> Result<String> {
   let secret_key = DescriptorSecretKey::XPrv(DescriptorXKey {
     origin: Some(origin),
     xkey: derived_private_key,
@@ -221,17 +427,7 @@ fn derive_and_import_descriptor(
 
   let desc = Descriptor::new_tr(public_key, None)?;
 
-  client.import_descriptors(ImportDescriptors {
-    descriptor: desc.to_string_with_secret(&key_map),
-    timestamp: Timestamp::Now,
-    active: Some(true),
-    range: None,
-    next_index: None,
-    internal: Some(change),
-    label: None,
-  })?;
-
-  Ok(())
+  Ok(desc.to_string_with_secret(&key_map))
 }
 
 pub(crate) fn bitcoin_rpc_client_for_wallet_command(
@@ -1,9 +1,16 @@
-use {super::*, std::collections::BTreeSet};
+use {
+  super::*,
+  brc20::{Operation, Quantity, MAX_DECIMALS},
+  std::collections::BTreeSet,
+};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Output {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub brc20: Option<BTreeMap<String, String>>,
   pub cardinal: u64,
   pub ordinal: u64,
+  pub pending: u64,
   #[serde(default, skip_serializing_if = "Option::is_none")]
   pub runes: Option<BTreeMap<Rune, u128>>,
   #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -19,12 +26,45 @@ pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
 
   let unspent_outputs = get_unspent_outputs(&client, &index)?;
 
-  let inscription_outputs = index
-    .get_inscriptions(&unspent_outputs)?
+  let pending = client.get_balances()?.mine.untrusted_pending.to_sat();
+
+  Ok(Box::new(balance(&index, unspent_outputs, pending)?))
+}
+
+/// Balance of `unspent_outputs`, broken down by what they hold.
+pub(crate) fn balance(
+  index: &Index,
+  unspent_outputs: BTreeMap<OutPoint, Amount>,
+  pending: u64,
+) -> Result<Output> {
+  let inscriptions = index.get_inscriptions(&unspent_outputs)?;
+
+  let inscription_outputs = inscriptions
     .keys()
     .map(|satpoint| satpoint.outpoint)
     .collect::<BTreeSet<OutPoint>>();
 
+  let mut transferable = BTreeMap::<String, u128>::new();
+
+  for inscription_id in inscriptions.values() {
+    let Some(inscription) = index.get_inscription_by_id(*inscription_id)? else {
+      continue;
+    };
+
+    let Some(Operation::Transfer { tick, amt }) = Operation::from_inscription(&inscription) else {
+      continue;
+    };
+
+    let Ok(amount) = amt
+      .parse::<Quantity>()
+      .and_then(|quantity| quantity.amount(MAX_DECIMALS))
+    else {
+      continue;
+    };
+
+    *transferable.entry(tick.to_lowercase()).or_default() += amount;
+  }
+
   let mut cardinal = 0;
   let mut ordinal = 0;
   let mut runes = BTreeMap::new();
@@ -45,13 +85,30 @@ pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
     }
   }
 
-  Ok(Box::new(Output {
+  Ok(Output {
+    brc20: (!transferable.is_empty()).then(|| {
+      transferable
+        .into_iter()
+        .map(|(tick, amount)| {
+          (
+            tick,
+            Pile {
+              amount,
+              divisibility: MAX_DECIMALS,
+              symbol: None,
+            }
+            .to_string(),
+          )
+        })
+        .collect()
+    }),
     cardinal,
     ordinal,
+    pending,
     runes: index.has_rune_index().then_some(runes),
     runic: index.has_rune_index().then_some(runic),
     total: cardinal + ordinal + runic,
-  }))
+  })
 }
 
 #[cfg(test)]
@@ -62,14 +119,16 @@ mod tests {
   fn runes_and_runic_fields_are_not_present_if_none() {
     assert_eq!(
       serde_json::to_string(&Output {
+        brc20: None,
         cardinal: 0,
         ordinal: 0,
+        pending: 0,
         runes: None,
         runic: None,
         total: 0
       })
       .unwrap(),
-      r#"{"cardinal":0,"ordinal":0,"total":0}"#
+      r#"{"cardinal":0,"ordinal":0,"pending":0,"total":0}"#
     );
   }
 }
@@ -0,0 +1,413 @@
+use {
+  super::*,
+  crate::subcommand::wallet::inscribe::{
+    batch::{Batch, Mode},
+    inscriber::Inscriber,
+  },
+};
+
+pub mod deploy;
+pub mod mint;
+pub mod send;
+
+pub(crate) const MAX_DECIMALS: u8 = 18;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Brc20 {
+  #[command(about = "Inscribe deployment")]
+  Deploy(deploy::Deploy),
+  #[command(about = "Inscribe mints")]
+  Mint(mint::Mint),
+  #[command(about = "Inscribe transfer and send it")]
+  Send(send::Send),
+}
+
+impl Brc20 {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    match self {
+      Self::Deploy(deploy) => deploy.run(wallet, options),
+      Self::Mint(mint) => mint.run(wallet, options),
+      Self::Send(send) => send.run(wallet, options),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Tick(String);
+
+impl FromStr for Tick {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ensure!(
+      matches!(s.len(), 4 | 5),
+      "BRC-20 tick `{s}` must be 4 or 5 bytes long"
+    );
+
+    Ok(Self(s.into()))
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Quantity(String, Decimal);
+
+impl Quantity {
+  /// Amount in base units for a token with `decimals` decimals, checked
+  /// against the limits the indexer enforces.
+  pub(crate) fn amount(&self, decimals: u8) -> Result<u128> {
+    ensure!(
+      fractional_digits(&self.0) <= decimals.into(),
+      "BRC-20 amount `{}` has more than {decimals} decimals",
+      self.0
+    );
+
+    self
+      .1
+      .to_amount(decimals)
+      .ok()
+      .filter(|amount| *amount <= u128::from(u64::MAX) * 10u128.pow(decimals.into()))
+      .with_context(|| format!("BRC-20 amount `{}` exceeds maximum of {}", self.0, u64::MAX))
+  }
+}
+
+/// Significant digits after the decimal point of `s`.
+fn fractional_digits(s: &str) -> usize {
+  s.split_once('.')
+    .map(|(_integer, fraction)| fraction.trim_end_matches('0').len())
+    .unwrap_or_default()
+}
+
+impl FromStr for Quantity {
+  type Err = Error;
+
+  /// BRC-20 amounts are strings of digits with an optional decimal point
+  /// between them. Signs, exponents, whitespace, and leading or trailing
+  /// decimal points make the amount, and the operation carrying it, invalid.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ensure!(
+      !s.is_empty()
+        && !s.starts_with('.')
+        && !s.ends_with('.')
+        && s.matches('.').count() <= 1
+        && s.chars().all(|c| c.is_ascii_digit() || c == '.'),
+      "BRC-20 amount `{s}` is not a decimal number"
+    );
+
+    ensure!(
+      fractional_digits(s) <= MAX_DECIMALS.into(),
+      "BRC-20 amount `{s}` has more than {MAX_DECIMALS} decimals"
+    );
+
+    let decimal = s
+      .parse::<Decimal>()
+      .map_err(|_| anyhow!("BRC-20 amount `{s}` exceeds maximum of {}", u64::MAX))?;
+
+    let quantity = Self(s.into(), decimal);
+
+    ensure!(
+      quantity.amount(MAX_DECIMALS)? > 0,
+      "BRC-20 amount must be greater than zero"
+    );
+
+    Ok(quantity)
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Operation {
+  Deploy {
+    tick: String,
+    max: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lim: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dec: Option<String>,
+  },
+  Mint {
+    tick: String,
+    amt: String,
+  },
+  Transfer {
+    tick: String,
+    amt: String,
+  },
+}
+
+impl Operation {
+  pub(crate) fn inscription(&self) -> Result<Inscription> {
+    #[derive(Serialize)]
+    struct Payload<'a> {
+      p: &'static str,
+      #[serde(flatten)]
+      operation: &'a Operation,
+    }
+
+    Ok(Inscription::new(
+      Some("text/plain;charset=utf-8".into()),
+      Some(serde_json::to_vec(&Payload {
+        p: "brc-20",
+        operation: self,
+      })?),
+    ))
+  }
+
+  /// Check the operation's fields against the rules that don't depend on
+  /// token state, returning the first violation.
+  pub(crate) fn validate(&self) -> Result {
+    match self {
+      Self::Deploy {
+        tick,
+        max,
+        lim,
+        dec,
+      } => {
+        tick.parse::<Tick>()?;
+
+        let decimals = match dec {
+          Some(dec) => dec
+            .parse::<u8>()
+            .ok()
+            .filter(|decimals| *decimals <= MAX_DECIMALS)
+            .ok_or_else(|| {
+              anyhow!("BRC-20 decimals `{dec}` must be an integer no greater than {MAX_DECIMALS}")
+            })?,
+          None => MAX_DECIMALS,
+        };
+
+        let max_amount = max.parse::<Quantity>()?.amount(decimals)?;
+
+        if let Some(lim) = lim {
+          ensure!(
+            lim.parse::<Quantity>()?.amount(decimals)? <= max_amount,
+            "BRC-20 limit `{lim}` exceeds maximum supply `{max}`"
+          );
+        }
+      }
+      Self::Mint { tick, amt } | Self::Transfer { tick, amt } => {
+        tick.parse::<Tick>()?;
+        amt.parse::<Quantity>()?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Parse a BRC-20 operation from the body of `inscription`, returning
+  /// `None` if it is not a BRC-20 payload.
+  pub(crate) fn from_inscription(inscription: &Inscription) -> Option<Self> {
+    #[derive(Deserialize)]
+    struct Payload {
+      p: String,
+      #[serde(flatten)]
+      operation: Operation,
+    }
+
+    let payload = serde_json::from_slice::<Payload>(inscription.body()?).ok()?;
+
+    (payload.p == "brc-20").then_some(payload.operation)
+  }
+}
+
+/// Inscribe `operations` in a single commit and reveal transaction pair.
+pub(crate) fn inscribe(
+  inscriber: &mut Inscriber,
+  operations: &[Operation],
+  destinations: Vec<Address>,
+  fee_rate: FeeRate,
+  postage: Amount,
+) -> Result<inscribe::Output> {
+  let (output, _reveal_tx) = inscriber.inscribe(&Batch {
+    commit_fee_rate: fee_rate,
+    destinations,
+    inscriptions: operations
+      .iter()
+      .map(Operation::inscription)
+      .collect::<Result<Vec<Inscription>>>()?,
+    mode: Mode::SeparateOutputs,
+    postage,
+    reveal_fee_rate: fee_rate,
+    ..Default::default()
+  })?;
+
+  Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tick_length_is_checked() {
+    assert!("ordi".parse::<Tick>().is_ok());
+    assert!("sats5".parse::<Tick>().is_ok());
+    assert_eq!(
+      "ord".parse::<Tick>().unwrap_err().to_string(),
+      "BRC-20 tick `ord` must be 4 or 5 bytes long"
+    );
+  }
+
+  #[test]
+  fn quantity_is_checked() {
+    assert!("100".parse::<Quantity>().is_ok());
+    assert!("0.5".parse::<Quantity>().is_ok());
+    assert!("0".parse::<Quantity>().is_err());
+    assert!("0.0000000000000000001".parse::<Quantity>().is_err());
+    assert!("foo".parse::<Quantity>().is_err());
+    assert_eq!(
+      "1e5".parse::<Quantity>().unwrap_err().to_string(),
+      "BRC-20 amount `1e5` is not a decimal number"
+    );
+    assert_eq!(
+      "99999999999999999999999999999999999999999"
+        .parse::<Quantity>()
+        .unwrap_err()
+        .to_string(),
+      "BRC-20 amount `99999999999999999999999999999999999999999` exceeds maximum of 18446744073709551615"
+    );
+    assert_eq!(
+      "0.1111111111111111111111111111111111111111"
+        .parse::<Quantity>()
+        .unwrap_err()
+        .to_string(),
+      "BRC-20 amount `0.1111111111111111111111111111111111111111` has more than 18 decimals"
+    );
+    assert!("18446744073709551616".parse::<Quantity>().is_err());
+    assert_eq!("1.5".parse::<Quantity>().unwrap().amount(1).unwrap(), 15);
+    assert!("1.55".parse::<Quantity>().unwrap().amount(1).is_err());
+  }
+
+  #[test]
+  fn deploy_payload_omits_unset_fields() {
+    assert_eq!(
+      Operation::Deploy {
+        tick: "ordi".into(),
+        max: "21000000".into(),
+        lim: None,
+        dec: None,
+      }
+      .inscription()
+      .unwrap()
+      .body
+      .unwrap(),
+      br#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"21000000"}"#,
+    );
+  }
+
+  #[test]
+  fn transfer_payload() {
+    assert_eq!(
+      Operation::Transfer {
+        tick: "ordi".into(),
+        amt: "100".into(),
+      }
+      .inscription()
+      .unwrap()
+      .body
+      .unwrap(),
+      br#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"100"}"#,
+    );
+  }
+
+  #[test]
+  fn operations_are_validated() {
+    #[track_caller]
+    fn case(body: &str, error: Option<&str>) {
+      let operation =
+        Operation::from_inscription(&inscription("text/plain;charset=utf-8", body)).unwrap();
+
+      assert_eq!(
+        operation
+          .validate()
+          .err()
+          .map(|err| err.to_string())
+          .as_deref(),
+        error
+      );
+    }
+
+    case(
+      r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1"}"#,
+      None,
+    );
+    case(
+      r#"{"p":"brc-20","op":"mint","tick":"ord","amt":"1"}"#,
+      Some("BRC-20 tick `ord` must be 4 or 5 bytes long"),
+    );
+    case(
+      r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"0"}"#,
+      Some("BRC-20 amount must be greater than zero"),
+    );
+    case(
+      r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"100","lim":"10","dec":"2"}"#,
+      None,
+    );
+    case(
+      r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"100","lim":"101"}"#,
+      Some("BRC-20 limit `101` exceeds maximum supply `100`"),
+    );
+    case(
+      r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"100","dec":"19"}"#,
+      Some("BRC-20 decimals `19` must be an integer no greater than 18"),
+    );
+  }
+
+  /// Payload vectors, checked against `Operation::from_inscription` and
+  /// `Operation::validate`. Rules which depend on token state, such as the
+  /// first deploy of a tick winning, are enforced by indexers, not by `ord`,
+  /// and so aren't covered.
+  #[test]
+  fn golden_vectors() {
+    #[derive(Deserialize)]
+    struct Vector {
+      description: String,
+      payload: String,
+      operation: Option<Operation>,
+      error: Option<String>,
+    }
+
+    let vectors = serde_json::from_str::<Vec<Vector>>(include_str!("brc20/vectors.json")).unwrap();
+
+    for vector in vectors {
+      let operation =
+        Operation::from_inscription(&inscription("text/plain;charset=utf-8", &vector.payload));
+
+      let error = operation
+        .as_ref()
+        .and_then(|operation| operation.validate().err())
+        .map(|err| err.to_string());
+
+      assert_eq!(operation, vector.operation, "{}", vector.description);
+      assert_eq!(error, vector.error, "{}", vector.description);
+    }
+  }
+
+  #[test]
+  fn operation_from_inscription() {
+    let operation = Operation::Transfer {
+      tick: "ordi".into(),
+      amt: "100".into(),
+    };
+
+    assert_eq!(
+      Operation::from_inscription(&operation.inscription().unwrap()),
+      Some(operation),
+    );
+
+    assert_eq!(
+      Operation::from_inscription(&Inscription::new(
+        Some("text/plain".into()),
+        Some(br#"{"p":"sns","op":"transfer","tick":"ordi","amt":"100"}"#.to_vec()),
+      )),
+      None,
+    );
+
+    assert_eq!(
+      Operation::from_inscription(&Inscription::new(
+        Some("text/plain".into()),
+        Some(b"foo".to_vec()),
+      )),
+      None,
+    );
+  }
+}
@@ -0,0 +1,94 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Deploy {
+  #[arg(long, help = "Set token decimals to <DECIMALS>. Default 18.")]
+  decimals: Option<u8>,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Limit mints to <LIMIT> tokens each. Default <MAX>.")]
+  limit: Option<Quantity>,
+  #[arg(long, help = "Set maximum supply to <MAX>.")]
+  max: Quantity,
+  #[arg(
+    long,
+    help = "Amount of postage to include in the deploy inscription. Default `10000sat`."
+  )]
+  postage: Option<Amount>,
+  #[arg(long, help = "Deploy <TICK>.")]
+  tick: Tick,
+}
+
+impl Deploy {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let operation = self.operation()?;
+
+    let mut inscriber = Inscriber::load(wallet, &options)?;
+
+    let destination = get_change_address(&inscriber.client, inscriber.chain)?;
+
+    Ok(Box::new(inscribe(
+      &mut inscriber,
+      &[operation],
+      vec![destination],
+      self.fee_rate,
+      self.postage.unwrap_or(TARGET_POSTAGE),
+    )?))
+  }
+
+  fn operation(&self) -> Result<Operation> {
+    let decimals = self.decimals.unwrap_or(MAX_DECIMALS);
+
+    ensure!(
+      decimals <= MAX_DECIMALS,
+      "<DECIMALS> must be equal to or less than {MAX_DECIMALS}"
+    );
+
+    let max = self.max.amount(decimals)?;
+
+    if let Some(limit) = &self.limit {
+      ensure!(
+        limit.amount(decimals)? <= max,
+        "<LIMIT> must be equal to or less than <MAX>"
+      );
+    }
+
+    Ok(Operation::Deploy {
+      tick: self.tick.0.clone(),
+      max: self.max.0.clone(),
+      lim: self.limit.as_ref().map(|limit| limit.0.clone()),
+      dec: self.decimals.map(|decimals| decimals.to_string()),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn limit_and_decimals_are_validated() {
+    #[track_caller]
+    fn case(args: &str, error: &str) {
+      let deploy = Deploy::try_parse_from(
+        format!("deploy --fee-rate 1 --tick ordi {args}").split_whitespace(),
+      )
+      .unwrap();
+
+      assert_eq!(deploy.operation().unwrap_err().to_string(), error);
+    }
+
+    case(
+      "--max 100 --limit 101",
+      "<LIMIT> must be equal to or less than <MAX>",
+    );
+    case(
+      "--max 100 --decimals 19",
+      "<DECIMALS> must be equal to or less than 18",
+    );
+    case(
+      "--max 1.5 --decimals 0",
+      "BRC-20 amount `1.5` has more than 0 decimals",
+    );
+  }
+}
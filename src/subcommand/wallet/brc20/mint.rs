@@ -0,0 +1,73 @@
+use super::*;
+
+const MAX_MINTS_PER_BATCH: u64 = 100;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Mint {
+  #[arg(long, help = "Mint <AMOUNT> of <TICK> per inscription.")]
+  amount: Quantity,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Amount of postage to include in each mint inscription. Default `10000sat`."
+  )]
+  postage: Option<Amount>,
+  #[arg(
+    long,
+    default_value = "1",
+    help = "Inscribe <REPEAT> mints. Mints are batched into commit and reveal transaction pairs of at most 100 mints each."
+  )]
+  repeat: u64,
+  #[arg(long, help = "Mint <TICK>.")]
+  tick: Tick,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub batches: Vec<inscribe::Output>,
+  pub total_fees: u64,
+}
+
+impl Mint {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    ensure!(self.repeat > 0, "<REPEAT> must be greater than zero");
+
+    let mut inscriber = Inscriber::load(wallet, &options)?;
+
+    let postage = self.postage.unwrap_or(TARGET_POSTAGE);
+
+    let mut batches = Vec::new();
+    let mut remaining = self.repeat;
+
+    while remaining > 0 {
+      let count = remaining.min(MAX_MINTS_PER_BATCH);
+
+      let operations = (0..count)
+        .map(|_| Operation::Mint {
+          tick: self.tick.0.clone(),
+          amt: self.amount.0.clone(),
+        })
+        .collect::<Vec<Operation>>();
+
+      let destinations = (0..count)
+        .map(|_| get_change_address(&inscriber.client, inscriber.chain))
+        .collect::<Result<Vec<Address>>>()?;
+
+      batches.push(inscribe(
+        &mut inscriber,
+        &operations,
+        destinations,
+        self.fee_rate,
+        postage,
+      )?);
+
+      remaining -= count;
+    }
+
+    Ok(Box::new(Output {
+      total_fees: batches.iter().map(|batch| batch.total_fees).sum(),
+      batches,
+    }))
+  }
+}
@@ -0,0 +1,113 @@
+use {
+  super::*, bitcoin::secp256k1::constants::SCHNORR_SIGNATURE_SIZE,
+  bitcoincore_rpc::bitcoincore_rpc_json::SignRawTransactionInput,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Send {
+  #[arg(long, help = "Send <AMOUNT> of <TICK>.")]
+  amount: Quantity,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Amount of postage to include in the transfer inscription. Default `10000sat`."
+  )]
+  postage: Option<Amount>,
+  #[arg(long, help = "Send <TICK>.")]
+  tick: Tick,
+  #[arg(long, help = "Send transfer inscription to <TO>.")]
+  to: Address<NetworkUnchecked>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub commit: Txid,
+  pub inscription: InscriptionId,
+  pub reveal: Txid,
+  pub send: Txid,
+  pub total_fees: u64,
+}
+
+impl Send {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let to = options.network_params().require_network(self.to)?;
+
+    let mut inscriber = Inscriber::load(wallet, &options)?;
+
+    let postage = self.postage.unwrap_or(TARGET_POSTAGE);
+
+    let owner = get_change_address(&inscriber.client, inscriber.chain)?;
+
+    let output = inscribe(
+      &mut inscriber,
+      &[Operation::Transfer {
+        tick: self.tick.0,
+        amt: self.amount.0,
+      }],
+      vec![owner.clone()],
+      self.fee_rate,
+      postage,
+    )?;
+
+    let location = output.inscriptions[0].location;
+
+    let mut transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: location.outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::from_slice(&[&[0; SCHNORR_SIGNATURE_SIZE]]),
+      }],
+      output: vec![TxOut {
+        script_pubkey: to.script_pubkey(),
+        value: 0,
+      }],
+    };
+
+    let fee = self.fee_rate.fee(transaction.vsize());
+
+    let value = postage
+      .checked_sub(fee)
+      .map(|value| value.to_sat())
+      .unwrap_or_default();
+
+    let dust = to.script_pubkey().dust_value().to_sat();
+
+    ensure!(
+      value >= dust,
+      "postage of {postage} is too small to pay a fee of {fee} and send a transfer inscription above the dust limit of {dust} sat",
+    );
+
+    transaction.input[0].witness = Witness::new();
+    transaction.output[0].value = value;
+
+    // The transfer transaction spends the unconfirmed reveal output, so the
+    // wallet may not know about it yet and needs to be told what it spends.
+    let signed = inscriber.client.sign_raw_transaction_with_wallet(
+      &transaction,
+      Some(&[SignRawTransactionInput {
+        txid: location.outpoint.txid,
+        vout: location.outpoint.vout,
+        script_pub_key: owner.script_pubkey(),
+        redeem_script: None,
+        amount: Some(postage),
+      }]),
+      None,
+    )?;
+
+    ensure!(signed.complete, "failed to sign transfer transaction");
+
+    let send = inscriber.client.send_raw_transaction(&signed.hex)?;
+
+    Ok(Box::new(Output {
+      commit: output.commit,
+      inscription: output.inscriptions[0].id,
+      reveal: output.reveal,
+      send,
+      total_fees: output.total_fees + (postage.to_sat() - value),
+    }))
+  }
+}
@@ -0,0 +1,62 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct BroadcastPsbt {
+  #[arg(
+    required = true,
+    help = "Finalize and broadcast base64-encoded <PSBTS>, in order."
+  )]
+  psbts: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub transactions: Vec<Txid>,
+}
+
+impl BroadcastPsbt {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let transactions = self
+      .psbts
+      .iter()
+      .map(|psbt| Self::finalize(psbt))
+      .collect::<Result<Vec<Transaction>>>()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let mut txids = Vec::new();
+
+    for transaction in transactions {
+      txids.push(client.send_raw_transaction(&transaction)?);
+    }
+
+    Ok(Box::new(Output {
+      transactions: txids,
+    }))
+  }
+
+  /// Ord wallets only contain taproot outputs, so inputs are either already
+  /// finalized or signed with a key path signature.
+  pub(crate) fn finalize(psbt: &str) -> Result<Transaction> {
+    let psbt = Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(psbt)
+        .context("failed to decode PSBT")?,
+    )
+    .context("failed to deserialize PSBT")?;
+
+    let mut transaction = psbt.unsigned_tx.clone();
+
+    for (i, (input, psbt_input)) in transaction.input.iter_mut().zip(&psbt.inputs).enumerate() {
+      input.witness = if let Some(witness) = &psbt_input.final_script_witness {
+        witness.clone()
+      } else if let Some(signature) = psbt_input.tap_key_sig {
+        Witness::from_slice(&[signature.to_vec()])
+      } else {
+        bail!("PSBT input {i} is not signed");
+      };
+    }
+
+    Ok(transaction)
+  }
+}
@@ -0,0 +1,233 @@
+use {
+  super::*, bitcoin::secp256k1::constants::SCHNORR_SIGNATURE_SIZE,
+  bitcoincore_rpc::bitcoincore_rpc_json::SignRawTransactionInput,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct BumpFee {
+  #[arg(long, help = "Bump fee rate to <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(help = "Bump fee of unconfirmed transaction <TXID>.")]
+  txid: Txid,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Copy, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub enum Method {
+  Cpfp,
+  Rbf,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub method: Method,
+  pub original: Txid,
+  pub transaction: Txid,
+}
+
+impl BumpFee {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let chain = options.chain();
+
+    ensure!(
+      client
+        .get_raw_transaction_info(&self.txid, None)?
+        .confirmations
+        .unwrap_or_default()
+        == 0,
+      "transaction {} is already confirmed",
+      self.txid,
+    );
+
+    let transaction = client.get_raw_transaction(&self.txid, None)?;
+
+    let unspent_outputs = get_unspent_outputs(&client, &index)?;
+
+    let inscriptions = index.get_inscriptions(&unspent_outputs)?;
+
+    let runic_outputs =
+      index.get_runic_outputs(&unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>())?;
+
+    // Wallet transactions only contain key path spends, which Bitcoin Core can
+    // re-sign. Reveal transactions contain a script path spend signed with an
+    // ephemeral key, so their fee can only be bumped with a child.
+    let replaceable = transaction.is_explicitly_rbf()
+      && transaction
+        .input
+        .iter()
+        .all(|input| input.witness.len() == 1);
+
+    let (method, txid) = if replaceable {
+      send::Send::lock_non_cardinal_outputs(
+        &client,
+        &inscriptions,
+        &runic_outputs,
+        unspent_outputs,
+      )?;
+
+      #[derive(Deserialize)]
+      struct BumpFeeResult {
+        txid: Txid,
+      }
+
+      let result = client.call::<BumpFeeResult>(
+        "bumpfee",
+        &[
+          serde_json::to_value(self.txid)?,
+          serde_json::json!({ "fee_rate": self.fee_rate.n() }),
+        ],
+      )?;
+
+      (Method::Rbf, result.txid)
+    } else {
+      let locked_outputs = get_locked_outputs(&client)?;
+
+      let cardinal = unspent_outputs
+        .iter()
+        .filter(|(outpoint, _)| {
+          !runic_outputs.contains(outpoint)
+            && !locked_outputs.contains(outpoint)
+            && !inscriptions
+              .keys()
+              .any(|satpoint| satpoint.outpoint == **outpoint)
+        })
+        .max_by_key(|(_, amount)| **amount)
+        .map(|(outpoint, amount)| (*outpoint, *amount))
+        .ok_or_else(|| anyhow!("wallet contains no cardinal utxos"))?;
+
+      let txid = self.cpfp(&client, chain, &transaction, cardinal)?;
+
+      (Method::Cpfp, txid)
+    };
+
+    Ok(Box::new(Output {
+      method,
+      original: self.txid,
+      transaction: txid,
+    }))
+  }
+
+  fn cpfp(
+    &self,
+    client: &Client,
+    chain: Chain,
+    parent: &Transaction,
+    (cardinal, cardinal_value): (OutPoint, Amount),
+  ) -> Result<Txid> {
+    let mut parent_fee = parent
+      .input
+      .iter()
+      .map(|input| -> Result<u64> {
+        Ok(
+          client
+            .get_raw_transaction(&input.previous_output.txid, None)?
+            .output[usize::try_from(input.previous_output.vout).unwrap()]
+          .value,
+        )
+      })
+      .sum::<Result<u64>>()?;
+
+    parent_fee -= parent.output.iter().map(|output| output.value).sum::<u64>();
+
+    let mut spent = None;
+
+    for (vout, output) in parent.output.iter().enumerate() {
+      let Ok(address) = chain.address_from_script(&output.script_pubkey) else {
+        continue;
+      };
+
+      #[derive(Deserialize)]
+      struct AddressInfo {
+        ismine: bool,
+      }
+
+      if client
+        .call::<AddressInfo>("getaddressinfo", &[address.to_string().into()])?
+        .ismine
+      {
+        spent = Some((
+          OutPoint {
+            txid: parent.txid(),
+            vout: vout.try_into().unwrap(),
+          },
+          output.clone(),
+        ));
+        break;
+      }
+    }
+
+    let (outpoint, output) =
+      spent.ok_or_else(|| anyhow!("transaction {} has no outputs in wallet", parent.txid()))?;
+
+    // The first output receives the parent output's sats, and with them any
+    // inscriptions, unchanged.
+    let mut child = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: [outpoint, cardinal]
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::from_slice(&[&[0; SCHNORR_SIGNATURE_SIZE]]),
+        })
+        .collect(),
+      output: vec![
+        TxOut {
+          script_pubkey: get_change_address(client, chain)?.script_pubkey(),
+          value: output.value,
+        },
+        TxOut {
+          script_pubkey: get_change_address(client, chain)?.script_pubkey(),
+          value: 0,
+        },
+      ],
+    };
+
+    let package_fee = self.fee_rate.fee(parent.vsize() + child.vsize()).to_sat();
+
+    ensure!(
+      package_fee > parent_fee,
+      "transaction {} already pays a fee rate of at least {} sat/vB",
+      parent.txid(),
+      self.fee_rate.n(),
+    );
+
+    let fee = package_fee - parent_fee;
+
+    let dust = child.output[1].script_pubkey.dust_value().to_sat();
+
+    ensure!(
+      cardinal_value.to_sat() >= fee + dust,
+      "wallet contains no cardinal utxo large enough to pay a fee of {fee} sat",
+    );
+
+    child.output[1].value = cardinal_value.to_sat() - fee;
+
+    for input in &mut child.input {
+      input.witness = Witness::new();
+    }
+
+    let signed = client.sign_raw_transaction_with_wallet(
+      &child,
+      Some(&[SignRawTransactionInput {
+        txid: outpoint.txid,
+        vout: outpoint.vout,
+        script_pub_key: output.script_pubkey,
+        redeem_script: None,
+        amount: Some(Amount::from_sat(output.value)),
+      }]),
+      None,
+    )?;
+
+    ensure!(signed.complete, "failed to sign child transaction");
+
+    Ok(client.send_raw_transaction(&signed.hex)?)
+  }
+}
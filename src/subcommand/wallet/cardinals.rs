@@ -6,14 +6,12 @@ pub struct CardinalUtxo {
   pub amount: u64,
 }
 
-pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
-  let index = Index::open(&options)?;
-
-  index.update()?;
+pub(crate) fn run(wallet: String, options: Options, index_url: Option<String>) -> SubcommandResult {
+  let index = WalletIndex::open(&options, index_url)?;
 
   let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
 
-  let unspent_outputs = get_unspent_outputs(&client, &index)?;
+  let unspent_outputs = index.get_unspent_outputs(&client)?;
 
   let inscribed_utxos = index
     .get_inscriptions(&unspent_outputs)?
@@ -17,13 +17,18 @@ pub(crate) struct Create {
 }
 
 impl Create {
-  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+  pub(crate) fn run(self, wallet: String, options: Options, backend: Backend) -> SubcommandResult {
     let mut entropy = [0; 16];
     rand::thread_rng().fill_bytes(&mut entropy);
 
     let mnemonic = Mnemonic::from_entropy(&entropy)?;
 
-    wallet::initialize(wallet, &options, mnemonic.to_seed(self.passphrase.clone()))?;
+    let seed = mnemonic.to_seed(self.passphrase.clone());
+
+    match backend {
+      Backend::Core => wallet::initialize(wallet, &options, seed)?,
+      Backend::Internal => InternalWallet::create(wallet, &options, seed)?,
+    }
 
     Ok(Box::new(Output {
       mnemonic,
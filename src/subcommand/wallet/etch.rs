@@ -2,25 +2,46 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Etch {
+  #[clap(
+    long,
+    help = "Close open mints at unix timestamp <DEADLINE>. Requires `--limit` or `--term`."
+  )]
+  deadline: Option<u32>,
   #[clap(long, help = "Set divisibility to <DIVISIBILITY>.")]
   divisibility: u8,
   #[clap(long, help = "Etch with fee rate of <FEE_RATE> sats/vB.")]
   fee_rate: FeeRate,
+  #[clap(
+    long,
+    help = "Allow open mints of up to <LIMIT> runes per transaction."
+  )]
+  limit: Option<Decimal>,
   #[clap(long, help = "Etch rune <RUNE>. May contain `.` or `•`as spacers.")]
   rune: SpacedRune,
   #[clap(long, help = "Set supply to <SUPPLY>.")]
   supply: Decimal,
   #[clap(long, help = "Set currency symbol to <SYMBOL>.")]
   symbol: char,
+  #[clap(
+    long,
+    help = "Close open mints <TERM> blocks after etching. Without `--limit`, mints are unlimited."
+  )]
+  term: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Output {
+  pub postage: u64,
   pub transaction: Txid,
 }
 
 impl Etch {
-  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+  pub(crate) fn run(
+    self,
+    wallet: String,
+    options: Options,
+    policy: PostagePolicy,
+  ) -> SubcommandResult {
     let index = Index::open(&options)?;
 
     ensure!(
@@ -58,23 +79,56 @@ impl Etch {
       "<DIVISIBILITY> must be equal to or less than 38"
     );
 
+    let limit = self
+      .limit
+      .map(|limit| limit.to_amount(self.divisibility))
+      .transpose()?;
+
+    if let Some(limit) = limit {
+      ensure!(
+        limit <= crate::runes::MAX_LIMIT,
+        "<LIMIT> must be equal to or less than {}",
+        crate::runes::MAX_LIMIT,
+      );
+    }
+
+    ensure!(
+      self.deadline.is_none() || limit.is_some() || self.term.is_some(),
+      "`--deadline` requires `--limit` or `--term`",
+    );
+
+    let supply = self.supply.to_amount(self.divisibility)?;
+
+    if let Some(limit) = limit {
+      ensure!(
+        supply <= limit,
+        "<SUPPLY> must be equal to or less than <LIMIT> for runes with open mints",
+      );
+    }
+
     let destination = get_change_address(&client, options.chain())?;
 
+    let postage = policy.postage(None, &destination.script_pubkey())?;
+
     let runestone = Runestone {
       etching: Some(Etching {
-        deadline: None,
+        deadline: self.deadline,
         divisibility: self.divisibility,
-        limit: None,
+        limit,
         rune: Some(rune),
         spacers,
         symbol: Some(self.symbol),
-        term: None,
+        term: self.term,
       }),
-      edicts: vec![Edict {
-        amount: self.supply.to_amount(self.divisibility)?,
-        id: 0,
-        output: 1,
-      }],
+      edicts: if supply > 0 {
+        vec![Edict {
+          amount: supply,
+          id: 0,
+          output: 1,
+        }]
+      } else {
+        Vec::new()
+      },
       default_output: None,
       burn: false,
     };
@@ -98,7 +152,7 @@ impl Etch {
         },
         TxOut {
           script_pubkey: destination.script_pubkey(),
-          value: TARGET_POSTAGE.to_sat(),
+          value: postage.to_sat(),
         },
       ],
     };
@@ -123,6 +177,9 @@ impl Etch {
 
     let transaction = client.send_raw_transaction(&signed_transaction)?;
 
-    Ok(Box::new(Output { transaction }))
+    Ok(Box::new(Output {
+      postage: postage.to_sat(),
+      transaction,
+    }))
   }
 }
@@ -0,0 +1,31 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ExportedDescriptor {
+  pub active: bool,
+  pub desc: String,
+  pub internal: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub descriptors: Vec<ExportedDescriptor>,
+}
+
+pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
+  let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+  Ok(Box::new(Output {
+    descriptors: client
+      .list_descriptors(None)?
+      .descriptors
+      .into_iter()
+      .filter(|descriptor| descriptor.desc.starts_with("tr("))
+      .map(|descriptor| ExportedDescriptor {
+        active: descriptor.active,
+        desc: descriptor.desc,
+        internal: descriptor.internal.unwrap_or_default(),
+      })
+      .collect(),
+  }))
+}
@@ -0,0 +1,66 @@
+use {super::*, export_descriptors::ExportedDescriptor};
+
+#[derive(Debug, Parser)]
+pub(crate) struct ImportDescriptors {
+  #[arg(help = "Import descriptors from <FILE>, as printed by `ord wallet export-descriptors`.")]
+  file: PathBuf,
+}
+
+impl ImportDescriptors {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let descriptors =
+      serde_json::from_reader::<_, export_descriptors::Output>(File::open(&self.file)?)
+        .with_context(|| format!("failed to parse descriptors in {}", self.file.display()))?
+        .descriptors;
+
+    Self::check(&descriptors)?;
+
+    check_version(options.bitcoin_rpc_client(None)?)?.create_wallet(
+      &wallet,
+      Some(true),
+      Some(true),
+      None,
+      None,
+    )?;
+
+    let client = options.bitcoin_rpc_client(Some(wallet))?;
+
+    for descriptor in descriptors {
+      let response =
+        client.import_descriptors(bitcoincore_rpc::bitcoincore_rpc_json::ImportDescriptors {
+          descriptor: descriptor.desc,
+          timestamp: Timestamp::Time(0),
+          active: Some(descriptor.active),
+          range: None,
+          next_index: None,
+          internal: Some(descriptor.internal),
+          label: None,
+        })?;
+
+      for result in response {
+        ensure!(result.success, "descriptor import failed");
+      }
+    }
+
+    Ok(Box::new(Empty {}))
+  }
+
+  fn check(descriptors: &[ExportedDescriptor]) -> Result {
+    ensure!(
+      descriptors.len() == 2
+        && descriptors
+          .iter()
+          .all(|descriptor| descriptor.desc.starts_with("tr(")),
+      "watch-only wallets must contain exactly two `tr` descriptors, like those exported from an `ord` wallet"
+    );
+
+    ensure!(
+      descriptors
+        .iter()
+        .all(|descriptor| !descriptor.desc.contains("prv")),
+      "descriptors must not contain private keys"
+    );
+
+    Ok(())
+  }
+}
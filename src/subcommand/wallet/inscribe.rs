@@ -12,11 +12,16 @@ use {
     taproot::Signature,
     taproot::{ControlBlock, LeafVersion, TapLeafHash, TaprootBuilder},
   },
-  bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, SignRawTransactionInput, Timestamp},
+  bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, Timestamp},
   bitcoincore_rpc::Client,
+  reqwest::Url,
+  std::io::Read,
 };
 
-mod batch;
+pub(crate) mod batch;
+pub(crate) mod inscriber;
+
+const MAX_URL_CONTENT_SIZE: u64 = 4_000_000;
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct InscriptionInfo {
@@ -27,32 +32,36 @@ pub struct InscriptionInfo {
 #[derive(Serialize, Deserialize)]
 pub struct Output {
   pub commit: Txid,
+  pub commit_psbt: Option<String>,
+  pub fee_rate: f64,
   pub inscriptions: Vec<InscriptionInfo>,
   pub parent: Option<InscriptionId>,
+  pub postage: u64,
   pub reveal: Txid,
+  pub reveal_psbt: Option<String>,
   pub total_fees: u64,
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct ParentInfo {
-  destination: Address,
-  id: InscriptionId,
-  location: SatPoint,
-  tx_out: TxOut,
+  pub(crate) destination: Address,
+  pub(crate) id: InscriptionId,
+  pub(crate) location: SatPoint,
+  pub(crate) tx_out: TxOut,
 }
 
 #[derive(Debug, Parser)]
 #[clap(
   group = ArgGroup::new("source")
       .required(true)
-      .args(&["file", "batch"]),
+      .args(&["file", "batch", "stdin", "url"]),
 )]
 pub(crate) struct Inscribe {
   #[arg(
     long,
     help = "Inscribe multiple inscriptions defined in a yaml <BATCH_FILE>.",
     conflicts_with_all = &[
-      "cbor_metadata", "destination", "file", "json_metadata", "metaprotocol", "parent", "postage", "reinscribe", "satpoint"
+      "cbor_metadata", "content_type", "destination", "file", "json_metadata", "metaprotocol", "parent", "postage", "reinscribe", "satpoint", "stdin", "url"
     ]
   )]
   pub(crate) batch: Option<PathBuf>,
@@ -69,12 +78,26 @@ pub(crate) struct Inscribe {
   pub(crate) commit_fee_rate: Option<FeeRate>,
   #[arg(long, help = "Compress inscription content with brotli.")]
   pub(crate) compress: bool,
+  #[arg(
+    long,
+    default_value = "6",
+    help = "Estimate fee rate for confirmation within <CONFIRMATION_TARGET> blocks with `--fee-rate auto`."
+  )]
+  pub(crate) confirmation_target: u16,
+  #[arg(
+    long,
+    help = "Use <CONTENT_TYPE> as inscription content type, instead of inferring it from file extension or URL response."
+  )]
+  pub(crate) content_type: Option<String>,
   #[arg(long, help = "Send inscription to <DESTINATION>.")]
   pub(crate) destination: Option<Address<NetworkUnchecked>>,
   #[arg(long, help = "Don't sign or broadcast transactions.")]
   pub(crate) dry_run: bool,
-  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
-  pub(crate) fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Use fee rate of <FEE_RATE> sats/vB. `auto` estimates a fee rate from Bitcoin Core's fee estimates and mempool."
+  )]
+  pub(crate) fee_rate: FeeRateArg,
   #[arg(long, help = "Inscribe sat with contents of <FILE>.")]
   pub(crate) file: Option<PathBuf>,
   #[arg(
@@ -99,16 +122,49 @@ pub(crate) struct Inscribe {
     help = "Amount of postage to include in the inscription. Default `10000sat`."
   )]
   pub(crate) postage: Option<Amount>,
-  #[clap(long, help = "Allow reinscription.")]
+  #[clap(
+    long = "allow-reinscription",
+    alias = "reinscribe",
+    help = "Allow inscribing a sat that is already inscribed. By default, inscribing will fail if the index shows that the target sat carries an inscription."
+  )]
   pub(crate) reinscribe: bool,
   #[arg(long, help = "Inscribe <SATPOINT>.")]
   pub(crate) satpoint: Option<SatPoint>,
   #[arg(long, help = "Inscribe <SAT>.", conflicts_with = "satpoint")]
   pub(crate) sat: Option<Sat>,
+  #[arg(
+    long,
+    help = "Inscribe sat with contents read from standard input.",
+    requires = "content_type"
+  )]
+  pub(crate) stdin: bool,
+  #[arg(
+    long,
+    value_enum,
+    default_value_t,
+    help = "Sign transactions with <SIGNER>. `hwi` signs with a hardware device using the `hwi` command."
+  )]
+  pub(crate) signer: Signer,
+  #[arg(
+    long,
+    help = "Don't sign or broadcast transactions, and print PSBTs to be signed offline and broadcast with `ord wallet broadcast-psbt`.",
+    conflicts_with = "dry_run"
+  )]
+  pub(crate) unsigned: bool,
+  #[arg(
+    long,
+    help = "Inscribe sat with contents downloaded from <URL>. Content may be at most 4 MB."
+  )]
+  pub(crate) url: Option<Url>,
 }
 
 impl Inscribe {
-  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+  pub(crate) fn run(
+    self,
+    wallet: String,
+    options: Options,
+    policy: PostagePolicy,
+  ) -> SubcommandResult {
     let metadata = Inscribe::parse_metadata(self.cbor_metadata, self.json_metadata)?;
 
     let index = Index::open(&options)?;
@@ -124,6 +180,8 @@ impl Inscribe {
 
     let chain = options.chain();
 
+    let fee_rate = self.fee_rate.resolve(&client, self.confirmation_target)?;
+
     let postage;
     let destinations;
     let inscriptions;
@@ -131,15 +189,19 @@ impl Inscribe {
     let parent_info;
     let sat;
 
-    match (self.file, self.batch) {
-      (Some(file), None) => {
+    match self.batch {
+      None => {
         parent_info = Inscribe::get_parent_info(self.parent, &index, &utxos, &client, chain)?;
 
-        postage = self.postage.unwrap_or(TARGET_POSTAGE);
+        postage = self.postage.unwrap_or(policy.target);
+
+        let (body, content_type) =
+          Inscribe::content(self.file, self.stdin, self.url, self.content_type)?;
 
-        inscriptions = vec![Inscription::from_file(
+        inscriptions = vec![Inscription::from_content(
           chain,
-          file,
+          body,
+          &content_type,
           self.parent,
           None,
           self.metaprotocol,
@@ -156,15 +218,16 @@ impl Inscribe {
           None => get_change_address(&client, chain)?,
         }];
       }
-      (None, Some(batch)) => {
+      Some(batch) => {
         let batchfile = Batchfile::load(&batch)?;
 
-        parent_info = Inscribe::get_parent_info(batchfile.parent, &index, &utxos, &client, chain)?;
+        parent_info =
+          Inscribe::get_parent_info(batchfile.parent(), &index, &utxos, &client, chain)?;
 
         postage = batchfile
           .postage
           .map(Amount::from_sat)
-          .unwrap_or(TARGET_POSTAGE);
+          .unwrap_or(policy.target);
 
         (inscriptions, destinations) = batchfile.inscriptions(
           &client,
@@ -183,7 +246,10 @@ impl Inscribe {
 
         sat = batchfile.sat;
       }
-      _ => unreachable!(),
+    }
+
+    for destination in &destinations {
+      policy.postage(Some(postage), &destination.script_pubkey())?;
     }
 
     let satpoint = if let Some(sat) = sat {
@@ -200,8 +266,8 @@ impl Inscribe {
       self.satpoint
     };
 
-    Batch {
-      commit_fee_rate: self.commit_fee_rate.unwrap_or(self.fee_rate),
+    let (output, _commit_tx, _reveal_tx) = Batch {
+      commit_fee_rate: self.commit_fee_rate.unwrap_or(fee_rate),
       destinations,
       dry_run: self.dry_run,
       inscriptions,
@@ -211,10 +277,87 @@ impl Inscribe {
       parent_info,
       postage,
       reinscribe: self.reinscribe,
-      reveal_fee_rate: self.fee_rate,
+      reveal_fee_rate: fee_rate,
       satpoint,
+      signer: self.signer,
+      unsigned: self.unsigned,
+    }
+    .inscribe(chain, &index, &client, &locked_utxos, runic_utxos, &utxos)?;
+
+    Ok(Box::new(output))
+  }
+
+  /// Read inscription content and content type from a file, standard input,
+  /// or URL. `content_type`, if set, takes precedence over the content type
+  /// inferred from the file extension or URL response.
+  fn content(
+    file: Option<PathBuf>,
+    stdin: bool,
+    url: Option<Url>,
+    content_type: Option<String>,
+  ) -> Result<(Vec<u8>, String)> {
+    if let Some(file) = file {
+      let body = fs::read(&file).with_context(|| format!("io error reading {}", file.display()))?;
+
+      let content_type = match content_type {
+        Some(content_type) => content_type,
+        None => Media::content_type_for_path(&file)?.0.into(),
+      };
+
+      return Ok((body, content_type));
+    }
+
+    if stdin {
+      let mut body = Vec::new();
+      io::stdin()
+        .read_to_end(&mut body)
+        .context("io error reading standard input")?;
+
+      return Ok((body, content_type.unwrap()));
     }
-    .inscribe(chain, &index, &client, &locked_utxos, runic_utxos, &utxos)
+
+    let url = url.unwrap();
+
+    ensure!(
+      matches!(url.scheme(), "http" | "https"),
+      "URL `{url}` must use http or https"
+    );
+
+    let response = reqwest::blocking::get(url.clone())
+      .and_then(|response| response.error_for_status())
+      .with_context(|| format!("failed to download {url}"))?;
+
+    let content_type = match content_type {
+      Some(content_type) => content_type,
+      None => response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(str::to_string)
+        .with_context(|| {
+          format!("{url} did not return a content type, set one with `--content-type`")
+        })?,
+    };
+
+    if let Some(length) = response.content_length() {
+      ensure!(
+        length <= MAX_URL_CONTENT_SIZE,
+        "content at {url} is {length} bytes, which exceeds the maximum of {MAX_URL_CONTENT_SIZE} bytes"
+      );
+    }
+
+    let mut body = Vec::new();
+    response
+      .take(MAX_URL_CONTENT_SIZE + 1)
+      .read_to_end(&mut body)
+      .with_context(|| format!("failed to download {url}"))?;
+
+    ensure!(
+      u64::try_from(body.len()).unwrap() <= MAX_URL_CONTENT_SIZE,
+      "content at {url} exceeds the maximum of {MAX_URL_CONTENT_SIZE} bytes"
+    );
+
+    Ok((body, content_type))
   }
 
   fn parse_metadata(cbor: Option<PathBuf>, json: Option<PathBuf>) -> Result<Option<Vec<u8>>> {
@@ -236,7 +379,7 @@ impl Inscribe {
     }
   }
 
-  fn get_parent_info(
+  pub(crate) fn get_parent_info(
     parent: Option<InscriptionId>,
     index: &Index,
     utxos: &BTreeMap<OutPoint, Amount>,
@@ -853,6 +996,47 @@ inscriptions:
       .contains("unknown field `unknown`"));
   }
 
+  #[test]
+  fn batch_with_multiple_parents_throws_error() {
+    let tempdir = TempDir::new().unwrap();
+    let batch_path = tempdir.path().join("batch.yaml");
+    fs::write(
+      &batch_path,
+      "mode: separate-outputs
+parent: 8d363b28528b0cb86b5fd48615493fb175bdf132d2a3d20b4251bba3f130a5abi0
+inscriptions:
+- file: meow.wav
+  parent: 8d363b28528b0cb86b5fd48615493fb175bdf132d2a3d20b4251bba3f130a5abi1
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+      Batchfile::load(&batch_path).unwrap_err().to_string(),
+      "batchfile may only contain one parent, found 2"
+    );
+  }
+
+  #[test]
+  fn batch_parent_may_be_set_on_inscription() {
+    let parent = "8d363b28528b0cb86b5fd48615493fb175bdf132d2a3d20b4251bba3f130a5abi0"
+      .parse::<InscriptionId>()
+      .unwrap();
+
+    let batchfile = Batchfile {
+      inscriptions: vec![
+        BatchEntry::default(),
+        BatchEntry {
+          parent: Some(parent),
+          ..Default::default()
+        },
+      ],
+      ..Default::default()
+    };
+
+    assert_eq!(batchfile.parent(), Some(parent));
+  }
+
   #[test]
   fn batch_inscribe_with_parent() {
     let utxos = vec![
@@ -1371,7 +1555,7 @@ inscriptions:
       Arguments::try_parse_from(["ord", "wallet", "inscribe", "--fee-rate", "1",])
         .unwrap_err()
         .to_string()
-        .contains("error: the following required arguments were not provided:\n  <--file <FILE>|--batch <BATCH>>")
+        .contains("error: the following required arguments were not provided:\n  <--file <FILE>|--batch <BATCH>|--stdin|--url <URL>>")
     );
   }
 
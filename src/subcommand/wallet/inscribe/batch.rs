@@ -1,18 +1,24 @@
 use super::*;
 
-pub(super) struct Batch {
-  pub(super) commit_fee_rate: FeeRate,
-  pub(super) destinations: Vec<Address>,
-  pub(super) dry_run: bool,
-  pub(super) inscriptions: Vec<Inscription>,
-  pub(super) mode: Mode,
-  pub(super) no_backup: bool,
-  pub(super) no_limit: bool,
-  pub(super) parent_info: Option<ParentInfo>,
-  pub(super) postage: Amount,
-  pub(super) reinscribe: bool,
-  pub(super) reveal_fee_rate: FeeRate,
-  pub(super) satpoint: Option<SatPoint>,
+/// First Bitcoin Core version that relays packages submitted with
+/// `submitpackage` on all networks.
+const SUBMIT_PACKAGE_VERSION: usize = 280000;
+
+pub(crate) struct Batch {
+  pub(crate) commit_fee_rate: FeeRate,
+  pub(crate) destinations: Vec<Address>,
+  pub(crate) dry_run: bool,
+  pub(crate) inscriptions: Vec<Inscription>,
+  pub(crate) mode: Mode,
+  pub(crate) no_backup: bool,
+  pub(crate) no_limit: bool,
+  pub(crate) parent_info: Option<ParentInfo>,
+  pub(crate) postage: Amount,
+  pub(crate) reinscribe: bool,
+  pub(crate) reveal_fee_rate: FeeRate,
+  pub(crate) satpoint: Option<SatPoint>,
+  pub(crate) signer: Signer,
+  pub(crate) unsigned: bool,
 }
 
 impl Default for Batch {
@@ -30,6 +36,8 @@ impl Default for Batch {
       reinscribe: false,
       reveal_fee_rate: 1.0.try_into().unwrap(),
       satpoint: None,
+      signer: Signer::Wallet,
+      unsigned: false,
     }
   }
 }
@@ -43,7 +51,7 @@ impl Batch {
     locked_utxos: &BTreeSet<OutPoint>,
     runic_utxos: BTreeSet<OutPoint>,
     utxos: &BTreeMap<OutPoint, Amount>,
-  ) -> SubcommandResult {
+  ) -> Result<(super::Output, Transaction, Transaction)> {
     let wallet_inscriptions = index.get_inscriptions(utxos)?;
 
     let commit_tx_change = [
@@ -62,39 +70,79 @@ impl Batch {
       )?;
 
     if self.dry_run {
-      return Ok(Box::new(self.output(
+      return Ok((
+        self.output(
+          commit_tx.txid(),
+          reveal_tx.txid(),
+          total_fees,
+          self.inscriptions.clone(),
+        ),
+        commit_tx,
+        reveal_tx,
+      ));
+    }
+
+    if self.unsigned {
+      if !self.no_backup {
+        Self::backup_recovery_key(client, recovery_key_pair, chain.network())?;
+      }
+
+      let mut prevouts = commit_tx
+        .output
+        .iter()
+        .enumerate()
+        .map(|(vout, output)| {
+          (
+            OutPoint {
+              txid: commit_tx.txid(),
+              vout: vout.try_into().unwrap(),
+            },
+            output.clone(),
+          )
+        })
+        .collect::<BTreeMap<OutPoint, TxOut>>();
+
+      if let Some(parent_info) = &self.parent_info {
+        prevouts.insert(parent_info.location.outpoint, parent_info.tx_out.clone());
+      }
+
+      let mut output = self.output(
         commit_tx.txid(),
         reveal_tx.txid(),
         total_fees,
         self.inscriptions.clone(),
-      )));
+      );
+
+      output.commit_psbt = Some(create_psbt(client, &commit_tx, &BTreeMap::new())?);
+      output.reveal_psbt = Some(create_psbt(client, &reveal_tx, &prevouts)?);
+
+      return Ok((output, commit_tx, reveal_tx));
     }
 
-    let signed_commit_tx = client
-      .sign_raw_transaction_with_wallet(&commit_tx, None, None)?
-      .hex;
+    let signed_commit_tx = self
+      .signer
+      .sign(client, chain, &commit_tx, &BTreeMap::new())?;
 
     let signed_reveal_tx = if self.parent_info.is_some() {
-      client
-        .sign_raw_transaction_with_wallet(
-          &reveal_tx,
-          Some(
-            &commit_tx
-              .output
-              .iter()
-              .enumerate()
-              .map(|(vout, output)| SignRawTransactionInput {
+      self.signer.sign(
+        client,
+        chain,
+        &reveal_tx,
+        &commit_tx
+          .output
+          .iter()
+          .enumerate()
+          .map(|(vout, output)| {
+            (
+              OutPoint {
                 txid: commit_tx.txid(),
                 vout: vout.try_into().unwrap(),
-                script_pub_key: output.script_pubkey.clone(),
-                redeem_script: None,
-                amount: Some(Amount::from_sat(output.value)),
-              })
-              .collect::<Vec<SignRawTransactionInput>>(),
-          ),
-          None,
-        )?
-        .hex
+              },
+              output.clone(),
+            )
+          })
+          .collect(),
+      )?
     } else {
       consensus::encode::serialize(&reveal_tx)
     };
@@ -103,9 +151,35 @@ impl Batch {
       Self::backup_recovery_key(client, recovery_key_pair, chain.network())?;
     }
 
-    let commit = client.send_raw_transaction(&signed_commit_tx)?;
+    let (commit, reveal) = Self::broadcast(client, &signed_commit_tx, &signed_reveal_tx)?;
+
+    Ok((
+      self.output(commit, reveal, total_fees, self.inscriptions.clone()),
+      commit_tx,
+      reveal_tx,
+    ))
+  }
+
+  /// Broadcast the commit and reveal transactions. If Bitcoin Core supports
+  /// it, they are submitted together as a package, so that the reveal can pay
+  /// for a commit that would not be relayed on its own. Otherwise, or if the
+  /// package is rejected, they are broadcast one after the other.
+  fn broadcast(client: &Client, commit: &[u8], reveal: &[u8]) -> Result<(Txid, Txid)> {
+    if client.version()? >= SUBMIT_PACKAGE_VERSION {
+      match Self::submit_package(client, &[commit, reveal]) {
+        Ok(()) => {
+          return Ok((
+            consensus::encode::deserialize::<Transaction>(commit)?.txid(),
+            consensus::encode::deserialize::<Transaction>(reveal)?.txid(),
+          ))
+        }
+        Err(err) => log::info!("package submission failed, broadcasting individually: {err}"),
+      }
+    }
+
+    let commit = client.send_raw_transaction(commit)?;
 
-    let reveal = match client.send_raw_transaction(&signed_reveal_tx) {
+    let reveal = match client.send_raw_transaction(reveal) {
       Ok(txid) => txid,
       Err(err) => {
         return Err(anyhow!(
@@ -114,12 +188,31 @@ impl Batch {
       }
     };
 
-    Ok(Box::new(self.output(
-      commit,
-      reveal,
-      total_fees,
-      self.inscriptions.clone(),
-    )))
+    Ok((commit, reveal))
+  }
+
+  fn submit_package(client: &Client, transactions: &[&[u8]]) -> Result {
+    #[derive(Deserialize)]
+    struct Output {
+      package_msg: String,
+    }
+
+    let output = client.call::<Output>(
+      "submitpackage",
+      &[transactions
+        .iter()
+        .map(hex::encode)
+        .collect::<Vec<String>>()
+        .into()],
+    )?;
+
+    ensure!(
+      output.package_msg == "success",
+      "package rejected: {}",
+      output.package_msg
+    );
+
+    Ok(())
   }
 
   fn output(
@@ -169,9 +262,13 @@ impl Batch {
 
     super::Output {
       commit,
+      commit_psbt: None,
+      fee_rate: self.reveal_fee_rate.n(),
       reveal,
+      reveal_psbt: None,
       total_fees,
       parent: self.parent_info.clone().map(|info| info.id),
+      postage: self.postage.to_sat(),
       inscriptions: inscriptions_output,
     }
   }
@@ -255,7 +352,7 @@ impl Batch {
 
     if self.reinscribe && !reinscription {
       return Err(anyhow!(
-        "reinscribe flag set but this would not be a reinscription"
+        "`--allow-reinscription` set but this would not be a reinscription"
       ));
     }
 
@@ -438,6 +535,15 @@ impl Batch {
       ),
     );
 
+    // the parent may be in an unconfirmed output of a previous batch, which
+    // isn't among the wallet's outputs
+    if let Some(parent_info) = &self.parent_info {
+      utxos.insert(
+        parent_info.location.outpoint,
+        Amount::from_sat(parent_info.tx_out.value),
+      );
+    }
+
     let total_fees =
       Self::calculate_fee(&unsigned_commit_tx, &utxos) + Self::calculate_fee(&reveal_tx, &utxos);
 
@@ -547,6 +653,7 @@ pub(crate) struct BatchEntry {
   pub(crate) file: PathBuf,
   pub(crate) metadata: Option<serde_yaml::Value>,
   pub(crate) metaprotocol: Option<String>,
+  pub(crate) parent: Option<InscriptionId>,
 }
 
 impl BatchEntry {
@@ -580,9 +687,31 @@ impl Batchfile {
       bail!("batchfile must contain at least one inscription");
     }
 
+    let parents = batchfile
+      .inscriptions
+      .iter()
+      .filter_map(|entry| entry.parent)
+      .chain(batchfile.parent)
+      .collect::<HashSet<InscriptionId>>();
+
+    if parents.len() > 1 {
+      bail!(
+        "batchfile may only contain one parent, found {}",
+        parents.len()
+      );
+    }
+
     Ok(batchfile)
   }
 
+  /// The parent spent by the reveal transaction, set either for the whole
+  /// batch or on individual inscriptions.
+  pub(crate) fn parent(&self) -> Option<InscriptionId> {
+    self
+      .parent
+      .or_else(|| self.inscriptions.iter().find_map(|entry| entry.parent))
+  }
+
   pub(crate) fn inscriptions(
     &self,
     client: &Client,
@@ -619,7 +748,7 @@ impl Batchfile {
       inscriptions.push(Inscription::from_file(
         chain,
         &entry.file,
-        self.parent,
+        entry.parent.or(self.parent),
         if i == 0 { None } else { Some(pointer) },
         entry.metaprotocol.clone(),
         match &metadata {
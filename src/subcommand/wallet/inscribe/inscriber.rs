@@ -0,0 +1,74 @@
+use super::*;
+
+/// Inscribes batches one after another, tracking the wallet outputs spent and
+/// created by each batch, so that batches can be chained before the previous
+/// ones confirm.
+pub(crate) struct Inscriber {
+  pub(crate) chain: Chain,
+  pub(crate) client: Client,
+  pub(crate) index: Index,
+  locked_utxos: BTreeSet<OutPoint>,
+  runic_utxos: BTreeSet<OutPoint>,
+  utxos: BTreeMap<OutPoint, Amount>,
+}
+
+impl Inscriber {
+  pub(crate) fn load(wallet: String, options: &Options) -> Result<Self> {
+    let index = Index::open(options)?;
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, options)?;
+
+    let utxos = get_unspent_outputs(&client, &index)?;
+
+    let locked_utxos = get_locked_outputs(&client)?;
+
+    let runic_utxos = index.get_runic_outputs(&utxos.keys().cloned().collect::<Vec<OutPoint>>())?;
+
+    Ok(Self {
+      chain: options.chain(),
+      client,
+      index,
+      locked_utxos,
+      runic_utxos,
+      utxos,
+    })
+  }
+
+  pub(crate) fn utxos(&self) -> &BTreeMap<OutPoint, Amount> {
+    &self.utxos
+  }
+
+  /// Inscribe `batch`, returning its output and reveal transaction.
+  pub(crate) fn inscribe(&mut self, batch: &Batch) -> Result<(Output, Transaction)> {
+    let (output, commit_tx, reveal_tx) = batch.inscribe(
+      self.chain,
+      &self.index,
+      &self.client,
+      &self.locked_utxos,
+      self.runic_utxos.clone(),
+      &self.utxos,
+    )?;
+
+    for input in commit_tx.input.iter().chain(&reveal_tx.input) {
+      self.utxos.remove(&input.previous_output);
+    }
+
+    for (vout, output) in commit_tx.output.iter().enumerate() {
+      let outpoint = OutPoint {
+        txid: commit_tx.txid(),
+        vout: vout.try_into().unwrap(),
+      };
+
+      if reveal_tx
+        .input
+        .iter()
+        .all(|input| input.previous_output != outpoint)
+      {
+        self.utxos.insert(outpoint, Amount::from_sat(output.value));
+      }
+    }
+
+    Ok((output, reveal_tx))
+  }
+}
@@ -8,21 +8,33 @@ pub struct Output {
   pub postage: u64,
 }
 
-pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
-  let index = Index::open(&options)?;
-  index.update()?;
+pub(crate) fn run(wallet: String, options: Options, index_url: Option<String>) -> SubcommandResult {
+  let index = WalletIndex::open(&options, index_url)?;
 
   let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
 
-  let unspent_outputs = get_unspent_outputs(&client, &index)?;
+  let unspent_outputs = index.get_unspent_outputs(&client)?;
 
   let inscriptions = index.get_inscriptions(&unspent_outputs)?;
 
-  let explorer = match options.chain() {
+  Ok(Box::new(output(
+    options.chain(),
+    inscriptions,
+    &unspent_outputs,
+  )))
+}
+
+pub(crate) fn output(
+  chain: Chain,
+  inscriptions: BTreeMap<SatPoint, InscriptionId>,
+  unspent_outputs: &BTreeMap<OutPoint, Amount>,
+) -> Vec<Output> {
+  let explorer = match chain {
     Chain::Mainnet => "https://ordinals.com/inscription/",
     Chain::Regtest => "http://localhost/inscription/",
     Chain::Signet => "https://signet.ordinals.com/inscription/",
     Chain::Testnet => "https://testnet.ordinals.com/inscription/",
+    Chain::Testnet4 => "https://testnet4.ordinals.com/inscription/",
   };
 
   let mut output = Vec::new();
@@ -38,5 +50,5 @@ pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
     }
   }
 
-  Ok(Box::new(output))
+  output
 }
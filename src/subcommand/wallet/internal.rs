@@ -0,0 +1,286 @@
+use {super::*, miniscript::descriptor::DescriptorPublicKey, std::io::Write};
+
+/// Addresses past the last one found holding outputs which are checked when
+/// syncing, like the gap limit of BIP 44 wallets.
+const GAP_LIMIT: u32 = 20;
+
+/// Wallet whose descriptors, unspent outputs, and inscriptions are kept by ord
+/// in `<DATA_DIR>/wallets/<WALLET>.json` instead of in a Bitcoin Core wallet,
+/// so that it can be used with nodes built without wallet support. Outputs
+/// are found by looking up the wallet's addresses in the address index, so
+/// the index must be built with `--index-addresses`. Like Bitcoin Core wallets
+/// created by ord, the file holds the wallet's private keys unencrypted.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub(crate) struct InternalWallet {
+  change_descriptor: String,
+  inscriptions: BTreeMap<SatPoint, InscriptionId>,
+  next_change_index: u32,
+  next_receive_index: u32,
+  receive_descriptor: String,
+  utxos: BTreeMap<OutPoint, u64>,
+}
+
+impl InternalWallet {
+  fn path(wallet: &str, options: &Options) -> PathBuf {
+    options
+      .data_dir()
+      .join("wallets")
+      .join(format!("{wallet}.json"))
+  }
+
+  pub(crate) fn create(wallet: String, options: &Options, seed: [u8; 64]) -> Result {
+    let path = Self::path(&wallet, options);
+
+    ensure!(!path.exists(), "wallet `{wallet}` already exists");
+
+    let [receive_descriptor, change_descriptor] =
+      derive_descriptors(options.chain().network(), seed)?;
+
+    Self {
+      change_descriptor,
+      inscriptions: BTreeMap::new(),
+      next_change_index: 0,
+      next_receive_index: 0,
+      receive_descriptor,
+      utxos: BTreeMap::new(),
+    }
+    .save(&path)
+  }
+
+  fn load(wallet: &str, options: &Options) -> Result<Self> {
+    let path = Self::path(wallet, options);
+
+    let json = fs::read(&path).with_context(|| {
+      format!("wallet `{wallet}` not found, create it with `ord wallet --backend internal create`")
+    })?;
+
+    serde_json::from_slice(&json).with_context(|| format!("failed to parse {}", path.display()))
+  }
+
+  /// Write the wallet to `path`, replacing the previous file only once the
+  /// new one is complete.
+  fn save(&self, path: &Path) -> Result {
+    fs::create_dir_all(path.parent().unwrap())?;
+
+    let tmp = path.with_extension("json.tmp");
+
+    let mut open_options = fs::OpenOptions::new();
+
+    open_options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut open_options, 0o600);
+
+    open_options
+      .open(&tmp)
+      .with_context(|| format!("failed to open {}", tmp.display()))?
+      .write_all(&serde_json::to_vec_pretty(self)?)?;
+
+    fs::rename(&tmp, path)?;
+
+    Ok(())
+  }
+
+  fn descriptor(&self, change: bool) -> Result<Descriptor<DescriptorPublicKey>> {
+    let descriptor = if change {
+      &self.change_descriptor
+    } else {
+      &self.receive_descriptor
+    };
+
+    Ok(Descriptor::<DescriptorPublicKey>::parse_descriptor(&Secp256k1::new(), descriptor)?.0)
+  }
+
+  fn next_index(&mut self, change: bool) -> &mut u32 {
+    if change {
+      &mut self.next_change_index
+    } else {
+      &mut self.next_receive_index
+    }
+  }
+
+  /// Replace the wallet's unspent outputs and inscriptions with those found
+  /// in `index`, checking addresses up to `GAP_LIMIT` past the last one
+  /// holding outputs.
+  fn sync(&mut self, index: &Index) -> Result {
+    ensure!(
+      index.has_address_index(),
+      "the internal wallet backend requires an index built with `--index-addresses`"
+    );
+
+    let mut utxos = BTreeMap::new();
+
+    for change in [false, true] {
+      let descriptor = self.descriptor(change)?;
+
+      let mut i = 0;
+
+      while i < *self.next_index(change) + GAP_LIMIT {
+        let script_pubkey = descriptor.at_derivation_index(i)?.script_pubkey();
+
+        let outputs = index.get_address_outputs(&script_pubkey)?;
+
+        if !outputs.is_empty() {
+          let next_index = self.next_index(change);
+          *next_index = (*next_index).max(i + 1);
+        }
+
+        for outpoint in outputs {
+          let value = match self.utxos.get(&outpoint) {
+            Some(value) => *value,
+            None => {
+              index
+                .get_transaction(outpoint.txid)?
+                .with_context(|| format!("failed to fetch transaction {}", outpoint.txid))?
+                .output[usize::try_from(outpoint.vout).unwrap()]
+              .value
+            }
+          };
+
+          utxos.insert(outpoint, value);
+        }
+
+        i += 1;
+      }
+    }
+
+    self.utxos = utxos;
+    self.inscriptions = index.get_inscriptions(&self.unspent_outputs())?;
+
+    Ok(())
+  }
+
+  fn unspent_outputs(&self) -> BTreeMap<OutPoint, Amount> {
+    self
+      .utxos
+      .iter()
+      .map(|(outpoint, value)| (*outpoint, Amount::from_sat(*value)))
+      .collect()
+  }
+}
+
+pub(crate) fn run(wallet: String, options: Options, subcommand: Subcommand) -> SubcommandResult {
+  let path = InternalWallet::path(&wallet, &options);
+
+  match subcommand {
+    Subcommand::Create(create) => return create.run(wallet, options, Backend::Internal),
+    Subcommand::Restore(restore) => return restore.run(wallet, options, Backend::Internal),
+    Subcommand::Balance
+    | Subcommand::Inscriptions
+    | Subcommand::Outputs
+    | Subcommand::Receive => {}
+    _ => bail!(
+      "the internal wallet backend only supports `balance`, `create`, `inscriptions`, `outputs`, `receive`, and `restore`"
+    ),
+  }
+
+  let mut internal = InternalWallet::load(&wallet, &options)?;
+
+  if let Subcommand::Receive = subcommand {
+    let address = internal
+      .descriptor(false)?
+      .at_derivation_index(internal.next_receive_index)?
+      .address(options.chain().network())?;
+
+    internal.next_receive_index += 1;
+
+    internal.save(&path)?;
+
+    return Ok(Box::new(receive::Output {
+      address: address.to_string().parse()?,
+    }));
+  }
+
+  let index = Index::open(&options)?;
+  index.update()?;
+
+  internal.sync(&index)?;
+
+  internal.save(&path)?;
+
+  let unspent_outputs = internal.unspent_outputs();
+
+  match subcommand {
+    Subcommand::Balance => Ok(Box::new(balance::balance(&index, unspent_outputs, 0)?)),
+    Subcommand::Inscriptions => Ok(Box::new(inscriptions::output(
+      options.chain(),
+      internal.inscriptions.clone(),
+      &unspent_outputs,
+    ))),
+    Subcommand::Outputs => Ok(Box::new(
+      unspent_outputs
+        .into_iter()
+        .map(|(output, amount)| outputs::Output {
+          output,
+          amount: amount.to_sat(),
+        })
+        .collect::<Vec<outputs::Output>>(),
+    )),
+    _ => unreachable!(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn create_persists_wallet() {
+    let tempdir = TempDir::new().unwrap();
+
+    let options = Arguments::try_parse_from([
+      "ord",
+      "--data-dir",
+      tempdir.path().to_str().unwrap(),
+      "index",
+      "update",
+    ])
+    .unwrap()
+    .options;
+
+    InternalWallet::create("foo".into(), &options, [0; 64]).unwrap();
+
+    let wallet = InternalWallet::load("foo", &options).unwrap();
+
+    assert!(wallet.receive_descriptor.starts_with("tr("));
+    assert!(wallet.change_descriptor.starts_with("tr("));
+    assert_eq!(wallet.next_receive_index, 0);
+
+    assert_eq!(
+      InternalWallet::create("foo".into(), &options, [0; 64])
+        .unwrap_err()
+        .to_string(),
+      "wallet `foo` already exists"
+    );
+
+    assert!(InternalWallet::load("bar", &options).is_err());
+  }
+
+  #[test]
+  fn receive_and_change_addresses_differ() {
+    let [receive_descriptor, change_descriptor] =
+      derive_descriptors(Network::Bitcoin, [0; 64]).unwrap();
+
+    let wallet = InternalWallet {
+      change_descriptor,
+      inscriptions: BTreeMap::new(),
+      next_change_index: 0,
+      next_receive_index: 0,
+      receive_descriptor,
+      utxos: BTreeMap::new(),
+    };
+
+    let address = |change: bool, index: u32| {
+      wallet
+        .descriptor(change)
+        .unwrap()
+        .at_derivation_index(index)
+        .unwrap()
+        .script_pubkey()
+    };
+
+    assert_ne!(address(false, 0), address(true, 0));
+    assert_ne!(address(false, 0), address(false, 1));
+    assert!(address(false, 0).is_v1_p2tr());
+  }
+}
@@ -0,0 +1,169 @@
+use {
+  super::*,
+  inscribe::{
+    batch::{Batch, Mode},
+    inscriber::Inscriber,
+    Inscribe,
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct MintCollection {
+  #[arg(
+    long,
+    default_value = "25",
+    help = "Inscribe at most <BATCH_SIZE> children per reveal transaction."
+  )]
+  batch_size: usize,
+  #[arg(
+    long,
+    default_value = "6",
+    help = "Estimate fee rate for confirmation within <CONFIRMATION_TARGET> blocks with `--fee-rate auto`."
+  )]
+  confirmation_target: u16,
+  #[arg(
+    long,
+    help = "Use fee rate of <FEE_RATE> sats/vB. `auto` estimates a fee rate from Bitcoin Core's fee estimates and mempool."
+  )]
+  fee_rate: FeeRateArg,
+  #[arg(long, help = "Make inscriptions children of <PARENT>.")]
+  parent: InscriptionId,
+  #[arg(
+    long,
+    help = "Amount of postage to include in each child. Default `10000sat`."
+  )]
+  postage: Option<Amount>,
+  #[arg(help = "Inscribe files in <DIRECTORY>, ordered by file name.")]
+  directory: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Child {
+  pub file: PathBuf,
+  pub id: InscriptionId,
+  pub number: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub batches: Vec<inscribe::Output>,
+  pub children: Vec<Child>,
+  pub parent: InscriptionId,
+  pub total_fees: u64,
+}
+
+impl MintCollection {
+  pub(crate) fn run(
+    self,
+    wallet: String,
+    options: Options,
+    policy: PostagePolicy,
+  ) -> SubcommandResult {
+    ensure!(
+      self.batch_size > 0,
+      "<BATCH_SIZE> must be greater than zero"
+    );
+
+    let mut files = fs::read_dir(&self.directory)
+      .with_context(|| format!("failed to read {}", self.directory.display()))?
+      .map(|entry| Ok(entry?.path()))
+      .collect::<Result<Vec<PathBuf>>>()?
+      .into_iter()
+      .filter(|path| path.is_file())
+      .collect::<Vec<PathBuf>>();
+
+    files.sort();
+
+    ensure!(
+      !files.is_empty(),
+      "directory {} contains no files",
+      self.directory.display()
+    );
+
+    let mut inscriber = Inscriber::load(wallet, &options)?;
+
+    let chain = inscriber.chain;
+
+    let fee_rate = self
+      .fee_rate
+      .resolve(&inscriber.client, self.confirmation_target)?;
+
+    let mut parent_info = Inscribe::get_parent_info(
+      Some(self.parent),
+      &inscriber.index,
+      inscriber.utxos(),
+      &inscriber.client,
+      chain,
+    )?
+    .unwrap();
+
+    let postage = policy.postage(self.postage, &parent_info.destination.script_pubkey())?;
+
+    let mut batches = Vec::new();
+    let mut children = Vec::new();
+
+    for chunk in files.chunks(self.batch_size) {
+      let mut inscriptions = Vec::new();
+      let mut pointer = parent_info.tx_out.value;
+
+      for (i, file) in chunk.iter().enumerate() {
+        inscriptions.push(Inscription::from_file(
+          chain,
+          file,
+          Some(self.parent),
+          if i == 0 { None } else { Some(pointer) },
+          None,
+          None,
+          false,
+        )?);
+
+        pointer += postage.to_sat();
+      }
+
+      let destinations = chunk
+        .iter()
+        .map(|_| get_change_address(&inscriber.client, chain))
+        .collect::<Result<Vec<Address>>>()?;
+
+      let (output, reveal_tx) = inscriber.inscribe(&Batch {
+        commit_fee_rate: fee_rate,
+        destinations,
+        inscriptions,
+        mode: Mode::SeparateOutputs,
+        parent_info: Some(parent_info.clone()),
+        postage,
+        reveal_fee_rate: fee_rate,
+        ..Default::default()
+      })?;
+
+      for (file, inscription) in chunk.iter().zip(&output.inscriptions) {
+        children.push(Child {
+          file: file.clone(),
+          id: inscription.id,
+          number: children.len().try_into().unwrap(),
+        });
+      }
+
+      // The reveal transaction returns the parent to the wallet in its first
+      // output, where the next batch picks it up.
+      parent_info.location = SatPoint {
+        outpoint: OutPoint {
+          txid: reveal_tx.txid(),
+          vout: 0,
+        },
+        offset: parent_info.location.offset,
+      };
+      parent_info.tx_out = reveal_tx.output[0].clone();
+      parent_info.destination = get_change_address(&inscriber.client, chain)?;
+
+      batches.push(output);
+    }
+
+    Ok(Box::new(Output {
+      total_fees: batches.iter().map(|batch| batch.total_fees).sum(),
+      batches,
+      children,
+      parent: self.parent,
+    }))
+  }
+}
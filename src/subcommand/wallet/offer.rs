@@ -0,0 +1,24 @@
+use {
+  super::*,
+  bitcoin::sighash::{EcdsaSighashType, TapSighashType},
+};
+
+pub mod accept;
+pub mod create;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Offer {
+  #[command(about = "Accept offer to sell inscription")]
+  Accept(accept::Accept),
+  #[command(about = "Create offer to sell inscription")]
+  Create(create::Create),
+}
+
+impl Offer {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    match self {
+      Self::Accept(accept) => accept.run(wallet, options),
+      Self::Create(create) => create.run(wallet, options),
+    }
+  }
+}
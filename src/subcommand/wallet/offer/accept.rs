@@ -0,0 +1,320 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+#[clap(
+  group = ArgGroup::new("amount")
+      .required(true)
+      .args(&["price", "max_price"]),
+)]
+pub(crate) struct Accept {
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Only accept offers that pay the seller exactly <PRICE>.")]
+  price: Option<Amount>,
+  #[arg(
+    long,
+    help = "Only accept offers that pay the seller no more than <MAX_PRICE>."
+  )]
+  max_price: Option<Amount>,
+  #[arg(help = "Accept base64-encoded offer <PSBT> created with `ord wallet offer create`.")]
+  psbt: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Output {
+  pub fee: u64,
+  pub inscriptions: Vec<InscriptionId>,
+  pub price: u64,
+  pub transaction: Txid,
+}
+
+impl Accept {
+  /// Complete an offer by adding a cardinal input before the seller's input,
+  /// and an output before the seller's payment output, which receives the
+  /// value of both inputs and with it the inscriptions, followed by inputs
+  /// which pay the price and fee, and change. The price is set by the seller
+  /// in the PSBT, so it is checked against `--price` or `--max-price` before
+  /// anything is signed.
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let offer = Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(&self.psbt)
+        .context("failed to decode PSBT")?,
+    )
+    .context("failed to deserialize PSBT")?;
+
+    ensure!(
+      offer.unsigned_tx.input.len() == 1 && offer.unsigned_tx.output.len() == 1,
+      "offer PSBT must have exactly one input and one output",
+    );
+
+    ensure!(
+      offer.unsigned_tx.input[0].script_sig.is_empty()
+        && offer.inputs[0].final_script_sig.is_none(),
+      "offer PSBT input must be a taproot key path spend",
+    );
+
+    let witness = match (
+      &offer.inputs[0].final_script_witness,
+      offer.inputs[0].tap_key_sig,
+    ) {
+      (Some(witness), _) => witness.clone(),
+      (None, Some(signature)) => Witness::from_slice(&[signature.to_vec()]),
+      (None, None) => bail!("offer PSBT input is not signed"),
+    };
+
+    ensure!(
+      Self::sighash_type(&witness) == Some(TapSighashType::SinglePlusAnyoneCanPay),
+      "offer PSBT input must be signed with SIGHASH_SINGLE|ANYONECANPAY",
+    );
+
+    let seller_input = TxIn {
+      witness,
+      ..offer.unsigned_tx.input[0].clone()
+    };
+
+    let seller_output = offer.unsigned_tx.output[0].clone();
+
+    let price = Amount::from_sat(seller_output.value);
+
+    if let Some(expected) = self.price {
+      ensure!(
+        price == expected,
+        "offer price of {} sat does not match --price of {} sat",
+        price.to_sat(),
+        expected.to_sat(),
+      );
+    }
+
+    if let Some(max_price) = self.max_price {
+      ensure!(
+        price <= max_price,
+        "offer price of {} sat exceeds --max-price of {} sat",
+        price.to_sat(),
+        max_price.to_sat(),
+      );
+    }
+
+    let outpoint = seller_input.previous_output;
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let chain = options.chain();
+
+    let inscriptions = index.get_inscriptions_on_output(outpoint)?;
+
+    ensure!(
+      !inscriptions.is_empty(),
+      "offer output {outpoint} holds no inscriptions"
+    );
+
+    // look up the spent output rather than trusting the PSBT, since its value
+    // determines the fee
+    let prevout = client
+      .get_raw_transaction(&outpoint.txid, None)?
+      .output
+      .into_iter()
+      .nth(outpoint.vout.try_into().unwrap())
+      .ok_or_else(|| anyhow!("output {outpoint} not found"))?;
+
+    // the sighash type is read from a key path signature, which only taproot
+    // outputs can be spent with, so other outputs can't be checked
+    ensure!(
+      prevout.script_pubkey.is_v1_p2tr(),
+      "offer PSBT input must spend a taproot output",
+    );
+
+    let unspent_outputs = get_unspent_outputs(&client, &index)?;
+
+    let mut non_cardinal = index
+      .get_inscriptions(&unspent_outputs)?
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    non_cardinal.extend(
+      index.get_runic_outputs(&unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>())?,
+    );
+
+    non_cardinal.extend(get_locked_outputs(&client)?);
+
+    non_cardinal.insert(outpoint);
+
+    let mut cardinals = unspent_outputs
+      .into_iter()
+      .filter(|(outpoint, _)| !non_cardinal.contains(outpoint))
+      .map(|(outpoint, amount)| (amount, outpoint))
+      .collect::<Vec<(Amount, OutPoint)>>();
+
+    cardinals.sort();
+
+    ensure!(
+      !cardinals.is_empty(),
+      "wallet contains no cardinal utxos to receive inscription with"
+    );
+
+    let (padding_value, padding_outpoint) = cardinals.remove(0);
+
+    let destination = client
+      .get_new_address(None, Some(bitcoincore_rpc::json::AddressType::Bech32m))?
+      .require_network(chain.network())?;
+
+    let change = get_change_address(&client, chain)?;
+
+    let mut transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![Self::input(padding_outpoint), seller_input],
+      output: vec![
+        TxOut {
+          script_pubkey: destination.script_pubkey(),
+          value: padding_value.to_sat() + prevout.value,
+        },
+        seller_output.clone(),
+      ],
+    };
+
+    let mut input_value = padding_value.to_sat() + prevout.value;
+
+    let mut payment_inputs = cardinals.into_iter().rev();
+
+    loop {
+      let output_value = transaction
+        .output
+        .iter()
+        .map(|output| output.value)
+        .sum::<u64>();
+
+      let mut with_change = transaction.clone();
+
+      with_change.output.push(TxOut {
+        script_pubkey: change.script_pubkey(),
+        value: 0,
+      });
+
+      let fee = self
+        .fee_rate
+        .fee(Self::estimate_vsize(&with_change))
+        .to_sat();
+
+      if let Some(excess) = input_value.checked_sub(output_value + fee) {
+        if excess >= change.script_pubkey().dust_value().to_sat() {
+          with_change.output.last_mut().unwrap().value = excess;
+          transaction = with_change;
+          break;
+        }
+      }
+
+      let fee = self
+        .fee_rate
+        .fee(Self::estimate_vsize(&transaction))
+        .to_sat();
+
+      if input_value >= output_value + fee {
+        break;
+      }
+
+      let (amount, outpoint) = payment_inputs.next().ok_or_else(|| {
+        anyhow!(
+          "wallet does not contain enough cardinal UTXOs, please add additional funds to wallet"
+        )
+      })?;
+
+      transaction.input.push(Self::input(outpoint));
+
+      input_value += amount.to_sat();
+    }
+
+    let fee = input_value
+      - transaction
+        .output
+        .iter()
+        .map(|output| output.value)
+        .sum::<u64>();
+
+    let signed_transaction = Signer::Wallet.sign(
+      &client,
+      chain,
+      &transaction,
+      &[(outpoint, prevout)].into_iter().collect(),
+    )?;
+
+    let transaction = client.send_raw_transaction(&signed_transaction)?;
+
+    Ok(Box::new(Output {
+      fee,
+      inscriptions,
+      price: seller_output.value,
+      transaction,
+    }))
+  }
+
+  fn input(previous_output: OutPoint) -> TxIn {
+    TxIn {
+      previous_output,
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }
+  }
+
+  /// Virtual size of `transaction` once the wallet's inputs, which are all
+  /// taproot, are signed with key path signatures.
+  fn estimate_vsize(transaction: &Transaction) -> usize {
+    let mut transaction = transaction.clone();
+
+    for input in &mut transaction.input {
+      if input.witness.is_empty() {
+        input.witness = Witness::from_slice(&[&[0; 64]]);
+      }
+    }
+
+    transaction.vsize()
+  }
+
+  /// Sighash type of a taproot key path spend's `witness`, which is only
+  /// included in the signature if it isn't SIGHASH_DEFAULT.
+  fn sighash_type(witness: &Witness) -> Option<TapSighashType> {
+    match witness.to_vec().as_slice() {
+      [signature] if signature.len() == 65 => TapSighashType::from_consensus_u8(signature[64]).ok(),
+      [signature] if signature.len() == 64 => Some(TapSighashType::Default),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sighash_type() {
+    let signature = |sighash_type: Option<u8>| {
+      let mut signature = vec![0; 64];
+      signature.extend(sighash_type);
+      Witness::from_slice(&[signature])
+    };
+
+    assert_eq!(
+      Accept::sighash_type(&signature(None)),
+      Some(TapSighashType::Default)
+    );
+
+    assert_eq!(
+      Accept::sighash_type(&signature(Some(0x83))),
+      Some(TapSighashType::SinglePlusAnyoneCanPay)
+    );
+
+    assert_eq!(
+      Accept::sighash_type(&signature(Some(0x01))),
+      Some(TapSighashType::All)
+    );
+
+    assert_eq!(Accept::sighash_type(&signature(Some(0x42))), None);
+    assert_eq!(Accept::sighash_type(&Witness::new()), None);
+  }
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Create {
+  #[arg(long, help = "Offer <INSCRIPTION> for sale.")]
+  inscription: InscriptionId,
+  #[arg(
+    long,
+    help = "Sell inscription for <PRICE>, paid to a new wallet address."
+  )]
+  price: Amount,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Output {
+  pub inscription: InscriptionId,
+  pub price: u64,
+  pub psbt: String,
+}
+
+impl Create {
+  /// Create a PSBT which spends the output holding the inscription and pays
+  /// the price to the seller. The input is signed with
+  /// SIGHASH_SINGLE|ANYONECANPAY, which only commits to the input and the
+  /// payment output, so that a buyer can add inputs to pay the price and an
+  /// output to receive the inscription, but can't change what the seller is
+  /// paid.
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let unspent_outputs = get_unspent_outputs(&client, &index)?;
+
+    let satpoint = index
+      .get_inscription_satpoint_by_id(self.inscription)?
+      .ok_or_else(|| anyhow!("inscription {} not found", self.inscription))?;
+
+    let outpoint = satpoint.outpoint;
+
+    ensure!(
+      unspent_outputs.contains_key(&outpoint),
+      "inscription {} not in wallet",
+      self.inscription,
+    );
+
+    ensure!(
+      index.get_inscriptions_on_output(outpoint)? == [self.inscription],
+      "output {outpoint} holds inscriptions other than {}",
+      self.inscription,
+    );
+
+    ensure!(
+      index.get_runic_outputs(&[outpoint])?.is_empty(),
+      "output {outpoint} holds runes",
+    );
+
+    let address = client
+      .get_new_address(None, Some(bitcoincore_rpc::json::AddressType::Bech32m))?
+      .require_network(options.chain().network())?;
+
+    let dust_value = address.script_pubkey().dust_value();
+
+    ensure!(
+      self.price >= dust_value,
+      "price {} sat is below dust limit of {} sat",
+      self.price.to_sat(),
+      dust_value.to_sat(),
+    );
+
+    let prevout = client
+      .get_raw_transaction(&outpoint.txid, None)?
+      .output
+      .into_iter()
+      .nth(outpoint.vout.try_into().unwrap())
+      .ok_or_else(|| anyhow!("output {outpoint} not found"))?;
+
+    let unsigned_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        script_pubkey: address.script_pubkey(),
+        value: self.price.to_sat(),
+      }],
+    };
+
+    let signed_transaction: Transaction = consensus::encode::deserialize(
+      &client
+        .sign_raw_transaction_with_wallet(
+          &unsigned_transaction,
+          None,
+          Some(EcdsaSighashType::SinglePlusAnyoneCanPay.into()),
+        )?
+        .hex,
+    )?;
+
+    let mut psbt = Psbt::from_unsigned_tx(unsigned_transaction)?;
+
+    psbt.inputs[0].witness_utxo = Some(prevout);
+    psbt.inputs[0].final_script_witness = Some(signed_transaction.input[0].witness.clone());
+
+    Ok(Box::new(Output {
+      inscription: self.inscription,
+      price: self.price.to_sat(),
+      psbt: base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+    }))
+  }
+}
@@ -6,15 +6,13 @@ pub struct Output {
   pub amount: u64,
 }
 
-pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
-  let index = Index::open(&options)?;
-
-  index.update()?;
+pub(crate) fn run(wallet: String, options: Options, index_url: Option<String>) -> SubcommandResult {
+  let index = WalletIndex::open(&options, index_url)?;
 
   let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
 
   let mut outputs = Vec::new();
-  for (output, amount) in get_unspent_outputs(&client, &index)? {
+  for (output, amount) in index.get_unspent_outputs(&client)? {
     outputs.push(Output {
       output,
       amount: amount.to_sat(),
@@ -0,0 +1,184 @@
+use super::*;
+
+#[derive(Debug, Default, Parser)]
+pub(crate) struct PostageOptions {
+  #[arg(
+    long,
+    help = "Add at most <MAX_PADDING_INPUTS> cardinal inputs to raise padding outputs above the dust limit. Default unlimited."
+  )]
+  pub(crate) max_padding_inputs: Option<usize>,
+  #[arg(
+    long,
+    help = "Strip postage above <MAX_POSTAGE> from sent inscriptions. Default `20000sat`."
+  )]
+  pub(crate) max_postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Refuse to create inscription and rune outputs with less than <MIN_POSTAGE>. Default `0sat`, in which case only the dust limit applies."
+  )]
+  pub(crate) min_postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Include <TARGET_POSTAGE> in inscription and rune outputs unless a command sets postage explicitly. Default `10000sat`."
+  )]
+  pub(crate) target_postage: Option<Amount>,
+}
+
+impl PostageOptions {
+  pub(crate) fn policy(&self) -> Result<PostagePolicy> {
+    let default = PostagePolicy::default();
+
+    let policy = PostagePolicy {
+      max: self.max_postage.unwrap_or(default.max),
+      max_padding_inputs: self.max_padding_inputs.or(default.max_padding_inputs),
+      min: self.min_postage.unwrap_or(default.min),
+      target: self.target_postage.unwrap_or(default.target),
+    };
+
+    ensure!(
+      policy.min <= policy.target,
+      "minimum postage {} sat is greater than target postage {} sat",
+      policy.min.to_sat(),
+      policy.target.to_sat(),
+    );
+
+    ensure!(
+      policy.target <= policy.max,
+      "target postage {} sat is greater than maximum postage {} sat",
+      policy.target.to_sat(),
+      policy.max.to_sat(),
+    );
+
+    Ok(policy)
+  }
+}
+
+/// How much value wallet commands attach to outputs carrying inscriptions or
+/// runes. `target` is used when a command doesn't set postage explicitly,
+/// `max` is the most postage a sent inscription keeps before the excess is
+/// stripped into change, and `min` is the least postage a command may set.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PostagePolicy {
+  pub(crate) max: Amount,
+  pub(crate) max_padding_inputs: Option<usize>,
+  pub(crate) min: Amount,
+  pub(crate) target: Amount,
+}
+
+impl Default for PostagePolicy {
+  fn default() -> Self {
+    Self {
+      max: TransactionBuilder::MAX_POSTAGE,
+      max_padding_inputs: None,
+      min: Amount::ZERO,
+      target: TARGET_POSTAGE,
+    }
+  }
+}
+
+impl PostagePolicy {
+  /// Postage for an output to `script_pubkey`, either `postage` if set, or the
+  /// target postage.
+  pub(crate) fn postage(&self, postage: Option<Amount>, script_pubkey: &Script) -> Result<Amount> {
+    let postage = postage.unwrap_or(self.target);
+
+    ensure!(
+      postage >= self.min,
+      "postage {} sat is below minimum postage {} sat",
+      postage.to_sat(),
+      self.min.to_sat(),
+    );
+
+    let dust_value = script_pubkey.dust_value();
+
+    ensure!(
+      postage >= dust_value,
+      "postage {} sat is below dust limit of {} sat",
+      postage.to_sat(),
+      dust_value.to_sat(),
+    );
+
+    Ok(postage)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn options(min: u64, target: u64, max: u64) -> PostageOptions {
+    PostageOptions {
+      max_padding_inputs: None,
+      max_postage: Some(Amount::from_sat(max)),
+      min_postage: Some(Amount::from_sat(min)),
+      target_postage: Some(Amount::from_sat(target)),
+    }
+  }
+
+  #[test]
+  fn default_policy() {
+    assert_eq!(
+      PostageOptions::default().policy().unwrap(),
+      PostagePolicy {
+        max: Amount::from_sat(20_000),
+        max_padding_inputs: None,
+        min: Amount::ZERO,
+        target: Amount::from_sat(10_000),
+      }
+    );
+  }
+
+  #[test]
+  fn policy_must_be_ordered() {
+    assert!(options(1000, 2000, 3000).policy().is_ok());
+    assert!(options(2000, 2000, 2000).policy().is_ok());
+
+    assert_eq!(
+      options(3000, 2000, 4000).policy().unwrap_err().to_string(),
+      "minimum postage 3000 sat is greater than target postage 2000 sat",
+    );
+
+    assert_eq!(
+      options(1000, 5000, 4000).policy().unwrap_err().to_string(),
+      "target postage 5000 sat is greater than maximum postage 4000 sat",
+    );
+  }
+
+  #[test]
+  fn postage() {
+    let policy = options(1000, 2000, 3000).policy().unwrap();
+
+    let script_pubkey = recipient().script_pubkey();
+
+    assert_eq!(
+      policy.postage(None, &script_pubkey).unwrap(),
+      Amount::from_sat(2000)
+    );
+
+    assert_eq!(
+      policy
+        .postage(Some(Amount::from_sat(50_000)), &script_pubkey)
+        .unwrap(),
+      Amount::from_sat(50_000)
+    );
+
+    assert_eq!(
+      policy
+        .postage(Some(Amount::from_sat(999)), &script_pubkey)
+        .unwrap_err()
+        .to_string(),
+      "postage 999 sat is below minimum postage 1000 sat",
+    );
+
+    assert_eq!(
+      PostagePolicy::default()
+        .postage(Some(Amount::from_sat(1)), &script_pubkey)
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "postage 1 sat is below dust limit of {} sat",
+        script_pubkey.dust_value().to_sat()
+      ),
+    );
+  }
+}
@@ -13,12 +13,18 @@ pub(crate) struct Restore {
 }
 
 impl Restore {
-  pub(crate) fn run(self, wallet_name: String, options: Options) -> SubcommandResult {
-    wallet::initialize(
-      wallet_name,
-      &options,
-      self.mnemonic.to_seed(self.passphrase),
-    )?;
+  pub(crate) fn run(
+    self,
+    wallet_name: String,
+    options: Options,
+    backend: Backend,
+  ) -> SubcommandResult {
+    let seed = self.mnemonic.to_seed(self.passphrase);
+
+    match backend {
+      Backend::Core => wallet::initialize(wallet_name, &options, seed)?,
+      Backend::Internal => InternalWallet::create(wallet_name, &options, seed)?,
+    }
 
     Ok(Box::new(Empty {}))
   }
@@ -0,0 +1,29 @@
+use super::*;
+
+pub mod mint;
+pub mod send;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Runes {
+  #[command(about = "Create rune")]
+  Etch(etch::Etch),
+  #[command(about = "Mint rune with open mint terms")]
+  Mint(mint::Mint),
+  #[command(about = "Send runes")]
+  Send(send::Send),
+}
+
+impl Runes {
+  pub(crate) fn run(
+    self,
+    wallet: String,
+    options: Options,
+    policy: PostagePolicy,
+  ) -> SubcommandResult {
+    match self {
+      Self::Etch(etch) => etch.run(wallet, options, policy),
+      Self::Mint(mint) => mint.run(wallet, options, policy),
+      Self::Send(send) => send.run(wallet, options, policy),
+    }
+  }
+}
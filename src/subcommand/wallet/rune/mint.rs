@@ -0,0 +1,158 @@
+use {super::*, crate::runes::CLAIM_BIT};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Mint {
+  #[arg(
+    long,
+    help = "Mint <AMOUNT> of rune. Defaults to the rune's mint limit."
+  )]
+  amount: Option<Decimal>,
+  #[arg(long, help = "Send minted runes to <DESTINATION>.")]
+  destination: Option<Address<NetworkUnchecked>>,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Mint rune <RUNE>.")]
+  rune: SpacedRune,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Output {
+  pub amount: u128,
+  pub postage: u64,
+  pub rune: SpacedRune,
+  pub transaction: Txid,
+}
+
+impl Mint {
+  pub(crate) fn run(
+    self,
+    wallet: String,
+    options: Options,
+    policy: PostagePolicy,
+  ) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    ensure!(
+      index.has_rune_index(),
+      "`ord wallet rune mint` requires index created with `--index-runes` flag",
+    );
+
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let rune = self.rune.rune;
+
+    let (id, entry) = index
+      .rune(rune)?
+      .with_context(|| format!("rune `{rune}` has not been etched"))?;
+
+    let limit = entry
+      .limit
+      .with_context(|| format!("rune `{rune}` does not have open mint terms"))?;
+
+    let height = index.block_count()?;
+
+    if let Some(end) = entry.end {
+      ensure!(height < end, "rune `{rune}` mint term ended at block {end}");
+    }
+
+    if let Some(deadline) = entry.deadline {
+      let time = index
+        .block_hash(None)?
+        .map(|hash| index.block_header(hash))
+        .transpose()?
+        .flatten()
+        .map(|header| header.time)
+        .unwrap_or_default();
+
+      ensure!(
+        time < deadline,
+        "rune `{rune}` mint deadline {deadline} has passed"
+      );
+    }
+
+    let amount = match self.amount {
+      Some(amount) => amount.to_amount(entry.divisibility)?,
+      None => limit,
+    };
+
+    ensure!(amount > 0, "mint amount must be greater than zero");
+
+    ensure!(
+      amount <= limit,
+      "mint amount {} exceeds `{rune}` mint limit of {}",
+      Pile {
+        amount,
+        divisibility: entry.divisibility,
+        symbol: entry.symbol,
+      },
+      Pile {
+        amount: limit,
+        divisibility: entry.divisibility,
+        symbol: entry.symbol,
+      },
+    );
+
+    let destination = match self.destination {
+      Some(destination) => destination.require_network(options.chain().network())?,
+      None => get_change_address(&client, options.chain())?,
+    };
+
+    let postage = policy.postage(None, &destination.script_pubkey())?;
+
+    let runestone = Runestone {
+      edicts: vec![Edict {
+        amount,
+        id: u128::from(id) | CLAIM_BIT,
+        output: 1,
+      }],
+      ..Default::default()
+    };
+
+    let unfunded_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: vec![
+        TxOut {
+          script_pubkey: runestone.encipher(),
+          value: 0,
+        },
+        TxOut {
+          script_pubkey: destination.script_pubkey(),
+          value: postage.to_sat(),
+        },
+      ],
+    };
+
+    let unspent_outputs = get_unspent_outputs(&client, &index)?;
+
+    let inscriptions = index.get_inscriptions(&unspent_outputs)?;
+
+    let runic_outputs =
+      index.get_runic_outputs(&unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>())?;
+
+    crate::subcommand::wallet::send::Send::lock_non_cardinal_outputs(
+      &client,
+      &inscriptions,
+      &runic_outputs,
+      unspent_outputs,
+    )?;
+
+    let unsigned_transaction = fund_raw_transaction(&client, self.fee_rate, &unfunded_transaction)?;
+
+    let signed_transaction = client
+      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+      .hex;
+
+    let transaction = client.send_raw_transaction(&signed_transaction)?;
+
+    Ok(Box::new(Output {
+      amount,
+      postage: postage.to_sat(),
+      rune: self.rune,
+      transaction,
+    }))
+  }
+}
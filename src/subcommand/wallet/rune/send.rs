@@ -0,0 +1,63 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Send {
+  address: Address<NetworkUnchecked>,
+  #[arg(help = "Send <AMOUNT> of rune.")]
+  amount: Decimal,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Send rune <RUNE>.")]
+  rune: SpacedRune,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Output {
+  pub postage: u64,
+  pub transaction: Txid,
+}
+
+impl Send {
+  pub(crate) fn run(
+    self,
+    wallet: String,
+    options: Options,
+    policy: PostagePolicy,
+  ) -> SubcommandResult {
+    let address = self.address.require_network(options.chain().network())?;
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let unspent_outputs = get_unspent_outputs(&client, &index)?;
+
+    let inscriptions = index.get_inscriptions(&unspent_outputs)?;
+
+    let runic_outputs =
+      index.get_runic_outputs(&unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>())?;
+
+    let postage = policy.postage(None, &address.script_pubkey())?;
+
+    let transaction = crate::subcommand::wallet::send::Send::send_runes(
+      address,
+      options.chain(),
+      &client,
+      self.amount,
+      self.fee_rate,
+      &index,
+      inscriptions,
+      postage,
+      self.rune,
+      runic_outputs,
+      unspent_outputs,
+    )?;
+
+    Ok(Box::new(Output {
+      postage: postage.to_sat(),
+      transaction,
+    }))
+  }
+}
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, reqwest::Url};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Sats {
@@ -7,6 +7,25 @@ pub(crate) struct Sats {
     help = "Find satoshis listed in first column of tab-separated value file <TSV>."
   )]
   tsv: Option<PathBuf>,
+  #[arg(
+    long,
+    conflicts_with = "tsv",
+    help = "Keep running, printing uncommon or rarer sats as they arrive in the wallet."
+  )]
+  watch: bool,
+  #[arg(
+    long,
+    default_value = "10",
+    requires = "watch",
+    help = "Check for newly arrived sats every <INTERVAL> seconds."
+  )]
+  interval: u64,
+  #[arg(
+    long,
+    requires = "watch",
+    help = "POST newly arrived sats to <WEBHOOK> as JSON."
+  )]
+  webhook: Option<Url>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -15,7 +34,7 @@ pub struct OutputTsv {
   pub output: OutPoint,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct OutputRare {
   pub sat: Sat,
   pub output: OutPoint,
@@ -35,6 +54,10 @@ impl Sats {
 
     let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
 
+    if self.watch {
+      return self.watch(&client, &index);
+    }
+
     let utxos = get_unspent_output_ranges(&client, &index)?;
 
     if let Some(path) = &self.tsv {
@@ -65,6 +88,80 @@ impl Sats {
   }
 }
 
+impl Sats {
+  /// Poll the wallet for rare sats, printing those that weren't in the wallet
+  /// at the previous poll as JSON lines, and sending them to the webhook, if
+  /// any, until shutdown. Sats already in the wallet at startup are not
+  /// reported.
+  fn watch(&self, client: &Client, index: &Index) -> SubcommandResult {
+    let webhook = reqwest::blocking::Client::new();
+
+    let mut seen = located_sats(&rare_sats(get_unspent_output_ranges(client, index)?));
+
+    log::info!("Watching wallet for {} rare sats", seen.len());
+
+    while !SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      thread::sleep(Duration::from_secs(self.interval));
+
+      index.update()?;
+
+      // outputs confirmed after the index was updated aren't in the index yet,
+      // and will be picked up on the next poll
+      let current = match get_unspent_output_ranges(client, index) {
+        Ok(utxos) => rare_sats(utxos),
+        Err(err) => {
+          log::warn!("failed to list wallet sats: {err}");
+          continue;
+        }
+      };
+
+      for arrival in arrivals(&seen, &current) {
+        println!("{}", serde_json::to_string(&arrival)?);
+
+        if let Some(url) = &self.webhook {
+          if let Err(err) = webhook
+            .post(url.clone())
+            .json(&arrival)
+            .send()
+            .and_then(|response| response.error_for_status())
+          {
+            log::warn!("failed to send sat {} to webhook {url}: {err}", arrival.sat);
+          }
+        }
+      }
+
+      seen = located_sats(&current);
+    }
+
+    Ok(Box::new(Empty {}))
+  }
+}
+
+fn located_sats(rare_sats: &[(OutPoint, Sat, u64, Rarity)]) -> BTreeSet<(OutPoint, Sat)> {
+  rare_sats
+    .iter()
+    .map(|(outpoint, sat, _offset, _rarity)| (*outpoint, *sat))
+    .collect()
+}
+
+/// Rare sats in `current` that were not in `seen`. A sat moving between two
+/// wallet outputs counts as an arrival, since the output holding it changed.
+fn arrivals(
+  seen: &BTreeSet<(OutPoint, Sat)>,
+  current: &[(OutPoint, Sat, u64, Rarity)],
+) -> Vec<OutputRare> {
+  current
+    .iter()
+    .filter(|(outpoint, sat, _offset, _rarity)| !seen.contains(&(*outpoint, *sat)))
+    .map(|(outpoint, sat, offset, rarity)| OutputRare {
+      sat: *sat,
+      output: *outpoint,
+      offset: *offset,
+      rarity: *rarity,
+    })
+    .collect()
+}
+
 fn rare_sats(utxos: Vec<(OutPoint, Vec<(u64, u64)>)>) -> Vec<(OutPoint, Sat, u64, Rarity)> {
   utxos
     .into_iter()
@@ -193,6 +290,50 @@ mod tests {
     )
   }
 
+  #[test]
+  fn arrivals_are_sats_not_previously_seen() {
+    let before = vec![(outpoint(1), Sat(0), 0, Rarity::Mythic)];
+
+    let after = vec![
+      (outpoint(1), Sat(0), 0, Rarity::Mythic),
+      (outpoint(2), Sat(50 * COIN_VALUE), 10, Rarity::Uncommon),
+      (outpoint(3), Sat(100 * COIN_VALUE), 0, Rarity::Uncommon),
+    ];
+
+    assert_eq!(
+      arrivals(&located_sats(&before), &after),
+      vec![
+        OutputRare {
+          sat: Sat(50 * COIN_VALUE),
+          output: outpoint(2),
+          offset: 10,
+          rarity: Rarity::Uncommon,
+        },
+        OutputRare {
+          sat: Sat(100 * COIN_VALUE),
+          output: outpoint(3),
+          offset: 0,
+          rarity: Rarity::Uncommon,
+        },
+      ]
+    );
+
+    assert_eq!(arrivals(&located_sats(&after), &after), Vec::new());
+
+    assert_eq!(
+      arrivals(
+        &located_sats(&after),
+        &[(outpoint(4), Sat(0), 0, Rarity::Mythic)]
+      ),
+      vec![OutputRare {
+        sat: Sat(0),
+        output: outpoint(4),
+        offset: 0,
+        rarity: Rarity::Mythic,
+      }]
+    );
+  }
+
   #[test]
   fn identify_from_tsv_none() {
     assert_eq!(
@@ -2,8 +2,10 @@ use {super::*, crate::subcommand::wallet::transaction_builder::Target};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Send {
-  address: Address<NetworkUnchecked>,
-  outgoing: Outgoing,
+  #[arg(help = "Send to <ADDRESS>, or to the address of output descriptor <ADDRESS>.")]
+  address: Destination,
+  #[arg(required_unless_present = "sat")]
+  outgoing: Option<Outgoing>,
   #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
   fee_rate: FeeRate,
   #[arg(
@@ -11,19 +13,42 @@ pub(crate) struct Send {
     help = "Target amount of postage to include with sent inscriptions. Default `10000sat`"
   )]
   pub(crate) postage: Option<Amount>,
+  #[arg(
+    long,
+    conflicts_with = "outgoing",
+    help = "Send <SAT>. Requires index created with `--index-sats`."
+  )]
+  pub(crate) sat: Option<Sat>,
+  #[arg(
+    long,
+    value_enum,
+    default_value_t,
+    help = "Sign transaction with <SIGNER>. `hwi` signs with a hardware device using the `hwi` command. Only supported when sending inscriptions or satpoints."
+  )]
+  pub(crate) signer: Signer,
+  #[arg(
+    long,
+    help = "Don't sign or broadcast transaction, and print PSBT to be signed offline and broadcast with `ord wallet broadcast-psbt`. Only supported when sending inscriptions or satpoints."
+  )]
+  pub(crate) unsigned: bool,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Output {
+  pub postage: Option<u64>,
+  pub psbt: Option<String>,
+  pub satpoint: Option<SatPoint>,
   pub transaction: Txid,
 }
 
 impl Send {
-  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
-    let address = self
-      .address
-      .clone()
-      .require_network(options.chain().network())?;
+  pub(crate) fn run(
+    self,
+    wallet: String,
+    options: Options,
+    policy: PostagePolicy,
+  ) -> SubcommandResult {
+    let address = self.address.address(options.chain().network())?;
 
     let index = Index::open(&options)?;
 
@@ -42,16 +67,50 @@ impl Send {
     let runic_outputs =
       index.get_runic_outputs(&unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>())?;
 
-    let satpoint = match self.outgoing {
+    let outgoing = match (self.outgoing, self.sat) {
+      (Some(outgoing), None) => outgoing,
+      (None, Some(sat)) => {
+        ensure!(
+          index.has_sat_index(),
+          "index must be built with `--index-sats` to use `--sat`"
+        );
+
+        Outgoing::SatPoint(
+          index
+            .find(sat)?
+            .ok_or_else(|| anyhow!("could not find sat `{sat}`"))?,
+        )
+      }
+      _ => unreachable!(),
+    };
+
+    ensure!(
+      !self.unsigned || matches!(outgoing, Outgoing::InscriptionId(_) | Outgoing::SatPoint(_)),
+      "`--unsigned` is only supported when sending inscriptions or satpoints"
+    );
+
+    ensure!(
+      self.signer == Signer::Wallet
+        || matches!(outgoing, Outgoing::InscriptionId(_) | Outgoing::SatPoint(_)),
+      "`--signer` is only supported when sending inscriptions or satpoints"
+    );
+
+    let satpoint = match outgoing {
       Outgoing::Amount(amount) => {
         Self::lock_non_cardinal_outputs(&client, &inscriptions, &runic_outputs, unspent_outputs)?;
         let transaction = Self::send_amount(&client, amount, address, self.fee_rate)?;
-        return Ok(Box::new(Output { transaction }));
+        return Ok(Box::new(Output {
+          postage: None,
+          psbt: None,
+          satpoint: None,
+          transaction,
+        }));
       }
       Outgoing::InscriptionId(id) => index
         .get_inscription_satpoint_by_id(id)?
         .ok_or_else(|| anyhow!("inscription {id} not found"))?,
       Outgoing::Rune { decimal, rune } => {
+        let postage = policy.postage(None, &address.script_pubkey())?;
         let transaction = Self::send_runes(
           address,
           chain,
@@ -60,11 +119,17 @@ impl Send {
           self.fee_rate,
           &index,
           inscriptions,
+          postage,
           rune,
           runic_outputs,
           unspent_outputs,
         )?;
-        return Ok(Box::new(Output { transaction }));
+        return Ok(Box::new(Output {
+          postage: Some(postage.to_sat()),
+          psbt: None,
+          satpoint: None,
+          transaction,
+        }));
       }
       Outgoing::SatPoint(satpoint) => {
         for inscription_satpoint in inscriptions.keys() {
@@ -87,8 +152,8 @@ impl Send {
       get_change_address(&client, chain)?,
     ];
 
-    let postage = if let Some(postage) = self.postage {
-      Target::ExactPostage(postage)
+    let target = if let Some(postage) = self.postage {
+      Target::ExactPostage(policy.postage(Some(postage), &address.script_pubkey())?)
     } else {
       Target::Postage
     };
@@ -96,26 +161,85 @@ impl Send {
     let unsigned_transaction = TransactionBuilder::new(
       satpoint,
       inscriptions,
-      unspent_outputs,
+      unspent_outputs.clone(),
       locked_outputs,
       runic_outputs,
       address.clone(),
       change,
       self.fee_rate,
-      postage,
+      target,
     )
+    .postage_policy(policy)
     .build_transaction()?;
 
-    let signed_tx = client
-      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
-      .hex;
+    let sent = Self::locate(&unsigned_transaction, &unspent_outputs, satpoint);
+
+    let postage = sent.map(|satpoint| {
+      unsigned_transaction.output[usize::try_from(satpoint.outpoint.vout).unwrap()].value
+    });
+
+    if self.unsigned {
+      return Ok(Box::new(Output {
+        postage,
+        psbt: Some(create_psbt(
+          &client,
+          &unsigned_transaction,
+          &BTreeMap::new(),
+        )?),
+        satpoint: sent,
+        transaction: unsigned_transaction.txid(),
+      }));
+    }
+
+    let signed_tx = self
+      .signer
+      .sign(&client, chain, &unsigned_transaction, &BTreeMap::new())?;
 
     let txid = client.send_raw_transaction(&signed_tx)?;
 
-    Ok(Box::new(Output { transaction: txid }))
+    Ok(Box::new(Output {
+      postage,
+      psbt: None,
+      satpoint: sent,
+      transaction: txid,
+    }))
+  }
+
+  /// Find where the sat at `satpoint` ends up in `transaction`, so the output
+  /// carrying it can be reported.
+  fn locate(
+    transaction: &Transaction,
+    amounts: &BTreeMap<OutPoint, Amount>,
+    satpoint: SatPoint,
+  ) -> Option<SatPoint> {
+    let mut offset = satpoint.offset;
+
+    for input in &transaction.input {
+      if input.previous_output == satpoint.outpoint {
+        break;
+      }
+
+      offset += amounts.get(&input.previous_output)?.to_sat();
+    }
+
+    for (vout, output) in transaction.output.iter().enumerate() {
+      if offset < output.value {
+        return Some(SatPoint {
+          outpoint: OutPoint {
+            txid: transaction.txid(),
+            vout: vout.try_into().unwrap(),
+          },
+          offset,
+        });
+      }
+
+      offset -= output.value;
+    }
+
+    None
   }
 
-  fn lock_non_cardinal_outputs(
+  pub(crate) fn lock_non_cardinal_outputs(
     client: &Client,
     inscriptions: &BTreeMap<SatPoint, InscriptionId>,
     runic_outputs: &BTreeSet<OutPoint>,
@@ -163,7 +287,7 @@ impl Send {
     )?)
   }
 
-  fn send_runes(
+  pub(crate) fn send_runes(
     address: Address,
     chain: Chain,
     client: &Client,
@@ -171,6 +295,7 @@ impl Send {
     fee_rate: FeeRate,
     index: &Index,
     inscriptions: BTreeMap<SatPoint, InscriptionId>,
+    postage: Amount,
     spaced_rune: SpacedRune,
     runic_outputs: BTreeSet<OutPoint>,
     unspent_outputs: BTreeMap<OutPoint, Amount>,
@@ -252,11 +377,11 @@ impl Send {
         },
         TxOut {
           script_pubkey: get_change_address(client, chain)?.script_pubkey(),
-          value: TARGET_POSTAGE.to_sat(),
+          value: postage.to_sat(),
         },
         TxOut {
           script_pubkey: address.script_pubkey(),
-          value: TARGET_POSTAGE.to_sat(),
+          value: postage.to_sat(),
         },
       ],
     };
@@ -0,0 +1,127 @@
+use {
+  super::*, bitcoincore_rpc::bitcoincore_rpc_json::SignRawTransactionInput, clap::ValueEnum,
+  std::process::Command,
+};
+
+#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Signer {
+  #[default]
+  Wallet,
+  Hwi,
+}
+
+impl Signer {
+  /// Sign `transaction`, returning the serialized signed transaction.
+  /// `prevouts` must contain outputs spent by `transaction` which are not yet
+  /// known to Bitcoin Core.
+  pub(crate) fn sign(
+    self,
+    client: &Client,
+    chain: Chain,
+    transaction: &Transaction,
+    prevouts: &BTreeMap<OutPoint, TxOut>,
+  ) -> Result<Vec<u8>> {
+    match self {
+      Self::Wallet => {
+        let inputs = prevouts
+          .iter()
+          .map(|(outpoint, tx_out)| SignRawTransactionInput {
+            txid: outpoint.txid,
+            vout: outpoint.vout,
+            script_pub_key: tx_out.script_pubkey.clone(),
+            redeem_script: None,
+            amount: Some(Amount::from_sat(tx_out.value)),
+          })
+          .collect::<Vec<SignRawTransactionInput>>();
+
+        Ok(
+          client
+            .sign_raw_transaction_with_wallet(
+              transaction,
+              (!inputs.is_empty()).then_some(inputs.as_slice()),
+              None,
+            )?
+            .hex,
+        )
+      }
+      Self::Hwi => {
+        let psbt = create_psbt(client, transaction, prevouts)?;
+
+        let descriptors = client.list_descriptors(None)?;
+
+        let fingerprint = descriptors
+          .descriptors
+          .iter()
+          .find_map(|descriptor| Self::fingerprint(&descriptor.desc))
+          .context("wallet descriptors do not contain a key origin fingerprint")?;
+
+        let output = Command::new("hwi")
+          .args([
+            "--chain",
+            match chain {
+              Chain::Mainnet => "main",
+              Chain::Testnet => "test",
+              Chain::Testnet4 => "testnet4",
+              Chain::Signet => "signet",
+              Chain::Regtest => "regtest",
+            },
+            "--fingerprint",
+            fingerprint,
+            "signtx",
+            &psbt,
+          ])
+          .output()
+          .context("failed to run `hwi`")?;
+
+        #[derive(Deserialize)]
+        struct Output {
+          error: Option<String>,
+          psbt: Option<String>,
+        }
+
+        let output = serde_json::from_slice::<Output>(&output.stdout).with_context(|| {
+          format!(
+            "failed to parse `hwi signtx` output: {}",
+            String::from_utf8_lossy(&output.stderr)
+          )
+        })?;
+
+        if let Some(error) = output.error {
+          bail!("`hwi signtx` failed: {error}");
+        }
+
+        let psbt = output.psbt.context("`hwi signtx` did not return a PSBT")?;
+
+        Ok(consensus::encode::serialize(
+          &broadcast_psbt::BroadcastPsbt::finalize(&psbt)?,
+        ))
+      }
+    }
+  }
+
+  /// Key origin fingerprint of `descriptor`, which HWI uses to select the
+  /// device holding the wallet's keys.
+  fn fingerprint(descriptor: &str) -> Option<&str> {
+    let start = descriptor.find('[')? + 1;
+    let fingerprint = descriptor.get(start..start + 8)?;
+    fingerprint
+      .chars()
+      .all(|c| c.is_ascii_hexdigit())
+      .then_some(fingerprint)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn fingerprint() {
+    assert_eq!(
+      Signer::fingerprint("tr([5a8c8b1b/86'/0'/0']xpub/0/*)#abcdefgh"),
+      Some("5a8c8b1b")
+    );
+    assert_eq!(Signer::fingerprint("tr(xpub/0/*)"), None);
+    assert_eq!(Signer::fingerprint("tr([5a8c/86'/0'/0']xpub/0/*)"), None);
+  }
+}
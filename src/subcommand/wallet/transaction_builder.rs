@@ -14,8 +14,10 @@
 //! recipient's address. To build the transaction call
 //! `Transaction::build_transaction`.
 //!
-//! `Target::Postage` ensures that the outgoing value is at most 20,000 sats,
-//! reducing it to 10,000 sats if coin selection requires adding excess value.
+//! `Target::Postage` ensures that the outgoing value is at most the maximum
+//! postage of the `PostagePolicy`, 20,000 sats by default, reducing it to the
+//! target postage, 10,000 sats by default, if coin selection requires adding
+//! excess value.
 //!
 //! `Target::Value(Amount)` ensures that the outgoing value is exactly the
 //! requested amount,
@@ -51,6 +53,7 @@ pub enum Error {
     inscribed_satpoint: SatPoint,
     inscription_id: InscriptionId,
   },
+  TooManyPaddingInputs(usize),
   ValueOverflow,
 }
 
@@ -82,6 +85,10 @@ impl fmt::Display for Error {
         f,
         "cannot send {outgoing_satpoint} without also sending inscription {inscription_id} at {inscribed_satpoint}"
       ),
+      Error::TooManyPaddingInputs(max) => write!(
+        f,
+        "padding alignment output above dust limit requires more than {max} additional inputs"
+      ),
       Error::ValueOverflow => write!(f, "arithmetic overflow calculating value"),
       Error::DuplicateAddress(address) => write!(f, "duplicate input address: {address}"),
     }
@@ -100,6 +107,7 @@ pub struct TransactionBuilder {
   locked_utxos: BTreeSet<OutPoint>,
   outgoing: SatPoint,
   outputs: Vec<(Address, Amount)>,
+  postage: PostagePolicy,
   recipient: Address,
   runic_utxos: BTreeSet<OutPoint>,
   target: Target,
@@ -136,6 +144,7 @@ impl TransactionBuilder {
       locked_utxos,
       outgoing,
       outputs: Vec::new(),
+      postage: PostagePolicy::default(),
       recipient,
       runic_utxos,
       target,
@@ -143,6 +152,11 @@ impl TransactionBuilder {
     }
   }
 
+  pub(crate) fn postage_policy(mut self, postage: PostagePolicy) -> Self {
+    self.postage = postage;
+    self
+  }
+
   pub fn build_transaction(self) -> Result<Transaction> {
     if self.change_addresses.len() < 2 {
       return Err(Error::DuplicateAddress(
@@ -266,7 +280,17 @@ impl TransactionBuilder {
       if self.outputs[0].1 >= dust_limit {
         tprintln!("no padding needed");
       } else {
+        let mut padding_inputs = 0;
+
         while self.outputs[0].1 < dust_limit {
+          if let Some(max) = self.postage.max_padding_inputs {
+            if padding_inputs >= max {
+              return Err(Error::TooManyPaddingInputs(max));
+            }
+          }
+
+          padding_inputs += 1;
+
           let (utxo, size) = self.select_cardinal_utxo(dust_limit - self.outputs[0].1, true)?;
 
           self.inputs.insert(0, utxo);
@@ -346,7 +370,7 @@ impl TransactionBuilder {
     if let Some(excess) = value.checked_sub(self.fee_rate.fee(self.estimate_vbytes())) {
       let (max, target) = match self.target {
         Target::ExactPostage(postage) => (postage, postage),
-        Target::Postage => (Self::MAX_POSTAGE, TARGET_POSTAGE),
+        Target::Postage => (self.postage.max, self.postage.target),
         Target::Value(value) => (value, value),
       };
 
@@ -558,7 +582,7 @@ impl TransactionBuilder {
         match self.target {
           Target::Postage => {
             assert!(
-              Amount::from_sat(output.value) <= Self::MAX_POSTAGE + slop,
+              Amount::from_sat(output.value) <= self.postage.max + slop,
               "invariant: excess postage is stripped"
             );
           }
@@ -781,6 +805,7 @@ mod tests {
         (change(0), Amount::from_sat(5_000)),
         (change(1), Amount::from_sat(1_724)),
       ],
+      postage: PostagePolicy::default(),
       target: Target::Postage,
     };
 
@@ -1320,6 +1345,7 @@ mod tests {
         (recipient(), Amount::from_sat(5_000)),
         (change(1), Amount::from_sat(1_774)),
       ],
+      postage: PostagePolicy::default(),
       target: Target::Postage,
     }
     .build()
@@ -1351,6 +1377,7 @@ mod tests {
         (change(0), Amount::from_sat(5_000)),
         (change(0), Amount::from_sat(1_774)),
       ],
+      postage: PostagePolicy::default(),
       target: Target::Postage,
     }
     .build()
@@ -1901,6 +1928,42 @@ mod tests {
     )
   }
 
+  #[test]
+  fn pad_alignment_output_respects_max_padding_inputs() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(20_000)),
+      (outpoint(2), Amount::from_sat(105)),
+      (outpoint(3), Amount::from_sat(104)),
+      (outpoint(4), Amount::from_sat(101)),
+    ];
+
+    let tx_builder = |max_padding_inputs| {
+      TransactionBuilder::new(
+        satpoint(1, 1),
+        BTreeMap::new(),
+        utxos.clone().into_iter().collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Value(Amount::from_sat(10_000)),
+      )
+      .postage_policy(PostagePolicy {
+        max_padding_inputs: Some(max_padding_inputs),
+        ..Default::default()
+      })
+      .select_outgoing()
+      .unwrap()
+      .align_outgoing()
+      .pad_alignment_output()
+    };
+
+    assert_eq!(tx_builder(2).unwrap_err(), Error::TooManyPaddingInputs(2));
+
+    assert_eq!(tx_builder(3).unwrap().inputs.len(), 4);
+  }
+
   fn select_cardinal_utxo_prefer_under_helper(
     target_value: Amount,
     prefer_under: bool,
@@ -0,0 +1,136 @@
+use {super::*, bitcoin::secp256k1::constants::SCHNORR_SIGNATURE_SIZE, bitcoincore_rpc::Client};
+
+pub mod consolidate;
+pub mod freeze;
+pub mod split;
+pub mod unfreeze;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Utxos {
+  #[command(about = "Consolidate small cardinal outputs")]
+  Consolidate(consolidate::Consolidate),
+  #[command(about = "Prevent outputs from being spent")]
+  Freeze(freeze::Freeze),
+  #[command(about = "Split cardinal output into padding outputs")]
+  Split(split::Split),
+  #[command(about = "Allow frozen outputs to be spent")]
+  Unfreeze(unfreeze::Unfreeze),
+}
+
+impl Utxos {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    match self {
+      Self::Consolidate(consolidate) => consolidate.run(wallet, options),
+      Self::Freeze(freeze) => freeze.run(wallet, options),
+      Self::Split(split) => split.run(wallet, options),
+      Self::Unfreeze(unfreeze) => unfreeze.run(wallet, options),
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub transaction: Txid,
+}
+
+/// Unspent wallet outputs that may be spent freely, excluding outputs that
+/// contain inscriptions or runes, or are frozen.
+pub(crate) fn cardinal_utxos(index: &Index, client: &Client) -> Result<BTreeMap<OutPoint, Amount>> {
+  let unspent_outputs = get_unspent_outputs(client, index)?;
+
+  let inscribed_outputs = index
+    .get_inscriptions(&unspent_outputs)?
+    .into_keys()
+    .map(|satpoint| satpoint.outpoint)
+    .collect::<BTreeSet<OutPoint>>();
+
+  let runic_outputs =
+    index.get_runic_outputs(&unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>())?;
+
+  let locked_outputs = get_locked_outputs(client)?;
+
+  Ok(
+    unspent_outputs
+      .into_iter()
+      .filter(|(outpoint, _)| {
+        !inscribed_outputs.contains(outpoint)
+          && !runic_outputs.contains(outpoint)
+          && !locked_outputs.contains(outpoint)
+      })
+      .collect(),
+  )
+}
+
+fn lock_unspent(client: &Client, unlock: bool, outpoints: &[OutPoint]) -> Result {
+  let outputs = outpoints
+    .iter()
+    .map(|outpoint| serde_json::json!({ "txid": outpoint.txid, "vout": outpoint.vout }))
+    .collect::<Vec<serde_json::Value>>();
+
+  ensure!(
+    client.call::<bool>("lockunspent", &[unlock.into(), outputs.into(), true.into()])?,
+    "failed to {} outputs",
+    if unlock { "unfreeze" } else { "freeze" },
+  );
+
+  Ok(())
+}
+
+/// Spend `inputs` to `outputs`, with the last output receiving the inputs'
+/// value not assigned to other outputs, less the fee.
+fn spend(
+  client: &Client,
+  inputs: Vec<(OutPoint, Amount)>,
+  mut outputs: Vec<TxOut>,
+  fee_rate: FeeRate,
+) -> Result<Txid> {
+  let total = inputs
+    .iter()
+    .map(|(_, amount)| amount.to_sat())
+    .sum::<u64>();
+
+  let mut transaction = Transaction {
+    version: 2,
+    lock_time: LockTime::ZERO,
+    input: inputs
+      .iter()
+      .map(|(previous_output, _)| TxIn {
+        previous_output: *previous_output,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::from_slice(&[&[0; SCHNORR_SIGNATURE_SIZE]]),
+      })
+      .collect(),
+    output: outputs.clone(),
+  };
+
+  let fee = fee_rate.fee(transaction.vsize()).to_sat();
+
+  let assigned = outputs[..outputs.len() - 1]
+    .iter()
+    .map(|output| output.value)
+    .sum::<u64>();
+
+  let last = outputs.last_mut().unwrap();
+
+  let remainder = total.saturating_sub(assigned + fee);
+
+  ensure!(
+    remainder >= last.script_pubkey.dust_value().to_sat(),
+    "inputs worth {total} sat are not enough to pay for outputs worth {assigned} sat and a fee of {fee} sat",
+  );
+
+  last.value = remainder;
+
+  transaction.output = outputs;
+
+  for input in &mut transaction.input {
+    input.witness = Witness::new();
+  }
+
+  let signed = client.sign_raw_transaction_with_wallet(&transaction, None, None)?;
+
+  ensure!(signed.complete, "failed to sign transaction");
+
+  Ok(client.send_raw_transaction(&signed.hex)?)
+}
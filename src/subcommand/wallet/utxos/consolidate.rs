@@ -0,0 +1,45 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Consolidate {
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Consolidate cardinal outputs worth at most <MAX_VALUE>. Default `10000sat`."
+  )]
+  max_value: Option<Amount>,
+}
+
+impl Consolidate {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let max_value = self.max_value.unwrap_or(TARGET_POSTAGE);
+
+    let inputs = cardinal_utxos(&index, &client)?
+      .into_iter()
+      .filter(|(_, amount)| *amount <= max_value)
+      .collect::<Vec<(OutPoint, Amount)>>();
+
+    ensure!(
+      inputs.len() >= 2,
+      "wallet contains fewer than two cardinal outputs worth at most {max_value}"
+    );
+
+    let transaction = spend(
+      &client,
+      inputs,
+      vec![TxOut {
+        script_pubkey: get_change_address(&client, options.chain())?.script_pubkey(),
+        value: 0,
+      }],
+      self.fee_rate,
+    )?;
+
+    Ok(Box::new(super::Output { transaction }))
+  }
+}
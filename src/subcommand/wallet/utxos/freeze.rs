@@ -0,0 +1,36 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Freeze {
+  #[arg(required = true, help = "Freeze <OUTPOINTS>.")]
+  outpoints: Vec<OutPoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub frozen: Vec<OutPoint>,
+}
+
+impl Freeze {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let unspent_outputs = get_unspent_outputs(&client, &index)?;
+
+    for outpoint in &self.outpoints {
+      ensure!(
+        unspent_outputs.contains_key(outpoint),
+        "output {outpoint} is not an unspent wallet output"
+      );
+    }
+
+    lock_unspent(&client, false, &self.outpoints)?;
+
+    Ok(Box::new(Output {
+      frozen: self.outpoints,
+    }))
+  }
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Split {
+  #[arg(long, help = "Create outputs of <AMOUNT>. Default `10000sat`.")]
+  amount: Option<Amount>,
+  #[arg(
+    long,
+    default_value = "1",
+    help = "Create <COUNT> outputs of <AMOUNT>."
+  )]
+  count: usize,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(help = "Split <OUTPOINT>.")]
+  outpoint: OutPoint,
+}
+
+impl Split {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    ensure!(self.count > 0, "<COUNT> must be greater than zero");
+
+    let index = Index::open(&options)?;
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let chain = options.chain();
+
+    let value = *cardinal_utxos(&index, &client)?
+      .get(&self.outpoint)
+      .ok_or_else(|| {
+        anyhow!(
+          "output {} is not a cardinal wallet output, and may contain inscriptions or runes, or be frozen",
+          self.outpoint
+        )
+      })?;
+
+    let amount = self.amount.unwrap_or(TARGET_POSTAGE);
+
+    let mut outputs = Vec::new();
+
+    for _ in 0..=self.count {
+      outputs.push(TxOut {
+        script_pubkey: get_change_address(&client, chain)?.script_pubkey(),
+        value: amount.to_sat(),
+      });
+    }
+
+    ensure!(
+      amount.to_sat() >= outputs[0].script_pubkey.dust_value().to_sat(),
+      "<AMOUNT> of {amount} is below the dust limit"
+    );
+
+    let transaction = spend(
+      &client,
+      vec![(self.outpoint, value)],
+      outputs,
+      self.fee_rate,
+    )?;
+
+    Ok(Box::new(super::Output { transaction }))
+  }
+}
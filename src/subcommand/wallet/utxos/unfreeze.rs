@@ -0,0 +1,33 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Unfreeze {
+  #[arg(required = true, help = "Unfreeze <OUTPOINTS>.")]
+  outpoints: Vec<OutPoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub unfrozen: Vec<OutPoint>,
+}
+
+impl Unfreeze {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let locked_outputs = get_locked_outputs(&client)?;
+
+    for outpoint in &self.outpoints {
+      ensure!(
+        locked_outputs.contains(outpoint),
+        "output {outpoint} is not frozen"
+      );
+    }
+
+    lock_unspent(&client, true, &self.outpoints)?;
+
+    Ok(Box::new(Output {
+      unfrozen: self.outpoints,
+    }))
+  }
+}
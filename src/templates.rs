@@ -1,38 +1,52 @@
 use {super::*, boilerplate::Boilerplate};
 
 pub(crate) use {
+  address::{
+    ActivityJson, AddressChangeJson, AddressChangesJson, AddressRuneBalanceJson, AddressRuneJson,
+    AddressRunesJson, RuneOutpointJson,
+  },
   block::{BlockHtml, BlockJson},
   blocks::BlocksHtml,
   children::{ChildrenHtml, ChildrenJson},
   clock::ClockSvg,
-  collections::CollectionsHtml,
+  collections::{
+    CollectionHolderJson, CollectionHoldersJson, CollectionStatsJson, CollectionsHtml,
+  },
+  dashboard::DashboardHtml,
+  fees::{BlockFeesJson, FeesJson},
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
-  inscription::{InscriptionHtml, InscriptionJson},
+  inscription::{
+    InscriptionHtml, InscriptionJson, InscriptionTransferJson, InscriptionTransfersJson,
+  },
   inscriptions::{InscriptionsHtml, InscriptionsJson},
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
   output::{OutputHtml, OutputJson},
   preview::{
-    PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
-    PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
+    PreviewAudioHtml, PreviewCheckJson, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
+    PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
+    PreviewVideoHtml,
   },
   range::RangeHtml,
   rare::RareTxt,
   rune::RuneHtml,
   runes::RunesHtml,
-  sat::{SatHtml, SatInscriptionJson, SatInscriptionsJson, SatJson},
+  sat::{SatHtml, SatInscriptionJson, SatInscriptionsJson, SatJson, SatSummaryJson},
   server_config::ServerConfig,
-  status::StatusHtml,
+  status::{IndexProgress, IndexStage, StatusHtml},
   transaction::TransactionHtml,
 };
 
+pub mod address;
 pub mod block;
 mod blocks;
 mod children;
 mod clock;
 pub mod collections;
+mod dashboard;
+pub mod fees;
 mod home;
 mod iframe;
 mod input;
@@ -41,7 +55,7 @@ pub mod inscriptions;
 mod inscriptions_block;
 mod metadata;
 pub mod output;
-mod preview;
+pub mod preview;
 mod range;
 mod rare;
 mod rune;
@@ -0,0 +1,63 @@
+use super::*;
+
+/// Page of an address's activity feed, newest first. `next` is passed as
+/// `?cursor=` to fetch the following page, and is `None` on the last page.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActivityJson {
+  pub activity: Vec<Activity>,
+  pub address: String,
+  pub next: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressRunesJson {
+  pub address: String,
+  pub runes: Vec<AddressRuneJson>,
+}
+
+/// Balance of one rune held by an address, in the rune's smallest unit, and
+/// formatted with the rune's divisibility and symbol.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressRuneJson {
+  pub amount: u128,
+  pub display: String,
+  pub divisibility: u8,
+  pub id: RuneId,
+  pub outpoints: Vec<RuneOutpointJson>,
+  pub rune: SpacedRune,
+  pub symbol: Option<char>,
+}
+
+/// Balance of one rune held by an address at the end of the block at
+/// `height`, which defaults to the chain tip.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressRuneBalanceJson {
+  pub address: String,
+  pub amount: u128,
+  pub display: String,
+  pub height: u32,
+  pub id: RuneId,
+  pub rune: SpacedRune,
+}
+
+/// Net changes to the address index made by the block at `height`. An
+/// outpoint created and spent in the same block appears in neither list.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressChangesJson {
+  pub added: Vec<AddressChangeJson>,
+  pub height: u32,
+  pub removed: Vec<AddressChangeJson>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressChangeJson {
+  pub address: Option<String>,
+  pub outpoint: OutPoint,
+  pub script_pubkey: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneOutpointJson {
+  pub amount: u128,
+  pub outpoint: OutPoint,
+}
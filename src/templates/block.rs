@@ -75,7 +75,7 @@ mod tests {
   fn html() {
     assert_regex_match!(
       BlockHtml::new(
-        Chain::Mainnet.genesis_block(),
+        Chain::Mainnet.genesis_block().unwrap(),
         Height(0),
         Height(0),
         0,
@@ -110,7 +110,7 @@ mod tests {
   fn next_active_when_not_last() {
     assert_regex_match!(
       BlockHtml::new(
-        Chain::Mainnet.genesis_block(),
+        Chain::Mainnet.genesis_block().unwrap(),
         Height(0),
         Height(1),
         0,
@@ -124,7 +124,7 @@ mod tests {
   fn prev_active_when_not_first() {
     assert_regex_match!(
       BlockHtml::new(
-        Chain::Mainnet.genesis_block(),
+        Chain::Mainnet.genesis_block().unwrap(),
         Height(1),
         Height(1),
         0,
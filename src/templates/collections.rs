@@ -13,6 +13,31 @@ impl PageContent for CollectionsHtml {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CollectionStatsJson {
+  pub first_child_height: Option<u32>,
+  pub holders: u64,
+  pub inscription_id: InscriptionId,
+  pub last_child_height: Option<u32>,
+  pub supply: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CollectionHoldersJson {
+  pub holders: Vec<CollectionHolderJson>,
+  pub more: bool,
+  pub page_index: usize,
+}
+
+/// A script pubkey holding `children` children of a collection, with its
+/// address if the script pubkey has one.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CollectionHolderJson {
+  pub address: Option<String>,
+  pub children: u64,
+  pub script_pubkey: String,
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
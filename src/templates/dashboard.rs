@@ -0,0 +1,157 @@
+use {super::*, crate::index::metrics::Samples};
+
+#[derive(Boilerplate)]
+pub(crate) struct DashboardHtml {
+  pub(crate) blocks_indexed: u32,
+  pub(crate) chain_block_count: Option<u32>,
+  pub(crate) progress: IndexProgress,
+  pub(crate) samples: Samples,
+}
+
+impl DashboardHtml {
+  fn lag(&self) -> Option<u32> {
+    self
+      .chain_block_count
+      .map(|block_count| block_count.saturating_sub(self.blocks_indexed))
+  }
+
+  fn commit_durations(&self) -> Sparkline {
+    Sparkline(
+      self
+        .samples
+        .commits
+        .iter()
+        .map(|commit| commit.duration.as_secs_f64())
+        .collect(),
+    )
+  }
+
+  fn events(&self) -> Sparkline {
+    Sparkline(
+      self
+        .samples
+        .events
+        .iter()
+        .map(|sample| sample.events as f64)
+        .collect(),
+    )
+  }
+
+  fn database_sizes(&self) -> Sparkline {
+    Sparkline(
+      self
+        .samples
+        .database_sizes
+        .iter()
+        .map(|size| *size as f64)
+        .collect(),
+    )
+  }
+}
+
+/// Inline SVG line chart of `values`, scaled so that the largest value
+/// touches the top.
+pub(crate) struct Sparkline(pub(crate) Vec<f64>);
+
+impl Sparkline {
+  const HEIGHT: f64 = 20.0;
+  const WIDTH: f64 = 100.0;
+}
+
+impl Display for Sparkline {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let max = self.0.iter().copied().fold(0.0, f64::max);
+
+    write!(
+      f,
+      "<svg class=sparkline viewBox='0 0 {} {}' preserveAspectRatio=none><polyline points='",
+      Self::WIDTH,
+      Self::HEIGHT,
+    )?;
+
+    for (i, value) in self.0.iter().enumerate() {
+      let x = if self.0.len() > 1 {
+        i as f64 / (self.0.len() - 1) as f64 * Self::WIDTH
+      } else {
+        0.0
+      };
+
+      let y = if max > 0.0 {
+        Self::HEIGHT - value / max * Self::HEIGHT
+      } else {
+        Self::HEIGHT
+      };
+
+      if i > 0 {
+        write!(f, " ")?;
+      }
+
+      write!(f, "{x:.1},{y:.1}")?;
+    }
+
+    write!(f, "'/></svg>")
+  }
+}
+
+impl PageContent for DashboardHtml {
+  fn title(&self) -> String {
+    "Dashboard".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sparkline() {
+    assert_eq!(
+      Sparkline(vec![0.0, 2.0, 1.0]).to_string(),
+      "<svg class=sparkline viewBox='0 0 100 20' preserveAspectRatio=none>\
+      <polyline points='0.0,20.0 50.0,0.0 100.0,10.0'/></svg>",
+    );
+  }
+
+  #[test]
+  fn empty_sparkline() {
+    assert_eq!(
+      Sparkline(Vec::new()).to_string(),
+      "<svg class=sparkline viewBox='0 0 100 20' preserveAspectRatio=none>\
+      <polyline points=''/></svg>",
+    );
+  }
+
+  #[test]
+  fn dashboard() {
+    assert_regex_match!(
+      DashboardHtml {
+        blocks_indexed: 3,
+        chain_block_count: Some(5),
+        progress: IndexProgress::default(),
+        samples: Samples::default(),
+      },
+      "<h1>Dashboard</h1>
+<dl>
+  <dt>blocks indexed</dt>
+  <dd>3</dd>
+  <dt>chain block count</dt>
+  <dd>5</dd>
+  <dt>indexing lag</dt>
+  <dd>2 blocks</dd>
+  <dt>indexing</dt>
+  <dd>idle</dd>
+  <dt>errors</dt>
+  <dd>0</dd>
+  <dt>commit duration</dt>
+  <dd><svg .*</svg></dd>
+  <dt>protocol events per block</dt>
+  <dd><svg .*</svg></dd>
+  <dt>database size</dt>
+  <dd><svg .*</svg></dd>
+</dl>
+<h2>Recent Reorgs</h2>
+<p>none</p>
+"
+    );
+  }
+}
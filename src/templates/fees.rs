@@ -0,0 +1,21 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct FeesJson {
+  pub blocks: Vec<BlockFeesJson>,
+}
+
+/// Inscription creations and transfers in the block at `height`, with the
+/// fees paid by their transactions and the value of the outputs they were
+/// sent to, in sats. A transaction's fee is split evenly between the
+/// inscriptions it creates, and separately between those it transfers.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockFeesJson {
+  pub creation_fees: u64,
+  pub creation_value: u64,
+  pub creations: u64,
+  pub height: u32,
+  pub transfer_fees: u64,
+  pub transfer_value: u64,
+  pub transfers: u64,
+}
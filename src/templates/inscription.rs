@@ -6,6 +6,7 @@ pub(crate) struct InscriptionHtml {
   pub(crate) children: Vec<InscriptionId>,
   pub(crate) genesis_fee: u64,
   pub(crate) genesis_height: u32,
+  pub(crate) hidden: bool,
   pub(crate) inscription: Inscription,
   pub(crate) inscription_id: InscriptionId,
   pub(crate) inscription_number: i32,
@@ -13,6 +14,7 @@ pub(crate) struct InscriptionHtml {
   pub(crate) output: Option<TxOut>,
   pub(crate) parent: Option<InscriptionId>,
   pub(crate) previous: Option<InscriptionId>,
+  pub(crate) recursive_endpoints: Vec<RecursiveEndpoint>,
   pub(crate) rune: Option<SpacedRune>,
   pub(crate) sat: Option<Sat>,
   pub(crate) satpoint: SatPoint,
@@ -24,8 +26,10 @@ pub(crate) struct InscriptionHtml {
 pub struct InscriptionJson {
   pub address: Option<String>,
   pub children: Vec<InscriptionId>,
+  pub cid: Option<String>,
   pub content_length: Option<usize>,
   pub content_type: Option<String>,
+  pub content_verdicts: Vec<ContentVerdict>,
   pub genesis_fee: u64,
   pub genesis_height: u32,
   pub inscription_id: InscriptionId,
@@ -34,12 +38,33 @@ pub struct InscriptionJson {
   pub output_value: Option<u64>,
   pub parent: Option<InscriptionId>,
   pub previous: Option<InscriptionId>,
+  pub recursive_endpoints: Vec<RecursiveEndpoint>,
   pub rune: Option<SpacedRune>,
   pub sat: Option<Sat>,
   pub satpoint: SatPoint,
   pub timestamp: i64,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionTransfersJson {
+  pub genesis_fee: u64,
+  pub genesis_height: u32,
+  pub inscription_id: InscriptionId,
+  pub transfers: Vec<InscriptionTransferJson>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionTransferJson {
+  pub fee: u64,
+  pub from_address: Option<String>,
+  pub from_satpoint: SatPoint,
+  pub height: u32,
+  pub to_address: Option<String>,
+  pub to_satpoint: SatPoint,
+  pub txid: Txid,
+  pub value: u64,
+}
+
 impl PageContent for InscriptionHtml {
   fn title(&self) -> String {
     format!("Inscription {}", self.inscription_number)
@@ -457,4 +482,36 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn hidden() {
+    assert_regex_match!(
+      InscriptionHtml {
+        genesis_fee: 1,
+        hidden: true,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        inscription_id: inscription_id(1),
+        inscription_number: 1,
+        satpoint: satpoint(1, 0),
+        ..Default::default()
+      },
+      "
+        <h1>Inscription 1</h1>
+        <div class=inscription>
+        <div>❮</div>
+        <iframe .* src=/preview/1{64}i1></iframe>
+        <div>❯</div>
+        </div>
+        <dl>
+          <dt>id</dt>
+          <dd class=monospace>1{64}i1</dd>
+          <dt>content type</dt>
+          <dd>text/plain;charset=utf-8</dd>
+          <dt>timestamp</dt>
+          .*
+        </dl>
+      "
+      .unindent()
+    );
+  }
 }
@@ -5,6 +5,7 @@ pub(crate) struct InscriptionsHtml {
   pub(crate) inscriptions: Vec<InscriptionId>,
   pub(crate) prev: Option<usize>,
   pub(crate) next: Option<usize>,
+  pub(crate) query: String,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -31,6 +32,7 @@ mod tests {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
         prev: None,
         next: None,
+        query: String::new(),
       },
       "
         <h1>All Inscriptions</h1>
@@ -54,6 +56,7 @@ mod tests {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
         prev: Some(1),
         next: Some(2),
+        query: String::new(),
       },
       "
         <h1>All Inscriptions</h1>
@@ -69,4 +72,23 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn pagination_links_keep_query() {
+    assert_regex_match!(
+      InscriptionsHtml {
+        inscriptions: Vec::new(),
+        prev: Some(1),
+        next: Some(3),
+        query: "?order=asc&sort=fee".into(),
+      },
+      "
+        .*
+        <a class=prev href=/inscriptions/1\\?order=asc&amp;sort=fee>prev</a>
+        <a class=next href=/inscriptions/3\\?order=asc&amp;sort=fee>next</a>
+        .*
+      "
+      .unindent()
+    );
+  }
 }
@@ -48,3 +48,17 @@ pub(crate) struct PreviewUnknownHtml;
 pub(crate) struct PreviewVideoHtml {
   pub(crate) inscription_id: InscriptionId,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct PreviewCheckJson {
+  pub content_type: Option<String>,
+  pub dependencies: Vec<InscriptionId>,
+  pub depth: u32,
+  pub external_urls: Vec<String>,
+  pub id: InscriptionId,
+  pub missing_dependencies: Vec<InscriptionId>,
+  pub recursive_endpoints: Vec<RecursiveEndpoint>,
+  pub safe: bool,
+  pub scripted: bool,
+  pub truncated: bool,
+}
@@ -4,6 +4,7 @@ use super::*;
 pub(crate) struct RuneHtml {
   pub(crate) entry: RuneEntry,
   pub(crate) id: RuneId,
+  pub(crate) info: Option<RuneInfo>,
   pub(crate) parent: Option<InscriptionId>,
 }
 
@@ -40,6 +41,14 @@ mod tests {
           height: 10,
           index: 9,
         },
+        info: Some(RuneInfo {
+          icon: Some(InscriptionId {
+            txid: Txid::all_zeros(),
+            index: 1,
+          }),
+          name: Some("Foo Coin".into()),
+          website: Some("https://foo.com".into()),
+        }),
         parent: Some(InscriptionId {
           txid: Txid::all_zeros(),
           index: 0,
@@ -78,6 +87,12 @@ mod tests {
   <dd><a class=monospace href=/tx/0{64}>0{64}</a></dd>
   <dt>parent</dt>
   <dd><a class=monospace href=/inscription/0{64}i0>0{64}i0</a></dd>
+  <dt>name</dt>
+  <dd>Foo Coin</dd>
+  <dt>icon</dt>
+  <dd><a class=monospace href=/inscription/0{64}i1>0{64}i1</a></dd>
+  <dt>website</dt>
+  <dd><a href=https://foo.com>https://foo.com</a></dd>
 </dl>
 "
     );
@@ -106,6 +121,7 @@ mod tests {
           height: 10,
           index: 9,
         },
+        info: None,
         parent: None,
       },
       "<h1>B•CGDENLQRQWDSLRUGSNLBTMFIJAV</h1>
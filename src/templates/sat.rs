@@ -26,6 +26,17 @@ pub struct SatJson {
   pub inscriptions: Vec<InscriptionId>,
 }
 
+/// Summary of a sat returned by the batch `/sats` endpoints.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatSummaryJson {
+  pub number: u64,
+  pub name: String,
+  pub decimal: String,
+  pub degree: String,
+  pub rarity: Rarity,
+  pub satpoint: Option<SatPoint>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct SatInscriptionsJson {
   pub ids: Vec<InscriptionId>,
@@ -9,6 +9,8 @@ pub struct StatusHtml {
   pub inscriptions: u64,
   pub lost_sats: u64,
   pub minimum_rune_for_next_block: Rune,
+  pub progress: IndexProgress,
+  pub quarantined_entries: u64,
   pub rune_index: bool,
   pub runes: u64,
   pub sat_index: bool,
@@ -18,6 +20,38 @@ pub struct StatusHtml {
   pub uptime: Duration,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct IndexProgress {
+  pub blocks_per_second: f64,
+  pub eta: Option<u64>,
+  pub height: u32,
+  pub stage: IndexStage,
+  pub target: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexStage {
+  Committing,
+  #[default]
+  Idle,
+  Indexing,
+}
+
+impl Display for IndexStage {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Committing => "committing",
+        Self::Idle => "idle",
+        Self::Indexing => "indexing",
+      }
+    )
+  }
+}
+
 impl PageContent for StatusHtml {
   fn title(&self) -> String {
     "Status".into()
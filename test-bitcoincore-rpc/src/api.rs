@@ -28,6 +28,19 @@ pub trait Api {
   #[rpc(name = "getblockcount")]
   fn get_block_count(&self) -> Result<u64, jsonrpc_core::Error>;
 
+  #[rpc(name = "generatetoaddress")]
+  fn generate_to_address(
+    &self,
+    nblocks: u64,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Vec<BlockHash>, jsonrpc_core::Error>;
+
+  #[rpc(name = "invalidateblock")]
+  fn invalidate_block(&self, block_hash: BlockHash) -> Result<(), jsonrpc_core::Error>;
+
+  #[rpc(name = "reconsiderblock")]
+  fn reconsider_block(&self, block_hash: BlockHash) -> Result<(), jsonrpc_core::Error>;
+
   #[rpc(name = "getwalletinfo")]
   fn get_wallet_info(&self) -> Result<GetWalletInfoResult, jsonrpc_core::Error>;
 
@@ -63,7 +76,7 @@ pub trait Api {
     &self,
     tx: String,
     utxos: Option<Vec<SignRawTransactionInput>>,
-    sighash_type: Option<()>,
+    sighash_type: Option<String>,
   ) -> Result<Value, jsonrpc_core::Error>;
 
   #[rpc(name = "sendrawtransaction")]
@@ -152,6 +165,7 @@ pub trait Api {
     &self,
     unlock: bool,
     outputs: Vec<JsonOutPoint>,
+    persistent: Option<bool>,
   ) -> Result<bool, jsonrpc_core::Error>;
 
   #[rpc(name = "listdescriptors")]
@@ -162,4 +176,35 @@ pub trait Api {
 
   #[rpc(name = "listwallets")]
   fn list_wallets(&self) -> Result<Vec<String>, jsonrpc_core::Error>;
+
+  #[rpc(name = "walletprocesspsbt")]
+  fn wallet_process_psbt(
+    &self,
+    psbt: String,
+    sign: Option<bool>,
+    sighash_type: Option<String>,
+    bip32derivs: Option<bool>,
+  ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error>;
+
+  #[rpc(name = "bumpfee")]
+  fn bump_fee(&self, txid: Txid, options: Option<Value>) -> Result<Value, jsonrpc_core::Error>;
+
+  #[rpc(name = "getaddressinfo")]
+  fn get_address_info(
+    &self,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Value, jsonrpc_core::Error>;
+
+  #[rpc(name = "estimatesmartfee")]
+  fn estimate_smart_fee(
+    &self,
+    conf_target: u16,
+    estimate_mode: Option<EstimateMode>,
+  ) -> Result<Value, jsonrpc_core::Error>;
+
+  #[rpc(name = "getrawmempool")]
+  fn get_raw_mempool(&self, verbose: Option<bool>) -> Result<Value, jsonrpc_core::Error>;
+
+  #[rpc(name = "submitpackage")]
+  fn submit_package(&self, rawtxs: Vec<String>) -> Result<Value, jsonrpc_core::Error>;
 }
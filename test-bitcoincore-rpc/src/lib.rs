@@ -23,7 +23,7 @@ use {
     GetTransactionResultDetail, GetTransactionResultDetailCategory, GetWalletInfoResult,
     ImportDescriptors, ImportMultiResult, ListDescriptorsResult, ListTransactionResult,
     ListUnspentResultEntry, LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult,
-    Timestamp, WalletTxInfo,
+    Timestamp, WalletProcessPsbtResult, WalletTxInfo,
   },
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
@@ -237,6 +237,40 @@ impl Handle {
     self.state().pop_block()
   }
 
+  /// Invalidate the block with `block_hash` and its descendants, like
+  /// `bitcoin-cli invalidateblock`.
+  pub fn invalidate_block(&self, block_hash: BlockHash) {
+    assert!(
+      self.state().invalidate_block(block_hash),
+      "block {block_hash} not found"
+    );
+  }
+
+  /// Reconsider the block with `block_hash` and its descendants, switching
+  /// back to them if they are the longest valid chain, like `bitcoin-cli
+  /// reconsiderblock`.
+  pub fn reconsider_block(&self, block_hash: BlockHash) {
+    assert!(
+      self.state().reconsider_block(block_hash),
+      "block {block_hash} not found"
+    );
+  }
+
+  /// Replace the last `depth` blocks with `n` new blocks, which include the
+  /// current mempool, and return the new blocks. The replaced blocks are
+  /// invalidated, and can be switched back to with `reconsider_block`.
+  pub fn reorg(&self, depth: usize, n: u64) -> Vec<Block> {
+    let mut state = self.state();
+    let height = state.hashes.len() - depth;
+    let block_hash = state.hashes[height];
+    assert!(state.invalidate_block(block_hash));
+    (0..n).map(|_| state.push_block(50 * COIN_VALUE)).collect()
+  }
+
+  pub fn best_block_hash(&self) -> BlockHash {
+    *self.state().hashes.last().unwrap()
+  }
+
   pub fn get_utxo_amount(&self, outpoint: &OutPoint) -> Option<Amount> {
     self.state().utxos.get(outpoint).cloned()
   }
@@ -246,6 +280,12 @@ impl Handle {
     state.blocks[&state.hashes[bi]].txdata[ti].clone()
   }
 
+  /// Set the fee rate, in sats/vB, returned by `estimatesmartfee`, which
+  /// otherwise has no estimate, like a regtest node.
+  pub fn set_fee_estimate(&self, fee_rate: f64) {
+    self.state().fee_estimate = Some(fee_rate);
+  }
+
   pub fn mempool(&self) -> Vec<Transaction> {
     self.state().mempool().to_vec()
   }
@@ -258,6 +298,10 @@ impl Handle {
     self.state().descriptors.push(desc);
   }
 
+  pub fn packages(&self) -> Vec<Vec<Txid>> {
+    self.state().packages.clone()
+  }
+
   pub fn sent(&self) -> Vec<Sent> {
     self.state().sent.clone()
   }
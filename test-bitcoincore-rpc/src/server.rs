@@ -26,6 +26,14 @@ impl Server {
   fn not_found() -> jsonrpc_core::Error {
     jsonrpc_core::Error::new(jsonrpc_core::types::error::ErrorCode::ServerError(-8))
   }
+
+  fn block_not_found() -> jsonrpc_core::Error {
+    jsonrpc_core::Error {
+      code: jsonrpc_core::types::error::ErrorCode::ServerError(-5),
+      message: "Block not found".into(),
+      data: None,
+    }
+  }
 }
 
 impl Api for Server {
@@ -148,7 +156,12 @@ impl Api for Server {
     verbosity: u64,
   ) -> Result<String, jsonrpc_core::Error> {
     assert_eq!(verbosity, 0, "Verbosity level {verbosity} is unsupported");
-    match self.state().blocks.get(&block_hash) {
+    let state = self.state();
+    match state
+      .blocks
+      .get(&block_hash)
+      .or_else(|| state.stale.get(&block_hash))
+    {
       Some(block) => Ok(hex::encode(serialize(block))),
       None => Err(Self::not_found()),
     }
@@ -166,6 +179,35 @@ impl Api for Server {
     )
   }
 
+  fn generate_to_address(
+    &self,
+    nblocks: u64,
+    _address: Address<NetworkUnchecked>,
+  ) -> Result<Vec<BlockHash>, jsonrpc_core::Error> {
+    let mut state = self.state();
+    Ok(
+      (0..nblocks)
+        .map(|_| state.push_block(50 * COIN_VALUE).block_hash())
+        .collect(),
+    )
+  }
+
+  fn invalidate_block(&self, block_hash: BlockHash) -> Result<(), jsonrpc_core::Error> {
+    if self.state().invalidate_block(block_hash) {
+      Ok(())
+    } else {
+      Err(Self::block_not_found())
+    }
+  }
+
+  fn reconsider_block(&self, block_hash: BlockHash) -> Result<(), jsonrpc_core::Error> {
+    if self.state().reconsider_block(block_hash) {
+      Ok(())
+    } else {
+      Err(Self::block_not_found())
+    }
+  }
+
   fn get_wallet_info(&self) -> Result<GetWalletInfoResult, jsonrpc_core::Error> {
     if let Some(wallet_name) = self.state().loaded_wallets.first().cloned() {
       Ok(GetWalletInfoResult {
@@ -337,14 +379,22 @@ impl Api for Server {
     &self,
     tx: String,
     _utxos: Option<Vec<SignRawTransactionInput>>,
-    sighash_type: Option<()>,
+    sighash_type: Option<String>,
   ) -> Result<Value, jsonrpc_core::Error> {
-    assert_eq!(sighash_type, None, "sighash_type param not supported");
+    let signature = match sighash_type.as_deref() {
+      None => vec![0; 64],
+      Some("SINGLE|ANYONECANPAY") => {
+        let mut signature = vec![0; 64];
+        signature.push(0x83);
+        signature
+      }
+      Some(sighash_type) => panic!("sighash_type `{sighash_type}` not supported"),
+    };
 
     let mut transaction: Transaction = deserialize(&hex::decode(tx).unwrap()).unwrap();
     for input in &mut transaction.input {
       if input.witness.is_empty() {
-        input.witness = Witness::from_slice(&[&[0; 64]]);
+        input.witness = Witness::from_slice(&[&signature]);
       }
     }
 
@@ -482,34 +532,41 @@ impl Api for Server {
     blockhash: Option<BlockHash>,
   ) -> Result<Value, jsonrpc_core::Error> {
     assert_eq!(blockhash, None, "Blockhash param is unsupported");
+
+    let state = self.state();
+
+    let confirmed = state.transactions.contains_key(&txid);
+
+    let Some(tx) = state
+      .transactions
+      .get(&txid)
+      .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid))
+    else {
+      return Err(Self::not_found());
+    };
+
     if verbose.unwrap_or(false) {
-      match self.state().transactions.get(&txid) {
-        Some(_) => Ok(
-          serde_json::to_value(GetRawTransactionResult {
-            in_active_chain: Some(true),
-            hex: Vec::new(),
-            txid: Txid::all_zeros(),
-            hash: Wtxid::all_zeros(),
-            size: 0,
-            vsize: 0,
-            version: 2,
-            locktime: 0,
-            vin: Vec::new(),
-            vout: Vec::new(),
-            blockhash: None,
-            confirmations: Some(1),
-            time: None,
-            blocktime: None,
-          })
-          .unwrap(),
-        ),
-        None => Err(Self::not_found()),
-      }
+      Ok(
+        serde_json::to_value(GetRawTransactionResult {
+          in_active_chain: confirmed.then_some(true),
+          hex: Vec::new(),
+          txid: Txid::all_zeros(),
+          hash: Wtxid::all_zeros(),
+          size: 0,
+          vsize: 0,
+          version: 2,
+          locktime: 0,
+          vin: Vec::new(),
+          vout: Vec::new(),
+          blockhash: None,
+          confirmations: confirmed.then_some(1),
+          time: None,
+          blocktime: None,
+        })
+        .unwrap(),
+      )
     } else {
-      match self.state().transactions.get(&txid) {
-        Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
-        None => Err(Self::not_found()),
-      }
+      Ok(Value::String(hex::encode(serialize(tx))))
     }
   }
 
@@ -529,11 +586,17 @@ impl Api for Server {
 
     let state = self.state();
 
+    let spent = state
+      .mempool
+      .iter()
+      .flat_map(|tx| tx.input.iter().map(|input| input.previous_output))
+      .collect::<BTreeSet<OutPoint>>();
+
     Ok(
       state
         .utxos
         .iter()
-        .filter(|(outpoint, _amount)| !state.locked.contains(outpoint))
+        .filter(|(outpoint, _amount)| !state.locked.contains(outpoint) && !spent.contains(outpoint))
         .map(|(outpoint, &amount)| ListUnspentResultEntry {
           txid: outpoint.txid,
           vout: outpoint.vout,
@@ -667,9 +730,8 @@ impl Api for Server {
     &self,
     unlock: bool,
     outputs: Vec<JsonOutPoint>,
+    _persistent: Option<bool>,
   ) -> Result<bool, jsonrpc_core::Error> {
-    assert!(!unlock);
-
     let mut state = self.state();
 
     if state.fail_lock_unspent {
@@ -682,7 +744,11 @@ impl Api for Server {
         txid: output.txid,
       };
       assert!(state.utxos.contains_key(&output));
-      state.locked.insert(output);
+      if unlock {
+        state.locked.remove(&output);
+      } else {
+        state.locked.insert(output);
+      }
     }
 
     Ok(true)
@@ -729,4 +795,153 @@ impl Api for Server {
         .collect::<Vec<String>>(),
     )
   }
+
+  fn wallet_process_psbt(
+    &self,
+    psbt: String,
+    sign: Option<bool>,
+    sighash_type: Option<String>,
+    bip32derivs: Option<bool>,
+  ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error> {
+    assert_eq!(sign, Some(false), "signing PSBTs is not supported");
+    assert_eq!(
+      sighash_type.as_deref().unwrap_or("ALL"),
+      "ALL",
+      "sighash_type param not supported"
+    );
+    assert_eq!(bip32derivs, Some(true), "bip32derivs param must be true");
+
+    Ok(WalletProcessPsbtResult {
+      psbt,
+      complete: false,
+    })
+  }
+
+  fn bump_fee(&self, txid: Txid, options: Option<Value>) -> Result<Value, jsonrpc_core::Error> {
+    let mut state = self.state();
+
+    let Some(tx) = state.mempool.iter_mut().find(|tx| tx.txid() == txid) else {
+      return Err(Self::not_found());
+    };
+
+    let fee_rate = options
+      .and_then(|options| options.get("fee_rate").and_then(Value::as_f64))
+      .unwrap();
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let increase = (fee_rate * tx.vsize() as f64) as u64;
+
+    tx.output.last_mut().unwrap().value -= increase;
+
+    Ok(serde_json::json!({
+      "txid": tx.txid(),
+      "origfee": 0.0,
+      "fee": Amount::from_sat(increase).to_btc(),
+      "errors": [],
+    }))
+  }
+
+  fn get_address_info(
+    &self,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Value, jsonrpc_core::Error> {
+    Ok(serde_json::json!({
+      "address": address.assume_checked(),
+      "ismine": true,
+    }))
+  }
+
+  fn submit_package(&self, rawtxs: Vec<String>) -> Result<Value, jsonrpc_core::Error> {
+    let mut state = self.state();
+
+    if state.version < 280000 {
+      return Err(jsonrpc_core::Error::method_not_found());
+    }
+
+    let transactions = rawtxs
+      .iter()
+      .map(|tx| deserialize::<Transaction>(&hex::decode(tx).unwrap()).unwrap())
+      .collect::<Vec<Transaction>>();
+
+    state
+      .packages
+      .push(transactions.iter().map(Transaction::txid).collect());
+
+    state.mempool.extend(transactions);
+
+    Ok(serde_json::json!({
+      "package_msg": "success",
+      "tx-results": {},
+      "replaced-transactions": [],
+    }))
+  }
+
+  fn estimate_smart_fee(
+    &self,
+    conf_target: u16,
+    _estimate_mode: Option<EstimateMode>,
+  ) -> Result<Value, jsonrpc_core::Error> {
+    Ok(match self.state().fee_estimate {
+      Some(fee_rate) => serde_json::json!({
+        "feerate": fee_rate / 100_000.0,
+        "blocks": conf_target,
+      }),
+      None => serde_json::json!({
+        "errors": ["Insufficient data or no feerate found"],
+        "blocks": 0,
+      }),
+    })
+  }
+
+  fn get_raw_mempool(&self, verbose: Option<bool>) -> Result<Value, jsonrpc_core::Error> {
+    let state = self.state();
+
+    if !verbose.unwrap_or(false) {
+      return Ok(
+        serde_json::to_value(
+          state
+            .mempool()
+            .iter()
+            .map(Transaction::txid)
+            .collect::<Vec<Txid>>(),
+        )
+        .unwrap(),
+      );
+    }
+
+    Ok(Value::Object(
+      state
+        .mempool()
+        .iter()
+        .map(|tx| {
+          let input_value = tx
+            .input
+            .iter()
+            .filter_map(|txin| {
+              state
+                .transactions
+                .get(&txin.previous_output.txid)
+                .map(|prev| prev.output[txin.previous_output.vout as usize].value)
+            })
+            .sum::<u64>();
+
+          let fee = Amount::from_sat(
+            input_value.saturating_sub(tx.output.iter().map(|txout| txout.value).sum::<u64>()),
+          )
+          .to_btc();
+
+          (
+            tx.txid().to_string(),
+            serde_json::json!({
+              "vsize": tx.vsize(),
+              "fees": {
+                "base": fee,
+                "modified": fee,
+              },
+            }),
+          )
+        })
+        .collect(),
+    ))
+  }
 }
@@ -5,13 +5,17 @@ pub(crate) struct State {
   pub(crate) change_addresses: Vec<Address>,
   pub(crate) descriptors: Vec<String>,
   pub(crate) fail_lock_unspent: bool,
+  pub(crate) fee_estimate: Option<f64>,
   pub(crate) hashes: Vec<BlockHash>,
+  pub(crate) invalid: BTreeSet<BlockHash>,
   pub(crate) loaded_wallets: BTreeSet<String>,
   pub(crate) locked: BTreeSet<OutPoint>,
   pub(crate) mempool: Vec<Transaction>,
   pub(crate) network: Network,
   pub(crate) nonce: u32,
+  pub(crate) packages: Vec<Vec<Txid>>,
   pub(crate) sent: Vec<Sent>,
+  pub(crate) stale: BTreeMap<BlockHash, Block>,
   pub(crate) transactions: BTreeMap<Txid, Transaction>,
   pub(crate) utxos: BTreeMap<OutPoint, Amount>,
   pub(crate) version: usize,
@@ -33,12 +37,16 @@ impl State {
       change_addresses: Vec::new(),
       descriptors: Vec::new(),
       fail_lock_unspent,
+      fee_estimate: None,
       hashes,
+      invalid: BTreeSet::new(),
       locked: BTreeSet::new(),
       mempool: Vec::new(),
       network,
       nonce: 0,
+      packages: Vec::new(),
       sent: Vec::new(),
+      stale: BTreeMap::new(),
       transactions: BTreeMap::new(),
       utxos: BTreeMap::new(),
       version,
@@ -136,6 +144,113 @@ impl State {
     blockhash
   }
 
+  /// Mark `block_hash` and its descendants invalid, disconnecting them from
+  /// the active chain if necessary and switching to the longest remaining
+  /// valid chain, like Bitcoin Core's `invalidateblock`. Disconnected blocks
+  /// are kept, so that they can be reconnected with `reconsider_block`.
+  pub(crate) fn invalidate_block(&mut self, block_hash: BlockHash) -> bool {
+    if block_hash == self.hashes[0]
+      || !(self.blocks.contains_key(&block_hash) || self.stale.contains_key(&block_hash))
+    {
+      return false;
+    }
+
+    self.invalid.insert(block_hash);
+
+    if let Some(height) = self.hashes.iter().position(|hash| *hash == block_hash) {
+      while self.hashes.len() > height {
+        self.disconnect_tip();
+      }
+
+      self.activate_best_chain();
+    }
+
+    true
+  }
+
+  /// Clear the invalid flag of `block_hash` and its descendants, and switch to
+  /// the longest valid chain, like Bitcoin Core's `reconsiderblock`. Chains of
+  /// equal length do not replace the active chain.
+  pub(crate) fn reconsider_block(&mut self, block_hash: BlockHash) -> bool {
+    if !(self.blocks.contains_key(&block_hash) || self.stale.contains_key(&block_hash)) {
+      return false;
+    }
+
+    let reconsidered = self
+      .invalid
+      .iter()
+      .copied()
+      .filter(|hash| self.stale_ancestors(*hash).contains(&block_hash))
+      .collect::<Vec<BlockHash>>();
+
+    for hash in reconsidered {
+      self.invalid.remove(&hash);
+    }
+
+    self.invalid.remove(&block_hash);
+
+    self.activate_best_chain();
+
+    true
+  }
+
+  fn disconnect_tip(&mut self) {
+    let block_hash = self.hashes.pop().unwrap();
+    let block = self.blocks.remove(&block_hash).unwrap();
+    self.stale.insert(block_hash, block);
+  }
+
+  /// `block_hash` and its ancestors that are not in the active chain, from
+  /// the tip down.
+  fn stale_ancestors(&self, mut block_hash: BlockHash) -> Vec<BlockHash> {
+    let mut ancestors = Vec::new();
+
+    while let Some(block) = self.stale.get(&block_hash) {
+      ancestors.push(block_hash);
+      block_hash = block.header.prev_blockhash;
+    }
+
+    ancestors
+  }
+
+  fn activate_best_chain(&mut self) {
+    let mut best = None;
+
+    for tip in self.stale.keys() {
+      let branch = self.stale_ancestors(*tip);
+
+      if branch.iter().any(|hash| self.invalid.contains(hash)) {
+        continue;
+      }
+
+      let parent = self.stale[branch.last().unwrap()].header.prev_blockhash;
+
+      let Some(fork) = self.hashes.iter().position(|hash| *hash == parent) else {
+        continue;
+      };
+
+      let height = fork + branch.len();
+
+      if height >= self.hashes.len() && best.as_ref().is_none_or(|(best, _, _)| height > *best) {
+        best = Some((height, fork, branch));
+      }
+    }
+
+    let Some((_, fork, branch)) = best else {
+      return;
+    };
+
+    while self.hashes.len() > fork + 1 {
+      self.disconnect_tip();
+    }
+
+    for block_hash in branch.into_iter().rev() {
+      let block = self.stale.remove(&block_hash).unwrap();
+      self.blocks.insert(block_hash, block);
+      self.hashes.push(block_hash);
+    }
+  }
+
   pub(crate) fn broadcast_tx(&mut self, template: TransactionTemplate) -> Txid {
     let mut total_value = 0;
     let mut input = Vec::new();
@@ -208,3 +323,85 @@ impl State {
     0
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn state() -> State {
+    State::new(Network::Regtest, 240000, false)
+  }
+
+  #[test]
+  fn invalidate_block_disconnects_descendants() {
+    let mut state = state();
+    let blocks = (0..3).map(|_| state.push_block(0)).collect::<Vec<Block>>();
+
+    assert!(state.invalidate_block(blocks[1].block_hash()));
+
+    assert_eq!(state.hashes.len(), 2);
+    assert_eq!(*state.hashes.last().unwrap(), blocks[0].block_hash());
+    assert_eq!(state.stale.len(), 2);
+  }
+
+  #[test]
+  fn invalidate_unknown_block_fails() {
+    let mut state = state();
+
+    assert!(!state.invalidate_block(BlockHash::all_zeros()));
+    assert!(!state.invalidate_block(state.hashes[0]));
+  }
+
+  #[test]
+  fn reconsider_block_reconnects_longer_chain() {
+    let mut state = state();
+    let original = (0..2).map(|_| state.push_block(0)).collect::<Vec<Block>>();
+
+    assert!(state.invalidate_block(original[0].block_hash()));
+    let replacement = state.push_block(0);
+
+    assert!(state.reconsider_block(original[0].block_hash()));
+
+    assert_eq!(
+      state.hashes[1..],
+      [original[0].block_hash(), original[1].block_hash()],
+    );
+    assert!(state.stale.contains_key(&replacement.block_hash()));
+  }
+
+  #[test]
+  fn reconsider_block_keeps_chain_of_equal_length() {
+    let mut state = state();
+    let original = state.push_block(0);
+
+    assert!(state.invalidate_block(original.block_hash()));
+    let replacement = state.push_block(0);
+
+    assert!(state.reconsider_block(original.block_hash()));
+
+    assert_eq!(state.hashes[1..], [replacement.block_hash()]);
+    assert!(state.invalid.is_empty());
+  }
+
+  #[test]
+  fn invalidate_block_switches_to_longest_valid_chain() {
+    let mut state = state();
+    let original = (0..2).map(|_| state.push_block(0)).collect::<Vec<Block>>();
+
+    assert!(state.invalidate_block(original[0].block_hash()));
+    let replacement = state.push_block(0);
+    assert!(state.reconsider_block(original[0].block_hash()));
+
+    assert!(state.invalidate_block(original[1].block_hash()));
+
+    assert_eq!(
+      state.hashes[1..],
+      [original[0].block_hash()],
+      "equal length chains do not replace the active chain",
+    );
+
+    assert!(state.invalidate_block(original[0].block_hash()));
+
+    assert_eq!(state.hashes[1..], [replacement.block_hash()]);
+  }
+}
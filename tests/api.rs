@@ -0,0 +1,81 @@
+use {
+  super::*,
+  clap::Parser,
+  ord::{
+    api::{Event, IndexHandle},
+    Options,
+  },
+};
+
+#[test]
+fn index_handle_queries_inscriptions_and_streams_events() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let options = Options::try_parse_from([
+    "ord",
+    "--rpc-url",
+    &rpc_server.url(),
+    "--cookie-file",
+    rpc_server.cookie_file().to_str().unwrap(),
+    "--data-dir",
+    tempdir.path().to_str().unwrap(),
+    "--first-inscription-height",
+    "0",
+  ])
+  .unwrap();
+
+  let handle = IndexHandle::open(&options).unwrap();
+
+  assert_eq!(handle.block_count().unwrap(), 0);
+
+  let events = handle.subscribe_events().unwrap();
+
+  let (inscription_id, reveal) = inscribe(&rpc_server);
+
+  handle.update().unwrap();
+
+  assert_eq!(handle.block_count().unwrap(), 3);
+
+  let location = SatPoint {
+    outpoint: OutPoint {
+      txid: reveal,
+      vout: 0,
+    },
+    offset: 0,
+  };
+
+  assert!(events.try_iter().flatten().any(|event| event
+    == Event::InscriptionCreated {
+      block_height: 2,
+      inscription_id,
+      inscription_number: 0,
+      location,
+      metaprotocol: None,
+      sequence_number: 0,
+    }));
+
+  assert_eq!(
+    handle.inscription(inscription_id).unwrap().unwrap().body,
+    Some(b"FOO".to_vec()),
+  );
+
+  assert_eq!(handle.inscription_id(0).unwrap(), Some(inscription_id));
+
+  assert_eq!(
+    handle.inscription_location(inscription_id).unwrap(),
+    Some(location),
+  );
+
+  assert_eq!(
+    handle.inscriptions_on_output(location.outpoint).unwrap(),
+    [inscription_id],
+  );
+
+  assert_eq!(
+    handle.sat_location(ord::Sat(0)).unwrap_err().to_string(),
+    "sat locations require index created with `--index-sats` flag",
+  );
+}
@@ -0,0 +1,66 @@
+use {
+  super::*,
+  bitcoin::{consensus, opcodes, script, Witness},
+  ord::subcommand::bench::Output,
+};
+
+#[test]
+fn bench_counts_protocol_messages_in_corpus() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let mut blocks = rpc_server.mine_blocks(1);
+
+  let mut witness = Witness::new();
+
+  witness.push(
+    script::Builder::new()
+      .push_opcode(opcodes::OP_FALSE)
+      .push_opcode(opcodes::all::OP_IF)
+      .push_slice(b"ord")
+      .push_slice([1])
+      .push_slice(b"text/plain;charset=utf-8")
+      .push_slice([])
+      .push_slice(b"foo")
+      .push_opcode(opcodes::all::OP_ENDIF)
+      .into_script(),
+  );
+  witness.push([]);
+
+  rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, witness)],
+    op_return: Some(
+      script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_slice(b"RUNE_TEST")
+        .into_script(),
+    ),
+    ..Default::default()
+  });
+
+  blocks.extend(rpc_server.mine_blocks(1));
+
+  let mut command = CommandBuilder::new("bench --iterations 2 corpus");
+
+  for (height, block) in blocks.iter().enumerate() {
+    command = command.write(
+      format!("corpus/{}.blk", height + 1),
+      consensus::serialize(block),
+    );
+  }
+
+  let output = command.run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.blocks, 4);
+  assert_eq!(output.transactions, 6);
+  assert_eq!(output.inscriptions, 2);
+  assert_eq!(output.runestones, 2);
+}
+
+#[test]
+fn bench_requires_blocks_in_corpus() {
+  CommandBuilder::new("bench corpus")
+    .write("corpus/foo.txt", "foo")
+    .expected_exit_code(1)
+    .expected_stderr("error: no `.blk` files found in corpus `corpus`\n")
+    .run_and_extract_stdout();
+}
@@ -0,0 +1,68 @@
+use {super::*, ord::subcommand::brc20::Output};
+
+const OWNER: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+#[test]
+fn simulate_accepts_valid_payload() {
+  let output = CommandBuilder::new(format!(
+    "brc20 simulate --payload mint.json --owner {OWNER}"
+  ))
+  .write(
+    "mint.json",
+    r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#,
+  )
+  .run_and_deserialize_output::<Output>();
+
+  assert!(output.accepted);
+  assert_eq!(output.error, None);
+  assert_eq!(output.owner, OWNER);
+}
+
+#[test]
+fn simulate_reports_rejection_error() {
+  let output = CommandBuilder::new(format!(
+    "brc20 simulate --payload mint.json --owner {OWNER}"
+  ))
+  .write(
+    "mint.json",
+    r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1.0000000000000000001"}"#,
+  )
+  .run_and_deserialize_output::<Output>();
+
+  assert!(!output.accepted);
+  assert_eq!(
+    output.error.unwrap(),
+    "BRC-20 amount `1.0000000000000000001` has more than 18 decimals",
+  );
+}
+
+#[test]
+fn simulate_rejects_non_brc20_payload() {
+  let output = CommandBuilder::new(format!(
+    "brc20 simulate --payload mint.json --owner {OWNER}"
+  ))
+  .write("mint.json", "foo")
+  .run_and_deserialize_output::<Output>();
+
+  assert!(!output.accepted);
+  assert_eq!(output.operation, None);
+  assert_eq!(output.error.unwrap(), "payload is not a BRC-20 operation");
+}
+
+#[test]
+fn simulate_rejects_malformed_amount() {
+  let output = CommandBuilder::new(format!(
+    "brc20 simulate --payload mint.json --owner {OWNER}"
+  ))
+  .write(
+    "mint.json",
+    r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1e5"}"#,
+  )
+  .run_and_deserialize_output::<Output>();
+
+  assert!(!output.accepted);
+  assert_eq!(
+    output.error.unwrap(),
+    "BRC-20 amount `1e5` is not a decimal number",
+  );
+}
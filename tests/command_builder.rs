@@ -54,7 +54,9 @@ impl CommandBuilder {
   }
 
   pub(crate) fn write(self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Self {
-    fs::write(self.tempdir.path().join(path), contents).unwrap();
+    let path = self.tempdir.path().join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
     self
   }
 
@@ -176,4 +178,16 @@ impl CommandBuilder {
     serde_json::from_str(&stdout)
       .unwrap_or_else(|err| panic!("Failed to deserialize JSON: {err}\n{stdout}"))
   }
+
+  /// Like `run_and_deserialize_output`, but also return the data directory,
+  /// so that it can be passed to the next command with `temp_dir`.
+  #[track_caller]
+  pub(crate) fn run_and_deserialize_output_with_tempdir<T: DeserializeOwned>(self) -> (T, TempDir) {
+    let (tempdir, stdout) = self.stdout_regex(".*").run();
+    (
+      serde_json::from_str(&stdout)
+        .unwrap_or_else(|err| panic!("Failed to deserialize JSON: {err}\n{stdout}")),
+      tempdir,
+    )
+  }
 }
@@ -0,0 +1,62 @@
+use {
+  super::*,
+  ord::subcommand::config::{Output, Section, Setting, Source},
+};
+
+#[test]
+fn check_lists_settings() {
+  let output = CommandBuilder::new("--config ord.yaml config check")
+    .write(
+      "ord.yaml",
+      "hidden:\noptions:\n  index-runes: true\nserver:\n  http-port: 8080\n",
+    )
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.config, Some("ord.yaml".into()));
+
+  assert_eq!(
+    output.settings,
+    [
+      Setting {
+        section: Section::Options,
+        option: "index-runes".into(),
+        source: Source::Config,
+        values: vec!["true".into()],
+      },
+      Setting {
+        section: Section::Server,
+        option: "http-port".into(),
+        source: Source::Config,
+        values: vec!["8080".into()],
+      },
+    ]
+  );
+}
+
+#[test]
+fn check_without_config_file() {
+  let output = CommandBuilder::new("config check").run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.config, None);
+  assert_eq!(output.settings, []);
+}
+
+#[test]
+fn check_rejects_invalid_values() {
+  CommandBuilder::new("--config ord.yaml config check")
+    .write("ord.yaml", "hidden:\nwallet:\n  name: [foo, [bar]]\n")
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: option `name` in `wallet` section of config must be a string, number, boolean, or list of those\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn unknown_options_are_rejected_by_every_command() {
+  CommandBuilder::new("--config ord.yaml epochs")
+    .write("ord.yaml", "hidden:\nserver:\n  foo: bar\n")
+    .expected_exit_code(1)
+    .expected_stderr("error: unknown option `foo` in `server` section of config\n")
+    .run_and_extract_stdout();
+}
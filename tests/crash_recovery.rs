@@ -0,0 +1,118 @@
+use {
+  super::*,
+  ord::subcommand::{index::verify, Empty},
+};
+
+fn table_entries(rpc_server: &test_bitcoincore_rpc::Handle, index: &Path) -> serde_json::Value {
+  let mut info = CommandBuilder::new(format!("--index {} index info", index.display()))
+    .rpc_server(rpc_server)
+    .run_and_deserialize_output::<serde_json::Value>();
+
+  let tables = info["tables"].as_object_mut().unwrap();
+
+  // commit statistics and write transaction timestamps depend on how many
+  // commits were made, and so differ between crashed and uncrashed indexes
+  tables.remove("STATISTIC_TO_COUNT");
+  tables.remove("WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP");
+
+  let entries = tables
+    .iter()
+    .map(|(name, table)| (name.clone(), table["entries"].clone()))
+    .collect::<serde_json::Map<String, serde_json::Value>>();
+
+  serde_json::json!({
+    "blocks_indexed": info["blocks_indexed"],
+    "tables": entries,
+  })
+}
+
+#[track_caller]
+fn index_recovers_from_crash_at(point: &str) {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  inscribe(&rpc_server);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let crashed = tempdir.path().join("crashed.redb");
+
+  CommandBuilder::new(format!("--index {} index update", crashed.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  inscribe(&rpc_server);
+
+  let output = CommandBuilder::new(format!(
+    "--index {} --crash-at {point} index update",
+    crashed.display()
+  ))
+  .rpc_server(&rpc_server)
+  .command()
+  .output()
+  .unwrap();
+
+  assert!(!output.status.success());
+
+  assert_regex_match!(
+    str::from_utf8(&output.stderr).unwrap(),
+    ".*aborting indexer at height [0-9]+ at .*\n.*",
+  );
+
+  CommandBuilder::new(format!("--index {} index update", crashed.display()))
+    .rpc_server(&rpc_server)
+    .stdout_regex("Index file `.*` needs recovery.*\n\\{\\}\n")
+    .run_and_extract_stdout();
+
+  assert_eq!(
+    CommandBuilder::new(format!("--index {} index verify", crashed.display()))
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<verify::Output>(),
+    verify::Output {
+      issues: Vec::new(),
+      repaired: 0,
+    },
+  );
+
+  let uncrashed = tempdir.path().join("uncrashed.redb");
+
+  pretty_assert_eq!(
+    table_entries(&rpc_server, &crashed),
+    table_entries(&rpc_server, &uncrashed),
+  );
+}
+
+#[test]
+fn index_recovers_from_crash_mid_block() {
+  index_recovers_from_crash_at("mid-block");
+}
+
+#[test]
+fn index_recovers_from_crash_before_commit() {
+  index_recovers_from_crash_at("pre-commit");
+}
+
+#[test]
+fn index_recovers_from_crash_after_commit() {
+  index_recovers_from_crash_at("post-commit");
+}
+
+#[test]
+fn crash_at_requires_integration_test() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let output = CommandBuilder::new("--crash-at pre-commit index update")
+    .rpc_server(&rpc_server)
+    .command()
+    .env_remove("ORD_INTEGRATION_TEST")
+    .output()
+    .unwrap();
+
+  assert_eq!(output.status.code(), Some(1));
+
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "error: `--crash-at` is only available in integration tests\n",
+  );
+}
@@ -5,7 +5,7 @@ use {
     TxIn, Witness,
   },
   ord::{
-    subcommand::decode::{CompactInscription, CompactOutput, RawOutput},
+    subcommand::decode::{tx, CompactInscription, CompactOutput, RawOutput},
     Envelope, Inscription,
   },
 };
@@ -140,3 +140,43 @@ fn compact() {
     },
   );
 }
+
+#[test]
+fn tx_from_raw_hex() {
+  let output = CommandBuilder::new(format!("decode tx {}", hex::encode(transaction())))
+    .run_and_deserialize_output::<tx::Output>();
+
+  assert_eq!(output.inscriptions.len(), 1);
+  assert_eq!(
+    output.inscriptions[0].content_type.as_deref(),
+    Some("text/plain;charset=utf-8")
+  );
+  assert_eq!(output.inscriptions[0].body.as_deref(), Some("00010203"));
+  assert_eq!(output.runestone, None);
+}
+
+#[test]
+fn tx_from_core() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, reveal) = inscribe(&rpc_server);
+
+  let output = CommandBuilder::new(format!("decode tx {reveal}"))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<tx::Output>();
+
+  assert_eq!(output.txid, reveal);
+  assert_eq!(output.inscriptions[0].id, inscription);
+  assert_eq!(output.inscriptions[0].text.as_deref(), Some("FOO"));
+  assert_eq!(output.inscriptions[0].brc20, None);
+}
+
+#[test]
+fn tx_rejects_invalid_transaction() {
+  CommandBuilder::new("decode tx foo")
+    .expected_exit_code(1)
+    .stderr_regex("error: transaction is not a txid or valid hex\n.*")
+    .run_and_extract_stdout();
+}
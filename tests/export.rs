@@ -0,0 +1,98 @@
+use {super::*, ord::subcommand::export::Output};
+
+#[test]
+fn export_inscriptions_to_csv() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, reveal) = inscribe(&rpc_server);
+
+  let tempdir = TempDir::new().unwrap();
+  let output = tempdir.path().join("export");
+
+  let export = CommandBuilder::new(format!(
+    "export inscriptions --blocks-per-file 2 --output {}",
+    output.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    export,
+    Output {
+      files: vec![
+        output.join("inscriptions-0000000000-0000000002.csv"),
+        output.join("inscriptions-0000000002-0000000003.csv"),
+      ],
+      from: 0,
+      rows: 1,
+      to: 3,
+    }
+  );
+
+  assert_eq!(
+    fs::read_to_string(&export.files[0]).unwrap(),
+    "sequence_number,number,id,height,timestamp,fee,sat,charms,parent_sequence_number,location\n",
+  );
+
+  assert_eq!(
+    fs::read_to_string(&export.files[1]).unwrap(),
+    format!(
+      "sequence_number,number,id,height,timestamp,fee,sat,charms,parent_sequence_number,location\n\
+       0,0,{inscription},2,2,138,,0,,{reveal}:0:0\n"
+    ),
+  );
+}
+
+#[test]
+fn export_continues_from_last_exported_height() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+  let output = tempdir.path().join("export");
+
+  let command = format!("export inscriptions --output {}", output.display());
+
+  let export = CommandBuilder::new(command.clone())
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(export.from, 0);
+  assert_eq!(export.to, 2);
+
+  rpc_server.mine_blocks(2);
+
+  let export = CommandBuilder::new(command.clone())
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    export,
+    Output {
+      files: vec![output.join("inscriptions-0000000002-0000000004.csv")],
+      from: 2,
+      rows: 0,
+      to: 4,
+    }
+  );
+
+  let export = CommandBuilder::new(command)
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(export.files, Vec::<PathBuf>::new());
+  assert_eq!(export.from, 4);
+  assert_eq!(export.to, 4);
+}
+
+#[test]
+fn export_runes_requires_rune_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("export runes --output export")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: `ord export runes` requires index created with `--index-runes` flag\n")
+    .run_and_extract_stdout();
+}
@@ -116,3 +116,245 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn export_and_import_index_deltas() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  inscribe(&rpc_server);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let deltas = tempdir.path().join("deltas.bin");
+
+  let export = CommandBuilder::new(format!("index export --output {}", deltas.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  assert_eq!(export.blocks, 3);
+  assert_eq!(export.from, 0);
+  assert_eq!(export.to, 3);
+
+  let import = CommandBuilder::new(format!(
+    "--index {} index import --input {}",
+    tempdir.path().join("replica.redb").display(),
+    deltas.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::import::Output>();
+
+  assert_eq!(import.blocks, 3);
+  assert_eq!(import.height, Some(2));
+}
+
+#[test]
+fn importing_deltas_that_do_not_follow_index_height_fails() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let deltas = tempdir.path().join("deltas.bin");
+
+  CommandBuilder::new(format!(
+    "index export --from 1 --output {}",
+    deltas.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  CommandBuilder::new(format!(
+    "--index {} index import --input {}",
+    tempdir.path().join("replica.redb").display(),
+    deltas.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: index delta for block 1 does not follow index height 0\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn importing_deltas_into_rune_index_fails() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let tempdir = TempDir::new().unwrap();
+
+  let deltas = tempdir.path().join("deltas.bin");
+
+  CommandBuilder::new(format!(
+    "--regtest index export --output {}",
+    deltas.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::export::Output>();
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes --index {} index import --input {}",
+    tempdir.path().join("replica.redb").display(),
+    deltas.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: index deltas cannot be imported into an index built with `--index-runes`\n",
+  )
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn compact_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(10);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  let before = index_path.metadata().unwrap().len();
+
+  let output = CommandBuilder::new(format!("--index {} index compact", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::index::compact::Output>();
+
+  assert_eq!(output.before, before);
+  assert_eq!(output.after, index_path.metadata().unwrap().len());
+  assert!(output.after <= output.before);
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+}
+
+#[test]
+fn stats_report_growth_since_baseline() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  let output = CommandBuilder::new(format!("--index {} index stats", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::index::stats::Output>();
+
+  assert_eq!(output.baseline_blocks_indexed, None);
+  assert_eq!(output.blocks_indexed, 2);
+
+  let height_to_block_header = &output.tables["HEIGHT_TO_BLOCK_HEADER"];
+  assert_eq!(height_to_block_header.entries, 2);
+  assert_eq!(height_to_block_header.protocol, None);
+  assert_eq!(height_to_block_header.entries_growth, None);
+
+  assert_eq!(
+    output.tables["RUNE_ID_TO_RUNE_ENTRY"].protocol.as_deref(),
+    Some("runes")
+  );
+
+  assert!(tempdir.path().join("foo.redb.stats").is_file());
+
+  rpc_server.mine_blocks(3);
+
+  let output = CommandBuilder::new(format!("--index {} index stats", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::index::stats::Output>();
+
+  assert_eq!(output.baseline_blocks_indexed, Some(2));
+  assert_eq!(output.blocks_indexed, 5);
+  assert_eq!(
+    output.tables["HEIGHT_TO_BLOCK_HEADER"].entries_growth,
+    Some(3)
+  );
+}
+
+#[test]
+fn backup_is_usable_as_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+  let backup_path = tempdir.path().join("backup.redb");
+
+  let manifest = CommandBuilder::new(format!(
+    "--index {} index backup --output {}",
+    index_path.display(),
+    backup_path.display(),
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::backup::Manifest>();
+
+  assert!(!manifest.compressed);
+  assert_eq!(manifest.size, backup_path.metadata().unwrap().len());
+  assert_eq!(
+    manifest.tables["HEIGHT_TO_BLOCK_HEADER"],
+    u64::from(manifest.blocks_indexed)
+  );
+
+  assert_eq!(
+    serde_json::from_str::<ord::subcommand::index::backup::Manifest>(
+      &fs::read_to_string(tempdir.path().join("backup.redb.manifest.json")).unwrap()
+    )
+    .unwrap(),
+    manifest
+  );
+
+  CommandBuilder::new(format!("--index {} index update", backup_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+}
+
+#[test]
+fn compressed_backup() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let backup_path = tempdir.path().join("backup.redb.br");
+
+  let manifest = CommandBuilder::new(format!(
+    "index backup --compress --output {}",
+    backup_path.display(),
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::backup::Manifest>();
+
+  assert!(manifest.compressed);
+  assert_eq!(manifest.size, backup_path.metadata().unwrap().len());
+  assert!(!tempdir.path().join("backup.redb.br.uncompressed").exists());
+}
+
+#[test]
+fn backup_does_not_overwrite_existing_files() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("index backup --output backup.redb")
+    .write("backup.redb", "foo")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: backup destination `backup.redb` already exists\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn requests_fail_over_to_failover_rpc_url() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "--rpc-url 127.0.0.1:1 --rpc-failover-url {} --cookie-file {} --rpc-retries 1 --rpc-retry-delay 1 index update",
+    rpc_server.url(),
+    rpc_server.cookie_file().display(),
+  ))
+  .run_and_deserialize_output::<Empty>();
+}
@@ -149,6 +149,8 @@ fn get_inscription() {
     InscriptionJson {
       parent: None,
       children: Vec::new(),
+      cid: None,
+      content_verdicts: Vec::new(),
       inscription_id,
       inscription_number: 0,
       genesis_height: 2,
@@ -162,11 +164,871 @@ fn get_inscription() {
       timestamp: 2,
       previous: None,
       next: None,
+      recursive_endpoints: Vec::new(),
       rune: None,
     }
   )
 }
 
+#[test]
+fn get_inscription_transfers() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  let (inscription_id, reveal) = inscribe(&rpc_server);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription_id}",
+  ))
+  .rpc_server(&rpc_server)
+  .stdout_regex(r".*")
+  .run_and_deserialize_output::<ord::subcommand::wallet::send::Output>()
+  .transaction;
+
+  rpc_server.mine_blocks(1);
+
+  let response = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"])
+    .json_request(format!("/inscription/{inscription_id}/transfers"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let mut transfers_json: InscriptionTransfersJson =
+    serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert_eq!(transfers_json.transfers.len(), 1);
+  assert_regex_match!(
+    transfers_json.transfers[0].from_address.take().unwrap(),
+    r"bc1p.*"
+  );
+
+  let genesis_fee = transfers_json.genesis_fee;
+  assert!(genesis_fee > 0);
+
+  let fee = transfers_json.transfers[0].fee;
+  assert!(fee > 0);
+
+  let transaction = rpc_server.tx(3, 1);
+  assert_eq!(transaction.txid(), send);
+
+  pretty_assert_eq!(
+    transfers_json,
+    InscriptionTransfersJson {
+      genesis_fee,
+      genesis_height: 2,
+      inscription_id,
+      transfers: vec![InscriptionTransferJson {
+        fee,
+        from_address: None,
+        from_satpoint: SatPoint::from_str(&format!("{reveal}:0:0")).unwrap(),
+        height: 3,
+        to_address: Some("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".into()),
+        to_satpoint: SatPoint::from_str(&format!("{send}:0:0")).unwrap(),
+        txid: send,
+        value: transaction.output[0].value,
+      }],
+    }
+  );
+}
+
+#[test]
+fn get_inscription_fees() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  let (inscription_id, _) = inscribe(&rpc_server);
+
+  CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription_id}",
+  ))
+  .rpc_server(&rpc_server)
+  .stdout_regex(r".*")
+  .run_and_deserialize_output::<ord::subcommand::wallet::send::Output>();
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  let transfers = serde_json::from_str::<InscriptionTransfersJson>(
+    &server
+      .json_request(format!("/inscription/{inscription_id}/transfers"))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  let response = server.json_request("/analytics/fees");
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<FeesJson>(&response.text().unwrap()).unwrap(),
+    FeesJson {
+      blocks: vec![
+        BlockFeesJson {
+          creation_fees: transfers.genesis_fee,
+          creation_value: 10_000,
+          creations: 1,
+          height: 2,
+          transfer_fees: 0,
+          transfer_value: 0,
+          transfers: 0,
+        },
+        BlockFeesJson {
+          creation_fees: 0,
+          creation_value: 0,
+          creations: 0,
+          height: 3,
+          transfer_fees: transfers.transfers[0].fee,
+          transfer_value: transfers.transfers[0].value,
+          transfers: 1,
+        },
+      ],
+    }
+  );
+
+  pretty_assert_eq!(
+    serde_json::from_str::<FeesJson>(
+      &server
+        .json_request("/analytics/fees?from=3&to=3")
+        .text()
+        .unwrap()
+    )
+    .unwrap()
+    .blocks
+    .len(),
+    1
+  );
+
+  assert_eq!(
+    server.json_request("/analytics/fees?from=3&to=2").status(),
+    StatusCode::BAD_REQUEST
+  );
+}
+
+#[test]
+fn get_collection_stats_and_holders() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let parent_id = CommandBuilder::new("wallet inscribe --fee-rate 1 --file parent.png")
+    .write("parent.png", [1; 520])
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>()
+    .inscriptions[0]
+    .id;
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  let response = server.json_request(format!("/collection/{parent_id}/stats"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<CollectionStatsJson>(&response.text().unwrap()).unwrap(),
+    CollectionStatsJson {
+      first_child_height: None,
+      holders: 0,
+      inscription_id: parent_id,
+      last_child_height: None,
+      supply: 0,
+    }
+  );
+
+  for _ in 0..2 {
+    CommandBuilder::new(format!(
+      "wallet inscribe --fee-rate 1 --parent {parent_id} --file child.png"
+    ))
+    .write("child.png", [1; 520])
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+    rpc_server.mine_blocks(1);
+  }
+
+  let response = server.json_request(format!("/collection/{parent_id}/stats"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<CollectionStatsJson>(&response.text().unwrap()).unwrap(),
+    CollectionStatsJson {
+      first_child_height: Some(3),
+      holders: 2,
+      inscription_id: parent_id,
+      last_child_height: Some(4),
+      supply: 2,
+    }
+  );
+
+  let response = server.json_request(format!("/collection/{parent_id}/holders"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let holders_json: CollectionHoldersJson =
+    serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert!(!holders_json.more);
+  assert_eq!(holders_json.page_index, 0);
+  assert_eq!(holders_json.holders.len(), 2);
+
+  for holder in holders_json.holders {
+    assert_eq!(holder.children, 1);
+    assert_regex_match!(holder.address.unwrap(), r"bc1p.*");
+  }
+
+  let response = server.json_request(format!("/collection/{parent_id}/holders/1"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<CollectionHoldersJson>(&response.text().unwrap()).unwrap(),
+    CollectionHoldersJson {
+      holders: Vec::new(),
+      more: false,
+      page_index: 1,
+    }
+  );
+}
+
+#[test]
+fn collection_holders_respect_query_timeout() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let parent_id = CommandBuilder::new("wallet inscribe --fee-rate 1 --file parent.png")
+    .write("parent.png", [1; 520])
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>()
+    .inscriptions[0]
+    .id;
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "wallet inscribe --fee-rate 1 --parent {parent_id} --file child.png"
+  ))
+  .write("child.png", [1; 520])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &[],
+    &["--enable-json-api", "--query-timeout", "0"],
+  );
+
+  let response = server.json_request(format!("/collection/{parent_id}/holders"));
+
+  assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+  assert_eq!(response.headers()["retry-after"], "1");
+  assert_eq!(
+    response.text().unwrap(),
+    "query deadline exceeded after collecting 0 results, try again later"
+  );
+}
+
+#[test]
+fn get_address_runes() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let etch = etch(&rpc_server, Rune(RUNE));
+
+  let tx = rpc_server.tx(2, 1);
+  assert_eq!(tx.txid(), etch.transaction);
+
+  let address = Address::from_script(&tx.output[1].script_pubkey, Network::Regtest).unwrap();
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--regtest", "--index-runes", "--index-addresses"],
+    &["--enable-json-api"],
+  );
+
+  let response = server.json_request(format!("/address/{address}/runes"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let address_runes = serde_json::from_str::<AddressRunesJson>(&response.text().unwrap()).unwrap();
+
+  assert_eq!(address_runes.address, address.to_string());
+  assert_eq!(address_runes.runes.len(), 1);
+
+  let rune = &address_runes.runes[0];
+
+  assert_eq!(rune.amount, 1000);
+  assert_eq!(rune.display, "1000\u{00A0}¢");
+  assert_eq!(rune.divisibility, 0);
+  assert_eq!(
+    rune.id,
+    RuneId {
+      height: 2,
+      index: 1,
+    }
+  );
+  assert_eq!(
+    rune.outpoints,
+    [RuneOutpointJson {
+      amount: 1000,
+      outpoint: OutPoint {
+        txid: etch.transaction,
+        vout: 1,
+      },
+    }]
+  );
+  assert_eq!(rune.rune.to_string(), Rune(RUNE).to_string());
+  assert_eq!(rune.symbol, Some('¢'));
+
+  let response = server.json_request("/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4/runes");
+
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn get_address_runes_requires_address_index() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let response = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--regtest", "--index-runes"],
+    &["--enable-json-api"],
+  )
+  .json_request("/address/bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080/runes");
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn get_address_rune_balance_at_height() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  etch(&rpc_server, Rune(RUNE));
+
+  let sender = Address::from_script(
+    &rpc_server.tx(2, 1).output[1].script_pubkey,
+    Network::Regtest,
+  )
+  .unwrap()
+  .to_string();
+
+  let recipient = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw";
+
+  CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 {recipient} 1000{}",
+    Rune(RUNE)
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::wallet::send::Output>();
+
+  rpc_server.mine_blocks(1);
+
+  let tip = rpc_server.height();
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--regtest", "--index-runes", "--index-addresses"],
+    &["--enable-json-api"],
+  );
+
+  let balance = |address: &str, height: Option<u64>| {
+    let response = server.json_request(format!(
+      "/address/{address}/runes/{}{}",
+      Rune(RUNE),
+      height
+        .map(|height| format!("?height={height}"))
+        .unwrap_or_default(),
+    ));
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    serde_json::from_str::<AddressRuneBalanceJson>(&response.text().unwrap()).unwrap()
+  };
+
+  let etched = balance(&sender, Some(2));
+
+  assert_eq!(etched.address, sender);
+  assert_eq!(etched.amount, 1000);
+  assert_eq!(etched.display, "1000\u{00A0}¢");
+  assert_eq!(etched.height, 2);
+  assert_eq!(
+    etched.id,
+    RuneId {
+      height: 2,
+      index: 1,
+    }
+  );
+  assert_eq!(etched.rune.to_string(), Rune(RUNE).to_string());
+
+  assert_eq!(balance(&sender, Some(1)).amount, 0);
+  assert_eq!(balance(recipient, Some(2)).amount, 0);
+
+  let current = balance(&sender, None);
+
+  assert_eq!(current.amount, 0);
+  assert_eq!(u64::from(current.height), tip);
+
+  assert_eq!(balance(recipient, None).amount, 1000);
+
+  assert_eq!(
+    server
+      .json_request(format!(
+        "/address/{recipient}/runes/{}?height={}",
+        Rune(RUNE),
+        tip + 1
+      ))
+      .status(),
+    StatusCode::NOT_FOUND
+  );
+}
+
+#[test]
+fn get_address_changes() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  rpc_server.mine_blocks(1);
+
+  let txid = rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    ..Default::default()
+  });
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--regtest", "--index-addresses"],
+    &["--enable-json-api"],
+  );
+
+  let response = server.json_request("/address-changes/2");
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let changes = serde_json::from_str::<AddressChangesJson>(&response.text().unwrap()).unwrap();
+
+  let spent = rpc_server.tx(1, 0);
+
+  assert_eq!(changes.height, 2);
+
+  assert_eq!(
+    changes.removed,
+    [AddressChangeJson {
+      address: None,
+      outpoint: OutPoint {
+        txid: spent.txid(),
+        vout: 0,
+      },
+      script_pubkey: hex::encode(spent.output[0].script_pubkey.as_bytes()),
+    }]
+  );
+
+  let mut added = changes
+    .added
+    .iter()
+    .map(|change| change.outpoint)
+    .collect::<Vec<OutPoint>>();
+
+  added.sort();
+
+  let mut expected = vec![
+    OutPoint {
+      txid: rpc_server.tx(2, 0).txid(),
+      vout: 0,
+    },
+    OutPoint { txid, vout: 0 },
+  ];
+
+  expected.sort();
+
+  assert_eq!(added, expected);
+
+  assert_eq!(
+    server.json_request("/address-changes/3").status(),
+    StatusCode::NOT_FOUND
+  );
+}
+
+#[test]
+fn get_address_changes_requires_address_index() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let response =
+    TestServer::spawn_with_server_args(&rpc_server, &["--regtest"], &["--enable-json-api"])
+      .json_request("/address-changes/0");
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn get_address_activity() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  let inscription_id = output.inscriptions[0].id;
+  let reveal = output.reveal;
+
+  let block = rpc_server.mine_blocks(1).remove(0);
+
+  let address = Address::from_script(
+    &block
+      .txdata
+      .iter()
+      .find(|tx| tx.txid() == reveal)
+      .unwrap()
+      .output[0]
+      .script_pubkey,
+    Network::Bitcoin,
+  )
+  .unwrap();
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription_id}",
+  ))
+  .rpc_server(&rpc_server)
+  .stdout_regex(r".*")
+  .run_and_deserialize_output::<ord::subcommand::wallet::send::Output>()
+  .transaction;
+
+  rpc_server.mine_blocks(1);
+
+  let server =
+    TestServer::spawn_with_server_args(&rpc_server, &["--index-addresses"], &["--enable-json-api"]);
+
+  let activity = |path: String| {
+    let response = server.json_request(path);
+    assert_eq!(response.status(), StatusCode::OK);
+    serde_json::from_str::<ActivityJson>(&response.text().unwrap()).unwrap()
+  };
+
+  let first = activity(format!("/activity/{address}?limit=1"));
+
+  assert_eq!(first.address, address.to_string());
+  assert_eq!(first.activity.len(), 1);
+  assert_eq!(first.activity[0].block_height, 3);
+  assert_eq!(first.activity[0].direction, Direction::Sent);
+  assert_eq!(first.activity[0].kind, ActivityKind::InscriptionTransferred);
+  assert_eq!(first.activity[0].inscription_id, Some(inscription_id));
+  assert_eq!(first.activity[0].txid, send);
+
+  let second = activity(format!(
+    "/activity/{address}?limit=1&cursor={}",
+    first.next.unwrap()
+  ));
+
+  assert_eq!(second.activity.len(), 1);
+  assert_eq!(second.activity[0].block_height, 2);
+  assert_eq!(second.activity[0].direction, Direction::Received);
+  assert_eq!(second.activity[0].kind, ActivityKind::InscriptionCreated);
+  assert_eq!(
+    second.activity[0].outpoint,
+    OutPoint {
+      txid: reveal,
+      vout: 0
+    }
+  );
+  assert_eq!(second.next, None);
+
+  let received = activity("/activity/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".into());
+
+  assert_eq!(received.activity.len(), 1);
+  assert_eq!(received.activity[0].direction, Direction::Received);
+  assert_eq!(received.activity[0].txid, send);
+}
+
+#[test]
+fn get_address_activity_requires_address_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let response = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"])
+    .json_request("/activity/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn get_sat_summaries() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  let (_, reveal) = inscribe(&rpc_server);
+
+  let server =
+    TestServer::spawn_with_server_args(&rpc_server, &["--index-sats"], &["--enable-json-api"]);
+
+  server.sync_server();
+
+  let post = |path: &str, body: serde_json::Value| {
+    reqwest::blocking::Client::new()
+      .post(server.url().join(path).unwrap())
+      .json(&body)
+      .send()
+      .unwrap()
+  };
+
+  let summaries = [
+    SatSummaryJson {
+      number: 0,
+      name: "nvtdijuwxlp".into(),
+      decimal: "0.0".into(),
+      degree: "0°0′0″0‴".into(),
+      rarity: Rarity::Mythic,
+      satpoint: Some(
+        "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0"
+          .parse()
+          .unwrap(),
+      ),
+    },
+    SatSummaryJson {
+      number: 50 * COIN_VALUE,
+      name: "nvtcsezkbth".into(),
+      decimal: "1.0".into(),
+      degree: "0°1′1″0‴".into(),
+      rarity: Rarity::Uncommon,
+      satpoint: Some(SatPoint::from_str(&format!("{reveal}:0:0")).unwrap()),
+    },
+  ];
+
+  let response = post("/sats", serde_json::json!(["0", "1.0"]));
+  assert_eq!(response.status(), StatusCode::OK);
+  pretty_assert_eq!(response.json::<Vec<SatSummaryJson>>().unwrap(), summaries);
+
+  let response = post(
+    "/sats/by-name",
+    serde_json::json!(["nvtdijuwxlp", "nvtcsezkbth"]),
+  );
+  assert_eq!(response.status(), StatusCode::OK);
+  pretty_assert_eq!(response.json::<Vec<SatSummaryJson>>().unwrap(), summaries);
+
+  let response = post("/sats/by-name", serde_json::json!(["nvtdijuwxlp", "0"]));
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+  assert_eq!(
+    response.text().unwrap(),
+    "invalid sat `0`: invalid character in sat name: 0"
+  );
+
+  let response = post("/sats", serde_json::json!(vec!["0"; 1001]));
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+  assert_eq!(
+    response.text().unwrap(),
+    "at most 1000 sats may be requested at once"
+  );
+}
+
+#[test]
+fn get_inscription_with_content_verdicts() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let inscription_id = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.png")
+    .write("foo.png", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>()
+    .inscriptions[0]
+    .id;
+
+  rpc_server.mine_blocks(1);
+
+  let response = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &[
+      "--content-validator",
+      "mime-sniff",
+      "--content-validator",
+      "max-size",
+      "--content-validator",
+      "image-decode",
+      "--max-content-size",
+      "3",
+    ],
+    &["--enable-json-api"],
+  )
+  .json_request(format!("/inscription/{inscription_id}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let inscription_json: InscriptionJson = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  pretty_assert_eq!(
+    inscription_json.content_verdicts,
+    [
+      ContentVerdict {
+        validator: ContentValidator::ImageDecode,
+        valid: false,
+      },
+      ContentVerdict {
+        validator: ContentValidator::MaxSize,
+        valid: true,
+      },
+      ContentVerdict {
+        validator: ContentValidator::MimeSniff,
+        valid: false,
+      },
+    ]
+  );
+}
+
+#[test]
+fn get_inscription_with_recursive_endpoints() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let inscription_id = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.html")
+    .write(
+      "foo.html",
+      "<script>fetch('/r/blockheight').then(() => fetch('/r/children/foo'))</script>",
+    )
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>()
+    .inscriptions[0]
+    .id;
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  let inscription_json: InscriptionJson = serde_json::from_str(
+    &server
+      .json_request(format!("/inscription/{inscription_id}"))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  pretty_assert_eq!(
+    inscription_json.recursive_endpoints,
+    [RecursiveEndpoint::BlockHeight, RecursiveEndpoint::Children],
+  );
+
+  server.assert_response_regex(
+    format!("/inscription/{inscription_id}"),
+    r".*<dt>recursive endpoints</dt>\s*<dd>\s*<span class=monospace>/r/blockheight</span>\s*<span class=monospace>/r/children/</span>\s*</dd>.*",
+  );
+}
+
+#[test]
+fn get_preview_check() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let script = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.js")
+    .write("foo.js", "fetch('https://foo.com/track')")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>()
+    .inscriptions[0]
+    .id;
+
+  rpc_server.mine_blocks(1);
+
+  let missing = InscriptionId {
+    txid: script.txid,
+    index: 1,
+  };
+
+  let page = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.html")
+    .write(
+      "foo.html",
+      format!(
+        "<script src=/content/{script}></script><img src=/content/{missing}><script>fetch('/r/blockheight')</script>"
+      ),
+    )
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>()
+    .inscriptions[0]
+    .id;
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  let check = |inscription_id: InscriptionId| {
+    serde_json::from_str::<PreviewCheckJson>(
+      &server
+        .json_request(format!("/preview/{inscription_id}/check"))
+        .text()
+        .unwrap(),
+    )
+    .unwrap()
+  };
+
+  pretty_assert_eq!(
+    check(page),
+    PreviewCheckJson {
+      content_type: Some("text/html;charset=utf-8".into()),
+      dependencies: vec![script],
+      depth: 1,
+      external_urls: vec!["https://foo.com/track".into()],
+      id: page,
+      missing_dependencies: vec![missing],
+      recursive_endpoints: vec![RecursiveEndpoint::BlockHeight, RecursiveEndpoint::Content],
+      safe: false,
+      scripted: true,
+      truncated: false,
+    }
+  );
+
+  pretty_assert_eq!(
+    check(script),
+    PreviewCheckJson {
+      content_type: Some("text/javascript".into()),
+      dependencies: Vec::new(),
+      depth: 0,
+      external_urls: vec!["https://foo.com/track".into()],
+      id: script,
+      missing_dependencies: Vec::new(),
+      recursive_endpoints: Vec::new(),
+      safe: false,
+      scripted: false,
+      truncated: false,
+    }
+  );
+
+  assert_eq!(
+    server
+      .json_request(format!("/preview/{missing}/check"))
+      .status(),
+    StatusCode::NOT_FOUND
+  );
+}
+
 #[test]
 fn get_inscriptions() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -221,6 +1083,87 @@ fn get_inscriptions() {
   assert_eq!(inscriptions_json.page_index, 1);
 }
 
+#[test]
+fn get_inscriptions_sorted() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  rpc_server.mine_blocks(3);
+
+  let mut inscriptions = Vec::new();
+
+  for (i, (content_type, body)) in [
+    ("text/plain;charset=utf-8", "a"),
+    ("text/html", "<b>hi</b>"),
+    ("text/plain", "hello world"),
+  ]
+  .into_iter()
+  .enumerate()
+  {
+    let txid = rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        i + 1,
+        0,
+        0,
+        envelope(&[b"ord", &[1], content_type.as_bytes(), &[], body.as_bytes()]),
+      )],
+      ..Default::default()
+    });
+
+    rpc_server.mine_blocks(1);
+
+    inscriptions.push(InscriptionId { txid, index: 0 });
+  }
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--index-inscription-sorts"],
+    &["--enable-json-api"],
+  );
+
+  for (query, expected) in [
+    ("", [2, 1, 0].as_slice()),
+    ("?sort=size", &[2, 1, 0]),
+    ("?sort=size&order=asc", &[0, 1, 2]),
+    ("?sort=number&order=asc", &[0, 1, 2]),
+    ("?content_type=text/plain", &[2, 0]),
+    ("?content_type=TEXT/PLAIN&sort=size&order=asc", &[0, 2]),
+    ("?content_type=text/html&sort=number", &[1]),
+    ("?content_type=image/png", &[]),
+  ] {
+    let response = server.json_request(format!("/inscriptions{query}"));
+    assert_eq!(response.status(), StatusCode::OK, "{query}");
+
+    pretty_assert_eq!(
+      serde_json::from_str::<InscriptionsJson>(&response.text().unwrap()).unwrap(),
+      InscriptionsJson {
+        inscriptions: expected.iter().map(|i| inscriptions[*i]).collect(),
+        more: false,
+        page_index: 0,
+      },
+      "{query}"
+    );
+  }
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  assert_eq!(
+    server
+      .json_request("/inscriptions?sort=number&order=asc")
+      .status(),
+    StatusCode::OK
+  );
+
+  for query in ["?sort=fee", "?content_type=text/plain"] {
+    assert_eq!(
+      server
+        .json_request(format!("/inscriptions{query}"))
+        .status(),
+      StatusCode::NOT_FOUND,
+      "{query}"
+    );
+  }
+}
+
 #[test]
 fn get_inscriptions_in_block() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -390,6 +1333,7 @@ fn get_status() {
 
   status_json.started = dummy_started;
   status_json.uptime = dummy_uptime;
+  status_json.progress = IndexProgress::default();
 
   pretty_assert_eq!(
     status_json,
@@ -401,6 +1345,8 @@ fn get_status() {
       inscriptions: 1,
       lost_sats: 0,
       minimum_rune_for_next_block: Rune(99246114928149462),
+      progress: IndexProgress::default(),
+      quarantined_entries: 0,
       rune_index: false,
       runes: 0,
       sat_index: true,
@@ -411,3 +1357,166 @@ fn get_status() {
     }
   );
 }
+
+#[test]
+fn json_rpc_requires_json_api() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let response = TestServer::spawn_with_server_args(&rpc_server, &[], &[])
+    .rpc_request(serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "getblockcount"}));
+
+  assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+}
+
+#[test]
+fn json_rpc_get_inscription() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  let (inscription_id, _reveal) = inscribe(&rpc_server);
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  for params in [
+    serde_json::json!([inscription_id.to_string()]),
+    serde_json::json!([0]),
+    serde_json::json!({"inscription": inscription_id.to_string()}),
+  ] {
+    let response = server.rpc_request(serde_json::json!({
+      "jsonrpc": "2.0",
+      "id": "a",
+      "method": "getinscription",
+      "params": params,
+    }));
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = response.json::<serde_json::Value>().unwrap();
+
+    assert_eq!(response["id"], "a");
+
+    let inscription_json =
+      serde_json::from_value::<InscriptionJson>(response["result"].clone()).unwrap();
+
+    assert_eq!(inscription_json.inscription_id, inscription_id);
+    assert_eq!(inscription_json.inscription_number, 0);
+    assert_eq!(inscription_json.genesis_height, 2);
+  }
+
+  let response = server
+    .rpc_request(serde_json::json!({
+      "jsonrpc": "2.0",
+      "id": 1,
+      "method": "getinscription",
+      "params": [1],
+    }))
+    .json::<serde_json::Value>()
+    .unwrap();
+
+  assert_eq!(response["error"]["code"], -32001);
+  assert_eq!(response["error"]["message"], "inscription 1 not found");
+}
+
+#[test]
+fn json_rpc_get_block_events() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  let (inscription_id, reveal) = inscribe(&rpc_server);
+
+  let response =
+    TestServer::spawn_with_server_args(&rpc_server, &["--index-events"], &["--enable-json-api"])
+      .rpc_request(serde_json::json!({
+          "jsonrpc": "2.0",
+          "id": 1,
+          "method": "getblockevents",
+        "params": [2],
+      }))
+      .json::<serde_json::Value>()
+      .unwrap();
+
+  pretty_assert_eq!(
+    response,
+    serde_json::json!({
+      "jsonrpc": "2.0",
+      "id": 1,
+      "result": {
+        "height": 2,
+        "events": [
+          {
+            "type": "inscription_created",
+            "block_height": 2,
+            "inscription_id": inscription_id,
+            "inscription_number": 0,
+            "location": format!("{reveal}:0:0"),
+            "metaprotocol": null,
+            "sequence_number": 0,
+          },
+        ],
+      },
+    })
+  );
+}
+
+#[test]
+fn json_rpc_respects_query_timeout() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  inscribe(&rpc_server);
+
+  let response = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--index-events"],
+    &["--enable-json-api", "--query-timeout", "0"],
+  )
+  .rpc_request(serde_json::json!({
+    "jsonrpc": "2.0",
+    "id": 1,
+    "method": "getblockevents",
+    "params": [2],
+  }))
+  .json::<serde_json::Value>()
+  .unwrap();
+
+  pretty_assert_eq!(
+    response,
+    serde_json::json!({
+      "jsonrpc": "2.0",
+      "id": 1,
+      "error": {
+        "code": -32002,
+        "message": "query deadline exceeded after collecting 0 results, try again later",
+      },
+    })
+  );
+}
+
+#[test]
+fn json_rpc_batch() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let response = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"])
+    .rpc_request(serde_json::json!([
+      {"jsonrpc": "2.0", "id": 1, "method": "getblockcount"},
+      {"jsonrpc": "2.0", "method": "getblockcount"},
+      {"jsonrpc": "2.0", "id": 2, "method": "foo"},
+      {"jsonrpc": "1.0", "id": 3, "method": "getblockcount"},
+      {"jsonrpc": "2.0", "id": 4, "method": "getblockevents", "params": ["bar"]},
+    ]))
+    .json::<serde_json::Value>()
+    .unwrap();
+
+  pretty_assert_eq!(
+    response,
+    serde_json::json!([
+      {"jsonrpc": "2.0", "id": 1, "result": 1},
+      {"jsonrpc": "2.0", "id": 2, "error": {"code": -32601, "message": "method `foo` not found"}},
+      {"jsonrpc": "2.0", "id": 3, "error": {"code": -32600, "message": "jsonrpc must be \"2.0\""}},
+      {"jsonrpc": "2.0", "id": 4, "error": {"code": -32602, "message": "`height` must be a block height"}},
+    ])
+  );
+}
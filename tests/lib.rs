@@ -14,10 +14,22 @@ use {
     rarity::Rarity,
     subcommand::runes::RuneInfo,
     templates::{
-      block::BlockJson, inscription::InscriptionJson, inscriptions::InscriptionsJson,
-      output::OutputJson, sat::SatJson, status::StatusHtml,
+      address::{
+        ActivityJson, AddressChangeJson, AddressChangesJson, AddressRuneBalanceJson,
+        AddressRunesJson, RuneOutpointJson,
+      },
+      block::BlockJson,
+      collections::{CollectionHoldersJson, CollectionStatsJson},
+      fees::{BlockFeesJson, FeesJson},
+      inscription::{InscriptionJson, InscriptionTransferJson, InscriptionTransfersJson},
+      inscriptions::InscriptionsJson,
+      output::OutputJson,
+      preview::PreviewCheckJson,
+      sat::{SatJson, SatSummaryJson},
+      status::{IndexProgress, StatusHtml},
     },
-    Edict, InscriptionId, Rune, RuneId, Runestone, SatPoint,
+    ActivityKind, ContentValidator, ContentVerdict, Direction, Edict, InscriptionId,
+    RecursiveEndpoint, Rune, RuneId, Runestone, SatPoint,
   },
   pretty_assertions::assert_eq as pretty_assert_eq,
   regex::Regex,
@@ -124,21 +136,31 @@ mod command_builder;
 mod expected;
 mod test_server;
 
+mod api;
 mod balances;
+mod bench;
+mod brc20;
+mod config;
 mod core;
+mod crash_recovery;
 mod decode;
 mod epochs;
 mod etch;
+mod export;
 mod find;
 mod index;
 mod info;
 mod json_api;
 mod list;
 mod parse;
+mod protocol;
+mod reconcile;
 mod runes;
 mod server;
 mod subsidy;
 mod supply;
+mod teleburn;
+mod test_fixture;
 mod traits;
 mod version;
 mod wallet;
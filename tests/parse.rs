@@ -5,6 +5,7 @@ fn name() {
   assert_eq!(
     CommandBuilder::new("parse a").run_and_deserialize_output::<Output>(),
     Output {
+      checksummed: None,
       object: Object::Integer(2099999997689999),
     }
   );
@@ -16,6 +17,7 @@ fn hash() {
     CommandBuilder::new("parse 0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
       .run_and_deserialize_output::<Output>(),
     Output {
+      checksummed: None,
       object: "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
         .parse::<Object>()
         .unwrap(),
@@ -30,3 +32,56 @@ fn unrecognized_object() {
     .expected_exit_code(2)
     .run_and_extract_stdout();
 }
+
+#[test]
+fn ids_and_names_without_checksums() {
+  let id = "1111111111111111111111111111111111111111111111111111111111111111i1";
+
+  assert_eq!(
+    CommandBuilder::new(format!("parse {id}")).run_and_deserialize_output::<Output>(),
+    Output {
+      checksummed: None,
+      object: Object::InscriptionId(id.parse().unwrap()),
+    }
+  );
+
+  assert_eq!(
+    CommandBuilder::new("parse nvtdijuwxlp").run_and_deserialize_output::<Output>(),
+    Output {
+      checksummed: None,
+      object: Object::Integer(0),
+    }
+  );
+}
+
+#[test]
+fn checksum() {
+  let id = "1111111111111111111111111111111111111111111111111111111111111111i1";
+
+  let checksummed = CommandBuilder::new(format!("parse --checksum {id}"))
+    .run_and_deserialize_output::<Output>()
+    .checksummed
+    .unwrap();
+
+  assert!(checksummed.starts_with(&format!("{id}-")));
+
+  assert_eq!(
+    CommandBuilder::new(format!("parse {checksummed}")).run_and_deserialize_output::<Output>(),
+    Output {
+      checksummed: None,
+      object: id.parse::<Object>().unwrap(),
+    }
+  );
+
+  CommandBuilder::new(format!("parse {}", checksummed.replacen("i1-", "i2-", 1)))
+    .stderr_regex(
+      r"error: .*invalid inscription ID: checksum `.{6}` does not match: check for typos\n.*",
+    )
+    .expected_exit_code(2)
+    .run_and_extract_stdout();
+
+  CommandBuilder::new("parse --checksum 0")
+    .expected_stderr("error: only inscription IDs and sat names can be checksummed\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
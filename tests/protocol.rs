@@ -0,0 +1,121 @@
+use {
+  super::*,
+  ord::subcommand::{
+    protocol::{GetOutput, ReindexOutput, TableOutput},
+    runes::Output,
+  },
+};
+
+#[test]
+fn tables_list_protocol_state_with_entry_counts() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  etch(&rpc_server, Rune(RUNE));
+
+  let tables = CommandBuilder::new("--index-runes --regtest protocol tables")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Vec<TableOutput>>();
+
+  assert_eq!(tables.len(), 6);
+
+  let table = tables
+    .iter()
+    .find(|table| table.table == "TRANSACTION_ID_TO_RUNE")
+    .unwrap();
+
+  assert_eq!(table.protocol, "runes");
+  assert_eq!(table.entries, 1);
+  assert!(table.stored_bytes > 0);
+}
+
+#[test]
+fn get_prints_decoded_values() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let etch = etch(&rpc_server, Rune(RUNE));
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--index-runes --regtest protocol get transaction-id-to-rune {}",
+      etch.transaction
+    ))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<GetOutput>(),
+    GetOutput {
+      table: "TRANSACTION_ID_TO_RUNE".into(),
+      key: etch.transaction.to_string(),
+      values: vec![Rune(RUNE).to_string()],
+    }
+  );
+}
+
+#[test]
+fn get_missing_key_is_an_error() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  CommandBuilder::new("--index-runes --regtest protocol get sequence-number-to-rune-id 0")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: key `0` not found in table `SEQUENCE_NUMBER_TO_RUNE_ID`\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn reindex_rebuilds_rune_state() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  etch(&rpc_server, Rune(RUNE));
+
+  let tempdir = TempDir::new().unwrap();
+  let index_path = tempdir.path().join("index.redb");
+
+  let runes = || {
+    CommandBuilder::new(format!(
+      "--index-runes --regtest --index {} runes",
+      index_path.display()
+    ))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Output>()
+  };
+
+  let before = runes();
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --regtest --index {} protocol reindex --protocol runes",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ReindexOutput>();
+
+  assert_eq!(output.protocol, "runes");
+  assert!(output.blocks > 0);
+
+  assert_eq!(runes(), before);
+}
+
+#[test]
+fn reindex_requires_protocol_index() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  CommandBuilder::new("--regtest protocol reindex --protocol runes")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: index was not built with `--index-runes`\n")
+    .run_and_extract_stdout();
+}
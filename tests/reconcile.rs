@@ -0,0 +1,80 @@
+use {
+  super::*,
+  ord::subcommand::reconcile::{Discrepancy, Output},
+};
+
+#[test]
+fn reconcile_against_identical_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  inscribe(&rpc_server);
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  ord_server.sync_server();
+
+  assert_eq!(
+    CommandBuilder::new(format!("reconcile --against {}", ord_server.url()))
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Output>(),
+    Output {
+      discrepancies: Vec::new(),
+      first_divergent_inscription: None,
+      from: 0,
+      to: 3,
+    }
+  );
+}
+
+#[test]
+fn reconcile_reports_missing_inscriptions() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _reveal) = inscribe(&rpc_server);
+
+  let other_rpc_server = test_bitcoincore_rpc::spawn();
+  other_rpc_server.mine_blocks(2);
+
+  let ord_server =
+    TestServer::spawn_with_server_args(&other_rpc_server, &[], &["--enable-json-api"]);
+
+  ord_server.sync_server();
+
+  assert_eq!(
+    CommandBuilder::new(format!("reconcile --against {}", ord_server.url()))
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Output>(),
+    Output {
+      discrepancies: vec![Discrepancy {
+        first_divergent_inscription: inscription,
+        height: 2,
+        missing_locally: Vec::new(),
+        missing_remotely: vec![inscription],
+      }],
+      first_divergent_inscription: Some(inscription),
+      from: 0,
+      to: 3,
+    }
+  );
+}
+
+#[test]
+fn reconcile_requires_json_api() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &[]);
+
+  CommandBuilder::new(format!(
+    "reconcile --against {} --from 0 --to 1",
+    ord_server.url()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!(
+    "error: {}inscriptions/block/0/0 returned 406 Not Acceptable\n",
+    ord_server.url()
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -47,7 +47,8 @@ fn inscription_page() {
   let ethereum_teleburn_address = CommandBuilder::new(format!("teleburn {inscription}"))
     .rpc_server(&rpc_server)
     .run_and_deserialize_output::<ord::subcommand::teleburn::Output>()
-    .ethereum;
+    .ethereum
+    .unwrap();
 
   TestServer::spawn_with_args(&rpc_server, &[]).assert_response_regex(
     format!("/inscription/{inscription}"),
@@ -492,3 +493,918 @@ fn inscription_transactions_are_stored_with_transaction_index() {
     StatusCode::NOT_FOUND,
   );
 }
+
+type Objects = std::sync::Arc<std::sync::Mutex<BTreeMap<String, (Vec<(String, String)>, Vec<u8>)>>>;
+
+/// Spawn an S3-compatible bucket that stores objects in memory.
+fn spawn_bucket() -> (String, Objects) {
+  use std::io::{BufRead, BufReader, Read};
+
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let url = format!("http://{}/bucket", listener.local_addr().unwrap());
+  let objects = Objects::default();
+
+  let clone = objects.clone();
+  thread::spawn(move || {
+    for stream in listener.incoming() {
+      let mut stream = stream.unwrap();
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+      let mut request_line = String::new();
+      reader.read_line(&mut request_line).unwrap();
+      let mut request_line = request_line.split_whitespace();
+      let method = request_line.next().unwrap().to_string();
+      let key = request_line
+        .next()
+        .unwrap()
+        .trim_start_matches("/bucket/")
+        .to_string();
+
+      let mut headers = Vec::new();
+      loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+        if line.is_empty() {
+          break;
+        }
+        let (name, value) = line.split_once(':').unwrap();
+        headers.push((name.to_lowercase(), value.trim().to_string()));
+      }
+
+      let content_length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .map(|(_, value)| value.parse().unwrap())
+        .unwrap_or(0);
+
+      let mut body = vec![0; content_length];
+      reader.read_exact(&mut body).unwrap();
+
+      let mut objects = clone.lock().unwrap();
+
+      let response = match method.as_str() {
+        "PUT" => {
+          objects.insert(key, (headers, body));
+          b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_vec()
+        }
+        "GET" | "HEAD" => match objects.get(&key) {
+          Some((headers, body)) => {
+            let mut response = format!(
+              "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n",
+              body.len()
+            );
+            for (name, value) in headers {
+              if name == "content-type" || name.starts_with("x-amz-meta-") {
+                response.push_str(&format!("{name}: {value}\r\n"));
+              }
+            }
+            response.push_str("\r\n");
+            let mut response = response.into_bytes();
+            if method == "GET" {
+              response.extend_from_slice(body);
+            }
+            response
+          }
+          None => {
+            b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_vec()
+          }
+        },
+        _ => panic!("unexpected method {method}"),
+      };
+
+      stream.write_all(&response).unwrap();
+    }
+  });
+
+  (url, objects)
+}
+
+#[test]
+fn inscription_content_is_uploaded_to_and_proxied_from_bucket() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let (url, objects) = spawn_bucket();
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--content-bucket", &url],
+    &["--content-bucket-mode", "proxy"],
+  );
+
+  server.sync_server();
+
+  // content is uploaded after the block is committed
+  for attempt in 0.. {
+    if objects
+      .lock()
+      .unwrap()
+      .contains_key(&inscription.to_string())
+    {
+      break;
+    }
+
+    assert!(attempt < 100, "content was not uploaded");
+
+    thread::sleep(Duration::from_millis(50));
+  }
+
+  {
+    let mut objects = objects.lock().unwrap();
+
+    let (headers, body) = objects.get_mut(&inscription.to_string()).unwrap();
+
+    assert_eq!(body, b"FOO");
+    assert!(headers.contains(&("content-type".into(), "text/plain;charset=utf-8".into())));
+    assert!(headers.contains(&(
+      "x-amz-meta-sha256".into(),
+      "9520437ce8902eb379a7d8aaa98fc4c94eeb07b6684854868fa6f72bf34b0fd3".into()
+    )));
+
+    *body = b"BAR".to_vec();
+  }
+
+  let response = server.request(format!("/content/{inscription}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response.headers().get("content-type").unwrap(),
+    "text/plain;charset=utf-8"
+  );
+  assert_eq!(response.bytes().unwrap(), "BAR");
+}
+
+#[test]
+fn inscription_content_is_redirected_to_bucket() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let (url, _objects) = spawn_bucket();
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--content-bucket", &url],
+    &["--content-bucket-mode", "redirect"],
+  );
+
+  server.sync_server();
+
+  let response = reqwest::blocking::Client::builder()
+    .redirect(reqwest::redirect::Policy::none())
+    .build()
+    .unwrap()
+    .get(
+      server
+        .url()
+        .join(&format!("/content/{inscription}"))
+        .unwrap(),
+    )
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+  assert_eq!(
+    response.headers().get("location").unwrap(),
+    &format!("{url}/{inscription}"),
+  );
+}
+
+#[test]
+fn hidden_inscription_content_is_not_redirected_to_bucket() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let (url, objects) = spawn_bucket();
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--content-bucket", &url],
+    &["--content-bucket-mode", "redirect", "--admin-token", "foo"],
+  );
+
+  server.sync_server();
+
+  for attempt in 0.. {
+    if objects
+      .lock()
+      .unwrap()
+      .contains_key(&inscription.to_string())
+    {
+      break;
+    }
+
+    assert!(attempt < 100, "content was not uploaded");
+
+    thread::sleep(Duration::from_millis(50));
+  }
+
+  let client = reqwest::blocking::Client::builder()
+    .redirect(reqwest::redirect::Policy::none())
+    .build()
+    .unwrap();
+
+  assert_eq!(
+    client
+      .put(
+        server
+          .url()
+          .join(&format!("/admin/hidden/{inscription}"))
+          .unwrap()
+      )
+      .bearer_auth("foo")
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::CREATED
+  );
+
+  let response = client
+    .get(
+      server
+        .url()
+        .join(&format!("/content/{inscription}"))
+        .unwrap(),
+    )
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response.text().unwrap(),
+    fs::read_to_string("templates/preview-unknown.html").unwrap()
+  );
+}
+
+#[test]
+fn content_bucket_mode_requires_content_bucket() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("server --content-bucket-mode proxy")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: `--content-bucket-mode` requires `--content-bucket`\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn electrum_subscriptions_are_notified_of_received_inscriptions() {
+  use std::{
+    io::{BufRead, BufReader},
+    net::TcpStream,
+  };
+
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let electrum_port = TcpListener::bind("127.0.0.1:0")
+    .unwrap()
+    .local_addr()
+    .unwrap()
+    .port();
+
+  let ord_server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &[],
+    &["--electrum-port", &electrum_port.to_string()],
+  );
+
+  ord_server.sync_server();
+
+  let destination = CommandBuilder::new("wallet receive")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .address
+    .assume_checked();
+
+  let mut stream = TcpStream::connect(("127.0.0.1", electrum_port)).unwrap();
+  stream
+    .set_read_timeout(Some(Duration::from_secs(10)))
+    .unwrap();
+  let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+  let mut read_message = || {
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    serde_json::from_str::<serde_json::Value>(&line).unwrap()
+  };
+
+  writeln!(
+    stream,
+    "{}",
+    serde_json::json!({
+      "jsonrpc": "2.0",
+      "id": 0,
+      "method": "inscriptions.address.subscribe",
+      "params": [destination.to_string()],
+    })
+  )
+  .unwrap();
+
+  assert_eq!(
+    read_message(),
+    serde_json::json!({"jsonrpc": "2.0", "id": 0, "result": 2}),
+  );
+
+  let inscribe = CommandBuilder::new(format!(
+    "wallet inscribe --destination {destination} --file foo.txt --fee-rate 1"
+  ))
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  assert_eq!(
+    read_message(),
+    serde_json::json!({
+      "jsonrpc": "2.0",
+      "method": "inscriptions.address.subscribe",
+      "params": [
+        destination.to_string(),
+        2,
+        [
+          {
+            "type": "inscription_received",
+            "inscription_id": inscribe.inscriptions[0].id,
+            "location": inscribe.inscriptions[0].location,
+          },
+        ],
+      ],
+    }),
+  );
+}
+
+#[test]
+fn dashboard_requires_token() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &[]);
+
+  assert_eq!(
+    ord_server.request("/dashboard").status(),
+    StatusCode::NOT_FOUND
+  );
+
+  let ord_server =
+    TestServer::spawn_with_server_args(&rpc_server, &[], &["--dashboard-token", "foo"]);
+
+  let url = ord_server.url().join("/dashboard").unwrap();
+
+  let response = reqwest::blocking::get(url.clone()).unwrap();
+
+  assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+  assert_eq!(
+    response.headers()[reqwest::header::WWW_AUTHENTICATE],
+    "Basic realm=\"ord\""
+  );
+
+  assert_eq!(
+    reqwest::blocking::Client::new()
+      .get(url)
+      .basic_auth("ord", Some("bar"))
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::UNAUTHORIZED
+  );
+}
+
+#[test]
+fn dashboard_shows_indexer_metrics() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let ord_server =
+    TestServer::spawn_with_server_args(&rpc_server, &[], &["--dashboard-token", "foo"]);
+
+  rpc_server.mine_blocks(2);
+
+  ord_server.sync_server();
+
+  let response = reqwest::blocking::Client::new()
+    .get(ord_server.url().join("/dashboard").unwrap())
+    .basic_auth("ord", Some("foo"))
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  assert_regex_match!(
+    response.text().unwrap(),
+    ".*<h1>Dashboard</h1>
+<dl>
+  <dt>blocks indexed</dt>
+  <dd>3</dd>
+  <dt>chain block count</dt>
+  <dd>3</dd>
+  <dt>indexing lag</dt>
+  <dd>0 blocks</dd>
+.*
+  <dt>last commit duration</dt>
+  <dd>[0-9]+ ms</dd>
+.*
+  <dt>last block protocol events</dt>
+  <dd>0</dd>
+.*
+  <dt>last database size</dt>
+  <dd>[0-9]+ bytes</dd>
+.*"
+  );
+}
+
+#[test]
+fn admin_api_requires_token() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &[]);
+
+  assert_eq!(
+    ord_server.request("/admin/hidden").status(),
+    StatusCode::NOT_FOUND
+  );
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--admin-token", "foo"]);
+
+  let url = ord_server.url().join("/admin/hidden").unwrap();
+
+  assert_eq!(
+    reqwest::blocking::get(url.clone()).unwrap().status(),
+    StatusCode::UNAUTHORIZED
+  );
+
+  assert_eq!(
+    reqwest::blocking::Client::new()
+      .get(url.clone())
+      .bearer_auth("bar")
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::UNAUTHORIZED
+  );
+
+  assert_eq!(
+    reqwest::blocking::Client::new()
+      .get(url)
+      .bearer_auth("foo")
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::OK
+  );
+}
+
+#[test]
+fn inscriptions_can_be_hidden_with_admin_api() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription_id, _) = inscribe(&rpc_server);
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--admin-token", "foo"]);
+
+  ord_server.sync_server();
+
+  let client = reqwest::blocking::Client::new();
+
+  let content = || {
+    ord_server
+      .request(format!("/content/{inscription_id}"))
+      .text()
+      .unwrap()
+  };
+
+  let admin = |method: reqwest::Method, path: &str| {
+    client
+      .request(method, ord_server.url().join(path).unwrap())
+      .bearer_auth("foo")
+      .send()
+      .unwrap()
+  };
+
+  let hidden_url = format!("/admin/hidden/{inscription_id}");
+
+  assert_eq!(content(), "FOO");
+
+  assert_eq!(
+    admin(reqwest::Method::PUT, &hidden_url).status(),
+    StatusCode::CREATED
+  );
+
+  assert_eq!(
+    admin(reqwest::Method::PUT, &hidden_url).status(),
+    StatusCode::OK
+  );
+
+  assert_ne!(content(), "FOO");
+
+  assert!(!ord_server
+    .request(format!("/inscription/{inscription_id}"))
+    .text()
+    .unwrap()
+    .contains(&format!("href=/content/{inscription_id}")));
+
+  assert_eq!(
+    admin(reqwest::Method::GET, "/admin/hidden")
+      .json::<serde_json::Value>()
+      .unwrap(),
+    serde_json::json!({
+      "config": [],
+      "stored": [inscription_id.to_string()],
+    }),
+  );
+
+  assert_eq!(
+    admin(reqwest::Method::DELETE, &hidden_url).status(),
+    StatusCode::NO_CONTENT
+  );
+
+  assert_eq!(
+    admin(reqwest::Method::DELETE, &hidden_url).status(),
+    StatusCode::NOT_FOUND
+  );
+
+  assert_eq!(content(), "FOO");
+}
+
+#[test]
+fn events_are_delivered_to_subscriptions() {
+  use std::io::Read;
+
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--admin-token", "foo"]);
+
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let hook = format!("http://{}/hook", listener.local_addr().unwrap());
+
+  let (sender, receiver) = std::sync::mpsc::channel();
+
+  std::thread::spawn(move || {
+    let (mut stream, _) = listener.accept().unwrap();
+
+    let mut request = Vec::new();
+    let mut buffer = [0; 4096];
+
+    let body = loop {
+      let n = stream.read(&mut buffer).unwrap();
+      request.extend_from_slice(&buffer[..n]);
+
+      let request = String::from_utf8_lossy(&request).to_string();
+
+      if let Some((headers, body)) = request.split_once("\r\n\r\n") {
+        let content_length = headers
+          .lines()
+          .find_map(|line| {
+            line
+              .to_lowercase()
+              .strip_prefix("content-length: ")
+              .map(str::to_string)
+          })
+          .unwrap()
+          .parse::<usize>()
+          .unwrap();
+
+        if body.len() == content_length {
+          break body.to_string();
+        }
+      }
+    };
+
+    stream
+      .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+      .unwrap();
+
+    sender.send(body).unwrap();
+  });
+
+  let client = reqwest::blocking::Client::new();
+
+  let admin = |method: reqwest::Method, path: &str| {
+    client
+      .request(method, ord_server.url().join(path).unwrap())
+      .bearer_auth("foo")
+  };
+
+  assert_eq!(
+    admin(reqwest::Method::POST, "/admin/subscriptions")
+      .json(&serde_json::json!({ "filter": "type ==", "url": hook }))
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::BAD_REQUEST
+  );
+
+  let response = admin(reqwest::Method::POST, "/admin/subscriptions")
+    .json(&serde_json::json!({
+      "filter": "type == \"block_indexed\" && block_height == 2",
+      "url": hook,
+    }))
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::CREATED);
+
+  let subscription = response.json::<ord::SubscriptionJson>().unwrap();
+
+  assert_eq!(
+    admin(reqwest::Method::GET, "/admin/subscriptions")
+      .send()
+      .unwrap()
+      .json::<Vec<ord::SubscriptionJson>>()
+      .unwrap(),
+    [ord::SubscriptionJson {
+      id: subscription.id,
+      subscription: ord::Subscription {
+        filter: "type == \"block_indexed\" && block_height == 2".into(),
+        url: hook.clone(),
+      },
+    }]
+  );
+
+  rpc_server.mine_blocks(2);
+
+  ord_server.sync_server();
+
+  let body = serde_json::from_str::<serde_json::Value>(
+    &receiver
+      .recv_timeout(std::time::Duration::from_secs(10))
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(body["block_height"], 2);
+  assert_eq!(body["subscription"], subscription.id);
+  assert_eq!(body["events"][0]["type"], "block_indexed");
+
+  assert_eq!(
+    admin(
+      reqwest::Method::DELETE,
+      &format!("/admin/subscriptions/{}", subscription.id)
+    )
+    .send()
+    .unwrap()
+    .status(),
+    StatusCode::NO_CONTENT
+  );
+
+  assert_eq!(
+    admin(
+      reqwest::Method::DELETE,
+      &format!("/admin/subscriptions/{}", subscription.id)
+    )
+    .send()
+    .unwrap()
+    .status(),
+    StatusCode::NOT_FOUND
+  );
+}
+
+#[test]
+fn watches_record_moves() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--admin-token", "foo"]);
+
+  let coinbase = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  ord_server.sync_server();
+
+  let client = reqwest::blocking::Client::new();
+
+  let admin = |method: reqwest::Method, path: &str| {
+    client
+      .request(method, ord_server.url().join(path).unwrap())
+      .bearer_auth("foo")
+  };
+
+  assert_eq!(
+    admin(reqwest::Method::POST, "/admin/watches")
+      .json(&serde_json::json!({ "location": "foo" }))
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::BAD_REQUEST
+  );
+
+  let location = SatPoint {
+    outpoint: OutPoint {
+      txid: coinbase,
+      vout: 0,
+    },
+    offset: 0,
+  };
+
+  let response = admin(reqwest::Method::POST, "/admin/watches")
+    .json(&serde_json::json!({ "location": location.to_string() }))
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::CREATED);
+
+  let watch = response.json::<ord::WatchJson>().unwrap();
+
+  assert_eq!(watch.moved, None);
+
+  let txid = rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    ..Default::default()
+  });
+
+  rpc_server.mine_blocks(1);
+
+  ord_server.sync_server();
+
+  assert_eq!(
+    admin(reqwest::Method::GET, "/admin/watches")
+      .send()
+      .unwrap()
+      .json::<Vec<ord::WatchJson>>()
+      .unwrap(),
+    [ord::WatchJson {
+      id: watch.id,
+      moved: Some(ord::WatchMove {
+        height: 2,
+        input: 0,
+        satpoint: Some(SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        }),
+        txid,
+      }),
+      watch: ord::Watch {
+        location: location.to_string(),
+        url: None,
+      },
+    }]
+  );
+
+  assert_eq!(
+    admin(
+      reqwest::Method::DELETE,
+      &format!("/admin/watches/{}", watch.id)
+    )
+    .send()
+    .unwrap()
+    .status(),
+    StatusCode::NO_CONTENT
+  );
+
+  assert!(admin(reqwest::Method::GET, "/admin/watches")
+    .send()
+    .unwrap()
+    .json::<Vec<ord::WatchJson>>()
+    .unwrap()
+    .is_empty());
+}
+
+#[test]
+fn export_blocks_requires_token() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &[]);
+
+  assert_eq!(
+    ord_server.request("/export/blocks").status(),
+    StatusCode::NOT_FOUND
+  );
+
+  let ord_server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--index-events"],
+    &["--export-token", "foo"],
+  );
+
+  ord_server.sync_server();
+
+  let url = ord_server.url().join("/export/blocks").unwrap();
+
+  assert_eq!(
+    reqwest::blocking::get(url.clone()).unwrap().status(),
+    StatusCode::UNAUTHORIZED
+  );
+
+  assert_eq!(
+    reqwest::blocking::Client::new()
+      .get(url.clone())
+      .bearer_auth("bar")
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::UNAUTHORIZED
+  );
+
+  assert_eq!(
+    reqwest::blocking::Client::new()
+      .get(url)
+      .bearer_auth("foo")
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::OK
+  );
+}
+
+#[test]
+fn export_blocks_streams_events_and_checksum() {
+  use bitcoin::hashes::{sha256, Hash};
+
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription_id, reveal) = inscribe(&rpc_server);
+
+  let ord_server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--index-events"],
+    &["--export-token", "foo"],
+  );
+
+  ord_server.sync_server();
+
+  let response = reqwest::blocking::Client::new()
+    .get(ord_server.url().join("/export/blocks?from=1&to=2").unwrap())
+    .bearer_auth("foo")
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response.headers()[reqwest::header::CONTENT_TYPE],
+    "application/x-ndjson"
+  );
+
+  let body = response.text().unwrap();
+
+  let lines = body.lines().collect::<Vec<&str>>();
+
+  let records = lines
+    .iter()
+    .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+    .collect::<Vec<serde_json::Value>>();
+
+  assert_eq!(records.len(), 4);
+
+  assert_eq!(records[0]["type"], "block");
+  assert_eq!(records[0]["height"], 1);
+  assert_eq!(records[1]["type"], "block");
+  assert_eq!(records[1]["height"], 2);
+
+  assert_eq!(
+    records[2],
+    serde_json::json!({
+      "type": "inscription_created",
+      "block_height": 2,
+      "inscription_id": inscription_id,
+      "inscription_number": 0,
+      "location": format!("{reveal}:0:0"),
+      "metaprotocol": null,
+      "sequence_number": 0,
+    })
+  );
+
+  let checksum = sha256::Hash::hash(
+    lines[..3]
+      .iter()
+      .map(|line| format!("{line}\n"))
+      .collect::<String>()
+      .as_bytes(),
+  );
+
+  assert_eq!(
+    records[3],
+    serde_json::json!({
+      "type": "checksum",
+      "from": 1,
+      "to": 2,
+      "records": 3,
+      "sha256": checksum.to_string(),
+    })
+  );
+
+  assert_eq!(
+    reqwest::blocking::Client::new()
+      .get(ord_server.url().join("/export/blocks?from=2&to=1").unwrap())
+      .bearer_auth("foo")
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::BAD_REQUEST
+  );
+
+  assert_eq!(
+    reqwest::blocking::Client::new()
+      .get(ord_server.url().join("/export/blocks?to=10").unwrap())
+      .bearer_auth("foo")
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::NOT_FOUND
+  );
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+const INSCRIPTION_ID: &str = "0000000000000000000000000000000000000000000000000000000000000000i0";
+
+#[test]
+fn teleburn_addresses_for_all_chains_by_default() {
+  let output = CommandBuilder::new(format!("teleburn {INSCRIPTION_ID}"))
+    .run_and_deserialize_output::<serde_json::Value>();
+
+  assert_eq!(
+    output,
+    serde_json::json!({
+      "ethereum": "0x6db65fD59fd356F6729140571B5BCd6bB3b83492",
+    }),
+  );
+}
+
+#[test]
+fn teleburn_address_for_chain() {
+  let output = CommandBuilder::new(format!("teleburn {INSCRIPTION_ID} --chain ethereum"))
+    .run_and_deserialize_output::<serde_json::Value>();
+
+  assert_eq!(
+    output,
+    serde_json::json!({
+      "ethereum": "0x6db65fD59fd356F6729140571B5BCd6bB3b83492",
+    }),
+  );
+}
+
+#[test]
+fn teleburn_addresses_are_not_supported_for_solana() {
+  CommandBuilder::new(format!("teleburn {INSCRIPTION_ID} --chain solana"))
+    .expected_stderr("error: teleburn addresses are not supported for solana\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
@@ -0,0 +1,109 @@
+use {
+  super::*,
+  ord::subcommand::{
+    test_fixture::brc20::{Output, Scenario},
+    wallet::brc20::Operation,
+  },
+};
+
+#[test]
+fn fixtures_require_regtest() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("test-fixture brc20 --scenario deploy-mint-race --output fixture")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: test fixtures can only be generated on regtest\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn deploy_mint_race() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let tempdir = TempDir::new().unwrap();
+  let fixture = tempdir.path().join("fixture");
+
+  let output = CommandBuilder::new(format!(
+    "--regtest test-fixture brc20 --scenario deploy-mint-race --output {}",
+    fixture.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.scenario, Scenario::DeployMintRace);
+  assert_eq!(output.blocks, 104);
+  assert_eq!(output.steps.len(), 5);
+
+  let mint = Operation::Mint {
+    tick: "ordi".into(),
+    amt: "1000".into(),
+  };
+
+  assert_eq!(
+    output
+      .operations
+      .iter()
+      .map(|record| (record.height, &record.operation))
+      .collect::<Vec<(u32, &Operation)>>(),
+    [
+      (102, &mint),
+      (
+        102,
+        &Operation::Deploy {
+          tick: "ordi".into(),
+          max: "21000".into(),
+          lim: Some("1000".into()),
+          dec: None,
+        }
+      ),
+      (103, &mint),
+    ],
+  );
+
+  assert_eq!(
+    serde_json::from_str::<Output>(&fs::read_to_string(fixture.join("fixture.json")).unwrap())
+      .unwrap(),
+    output
+  );
+
+  assert_eq!(
+    bitcoin::consensus::encode::deserialize::<bitcoin::Block>(
+      &fs::read(fixture.join("blocks/103.blk")).unwrap()
+    )
+    .unwrap()
+    .block_hash(),
+    rpc_server.best_block_hash(),
+  );
+}
+
+#[test]
+fn reorg_across_settle_saves_stale_blocks() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let tempdir = TempDir::new().unwrap();
+  let fixture = tempdir.path().join("fixture");
+
+  let output = CommandBuilder::new(format!(
+    "--regtest test-fixture brc20 --scenario reorg-across-settle --output {}",
+    fixture.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.blocks, 106);
+  assert_eq!(
+    output.steps.last().unwrap().description,
+    "replace last 1 blocks with 2 blocks"
+  );
+  assert!(fixture.join("stale/104.blk").exists());
+  assert!(fixture.join("blocks/105.blk").exists());
+}
@@ -35,9 +35,9 @@ impl TestServer {
       .port();
 
     let child = Command::new(executable_path("ord")).args(format!(
-      "--rpc-url {} --bitcoin-data-dir {} --data-dir {} {} server {} --http-port {port} --address 127.0.0.1",
+      "--rpc-url {} --cookie-file {} --data-dir {} {} server {} --http-port {port} --address 127.0.0.1",
       rpc_server.url(),
-      tempdir.path().display(),
+      tempdir.path().join(".cookie").display(),
       tempdir.path().display(),
       ord_args.join(" "),
       server_args.join(" "),
@@ -97,6 +97,16 @@ impl TestServer {
       .unwrap()
   }
 
+  pub(crate) fn rpc_request(&self, body: serde_json::Value) -> Response {
+    self.sync_server();
+
+    reqwest::blocking::Client::new()
+      .post(self.url().join("/rpc").unwrap())
+      .json(&body)
+      .send()
+      .unwrap()
+  }
+
   pub(crate) fn sync_server(&self) {
     let client = Client::new(&self.rpc_url, Auth::None).unwrap();
     let chain_block_count = client.get_block_count().unwrap() + 1;
@@ -106,7 +116,7 @@ impl TestServer {
       assert_eq!(response.status(), StatusCode::OK);
       if response.text().unwrap().parse::<u64>().unwrap() >= chain_block_count {
         break;
-      } else if i == 20 {
+      } else if i == 400 {
         panic!("index failed to synchronize with chain");
       }
       thread::sleep(Duration::from_millis(25));
@@ -1,13 +1,22 @@
 use super::*;
 
 mod balance;
+mod brc20;
+mod broadcast_psbt;
+mod bump_fee;
 mod cardinals;
 mod create;
+mod descriptors;
 mod inscribe;
 mod inscriptions;
+mod internal;
+mod mint_collection;
+mod offer;
 mod outputs;
 mod receive;
 mod restore;
+mod rune;
 mod sats;
 mod send;
 mod transactions;
+mod utxos;
@@ -20,8 +20,10 @@ fn wallet_balance() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 50 * COIN_VALUE,
       ordinal: 0,
+      pending: 0,
       runic: None,
       runes: None,
       total: 50 * COIN_VALUE,
@@ -40,8 +42,10 @@ fn inscribed_utxos_are_deducted_from_cardinal() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 0,
       ordinal: 0,
+      pending: 0,
       runic: None,
       runes: None,
       total: 0,
@@ -55,8 +59,10 @@ fn inscribed_utxos_are_deducted_from_cardinal() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 100 * COIN_VALUE - 10_000,
       ordinal: 10_000,
+      pending: 0,
       runic: None,
       runes: None,
       total: 100 * COIN_VALUE,
@@ -77,8 +83,10 @@ fn runic_utxos_are_deducted_from_cardinal() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 0,
       ordinal: 0,
+      pending: 0,
       runic: Some(0),
       runes: Some(BTreeMap::new()),
       total: 0,
@@ -92,11 +100,44 @@ fn runic_utxos_are_deducted_from_cardinal() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 100 * COIN_VALUE - 10_000,
       ordinal: 0,
+      pending: 0,
       runic: Some(10_000),
       runes: Some(vec![(Rune(RUNE), 1000)].into_iter().collect()),
       total: 100 * COIN_VALUE,
     }
   );
 }
+
+#[test]
+fn brc20_transfer_inscriptions_are_transferable() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  for amount in ["1.5", "2"] {
+    CommandBuilder::new("wallet inscribe --fee-rate 1 --file transfer.txt")
+      .write(
+        "transfer.txt",
+        format!(r#"{{"p":"brc-20","op":"transfer","tick":"ORDI","amt":"{amount}"}}"#),
+      )
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
+
+    rpc_server.mine_blocks(1);
+  }
+
+  assert_eq!(
+    CommandBuilder::new("wallet balance")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Output>()
+      .brc20,
+    Some(
+      [("ordi".to_string(), "3.5".to_string())]
+        .into_iter()
+        .collect()
+    ),
+  );
+}
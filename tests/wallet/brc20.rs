@@ -0,0 +1,112 @@
+use {super::*, ord::subcommand::wallet::brc20::send::Output};
+
+#[test]
+fn send_inscribes_transfer_and_sends_it() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(
+    "wallet brc20 send --fee-rate 1 --tick ordi --amount 100 --to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  let mempool = rpc_server.mempool();
+  assert_eq!(mempool.len(), 3);
+  assert_eq!(mempool[2].txid(), output.send);
+  assert_eq!(
+    mempool[2].input[0].previous_output,
+    OutPoint {
+      txid: output.reveal,
+      vout: 0
+    }
+  );
+
+  rpc_server.mine_blocks(1);
+
+  let ord_server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  ord_server.assert_response_regex(
+    format!("/inscription/{}", output.inscription),
+    format!(
+      ".*<dt>location</dt>\\s*<dd class=monospace>{}:0:0</dd>.*",
+      output.send
+    ),
+  );
+
+  let response = ord_server.request(format!("/content/{}", output.inscription));
+  assert_eq!(
+    response.text().unwrap(),
+    r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"100"}"#
+  );
+}
+
+#[test]
+fn send_rejects_invalid_tick() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  CommandBuilder::new(
+    "wallet brc20 send --fee-rate 1 --tick ord --amount 100 --to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server)
+  .stderr_regex(".*BRC-20 tick `ord` must be 4 or 5 bytes long.*")
+  .expected_exit_code(2)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn mint_batches_repeated_mints() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output =
+    CommandBuilder::new("wallet brc20 mint --fee-rate 1 --tick ordi --amount 1000 --repeat 101")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<ord::subcommand::wallet::brc20::mint::Output>();
+
+  assert_eq!(output.batches.len(), 2);
+  assert_eq!(output.batches[0].inscriptions.len(), 100);
+  assert_eq!(output.batches[1].inscriptions.len(), 1);
+
+  let mempool = rpc_server.mempool();
+  assert_eq!(mempool.len(), 4);
+  assert!(mempool[2]
+    .input
+    .iter()
+    .any(|input| input.previous_output.txid == output.batches[0].commit));
+
+  rpc_server.mine_blocks(1);
+
+  let ord_server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let response = ord_server.request(format!("/content/{}", output.batches[1].inscriptions[0].id));
+  assert_eq!(
+    response.text().unwrap(),
+    r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"1000"}"#
+  );
+}
+
+#[test]
+fn deploy_inscribes_deployment() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(
+    "wallet brc20 deploy --fee-rate 1 --tick ordi --max 21000000 --limit 1000 --decimals 8",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let response = TestServer::spawn_with_args(&rpc_server, &[])
+    .request(format!("/content/{}", output.inscriptions[0].id));
+  assert_eq!(
+    response.text().unwrap(),
+    r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"21000000","lim":"1000","dec":"8"}"#
+  );
+}
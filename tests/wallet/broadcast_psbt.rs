@@ -0,0 +1,82 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{psbt::Psbt, secp256k1::schnorr, sighash::TapSighashType, taproot::Signature},
+  ord::subcommand::wallet::broadcast_psbt::Output,
+};
+
+fn sign(psbt: &str) -> String {
+  let mut psbt = Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(psbt)
+      .unwrap(),
+  )
+  .unwrap();
+
+  for input in &mut psbt.inputs {
+    assert!(input.witness_utxo.is_some());
+
+    if input.final_script_witness.is_none() {
+      input.tap_key_sig = Some(Signature {
+        sig: schnorr::Signature::from_slice(&[1; 64]).unwrap(),
+        hash_ty: TapSighashType::Default,
+      });
+    }
+  }
+
+  base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+}
+
+#[test]
+fn unsigned_inscription_can_be_signed_offline_and_broadcast() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let inscribe = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt --unsigned")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  assert!(rpc_server.mempool().is_empty());
+
+  let output = CommandBuilder::new(format!(
+    "wallet broadcast-psbt {} {}",
+    sign(&inscribe.commit_psbt.unwrap()),
+    sign(&inscribe.reveal_psbt.unwrap()),
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.transactions, [inscribe.commit, inscribe.reveal]);
+
+  rpc_server.mine_blocks(1);
+
+  let response = TestServer::spawn_with_args(&rpc_server, &[])
+    .request(format!("/content/{}", inscribe.inscriptions[0].id));
+
+  assert_eq!(response.text().unwrap(), "FOO");
+}
+
+#[test]
+fn unsigned_psbts_cannot_be_broadcast() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let inscribe = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt --unsigned")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  CommandBuilder::new(format!(
+    "wallet broadcast-psbt {}",
+    inscribe.commit_psbt.unwrap()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: PSBT input 0 is not signed\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(rpc_server.mempool().is_empty());
+}
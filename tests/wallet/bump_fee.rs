@@ -0,0 +1,82 @@
+use {
+  super::*,
+  ord::subcommand::wallet::bump_fee::{Method, Output},
+};
+
+#[test]
+fn send_is_replaced() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::wallet::send::Output>();
+
+  let output = CommandBuilder::new(format!("wallet bump-fee --fee-rate 5 {}", send.transaction))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.method, Method::Rbf);
+  assert_eq!(output.original, send.transaction);
+
+  let mempool = rpc_server.mempool();
+  assert_eq!(mempool.len(), 1);
+  assert_eq!(mempool[0].txid(), output.transaction);
+}
+
+#[test]
+fn reveal_is_bumped_with_child() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(2);
+
+  let inscribe = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  let output = CommandBuilder::new(format!("wallet bump-fee --fee-rate 10 {}", inscribe.reveal))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.method, Method::Cpfp);
+
+  let mempool = rpc_server.mempool();
+  assert_eq!(mempool.len(), 3);
+  assert_eq!(mempool[2].txid(), output.transaction);
+  assert_eq!(
+    mempool[2].input[0].previous_output,
+    OutPoint {
+      txid: inscribe.reveal,
+      vout: 0
+    }
+  );
+
+  rpc_server.mine_blocks(1);
+
+  TestServer::spawn_with_args(&rpc_server, &[]).assert_response_regex(
+    format!("/inscription/{}", inscribe.inscriptions[0].id),
+    format!(
+      ".*<dt>location</dt>\\s*<dd class=monospace>{}:0:0</dd>.*",
+      output.transaction
+    ),
+  );
+}
+
+#[test]
+fn confirmed_transactions_cannot_be_bumped() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  CommandBuilder::new(format!("wallet bump-fee --fee-rate 10 {txid}"))
+    .rpc_server(&rpc_server)
+    .expected_stderr(format!("error: transaction {txid} is already confirmed\n"))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
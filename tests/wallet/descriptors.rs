@@ -0,0 +1,60 @@
+use {
+  super::*,
+  ord::subcommand::{
+    wallet::{balance, export_descriptors::Output},
+    Empty,
+  },
+};
+
+#[test]
+fn export_descriptors() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let output = CommandBuilder::new("wallet export-descriptors")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.descriptors.len(), 2);
+  assert!(output
+    .descriptors
+    .iter()
+    .all(|descriptor| descriptor.desc.starts_with("tr(")));
+}
+
+#[test]
+fn import_descriptors_creates_watch_only_wallet() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("wallet --name watch import-descriptors descriptors.json")
+    .write(
+      "descriptors.json",
+      r#"{"descriptors":[{"active":true,"desc":"tr([5a8c8b1b/86'/0'/0']xpub/0/*)","internal":false},{"active":true,"desc":"tr([5a8c8b1b/86'/0'/0']xpub/1/*)","internal":true}]}"#,
+    )
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  assert!(rpc_server.wallets().contains("watch"));
+  assert_eq!(rpc_server.descriptors().len(), 2);
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("wallet --name watch balance")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<balance::Output>();
+}
+
+#[test]
+fn private_descriptors_cannot_be_imported() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("wallet --name watch import-descriptors descriptors.json")
+    .write(
+      "descriptors.json",
+      r#"{"descriptors":[{"active":true,"desc":"tr(tprv/0/*)","internal":false},{"active":true,"desc":"tr(tprv/1/*)","internal":true}]}"#,
+    )
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: descriptors must not contain private keys\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
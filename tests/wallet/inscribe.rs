@@ -287,6 +287,38 @@ fn inscribe_with_fee_rate() {
   );
 }
 
+#[test]
+fn inscribe_with_auto_fee_rate() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet inscribe --file foo.txt --fee-rate auto --dry-run")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  assert_eq!(output.fee_rate, 1.0);
+
+  rpc_server.set_fee_estimate(4.5);
+
+  let output = CommandBuilder::new("wallet inscribe --file foo.txt --fee-rate auto --dry-run")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  assert_eq!(output.fee_rate, 4.5);
+
+  CommandBuilder::new(
+    "wallet inscribe --file foo.txt --fee-rate auto --confirmation-target 0 --dry-run",
+  )
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: confirmation target must be greater than zero\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
 #[test]
 fn inscribe_with_commit_fee_rate() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -467,6 +499,58 @@ fn inscribe_works_with_postage() {
   pretty_assert_eq!(inscriptions[0].postage, 5 * COIN_VALUE);
 }
 
+#[test]
+fn inscribe_uses_target_postage() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output =
+    CommandBuilder::new("wallet --target-postage 5000sat inscribe --file foo.txt --fee-rate 1")
+      .write("foo.txt", "FOO")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
+
+  assert_eq!(output.postage, 5000);
+
+  rpc_server.mine_blocks(1);
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Vec<ord::subcommand::wallet::inscriptions::Output>>();
+
+  pretty_assert_eq!(inscriptions[0].postage, 5000);
+}
+
+#[test]
+fn inscribe_refuses_postage_below_minimum() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "wallet --min-postage 2000sat inscribe --file foo.txt --postage 1000sat --fee-rate 1",
+  )
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: postage 1000 sat is below minimum postage 2000 sat\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn postage_policy_must_be_ordered() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  CommandBuilder::new("wallet --target-postage 30000sat inscribe --file foo.txt --fee-rate 1")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: target postage 30000 sat is greater than maximum postage 20000 sat\n")
+    .run_and_extract_stdout();
+}
+
 #[test]
 fn inscribe_with_non_existent_parent_inscription() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -621,7 +705,7 @@ fn with_reinscribe_flag_but_not_actually_a_reinscription() {
   .write("orchid.png", [1; 520])
   .rpc_server(&rpc_server)
   .expected_exit_code(1)
-  .stderr_regex("error: reinscribe flag set but this would not be a reinscription.*")
+  .stderr_regex("error: `--allow-reinscription` set but this would not be a reinscription.*")
   .run_and_extract_stdout();
 }
 
@@ -1800,3 +1884,154 @@ fn server_can_decompress_brotli() {
   assert_eq!(response.status(), StatusCode::OK);
   assert_eq!(response.bytes().unwrap().deref(), [0; 350_000]);
 }
+
+#[test]
+fn commit_and_reveal_are_submitted_as_package_if_supported() {
+  let rpc_server = test_bitcoincore_rpc::builder().version(280000).build();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  assert_eq!(
+    rpc_server.packages(),
+    vec![vec![output.commit, output.reveal]]
+  );
+
+  assert_eq!(rpc_server.mempool().len(), 2);
+}
+
+#[test]
+fn commit_and_reveal_are_broadcast_individually_if_packages_are_not_supported() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  assert!(rpc_server.packages().is_empty());
+
+  assert_eq!(rpc_server.mempool().len(), 2);
+}
+
+#[test]
+fn inscribe_from_stdin() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --stdin --content-type image/png")
+    .stdin("PNG".into())
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let request = TestServer::spawn_with_args(&rpc_server, &[])
+    .request(format!("/content/{}", output.inscriptions[0].id));
+
+  assert_eq!(request.status(), 200);
+  assert_eq!(request.headers().get("content-type").unwrap(), "image/png");
+  assert_eq!(request.text().unwrap(), "PNG");
+}
+
+#[test]
+fn inscribe_from_stdin_requires_content_type() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("wallet inscribe --fee-rate 1 --stdin")
+    .stdin("PNG".into())
+    .rpc_server(&rpc_server)
+    .expected_exit_code(2)
+    .stderr_regex("error: the following required arguments were not provided:.*--content-type.*")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn content_type_overrides_file_extension() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(
+    "wallet inscribe --fee-rate 1 --file pepe.xyz --content-type application/x-pepe",
+  )
+  .write("pepe.xyz", "PEPE")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let request = TestServer::spawn_with_args(&rpc_server, &[])
+    .request(format!("/content/{}", output.inscriptions[0].id));
+
+  assert_eq!(
+    request.headers().get("content-type").unwrap(),
+    "application/x-pepe"
+  );
+}
+
+#[test]
+fn inscribe_from_url() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let ord_server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let output = CommandBuilder::new(format!(
+    "wallet inscribe --fee-rate 1 --url {}",
+    ord_server
+      .url()
+      .join(&format!("/content/{inscription}"))
+      .unwrap(),
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let request = TestServer::spawn_with_args(&rpc_server, &[])
+    .request(format!("/content/{}", output.inscriptions[0].id));
+
+  assert_eq!(
+    request.headers().get("content-type").unwrap(),
+    "text/plain;charset=utf-8"
+  );
+  assert_eq!(request.text().unwrap(), "FOO");
+}
+
+#[test]
+fn reinscribe_with_allow_reinscription_flag() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, reveal) = inscribe(&rpc_server);
+
+  let reinscribe = CommandBuilder::new(format!(
+    "wallet inscribe --file orchid.png --fee-rate 1 --allow-reinscription --satpoint {reveal}:0:0"
+  ))
+  .write("orchid.png", [1; 520])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let request = TestServer::spawn_with_args(&rpc_server, &[])
+    .request(format!("/content/{}", reinscribe.inscriptions[0].id));
+
+  assert_eq!(request.status(), 200);
+
+  assert_ne!(inscription, reinscribe.inscriptions[0].id);
+}
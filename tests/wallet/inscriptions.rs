@@ -49,6 +49,44 @@ fn inscriptions() {
   assert_eq!(output[0].location, format!("{txid}:0:0").parse().unwrap());
 }
 
+#[test]
+fn inscriptions_can_be_queried_from_remote_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, reveal) = inscribe(&rpc_server);
+
+  let ord_server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--enable-json-api"]);
+
+  ord_server.sync_server();
+
+  let output = CommandBuilder::new(format!(
+    "wallet --index-url {} inscriptions",
+    ord_server.url()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Vec<inscriptions::Output>>();
+
+  assert_eq!(output.len(), 1);
+  assert_eq!(output[0].inscription, inscription);
+  assert_eq!(output[0].location, format!("{reveal}:0:0").parse().unwrap());
+}
+
+#[test]
+fn index_url_is_rejected_by_unsupported_commands() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  CommandBuilder::new("wallet --index-url http://127.0.0.1:1 balance")
+    .rpc_server(&rpc_server)
+    .expected_stderr(
+      "error: `--index-url` is only supported by `ord wallet cardinals`, `ord wallet inscriptions`, and `ord wallet outputs`\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
 #[test]
 fn inscriptions_includes_locked_utxos() {
   let rpc_server = test_bitcoincore_rpc::spawn();
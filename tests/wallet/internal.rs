@@ -0,0 +1,83 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{balance, create, outputs, receive, send},
+};
+
+#[test]
+fn internal_wallet_finds_received_outputs() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (_, tempdir) =
+    CommandBuilder::new("--index-addresses wallet --backend internal --name foo create")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output_with_tempdir::<create::Output>();
+
+  let (receive, tempdir) =
+    CommandBuilder::new("--index-addresses wallet --backend internal --name foo receive")
+      .rpc_server(&rpc_server)
+      .temp_dir(tempdir)
+      .run_and_deserialize_output_with_tempdir::<receive::Output>();
+
+  let address = receive.address.require_network(Network::Bitcoin).unwrap();
+
+  let send = CommandBuilder::new(format!(
+    "--index-addresses wallet send --fee-rate 1 {address} 1btc"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<send::Output>();
+
+  rpc_server.mine_blocks(1);
+
+  let (outputs, tempdir) =
+    CommandBuilder::new("--index-addresses wallet --backend internal --name foo outputs")
+      .rpc_server(&rpc_server)
+      .temp_dir(tempdir)
+      .run_and_deserialize_output_with_tempdir::<Vec<outputs::Output>>();
+
+  assert!(outputs
+    .iter()
+    .all(|output| output.output.txid == send.transaction));
+  assert!(outputs.iter().any(|output| output.amount == COIN_VALUE));
+
+  let balance =
+    CommandBuilder::new("--index-addresses wallet --backend internal --name foo balance")
+      .rpc_server(&rpc_server)
+      .temp_dir(tempdir)
+      .run_and_deserialize_output::<balance::Output>();
+
+  assert_eq!(
+    balance.cardinal,
+    outputs.iter().map(|output| output.amount).sum::<u64>()
+  );
+}
+
+#[test]
+fn internal_wallet_requires_address_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let (_, tempdir) = CommandBuilder::new("wallet --backend internal create")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output_with_tempdir::<create::Output>();
+
+  CommandBuilder::new("wallet --backend internal balance")
+    .rpc_server(&rpc_server)
+    .temp_dir(tempdir)
+    .expected_stderr(
+      "error: the internal wallet backend requires an index built with `--index-addresses`\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn internal_wallet_rejects_unsupported_commands() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("wallet --backend internal sats")
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: the internal wallet backend only supports `balance`, `create`, `inscriptions`, `outputs`, `receive`, and `restore`\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
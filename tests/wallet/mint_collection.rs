@@ -0,0 +1,53 @@
+use {super::*, ord::subcommand::wallet::mint_collection::Output};
+
+#[test]
+fn children_are_inscribed_in_batches_with_parent() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (parent, _) = inscribe(&rpc_server);
+
+  let output = CommandBuilder::new(format!(
+    "wallet mint-collection --fee-rate 1 --parent {parent} --batch-size 2 collection"
+  ))
+  .write("collection/0.txt", "zero")
+  .write("collection/1.txt", "one")
+  .write("collection/2.txt", "two")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.parent, parent);
+  assert_eq!(output.batches.len(), 2);
+  assert_eq!(output.children.len(), 3);
+  assert_eq!(
+    output
+      .children
+      .iter()
+      .map(|child| child.number)
+      .collect::<Vec<u64>>(),
+    [0, 1, 2]
+  );
+  assert!(output.children[2].file.ends_with("2.txt"));
+
+  let mempool = rpc_server.mempool();
+  assert_eq!(mempool.len(), 4);
+  assert_eq!(
+    mempool[3].input[0].previous_output,
+    OutPoint {
+      txid: output.batches[0].reveal,
+      vout: 0
+    }
+  );
+
+  rpc_server.mine_blocks(1);
+
+  let ord_server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  for child in &output.children {
+    ord_server.assert_response_regex(
+      format!("/inscription/{}", child.id),
+      format!(".*<dt>parent</dt>\\s*<dd><a class=monospace href=/inscription/{parent}>.*"),
+    );
+  }
+}
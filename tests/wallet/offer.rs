@@ -0,0 +1,242 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{psbt::Psbt, ScriptBuf, Witness},
+  ord::subcommand::wallet::{
+    offer::{accept, create},
+    send,
+  },
+};
+
+#[test]
+fn inscriptions_can_be_offered_and_bought() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, reveal) = inscribe(&rpc_server);
+
+  let offer = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 100000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<create::Output>();
+
+  assert_eq!(offer.inscription, inscription);
+  assert_eq!(offer.price, 100_000);
+
+  let psbt = Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(&offer.psbt)
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(
+    psbt.unsigned_tx.input[0].previous_output,
+    OutPoint {
+      txid: reveal,
+      vout: 0
+    }
+  );
+  assert_eq!(psbt.unsigned_tx.output[0].value, 100_000);
+  assert_eq!(
+    psbt.inputs[0]
+      .final_script_witness
+      .as_ref()
+      .unwrap()
+      .to_vec()[0][64],
+    0x83
+  );
+
+  let output = CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --price 100000sat {}",
+    offer.psbt
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<accept::Output>();
+
+  assert_eq!(output.inscriptions, [inscription]);
+  assert_eq!(output.price, 100_000);
+
+  let transaction = &rpc_server.mempool()[0];
+
+  assert_eq!(transaction.txid(), output.transaction);
+  assert_eq!(transaction.input[1], {
+    let mut input = psbt.unsigned_tx.input[0].clone();
+    input.witness = psbt.inputs[0].final_script_witness.clone().unwrap();
+    input
+  });
+  assert_eq!(transaction.output[1], psbt.unsigned_tx.output[0]);
+
+  rpc_server.mine_blocks(1);
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Vec<ord::subcommand::wallet::inscriptions::Output>>();
+
+  assert_eq!(inscriptions.len(), 1);
+  assert_eq!(inscriptions[0].inscription, inscription);
+  assert_eq!(
+    inscriptions[0].location.outpoint,
+    OutPoint {
+      txid: output.transaction,
+      vout: 0
+    }
+  );
+}
+
+#[test]
+fn offers_must_be_signed_with_sighash_single_anyonecanpay() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let offer = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 100000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<create::Output>();
+
+  let mut psbt = Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(&offer.psbt)
+      .unwrap(),
+  )
+  .unwrap();
+
+  psbt.inputs[0].final_script_witness = Some(Witness::from_slice(&[&[0; 64]]));
+
+  CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --price 100000sat {}",
+    base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: offer PSBT input must be signed with SIGHASH_SINGLE|ANYONECANPAY\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn offer_price_must_be_confirmed() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let offer = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 100000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<create::Output>();
+
+  CommandBuilder::new(format!("wallet offer accept --fee-rate 1 {}", offer.psbt))
+    .rpc_server(&rpc_server)
+    .stderr_regex("error: the following required arguments were not provided:.*")
+    .expected_exit_code(2)
+    .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --price 90000sat {}",
+    offer.psbt
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: offer price of 100000 sat does not match --price of 90000 sat\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --max-price 99999sat {}",
+    offer.psbt
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: offer price of 100000 sat exceeds --max-price of 99999 sat\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(rpc_server.mempool().is_empty());
+
+  let output = CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --max-price 100000sat {}",
+    offer.psbt
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<accept::Output>();
+
+  assert_eq!(output.price, 100_000);
+  assert_eq!(rpc_server.mempool()[0].txid(), output.transaction);
+}
+
+#[test]
+fn offer_inputs_must_be_taproot_key_path_spends() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let offer = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 100000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<create::Output>();
+
+  let psbt = Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(&offer.psbt)
+      .unwrap(),
+  )
+  .unwrap();
+
+  let mut with_script_sig = psbt.clone();
+
+  with_script_sig.inputs[0].final_script_sig = Some(ScriptBuf::from_bytes(vec![0]));
+
+  CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --price 100000sat {}",
+    base64::engine::general_purpose::STANDARD.encode(with_script_sig.serialize())
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: offer PSBT input must be a taproot key path spend\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<send::Output>();
+
+  rpc_server.mine_blocks(1);
+
+  let mut segwit_v0 = psbt;
+
+  segwit_v0.unsigned_tx.input[0].previous_output = OutPoint {
+    txid: send.transaction,
+    vout: 0,
+  };
+
+  CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --price 100000sat {}",
+    base64::engine::general_purpose::STANDARD.encode(segwit_v0.serialize())
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: offer PSBT input must spend a taproot output\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn offered_inscription_must_be_in_wallet() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  CommandBuilder::new(format!(
+    "wallet offer create --inscription {txid}i0 --price 100000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!("error: inscription {txid}i0 not found\n"))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -0,0 +1,220 @@
+use {
+  super::*,
+  ord::subcommand::wallet::rune::{mint, send},
+};
+
+fn etch_open(rpc_server: &test_bitcoincore_rpc::Handle, rune: Rune) -> Etch {
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --regtest wallet rune etch --rune {rune} --divisibility 0 --fee-rate 0 --supply 0 --limit 100 --term 3 --symbol ¢",
+  ))
+  .rpc_server(rpc_server)
+  .run_and_deserialize_output();
+
+  rpc_server.mine_blocks(1);
+
+  output
+}
+
+#[test]
+fn open_rune_can_be_minted() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let rune = Rune(RUNE);
+
+  etch_open(&rpc_server, rune);
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --regtest wallet rune mint --fee-rate 1 --rune {rune} --amount 60"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<mint::Output>();
+
+  assert_eq!(output.amount, 60);
+
+  rpc_server.mine_blocks(1);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::balances::Output>();
+
+  assert_eq!(
+    balances.runes[&rune],
+    [(
+      OutPoint {
+        txid: output.transaction,
+        vout: 1,
+      },
+      60
+    )]
+    .into_iter()
+    .collect()
+  );
+
+  pretty_assert_eq!(runes(&rpc_server)[&rune].supply, 60);
+}
+
+#[test]
+fn mint_defaults_to_limit() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let rune = Rune(RUNE);
+
+  etch_open(&rpc_server, rune);
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --regtest wallet rune mint --fee-rate 1 --rune {rune}"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<mint::Output>();
+
+  assert_eq!(output.amount, 100);
+}
+
+#[test]
+fn mint_over_limit_is_an_error() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let rune = Rune(RUNE);
+
+  etch_open(&rpc_server, rune);
+
+  CommandBuilder::new(format!(
+    "--index-runes --regtest wallet rune mint --fee-rate 1 --rune {rune} --amount 101"
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!(
+    "error: mint amount 101\u{a0}¢ exceeds `{rune}` mint limit of 100\u{a0}¢\n"
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn rune_without_open_mints_cannot_be_minted() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let rune = Rune(RUNE);
+
+  etch(&rpc_server, rune);
+
+  CommandBuilder::new(format!(
+    "--index-runes --regtest wallet rune mint --fee-rate 1 --rune {rune}"
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!(
+    "error: rune `{rune}` does not have open mint terms\n"
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn rune_cannot_be_minted_after_term_ends() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let rune = Rune(RUNE);
+
+  etch_open(&rpc_server, rune);
+
+  rpc_server.mine_blocks(2);
+
+  CommandBuilder::new(format!(
+    "--index-runes --regtest wallet rune mint --fee-rate 1 --rune {rune}"
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!("error: rune `{rune}` mint term ended at block 5\n"))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn unknown_rune_cannot_be_minted() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "--index-runes --regtest wallet rune mint --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!(
+    "error: rune `{}` has not been etched\n",
+    Rune(RUNE)
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn runes_can_be_sent() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  let rune = Rune(RUNE);
+
+  etch(&rpc_server, rune);
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --regtest wallet rune send --fee-rate 1 --rune {rune} bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 750"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<send::Output>();
+
+  rpc_server.mine_blocks(1);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::balances::Output>();
+
+  assert_eq!(
+    balances.runes[&rune],
+    [
+      (
+        OutPoint {
+          txid: output.transaction,
+          vout: 1,
+        },
+        250
+      ),
+      (
+        OutPoint {
+          txid: output.transaction,
+          vout: 2,
+        },
+        750
+      ),
+    ]
+    .into_iter()
+    .collect()
+  );
+}
@@ -69,3 +69,15 @@ fn sats_from_tsv_file_not_found() {
     .stderr_regex("error: I/O error reading `.*`\nbecause: .*\n")
     .run_and_extract_stdout();
 }
+
+#[test]
+fn webhook_requires_watch() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  CommandBuilder::new("--index-sats wallet sats --webhook http://localhost:1")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(2)
+    .stderr_regex("error: the following required arguments were not provided:.*--watch.*")
+    .run_and_extract_stdout();
+}
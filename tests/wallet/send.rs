@@ -469,12 +469,14 @@ fn wallet_send_with_fee_rate_and_target_postage() {
 
   let (inscription, _) = inscribe(&rpc_server);
 
-  CommandBuilder::new(format!(
+  let output = CommandBuilder::new(format!(
     "wallet send bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription} --fee-rate 2.0 --postage 77000sat"
   ))
   .rpc_server(&rpc_server)
   .run_and_deserialize_output::<Output>();
 
+  pretty_assert_eq!(output.postage, Some(77_000));
+
   let tx = &rpc_server.mempool()[0];
   let mut fee = 0;
   for input in &tx.input {
@@ -493,6 +495,24 @@ fn wallet_send_with_fee_rate_and_target_postage() {
   pretty_assert_eq!(tx.output[0].value, 77_000);
 }
 
+#[test]
+fn wallet_send_strips_postage_above_max_postage() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let output = CommandBuilder::new(format!(
+    "wallet --target-postage 2000sat --max-postage 5000sat send bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription} --fee-rate 1"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  pretty_assert_eq!(output.postage, Some(2000));
+  pretty_assert_eq!(rpc_server.mempool()[0].output[0].value, 2000);
+}
+
 #[test]
 fn send_btc_does_not_send_locked_utxos() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -913,8 +933,10 @@ fn sending_rune_does_not_send_inscription() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<ord::subcommand::wallet::balance::Output>(),
     ord::subcommand::wallet::balance::Output {
+      brc20: None,
       cardinal: 10000,
       ordinal: 10000,
+      pending: 0,
       runic: Some(0),
       runes: Some(BTreeMap::new()),
       total: 20000,
@@ -937,8 +959,10 @@ fn sending_rune_does_not_send_inscription() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<ord::subcommand::wallet::balance::Output>(),
     ord::subcommand::wallet::balance::Output {
+      brc20: None,
       cardinal: 0,
       ordinal: 10000,
+      pending: 0,
       runic: Some(10000),
       runes: Some(vec![(rune, 1000)].into_iter().collect()),
       total: 20000,
@@ -954,3 +978,114 @@ fn sending_rune_does_not_send_inscription() {
   .stderr_regex("error:.*")
   .run_and_extract_stdout();
 }
+
+#[test]
+fn send_unsigned_outputs_psbt() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let output = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 --unsigned bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert!(output.psbt.is_some());
+  assert!(rpc_server.mempool().is_empty());
+}
+
+#[test]
+fn send_sat_reports_its_new_location() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(
+    "--index-sats wallet send --fee-rate 1 --sat 5000000000 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    output.satpoint,
+    Some(SatPoint {
+      outpoint: OutPoint {
+        txid: output.transaction,
+        vout: 0
+      },
+      offset: 0
+    })
+  );
+}
+
+#[test]
+fn send_sat_requires_sat_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "wallet send --fee-rate 1 --sat 5000000000 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: index must be built with `--index-sats` to use `--sat`\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn inscriptions_can_be_sent_to_descriptor() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 wsh(and_v(v:pk(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5),older(144))) {inscription}",
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  let tx = &rpc_server.mempool()[0];
+
+  assert_eq!(tx.txid(), output.transaction);
+  assert!(tx.output[0].script_pubkey.is_v0_p2wsh());
+}
+
+#[test]
+fn send_to_descriptor_with_wildcard_is_an_error() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 tr(xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/0/*) {inscription}",
+  ))
+  .rpc_server(&rpc_server)
+  .stderr_regex("error: descriptor `tr\\(.*\\)#[a-z0-9]{8}` may not contain wildcards\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn send_amount_with_hwi_signer_is_an_error() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "wallet send --fee-rate 1 --signer hwi bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+  )
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: `--signer` is only supported when sending inscriptions or satpoints\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
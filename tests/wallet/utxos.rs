@@ -0,0 +1,89 @@
+use {
+  super::*,
+  ord::subcommand::wallet::utxos::{freeze, unfreeze, Output},
+};
+
+#[test]
+fn frozen_outputs_are_not_split_or_consolidated() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  let output = CommandBuilder::new(format!("wallet utxos freeze {txid}:0"))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<freeze::Output>();
+
+  assert_eq!(output.frozen, [OutPoint { txid, vout: 0 }]);
+
+  CommandBuilder::new(format!("wallet utxos split --fee-rate 1 {txid}:0"))
+    .rpc_server(&rpc_server)
+    .expected_stderr(format!("error: output {txid}:0 is not a cardinal wallet output, and may contain inscriptions or runes, or be frozen\n"))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!("wallet utxos unfreeze {txid}:0"))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<unfreeze::Output>();
+
+  assert_eq!(output.unfrozen, [OutPoint { txid, vout: 0 }]);
+}
+
+#[test]
+fn split_creates_padding_outputs() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  let output = CommandBuilder::new(format!(
+    "wallet utxos split --fee-rate 1 --count 3 --amount 600sat {txid}:0"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  let transaction = &rpc_server.mempool()[0];
+  assert_eq!(transaction.txid(), output.transaction);
+  assert_eq!(transaction.output.len(), 4);
+  assert!(transaction.output[..3]
+    .iter()
+    .all(|output| output.value == 600));
+}
+
+#[test]
+fn inscribed_outputs_are_not_split() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  CommandBuilder::new(format!(
+    "wallet utxos split --fee-rate 1 {}:0",
+    inscription.txid
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!("error: output {}:0 is not a cardinal wallet output, and may contain inscriptions or runes, or be frozen\n", inscription.txid))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn consolidate_spends_small_cardinal_outputs() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  rpc_server.mine_blocks_with_subsidy(2, 5_000);
+
+  let output = CommandBuilder::new("wallet utxos consolidate --fee-rate 1")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Output>();
+
+  let transaction = &rpc_server.mempool()[0];
+  assert_eq!(transaction.txid(), output.transaction);
+  assert_eq!(transaction.input.len(), 2);
+  assert!(transaction
+    .input
+    .iter()
+    .all(|input| input.previous_output.txid != inscription.txid));
+}